@@ -28,9 +28,24 @@ pub mod transferd {
 pub mod chatd {
     include!("src/chatd/opts.rs");
 }
+pub mod maild {
+    include!("src/maild/opts.rs");
+}
+pub mod boardd {
+    include!("src/boardd/opts.rs");
+}
+pub mod syncd {
+    include!("src/syncd/opts.rs");
+}
 pub mod downpourd {
     include!("src/downpourd/opts.rs");
 }
+pub mod mqttd {
+    include!("src/mqttd/opts.rs");
+}
+pub mod nostrd {
+    include!("src/nostrd/opts.rs");
+}
 
 fn main() -> Result<(), configure_me_codegen::Error> {
     let outdir = "./shell";
@@ -40,14 +55,19 @@ fn main() -> Result<(), configure_me_codegen::Error> {
         stormd::Opts::command(),
         transferd::Opts::command(),
         chatd::Opts::command(),
+        maild::Opts::command(),
+        boardd::Opts::command(),
+        syncd::Opts::command(),
         downpourd::Opts::command(),
+        mqttd::Opts::command(),
+        nostrd::Opts::command(),
     ]
     .iter_mut()
     {
         let name = app.get_name().to_string();
-        generate_to(Bash, app, &name, &outdir)?;
-        generate_to(PowerShell, app, &name, &outdir)?;
-        generate_to(Zsh, app, &name, &outdir)?;
+        generate_to(Bash, app, &name, outdir)?;
+        generate_to(PowerShell, app, &name, outdir)?;
+        generate_to(Zsh, app, &name, outdir)?;
     }
 
     // configure_me_codegen::build_script_auto()
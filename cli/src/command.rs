@@ -8,17 +8,29 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::io::{BufRead, Write};
+use std::collections::BTreeSet;
+use std::io::{BufRead, Read, Write};
 use std::{fs, io};
 
 use amplify::num::u24;
 use amplify::IoError;
-use internet2::addr::PartialNodeAddr;
+use internet2::addr::{NodeId, PartialNodeAddr};
 use lnp::addr::LnpAddr;
 use microservices::rpc::ServerError;
-use storm::{Chunk, Container, ContainerHeader};
+use secp256k1::SecretKey;
+use storm::{
+    Chunk, ChunkId, Container, ContainerFullId, ContainerHeader, ContainerId, MesgId, StormApp,
+};
+use storm_rpc::{
+    AdminCommand, AdminOutcome, ApprovalOutcome, AttestationError, BatchResult, BoardInfo,
+    BoardModeration, BoardPostRequest, ChatHistoryReq, ContainerKey, EventFilter, Filter,
+    InlineChunks, Interest, JobKind, LimitScope, ListQuery, Mail, MailQuery, MailRequest,
+    ManifestError, NodeInfo, NotifyPref, PageReq, Paged, Patch, RateLimit, Replication, ServiceId,
+    SignedManifest, TopicId, UnsealError, WaitEvent, PATCH_MIME, SEALED_CHUNK_OVERHEAD,
+};
 use strict_encoding::{MediumVec, StrictDecode, StrictEncode};
 
+use crate::opts::{AppCommand, ContainerCommand, ListArgs, RemoteCommand, TopicCommand};
 use crate::{Command, Opts};
 
 #[derive(Debug, Display, Error, From)]
@@ -39,6 +51,39 @@ pub enum Error {
 
     #[from]
     StrictEncoding(strict_encoding::Error),
+
+    #[from]
+    Unseal(UnsealError),
+
+    #[display("node is not ready")]
+    NotReady,
+
+    #[display("container {0} is absent in the store")]
+    UnknownContainer(ContainerId),
+
+    #[from]
+    Manifest(ManifestError),
+
+    #[display("{0} containers are absent from the manifest")]
+    MissingContainers(usize),
+
+    #[from]
+    Attestation(AttestationError),
+
+    #[display("peer {0} has not sent attestation of its software")]
+    NoAttestation(NodeId),
+
+    #[display("peer runs version {0}, which is below the required one")]
+    OutdatedVersion(String),
+
+    #[display("managed node has refused the command: {0}")]
+    AdminRefused(String),
+
+    #[display("no pending proposal {0:#018x}")]
+    UnknownProposal(u64),
+
+    #[display("approved action {0:#018x} has failed")]
+    ActionFailed(u64),
 }
 
 impl Opts {
@@ -48,10 +93,20 @@ impl Opts {
         store_client: &mut store_rpc::Client,
         lnp_client: &mut lnp_rpc::Client,
     ) -> Result<(), Error> {
+        // Progress goes to stderr when stdout carries JSON output
+        let json = self.json;
         let progress = |info| {
-            println!("{}", info);
+            if json {
+                eprintln!("{}", info);
+            } else {
+                println!("{}", info);
+            }
         };
 
+        if let Some(request_id) = self.request_id {
+            storm_client.set_request_id(request_id);
+        }
+
         debug!("Performing {:?}", self.command);
         match self.command {
             Command::ChatSend { connect, peer } => {
@@ -64,48 +119,407 @@ impl Opts {
                     storm_client.chat_tell(peer, line?)?;
                 }
             }
+            Command::Info => {
+                let info = storm_client.node_info()?;
+                report_info(&info);
+            }
+            Command::Remote { peer, command } => {
+                let (container_ids, command) = match command {
+                    RemoteCommand::Status => (vec![], AdminCommand::Status),
+                    RemoteCommand::Pin { container_ids } => {
+                        (container_ids.clone(), AdminCommand::Pin(container_ids))
+                    }
+                    RemoteCommand::Replicate {
+                        app,
+                        from,
+                        container_id,
+                    } => (
+                        vec![container_id],
+                        AdminCommand::Replicate(Replication {
+                            app: StormApp::from(app),
+                            container_id,
+                            source: from,
+                        }),
+                    ),
+                };
+                match storm_client.administer(peer, command)? {
+                    AdminOutcome::Status(info) => report_info(&info),
+                    AdminOutcome::Pinned(result) => report_batch(&container_ids, &result),
+                    AdminOutcome::Replicating => {
+                        println!("Managed node has started retrieving the container")
+                    }
+                    AdminOutcome::Refused(reason) => return Err(Error::AdminRefused(reason)),
+                }
+            }
+            Command::Container(ContainerCommand::Send {
+                connect,
+                mime,
+                info,
+                encrypt,
+                peer,
+                path,
+            }) => {
+                if let Some(addr) = connect {
+                    let remote_node = PartialNodeAddr { id: peer, addr };
+                    lnp_client.connect(LnpAddr::bifrost(remote_node))?;
+                }
+                let data = fs::read(path)?;
+                let header = ContainerHeader {
+                    version: 0,
+                    mime,
+                    info: info.unwrap_or_default(),
+                    size: data.len() as u64,
+                };
+                let key = encrypt.then(ContainerKey::random);
+                let (id, total_chunks) =
+                    store_container(store_client, header, &data, key.as_ref())?;
+                eprintln!("Containerized ({} chunks in total)", total_chunks);
+                storm_client.upload(peer, id, progress)?;
+                if json {
+                    let key = key.map(|key| format!(",\"key\":\"{}\"", key)).unwrap_or_default();
+                    println!(
+                        "{{\"container_id\":\"{}\",\"peer\":\"{}\",\"chunks\":{}{}}}",
+                        id, peer, total_chunks, key
+                    );
+                } else {
+                    println!("{}", id);
+                    if let Some(key) = key {
+                        eprintln!("Container is encrypted; pass the key only to the recipients:");
+                        println!("{}", key);
+                    }
+                }
+            }
+            Command::Container(ContainerCommand::Get {
+                connect,
+                peer,
+                container_id,
+                key,
+                output,
+            }) => {
+                if let Some(addr) = connect {
+                    let remote_node = PartialNodeAddr { id: peer, addr };
+                    lnp_client.connect(LnpAddr::bifrost(remote_node))?;
+                }
+                storm_client.download(peer, container_id, progress)?;
+                let (header, data) = read_container(store_client, container_id, key.as_ref())?;
+                fs::write(&output, &data)?;
+                if json {
+                    println!(
+                        "{{\"container_id\":\"{}\",\"path\":{},\"size\":{},\"mime\":{},\"info\":\
+                         {}}}",
+                        container_id,
+                        json_string(&output.display().to_string()),
+                        header.size,
+                        json_string(header.mime.as_str()),
+                        json_string(&header.info)
+                    );
+                } else {
+                    println!("Size: {} bytes", header.size);
+                    println!("MIME: {}", header.mime);
+                    println!("Info: {}", header.info);
+                    eprintln!("Saved to {}", output.display());
+                }
+            }
+            Command::App(AppCommand::List) => {
+                let apps = storm_client.node_info()?.apps;
+                if json {
+                    let apps = apps.iter().map(|app| json_string(&app.to_string()));
+                    println!("[{}]", apps.collect::<Vec<_>>().join(","));
+                } else {
+                    for app in apps {
+                        println!("{}", app);
+                    }
+                }
+            }
+            Command::Topic(TopicCommand::List { app, peer }) => {
+                let app = StormApp::from(app);
+                let mut topics = vec![];
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.peer_topics(peer, app, req.clone())?;
+                    topics.extend(page.items.iter().copied());
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+                if json {
+                    let topics = topics.iter().map(|topic_id| format!("\"{}\"", topic_id));
+                    println!("[{}]", topics.collect::<Vec<_>>().join(","));
+                } else {
+                    for topic_id in topics {
+                        println!("{}", topic_id);
+                    }
+                }
+            }
             Command::ChatListen { connect, peer } => {
                 if let Some(addr) = connect {
                     let remote_node = PartialNodeAddr { id: peer, addr };
                     lnp_client.connect(LnpAddr::bifrost(remote_node))?;
                 }
                 loop {
-                    let line = storm_client.chat_recv(peer)?;
-                    println!("> {}", line);
+                    let entry = storm_client.chat_recv(peer)?;
+                    println!("> {}", entry.text);
+                    for preview in entry.previews {
+                        println!("  {}", preview);
+                        if let Some(description) = preview.description {
+                            println!("  {}", description);
+                        }
+                    }
+                }
+            }
+            Command::ChatMute { peer } => {
+                storm_client.set_chat_notify(peer, NotifyPref::Mute)?;
+            }
+            Command::ChatUnmute { peer } => {
+                storm_client.set_chat_notify(peer, NotifyPref::Notify)?;
+            }
+            Command::ChatDisappear { peer, after } => {
+                storm_client.set_chat_disappear(peer, after)?;
+            }
+            Command::ChatContact { name, peer } => {
+                storm_client.set_chat_contact(name, peer)?;
+            }
+            Command::ChatVerify { name } => {
+                storm_client.verify_chat_contact(name)?;
+            }
+            Command::ChatContacts => {
+                for contact in storm_client.chat_contacts()? {
+                    println!("{}", contact);
+                }
+            }
+            Command::ChatSafety { peer } => {
+                let local_id = local_node_id(lnp_client)?;
+                println!("{}", storm_client.chat_safety(local_id, peer)?);
+            }
+            Command::ChatBlock { peer } => {
+                storm_client.chat_block(peer, true)?;
+            }
+            Command::ChatUnblock { peer } => {
+                storm_client.chat_block(peer, false)?;
+            }
+            Command::ChatBlocked => {
+                for peer in storm_client.chat_blocked()? {
+                    println!("{}", peer);
+                }
+            }
+            Command::ChatNotify => {
+                for (peer, pref) in storm_client.chat_notify()? {
+                    println!("{} {}", peer, pref);
+                }
+            }
+            Command::ChatDraft {
+                peer,
+                save: Some(draft),
+            } => {
+                storm_client.chat_save_draft(peer, draft)?;
+            }
+            Command::ChatDraft { peer, save: None } => {
+                if let Some(draft) = storm_client.chat_draft(peer)? {
+                    println!("{}", draft);
+                }
+            }
+            Command::MailSend {
+                relay,
+                attachments,
+                peer,
+                subject,
+                body,
+            } => {
+                let body = match body {
+                    Some(body) => body,
+                    None => {
+                        let mut body = s!("");
+                        io::stdin().read_to_string(&mut body)?;
+                        body
+                    }
+                };
+                let request = MailRequest {
+                    recipient: peer,
+                    relay,
+                    mail: Mail {
+                        subject,
+                        body,
+                        attachments,
+                    },
+                };
+                let id = storm_client.send_mail(request)?;
+                println!("Mail {} is put into the outbox", id);
+            }
+            Command::MailList { list, folder } => {
+                let mut req = MailQuery {
+                    folder,
+                    query: list_query(list),
+                };
+                loop {
+                    let page = storm_client.list_mail(req.clone())?;
+                    for entry in &page.items {
+                        println!("{}", entry);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::MailRead { id } => {
+                let entry = storm_client.read_mail(id)?;
+                let dir = if entry.incoming { "From" } else { "To" };
+                println!("{}: {}", dir, entry.peer);
+                if let Some(relay) = entry.relay {
+                    println!("Relay: {}", relay);
+                }
+                println!("Date: {}", entry.timestamp);
+                println!("Subject: {}", entry.mail.subject);
+                for container_id in &entry.mail.attachments {
+                    println!("Attachment: {}", container_id);
+                }
+                println!("\n{}", entry.mail.body);
+            }
+            Command::MailMove { id, folder } => {
+                storm_client.move_mail(id, folder)?;
+            }
+            Command::MailDelete { id } => {
+                storm_client.delete_mail(id)?;
+            }
+            Command::BoardCreate { moderators, name } => {
+                let info = BoardInfo {
+                    name,
+                    moderators: moderators.into_iter().collect(),
+                };
+                println!("{}", storm_client.create_board(info)?);
+            }
+            Command::BoardSubscribe { peer, board } => {
+                storm_client.subscribe_board(peer, board)?;
+            }
+            Command::BoardUnsubscribe { board } => {
+                storm_client.unsubscribe_board(board)?;
+            }
+            Command::BoardList => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_boards(req.clone())?;
+                    for board in &page.items {
+                        println!("{}", board);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::BoardRead { board } => {
+                let mut req = Paged::with(board);
+                loop {
+                    let page = storm_client.read_board(req.clone())?;
+                    for post in &page.items {
+                        println!("{}", post);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::BoardPost { board, text } => {
+                let text = match text {
+                    Some(text) => text,
+                    None => {
+                        let mut text = s!("");
+                        io::stdin().read_to_string(&mut text)?;
+                        text
+                    }
+                };
+                let request = BoardPostRequest {
+                    board,
+                    author: local_node_id(lnp_client)?,
+                    text,
+                };
+                println!("{}", storm_client.post_board(request)?);
+            }
+            Command::BoardModerate {
+                board,
+                post,
+                action,
+                key,
+            } => {
+                let secret_key = SecretKey::strict_deserialize(fs::read(key)?)?;
+                let moderation = BoardModeration::with(board, post, action, &secret_key);
+                storm_client.moderate_board(moderation.sign(&secret_key))?;
+            }
+            Command::ChatHistory {
+                topic,
+                from,
+                limit,
+                peer,
+            } => {
+                let mut req = ChatHistoryReq {
+                    peer,
+                    topic,
+                    from,
+                    page: PageReq {
+                        cursor: None,
+                        limit,
+                    },
+                };
+                let mut left = limit as usize;
+                while left > 0 {
+                    let page = storm_client.chat_history(req.clone())?;
+                    for entry in page.items.iter().take(left) {
+                        println!("{}", entry);
+                    }
+                    left = left.saturating_sub(page.items.len());
+                    match req.page.next(&page) {
+                        Some(next) => req.page = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::ChatSearch { peer, query } => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.chat_search(peer, query.clone(), req.clone())?;
+                    for found in &page.items {
+                        let dir = if found.entry.incoming { '>' } else { '<' };
+                        println!("{} {} {}", found.entry.timestamp, dir, found.highlight("[", "]"));
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
                 }
             }
             Command::Containerize { mime, path, info } => {
-                // TODO: Make this procedure part of Storm Core (containerization of arbitrary vec)
                 let data = fs::read(path)?;
-                let mut chunk_ids = MediumVec::new();
-                let size = data.len() as u64;
-                for piece in data.chunks(u24::MAX.into_usize()) {
-                    let chunk = Chunk::try_from(piece)?;
-                    let chunk_id = chunk.chunk_id();
-                    store_client.store(storm_rpc::DB_TABLE_CHUNKS, chunk_id, &chunk)?;
-                    chunk_ids.push(chunk_id)?;
-                }
-
-                let total_chunks = chunk_ids.len();
                 let header = ContainerHeader {
                     version: 0,
                     mime,
                     info: info.unwrap_or_default(),
-                    size,
+                    size: data.len() as u64,
                 };
-                let header_chunk = Chunk::try_from(header.strict_serialize()?)?;
-                let container = Container {
-                    header,
-                    chunks: chunk_ids,
-                };
-                let container_chunk = Chunk::try_from(container.strict_serialize()?)?;
-
-                let id = container.container_id();
-                store_client.store(storm_rpc::DB_TABLE_CONTAINER_HEADERS, id, &header_chunk)?;
-                store_client.store(storm_rpc::DB_TABLE_CONTAINERS, id, &container_chunk)?;
+                let (id, total_chunks) = store_container(store_client, header, &data, None)?;
                 eprintln!("Containerized ({} chunks in total)", total_chunks);
                 println!("{}", id);
             }
+            Command::Patch { base, target } => {
+                let (_, base_data) = read_container(store_client, base, None)?;
+                let (header, target_data) = read_container(store_client, target, None)?;
+                let patch = Patch::diff(base, &base_data, target, header, &target_data);
+                let data = patch.strict_serialize()?;
+                let header = ContainerHeader {
+                    version: 0,
+                    mime: PATCH_MIME.try_into().expect("static ASCII string"),
+                    info: format!("patch of {} against {}", target, base),
+                    size: data.len() as u64,
+                };
+                let (id, _) = store_container(store_client, header, &data, None)?;
+                eprintln!(
+                    "Patch takes {} bytes for {} bytes of the target",
+                    data.len(),
+                    target_data.len()
+                );
+                println!("{}", id);
+            }
             Command::Assemble { container_id, path } => {
                 // TODO: Make this procedure part of Storm Core (assembling data from a container)
                 let container_chunk = store_client
@@ -117,9 +531,8 @@ impl Opts {
                 println!("Info: {}", container.header.info);
                 let mut file = fs::File::create(&path)?;
                 for chunk_id in container.chunks {
-                    let chunk = store_client
-                        .retrieve_chunk(storm_rpc::DB_TABLE_CHUNKS, chunk_id)?
-                        .expect(&format!("Chunk {} is absent", chunk_id));
+                    let chunk = retrieve_chunk(store_client, container_id, chunk_id)?
+                        .unwrap_or_else(|| panic!("Chunk {} is absent", chunk_id));
                     file.write_all(chunk.as_slice())?;
                 }
                 eprintln!("Saved to {}", path.display());
@@ -137,6 +550,7 @@ impl Opts {
             }
             Command::Download {
                 connect,
+                detach,
                 peer,
                 container_id,
             } => {
@@ -144,9 +558,645 @@ impl Opts {
                     let remote_node = PartialNodeAddr { id: peer, addr };
                     lnp_client.connect(LnpAddr::bifrost(remote_node))?;
                 }
-                storm_client.download(peer, container_id, progress)?;
+                if detach {
+                    // Files downloaded from the command line are not attached to a message
+                    let container_id = ContainerFullId {
+                        message_id: MesgId::default(),
+                        container_id,
+                    };
+                    let job_id = storm_client.submit_job(JobKind::Pull, peer, container_id)?;
+                    println!("{:#018x}", job_id);
+                } else {
+                    storm_client.download(peer, container_id, progress)?;
+                }
+            }
+            Command::JobStatus { id } => {
+                println!("{}", storm_client.job_status(id)?);
+            }
+            Command::Pin { container_ids } => {
+                let result = storm_client.pin_many(container_ids.clone())?;
+                report_batch(&container_ids, &result);
+            }
+            Command::Unpin {
+                container_ids,
+                dry_run,
+            } => {
+                let result = storm_client.unpin_many(container_ids.clone(), dry_run)?;
+                report_batch(&container_ids, &result);
+            }
+            Command::Evict {
+                container_ids,
+                dry_run,
+            } => {
+                let result = storm_client.evict_many(container_ids.clone(), dry_run)?;
+                report_batch(&container_ids, &result);
+            }
+            Command::Gc { dry_run } => {
+                let report = storm_client.collect_garbage(dry_run)?;
+                if dry_run {
+                    for chunk_id in &report.collected_chunks {
+                        println!("{}", chunk_id);
+                    }
+                }
+                println!("{}", report);
+            }
+            Command::Jobs => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_jobs(req.clone())?;
+                    for info in &page.items {
+                        println!("{}", info);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Sessions => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_sessions(req.clone())?;
+                    for info in &page.items {
+                        println!("{}", info);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::DisconnectSession { token } => {
+                storm_client.disconnect_session(token)?;
+            }
+            Command::Events { kind, app, peer } => {
+                let filter = EventFilter {
+                    kinds: kind.into_iter().collect(),
+                    apps: app.into_iter().map(StormApp::from).collect(),
+                    peers: peer.into_iter().collect(),
+                };
+                storm_client.subscribe_filtered(filter)?;
+                loop {
+                    println!("{}", storm_client.recv_event()?);
+                }
+            }
+            Command::WaitEvent {
+                since,
+                timeout,
+                kind,
+                app,
+                peer,
+            } => {
+                let filter = EventFilter {
+                    kinds: kind.into_iter().collect(),
+                    apps: app.into_iter().map(StormApp::from).collect(),
+                    peers: peer.into_iter().collect(),
+                };
+                let polled = storm_client.wait_event(WaitEvent {
+                    since,
+                    timeout,
+                    filter,
+                })?;
+                for event in polled.events {
+                    println!("{}", event);
+                }
+                println!("Cursor: {}", polled.cursor);
+            }
+            Command::RegisterInterest {
+                app,
+                container,
+                topic,
+                peer,
+            } => {
+                let interest = Interest {
+                    app: StormApp::from(app),
+                    containers: container.into_iter().collect(),
+                    messages: topic.into_iter().map(TopicId::to_wire).collect(),
+                };
+                storm_client.register_interest(peer, interest)?;
+            }
+            Command::PeerApps { peer } => {
+                let mut req = Paged::with(peer);
+                loop {
+                    let page = storm_client.peer_apps(req.clone())?;
+                    for app in &page.items {
+                        println!("{}", app);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Attestation { peer, min_version } => {
+                let signed = storm_client.attestation(peer)?.ok_or(Error::NoAttestation(peer))?;
+                signed.verify()?;
+                let attestation = signed.attestation;
+                println!("{}", attestation);
+                let apps = attestation.apps.iter().map(StormApp::to_string).collect::<Vec<_>>();
+                println!("Apps: {}", apps.join(", "));
+                if let Some(min_version) = min_version {
+                    if !attestation.has_version(&min_version) {
+                        return Err(Error::OutdatedVersion(attestation.version));
+                    }
+                }
+            }
+            Command::PeerFeatures { peer } => match storm_client.peer_features(peer)? {
+                Some(features) => println!("{}", features),
+                None => println!("Peer has not advertised its features"),
+            },
+            Command::TransferProgress { container_id } => {
+                let progress = storm_client.transfer_progress(container_id)?;
+                println!("{}", progress);
+                if progress.active {
+                    println!("Retrieval is in progress");
+                } else if progress.interrupted {
+                    println!("Retrieval is interrupted and resumes once the peer is online");
+                }
+            }
+            Command::Replicate {
+                peers,
+                min_replicas,
+                container_id,
+            } => {
+                let peers = peers.into_iter().collect::<BTreeSet<_>>();
+                let min_replicas =
+                    min_replicas.unwrap_or_else(|| peers.len().min(u8::MAX as usize) as u8);
+                let status = storm_client.replicate(container_id, peers, min_replicas)?;
+                println!("{}", status);
+            }
+            Command::Replication { container_id } => {
+                let status = storm_client.replication(container_id)?;
+                println!("{}", status);
+                if status.is_satisfied() {
+                    println!("Container is replicated");
+                } else if status.is_settled() {
+                    println!("Container has not reached the required replicas");
+                }
+            }
+            Command::Daemons => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_daemons(req.clone())?;
+                    for daemon in &page.items {
+                        println!("{}", daemon);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Health { ready } => {
+                let health = storm_client.health()?;
+                println!("Node is alive and {}", health);
+                for check in &health.checks {
+                    println!("- {}", check);
+                }
+                if ready && !health.is_ready() {
+                    return Err(Error::NotReady);
+                }
+            }
+            Command::FlagPeer { peer } => {
+                storm_client.flag_peer(peer, true)?;
+            }
+            Command::UnflagPeer { peer } => {
+                storm_client.flag_peer(peer, false)?;
+            }
+            Command::Quarantine => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.quarantine(req.clone())?;
+                    for held in &page.items {
+                        println!("{}", held);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::BanPeer { peer } => {
+                storm_client.ban_peer(peer, true)?;
+            }
+            Command::UnbanPeer { peer } => {
+                storm_client.ban_peer(peer, false)?;
+            }
+            Command::Bans => {
+                for peer in storm_client.bans()? {
+                    println!("{}", peer);
+                }
+            }
+            Command::RateLimits => {
+                println!("{}", storm_client.rate_limits()?);
+            }
+            Command::SetRateLimit {
+                peer,
+                per_peer,
+                bytes,
+                chunks,
+            } => {
+                let scope = match peer {
+                    Some(peer) => LimitScope::Peer(peer),
+                    None if per_peer => LimitScope::PerPeer,
+                    None => LimitScope::Global,
+                };
+                let limit = RateLimit {
+                    bytes_per_sec: bytes,
+                    chunks_per_sec: chunks,
+                };
+                storm_client.set_rate_limit(scope, limit)?;
+            }
+            Command::Memory => {
+                println!("{}", storm_client.memory()?);
+            }
+            Command::Chaos {
+                target: Some(target),
+                rate,
+                max_delay,
+            } => {
+                storm_client.set_fault(target, rate, max_delay)?;
+            }
+            Command::Chaos { target: None, .. } => {
+                for fault in storm_client.faults()? {
+                    println!("{}", fault);
+                }
+            }
+            Command::Release { container_id } => {
+                storm_client.release_container(container_id)?;
+            }
+            Command::Materialize { container_id } => {
+                storm_client.materialize(container_id)?;
+            }
+            Command::DeclareVersion { container_id, base } => {
+                storm_client.declare_version(container_id, base)?;
+            }
+            Command::AppUsage => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.app_usage(req.clone())?;
+                    for usage in &page.items {
+                        println!("{}", usage);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::RebuildIndex => {
+                storm_client.rebuild_index(progress)?;
+            }
+            Command::ExportManifest => {
+                let info = storm_client.export_manifest()?;
+                println!("Exported manifest {}", info);
+            }
+            Command::RotateManifestKey => {
+                storm_client.rotate_manifest_key()?;
+                println!("Manifest key is replaced");
+            }
+            Command::EnterMaintenance { retry_after } => {
+                storm_client.enter_maintenance(retry_after)?;
+                println!("Node is in maintenance");
+            }
+            Command::ExitMaintenance => {
+                storm_client.exit_maintenance()?;
+                println!("Node serves the remote peers");
+            }
+            Command::Proposals => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_proposals(req.clone())?;
+                    for info in &page.items {
+                        println!("{}", info);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Approve { proposal_id, key } => {
+                let secret_key = SecretKey::strict_deserialize(fs::read(key)?)?;
+                let mut req = PageReq::default();
+                let info = loop {
+                    let page = storm_client.list_proposals(req.clone())?;
+                    if let Some(info) =
+                        page.items.iter().find(|info| info.proposal.id == proposal_id)
+                    {
+                        break info.clone();
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => return Err(Error::UnknownProposal(proposal_id)),
+                    }
+                };
+                let approval = info.proposal.approve(&secret_key);
+                let outcome = storm_client.approve(approval)?;
+                println!("Proposal {}: {}", info.proposal, outcome);
+                if let ApprovalOutcome::Failed(_) = outcome {
+                    return Err(Error::ActionFailed(info.proposal.id));
+                }
+            }
+            Command::Audit => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_audit(req.clone())?;
+                    for record in &page.items {
+                        println!("{}", record);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::VerifyManifest {
+                path,
+                container_ids,
+            } => {
+                let signed = SignedManifest::strict_deserialize(fs::read(path)?)?;
+                signed.verify()?;
+                let manifest = &signed.manifest;
+                println!(
+                    "Manifest signed by {} at {}: {} containers and {} pins",
+                    manifest.signer,
+                    manifest.timestamp,
+                    manifest.containers.len(),
+                    manifest.pins.len()
+                );
+                let missing = signed.missing(container_ids);
+                for container_id in &missing {
+                    println!("Missing container {}", container_id);
+                }
+                if !missing.is_empty() {
+                    return Err(Error::MissingContainers(missing.len()));
+                }
+            }
+            Command::Backup => {
+                let info = storm_client.create_backup()?;
+                println!("Writing backup {}", info);
+            }
+            Command::Stats => {
+                let stats = storm_client.stats()?;
+                println!("Lifetime: {}", stats.lifetime);
+                println!("Since boot: {}", stats.since_boot);
+            }
+            Command::PeerStats => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.peer_stats(req.clone())?;
+                    for stats in &page.items {
+                        println!("{}", stats);
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::ContainerStats => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.container_stats(req.clone())?;
+                    for stats in &page.items {
+                        print!("{}", stats);
+                        if stats.cached {
+                            print!(", cached");
+                        }
+                        if stats.pinned {
+                            print!(", pinned");
+                        }
+                        println!();
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Containers { list } => {
+                let mut query = list_query(list);
+                loop {
+                    let page = storm_client.list_containers(query.clone())?;
+                    for entry in &page.items {
+                        print!("{}", entry);
+                        if let Some(app) = entry.app {
+                            print!(", {}", app);
+                        }
+                        if let Some(peer) = entry.peer {
+                            print!(", peer {}", peer);
+                        }
+                        println!();
+                    }
+                    match query.next(&page) {
+                        Some(next) => query = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::Transfers { list } => {
+                let mut query = list_query(list);
+                loop {
+                    let page = storm_client.list_transfers(query.clone())?;
+                    for entry in &page.items {
+                        print!("{}", entry);
+                        if let Some(app) = entry.app {
+                            print!(", {}", app);
+                        }
+                        if let Some(peer) = entry.peer {
+                            print!(", peer {}", peer);
+                        }
+                        println!();
+                    }
+                    match query.next(&page) {
+                        Some(next) => query = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::ShareCreate {
+                peer,
+                ttl,
+                container_id,
+            } => {
+                let token = storm_client.create_share(container_id, peer, ttl)?;
+                eprintln!("Share token {:#018x} issued", token.id);
+                println!("{}", token);
+            }
+            Command::ShareRevoke { id } => {
+                storm_client.revoke_share(id)?;
+                eprintln!("Share token {:#018x} revoked", id);
+            }
+            Command::ShareList => {
+                let mut req = PageReq::default();
+                loop {
+                    let page = storm_client.list_shares(req.clone())?;
+                    for info in &page.items {
+                        print!("{}", info);
+                        if let Some(peer) = info.grantee {
+                            print!(", granted to {}", peer);
+                        }
+                        if info.revoked {
+                            print!(", revoked");
+                        }
+                        println!();
+                    }
+                    match req.next(&page) {
+                        Some(next) => req = next,
+                        None => break,
+                    }
+                }
+            }
+            Command::ShareVerify { token } => {
+                storm_client.verify_share(token)?;
+                eprintln!("Share token is valid");
             }
         }
         Ok(())
     }
 }
+
+// TODO: Make this procedure part of Storm Core (containerization of arbitrary vec)
+/// Splits data into chunks and stores them with the container. If the key is given, the chunks
+/// are encrypted, leaving the header unencrypted.
+fn store_container(
+    store_client: &mut store_rpc::Client,
+    header: ContainerHeader,
+    data: &[u8],
+    key: Option<&ContainerKey>,
+) -> Result<(ContainerId, usize), Error> {
+    let mut chunk_ids = MediumVec::new();
+    let piece_len = match key {
+        Some(_) => u24::MAX.into_usize() - SEALED_CHUNK_OVERHEAD,
+        None => u24::MAX.into_usize(),
+    };
+    for (index, piece) in data.chunks(piece_len).enumerate() {
+        let chunk = match key {
+            Some(key) => Chunk::try_from(key.seal(index as u32, piece))?,
+            None => Chunk::try_from(piece)?,
+        };
+        let chunk_id = chunk.chunk_id();
+        store_client.store(storm_rpc::DB_TABLE_CHUNKS, chunk_id, &chunk)?;
+        chunk_ids.push(chunk_id)?;
+    }
+
+    let total_chunks = chunk_ids.len();
+    let header_chunk = Chunk::try_from(header.strict_serialize()?)?;
+    let container = Container {
+        header,
+        chunks: chunk_ids,
+    };
+    let container_chunk = Chunk::try_from(container.strict_serialize()?)?;
+
+    let id = container.container_id();
+    store_client.store(storm_rpc::DB_TABLE_CONTAINER_HEADERS, id, &header_chunk)?;
+    store_client.store(storm_rpc::DB_TABLE_CONTAINERS, id, &container_chunk)?;
+    Ok((id, total_chunks))
+}
+
+fn read_container(
+    store_client: &mut store_rpc::Client,
+    container_id: ContainerId,
+    key: Option<&ContainerKey>,
+) -> Result<(ContainerHeader, Vec<u8>), Error> {
+    let container_chunk = store_client
+        .retrieve_chunk(storm_rpc::DB_TABLE_CONTAINERS, container_id)?
+        .ok_or(Error::UnknownContainer(container_id))?;
+    let container = Container::strict_deserialize(container_chunk)?;
+    let mut data = Vec::with_capacity(container.header.size as usize);
+    for (index, chunk_id) in container.chunks.into_iter().enumerate() {
+        let chunk = retrieve_chunk(store_client, container_id, chunk_id)?
+            .unwrap_or_else(|| panic!("Chunk {} is absent", chunk_id));
+        match key {
+            Some(key) => data.extend(key.unseal(index as u32, chunk.as_slice())?),
+            None => data.extend_from_slice(chunk.as_slice()),
+        }
+    }
+    Ok((container.header, data))
+}
+
+/// Reads the chunk of the container, which the node may keep inline with the container if it is
+/// small. Chunks collected by the node garbage collector are reported as absent.
+fn retrieve_chunk(
+    store_client: &mut store_rpc::Client,
+    container_id: ContainerId,
+    chunk_id: ChunkId,
+) -> Result<Option<Chunk>, Error> {
+    if let Some(chunk) = store_client.retrieve_chunk(storm_rpc::DB_TABLE_CHUNKS, chunk_id)? {
+        if !chunk.is_empty() || chunk.chunk_id() == chunk_id {
+            return Ok(Some(chunk));
+        }
+    }
+    Ok(store_client
+        .retrieve_chunk(storm_rpc::DB_TABLE_INLINE_CHUNKS, container_id)?
+        .and_then(|record| InlineChunks::from_record(record).get(chunk_id).cloned()))
+}
+
+/// Renders string as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn report_info(info: &NodeInfo) {
+    println!("{}", info);
+    let apps = info.apps.iter().map(StormApp::to_string).collect::<Vec<_>>();
+    println!("Registered apps: {}", apps.join(", "));
+    let daemons = info.daemons.iter().map(ServiceId::to_string).collect::<Vec<_>>();
+    println!("Connected daemons: {}", daemons.join(", "));
+    println!("Known peers: {}", info.peers);
+    println!("Containers: {} using {} bytes", info.containers, info.storage);
+    println!("Features: {}", info.features);
+    if info.maintenance {
+        println!("Maintenance: new transfer requests of the remote peers are rejected");
+    }
+    if info.degraded {
+        println!("Degraded: connection to LNP node is lost");
+    }
+    if let Some(repair) = info.repair {
+        println!("Store repaired after unclean shutdown: {}", repair);
+    }
+}
+
+fn report_batch(container_ids: &[ContainerId], result: &BatchResult) {
+    for (container_id, status) in container_ids.iter().zip(&result.items) {
+        println!("{}: {}", container_id, status);
+    }
+    eprintln!("Batch of {}", result);
+}
+
+fn list_query(list: ListArgs) -> ListQuery {
+    ListQuery {
+        filter: Filter {
+            app: list.app.map(StormApp::from),
+            peer: list.peer,
+            min_size: list.min_size,
+            max_size: list.max_size,
+            since: list.since,
+            until: list.until,
+        },
+        sort: list.sort,
+        descending: list.desc,
+        page: none!(),
+    }
+}
+
+fn local_node_id(lnp_client: &mut lnp_rpc::Client) -> Result<NodeId, Error> {
+    lnp_client.request(lnp_rpc::ServiceId::LnpBroker, lnp_rpc::RpcMsg::GetInfo)?;
+    match lnp_client.response()? {
+        lnp_rpc::RpcMsg::NodeInfo(info) => Ok(info.node_id),
+        _ => Err(storm_rpc::Error::UnexpectedServerResponse.into()),
+    }
+}
@@ -9,6 +9,8 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 #![recursion_limit = "256"]
+// ESB errors are large, but are returned by all the client calls
+#![allow(clippy::result_large_err)]
 
 //! Command-line interface to Storm Node
 
@@ -66,6 +68,8 @@ fn main() {
         store_rpc::Client::with(&store_endpoint.clone()).expect("Error initializing store client");
 
     trace!("Executing command: {}", opts.command);
-    opts.exec(&mut storm_client, &mut store_client, &mut lnp_client)
-        .unwrap_or_else(|err| eprintln!("{} {}\n", "Error:".err(), err.err_details()));
+    if let Err(err) = opts.exec(&mut storm_client, &mut store_client, &mut lnp_client) {
+        eprintln!("{} {}\n", "Error:".err(), err.err_details());
+        std::process::exit(1);
+    }
 }
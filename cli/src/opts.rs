@@ -15,7 +15,10 @@ use lnp_rpc::LNP_NODE_RPC_ENDPOINT;
 use stens::AsciiString;
 use store_rpc::STORED_RPC_ENDPOINT;
 use storm::ContainerId;
-use storm_rpc::{CHATD_RPC_ENDPOINT, STORM_NODE_RPC_ENDPOINT};
+use storm_rpc::{
+    BoardAction, ContainerKey, EventKind, FaultTarget, MailFolder, MailId, PostId, ShareToken,
+    SortBy, TopicId, CHATD_RPC_ENDPOINT, STORM_NODE_RPC_ENDPOINT,
+};
 
 /// Command-line tool for working with store daemon
 #[derive(Parser, Clone, PartialEq, Eq, Debug)]
@@ -72,6 +75,17 @@ pub struct Opts {
     )]
     pub lnp_endpoint: ServiceAddr,
 
+    /// Id for the mutating request (upload, download, share creation or revocation), in hex.
+    ///
+    /// Repeating the command with the same request id guarantees that the node does not execute
+    /// the request twice, which is useful for retries after a timeout.
+    #[clap(long, global = true, parse(try_from_str = parse_hex_id))]
+    pub request_id: Option<u64>,
+
+    /// Print output of the `container`, `app` and `topic` commands in JSON format.
+    #[clap(long, global = true)]
+    pub json: bool,
+
     /// Set verbosity level.
     ///
     /// Can be used multiple times to increase verbosity.
@@ -83,9 +97,75 @@ pub struct Opts {
     pub command: Command,
 }
 
+/// Filtering and sorting of the listed items:
+#[derive(Args, Clone, PartialEq, Eq, Debug)]
+pub struct ListArgs {
+    /// Show only items of the application with the given code.
+    #[clap(long)]
+    pub app: Option<u16>,
+
+    /// Show only items transferred with the given remote peer.
+    #[clap(long)]
+    pub peer: Option<NodeId>,
+
+    /// Minimal item size, in bytes.
+    #[clap(long)]
+    pub min_size: Option<u64>,
+
+    /// Maximal item size, in bytes.
+    #[clap(long)]
+    pub max_size: Option<u64>,
+
+    /// Show only items transferred after the given UNIX timestamp.
+    #[clap(long)]
+    pub since: Option<u64>,
+
+    /// Show only items transferred before the given UNIX timestamp.
+    #[clap(long)]
+    pub until: Option<u64>,
+
+    /// Sort order: `default`, `size` or `timestamp`.
+    #[clap(long, default_value = "default")]
+    pub sort: SortBy,
+
+    /// Sort in descending order.
+    #[clap(long)]
+    pub desc: bool,
+}
+
 /// Command-line commands:
 #[derive(Subcommand, Clone, PartialEq, Eq, Debug, Display)]
 pub enum Command {
+    /// Show information about the node state.
+    #[display("info")]
+    Info,
+
+    /// Exchange files with the remote peers as containers.
+    #[display("container {0}")]
+    #[clap(subcommand)]
+    Container(ContainerCommand),
+
+    /// Inspect Storm apps registered with the node.
+    #[display("app {0}")]
+    #[clap(subcommand)]
+    App(AppCommand),
+
+    /// Inspect topics of the remote peer apps.
+    #[display("topic {0}")]
+    #[clap(subcommand)]
+    Topic(TopicCommand),
+
+    /// Manage another node of the operator over the Storm protocol. The managed node must be
+    /// run with `--admin` giving the id of this node.
+    #[display("remote {command}")]
+    Remote {
+        /// Node id (public key) of the managed node.
+        peer: NodeId,
+
+        #[clap(subcommand)]
+        command: RemoteCommand,
+    },
+
     /// Listen for the incoming chat messages from a remote peer.
     #[display("chat-listen")]
     ChatListen {
@@ -108,6 +188,248 @@ pub enum Command {
         peer: NodeId,
     },
 
+    /// Mute conversation with the remote peer. Messages from the peer are not published to the
+    /// chat listeners.
+    #[display("chat-mute")]
+    ChatMute {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Restore notifications for the conversation with the remote peer.
+    #[display("chat-unmute")]
+    ChatUnmute {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Make messages of the conversation with the remote peer disappear after the given time. The
+    /// setting applies to both sides of the conversation.
+    #[display("chat-disappear")]
+    ChatDisappear {
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Number of seconds after which the messages disappear; zero keeps the messages.
+        after: u64,
+    },
+
+    /// Bind the contact name to the remote peer key. Binding the name to another key resets the
+    /// contact verification.
+    #[display("chat-contact")]
+    ChatContact {
+        /// Contact name.
+        name: String,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Mark the contact as verified after comparing the safety numbers with the peer.
+    #[display("chat-verify")]
+    ChatVerify {
+        /// Contact name.
+        name: String,
+    },
+
+    /// List chat contacts.
+    #[display("chat-contacts")]
+    ChatContacts,
+
+    /// Print safety number of the conversation with the remote peer, which should match the one
+    /// seen by the peer.
+    #[display("chat-safety")]
+    ChatSafety {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Block chat messages from the remote peer.
+    #[display("chat-block")]
+    ChatBlock {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Unblock chat messages from the remote peer.
+    #[display("chat-unblock")]
+    ChatUnblock {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// List peers blocked in chat.
+    #[display("chat-blocked")]
+    ChatBlocked,
+
+    /// List conversations with non-default notification preferences.
+    #[display("chat-notify")]
+    ChatNotify,
+
+    /// Print unsent message saved for the conversation with the remote peer, or save a new one.
+    #[display("chat-draft")]
+    ChatDraft {
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Save the draft text; an empty text removes the draft.
+        #[clap(long)]
+        save: Option<String>,
+    },
+
+    /// Search the conversation with the remote peer for the messages containing the query.
+    #[display("chat-search")]
+    ChatSearch {
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Text to search for, ignoring ASCII case.
+        query: String,
+    },
+
+    /// Print messages of the conversation with the remote peer, oldest first.
+    #[display("chat-history")]
+    ChatHistory {
+        /// Print messages of the topic conversation instead of the direct one.
+        #[clap(long)]
+        topic: Option<TopicId>,
+
+        /// UNIX timestamp of the earliest message to print.
+        #[clap(long, default_value = "0")]
+        from: u64,
+
+        /// Maximal number of messages to print.
+        #[clap(long, default_value = "100")]
+        limit: u16,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Send a mail to the remote peer.
+    #[display("mail-send")]
+    MailSend {
+        /// Relay node which keeps the mail until the recipient is reachable. If not given, the
+        /// mail is sent directly.
+        #[clap(long)]
+        relay: Option<NodeId>,
+
+        /// Container attached to the mail. The argument can be given multiple times.
+        #[clap(long = "attach")]
+        attachments: Vec<ContainerId>,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Mail subject.
+        subject: String,
+
+        /// Mail body. If not given, the body is read from STDIN.
+        body: Option<String>,
+    },
+
+    /// List mails in the mailbox folder.
+    #[display("mail-list")]
+    MailList {
+        #[clap(flatten)]
+        list: ListArgs,
+
+        /// Mailbox folder: `inbox`, `outbox`, `sent`, `archive` or `trash`.
+        #[clap(default_value = "inbox")]
+        folder: MailFolder,
+    },
+
+    /// Print the mail, marking it as read.
+    #[display("mail-read")]
+    MailRead {
+        /// Mail id.
+        id: MailId,
+    },
+
+    /// Move the mail to another mailbox folder.
+    #[display("mail-move")]
+    MailMove {
+        /// Mail id.
+        id: MailId,
+
+        /// Mailbox folder: `inbox`, `archive` or `trash`.
+        folder: MailFolder,
+    },
+
+    /// Delete the mail, cancelling its delivery if it is not yet delivered.
+    #[display("mail-delete")]
+    MailDelete {
+        /// Mail id.
+        id: MailId,
+    },
+
+    /// Create a public board moderated by this node and the given nodes.
+    #[display("board-create")]
+    BoardCreate {
+        /// Board moderator key (public key) which signs the moderator actions. The argument can be
+        /// given multiple times.
+        #[clap(long = "moderator", required = true)]
+        moderators: Vec<NodeId>,
+
+        /// Board name.
+        name: String,
+    },
+
+    /// Subscribe to the board through the remote peer, syncing the board history from it.
+    #[display("board-subscribe")]
+    BoardSubscribe {
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Board id.
+        board: TopicId,
+    },
+
+    /// Unsubscribe from the board, forgetting its history.
+    #[display("board-unsubscribe")]
+    BoardUnsubscribe {
+        /// Board id.
+        board: TopicId,
+    },
+
+    /// List boards created by the node or which the node is subscribed to.
+    #[display("board-list")]
+    BoardList,
+
+    /// Print posts of the board, pinned posts first.
+    #[display("board-read")]
+    BoardRead {
+        /// Board id.
+        board: TopicId,
+    },
+
+    /// Publish a post on the board.
+    #[display("board-post")]
+    BoardPost {
+        /// Board id.
+        board: TopicId,
+
+        /// Post text. If not given, the text is read from STDIN.
+        text: Option<String>,
+    },
+
+    /// Pin, unpin or remove a board post, signing the action with a board moderator key.
+    #[display("board-moderate")]
+    BoardModerate {
+        /// Board id.
+        board: TopicId,
+
+        /// Post id.
+        post: PostId,
+
+        /// Moderator action: `pin`, `unpin` or `remove`.
+        action: BoardAction,
+
+        /// Path to the file with the moderator secret key.
+        #[clap(long)]
+        key: PathBuf,
+    },
+
     /// Convert on-disk file into a container in the Store database.
     #[display("containerize")]
     Containerize {
@@ -133,6 +455,17 @@ pub enum Command {
         path: PathBuf,
     },
 
+    /// Compute binary diff of a container against its base container and save it as a patch
+    /// container, from which the receiving nodes reconstruct the target container.
+    #[display("patch")]
+    Patch {
+        /// Id of the base container, which must be known to the receiving nodes.
+        base: ContainerId,
+
+        /// Id of the container reconstructed by the patch.
+        target: ContainerId,
+    },
+
     /// Upload given container to the remote peer
     #[display("upload")]
     Upload {
@@ -154,10 +487,565 @@ pub enum Command {
         #[clap(long)]
         connect: Option<PartialSocketAddr>,
 
+        /// Run download as a background job, printing its id instead of waiting for completion
+        #[clap(short, long)]
+        detach: bool,
+
         /// Remote node id (public key).
         peer: NodeId,
 
         /// Container to send
         container_id: ContainerId,
     },
+
+    /// Pin containers, excluding them from cache eviction. Containers which are not retrieved yet
+    /// are pinned ahead of the retrieval.
+    #[display("pin")]
+    Pin {
+        /// Containers to pin
+        #[clap(required = true)]
+        container_ids: Vec<ContainerId>,
+    },
+
+    /// Remove pins from the containers.
+    #[display("unpin")]
+    Unpin {
+        /// Containers to unpin
+        #[clap(required = true)]
+        container_ids: Vec<ContainerId>,
+
+        /// Only report the containers which would be unpinned.
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Evict containers from the node storage. No containers are evicted if any of them is
+    /// unknown to the node or pinned.
+    #[display("evict")]
+    Evict {
+        /// Containers to evict
+        #[clap(required = true)]
+        container_ids: Vec<ContainerId>,
+
+        /// Only report the containers which would be evicted and their size.
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Collect chunks which are not referenced by any of the stored containers for the grace
+    /// period.
+    #[display("gc")]
+    Gc {
+        /// Only report the chunks which would be collected.
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Show status of a background job.
+    #[display("job-status")]
+    JobStatus {
+        /// Job id, as reported by `download --detach`.
+        #[clap(parse(try_from_str = parse_hex_id))]
+        id: u64,
+    },
+
+    /// List background jobs.
+    #[display("jobs")]
+    Jobs,
+
+    /// List sessions of the RPC clients connected to the node.
+    #[display("sessions")]
+    Sessions,
+
+    /// Disconnect RPC client, refusing its further requests.
+    #[display("disconnect-session")]
+    DisconnectSession {
+        /// Session token, as reported by `sessions`.
+        #[clap(parse(try_from_str = parse_hex_id))]
+        token: u64,
+    },
+
+    /// List containers known to the node.
+    #[display("containers")]
+    Containers {
+        #[clap(flatten)]
+        list: ListArgs,
+    },
+
+    /// List container transfers which are in progress or are interrupted.
+    #[display("transfers")]
+    Transfers {
+        #[clap(flatten)]
+        list: ListArgs,
+    },
+
+    /// List container request statistics collected by the node, most popular first.
+    #[display("container-stats")]
+    ContainerStats,
+
+    /// List latency and throughput measured for the remote peers.
+    #[display("peer-stats")]
+    PeerStats,
+
+    /// Print node events as they happen.
+    #[display("events")]
+    Events {
+        /// Kind of the events to print: `transfer`, `message` or `topic`. If not given, events
+        /// of all kinds are printed. The argument can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        kind: Vec<EventKind>,
+
+        /// Print only the events of the app with the given id. The argument can be given
+        /// multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        app: Vec<u16>,
+
+        /// Print only the events caused by the remote peer with the given node id. The argument
+        /// can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        peer: Vec<NodeId>,
+    },
+
+    /// Wait for a node event and print the events published since the cursor, followed by the
+    /// cursor to pass to the next invocation. Nothing but the cursor is printed if no matching
+    /// event is published within the timeout.
+    #[display("wait-event")]
+    WaitEvent {
+        /// Cursor printed by the previous invocation. If not given, only the events published
+        /// after the command start are awaited.
+        #[clap(long)]
+        since: Option<u64>,
+
+        /// Time to wait for an event, in seconds.
+        #[clap(long, default_value = "60")]
+        timeout: u32,
+
+        /// Kind of the events to wait for: `transfer`, `message` or `topic`. If not given,
+        /// events of all kinds are awaited. The argument can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        kind: Vec<EventKind>,
+
+        /// Wait only for the events of the app with the given id. The argument can be given
+        /// multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        app: Vec<u16>,
+
+        /// Wait only for the events caused by the remote peer with the given node id. The
+        /// argument can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        peer: Vec<NodeId>,
+    },
+
+    /// List Storm apps run by the remote peer.
+    #[display("peer-apps")]
+    PeerApps {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Register interest in the app containers and topics with the remote peer, which then
+    /// announces only the matching containers and sends only the posts in the matching topics.
+    ///
+    /// Registering no containers and topics withdraws the interest.
+    #[display("register-interest")]
+    RegisterInterest {
+        /// Storm app id the interest is registered for.
+        #[clap(long)]
+        app: u16,
+
+        /// Container to register interest in. The argument can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        container: Vec<ContainerId>,
+
+        /// Topic to register interest in. The argument can be given multiple times.
+        #[clap(long, multiple_occurrences = true)]
+        topic: Vec<TopicId>,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Show attestation of the software run by the remote peer.
+    #[display("attestation")]
+    Attestation {
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Fail unless the peer attests this or a later version.
+        #[clap(long)]
+        min_version: Option<String>,
+    },
+
+    /// Show features advertised by the remote peer.
+    #[display("peer-features")]
+    PeerFeatures {
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+
+    /// Show progress of the container retrieval.
+    #[display("transfer-progress")]
+    TransferProgress {
+        /// Container which is retrieved.
+        container_id: ContainerId,
+    },
+
+    /// Replicate the stored container to the storage peers, which retrieve and pin it.
+    #[display("replicate")]
+    Replicate {
+        /// Storage peer node id (public key) to keep the replica; may be repeated.
+        #[clap(long = "peer", required = true)]
+        peers: Vec<NodeId>,
+
+        /// Number of the confirmed replicas required; defaults to all the given peers.
+        #[clap(long)]
+        min_replicas: Option<u8>,
+
+        /// Container to replicate.
+        container_id: ContainerId,
+    },
+
+    /// Show status of the container replication to the storage peers.
+    #[display("replication")]
+    Replication {
+        /// Replicated container.
+        container_id: ContainerId,
+    },
+
+    /// List daemons connected to the node.
+    #[display("daemons")]
+    Daemons,
+
+    /// Check node health. Fails if the node does not respond; with `--ready` also fails if the
+    /// node is not ready to serve requests.
+    #[display("health")]
+    Health {
+        /// Require the node to be ready.
+        #[clap(long)]
+        ready: bool,
+    },
+
+    /// Flag the remote peer, quarantining containers retrieved from it until they are released.
+    #[display("flag-peer")]
+    FlagPeer {
+        /// Remote node id (public key)
+        peer: NodeId,
+    },
+
+    /// Remove flag from the remote peer.
+    #[display("unflag-peer")]
+    UnflagPeer {
+        /// Remote node id (public key)
+        peer: NodeId,
+    },
+
+    /// List containers retrieved from the flagged peers.
+    #[display("quarantine")]
+    Quarantine,
+
+    /// Ban the remote peer, dropping its posts and topic proposals before they reach the apps.
+    #[display("ban-peer")]
+    BanPeer {
+        /// Remote node id (public key)
+        peer: NodeId,
+    },
+
+    /// Remove the remote peer from the ban list.
+    #[display("unban-peer")]
+    UnbanPeer {
+        /// Remote node id (public key)
+        peer: NodeId,
+    },
+
+    /// List banned peers.
+    #[display("bans")]
+    Bans,
+
+    /// Show rate limits of the chunks sent to the remote peers.
+    #[display("rate-limits")]
+    RateLimits,
+
+    /// Change rate limit of the chunks sent to the remote peers. Omitted rates are not limited.
+    ///
+    /// Without `--peer` or `--per-peer` the limit applies to all the remote peers together.
+    #[display("set-rate-limit")]
+    SetRateLimit {
+        /// Remote node id (public key) which own limit is changed
+        #[clap(long, conflicts_with = "per-peer")]
+        peer: Option<NodeId>,
+
+        /// Change the limit applied to each of the remote peers which has no own limit
+        #[clap(long)]
+        per_peer: bool,
+
+        /// Maximal number of bytes per second
+        #[clap(long)]
+        bytes: Option<u64>,
+
+        /// Maximal number of chunks per second
+        #[clap(long)]
+        chunks: Option<u32>,
+    },
+
+    /// Show memory accounted by the node against its budget.
+    #[display("memory")]
+    Memory,
+
+    /// Inject faults into the node for the chaos testing, or show the injected faults if no
+    /// target is given. Requires node built with the `chaos` feature.
+    #[display("chaos")]
+    #[clap(hide = true)]
+    Chaos {
+        /// Subsystem to inject faults into: `bus-delay`, `chunk-drop` or `fsync-failure`
+        target: Option<FaultTarget>,
+
+        /// Probability of the fault, in per-mille; zero disables the fault
+        #[clap(long, default_value = "0")]
+        rate: u16,
+
+        /// Maximal delay of a bus message, in milliseconds
+        #[clap(long, default_value = "1000")]
+        max_delay: u32,
+    },
+
+    /// Release container from the quarantine.
+    #[display("release")]
+    Release {
+        /// Container id
+        container_id: ContainerId,
+    },
+
+    /// Retrieve chunks of the container which was fetched metadata-first.
+    #[display("materialize")]
+    Materialize {
+        /// Container id
+        container_id: ContainerId,
+    },
+
+    /// Declare container to be a new version of another container, so only the chunks which
+    /// differ from the previous version are retrieved.
+    #[display("declare-version")]
+    DeclareVersion {
+        /// Container id
+        container_id: ContainerId,
+
+        /// Id of the previous version of the container
+        base: ContainerId,
+    },
+
+    /// Show storage used by the containers of each app.
+    #[display("app-usage")]
+    AppUsage,
+
+    /// Rebuild the container index from the container manifests kept in the store.
+    #[display("rebuild-index")]
+    RebuildIndex,
+
+    /// Export a signed manifest of the containers and pins hosted by the node into the node data
+    /// directory.
+    #[display("export-manifest")]
+    ExportManifest,
+
+    /// Replace the key signing the manifests and the attestations of the node.
+    #[display("rotate-manifest-key")]
+    RotateManifestKey,
+
+    /// Put the node into maintenance: remote peers requesting new transfers are rejected and told
+    /// when to retry, while the transfers in progress drain.
+    #[display("enter-maintenance")]
+    EnterMaintenance {
+        /// Number of seconds after which the remote peers may retry their requests.
+        #[clap(long, default_value = "600")]
+        retry_after: u32,
+    },
+
+    /// Resume serving the remote peers after maintenance.
+    #[display("exit-maintenance")]
+    ExitMaintenance,
+
+    /// List admin actions awaiting approvals of the operator keys.
+    #[display("proposals")]
+    Proposals,
+
+    /// Approve admin action with an operator key.
+    #[display("approve")]
+    Approve {
+        /// Proposal id, as reported by `proposals`.
+        #[clap(parse(try_from_str = parse_hex_id))]
+        proposal_id: u64,
+
+        /// Path to the file with the operator secret key.
+        #[clap(long)]
+        key: PathBuf,
+    },
+
+    /// Show audit log of the admin actions requiring approvals.
+    #[display("audit")]
+    Audit,
+
+    /// Verify signature of a manifest exported by a node and check that it lists the given
+    /// containers.
+    #[display("verify-manifest")]
+    VerifyManifest {
+        /// Path to the manifest file.
+        path: PathBuf,
+
+        /// Containers which the node must host.
+        container_ids: Vec<ContainerId>,
+    },
+
+    /// Back up the container index and the pin set into the node data directory.
+    #[display("backup")]
+    Backup,
+
+    /// Show node activity counters for the node lifetime and since its start.
+    #[display("stats")]
+    Stats,
+
+    /// Issue a time-limited share token granting read access to a container.
+    #[display("share-create")]
+    ShareCreate {
+        /// Remote node id (public key) which is granted the access. If not given, the token
+        /// may be used by anyone knowing it.
+        #[clap(long)]
+        peer: Option<NodeId>,
+
+        /// Number of seconds the token remains valid.
+        #[clap(long, default_value = "86400")]
+        ttl: u64,
+
+        /// Container to share
+        container_id: ContainerId,
+    },
+
+    /// Revoke previously issued share token.
+    #[display("share-revoke")]
+    ShareRevoke {
+        /// Share token id, as reported by `share-list`.
+        #[clap(parse(try_from_str = parse_hex_id))]
+        id: u64,
+    },
+
+    /// List issued share tokens with their usage.
+    #[display("share-list")]
+    ShareList,
+
+    /// Verify share token presented by a third party.
+    #[display("share-verify")]
+    ShareVerify {
+        /// Share token
+        token: ShareToken,
+    },
+}
+
+/// Container commands:
+#[derive(Subcommand, Clone, PartialEq, Eq, Debug, Display)]
+pub enum ContainerCommand {
+    /// Containerize the file and upload the container to the remote peer.
+    #[display("send")]
+    Send {
+        /// Remote node address to force connection (re)establishment
+        #[clap(long)]
+        connect: Option<PartialSocketAddr>,
+
+        /// MIME file type
+        #[clap(short, long, default_value = "application/octet-stream")]
+        mime: AsciiString,
+
+        /// Information about the container
+        #[clap(long)]
+        info: Option<String>,
+
+        /// Encrypt the container chunks with a new key, so the nodes storing the container can't
+        /// read it. The key is printed after the container id and must be passed only to the
+        /// recipients.
+        #[clap(long)]
+        encrypt: bool,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Local file to send.
+        path: PathBuf,
+    },
+
+    /// Download the container from the remote peer and assemble it into a file.
+    #[display("get")]
+    Get {
+        /// Remote node address to force connection (re)establishment
+        #[clap(long)]
+        connect: Option<PartialSocketAddr>,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+
+        /// Container to retrieve
+        container_id: ContainerId,
+
+        /// Key to decrypt the container which was sent encrypted
+        #[clap(long)]
+        key: Option<ContainerKey>,
+
+        /// Path and filename to save the file.
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+}
+
+/// App commands:
+#[derive(Subcommand, Clone, PartialEq, Eq, Debug, Display)]
+pub enum AppCommand {
+    /// List Storm apps registered with the node.
+    #[display("list")]
+    List,
+}
+
+/// Topic commands:
+#[derive(Subcommand, Clone, PartialEq, Eq, Debug, Display)]
+pub enum TopicCommand {
+    /// List topics of the app run by the remote peer.
+    #[display("list")]
+    List {
+        /// Code of the application.
+        #[clap(long)]
+        app: u16,
+
+        /// Remote node id (public key).
+        peer: NodeId,
+    },
+}
+
+/// Commands to the managed node:
+#[derive(Subcommand, Clone, PartialEq, Eq, Debug, Display)]
+pub enum RemoteCommand {
+    /// Show information about the managed node state.
+    #[display("status")]
+    Status,
+
+    /// Pin containers stored by the managed node.
+    #[display("pin")]
+    Pin {
+        /// Containers to pin
+        #[clap(required = true)]
+        container_ids: Vec<ContainerId>,
+    },
+
+    /// Retrieve container to the managed node from another peer and pin it.
+    #[display("replicate")]
+    Replicate {
+        /// Code of the application the container belongs to.
+        #[clap(long)]
+        app: u16,
+
+        /// Remote node id (public key) the container is retrieved from.
+        #[clap(long)]
+        from: NodeId,
+
+        /// Container to replicate
+        container_id: ContainerId,
+    },
+}
+
+fn parse_hex_id(s: &str) -> Result<u64, std::num::ParseIntError> {
+    u64::from_str_radix(s.trim_start_matches("0x"), 16)
 }
@@ -0,0 +1,206 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Generators of arbitrary extension messages, used for checking that encoding of each message
+//! round-trips.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use internet2::addr::NodeId;
+use internet2::{TypedEnum, Unmarshall};
+use rand::Rng;
+use storm::{ContainerFullId, ContainerId, ContainerInfo, Mesg, MesgId, StormApp, Topic};
+use storm_rpc::arbitrary::{Arbitrary, RoundtripError};
+use storm_rpc::{AddressedMsg, BatchResult, BusFeatures, Dictionary, TopicId};
+
+use crate::{
+    DeclineReason, Declined, DeliveryReport, DeliveryStatus, EventClass, EventDigest, ExtMsg,
+    MultiPost, NodeEvent, OpaqueMsg, TopicsDelta, STORM_EXT_UNMARSHALLER,
+};
+
+impl Arbitrary for DeliveryStatus {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        if rng.gen() {
+            DeliveryStatus::Sent(rng.gen())
+        } else {
+            DeliveryStatus::Failed(rng.gen())
+        }
+    }
+}
+
+impl Arbitrary for MultiPost {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        MultiPost {
+            recipients: BTreeSet::arbitrary(rng),
+            mesg: Mesg::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for DeliveryReport {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        DeliveryReport {
+            mesg_id: MesgId::arbitrary(rng),
+            statuses: BTreeMap::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for TopicsDelta {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        TopicsDelta {
+            missing: BTreeSet::arbitrary(rng),
+            extra: BTreeSet::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for OpaqueMsg {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        OpaqueMsg {
+            ty: rng.gen(),
+            payload: Vec::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for DeclineReason {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..6) {
+            0 => DeclineReason::Unspecified,
+            1 => DeclineReason::Spam,
+            2 => DeclineReason::OffTopic,
+            3 => DeclineReason::Duplicate,
+            4 => DeclineReason::Abuse,
+            _ => DeclineReason::Policy,
+        }
+    }
+}
+
+impl Arbitrary for Declined {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Declined {
+            id: MesgId::arbitrary(rng),
+            reason: DeclineReason::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for EventClass {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..3) {
+            0 => EventClass::Transfer,
+            1 => EventClass::Cache,
+            _ => EventClass::Quarantine,
+        }
+    }
+}
+
+impl Arbitrary for NodeEvent {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..4) {
+            0 => NodeEvent::TransferComplete {
+                container_id: ContainerId::arbitrary(rng),
+                success: rng.gen(),
+            },
+            1 => NodeEvent::CacheAdmitted(ContainerId::arbitrary(rng)),
+            2 => NodeEvent::CacheEvicted(ContainerId::arbitrary(rng)),
+            _ => NodeEvent::Quarantined {
+                container_id: ContainerId::arbitrary(rng),
+                peer: NodeId::arbitrary(rng),
+            },
+        }
+    }
+}
+
+impl Arbitrary for EventDigest {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        EventDigest {
+            class: EventClass::arbitrary(rng),
+            since: rng.gen(),
+            events: Vec::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for ExtMsg {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..31) {
+            0 => ExtMsg::RegisterApp(StormApp::arbitrary(rng)),
+            1 => ExtMsg::Negotiate(BusFeatures::arbitrary(rng)),
+            2 => ExtMsg::Degraded(rng.gen()),
+            3 => ExtMsg::ListTopics(AddressedMsg::<()>::arbitrary(rng)),
+            4 => ExtMsg::Topics(AddressedMsg::<BTreeSet<TopicId>>::arbitrary(rng)),
+            5 => ExtMsg::ProposeTopic(AddressedMsg::<Topic>::arbitrary(rng)),
+            6 => ExtMsg::Post(AddressedMsg::<Mesg>::arbitrary(rng)),
+            7 => ExtMsg::PostMany(MultiPost::arbitrary(rng)),
+            8 => ExtMsg::DeliveryReport(DeliveryReport::arbitrary(rng)),
+            9 => ExtMsg::Read(AddressedMsg::<MesgId>::arbitrary(rng)),
+            10 => ExtMsg::ContainerAnnouncement(AddressedMsg::<ContainerInfo>::arbitrary(rng)),
+            11 => ExtMsg::ContainerRetrieved(ContainerId::arbitrary(rng)),
+            12 => ExtMsg::RetrieveContainer(AddressedMsg::<ContainerFullId>::arbitrary(rng)),
+            13 => ExtMsg::SendContainer(AddressedMsg::<ContainerFullId>::arbitrary(rng)),
+            14 => ExtMsg::Decline(AddressedMsg::<MesgId>::arbitrary(rng)),
+            15 => ExtMsg::AcceptMany(Vec::arbitrary(rng)),
+            16 => ExtMsg::BatchResult(BatchResult::arbitrary(rng)),
+            17 => ExtMsg::DeclineTopic(AddressedMsg::<TopicId>::arbitrary(rng)),
+            18 => ExtMsg::AcceptTopic(AddressedMsg::<TopicId>::arbitrary(rng)),
+            19 => ExtMsg::Opaque(AddressedMsg::<OpaqueMsg>::arbitrary(rng)),
+            20 => ExtMsg::Event(NodeEvent::arbitrary(rng)),
+            21 => ExtMsg::EventDigest(EventDigest::arbitrary(rng)),
+            22 => ExtMsg::Dictionary(Dictionary::arbitrary(rng)),
+            23 => ExtMsg::SyncTopics(NodeId::arbitrary(rng)),
+            24 => ExtMsg::TopicsSynced(AddressedMsg::<TopicsDelta>::arbitrary(rng)),
+            25 => ExtMsg::UnregisterApp(StormApp::arbitrary(rng)),
+            26 => ExtMsg::Heartbeat(rng.gen()),
+            27 => ExtMsg::RetrieveMetadata(AddressedMsg::<ContainerFullId>::arbitrary(rng)),
+            28 => ExtMsg::MetadataRetrieved(ContainerId::arbitrary(rng)),
+            29 => ExtMsg::DeclineWithReason(AddressedMsg::<Declined>::arbitrary(rng)),
+            _ => ExtMsg::Accept(AddressedMsg::<MesgId>::arbitrary(rng)),
+        }
+    }
+}
+
+/// Checks that the extension message decoded from its wire representation has exactly the same
+/// representation.
+pub fn roundtrip_ext(msg: &ExtMsg) -> Result<(), RoundtripError> {
+    let original = msg.serialize();
+    let decoded = STORM_EXT_UNMARSHALLER
+        .unmarshall(original.as_slice())
+        .map_err(|err| RoundtripError::Wire(err.to_string()))?
+        .serialize();
+    if original != decoded {
+        return Err(RoundtripError::Mismatch { original, decoded });
+    }
+    Ok(())
+}
+
+/// Generates given number of arbitrary extension messages, checking round-trip of each of them.
+pub fn check_ext_roundtrips<R>(rng: &mut R, iterations: usize) -> Result<(), RoundtripError>
+where R: Rng + ?Sized {
+    (0..iterations).try_for_each(|_| roundtrip_ext(&ExtMsg::arbitrary(rng)))
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn ext_roundtrips(seed in any::<u64>()) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            check_ext_roundtrips(&mut rng, 32).map_err(|err| TestCaseError::fail(err.to_string()))?;
+        }
+    }
+}
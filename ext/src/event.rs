@@ -0,0 +1,109 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+
+/// Class of the node events. Events of a low-priority class may be batched by the node into
+/// periodic digests.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum EventClass {
+    /// Completion of the container transfers.
+    #[display("transfer")]
+    Transfer,
+
+    /// Admission and eviction of the cached containers.
+    #[display("cache")]
+    Cache,
+
+    /// Quarantine of the containers from the flagged peers.
+    #[display("quarantine")]
+    Quarantine,
+}
+
+/// Error parsing [`EventClass`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownEventClass(String);
+
+impl Display for UnknownEventClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown event class `{}`; allowed values are `transfer`, `cache` and `quarantine`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for EventClass {
+    type Err = UnknownEventClass;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transfer" => Ok(EventClass::Transfer),
+            "cache" => Ok(EventClass::Cache),
+            "quarantine" => Ok(EventClass::Quarantine),
+            other => Err(UnknownEventClass(other.to_owned())),
+        }
+    }
+}
+
+/// Event happened in the node, which is broadcasted to the registered extensions.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum NodeEvent {
+    #[display("transfer_complete({container_id}, {success})")]
+    TransferComplete {
+        container_id: ContainerId,
+        success: bool,
+    },
+
+    #[display("cache_admitted({0})")]
+    CacheAdmitted(ContainerId),
+
+    #[display("cache_evicted({0})")]
+    CacheEvicted(ContainerId),
+
+    #[display("quarantined({container_id}, {peer})")]
+    Quarantined {
+        container_id: ContainerId,
+        peer: NodeId,
+    },
+}
+
+impl NodeEvent {
+    pub fn class(&self) -> EventClass {
+        match self {
+            NodeEvent::TransferComplete { .. } => EventClass::Transfer,
+            NodeEvent::CacheAdmitted(_) | NodeEvent::CacheEvicted(_) => EventClass::Cache,
+            NodeEvent::Quarantined { .. } => EventClass::Quarantine,
+        }
+    }
+}
+
+/// Summary of the events of a single class happened during the digest period.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct EventDigest {
+    pub class: EventClass,
+    /// UNIX timestamp of the digest period start.
+    pub since: u64,
+    pub events: Vec<NodeEvent>,
+}
+
+impl Display for EventDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} events since {}", self.class, self.events.len(), self.since)
+    }
+}
@@ -20,13 +20,22 @@ extern crate internet2;
 #[cfg(feature = "serde")]
 extern crate serde_crate as serde;
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod event;
 mod messages;
+mod opaque;
 
+pub use event::{EventClass, EventDigest, NodeEvent, UnknownEventClass};
 use internet2::{CreateUnmarshaller, Unmarshaller};
-pub use messages::{ExtMsg, StormExtMsg};
+pub use messages::{
+    DeclineReason, Declined, DeliveryReport, DeliveryStatus, ExtLane, ExtMsg, MultiPost,
+    StormExtMsg, TopicsDelta,
+};
 use once_cell::sync::Lazy;
+pub use opaque::{OpaqueEnvelope, OpaqueMsg, BIFROST_APP_STORM_OPAQUE, OPAQUE_PAYLOAD_LIMIT};
 
-#[cfg(any(target_os = "linux"))]
+#[cfg(target_os = "linux")]
 pub const STORM_NODE_DATA_DIR: &str = "~/.storm_node";
 #[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
 pub const STORM_NODE_DATA_DIR: &str = "~/.storm_node";
@@ -42,4 +51,4 @@ pub const STORM_NODE_DATA_DIR: &str = ".";
 pub const STORM_NODE_EXT_ENDPOINT: &str = const_format::formatcp!("{}/storm", STORM_NODE_DATA_DIR);
 
 pub static STORM_EXT_UNMARSHALLER: Lazy<Unmarshaller<ExtMsg>> =
-    Lazy::new(|| ExtMsg::create_unmarshaller());
+    Lazy::new(ExtMsg::create_unmarshaller);
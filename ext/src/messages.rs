@@ -8,16 +8,24 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
 
 use internet2::addr::NodeId;
 use microservices::rpc;
 use storm::p2p::{self, AppMsg};
 use storm::{ContainerFullId, ContainerId, ContainerInfo, Mesg, MesgId, StormApp, Topic};
-use storm_rpc::AddressedMsg;
+use storm_rpc::{
+    AddressedMsg, BatchResult, BusFeatures, Compressed, DictCompressed, Dictionary, TopicId,
+};
 use strict_encoding::StrictEncode;
 
+use crate::{EventDigest, NodeEvent, OpaqueMsg};
+
 /// We need this wrapper type to be compatible with Storm Node having multiple message buses
+// The type documents the wire format of the extension bus, while the node and the extensions
+// decode it with their own bus messages
+#[allow(dead_code)]
 #[derive(Clone, Debug, Display, From, Api)]
 #[api(encoding = "strict")]
 #[non_exhaustive]
@@ -26,6 +34,18 @@ pub(crate) enum BusMsg {
     #[display(inner)]
     #[from]
     Ext(ExtMsg),
+
+    /// Compressed encoding of another message
+    #[api(type = 0x7f)]
+    #[display(inner)]
+    #[from]
+    Compressed(Compressed),
+
+    /// Encoding of another message compressed with the dictionary of the app
+    #[api(type = 0x7e)]
+    #[display(inner)]
+    #[from]
+    DictCompressed(DictCompressed),
 }
 
 impl rpc::Request for BusMsg {}
@@ -42,14 +62,63 @@ pub enum ExtMsg {
     #[display("register_app({0})")]
     RegisterApp(StormApp),
 
-    /* TODO: Consider developing sync API like
-    /// Extension request to sync topics with the remote peer.
+    /// An extension app disconnecting from the Storm node signals with this message that the
+    /// node must no longer route messages to it. Messages from the remote peers targeting the
+    /// app are declined by the node until the app registers again.
+    #[api(type = 0x0101)]
+    #[display("unregister_app({0})")]
+    UnregisterApp(StormApp),
+
+    /// Liveness check sent by the node to the registered extensions, which must echo it back.
+    /// Extensions which do not reply within the liveness timeout are unregistered.
+    #[api(type = 0x0109)]
+    #[display("heartbeat({0})")]
+    Heartbeat(u64),
+
+    /// Connection handshake following app registration: the extension reports the bus features
+    /// it supports and the node replies with the features which will be used for the connection.
+    #[api(type = 0x0104)]
+    #[display("negotiate({0})")]
+    Negotiate(BusFeatures),
+
+    /// Notification sent by the node to the registered extensions when the connection to the LNP
+    /// node is lost (`true`) or restored (`false`). While degraded, the node queues outbound
+    /// messages to the remote peers.
+    #[api(type = 0x0105)]
+    #[display("degraded({0})")]
+    Degraded(bool),
+
+    /// Event happened in the node, broadcasted to the registered extensions. Low-priority events
+    /// may be batched into periodic digests, depending on the node configuration.
+    #[api(type = 0x0106)]
+    #[display("event({0})")]
+    Event(NodeEvent),
+
+    /// Batch of the low-priority events of the same class, sent periodically instead of the
+    /// individual events of the class.
+    #[api(type = 0x0107)]
+    #[display("event_digest({0})")]
+    EventDigest(EventDigest),
+
+    /// Compression dictionary of the app, sent by the node to the extensions which negotiated
+    /// dictionary compression. Starting from this message, small messages between the node and
+    /// the extension are compressed with the dictionary.
+    #[api(type = 0x0108)]
+    #[display("dictionary({0})")]
+    Dictionary(Dictionary),
+
+    /// Extension request to sync topics of the app with the remote peer. The node reconciles
+    /// the topics of the app with the topics of the same app on the peer, requests the topics
+    /// missed by the app from the peer and replies with [`ExtMsg::TopicsSynced`].
     #[api(type = 0x0004)]
     #[display("sync_topics({0})")]
     SyncTopics(NodeId),
 
-    SyncMessages(PeerMsg<MesgFullId>),
-     */
+    /// Result of the topic sync with the remote peer requested with [`ExtMsg::SyncTopics`].
+    #[api(type = 0x0005)]
+    #[display("topics_synced({0})")]
+    TopicsSynced(AddressedMsg<TopicsDelta>),
+
     /// List topics known to the local Storm node.
     #[api(type = 0x0102)]
     #[display("list_topics()")]
@@ -58,7 +127,7 @@ pub enum ExtMsg {
     /// Response to `ListTopics` request.
     #[api(type = 0x0103)]
     #[display("topics(...)")]
-    Topics(AddressedMsg<BTreeSet<MesgId>>),
+    Topics(AddressedMsg<BTreeSet<TopicId>>),
 
     /// Sent or received propose to create a new Storm application topic which must be accepted or
     /// not.
@@ -72,6 +141,18 @@ pub enum ExtMsg {
     #[display("post({0})")]
     Post(AddressedMsg<Mesg>),
 
+    /// A message from the app extension to the Storm node requesting to post the same message to
+    /// multiple remote peers. The node replies with [`ExtMsg::DeliveryReport`] once all the
+    /// recipients were processed.
+    #[api(type = 0x0020)]
+    #[display("post_many({0})")]
+    PostMany(MultiPost),
+
+    /// Report on delivery of a message sent with [`ExtMsg::PostMany`] to each of its recipients.
+    #[api(type = 0x0021)]
+    #[display("delivery_report({0})")]
+    DeliveryReport(DeliveryReport),
+
     /// A message from app extension to external peer requesting certain message or a topic from a
     /// remote peer.
     #[api(type = 0x000a)]
@@ -93,23 +174,199 @@ pub enum ExtMsg {
     #[display("retrieve_container({0})")]
     RetrieveContainer(AddressedMsg<ContainerFullId>),
 
+    /// Command from an extension to the main daemon to retrieve only the header and the manifest
+    /// of the container from the remote peer. Chunks are retrieved once the container is read or
+    /// materialized. The node replies with [`ExtMsg::MetadataRetrieved`].
+    #[api(type = 0x0015)]
+    #[display("retrieve_metadata({0})")]
+    RetrieveMetadata(AddressedMsg<ContainerFullId>),
+
+    /// Notification about complete container metadata download requested with
+    /// [`ExtMsg::RetrieveMetadata`].
+    #[api(type = 0x0016)]
+    #[display("metadata_retrieved({0})")]
+    MetadataRetrieved(ContainerId),
+
     /// Command from an extension to the main daemon to send container to the remote peer
     #[api(type = 0x0014)]
     #[display("send_container({0})")]
     SendContainer(AddressedMsg<ContainerFullId>),
 
-    /// Command to the storm node to decline the topic or a message with a specific id coming from
-    /// certain peer.
+    /// Command to the storm node to decline the message with a specific id coming from certain
+    /// peer.
     #[api(type = 0x001c)]
     #[display("decline({0})")]
     Decline(AddressedMsg<MesgId>),
 
-    /// Command to the storm node to accept the topic or a message with a specific id coming from
-    /// certain peer. This also requests the node to download all the unknown containers for the
-    /// topic or the message.
+    /// Command to the storm node to decline the message or the topic proposal coming from
+    /// certain peer, telling the peer the reason. Sent by the node to the extension when a
+    /// remote peer declines a message of the app giving the reason.
+    #[api(type = 0x0027)]
+    #[display("decline_with_reason({0})")]
+    DeclineWithReason(AddressedMsg<Declined>),
+
+    /// Command to the storm node to decline the topic proposed by certain peer. Sent by the node
+    /// to the extension when a remote peer declines a topic known to the node.
+    #[api(type = 0x0025)]
+    #[display("decline_topic({0})")]
+    DeclineTopic(AddressedMsg<TopicId>),
+
+    /// Command to the storm node to accept multiple messages at once. The node replies
+    /// with [`ExtMsg::BatchResult`] containing per-item results.
+    #[api(type = 0x0022)]
+    #[display("accept_many(...)")]
+    AcceptMany(Vec<AddressedMsg<MesgId>>),
+
+    /// Per-item results of a batch command.
+    #[api(type = 0x0023)]
+    #[display("batch_result({0})")]
+    BatchResult(BatchResult),
+
+    /// Command to the storm node to accept the message with a specific id coming from certain
+    /// peer. This also requests the node to download all the unknown containers for the message.
     #[api(type = 0x001e)]
     #[display("accept({0})")]
     Accept(AddressedMsg<MesgId>),
+
+    /// Custom app message exchanged with the same app on the remote peer. The node does not
+    /// interpret the payload and delivers it only to the app which has the same id as the sender.
+    #[api(type = 0x0026)]
+    #[display("opaque({0})")]
+    Opaque(AddressedMsg<OpaqueMsg>),
+
+    /// Command to the storm node to accept the topic proposed by certain peer. Sent by the node
+    /// to the extension when a remote peer accepts a topic known to the node.
+    #[api(type = 0x0024)]
+    #[display("accept_topic({0})")]
+    AcceptTopic(AddressedMsg<TopicId>),
+}
+
+/// Message which has to be delivered to a set of remote peers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct MultiPost {
+    pub recipients: BTreeSet<NodeId>,
+    pub mesg: Mesg,
+}
+
+impl Display for MultiPost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {} recipients", self.mesg, self.recipients.len())
+    }
+}
+
+/// Reason given to the remote peer for declining its message or topic proposal.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum DeclineReason {
+    #[display("unspecified")]
+    Unspecified,
+
+    /// Unsolicited bulk messages or topic proposals.
+    #[display("spam")]
+    Spam,
+
+    /// Message does not match the topic it is posted to.
+    #[display("off-topic")]
+    OffTopic,
+
+    /// Message or topic which is already known.
+    #[display("duplicate")]
+    Duplicate,
+
+    /// Abusive or illegal content.
+    #[display("abuse")]
+    Abuse,
+
+    /// Message is refused by the policy of the node.
+    #[display("policy")]
+    Policy,
+}
+
+impl DeclineReason {
+    /// Detects whether the decline counts towards the automatic ban of the peer.
+    pub fn is_spam(self) -> bool { matches!(self, DeclineReason::Spam | DeclineReason::Abuse) }
+}
+
+/// Message or topic proposal declined with a reason.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{id} ({reason})")]
+pub struct Declined {
+    /// Id of the declined message, or the wire id of the declined topic.
+    pub id: MesgId,
+    pub reason: DeclineReason,
+}
+
+/// Difference between the topics of an app on the local node and on a remote peer.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct TopicsDelta {
+    /// Topics known to the remote peer and missed by the app. They are requested from the peer
+    /// and delivered to the app as usual.
+    pub missing: BTreeSet<TopicId>,
+    /// Topics of the app which are unknown to the remote peer.
+    pub extra: BTreeSet<TopicId>,
+}
+
+impl TopicsDelta {
+    pub fn is_empty(&self) -> bool { self.missing.is_empty() && self.extra.is_empty() }
+}
+
+impl Display for TopicsDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} missing, {} extra topics", self.missing.len(), self.extra.len())
+    }
+}
+
+/// Delivery status of a message for a single recipient.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum DeliveryStatus {
+    /// the message was sent after {0} attempt(s)
+    #[display(doc_comments)]
+    Sent(u8),
+
+    /// the message was not sent after {0} attempt(s)
+    #[display(doc_comments)]
+    Failed(u8),
+}
+
+/// Per-recipient delivery report for a message sent to multiple peers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct DeliveryReport {
+    pub mesg_id: MesgId,
+    pub statuses: BTreeMap<NodeId, DeliveryStatus>,
+}
+
+impl DeliveryReport {
+    /// Number of recipients the message was delivered to.
+    pub fn delivered(&self) -> usize {
+        self.statuses.values().filter(|status| matches!(status, DeliveryStatus::Sent(_))).count()
+    }
+
+    /// Number of recipients the message has failed to be delivered to.
+    pub fn failed(&self) -> usize { self.statuses.len() - self.delivered() }
+}
+
+impl Display for DeliveryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {} delivered, {} failed", self.mesg_id, self.delivered(), self.failed())
+    }
+}
+
+/// Delivery lane of the extension message. Control-plane messages are never delayed by bulk data
+/// notifications.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+pub enum ExtLane {
+    /// Time-sensitive control messages: app registration, accept/decline, delivery receipts etc.
+    #[display("control")]
+    Control,
+
+    /// Bulk data notifications: posts and container announcements.
+    #[display("bulk")]
+    Bulk,
 }
 
 pub trait StormExtMsg {
@@ -124,6 +381,7 @@ impl StormExtMsg for p2p::Messages {
                 (app, ExtMsg::ListTopics(AddressedMsg { remote_id, data }))
             }
             p2p::Messages::AppTopics(AppMsg { data, app }) => {
+                let data = data.into_iter().map(TopicId::from_wire).collect();
                 (app, ExtMsg::Topics(AddressedMsg { remote_id, data }))
             }
             p2p::Messages::ProposeTopic(AppMsg { data, app }) => {
@@ -153,38 +411,105 @@ impl StormExtMsg for p2p::Messages {
 }
 
 impl ExtMsg {
+    pub fn lane(&self) -> ExtLane {
+        match self {
+            ExtMsg::Post(_)
+            | ExtMsg::PostMany(_)
+            | ExtMsg::ContainerAnnouncement(_)
+            | ExtMsg::Opaque(_) => ExtLane::Bulk,
+            ExtMsg::RegisterApp(_)
+            | ExtMsg::UnregisterApp(_)
+            | ExtMsg::Heartbeat(_)
+            | ExtMsg::Negotiate(_)
+            | ExtMsg::Degraded(_)
+            | ExtMsg::Event(_)
+            | ExtMsg::EventDigest(_)
+            | ExtMsg::Dictionary(_)
+            | ExtMsg::ListTopics(_)
+            | ExtMsg::Topics(_)
+            | ExtMsg::SyncTopics(_)
+            | ExtMsg::TopicsSynced(_)
+            | ExtMsg::ProposeTopic(_)
+            | ExtMsg::Read(_)
+            | ExtMsg::DeliveryReport(_)
+            | ExtMsg::AcceptMany(_)
+            | ExtMsg::BatchResult(_)
+            | ExtMsg::ContainerRetrieved(_)
+            | ExtMsg::RetrieveContainer(_)
+            | ExtMsg::RetrieveMetadata(_)
+            | ExtMsg::MetadataRetrieved(_)
+            | ExtMsg::SendContainer(_)
+            | ExtMsg::Decline(_)
+            | ExtMsg::DeclineWithReason(_)
+            | ExtMsg::DeclineTopic(_)
+            | ExtMsg::Accept(_)
+            | ExtMsg::AcceptTopic(_) => ExtLane::Control,
+        }
+    }
+
     pub fn remote_id(&self) -> NodeId {
         match self {
-            ExtMsg::RegisterApp(_) => {
-                unreachable!("ExtMsg::remote_id must not be called on ExtMsg::RegisterApp")
+            ExtMsg::RegisterApp(_)
+            | ExtMsg::UnregisterApp(_)
+            | ExtMsg::Heartbeat(_)
+            | ExtMsg::Negotiate(_)
+            | ExtMsg::Degraded(_)
+            | ExtMsg::Event(_)
+            | ExtMsg::EventDigest(_)
+            | ExtMsg::Dictionary(_) => {
+                unreachable!("ExtMsg::remote_id must not be called on node service messages")
             }
             ExtMsg::ContainerRetrieved(_) => {
                 unreachable!("ExtMsg::remote_id must not be called on ExtMsg::ContainerRetrieved")
             }
+            ExtMsg::MetadataRetrieved(_) => {
+                unreachable!("ExtMsg::remote_id must not be called on ExtMsg::MetadataRetrieved")
+            }
+            ExtMsg::PostMany(_)
+            | ExtMsg::DeliveryReport(_)
+            | ExtMsg::AcceptMany(_)
+            | ExtMsg::BatchResult(_) => {
+                unreachable!("ExtMsg::remote_id must not be called on batch messages")
+            }
+            ExtMsg::SyncTopics(remote_id) => *remote_id,
             ExtMsg::ListTopics(AddressedMsg { remote_id, .. })
             | ExtMsg::Topics(AddressedMsg { remote_id, .. })
+            | ExtMsg::TopicsSynced(AddressedMsg { remote_id, .. })
             | ExtMsg::ProposeTopic(AddressedMsg { remote_id, .. })
             | ExtMsg::Post(AddressedMsg { remote_id, .. })
             | ExtMsg::Read(AddressedMsg { remote_id, .. })
             | ExtMsg::ContainerAnnouncement(AddressedMsg { remote_id, .. })
             | ExtMsg::RetrieveContainer(AddressedMsg { remote_id, .. })
+            | ExtMsg::RetrieveMetadata(AddressedMsg { remote_id, .. })
             | ExtMsg::SendContainer(AddressedMsg { remote_id, .. })
             | ExtMsg::Decline(AddressedMsg { remote_id, .. })
             | ExtMsg::Accept(AddressedMsg { remote_id, .. }) => *remote_id,
+            ExtMsg::DeclineWithReason(AddressedMsg { remote_id, .. }) => *remote_id,
+            ExtMsg::DeclineTopic(AddressedMsg { remote_id, .. })
+            | ExtMsg::AcceptTopic(AddressedMsg { remote_id, .. }) => *remote_id,
+            ExtMsg::Opaque(AddressedMsg { remote_id, .. }) => *remote_id,
         }
     }
 
     pub fn p2p_message(self, app: StormApp) -> p2p::Messages {
         match self {
-            ExtMsg::RegisterApp(_) => {
-                unreachable!("ExtMsg::remote_id must not be called on ExtMsg::RegisterApp")
+            ExtMsg::RegisterApp(_)
+            | ExtMsg::UnregisterApp(_)
+            | ExtMsg::Heartbeat(_)
+            | ExtMsg::Negotiate(_)
+            | ExtMsg::Degraded(_)
+            | ExtMsg::Event(_)
+            | ExtMsg::EventDigest(_)
+            | ExtMsg::Dictionary(_) => {
+                unreachable!("ExtMsg::p2p_message must not be called on node service messages")
             }
             ExtMsg::ListTopics(AddressedMsg { data, .. }) => {
                 p2p::Messages::ListTopics(AppMsg { app, data })
             }
-            ExtMsg::Topics(AddressedMsg { data, .. }) => {
-                p2p::Messages::AppTopics(AppMsg { app, data })
-            }
+            ExtMsg::Topics(AddressedMsg { data, .. }) => p2p::Messages::AppTopics(AppMsg {
+                app,
+                data: data.into_iter().map(TopicId::to_wire).collect(),
+            }),
             ExtMsg::ProposeTopic(AddressedMsg { data, .. }) => {
                 p2p::Messages::ProposeTopic(AppMsg { app, data })
             }
@@ -193,37 +518,80 @@ impl ExtMsg {
             ExtMsg::Decline(AddressedMsg { data, .. }) => {
                 p2p::Messages::Decline(AppMsg { app, data })
             }
+            ExtMsg::DeclineWithReason(AddressedMsg { data, .. }) => {
+                p2p::Messages::Decline(AppMsg { app, data: data.id })
+            }
             ExtMsg::Accept(AddressedMsg { data, .. }) => {
                 p2p::Messages::Accept(AppMsg { app, data })
             }
+            ExtMsg::DeclineTopic(AddressedMsg { data, .. }) => p2p::Messages::Decline(AppMsg {
+                app,
+                data: data.to_wire(),
+            }),
+            ExtMsg::AcceptTopic(AddressedMsg { data, .. }) => p2p::Messages::Accept(AppMsg {
+                app,
+                data: data.to_wire(),
+            }),
             ExtMsg::ContainerAnnouncement(AddressedMsg { data, .. }) => {
                 p2p::Messages::AnnounceContainer(AppMsg { app, data })
             }
             ExtMsg::SendContainer(_)
             | ExtMsg::RetrieveContainer(_)
-            | ExtMsg::ContainerRetrieved(_) => {
+            | ExtMsg::RetrieveMetadata(_)
+            | ExtMsg::ContainerRetrieved(_)
+            | ExtMsg::MetadataRetrieved(_) => {
                 unreachable!("the task is handled by a dedicated daemon")
             }
+            ExtMsg::PostMany(_)
+            | ExtMsg::DeliveryReport(_)
+            | ExtMsg::AcceptMany(_)
+            | ExtMsg::BatchResult(_) => {
+                unreachable!("batch messages are handled by the Storm node itself")
+            }
+            ExtMsg::SyncTopics(_) | ExtMsg::TopicsSynced(_) => {
+                unreachable!("topic sync is handled by the Storm node itself")
+            }
+            ExtMsg::Opaque(_) => {
+                unreachable!("app-opaque messages are not a part of the Storm p2p protocol")
+            }
         }
     }
 
     // TODO: Seemingly unused method, consider removing
     pub fn to_payload(&self) -> Vec<u8> {
         match self {
-            ExtMsg::RegisterApp(_) => {
-                unreachable!("ExtMsg::to_payload must not be called on ExtMsg::RegisterApp")
+            ExtMsg::RegisterApp(_) | ExtMsg::UnregisterApp(_) => {
+                unreachable!("ExtMsg::to_payload must not be called on app registration messages")
             }
+            ExtMsg::Heartbeat(nonce) => nonce.strict_serialize(),
             ExtMsg::ListTopics(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::Topics(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::SyncTopics(remote_id) => remote_id.strict_serialize(),
+            ExtMsg::TopicsSynced(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::ProposeTopic(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::Post(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::Read(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::Decline(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::DeclineWithReason(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::Accept(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::DeclineTopic(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::AcceptTopic(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::Opaque(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::ContainerAnnouncement(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::SendContainer(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::RetrieveContainer(AddressedMsg { data, .. }) => data.strict_serialize(),
             ExtMsg::ContainerRetrieved(container_id) => container_id.strict_serialize(),
+            ExtMsg::RetrieveMetadata(AddressedMsg { data, .. }) => data.strict_serialize(),
+            ExtMsg::MetadataRetrieved(container_id) => container_id.strict_serialize(),
+            ExtMsg::Negotiate(features) => features.strict_serialize(),
+            ExtMsg::Degraded(degraded) => degraded.strict_serialize(),
+            ExtMsg::Event(event) => event.strict_serialize(),
+            ExtMsg::EventDigest(digest) => digest.strict_serialize(),
+            ExtMsg::Dictionary(dict) => dict.strict_serialize(),
+            ExtMsg::PostMany(post) => post.strict_serialize(),
+            ExtMsg::DeliveryReport(report) => report.strict_serialize(),
+            ExtMsg::AcceptMany(accepts) => accepts.strict_serialize(),
+            ExtMsg::BatchResult(result) => result.strict_serialize(),
         }
         .expect("extension-generated message can't be serialized as a bifrost message payload")
     }
@@ -0,0 +1,51 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+
+use storm::StormApp;
+
+/// Bifrost application code under which the app-opaque messages are exchanged between the Storm
+/// nodes. The code belongs to the vendor range, since the envelope is not a part of the Storm
+/// p2p protocol.
+pub const BIFROST_APP_STORM_OPAQUE: u16 = 0x8000 | 0x5354;
+
+/// Maximal size of the payload of an app-opaque message, leaving space for the envelope within a
+/// single Bifrost message. Larger data must be transferred as containers.
+pub const OPAQUE_PAYLOAD_LIMIT: usize = 60 * 1024;
+
+/// Custom message of a Storm app, which is delivered by the node to the same app on the remote
+/// peer without interpreting its payload.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct OpaqueMsg {
+    /// App-defined message type.
+    pub ty: u16,
+    /// Strict-encoded message data.
+    pub payload: Vec<u8>,
+}
+
+impl Display for OpaqueMsg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "type {:#06x}, {} bytes", self.ty, self.payload.len())
+    }
+}
+
+impl OpaqueMsg {
+    pub fn is_oversized(&self) -> bool { self.payload.len() > OPAQUE_PAYLOAD_LIMIT }
+}
+
+/// Representation of the app-opaque message on the p2p wire.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct OpaqueEnvelope {
+    pub app: StormApp,
+    pub msg: OpaqueMsg,
+}
@@ -0,0 +1,62 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use internet2::addr::NodeId;
+use storm::{ContainerId, StormApp};
+
+use crate::{BatchResult, NodeInfo};
+
+/// Management command which a node of the operator issues to another node of the same
+/// operator over the Storm protocol.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum AdminCommand {
+    /// Report information about the managed node.
+    #[display("status")]
+    Status,
+
+    /// Pin containers stored by the managed node.
+    #[display("pin(...)")]
+    Pin(Vec<ContainerId>),
+
+    /// Retrieve container from another peer and pin it once retrieved.
+    #[display("replicate({0})")]
+    Replicate(Replication),
+}
+
+/// Container which the managed node retrieves and pins.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id} of {app} from {source}")]
+pub struct Replication {
+    pub app: StormApp,
+    pub container_id: ContainerId,
+    /// Remote peer the container is retrieved from.
+    pub source: NodeId,
+}
+
+/// Outcome of [`AdminCommand`] on the managed node.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum AdminOutcome {
+    #[display(inner)]
+    Status(NodeInfo),
+
+    #[display(inner)]
+    Pinned(BatchResult),
+
+    /// retrieval of the container has started
+    #[display(doc_comments)]
+    Replicating,
+
+    /// the command is refused: {0}
+    #[display(doc_comments)]
+    Refused(String),
+}
@@ -0,0 +1,173 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+use internet2::addr::NodeId;
+use secp256k1::hashes::sha256;
+use secp256k1::{ecdsa, Message, PublicKey, Secp256k1, SecretKey};
+use storm::ContainerId;
+use strict_encoding::StrictEncode;
+
+/// Sensitive admin action which a node shared by several operators executes only once it is
+/// approved by enough of the operator keys.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum AdminAction {
+    /// Purge containers from the node storage.
+    #[display("evict({0:?})")]
+    Evict(Vec<ContainerId>),
+
+    /// Collect chunks not referenced by the stored containers.
+    #[display("collect_garbage()")]
+    CollectGarbage,
+
+    /// Replace the key signing the manifests and the attestations of the node.
+    #[display("rotate_manifest_key()")]
+    RotateManifestKey,
+
+    /// Unpin containers, exposing them to eviction.
+    #[display("unpin({0:?})")]
+    Unpin(Vec<ContainerId>),
+
+    /// Rebuild the container index from the store, discarding the apps and the peers of the
+    /// containers.
+    #[display("rebuild_index()")]
+    RebuildIndex,
+
+    /// Revoke share token, cutting its grantee off the container.
+    #[display("revoke_share({0:#018x})")]
+    RevokeShare(u64),
+}
+
+/// Admin action awaiting approvals.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{id:#018x}: {action}")]
+pub struct Proposal {
+    pub id: u64,
+    pub action: AdminAction,
+    /// UNIX timestamp of the proposal.
+    pub timestamp: u64,
+}
+
+/// Signature of an operator key approving a [`Proposal`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Approval {
+    pub proposal_id: u64,
+    pub approver: NodeId,
+    pub signature: ecdsa::Signature,
+}
+
+/// Errors verifying [`Approval`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ApprovalError {
+    /// approval signature does not match the proposal and the approver key
+    InvalidSignature,
+}
+
+impl Proposal {
+    fn message(&self) -> Message {
+        let data = self.strict_serialize().expect("in-memory proposal encoding");
+        Message::from_hashed_data::<sha256::Hash>(&data)
+    }
+
+    /// Signs approval of the proposal with the operator key.
+    pub fn approve(&self, secret_key: &SecretKey) -> Approval {
+        let secp = Secp256k1::signing_only();
+        Approval {
+            proposal_id: self.id,
+            approver: NodeId::from(PublicKey::from_secret_key(&secp, secret_key)),
+            signature: secp.sign_ecdsa(&self.message(), secret_key),
+        }
+    }
+}
+
+impl Approval {
+    pub fn verify(&self, proposal: &Proposal) -> Result<(), ApprovalError> {
+        if self.proposal_id != proposal.id {
+            return Err(ApprovalError::InvalidSignature);
+        }
+        Secp256k1::verification_only()
+            .verify_ecdsa(&proposal.message(), &self.signature, &self.approver.public_key())
+            .map_err(|_| ApprovalError::InvalidSignature)
+    }
+}
+
+/// Proposal pending on the node together with the keys which have approved it.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ProposalInfo {
+    pub proposal: Proposal,
+    pub approvers: BTreeSet<NodeId>,
+    /// Number of approvals required to execute the action.
+    pub threshold: u8,
+}
+
+impl Display for ProposalInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, approved by {} of {} keys",
+            self.proposal,
+            self.approvers.len(),
+            self.threshold
+        )
+    }
+}
+
+/// Result of submitting an [`Approval`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(doc_comments)]
+pub enum ApprovalOutcome {
+    /// action awaits {0} more approvals
+    Pending(u8),
+
+    /// action is executed: {0}
+    Executed(String),
+
+    /// action has failed: {0}
+    Failed(String),
+}
+
+/// Step in the life of a proposal, recorded in the audit log of the node.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum AuditEvent {
+    #[display("proposed")]
+    Proposed,
+
+    #[display("approved by {0}")]
+    Approved(NodeId),
+
+    #[display("executed: {0}")]
+    Executed(String),
+
+    #[display("failed: {0}")]
+    Failed(String),
+}
+
+/// Record of the audit log of the admin actions.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{no}. {timestamp} {proposal_id:#018x} {action}: {event}")]
+pub struct AuditRecord {
+    /// Sequential number of the record.
+    pub no: u64,
+    /// UNIX timestamp of the record.
+    pub timestamp: u64,
+    pub proposal_id: u64,
+    pub action: AdminAction,
+    pub event: AuditEvent,
+}
@@ -0,0 +1,505 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Generators of arbitrary values for the Storm p2p and RPC message types, used for checking
+//! that encoding of each message round-trips.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use internet2::addr::NodeId;
+use internet2::{TypedEnum, Unmarshall};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use storm::p2p::{AppMsg, ChunkPull, ChunkPush, Messages, STORM_P2P_UNMARSHALLER};
+use storm::{
+    Chunk, ChunkId, Container, ContainerFullId, ContainerHeader, ContainerId, ContainerInfo, Mesg,
+    MesgId, StormApp, Topic,
+};
+use strict_encoding::{MediumVec, StrictDecode, StrictEncode};
+
+use crate::{
+    AddressedMsg, BatchResult, BusFeatures, Cursor, Dictionary, Fault, FaultTarget, Idempotent,
+    ItemStatus, Page, PageReq, Paged, RpcMsg, TopicId,
+};
+
+/// Maximal number of items in the generated collections.
+const MAX_ITEMS: usize = 8;
+
+/// Maximal length of the generated strings and byte vectors.
+const MAX_LEN: usize = 64;
+
+/// Type which values can be randomly generated.
+pub trait Arbitrary: Sized {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum RoundtripError {
+    /// value can't be encoded or decoded. Details: {0}
+    #[from]
+    Encoding(strict_encoding::Error),
+
+    /// message can't be decoded from its wire representation. Details: {0}
+    Wire(String),
+
+    /// decoded value has encoding different from the original one
+    Mismatch { original: Vec<u8>, decoded: Vec<u8> },
+}
+
+/// Checks that the value decoded from its strict encoding has exactly the same encoding.
+pub fn roundtrip<T>(value: &T) -> Result<(), RoundtripError>
+where T: StrictEncode + StrictDecode {
+    let original = value.strict_serialize()?;
+    let decoded = T::strict_deserialize(&original)?.strict_serialize()?;
+    if original != decoded {
+        return Err(RoundtripError::Mismatch { original, decoded });
+    }
+    Ok(())
+}
+
+/// Checks that the p2p message decoded from its wire representation has exactly the same
+/// representation.
+pub fn roundtrip_p2p(msg: &Messages) -> Result<(), RoundtripError> {
+    let original = msg.serialize();
+    let decoded = STORM_P2P_UNMARSHALLER
+        .unmarshall(original.as_slice())
+        .map_err(|err| RoundtripError::Wire(err.to_string()))?
+        .serialize();
+    if original != decoded {
+        return Err(RoundtripError::Mismatch { original, decoded });
+    }
+    Ok(())
+}
+
+/// Generates given number of arbitrary values, checking round-trip of each of them.
+pub fn check_roundtrips<T, R>(rng: &mut R, iterations: usize) -> Result<(), RoundtripError>
+where
+    T: Arbitrary + StrictEncode + StrictDecode,
+    R: Rng + ?Sized,
+{
+    (0..iterations).try_for_each(|_| roundtrip(&T::arbitrary(rng)))
+}
+
+/// Generates given number of arbitrary p2p messages, checking round-trip of each of them.
+pub fn check_p2p_roundtrips<R>(rng: &mut R, iterations: usize) -> Result<(), RoundtripError>
+where R: Rng + ?Sized {
+    (0..iterations).try_for_each(|_| roundtrip_p2p(&Messages::arbitrary(rng)))
+}
+
+fn len<R: Rng + ?Sized>(rng: &mut R, max: usize) -> usize { rng.gen_range(0..=max) }
+
+impl Arbitrary for () {
+    fn arbitrary<R: Rng + ?Sized>(_rng: &mut R) -> Self {}
+}
+
+impl Arbitrary for bool {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { rng.gen() }
+}
+
+impl Arbitrary for u8 {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { rng.gen() }
+}
+
+impl Arbitrary for u16 {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { rng.gen() }
+}
+
+impl Arbitrary for u32 {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { rng.gen() }
+}
+
+impl Arbitrary for u64 {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { rng.gen() }
+}
+
+impl Arbitrary for String {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = len(rng, MAX_LEN);
+        rng.sample_iter(Alphanumeric).take(len).map(char::from).collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        if rng.gen() {
+            Some(T::arbitrary(rng))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = len(rng, MAX_ITEMS);
+        (0..len).map(|_| T::arbitrary(rng)).collect()
+    }
+}
+
+impl<T: Arbitrary + Ord> Arbitrary for BTreeSet<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = len(rng, MAX_ITEMS);
+        (0..len).map(|_| T::arbitrary(rng)).collect()
+    }
+}
+
+impl<K: Arbitrary + Ord, V: Arbitrary> Arbitrary for BTreeMap<K, V> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = len(rng, MAX_ITEMS);
+        (0..len).map(|_| (K::arbitrary(rng), V::arbitrary(rng))).collect()
+    }
+}
+
+fn bytes<R: Rng + ?Sized>(rng: &mut R) -> Vec<u8> {
+    let len = len(rng, MAX_LEN);
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+fn hash<T: StrictDecode, R: Rng + ?Sized>(rng: &mut R) -> T {
+    T::strict_deserialize(rng.gen::<[u8; 32]>()).expect("any 32 bytes are a valid hash")
+}
+
+impl Arbitrary for MesgId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { hash(rng) }
+}
+
+impl Arbitrary for ContainerId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { hash(rng) }
+}
+
+impl Arbitrary for ChunkId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { hash(rng) }
+}
+
+impl Arbitrary for NodeId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        // About a half of the random x coordinates are on the curve
+        loop {
+            let mut data = [0u8; 33];
+            data[0] = if rng.gen() { 0x02 } else { 0x03 };
+            rng.fill(&mut data[1..]);
+            if let Ok(node_id) = NodeId::strict_deserialize(data) {
+                return node_id;
+            }
+        }
+    }
+}
+
+impl Arbitrary for StormApp {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        // Prefer well-known apps, but sometimes produce future and vendor ones
+        match rng.gen_range(0..4) {
+            0 => StormApp::from(rng.gen::<u16>()),
+            _ => StormApp::from(rng.gen_range(0..0x12)),
+        }
+    }
+}
+
+impl Arbitrary for Topic {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Topic {
+            body: bytes(rng),
+            container_ids: Vec::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for TopicId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { TopicId::from(&Topic::arbitrary(rng)) }
+}
+
+impl Arbitrary for Mesg {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Mesg {
+            parent_id: MesgId::arbitrary(rng),
+            body: bytes(rng),
+            container_ids: Vec::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for ContainerFullId {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        ContainerFullId {
+            message_id: MesgId::arbitrary(rng),
+            container_id: ContainerId::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for ContainerHeader {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        // MIME type is an ASCII string, which shares encoding with the alphanumeric strings
+        let mut data = vec![];
+        u16::arbitrary(rng).strict_encode(&mut data).expect("memory encoding does not fail");
+        String::arbitrary(rng).strict_encode(&mut data).expect("memory encoding does not fail");
+        String::arbitrary(rng).strict_encode(&mut data).expect("memory encoding does not fail");
+        u64::arbitrary(rng).strict_encode(&mut data).expect("memory encoding does not fail");
+        ContainerHeader::strict_deserialize(data).expect("container header fields are valid")
+    }
+}
+
+impl Arbitrary for ContainerInfo {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        ContainerInfo {
+            header: ContainerHeader::arbitrary(rng),
+            id: ContainerFullId::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for Container {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Container {
+            header: ContainerHeader::arbitrary(rng),
+            chunks: MediumVec::try_from(Vec::arbitrary(rng))
+                .expect("number of chunks is below the limit"),
+        }
+    }
+}
+
+impl Arbitrary for Chunk {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Chunk::try_from(bytes(rng)).expect("chunk data are below the limit")
+    }
+}
+
+impl<T: Arbitrary + StrictEncode + StrictDecode> Arbitrary for AppMsg<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        AppMsg {
+            app: StormApp::arbitrary(rng),
+            data: T::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for ChunkPull {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        ChunkPull {
+            app: StormApp::arbitrary(rng),
+            message_id: MesgId::arbitrary(rng),
+            container_id: ContainerId::arbitrary(rng),
+            chunk_ids: BTreeSet::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for ChunkPush {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let chunk = Chunk::arbitrary(rng);
+        ChunkPush {
+            app: StormApp::arbitrary(rng),
+            container_id: ContainerId::arbitrary(rng),
+            chunk_id: chunk.chunk_id(),
+            chunk,
+        }
+    }
+}
+
+impl Arbitrary for Messages {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..14) {
+            0 => Messages::ListApps,
+            1 => Messages::ActiveApps(BTreeSet::arbitrary(rng)),
+            2 => Messages::ListTopics(AppMsg::arbitrary(rng)),
+            3 => Messages::AppTopics(AppMsg::arbitrary(rng)),
+            4 => Messages::ProposeTopic(AppMsg::arbitrary(rng)),
+            5 => Messages::Post(AppMsg::arbitrary(rng)),
+            6 => Messages::Read(AppMsg::arbitrary(rng)),
+            7 => Messages::Decline(AppMsg::arbitrary(rng)),
+            8 => Messages::Accept(AppMsg::arbitrary(rng)),
+            9 => Messages::AnnounceContainer(AppMsg::arbitrary(rng)),
+            10 => Messages::PullContainer(AppMsg::arbitrary(rng)),
+            11 => Messages::PushContainer(AppMsg::arbitrary(rng)),
+            12 => Messages::Reject(AppMsg::arbitrary(rng)),
+            _ if rng.gen() => Messages::PullChunk(ChunkPull::arbitrary(rng)),
+            _ => Messages::PushChunk(ChunkPush::arbitrary(rng)),
+        }
+    }
+}
+
+impl<T: Arbitrary + StrictEncode + StrictDecode> Arbitrary for AddressedMsg<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        AddressedMsg {
+            remote_id: NodeId::arbitrary(rng),
+            data: T::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for BusFeatures {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        BusFeatures {
+            compression: rng.gen(),
+            dictionaries: rng.gen(),
+        }
+    }
+}
+
+impl Arbitrary for Dictionary {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { Dictionary::with(&Vec::arbitrary(rng)) }
+}
+
+impl Arbitrary for ItemStatus {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen_range(0..3) {
+            0 => ItemStatus::Done,
+            1 => ItemStatus::Unchanged,
+            _ => ItemStatus::Failed(String::arbitrary(rng)),
+        }
+    }
+}
+
+impl Arbitrary for BatchResult {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        BatchResult {
+            applied: rng.gen(),
+            items: Vec::arbitrary(rng),
+            bytes: rng.gen(),
+        }
+    }
+}
+
+impl Arbitrary for Cursor {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { Cursor::with(&u64::arbitrary(rng)) }
+}
+
+impl Arbitrary for PageReq {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        PageReq {
+            cursor: Option::arbitrary(rng),
+            limit: rng.gen(),
+        }
+    }
+}
+
+impl<T: Arbitrary + StrictEncode + StrictDecode> Arbitrary for Page<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Page {
+            items: Vec::arbitrary(rng),
+            next: Option::arbitrary(rng),
+        }
+    }
+}
+
+impl<T: Arbitrary + StrictEncode + StrictDecode> Arbitrary for Paged<T> {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Paged {
+            query: T::arbitrary(rng),
+            page: PageReq::arbitrary(rng),
+        }
+    }
+}
+
+impl Arbitrary for FaultTarget {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        FaultTarget::ALL[rng.gen_range(0..FaultTarget::ALL.len())]
+    }
+}
+
+impl Arbitrary for Fault {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Fault {
+            target: FaultTarget::arbitrary(rng),
+            rate: rng.gen_range(0..=1000),
+            max_delay_ms: rng.gen(),
+        }
+    }
+}
+
+/// Generates RPC requests and replies which payloads have generators; the requests are never
+/// nested into more than one [`Idempotent`] wrapper.
+fn arbitrary_rpc<R: Rng + ?Sized>(rng: &mut R, nested: bool) -> RpcMsg {
+    match rng.gen_range(0..48) {
+        0 => RpcMsg::Negotiate(BusFeatures::arbitrary(rng)),
+        1 => RpcMsg::ChatUnblock(NodeId::arbitrary(rng)),
+        2 => RpcMsg::SendChat(AddressedMsg::arbitrary(rng)),
+        3 => RpcMsg::SetChatDisappear(AddressedMsg::arbitrary(rng)),
+        4 => RpcMsg::VerifyChatContact(String::arbitrary(rng)),
+        5 => RpcMsg::ChatBlock(NodeId::arbitrary(rng)),
+        6 => RpcMsg::ChatSaveDraft(AddressedMsg::arbitrary(rng)),
+        7 => RpcMsg::ChatSearch(Paged::arbitrary(rng)),
+        8 => RpcMsg::SubscribeBoard(AddressedMsg::arbitrary(rng)),
+        9 => RpcMsg::ReadBoard(Paged::arbitrary(rng)),
+        10 => RpcMsg::ListJobs(PageReq::arbitrary(rng)),
+        11 => RpcMsg::ListSessions(PageReq::arbitrary(rng)),
+        12 => RpcMsg::GetInfo,
+        13 => RpcMsg::ListDaemons(PageReq::arbitrary(rng)),
+        14 => RpcMsg::GetContainerStats(PageReq::arbitrary(rng)),
+        15 => RpcMsg::GetAttestation(NodeId::arbitrary(rng)),
+        16 => RpcMsg::GetTransferProgress(ContainerId::arbitrary(rng)),
+        17 => RpcMsg::GetReplication(ContainerId::arbitrary(rng)),
+        18 => RpcMsg::GetHealth,
+        19 => RpcMsg::CreateBackup,
+        20 => RpcMsg::FlagPeer(NodeId::arbitrary(rng)),
+        21 => RpcMsg::ListQuarantine(PageReq::arbitrary(rng)),
+        22 => RpcMsg::ReleaseContainer(ContainerId::arbitrary(rng)),
+        23 => RpcMsg::BanPeer(NodeId::arbitrary(rng)),
+        24 => RpcMsg::GetMemory,
+        25 => RpcMsg::GetFaults,
+        26 => RpcMsg::SetFault(Fault::arbitrary(rng)),
+        27 => RpcMsg::Materialize(ContainerId::arbitrary(rng)),
+        28 => RpcMsg::GetPeerStats(PageReq::arbitrary(rng)),
+        29 => RpcMsg::Unsubscribe,
+        30 => RpcMsg::ListPeerApps(Paged::arbitrary(rng)),
+        31 => RpcMsg::ListPeerTopics(Paged::arbitrary(rng)),
+        32 => RpcMsg::PinMany(Vec::arbitrary(rng)),
+        33 => RpcMsg::CollectGarbage(rng.gen()),
+        34 => RpcMsg::EnterMaintenance(rng.gen()),
+        35 => RpcMsg::RevokeShare(rng.gen()),
+        36 => RpcMsg::ListAudit(PageReq::arbitrary(rng)),
+        37 => RpcMsg::ListShares(PageReq::arbitrary(rng)),
+        38 => RpcMsg::PeerApps(Page::arbitrary(rng)),
+        39 => RpcMsg::PeerTopics(Page::arbitrary(rng)),
+        40 => RpcMsg::BatchResult(BatchResult::arbitrary(rng)),
+        41 => RpcMsg::Bans(BTreeSet::arbitrary(rng)),
+        42 => RpcMsg::Faults(Vec::arbitrary(rng)),
+        43 => RpcMsg::ChatSafety(String::arbitrary(rng)),
+        44 => RpcMsg::ChatBlocked(BTreeSet::arbitrary(rng)),
+        45 => RpcMsg::ChatDraft(Option::arbitrary(rng)),
+        46 if !nested => RpcMsg::Idempotent(Idempotent {
+            request_id: rng.gen(),
+            request: Box::new(arbitrary_rpc(rng, true)),
+        }),
+        _ => RpcMsg::Progress(String::arbitrary(rng)),
+    }
+}
+
+impl Arbitrary for RpcMsg {
+    fn arbitrary<R: Rng + ?Sized>(rng: &mut R) -> Self { arbitrary_rpc(rng, false) }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const ITERATIONS: usize = 32;
+
+    proptest! {
+        #[test]
+        fn rpc_roundtrips(seed in any::<u64>()) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            check_roundtrips::<RpcMsg, _>(&mut rng, ITERATIONS)
+                .map_err(|err| TestCaseError::fail(err.to_string()))?;
+        }
+
+        #[test]
+        fn p2p_roundtrips(seed in any::<u64>()) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            check_p2p_roundtrips(&mut rng, ITERATIONS)
+                .map_err(|err| TestCaseError::fail(err.to_string()))?;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+use internet2::addr::NodeId;
+use secp256k1::hashes::sha256;
+use secp256k1::{ecdsa, Message, Secp256k1, SecretKey};
+use storm::StormApp;
+use strict_encoding::StrictEncode;
+
+/// Statement of a node about the software it runs.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Attestation {
+    /// Key of the node the attestation is signed with.
+    pub signer: NodeId,
+    /// Version of the node software.
+    pub version: String,
+    /// Features the node software is compiled with.
+    pub features: BTreeSet<String>,
+    /// Apps registered with the node.
+    pub apps: BTreeSet<StormApp>,
+    /// UNIX timestamp of the attestation.
+    pub timestamp: u64,
+}
+
+/// [`Attestation`] with the signature of the node which has issued it.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct SignedAttestation {
+    pub attestation: Attestation,
+    pub signature: ecdsa::Signature,
+}
+
+/// Errors verifying [`SignedAttestation`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AttestationError {
+    /// attestation signature does not match the attestation content and the signer key
+    InvalidSignature,
+}
+
+impl Display for Attestation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "stormd {} at {}, signed by {}", self.version, self.timestamp, self.signer)?;
+        if !self.features.is_empty() {
+            let features = self.features.iter().cloned().collect::<Vec<_>>();
+            write!(f, ", features: {}", features.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Attestation {
+    fn message(&self) -> Message {
+        let data = self.strict_serialize().expect("in-memory attestation encoding");
+        Message::from_hashed_data::<sha256::Hash>(&data)
+    }
+
+    /// Signs the attestation with the secret key of [`Attestation::signer`].
+    pub fn sign(self, secret_key: &SecretKey) -> SignedAttestation {
+        let signature = Secp256k1::signing_only().sign_ecdsa(&self.message(), secret_key);
+        SignedAttestation {
+            attestation: self,
+            signature,
+        }
+    }
+
+    /// Checks that the attested version is not below the given one. Versions are compared by
+    /// their dot-separated numeric components, ignoring pre-release suffixes.
+    pub fn has_version(&self, min_version: &str) -> bool {
+        fn parse(version: &str) -> Vec<u64> {
+            let mut parts = version
+                .split(['-', '+'])
+                .next()
+                .unwrap_or_default()
+                .split('.')
+                .map(|part| part.parse().unwrap_or_default())
+                .collect::<Vec<_>>();
+            while parts.last() == Some(&0) {
+                parts.pop();
+            }
+            parts
+        }
+        parse(&self.version) >= parse(min_version)
+    }
+}
+
+impl SignedAttestation {
+    pub fn verify(&self) -> Result<(), AttestationError> {
+        Secp256k1::verification_only()
+            .verify_ecdsa(
+                &self.attestation.message(),
+                &self.signature,
+                &self.attestation.signer.public_key(),
+            )
+            .map_err(|_| AttestationError::InvalidSignature)
+    }
+}
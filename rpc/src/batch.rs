@@ -0,0 +1,73 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+
+use storm::ContainerId;
+
+/// Result of processing a single item of a batch request.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum ItemStatus {
+    /// the item was processed
+    #[display(doc_comments)]
+    Done,
+
+    /// the item required no changes
+    #[display(doc_comments)]
+    Unchanged,
+
+    /// the item has failed: {0}
+    #[display(doc_comments)]
+    Failed(String),
+}
+
+/// Batch request changing the containers.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ContainerBatch {
+    pub container_ids: Vec<ContainerId>,
+    /// Whether the node only reports the containers which would be changed, without applying
+    /// the batch.
+    pub dry_run: bool,
+}
+
+/// Per-item results of a batch request.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct BatchResult {
+    /// Whether the changes were applied. Transactional batches are applied only if none of the
+    /// items has failed.
+    pub applied: bool,
+    /// Statuses of the items, in the order of the request.
+    pub items: Vec<ItemStatus>,
+    /// Total size of the containers changed by the batch, in bytes; zero for the batches not
+    /// changing containers.
+    pub bytes: u64,
+}
+
+impl BatchResult {
+    pub fn failed(&self) -> usize {
+        self.items.iter().filter(|status| matches!(status, ItemStatus::Failed(_))).count()
+    }
+}
+
+impl Display for BatchResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} items, {} failed", self.items.len(), self.failed())?;
+        if self.bytes > 0 {
+            write!(f, ", {} bytes", self.bytes)?;
+        }
+        if !self.applied {
+            f.write_str(", not applied")?;
+        }
+        Ok(())
+    }
+}
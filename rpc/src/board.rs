@@ -0,0 +1,235 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use internet2::addr::NodeId;
+use secp256k1::hashes::sha256;
+use secp256k1::{ecdsa, Message, PublicKey, Secp256k1, SecretKey};
+use storm::{Mesg, MesgId};
+use strict_encoding::StrictEncode;
+
+use crate::topic::mesg_id_from_str;
+use crate::TopicId;
+
+/// Metadata of a public board. It is kept as the body of the board topic, so the board id
+/// commits to the set of the board moderators.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{name}")]
+pub struct BoardInfo {
+    pub name: String,
+    /// Keys which may pin and remove the board posts.
+    pub moderators: BTreeSet<NodeId>,
+}
+
+/// Identifier of a board post, committing to the board and to the post author, timestamp and
+/// text. It is computed as the id of a message to the board topic with the post content.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(inner)]
+pub struct PostId(MesgId);
+
+impl From<&Mesg> for PostId {
+    fn from(mesg: &Mesg) -> Self { PostId(mesg.mesg_id()) }
+}
+
+impl FromStr for PostId {
+    type Err = strict_encoding::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { mesg_id_from_str(s).map(PostId) }
+}
+
+/// Post on a public board.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct BoardPost {
+    pub id: PostId,
+    /// Node which published the post, as claimed by the post itself.
+    pub author: NodeId,
+    /// UNIX timestamp of the moment the post was published.
+    pub timestamp: u64,
+    pub text: String,
+    pub pinned: bool,
+    /// Whether the post was removed by a moderator. Text of the removed posts is erased, but the
+    /// posts are kept in the history so they are not restored by the peers which still have
+    /// them.
+    pub removed: bool,
+}
+
+impl Display for BoardPost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let pin = if self.pinned { '*' } else { ' ' };
+        write!(f, "{}{} {} {}\n  {}", pin, self.id, self.timestamp, self.author, self.text)
+    }
+}
+
+/// Information about a board known to the node.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct BoardSummary {
+    pub id: TopicId,
+    pub info: BoardInfo,
+    /// Peer the node is subscribed to the board through; `None` for the boards created by the
+    /// node.
+    pub upstream: Option<NodeId>,
+    /// Number of the peers subscribed to the board through this node.
+    pub subscribers: u32,
+    /// Number of the posts which are not removed.
+    pub posts: u32,
+}
+
+impl Display for BoardSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {} posts, {} subscribers",
+            self.id, self.info, self.posts, self.subscribers
+        )?;
+        if let Some(upstream) = self.upstream {
+            write!(f, ", via {}", upstream)?;
+        }
+        Ok(())
+    }
+}
+
+/// Request to publish a post on a board.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("post to {board}")]
+pub struct BoardPostRequest {
+    pub board: TopicId,
+    pub author: NodeId,
+    pub text: String,
+}
+
+/// Action of a board moderator on a post.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum BoardAction {
+    #[display("pin")]
+    Pin,
+
+    #[display("unpin")]
+    Unpin,
+
+    #[display("remove")]
+    Remove,
+}
+
+/// Error parsing [`BoardAction`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownBoardAction(String);
+
+impl Display for UnknownBoardAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown board action `{}`; allowed values are `pin`, `unpin` and `remove`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for BoardAction {
+    type Err = UnknownBoardAction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pin" => Ok(BoardAction::Pin),
+            "unpin" => Ok(BoardAction::Unpin),
+            "remove" => Ok(BoardAction::Remove),
+            other => Err(UnknownBoardAction(other.to_owned())),
+        }
+    }
+}
+
+/// Moderator action on a board post.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{action} {post_id} on {board}")]
+pub struct BoardModeration {
+    pub board: TopicId,
+    pub post_id: PostId,
+    /// Key of the moderator the action is signed with.
+    pub moderator: NodeId,
+    pub action: BoardAction,
+    /// UNIX timestamp of the action; the actions older than the last one applied to the post are
+    /// ignored.
+    pub timestamp: u64,
+}
+
+/// [`BoardModeration`] with the signature of the moderator.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{moderation}")]
+pub struct SignedModeration {
+    pub moderation: BoardModeration,
+    pub signature: ecdsa::Signature,
+}
+
+/// Errors verifying [`SignedModeration`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ModerationError {
+    /// moderation signature does not match the moderator action and the moderator key
+    InvalidSignature,
+}
+
+impl BoardModeration {
+    /// Constructs moderator action of the key, taken at the current time.
+    pub fn with(
+        board: TopicId,
+        post_id: PostId,
+        action: BoardAction,
+        secret_key: &SecretKey,
+    ) -> Self {
+        let moderator = PublicKey::from_secret_key(&Secp256k1::signing_only(), secret_key);
+        BoardModeration {
+            board,
+            post_id,
+            moderator: NodeId::from(moderator),
+            action,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn message(&self) -> Message {
+        let data = self.strict_serialize().expect("in-memory moderation encoding");
+        Message::from_hashed_data::<sha256::Hash>(&data)
+    }
+
+    /// Signs the action with the secret key of [`BoardModeration::moderator`].
+    pub fn sign(self, secret_key: &SecretKey) -> SignedModeration {
+        let signature = Secp256k1::signing_only().sign_ecdsa(&self.message(), secret_key);
+        SignedModeration {
+            moderation: self,
+            signature,
+        }
+    }
+}
+
+impl SignedModeration {
+    pub fn verify(&self) -> Result<(), ModerationError> {
+        Secp256k1::verification_only()
+            .verify_ecdsa(
+                &self.moderation.message(),
+                &self.signature,
+                &self.moderation.moderator.public_key(),
+            )
+            .map_err(|_| ModerationError::InvalidSignature)
+    }
+}
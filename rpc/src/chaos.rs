@@ -0,0 +1,91 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Subsystem of the node into which the chaos testing hooks inject faults.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum FaultTarget {
+    /// Messages handled by the node on its service buses are delayed.
+    #[display("bus-delay")]
+    BusDelay,
+
+    /// Chunks received from the remote peers are dropped.
+    #[display("chunk-drop")]
+    ChunkDrop,
+
+    /// Syncs of the node journals and checkpoints to the disk fail.
+    #[display("fsync-failure")]
+    FsyncFailure,
+}
+
+impl FaultTarget {
+    pub const ALL: [FaultTarget; 3] =
+        [FaultTarget::BusDelay, FaultTarget::ChunkDrop, FaultTarget::FsyncFailure];
+}
+
+/// Error parsing [`FaultTarget`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownFaultTarget(String);
+
+impl Display for UnknownFaultTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown fault target `{}`; allowed values are `bus-delay`, `chunk-drop` and \
+             `fsync-failure`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for FaultTarget {
+    type Err = UnknownFaultTarget;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bus-delay" => Ok(FaultTarget::BusDelay),
+            "chunk-drop" => Ok(FaultTarget::ChunkDrop),
+            "fsync-failure" => Ok(FaultTarget::FsyncFailure),
+            other => Err(UnknownFaultTarget(other.to_owned())),
+        }
+    }
+}
+
+/// Fault injected into a subsystem of the node.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Fault {
+    pub target: FaultTarget,
+    /// Probability of the fault, in per-mille; zero disables the fault
+    pub rate: u16,
+    /// Maximal delay of a message, in milliseconds; used by [`FaultTarget::BusDelay`] only
+    pub max_delay_ms: u32,
+}
+
+impl Fault {
+    pub fn is_enabled(&self) -> bool { self.rate > 0 }
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.target {
+            _ if !self.is_enabled() => write!(f, "{}: disabled", self.target),
+            FaultTarget::BusDelay => write!(
+                f,
+                "{}: {} per mille, up to {} ms",
+                self.target, self.rate, self.max_delay_ms
+            ),
+            _ => write!(f, "{}: {} per mille", self.target, self.rate),
+        }
+    }
+}
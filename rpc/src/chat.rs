@@ -0,0 +1,179 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+
+use internet2::addr::NodeId;
+
+use crate::{PageReq, TopicId};
+
+/// Notification preference of a chat conversation.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum NotifyPref {
+    /// Incoming messages are published to the chat subscribers.
+    #[display("notify")]
+    Notify,
+
+    /// Incoming messages are not published to the chat subscribers.
+    #[display("mute")]
+    Mute,
+}
+
+impl Default for NotifyPref {
+    fn default() -> Self { NotifyPref::Notify }
+}
+
+/// Message of a chat conversation kept in the chat history.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ChatEntry {
+    /// UNIX timestamp of the moment the message was sent or received.
+    pub timestamp: u64,
+    /// Whether the message was received from the remote peer.
+    pub incoming: bool,
+    pub text: String,
+    /// Previews of the links mentioned in the message, attached by the sender.
+    pub previews: Vec<LinkPreview>,
+    /// UNIX timestamp after which the message disappears from the history, if the conversation
+    /// has disappearing messages turned on.
+    pub expires: Option<u64>,
+}
+
+impl Display for ChatEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let dir = if self.incoming { '>' } else { '<' };
+        write!(f, "{} {} {}", self.timestamp, dir, self.text)
+    }
+}
+
+/// Request for the messages of a chat conversation, used by the clients to backfill the
+/// conversation after reconnecting.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ChatHistoryReq {
+    pub peer: NodeId,
+    /// Topic of the conversation; if not given, the direct conversation with the peer is used.
+    pub topic: Option<TopicId>,
+    /// UNIX timestamp of the earliest message to return.
+    pub from: u64,
+    pub page: PageReq,
+}
+
+impl Display for ChatHistoryReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.peer)?;
+        if let Some(topic) = self.topic {
+            write!(f, "/{}", topic)?;
+        }
+        write!(f, ", from {}, {}", self.from, self.page)
+    }
+}
+
+/// Metadata of a web page linked from a chat message, which is rendered by the receiving clients
+/// without fetching the page themselves.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{title} <{url}>")]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// URL of the page preview image.
+    pub image: Option<String>,
+}
+
+/// Chat message text together with the previews of the links it mentions.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{text}")]
+pub struct PreviewedChat {
+    pub text: String,
+    pub previews: Vec<LinkPreview>,
+}
+
+/// Message of a chat conversation matching the search query.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ChatMatch {
+    pub entry: ChatEntry,
+    /// Byte offsets of the query matches inside the message text.
+    pub offsets: Vec<u32>,
+    /// Byte length of each of the matches.
+    pub len: u32,
+}
+
+impl ChatMatch {
+    /// Returns message text with the matches enclosed into the given markers.
+    pub fn highlight(&self, open: &str, close: &str) -> String {
+        let text = &self.entry.text;
+        let mut highlighted = String::with_capacity(text.len());
+        let mut pos = 0usize;
+        for offset in &self.offsets {
+            let start = *offset as usize;
+            let end = start + self.len as usize;
+            highlighted.push_str(&text[pos..start]);
+            highlighted.push_str(open);
+            highlighted.push_str(&text[start..end]);
+            highlighted.push_str(close);
+            pos = end;
+        }
+        highlighted.push_str(&text[pos..]);
+        highlighted
+    }
+}
+
+/// Chat contact binding a name known to the user to the peer key.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ChatContact {
+    pub name: String,
+    pub remote_id: NodeId,
+    /// Whether the user has confirmed the contact key by comparing the safety numbers. The flag
+    /// is reset when the contact name is bound to another key.
+    pub verified: bool,
+}
+
+impl Display for ChatContact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.remote_id)?;
+        if self.verified {
+            f.write_str(" (verified)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Change of the key bound to a chat contact.
+///
+/// The old and the new key are the same if the peer has changed its chat encryption key; the
+/// name is then the peer node id if the peer is not a contact.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct KeyChange {
+    pub name: String,
+    pub old: NodeId,
+    pub new: NodeId,
+}
+
+impl KeyChange {
+    /// Returns whether the change is the change of the peer chat encryption key.
+    pub fn is_chat_key(&self) -> bool { self.old == self.new }
+}
+
+impl Display for KeyChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_chat_key() {
+            write!(f, "{}: chat key of {} has changed", self.name, self.new)
+        } else {
+            write!(f, "{}: {} -> {}", self.name, self.old, self.new)
+        }
+    }
+}
@@ -8,17 +8,32 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Deref;
 use std::thread::sleep;
 use std::time::Duration;
 
 use internet2::addr::{NodeId, ServiceAddr};
-use internet2::ZmqSocketType;
+use internet2::{presentation, CreateUnmarshaller, TypedEnum, Unmarshall, ZmqSocketType};
 use microservices::esb::{self, BusId, ClientId, PollItem};
 use microservices::util::OptionDetails;
 use storm::{ContainerFullId, ContainerId, StormApp};
 
 use crate::messages::RadioMsg;
-use crate::{AddressedMsg, AppContainer, BusMsg, Error, RpcMsg, ServiceId};
+use crate::{
+    AddressedMsg, AdminCommand, AdminOutcome, AppContainer, AppUsage, Approval, ApprovalOutcome,
+    AuditRecord, BackupInfo, BatchResult, BoardInfo, BoardPost, BoardPostRequest, BoardSummary,
+    BridgeMsg, BusFeatures, BusMsg, ChatContact, ChatEntry, ChatHistoryReq, ChatMatch, Compressed,
+    ContainerBatch, ContainerEntry, ContainerStats, ContainerVersion, DaemonInfo, Error, Event,
+    EventFilter, EventKind, Fault, FaultTarget, FolderChange, GcReport, Health, Idempotent,
+    Interest, JobId, JobInfo, JobKind, JobRequest, LimitScope, ListQuery, MailEntry, MailFolder,
+    MailId, MailMove, MailQuery, MailRequest, ManifestInfo, MemoryReport, NodeFeatures, NodeInfo,
+    NodeStats, NotifyPref, Page, PageReq, Paged, PeerStats, PolledEvents, PostId, PreviewedChat,
+    ProposalInfo, QuarantinedContainer, RateLimit, RateLimitUpdate, RateLimits, ReplicateRequest,
+    ReplicationStatus, RequestId, RpcMsg, ServiceId, SessionInfo, SessionToken, ShareInfo,
+    ShareRequest, ShareToken, SignedAttestation, SignedModeration, TopicId, TransferEntry,
+    TransferProgress, WaitEvent, COMPRESSION_THRESHOLD,
+};
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
 enum Bus {
@@ -29,6 +44,10 @@ enum Bus {
     /// Pub/sub bus used for chat daemon
     #[display("CHAT")]
     Chat,
+
+    /// Pub/sub bus with the node event notifications
+    #[display("NOTIFY")]
+    Notify,
 }
 
 impl BusId for Bus {
@@ -41,6 +60,9 @@ type Endpoints = esb::EndpointList<Bus>;
 pub struct Client {
     client_id: ClientId,
     user_agent: String,
+    features: BusFeatures,
+    request_id: Option<RequestId>,
+    events: BTreeSet<EventKind>,
     response_queue: Vec<PollItem<Bus, BusMsg>>,
     esb: esb::Controller<Bus, BusMsg, Handler>,
 }
@@ -76,26 +98,62 @@ impl Client {
         // We have to sleep in order for ZMQ to bootstrap
         sleep(Duration::from_secs_f32(0.1));
 
-        Ok(Self {
+        let mut client = Self {
             client_id,
             user_agent,
+            features: none!(),
+            request_id: None,
+            events: empty!(),
             response_queue: empty!(),
             esb,
-        })
+        };
+        // The reply is processed together with the first response from the node
+        client.request(RpcMsg::Negotiate(BusFeatures::supported()), ServiceId::stormd())?;
+        Ok(client)
     }
 
     pub fn client_id(&self) -> ClientId { self.client_id }
 
+    /// Sets request id used for the next mutating request. Retrying the request with the same id
+    /// guarantees that the node does not execute it twice. If no id is set, a random one is used.
+    pub fn set_request_id(&mut self, request_id: RequestId) { self.request_id = Some(request_id) }
+
+    fn idempotent(&mut self, request: RpcMsg) -> RpcMsg {
+        RpcMsg::Idempotent(Idempotent {
+            request_id: self.request_id.take().unwrap_or_else(rand::random),
+            request: Box::new(request),
+        })
+    }
+
     fn request(&mut self, req: impl Into<RpcMsg>, service_id: ServiceId) -> Result<(), Error> {
         let req = req.into();
         debug!("Executing {}", req);
-        self.esb.send_to(Bus::Rpc, service_id, BusMsg::Rpc(req))?;
+        let mut msg = BusMsg::Rpc(req);
+        if self.features.compression && service_id == ServiceId::stormd() {
+            let data = msg.serialize();
+            if data.len() > COMPRESSION_THRESHOLD {
+                msg = BusMsg::Compressed(
+                    Compressed::deflate(&data).map_err(presentation::Error::from)?,
+                );
+            }
+        }
+        self.esb.send_to(Bus::Rpc, service_id, msg)?;
         Ok(())
     }
 
     fn response(&mut self) -> Result<PollItem<Bus, BusMsg>, Error> {
         loop {
-            if let Some(poll) = self.response_queue.pop() {
+            if let Some(mut poll) = self.response_queue.pop() {
+                if let BusMsg::Compressed(compressed) = poll.request {
+                    let data = compressed.inflate().map_err(presentation::Error::from)?;
+                    poll.request =
+                        BusMsg::create_unmarshaller().unmarshall(&*data)?.deref().clone();
+                }
+                if let BusMsg::Rpc(RpcMsg::Negotiate(features)) = poll.request {
+                    debug!("Negotiated bus features: {}", features);
+                    self.features = features;
+                    continue;
+                }
                 trace!("Got response {} from {} via {}", poll.request, poll.source, poll.bus_id);
                 return Ok(poll);
             } else {
@@ -106,6 +164,18 @@ impl Client {
         }
     }
 
+    fn rpc_request(
+        &mut self,
+        request: impl Into<RpcMsg>,
+        service_id: ServiceId,
+    ) -> Result<RpcMsg, Error> {
+        self.request(request, service_id)?;
+        match self.response()?.request {
+            BusMsg::Rpc(rpc) => rpc.failure_to_error(),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
     fn progressive_request(
         &mut self,
         request: impl Into<RpcMsg>,
@@ -148,18 +218,326 @@ impl Client {
         )
     }
 
-    pub fn chat_recv(&mut self, from_remote_id: NodeId) -> Result<String, Error> {
-        let poll = self.response()?;
-        match poll.request {
-            BusMsg::Chat(RadioMsg::Received(AddressedMsg { remote_id, data }))
-                if remote_id == from_remote_id =>
-            {
-                Ok(data)
-            }
+    pub fn chat_previewed(&mut self, remote_id: NodeId, chat: PreviewedChat) -> Result<(), Error> {
+        self.request(
+            RpcMsg::ChatPreviewed(AddressedMsg {
+                remote_id,
+                data: chat,
+            }),
+            ServiceId::chatd(),
+        )
+    }
+
+    pub fn set_chat_notify(&mut self, remote_id: NodeId, pref: NotifyPref) -> Result<(), Error> {
+        let req = RpcMsg::SetChatNotify(AddressedMsg {
+            remote_id,
+            data: pref,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn set_chat_disappear(&mut self, remote_id: NodeId, after: u64) -> Result<(), Error> {
+        let req = RpcMsg::SetChatDisappear(AddressedMsg {
+            remote_id,
+            data: after,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn set_chat_contact(&mut self, name: String, remote_id: NodeId) -> Result<(), Error> {
+        let req = RpcMsg::SetChatContact(AddressedMsg {
+            remote_id,
+            data: name,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn verify_chat_contact(&mut self, name: String) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::VerifyChatContact(name), ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_contacts(&mut self) -> Result<Vec<ChatContact>, Error> {
+        match self.rpc_request(RpcMsg::ListChatContacts, ServiceId::chatd())? {
+            RpcMsg::ChatContacts(contacts) => Ok(contacts),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_safety(&mut self, local_id: NodeId, remote_id: NodeId) -> Result<String, Error> {
+        let req = RpcMsg::GetChatSafety(AddressedMsg {
+            remote_id,
+            data: local_id,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::ChatSafety(code) => Ok(code),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_block(&mut self, remote_id: NodeId, blocked: bool) -> Result<(), Error> {
+        let req =
+            if blocked { RpcMsg::ChatBlock(remote_id) } else { RpcMsg::ChatUnblock(remote_id) };
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Passes message published to an external broker to the bridge daemon for posting it to the
+    /// Storm network.
+    pub fn bridge_inbound(&mut self, bridge: ServiceId, msg: BridgeMsg) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::BridgeInbound(msg), bridge)? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn folder_changed(&mut self, change: FolderChange) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::FolderChanged(change), ServiceId::syncd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_blocked(&mut self) -> Result<BTreeSet<NodeId>, Error> {
+        match self.rpc_request(RpcMsg::ListChatBlocked, ServiceId::chatd())? {
+            RpcMsg::ChatBlocked(blocked) => Ok(blocked),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_notify(&mut self) -> Result<BTreeMap<NodeId, NotifyPref>, Error> {
+        match self.rpc_request(RpcMsg::ListChatNotify, ServiceId::chatd())? {
+            RpcMsg::ChatNotify(prefs) => Ok(prefs),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_save_draft(&mut self, remote_id: NodeId, draft: String) -> Result<(), Error> {
+        let req = RpcMsg::ChatSaveDraft(AddressedMsg {
+            remote_id,
+            data: draft,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_draft(&mut self, remote_id: NodeId) -> Result<Option<String>, Error> {
+        match self.rpc_request(RpcMsg::ChatGetDraft(remote_id), ServiceId::chatd())? {
+            RpcMsg::ChatDraft(draft) => Ok(draft),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_history(&mut self, req: ChatHistoryReq) -> Result<Page<ChatEntry>, Error> {
+        match self.rpc_request(RpcMsg::ChatHistory(req), ServiceId::chatd())? {
+            RpcMsg::ChatEntries(entries) => Ok(entries),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn chat_search(
+        &mut self,
+        remote_id: NodeId,
+        query: String,
+        page: PageReq,
+    ) -> Result<Page<ChatMatch>, Error> {
+        let req = RpcMsg::ChatSearch(Paged {
+            query: AddressedMsg {
+                remote_id,
+                data: query,
+            },
+            page,
+        });
+        match self.rpc_request(req, ServiceId::chatd())? {
+            RpcMsg::ChatMatches(matches) => Ok(matches),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn send_mail(&mut self, request: MailRequest) -> Result<MailId, Error> {
+        match self.rpc_request(RpcMsg::SendMail(request), ServiceId::maild())? {
+            RpcMsg::MailSent(id) => Ok(id),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_mail(&mut self, query: MailQuery) -> Result<Page<MailEntry>, Error> {
+        match self.rpc_request(RpcMsg::ListMail(query), ServiceId::maild())? {
+            RpcMsg::MailList(mails) => Ok(mails),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn read_mail(&mut self, id: MailId) -> Result<MailEntry, Error> {
+        match self.rpc_request(RpcMsg::ReadMail(id), ServiceId::maild())? {
+            RpcMsg::MailRead(entry) => Ok(entry),
             _ => Err(Error::UnexpectedServerResponse),
         }
     }
 
+    pub fn move_mail(&mut self, id: MailId, folder: MailFolder) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::MoveMail(MailMove { id, folder }), ServiceId::maild())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn delete_mail(&mut self, id: MailId) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::DeleteMail(id), ServiceId::maild())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn create_board(&mut self, info: BoardInfo) -> Result<TopicId, Error> {
+        match self.rpc_request(RpcMsg::CreateBoard(info), ServiceId::boardd())? {
+            RpcMsg::BoardCreated(board) => Ok(board),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn subscribe_board(&mut self, remote_id: NodeId, board: TopicId) -> Result<(), Error> {
+        let req = RpcMsg::SubscribeBoard(AddressedMsg {
+            remote_id,
+            data: board,
+        });
+        match self.rpc_request(req, ServiceId::boardd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn unsubscribe_board(&mut self, board: TopicId) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::UnsubscribeBoard(board), ServiceId::boardd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_boards(&mut self, page: PageReq) -> Result<Page<BoardSummary>, Error> {
+        match self.rpc_request(RpcMsg::ListBoards(page), ServiceId::boardd())? {
+            RpcMsg::Boards(boards) => Ok(boards),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn read_board(&mut self, req: Paged<TopicId>) -> Result<Page<BoardPost>, Error> {
+        match self.rpc_request(RpcMsg::ReadBoard(req), ServiceId::boardd())? {
+            RpcMsg::BoardPosts(posts) => Ok(posts),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn post_board(&mut self, request: BoardPostRequest) -> Result<PostId, Error> {
+        match self.rpc_request(RpcMsg::PostBoard(request), ServiceId::boardd())? {
+            RpcMsg::BoardPosted(id) => Ok(id),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn moderate_board(&mut self, moderation: SignedModeration) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::ModerateBoard(moderation), ServiceId::boardd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Subscribes to the node events of the given kinds, which are published on the notification
+    /// endpoint of the node and are received with [`Client::recv_event`].
+    pub fn subscribe(
+        &mut self,
+        notify_endpoint: ServiceAddr,
+        events: BTreeSet<EventKind>,
+    ) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::Subscribe(events.clone()), ServiceId::stormd())? {
+            RpcMsg::Success(_) => {}
+            _ => return Err(Error::UnexpectedServerResponse),
+        }
+        debug!("Subscribing to node events via {}", notify_endpoint);
+        self.esb.add_service_bus(
+            Bus::Notify,
+            esb::BusConfig::with_subscription(notify_endpoint, ZmqSocketType::Sub, None),
+        )?;
+        // We have to sleep in order for ZMQ to bootstrap
+        sleep(Duration::from_secs_f32(0.1));
+        self.events.extend(events);
+        Ok(())
+    }
+
+    /// Subscribes the client to the node events matching the filter. Unlike [`Client::subscribe`],
+    /// the events are filtered by the node and are sent to this client only, so no notification
+    /// endpoint is needed. The events are received with [`Client::recv_event`].
+    pub fn subscribe_filtered(&mut self, filter: EventFilter) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::SubscribeFiltered(filter), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn unsubscribe(&mut self) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::Unsubscribe, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Blocks until the node publishes an event matching the request filter or the request times
+    /// out. Unlike the subscriptions, requires neither a notification endpoint nor a loop
+    /// receiving the events; the returned cursor is passed with the next request so no events are
+    /// missed in between.
+    pub fn wait_event(&mut self, request: WaitEvent) -> Result<PolledEvents, Error> {
+        match self.rpc_request(RpcMsg::WaitEvent(request), ServiceId::stormd())? {
+            RpcMsg::PolledEvents(polled) => Ok(polled),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Waits for the next node event the client has subscribed to.
+    pub fn recv_event(&mut self) -> Result<Event, Error> {
+        loop {
+            let poll = self.response()?;
+            match poll.request {
+                // Events sent over the RPC bus are already filtered by the node
+                BusMsg::Notify(event) if poll.bus_id == Bus::Rpc => return Ok(event),
+                BusMsg::Notify(event) if self.events.contains(&event.kind()) => return Ok(event),
+                BusMsg::Notify(_) | BusMsg::Chat(_) => {}
+                _ => return Err(Error::UnexpectedServerResponse),
+            }
+        }
+    }
+
+    pub fn chat_recv(&mut self, from_remote_id: NodeId) -> Result<ChatEntry, Error> {
+        loop {
+            let poll = self.response()?;
+            match poll.request {
+                BusMsg::Chat(RadioMsg::Received(AddressedMsg { remote_id, data }))
+                    if remote_id == from_remote_id =>
+                {
+                    return Ok(data)
+                }
+                BusMsg::Chat(RadioMsg::KeyChanged(change)) => {
+                    warn!("Chat contact key change {}", change);
+                }
+                _ => return Err(Error::UnexpectedServerResponse),
+            }
+        }
+    }
+
     pub fn upload(
         &mut self,
         remote_id: NodeId,
@@ -177,8 +555,9 @@ impl Client {
                 },
             },
         };
+        let request = self.idempotent(RpcMsg::SendContainer(msg));
         self.progressive_request(
-            RpcMsg::SendContainer(msg),
+            request,
             // TODO: Send to downpourd
             ServiceId::stormd(),
             progress,
@@ -202,13 +581,511 @@ impl Client {
                 },
             },
         };
+        let request = self.idempotent(RpcMsg::GetContainer(msg));
         self.progressive_request(
-            RpcMsg::GetContainer(msg),
+            request,
             // TODO: Send to downpourd
             ServiceId::stormd(),
             progress,
         )
     }
+
+    pub fn submit_job(
+        &mut self,
+        kind: JobKind,
+        remote_id: NodeId,
+        container_id: ContainerFullId,
+    ) -> Result<JobId, Error> {
+        let request = JobRequest {
+            kind,
+            container: AddressedMsg {
+                remote_id,
+                data: AppContainer {
+                    storm_app: StormApp::FileTransfer,
+                    container_id,
+                },
+            },
+        };
+        let request = self.idempotent(RpcMsg::SubmitJob(request));
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::Job(job_id) => Ok(job_id),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn job_status(&mut self, job_id: JobId) -> Result<JobInfo, Error> {
+        match self.rpc_request(RpcMsg::JobStatus(job_id), ServiceId::stormd())? {
+            RpcMsg::JobInfo(info) => Ok(info),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_jobs(&mut self, page: PageReq) -> Result<Page<JobInfo>, Error> {
+        match self.rpc_request(RpcMsg::ListJobs(page), ServiceId::stormd())? {
+            RpcMsg::Jobs(jobs) => Ok(jobs),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_sessions(&mut self, page: PageReq) -> Result<Page<SessionInfo>, Error> {
+        match self.rpc_request(RpcMsg::ListSessions(page), ServiceId::stormd())? {
+            RpcMsg::Sessions(sessions) => Ok(sessions),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn disconnect_session(&mut self, token: SessionToken) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::DisconnectSession(token), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn pin_many(&mut self, container_ids: Vec<ContainerId>) -> Result<BatchResult, Error> {
+        let request = self.idempotent(RpcMsg::PinMany(container_ids));
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::BatchResult(result) => Ok(result),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Removes pins from the containers. With `dry_run` set, only reports the containers which
+    /// would be unpinned.
+    pub fn unpin_many(
+        &mut self,
+        container_ids: Vec<ContainerId>,
+        dry_run: bool,
+    ) -> Result<BatchResult, Error> {
+        let batch = ContainerBatch {
+            container_ids,
+            dry_run,
+        };
+        let request = self.batch_request(RpcMsg::UnpinMany(batch), dry_run);
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::BatchResult(result) => Ok(result),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Evicts containers from the node storage. With `dry_run` set, only reports the containers
+    /// which would be evicted and their size.
+    pub fn evict_many(
+        &mut self,
+        container_ids: Vec<ContainerId>,
+        dry_run: bool,
+    ) -> Result<BatchResult, Error> {
+        let batch = ContainerBatch {
+            container_ids,
+            dry_run,
+        };
+        let request = self.batch_request(RpcMsg::EvictMany(batch), dry_run);
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::BatchResult(result) => Ok(result),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Dry runs change nothing, so they are not made idempotent.
+    fn batch_request(&mut self, request: RpcMsg, dry_run: bool) -> RpcMsg {
+        if dry_run {
+            request
+        } else {
+            self.idempotent(request)
+        }
+    }
+
+    /// Collects orphaned chunks. With `dry_run` set, only reports the chunks which would be
+    /// collected.
+    pub fn collect_garbage(&mut self, dry_run: bool) -> Result<GcReport, Error> {
+        match self.rpc_request(RpcMsg::CollectGarbage(dry_run), ServiceId::stormd())? {
+            RpcMsg::GcReport(report) => Ok(report),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Requests the node to stop. The node replies once the child daemons are asked to stop and
+    /// exits after they have persisted their state.
+    pub fn shutdown(&mut self) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::Shutdown, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Puts the node into maintenance; remote peers are told to retry their transfer requests
+    /// after the given number of seconds.
+    pub fn enter_maintenance(&mut self, retry_after: u32) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::EnterMaintenance(retry_after), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn exit_maintenance(&mut self) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::ExitMaintenance, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Replaces the key signing the manifests and the attestations of the node.
+    pub fn rotate_manifest_key(&mut self) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::RotateManifestKey, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Lists admin actions awaiting approvals of the operator keys.
+    pub fn list_proposals(&mut self, page: PageReq) -> Result<Page<ProposalInfo>, Error> {
+        match self.rpc_request(RpcMsg::ListProposals(page), ServiceId::stormd())? {
+            RpcMsg::Proposals(proposals) => Ok(proposals),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn approve(&mut self, approval: Approval) -> Result<ApprovalOutcome, Error> {
+        match self.rpc_request(RpcMsg::Approve(approval), ServiceId::stormd())? {
+            RpcMsg::ApprovalOutcome(outcome) => Ok(outcome),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_audit(&mut self, page: PageReq) -> Result<Page<AuditRecord>, Error> {
+        match self.rpc_request(RpcMsg::ListAudit(page), ServiceId::stormd())? {
+            RpcMsg::AuditLog(records) => Ok(records),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn node_info(&mut self) -> Result<NodeInfo, Error> {
+        match self.rpc_request(RpcMsg::GetInfo, ServiceId::stormd())? {
+            RpcMsg::NodeInfo(info) => Ok(info),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_daemons(&mut self, page: PageReq) -> Result<Page<DaemonInfo>, Error> {
+        match self.rpc_request(RpcMsg::ListDaemons(page), ServiceId::stormd())? {
+            RpcMsg::Daemons(daemons) => Ok(daemons),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn container_stats(&mut self, page: PageReq) -> Result<Page<ContainerStats>, Error> {
+        match self.rpc_request(RpcMsg::GetContainerStats(page), ServiceId::stormd())? {
+            RpcMsg::ContainerStats(stats) => Ok(stats),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn peer_stats(&mut self, page: PageReq) -> Result<Page<PeerStats>, Error> {
+        match self.rpc_request(RpcMsg::GetPeerStats(page), ServiceId::stormd())? {
+            RpcMsg::PeerStats(stats) => Ok(stats),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn peer_apps(&mut self, req: Paged<NodeId>) -> Result<Page<StormApp>, Error> {
+        match self.rpc_request(RpcMsg::ListPeerApps(req), ServiceId::stormd())? {
+            RpcMsg::PeerApps(apps) => Ok(apps),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn peer_topics(
+        &mut self,
+        peer: NodeId,
+        app: StormApp,
+        page: PageReq,
+    ) -> Result<Page<TopicId>, Error> {
+        let request = RpcMsg::ListPeerTopics(Paged {
+            query: AddressedMsg {
+                remote_id: peer,
+                data: app,
+            },
+            page,
+        });
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::PeerTopics(topics) => Ok(topics),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn administer(
+        &mut self,
+        peer: NodeId,
+        command: AdminCommand,
+    ) -> Result<AdminOutcome, Error> {
+        let request = RpcMsg::Administer(AddressedMsg {
+            remote_id: peer,
+            data: command,
+        });
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::AdminOutcome(outcome) => Ok(outcome),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn register_interest(&mut self, peer: NodeId, interest: Interest) -> Result<(), Error> {
+        let request = RpcMsg::RegisterInterest(AddressedMsg {
+            remote_id: peer,
+            data: interest,
+        });
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn attestation(&mut self, peer: NodeId) -> Result<Option<SignedAttestation>, Error> {
+        match self.rpc_request(RpcMsg::GetAttestation(peer), ServiceId::stormd())? {
+            RpcMsg::PeerAttestation(attestation) => Ok(attestation),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Returns features advertised by the remote peer, if the peer has sent them.
+    pub fn peer_features(&mut self, peer: NodeId) -> Result<Option<NodeFeatures>, Error> {
+        match self.rpc_request(RpcMsg::GetPeerFeatures(peer), ServiceId::stormd())? {
+            RpcMsg::PeerFeatures(features) => Ok(features),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn transfer_progress(
+        &mut self,
+        container_id: ContainerId,
+    ) -> Result<TransferProgress, Error> {
+        match self.rpc_request(RpcMsg::GetTransferProgress(container_id), ServiceId::stormd())? {
+            RpcMsg::TransferProgress(progress) => Ok(progress),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Asks the storage peers to keep replicas of the stored container, returning the initial
+    /// replication status.
+    pub fn replicate(
+        &mut self,
+        container_id: ContainerId,
+        peers: BTreeSet<NodeId>,
+        min_replicas: u8,
+    ) -> Result<ReplicationStatus, Error> {
+        let req = RpcMsg::Replicate(ReplicateRequest {
+            container_id,
+            peers,
+            min_replicas,
+        });
+        match self.rpc_request(req, ServiceId::stormd())? {
+            RpcMsg::ReplicationStatus(status) => Ok(status),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn replication(&mut self, container_id: ContainerId) -> Result<ReplicationStatus, Error> {
+        match self.rpc_request(RpcMsg::GetReplication(container_id), ServiceId::stormd())? {
+            RpcMsg::ReplicationStatus(status) => Ok(status),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn health(&mut self) -> Result<Health, Error> {
+        match self.rpc_request(RpcMsg::GetHealth, ServiceId::stormd())? {
+            RpcMsg::Health(health) => Ok(health),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn flag_peer(&mut self, peer: NodeId, flagged: bool) -> Result<(), Error> {
+        let req = if flagged { RpcMsg::FlagPeer(peer) } else { RpcMsg::UnflagPeer(peer) };
+        match self.rpc_request(req, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn ban_peer(&mut self, peer: NodeId, banned: bool) -> Result<(), Error> {
+        let req = if banned { RpcMsg::BanPeer(peer) } else { RpcMsg::UnbanPeer(peer) };
+        match self.rpc_request(req, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn bans(&mut self) -> Result<BTreeSet<NodeId>, Error> {
+        match self.rpc_request(RpcMsg::ListBans, ServiceId::stormd())? {
+            RpcMsg::Bans(banned) => Ok(banned),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn rate_limits(&mut self) -> Result<RateLimits, Error> {
+        match self.rpc_request(RpcMsg::GetRateLimits, ServiceId::stormd())? {
+            RpcMsg::RateLimits(limits) => Ok(limits),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Changes rate limit of the chunks sent to the remote peers; unlimited rate removes the
+    /// limit.
+    pub fn set_rate_limit(&mut self, scope: LimitScope, limit: RateLimit) -> Result<(), Error> {
+        let req = RpcMsg::SetRateLimit(RateLimitUpdate { scope, limit });
+        match self.rpc_request(req, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn memory(&mut self) -> Result<MemoryReport, Error> {
+        match self.rpc_request(RpcMsg::GetMemory, ServiceId::stormd())? {
+            RpcMsg::Memory(report) => Ok(report),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn faults(&mut self) -> Result<Vec<Fault>, Error> {
+        match self.rpc_request(RpcMsg::GetFaults, ServiceId::stormd())? {
+            RpcMsg::Faults(faults) => Ok(faults),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    /// Injects fault into a subsystem of the node; zero rate disables the fault.
+    pub fn set_fault(
+        &mut self,
+        target: FaultTarget,
+        rate: u16,
+        max_delay_ms: u32,
+    ) -> Result<(), Error> {
+        let req = RpcMsg::SetFault(Fault {
+            target,
+            rate,
+            max_delay_ms,
+        });
+        match self.rpc_request(req, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn quarantine(&mut self, page: PageReq) -> Result<Page<QuarantinedContainer>, Error> {
+        match self.rpc_request(RpcMsg::ListQuarantine(page), ServiceId::stormd())? {
+            RpcMsg::Quarantine(page) => Ok(page),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn release_container(&mut self, container_id: ContainerId) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::ReleaseContainer(container_id), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn materialize(&mut self, container_id: ContainerId) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::Materialize(container_id), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn declare_version(
+        &mut self,
+        container_id: ContainerId,
+        base: ContainerId,
+    ) -> Result<(), Error> {
+        let version = ContainerVersion { container_id, base };
+        match self.rpc_request(RpcMsg::DeclareVersion(version), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn app_usage(&mut self, page: PageReq) -> Result<Page<AppUsage>, Error> {
+        match self.rpc_request(RpcMsg::GetAppUsage(page), ServiceId::stormd())? {
+            RpcMsg::AppUsage(usage) => Ok(usage),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn rebuild_index(&mut self, progress: impl Fn(String)) -> Result<(), Error> {
+        self.progressive_request(RpcMsg::RebuildIndex, ServiceId::stormd(), progress)
+    }
+
+    pub fn export_manifest(&mut self) -> Result<ManifestInfo, Error> {
+        match self.rpc_request(RpcMsg::ExportManifest, ServiceId::stormd())? {
+            RpcMsg::Manifest(info) => Ok(info),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn create_backup(&mut self) -> Result<BackupInfo, Error> {
+        match self.rpc_request(RpcMsg::CreateBackup, ServiceId::stormd())? {
+            RpcMsg::Backup(info) => Ok(info),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn stats(&mut self) -> Result<NodeStats, Error> {
+        match self.rpc_request(RpcMsg::GetStats, ServiceId::stormd())? {
+            RpcMsg::Stats(stats) => Ok(stats),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_containers(&mut self, query: ListQuery) -> Result<Page<ContainerEntry>, Error> {
+        match self.rpc_request(RpcMsg::ListContainers(query), ServiceId::stormd())? {
+            RpcMsg::Containers(containers) => Ok(containers),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_transfers(&mut self, query: ListQuery) -> Result<Page<TransferEntry>, Error> {
+        match self.rpc_request(RpcMsg::ListTransfers(query), ServiceId::stormd())? {
+            RpcMsg::Transfers(transfers) => Ok(transfers),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn create_share(
+        &mut self,
+        container_id: ContainerId,
+        grantee: Option<NodeId>,
+        duration: u64,
+    ) -> Result<ShareToken, Error> {
+        let request = ShareRequest {
+            container_id,
+            grantee,
+            duration,
+        };
+        let request = self.idempotent(RpcMsg::CreateShare(request));
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::ShareToken(token) => Ok(token),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn revoke_share(&mut self, token_id: u64) -> Result<(), Error> {
+        let request = self.idempotent(RpcMsg::RevokeShare(token_id));
+        match self.rpc_request(request, ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn list_shares(&mut self, page: PageReq) -> Result<Page<ShareInfo>, Error> {
+        match self.rpc_request(RpcMsg::ListShares(page), ServiceId::stormd())? {
+            RpcMsg::Shares(shares) => Ok(shares),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
+
+    pub fn verify_share(&mut self, token: ShareToken) -> Result<(), Error> {
+        match self.rpc_request(RpcMsg::VerifyShare(token), ServiceId::stormd())? {
+            RpcMsg::Success(_) => Ok(()),
+            _ => Err(Error::UnexpectedServerResponse),
+        }
+    }
 }
 
 pub struct Handler {
@@ -239,6 +1116,6 @@ impl esb::Handler<Bus> for Handler {
         err: esb::Error<ServiceId>,
     ) -> Result<(), Self::Error> {
         // We simply propagate the error since it already has been reported
-        Err(err.into())
+        Err(err)
     }
 }
@@ -0,0 +1,272 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use deflate::write::DeflateEncoder;
+use deflate::Compression;
+use inflate::InflateStream;
+use strict_encoding::MediumVec;
+
+/// Size of the encoded bus message, in bytes, above which the message gets compressed, if
+/// compression was negotiated with the other side.
+pub const COMPRESSION_THRESHOLD: usize = 4096;
+
+/// Size of the encoded bus message, in bytes, from which the messages not exceeding
+/// [`COMPRESSION_THRESHOLD`] get compressed with the dictionary of the app, if dictionary
+/// compression was negotiated with the other side and the dictionary is known to both sides.
+pub const DICT_COMPRESSION_THRESHOLD: usize = 64;
+
+/// Maximal size of a compression dictionary, matching the window of DEFLATE back-references.
+pub const DICT_MAX_SIZE: usize = 32 * 1024;
+
+/// Maximal size of a decompressed bus message, which fits a chunk of the maximal size together
+/// with the rest of the message.
+pub const INFLATED_MAX_SIZE: usize = (1 << 24) + 64 * 1024;
+
+/// Decompresses DEFLATE stream, failing once the output exceeds the limit, so that a small
+/// compressed message can't exhaust memory of the receiving side.
+fn inflate_limited(data: &[u8], limit: usize) -> Result<Vec<u8>, strict_encoding::Error> {
+    let mut stream = InflateStream::new();
+    let mut inflated = Vec::new();
+    let mut pos = 0;
+    loop {
+        let (read, output) =
+            stream.update(&data[pos..]).map_err(strict_encoding::Error::DataIntegrityError)?;
+        if output.is_empty() {
+            break;
+        }
+        if inflated.len() + output.len() > limit {
+            return Err(strict_encoding::Error::DataIntegrityError(format!(
+                "decompressed message exceeds {} bytes",
+                limit
+            )));
+        }
+        pos += read;
+        inflated.extend_from_slice(output);
+    }
+    Ok(inflated)
+}
+
+/// Features of a local bus connection, negotiated in the connection handshake.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("compression={compression}, dictionaries={dictionaries}")]
+pub struct BusFeatures {
+    /// Support for transparent compression of large bus messages.
+    pub compression: bool,
+
+    /// Support for compression of small bus messages with the per-app dictionaries. Used only
+    /// together with `compression`.
+    pub dictionaries: bool,
+}
+
+impl BusFeatures {
+    /// Features supported by this implementation.
+    pub fn supported() -> Self {
+        BusFeatures {
+            compression: true,
+            dictionaries: true,
+        }
+    }
+
+    /// Features supported by both sides of the connection.
+    pub fn intersect(self, other: BusFeatures) -> Self {
+        BusFeatures {
+            compression: self.compression && other.compression,
+            dictionaries: self.dictionaries && other.dictionaries,
+        }
+    }
+}
+
+/// Deflate-compressed network encoding of a bus message.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Compressed(MediumVec<u8>);
+
+impl Display for Compressed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "compressed({} bytes)", self.0.len())
+    }
+}
+
+impl Compressed {
+    pub fn deflate(data: &[u8]) -> Result<Self, strict_encoding::Error> {
+        MediumVec::try_from(deflate::deflate_bytes(data)).map(Compressed)
+    }
+
+    /// Decompresses the message, failing if it exceeds [`INFLATED_MAX_SIZE`].
+    pub fn inflate(&self) -> Result<Vec<u8>, strict_encoding::Error> {
+        inflate_limited(&self.0, INFLATED_MAX_SIZE)
+    }
+}
+
+/// Compression dictionary of a Storm app: data similar to the app messages, which DEFLATE
+/// back-references of the compressed messages may point to. This improves compression of the
+/// small messages, which are only slightly compressible on their own.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Dictionary(MediumVec<u8>);
+
+impl Display for Dictionary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "dictionary({:#010x}, {} bytes)", self.id(), self.0.len())
+    }
+}
+
+impl Dictionary {
+    /// Constructs dictionary from the data, keeping its last [`DICT_MAX_SIZE`] bytes.
+    pub fn with(data: &[u8]) -> Self {
+        let data = &data[data.len().saturating_sub(DICT_MAX_SIZE)..];
+        Dictionary(MediumVec::try_from(data.to_vec()).expect("dictionary size is limited"))
+    }
+
+    /// Trains dictionary on the samples of the app messages, listed from the oldest to the most
+    /// recent. The most recent samples are preferred and placed at the end of the dictionary,
+    /// where back-references to them are the shortest.
+    pub fn train(samples: &[Vec<u8>]) -> Self {
+        let mut selected = vec![];
+        let mut size = 0usize;
+        for sample in samples.iter().rev() {
+            if size + sample.len() > DICT_MAX_SIZE {
+                break;
+            }
+            size += sample.len();
+            selected.push(sample.as_slice());
+        }
+        selected.reverse();
+        Dictionary::with(&selected.concat())
+    }
+
+    /// Adler-32 checksum of the dictionary data, which is used to identify the dictionary in the
+    /// same way zlib identifies preset dictionaries.
+    pub fn id(&self) -> u32 {
+        const MOD_ADLER: u32 = 65521;
+        let (a, b) = self.0.iter().fold((1u32, 0u32), |(a, b), byte| {
+            let a = (a + *byte as u32) % MOD_ADLER;
+            (a, (b + a) % MOD_ADLER)
+        });
+        (b << 16) | a
+    }
+
+    pub fn as_bytes(&self) -> &[u8] { &self.0 }
+
+    /// Compresses data with the dictionary.
+    ///
+    /// The dictionary is compressed first and followed by a sync flush, after which the data are
+    /// compressed. Only the data part of the output is kept, and since the sync flush completes
+    /// the DEFLATE block at a byte boundary, the data part may follow any DEFLATE encoding of the
+    /// dictionary for decompression.
+    pub fn deflate(&self, data: &[u8]) -> Result<DictCompressed, strict_encoding::Error> {
+        let output = Rc::new(RefCell::new(Vec::with_capacity(self.0.len() + data.len())));
+        let mut encoder = DeflateEncoder::new(Sink(output.clone()), Compression::Default);
+        encoder.write_all(&self.0).expect("in-memory compression");
+        encoder.flush().expect("in-memory compression");
+        let mark = output.borrow().len();
+        encoder.write_all(data).expect("in-memory compression");
+        encoder.finish().expect("in-memory compression");
+        let compressed = output.borrow_mut().split_off(mark);
+        Ok(DictCompressed {
+            dict_id: self.id(),
+            data: MediumVec::try_from(compressed)?,
+        })
+    }
+
+    /// Encodes the dictionary as a sequence of non-final DEFLATE stored blocks.
+    fn stored_blocks(&self) -> Vec<u8> {
+        let mut blocks = Vec::with_capacity(self.0.len() + 5 * (self.0.len() / 0xFFFF + 1));
+        for block in self.0.chunks(0xFFFF) {
+            let len = block.len() as u16;
+            blocks.push(0);
+            blocks.extend(len.to_le_bytes());
+            blocks.extend((!len).to_le_bytes());
+            blocks.extend(block);
+        }
+        blocks
+    }
+}
+
+/// Writer shared between the DEFLATE encoder and the code reading its output.
+struct Sink(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+/// Deflate-compressed network encoding of a bus message, which refers to the compression
+/// dictionary of the app.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct DictCompressed {
+    /// Id of the dictionary used to compress the message.
+    pub dict_id: u32,
+    data: MediumVec<u8>,
+}
+
+impl Display for DictCompressed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "compressed({} bytes, dictionary {:#010x})", self.data.len(), self.dict_id)
+    }
+}
+
+impl DictCompressed {
+    /// Decompresses the message with the dictionary, failing if the message exceeds
+    /// [`INFLATED_MAX_SIZE`].
+    pub fn inflate(&self, dict: &Dictionary) -> Result<Vec<u8>, strict_encoding::Error> {
+        if dict.id() != self.dict_id {
+            return Err(strict_encoding::Error::DataIntegrityError(format!(
+                "message is compressed with dictionary {:#010x}, while the known dictionary is \
+                 {:#010x}",
+                self.dict_id,
+                dict.id()
+            )));
+        }
+        let mut stream = dict.stored_blocks();
+        stream.extend_from_slice(&self.data);
+        let mut data = inflate_limited(&stream, dict.0.len() + INFLATED_MAX_SIZE)?;
+        Ok(data.split_off(dict.0.len()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inflates_within_limit() {
+        let data = vec![0u8; 10_000];
+        let compressed = Compressed::deflate(&data).unwrap();
+        assert_eq!(compressed.inflate().unwrap(), data);
+        assert_eq!(inflate_limited(&compressed.0, data.len()).unwrap(), data);
+    }
+
+    #[test]
+    fn rejects_oversized_output() {
+        let compressed = Compressed::deflate(&vec![0u8; 10_000]).unwrap();
+        assert!(compressed.0.len() < 100);
+        assert!(inflate_limited(&compressed.0, 9_999).is_err());
+    }
+
+    #[test]
+    fn inflates_with_dictionary() {
+        let dict = Dictionary::with(b"storm container announcement");
+        let data = b"storm container announcement of a new container".to_vec();
+        let compressed = dict.deflate(&data).unwrap();
+        assert_eq!(compressed.inflate(&dict).unwrap(), data);
+    }
+}
@@ -0,0 +1,66 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::hex::{self, FromHex, ToHex};
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Number of bytes added to each chunk by its encryption.
+pub const SEALED_CHUNK_OVERHEAD: usize = 16;
+
+/// Error decrypting container chunk.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display("chunk {0} of the container can't be decrypted with the given key")]
+pub struct UnsealError(pub u32);
+
+/// Key encrypting chunks of a single container.
+///
+/// Containers are encrypted by their owner before they are stored and pushed to the remote
+/// peers, so the nodes storing and relaying them keep only the ciphertext. The key is never sent
+/// to the nodes; the owner passes it only to the intended recipients, for instance over an
+/// end-to-end encrypted chat. Container headers and manifests are not encrypted.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ContainerKey([u8; 32]);
+
+impl ContainerKey {
+    pub fn random() -> Self { ContainerKey(rand::random()) }
+
+    /// Encrypts chunk data, binding it to the position of the chunk in the container.
+    pub fn seal(&self, index: u32, data: &[u8]) -> Vec<u8> {
+        self.cipher().encrypt(&Self::nonce(index), data).expect("in-memory encryption")
+    }
+
+    /// Decrypts chunk data, checking that the chunk is at its original position.
+    pub fn unseal(&self, index: u32, data: &[u8]) -> Result<Vec<u8>, UnsealError> {
+        self.cipher().decrypt(&Self::nonce(index), data).map_err(|_| UnsealError(index))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 { ChaCha20Poly1305::new(Key::from_slice(&self.0)) }
+
+    /// Container keys are never reused, so the chunk index is a unique nonce.
+    fn nonce(index: u32) -> Nonce {
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&index.to_le_bytes());
+        *Nonce::from_slice(&nonce)
+    }
+}
+
+impl Display for ContainerKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(&self.0.to_hex()) }
+}
+
+impl FromStr for ContainerKey {
+    type Err = hex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { <[u8; 32]>::from_hex(s).map(ContainerKey) }
+}
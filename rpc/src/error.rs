@@ -10,6 +10,7 @@
 
 use std::fmt::{self, Display, Formatter};
 
+use internet2::presentation;
 use microservices::{esb, rpc};
 
 use crate::{RpcMsg, ServiceId};
@@ -34,6 +35,30 @@ pub enum FailureCode {
     TransferAutomation = 0x20,
 
     UnknownContainer = 0x21,
+
+    ShareToken = 0x22,
+
+    UnknownJob = 0x23,
+
+    UnknownTopic = 0x24,
+
+    UnknownContact = 0x25,
+
+    UnknownMail = 0x26,
+
+    UnknownPost = 0x27,
+
+    UnknownSession = 0x28,
+
+    JobLimit = 0x29,
+
+    RemoteTimeout = 0x2A,
+
+    ApprovalRequired = 0x2B,
+
+    Approval = 0x2C,
+
+    ReadOnly = 0x2D,
 }
 
 impl Display for FailureCode {
@@ -46,6 +71,25 @@ impl Display for FailureCode {
 impl From<u16> for FailureCode {
     fn from(value: u16) -> Self {
         match value {
+            0x02 => FailureCode::Encoding,
+            0x03 => FailureCode::Launch,
+            0x10 => FailureCode::Esb,
+            0x11 => FailureCode::UnexpectedRequest,
+            0x12 => FailureCode::Store,
+            0x20 => FailureCode::TransferAutomation,
+            0x21 => FailureCode::UnknownContainer,
+            0x22 => FailureCode::ShareToken,
+            0x23 => FailureCode::UnknownJob,
+            0x24 => FailureCode::UnknownTopic,
+            0x25 => FailureCode::UnknownContact,
+            0x26 => FailureCode::UnknownMail,
+            0x27 => FailureCode::UnknownPost,
+            0x28 => FailureCode::UnknownSession,
+            0x29 => FailureCode::JobLimit,
+            0x2A => FailureCode::RemoteTimeout,
+            0x2B => FailureCode::ApprovalRequired,
+            0x2C => FailureCode::Approval,
+            0x2D => FailureCode::ReadOnly,
             _ => FailureCode::Unknown,
         }
     }
@@ -79,6 +123,10 @@ pub enum Error {
 
     /// unexpected server response
     UnexpectedServerResponse,
+
+    /// invalid compressed message from the server. Details: {0}
+    #[from]
+    Compression(presentation::Error),
 }
 
 impl RpcMsg {
@@ -0,0 +1,192 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use internet2::addr::NodeId;
+use storm::{ContainerId, MesgId, StormApp};
+
+use crate::TopicId;
+
+/// Kind of the node events the clients subscribe to.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum EventKind {
+    /// Completed container transfers.
+    #[display("transfer")]
+    Transfer,
+
+    /// Messages received by the apps from the remote peers, including chat messages.
+    #[display("message")]
+    Message,
+
+    /// Topics proposed by the remote peers.
+    #[display("topic")]
+    Topic,
+}
+
+/// Error parsing [`EventKind`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownEventKind(String);
+
+impl Display for UnknownEventKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown event kind `{}`; allowed values are `transfer`, `message` and `topic`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = UnknownEventKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transfer" => Ok(EventKind::Transfer),
+            "message" => Ok(EventKind::Message),
+            "topic" => Ok(EventKind::Topic),
+            other => Err(UnknownEventKind(other.to_owned())),
+        }
+    }
+}
+
+/// Event published by the node on the notification bus.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum Event {
+    #[display("transfer of {container_id} is complete, success: {success}")]
+    TransferComplete {
+        container_id: ContainerId,
+        success: bool,
+    },
+
+    #[display("{app} received message {mesg_id} from {remote_id}")]
+    MessageReceived {
+        app: StormApp,
+        remote_id: NodeId,
+        mesg_id: MesgId,
+    },
+
+    #[display("{remote_id} proposed {app} topic {topic_id}")]
+    TopicProposed {
+        app: StormApp,
+        remote_id: NodeId,
+        topic_id: TopicId,
+    },
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::TransferComplete { .. } => EventKind::Transfer,
+            Event::MessageReceived { .. } => EventKind::Message,
+            Event::TopicProposed { .. } => EventKind::Topic,
+        }
+    }
+
+    pub fn app(&self) -> Option<StormApp> {
+        match self {
+            Event::TransferComplete { .. } => None,
+            Event::MessageReceived { app, .. } | Event::TopicProposed { app, .. } => Some(*app),
+        }
+    }
+
+    pub fn remote_id(&self) -> Option<NodeId> {
+        match self {
+            Event::TransferComplete { .. } => None,
+            Event::MessageReceived { remote_id, .. } | Event::TopicProposed { remote_id, .. } => {
+                Some(*remote_id)
+            }
+        }
+    }
+}
+
+/// Filter of the node events delivered to a client.
+///
+/// Empty set matches any value. Events not related to an app or a peer, like completed
+/// transfers, match only the filters which do not restrict the apps or the peers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct EventFilter {
+    pub kinds: BTreeSet<EventKind>,
+    pub apps: BTreeSet<StormApp>,
+    pub peers: BTreeSet<NodeId>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        fn allows<T: Ord>(set: &BTreeSet<T>, value: Option<T>) -> bool {
+            set.is_empty() || value.map(|value| set.contains(&value)).unwrap_or_default()
+        }
+        allows(&self.kinds, Some(event.kind()))
+            && allows(&self.apps, event.app())
+            && allows(&self.peers, event.remote_id())
+    }
+}
+
+impl Display for EventFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn list<T: Display>(f: &mut Formatter<'_>, name: &str, set: &BTreeSet<T>) -> fmt::Result {
+            write!(f, "{}: ", name)?;
+            if set.is_empty() {
+                return f.write_str("all");
+            }
+            let mut iter = set.iter();
+            if let Some(first) = iter.next() {
+                write!(f, "{}", first)?;
+            }
+            for item in iter {
+                write!(f, ", {}", item)?;
+            }
+            Ok(())
+        }
+        list(f, "kinds", &self.kinds)?;
+        list(f, "; apps", &self.apps)?;
+        list(f, "; peers", &self.peers)
+    }
+}
+
+/// Long-poll request for the node events.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct WaitEvent {
+    /// Cursor returned by the previous request, so the events published after it are returned.
+    /// If not given, only the events published after the request are awaited.
+    pub since: Option<u64>,
+    /// Time to wait for a matching event, in seconds
+    pub timeout: u32,
+    pub filter: EventFilter,
+}
+
+impl Display for WaitEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.since {
+            Some(since) => write!(f, "since {}", since)?,
+            None => f.write_str("since now")?,
+        }
+        write!(f, ", timeout {}s, {}", self.timeout, self.filter)
+    }
+}
+
+/// Events returned to the long-poll request. No events are returned if the request has timed
+/// out.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{cursor}, ...")]
+pub struct PolledEvents {
+    /// Sequence number of the last event published by the node, to be passed as
+    /// [`WaitEvent::since`] with the next request
+    pub cursor: u64,
+    pub events: Vec<Event>,
+}
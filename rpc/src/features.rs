@@ -0,0 +1,60 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Features of a Storm node advertised to the remote peers, as a vector of feature bits.
+///
+/// Bits unknown to the receiving node are preserved and ignored.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct NodeFeatures(u64);
+
+impl NodeFeatures {
+    /// Node is a read-only replica: it serves the hosted containers and answers topic queries,
+    /// but refuses pushed containers, posts and topic proposals.
+    pub const READ_ONLY: u8 = 0;
+
+    /// Node is a storage peer: it retrieves and keeps replicas of the containers on the requests
+    /// of the remote peers.
+    pub const STORAGE: u8 = 1;
+
+    pub fn with_bits(bits: u64) -> Self { NodeFeatures(bits) }
+
+    pub fn bits(self) -> u64 { self.0 }
+
+    pub fn set(&mut self, bit: u8) { self.0 |= 1 << bit; }
+
+    pub fn has(self, bit: u8) -> bool { self.0 & (1 << bit) != 0 }
+
+    pub fn is_read_only(self) -> bool { self.has(Self::READ_ONLY) }
+
+    pub fn is_storage(self) -> bool { self.has(Self::STORAGE) }
+}
+
+impl Display for NodeFeatures {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("none");
+        }
+        let mut names = vec![];
+        if self.is_read_only() {
+            names.push(s!("read-only"));
+        }
+        if self.is_storage() {
+            names.push(s!("storage"));
+        }
+        let unknown = self.0 & !(1 << Self::READ_ONLY | 1 << Self::STORAGE);
+        if unknown != 0 {
+            names.push(format!("unknown({:#x})", unknown));
+        }
+        f.write_str(&names.join(", "))
+    }
+}
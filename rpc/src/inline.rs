@@ -0,0 +1,70 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+
+use storm::{Chunk, ChunkId};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+/// Size of the chunk, in bytes, up to which it is kept inline with its container rather than as
+/// a separate record of the chunk table.
+pub const INLINE_CHUNK_SIZE: usize = 4096;
+
+/// Total size of the chunks, in bytes, which may be kept inline with a container. Chunks of the
+/// container outgrowing it are promoted to the chunk table.
+pub const INLINE_RECORD_LIMIT: usize = 64 * 1024;
+
+/// Small chunks of a container, kept in the store as a single record under the container id.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictEncode, StrictDecode)]
+pub struct InlineChunks {
+    chunks: BTreeMap<ChunkId, Chunk>,
+}
+
+impl InlineChunks {
+    /// Detects whether the chunk is small enough to be kept inline.
+    pub fn is_inline(chunk: &Chunk) -> bool { chunk.as_slice().len() <= INLINE_CHUNK_SIZE }
+
+    pub fn is_empty(&self) -> bool { self.chunks.is_empty() }
+
+    /// Total size of the chunks, in bytes.
+    pub fn size(&self) -> usize { self.chunks.values().map(|chunk| chunk.as_slice().len()).sum() }
+
+    pub fn contains(&self, chunk_id: ChunkId) -> bool { self.chunks.contains_key(&chunk_id) }
+
+    pub fn get(&self, chunk_id: ChunkId) -> Option<&Chunk> { self.chunks.get(&chunk_id) }
+
+    /// Adds the chunk, unless it is not small enough, or the record would outgrow
+    /// [`INLINE_RECORD_LIMIT`]. Returns whether the chunk was added.
+    pub fn insert(&mut self, chunk_id: ChunkId, chunk: Chunk) -> bool {
+        if !Self::is_inline(&chunk) || self.size() + chunk.as_slice().len() > INLINE_RECORD_LIMIT {
+            return false;
+        }
+        self.chunks.insert(chunk_id, chunk);
+        true
+    }
+
+    /// Takes the chunks out of the record, which have to be promoted to the chunk table.
+    pub fn promote(&mut self) -> BTreeMap<ChunkId, Chunk> { std::mem::take(&mut self.chunks) }
+
+    /// Reads the record from its stored form; records which can't be read are treated as
+    /// empty, since their chunks are retrieved again.
+    pub fn from_record(record: Chunk) -> Self {
+        InlineChunks::strict_deserialize(record.as_slice()).unwrap_or_else(|err| {
+            warn!("Inline chunk record is corrupted and is ignored. Details: {}", err);
+            InlineChunks::default()
+        })
+    }
+
+    /// Returns the record in its stored form.
+    pub fn to_record(&self) -> Result<Chunk, strict_encoding::Error> {
+        Chunk::try_from(self.strict_serialize()?)
+    }
+}
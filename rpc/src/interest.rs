@@ -0,0 +1,44 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+use storm::{ContainerId, MesgId, StormApp};
+
+/// Containers and messages of an app which the node is interested in. The remote peer the
+/// interest is registered with announces only the containers with these ids, and sends only the
+/// posts with these ids, replying to these messages or referring to these containers.
+///
+/// Since the interest is sent to the peer as a Bloom filter, the peer may also send some
+/// unrelated content. Interest with no ids withdraws the registration.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Interest {
+    pub app: StormApp,
+    pub containers: BTreeSet<ContainerId>,
+    pub messages: BTreeSet<MesgId>,
+}
+
+impl Interest {
+    pub fn is_empty(&self) -> bool { self.containers.is_empty() && self.messages.is_empty() }
+}
+
+impl Display for Interest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {} containers, {} messages",
+            self.app,
+            self.containers.len(),
+            self.messages.len()
+        )
+    }
+}
@@ -0,0 +1,73 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+
+use crate::{AddressedMsg, AppContainer};
+
+/// Identifier of a long-running operation executed by the node in background.
+pub type JobId = u64;
+
+/// Kind of the long-running operation.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum JobKind {
+    /// Retrieval of a container from a remote peer.
+    #[display("pull")]
+    Pull,
+}
+
+/// State of a background job.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum JobState {
+    /// The job waits for a free transfer service.
+    #[display("queued")]
+    Queued,
+
+    #[display("running")]
+    Running,
+
+    #[display("completed")]
+    Completed,
+
+    #[display("failed")]
+    Failed,
+}
+
+impl JobState {
+    pub fn is_finished(self) -> bool { matches!(self, JobState::Completed | JobState::Failed) }
+}
+
+/// Request to start a background job.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{kind} {container}")]
+pub struct JobRequest {
+    pub kind: JobKind,
+    pub container: AddressedMsg<AppContainer>,
+}
+
+/// Status of a background job.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{id:#018x}: {kind} {container_id}, {state}")]
+pub struct JobInfo {
+    pub id: JobId,
+    pub kind: JobKind,
+    pub container_id: ContainerId,
+    pub remote_id: NodeId,
+    pub state: JobState,
+    /// UNIX timestamp of the job creation.
+    pub created: u64,
+    /// UNIX timestamp of the last job state change.
+    pub updated: u64,
+}
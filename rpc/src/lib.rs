@@ -13,6 +13,8 @@
 //! requests which may be done directly to the main storm daemon.
 
 #![recursion_limit = "256"]
+// ESB errors are large, but are returned by all the client calls
+#![allow(clippy::result_large_err)]
 
 #[macro_use]
 extern crate amplify;
@@ -26,20 +28,94 @@ extern crate log;
 #[cfg(feature = "serde")]
 extern crate serde_crate as serde;
 
+mod admin;
+mod approval;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod attestation;
+mod batch;
+mod board;
+mod chaos;
+mod chat;
 pub mod client;
+mod compress;
+mod envelope;
 mod error;
+mod event;
+mod features;
+mod inline;
+mod interest;
+mod job;
+mod limits;
+mod mail;
+mod manifest;
+mod memory;
 mod messages;
+mod page;
+mod patch;
+mod query;
+mod replication;
 mod service_id;
+mod session;
+mod share;
+mod topic;
 
+pub use admin::{AdminCommand, AdminOutcome, Replication};
+pub use approval::{
+    AdminAction, Approval, ApprovalError, ApprovalOutcome, AuditEvent, AuditRecord, Proposal,
+    ProposalInfo,
+};
+pub use attestation::{Attestation, AttestationError, SignedAttestation};
+pub use batch::{BatchResult, ContainerBatch, ItemStatus};
+pub use board::{
+    BoardAction, BoardInfo, BoardModeration, BoardPost, BoardPostRequest, BoardSummary,
+    ModerationError, PostId, SignedModeration, UnknownBoardAction,
+};
+pub use chaos::{Fault, FaultTarget, UnknownFaultTarget};
+pub use chat::{
+    ChatContact, ChatEntry, ChatHistoryReq, ChatMatch, KeyChange, LinkPreview, NotifyPref,
+    PreviewedChat,
+};
 pub use client::Client;
+pub use compress::{
+    BusFeatures, Compressed, DictCompressed, Dictionary, COMPRESSION_THRESHOLD,
+    DICT_COMPRESSION_THRESHOLD, DICT_MAX_SIZE, INFLATED_MAX_SIZE,
+};
+pub use envelope::{ContainerKey, UnsealError, SEALED_CHUNK_OVERHEAD};
 pub use error::{Error, FailureCode};
+pub use event::{Event, EventFilter, EventKind, PolledEvents, UnknownEventKind, WaitEvent};
+pub use features::NodeFeatures;
+pub use inline::{InlineChunks, INLINE_CHUNK_SIZE, INLINE_RECORD_LIMIT};
+pub use interest::Interest;
+pub use job::{JobId, JobInfo, JobKind, JobRequest, JobState};
+pub use limits::{LimitScope, RateLimit, RateLimitUpdate, RateLimits};
+pub use mail::{
+    Mail, MailEntry, MailFolder, MailId, MailMove, MailQuery, MailRequest, UnknownMailFolder,
+};
+pub use manifest::{Manifest, ManifestError, ManifestInfo, SignedManifest};
+pub use memory::{MemoryPressure, MemoryReport};
 pub(crate) use messages::BusMsg;
-pub use messages::{AddressedMsg, AppContainer, RadioMsg, RpcMsg};
-pub use service_id::ServiceId;
+pub use messages::{
+    AddressedMsg, AppContainer, AppUsage, BackupInfo, BridgeMsg, ContainerEntry, ContainerStats,
+    ContainerVersion, DaemonInfo, FolderChange, GcReport, Health, HealthCheck, Idempotent,
+    NodeCounters, NodeInfo, NodeStats, PeerStats, QuarantinedContainer, RadioMsg, RepairSummary,
+    RequestId, RpcMsg, TransferEntry, TransferProgress,
+};
+pub use page::{Cursor, Page, PageReq, Paged, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+pub use patch::{Patch, PatchError, PatchOp, PATCH_BLOCK_SIZE, PATCH_MIME};
+pub use query::{Filter, ListQuery, Listed, SortBy, UnknownSortOrder};
+pub use replication::{ReplicaState, ReplicateRequest, ReplicationStatus};
+pub use service_id::{ServiceId, STORM_APP_BOARD, STORM_APP_MAIL, STORM_APP_SYNC};
+pub use session::{SessionInfo, SessionToken};
+pub use share::{ShareInfo, ShareRequest, ShareToken};
+pub use topic::{TopicId, TopicMismatch};
 
 pub const STORM_NODE_RPC_ENDPOINT: &str = "0.0.0.0:64964";
 pub const CHATD_RPC_ENDPOINT: &str = "0.0.0.0:40940";
+pub const STORM_NODE_NOTIFY_ENDPOINT: &str = "0.0.0.0:64966";
 
-pub const DB_TABLE_CONTAINER_HEADERS: &'static str = "container_headers";
-pub const DB_TABLE_CONTAINERS: &'static str = "containers";
-pub const DB_TABLE_CHUNKS: &'static str = "chunks";
+pub const DB_TABLE_CONTAINER_HEADERS: &str = "container_headers";
+pub const DB_TABLE_CONTAINERS: &str = "containers";
+pub const DB_TABLE_CHUNKS: &str = "chunks";
+pub const DB_TABLE_CONTAINER_VERSIONS: &str = "container_versions";
+pub const DB_TABLE_INLINE_CHUNKS: &str = "inline_chunks";
@@ -0,0 +1,89 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use internet2::addr::NodeId;
+
+/// Limit of the rate at which the node sends chunks to the remote peers. Absent values are not
+/// limited.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct RateLimit {
+    pub bytes_per_sec: Option<u64>,
+    pub chunks_per_sec: Option<u32>,
+}
+
+impl RateLimit {
+    pub fn is_unlimited(&self) -> bool {
+        self.bytes_per_sec.is_none() && self.chunks_per_sec.is_none()
+    }
+}
+
+impl Display for RateLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.bytes_per_sec, self.chunks_per_sec) {
+            (None, None) => f.write_str("unlimited"),
+            (Some(bytes), None) => write!(f, "{} bytes/s", bytes),
+            (None, Some(chunks)) => write!(f, "{} chunks/s", chunks),
+            (Some(bytes), Some(chunks)) => write!(f, "{} bytes/s, {} chunks/s", bytes, chunks),
+        }
+    }
+}
+
+/// Traffic a rate limit applies to.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum LimitScope {
+    /// Chunks sent to all the remote peers together.
+    #[display("global")]
+    Global,
+
+    /// Chunks sent to each of the remote peers which has no own limit.
+    #[display("per-peer")]
+    PerPeer,
+
+    /// Chunks sent to the remote peer. Unlimited rate removes the own limit of the peer.
+    #[display("peer {0}")]
+    Peer(NodeId),
+}
+
+/// Request to change a rate limit of the node.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{scope}: {limit}")]
+pub struct RateLimitUpdate {
+    pub scope: LimitScope,
+    pub limit: RateLimit,
+}
+
+/// Rate limits of the node and the state of the limited traffic.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct RateLimits {
+    pub global: RateLimit,
+    pub per_peer: RateLimit,
+    /// Own limits of the remote peers
+    pub peers: BTreeMap<NodeId, RateLimit>,
+    /// Number of chunk requests of the remote peers deferred by the limits
+    pub deferred: u32,
+}
+
+impl Display for RateLimits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Global: {}", self.global)?;
+        writeln!(f, "Per peer: {}", self.per_peer)?;
+        for (remote_id, limit) in &self.peers {
+            writeln!(f, "Peer {}: {}", remote_id, limit)?;
+        }
+        write!(f, "Deferred chunk requests: {}", self.deferred)
+    }
+}
@@ -0,0 +1,172 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use internet2::addr::NodeId;
+use storm::{ContainerId, StormApp};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::{ListQuery, Listed, Page, STORM_APP_MAIL};
+
+/// Local id of a mail in the mailbox.
+pub type MailId = u64;
+
+/// Mailbox folder.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum MailFolder {
+    /// Received mails.
+    #[display("inbox")]
+    Inbox,
+
+    /// Mails which are not yet acknowledged by the recipient or the relay.
+    #[display("outbox")]
+    Outbox,
+
+    /// Mails acknowledged by the recipient or the relay.
+    #[display("sent")]
+    Sent,
+
+    #[display("archive")]
+    Archive,
+
+    #[display("trash")]
+    Trash,
+}
+
+/// Error parsing [`MailFolder`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownMailFolder(String);
+
+impl Display for UnknownMailFolder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown mail folder `{}`; allowed values are `inbox`, `outbox`, `sent`, `archive`              and `trash`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for MailFolder {
+    type Err = UnknownMailFolder;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inbox" => Ok(MailFolder::Inbox),
+            "outbox" => Ok(MailFolder::Outbox),
+            "sent" => Ok(MailFolder::Sent),
+            "archive" => Ok(MailFolder::Archive),
+            "trash" => Ok(MailFolder::Trash),
+            other => Err(UnknownMailFolder(other.to_owned())),
+        }
+    }
+}
+
+/// Content of a mail.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Mail {
+    pub subject: String,
+    pub body: String,
+    /// Containers attached to the mail, which are retrieved by the recipient.
+    pub attachments: Vec<ContainerId>,
+}
+
+/// Request to send a mail.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("mail to {recipient}")]
+pub struct MailRequest {
+    pub recipient: NodeId,
+    /// Relay node which stores the mail and forwards it to the recipient once the recipient is
+    /// reachable; the mail is sent directly if no relay is given.
+    pub relay: Option<NodeId>,
+    pub mail: Mail,
+}
+
+/// Mail kept in the mailbox.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct MailEntry {
+    pub id: MailId,
+    pub folder: MailFolder,
+    /// UNIX timestamp of the moment the mail was sent or received.
+    pub timestamp: u64,
+    /// Sender of the received mails or recipient of the sent ones.
+    pub peer: NodeId,
+    /// Whether the mail was received from the peer.
+    pub incoming: bool,
+    /// Relay which the mail was sent or received through.
+    pub relay: Option<NodeId>,
+    /// Whether the received mail was read.
+    pub read: bool,
+    pub mail: Mail,
+}
+
+impl Display for MailEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let dir = if self.incoming { "from" } else { "to" };
+        let unread = if self.incoming && !self.read { '*' } else { ' ' };
+        write!(
+            f,
+            "{:>6}{} {} {} {} {}",
+            self.id, unread, self.timestamp, dir, self.peer, self.mail.subject
+        )?;
+        if !self.mail.attachments.is_empty() {
+            write!(f, " ({} attachments)", self.mail.attachments.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl Listed for MailEntry {
+    fn app(&self) -> Option<StormApp> { Some(StormApp::Vendor(STORM_APP_MAIL)) }
+    fn peer(&self) -> Option<NodeId> { Some(self.peer) }
+    fn size(&self) -> u64 { (self.mail.subject.len() + self.mail.body.len()) as u64 }
+    fn timestamp(&self) -> u64 { self.timestamp }
+}
+
+/// Request for the mails of a mailbox folder matching the query. The mails are ordered by their
+/// ids by default.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct MailQuery {
+    pub folder: MailFolder,
+    pub query: ListQuery,
+}
+
+impl Display for MailQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.folder, self.query)
+    }
+}
+
+impl MailQuery {
+    /// Query for the page following the given one. Returns `None` if there are no more pages.
+    pub fn next<T>(&self, page: &Page<T>) -> Option<MailQuery>
+    where T: StrictEncode + StrictDecode {
+        self.query.next(page).map(|query| MailQuery {
+            folder: self.folder,
+            query,
+        })
+    }
+}
+
+/// Request to move a mail to another folder.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{id} to {folder}")]
+pub struct MailMove {
+    pub id: MailId,
+    pub folder: MailFolder,
+}
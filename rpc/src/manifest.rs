@@ -0,0 +1,98 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use internet2::addr::NodeId;
+use secp256k1::hashes::sha256;
+use secp256k1::{ecdsa, Message, Secp256k1, SecretKey};
+use storm::ContainerId;
+use strict_encoding::StrictEncode;
+
+/// Inventory of the containers and pins hosted by a node at a point in time.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct Manifest {
+    /// Key of the node the manifest is signed with.
+    pub signer: NodeId,
+    /// UNIX timestamp of the inventory.
+    pub timestamp: u64,
+    /// Hosted containers with their sizes, in bytes.
+    pub containers: BTreeMap<ContainerId, u64>,
+    /// Containers excluded from cache eviction.
+    pub pins: BTreeSet<ContainerId>,
+}
+
+/// [`Manifest`] with the signature of the node which has issued it. Manifests are exported for
+/// the audits and for the nodes which rely on the issuer for replication.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct SignedManifest {
+    pub manifest: Manifest,
+    pub signature: ecdsa::Signature,
+}
+
+/// Information about the manifest exported by the node.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{path}: {containers} containers and {pins} pins at {timestamp}, signed by {signer}")]
+pub struct ManifestInfo {
+    /// Path to the manifest file on the node host.
+    pub path: String,
+    pub signer: NodeId,
+    pub timestamp: u64,
+    pub containers: u32,
+    pub pins: u32,
+}
+
+/// Errors verifying [`SignedManifest`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ManifestError {
+    /// manifest signature does not match the manifest content and the signer key
+    InvalidSignature,
+}
+
+impl Manifest {
+    fn message(&self) -> Message {
+        let data = self.strict_serialize().expect("in-memory manifest encoding");
+        Message::from_hashed_data::<sha256::Hash>(&data)
+    }
+
+    /// Signs the manifest with the secret key of [`Manifest::signer`].
+    pub fn sign(self, secret_key: &SecretKey) -> SignedManifest {
+        let signature = Secp256k1::signing_only().sign_ecdsa(&self.message(), secret_key);
+        SignedManifest {
+            manifest: self,
+            signature,
+        }
+    }
+}
+
+impl SignedManifest {
+    pub fn verify(&self) -> Result<(), ManifestError> {
+        Secp256k1::verification_only()
+            .verify_ecdsa(
+                &self.manifest.message(),
+                &self.signature,
+                &self.manifest.signer.public_key(),
+            )
+            .map_err(|_| ManifestError::InvalidSignature)
+    }
+
+    /// Returns containers which are absent from the manifest.
+    pub fn missing(
+        &self,
+        container_ids: impl IntoIterator<Item = ContainerId>,
+    ) -> Vec<ContainerId> {
+        container_ids
+            .into_iter()
+            .filter(|container_id| !self.manifest.containers.contains_key(container_id))
+            .collect()
+    }
+}
@@ -0,0 +1,63 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// Memory use of the node relative to its budget.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum MemoryPressure {
+    /// Memory use is within the budget, or the budget is not set.
+    #[display("normal")]
+    Normal,
+
+    /// Memory use approaches the budget; the node shrinks its caches.
+    #[display("high")]
+    High,
+
+    /// Memory use exceeds the budget; the node does not start new transfers until it goes
+    /// down.
+    #[display("critical")]
+    Critical,
+}
+
+/// Memory accounted by the node against its budget.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct MemoryReport {
+    /// Memory budget of the node, in bytes
+    pub budget: Option<u64>,
+    /// Memory used by the caches, buffers and queues of the daemons, in bytes
+    pub accounted: u64,
+    /// Resident memory of the node process, in bytes, if it is known
+    pub resident: Option<u64>,
+    pub pressure: MemoryPressure,
+    /// Memory accounted for each of the components, in bytes
+    pub components: BTreeMap<String, u64>,
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.budget {
+            Some(budget) => writeln!(f, "Budget: {} bytes", budget)?,
+            None => writeln!(f, "Budget: unlimited")?,
+        }
+        writeln!(f, "Accounted: {} bytes", self.accounted)?;
+        if let Some(resident) = self.resident {
+            writeln!(f, "Resident: {} bytes", resident)?;
+        }
+        write!(f, "Pressure: {}", self.pressure)?;
+        for (component, bytes) in &self.components {
+            write!(f, "\n{} {}", component, bytes)?;
+        }
+        Ok(())
+    }
+}
@@ -8,17 +8,28 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display, Formatter};
+use std::io;
 
 use internet2::addr::NodeId;
 use internet2::presentation;
 use microservices::rpc;
 use microservices::util::OptionDetails;
 use storm::p2p::AppMsg;
-use storm::{ContainerFullId, StormApp};
+use storm::{ChunkId, ContainerFullId, ContainerId, StormApp};
 use strict_encoding::{StrictDecode, StrictEncode};
 
-use crate::FailureCode;
+use crate::{
+    AdminCommand, AdminOutcome, Approval, ApprovalOutcome, AuditRecord, BatchResult, BoardInfo,
+    BoardPost, BoardPostRequest, BoardSummary, BusFeatures, ChatContact, ChatEntry, ChatHistoryReq,
+    ChatMatch, Compressed, ContainerBatch, Event, EventFilter, EventKind, FailureCode, Fault,
+    Interest, JobId, JobInfo, JobRequest, KeyChange, ListQuery, Listed, MailEntry, MailId,
+    MailMove, MailQuery, MailRequest, ManifestInfo, MemoryReport, NodeFeatures, NotifyPref, Page,
+    PageReq, Paged, PolledEvents, PostId, PreviewedChat, ProposalInfo, RateLimitUpdate, RateLimits,
+    ReplicateRequest, ReplicationStatus, ServiceId, SessionInfo, SessionToken, ShareInfo,
+    ShareRequest, ShareToken, SignedAttestation, SignedModeration, TopicId, WaitEvent,
+};
 
 /// We need this wrapper type to be compatible with Storm Node having multiple message buses
 #[derive(Clone, Debug, Display, From, Api)]
@@ -35,6 +46,18 @@ pub(crate) enum BusMsg {
     #[display(inner)]
     #[from]
     Chat(RadioMsg),
+
+    /// Node events published to the subscribed clients
+    #[api(type = 0x82)]
+    #[display(inner)]
+    #[from]
+    Notify(Event),
+
+    /// Compressed encoding of another message
+    #[api(type = 0x7f)]
+    #[display(inner)]
+    #[from]
+    Compressed(Compressed),
 }
 
 impl rpc::Request for BusMsg {}
@@ -43,6 +66,11 @@ impl rpc::Request for BusMsg {}
 #[derive(NetworkEncode, NetworkDecode)]
 #[display(inner)]
 pub enum RpcMsg {
+    /// Connection handshake: the client reports the features it supports and the node replies
+    /// with the features which will be used for the connection.
+    #[display("negotiate({0})")]
+    Negotiate(BusFeatures),
+
     /* This will require LNP Node internals refactoring, in turn requiring microservice lib
     refactoring to allow multiple ids on the same controller (one per message bus).
 
@@ -58,14 +86,547 @@ pub enum RpcMsg {
     #[display("send_chat({0})")]
     SendChat(AddressedMsg<String>),
 
+    /// Chat message which link previews were fetched by the preview worker of the chat daemon,
+    /// which the daemon should send to the peer.
+    #[display("chat_previewed({0})")]
+    ChatPreviewed(AddressedMsg<PreviewedChat>),
+
+    /// Set notification preference for the conversation with the remote peer.
+    #[display("set_chat_notify({0})")]
+    SetChatNotify(AddressedMsg<NotifyPref>),
+
+    /// Set number of seconds after which the messages of the conversation with the remote peer
+    /// disappear, or zero to keep them. The setting is sent to the peer, which applies it too.
+    #[display("set_chat_disappear({0})")]
+    SetChatDisappear(AddressedMsg<u64>),
+
+    /// Bind the contact name to the peer key. If the name was bound to another key, the contact
+    /// verification is reset and the key change is published to the chat subscribers.
+    #[display("set_chat_contact({0})")]
+    SetChatContact(AddressedMsg<String>),
+
+    /// Mark chat contact with the given name as verified.
+    #[display("verify_chat_contact({0})")]
+    VerifyChatContact(String),
+
+    /// List chat contacts.
+    #[display("list_chat_contacts()")]
+    ListChatContacts,
+
+    /// Request safety number of the conversation with the remote peer. The request data is the
+    /// local node id.
+    #[display("get_chat_safety({0})")]
+    GetChatSafety(AddressedMsg<NodeId>),
+
+    /// Block chat messages from the remote peer. Messages from the blocked peers are silently
+    /// dropped by chatd; other apps are not affected.
+    #[display("chat_block({0})")]
+    ChatBlock(NodeId),
+
+    /// Remove the remote peer from the chat block list.
+    #[display("chat_unblock({0})")]
+    ChatUnblock(NodeId),
+
+    /// List peers blocked in chat.
+    #[display("list_chat_blocked()")]
+    ListChatBlocked,
+
+    /// List conversations which notification preferences differ from the default.
+    #[display("list_chat_notify()")]
+    ListChatNotify,
+
+    /// Save unsent message for the conversation with the remote peer. Empty message removes the
+    /// draft.
+    #[display("chat_save_draft({0})")]
+    ChatSaveDraft(AddressedMsg<String>),
+
+    /// Request unsent message saved for the conversation with the remote peer.
+    #[display("chat_get_draft({0})")]
+    ChatGetDraft(NodeId),
+
+    /// Search the history of the conversation with the remote peer for the messages containing
+    /// the query, ignoring ASCII case.
+    #[display("chat_search({0})")]
+    ChatSearch(Paged<AddressedMsg<String>>),
+
+    /// Request messages of the conversation with the remote peer, oldest first.
+    #[display("chat_history({0})")]
+    ChatHistory(ChatHistoryReq),
+
+    /// Message published to an external broker, which the bridge daemon should post to the Storm
+    /// network.
+    #[display("bridge_inbound({0})")]
+    BridgeInbound(BridgeMsg),
+
+    /// File in a replicated folder was changed, which the folder sync daemon should announce to
+    /// the peer.
+    #[display("folder_changed({0})")]
+    FolderChanged(FolderChange),
+
+    /// Send a mail, directly or through a relay node. The node replies with the id of the mail
+    /// in the outbox.
+    #[display("send_mail({0})")]
+    SendMail(MailRequest),
+
+    /// List mails in the mailbox folder.
+    #[display("list_mail({0})")]
+    ListMail(MailQuery),
+
+    /// Request a mail from the mailbox, marking it as read.
+    #[display("read_mail({0})")]
+    ReadMail(MailId),
+
+    /// Move a mail to another mailbox folder.
+    #[display("move_mail({0})")]
+    MoveMail(MailMove),
+
+    /// Delete a mail from the mailbox.
+    #[display("delete_mail({0})")]
+    DeleteMail(MailId),
+
+    /// Create a public board. The node replies with the id of the board topic.
+    #[display("create_board({0})")]
+    CreateBoard(BoardInfo),
+
+    /// Subscribe to the board through the remote peer, which sends the board history back.
+    #[display("subscribe_board({0})")]
+    SubscribeBoard(AddressedMsg<TopicId>),
+
+    #[display("unsubscribe_board({0})")]
+    UnsubscribeBoard(TopicId),
+
+    #[display("list_boards({0})")]
+    ListBoards(PageReq),
+
+    /// Request posts of the board which are not removed, pinned posts first.
+    #[display("read_board({0})")]
+    ReadBoard(Paged<TopicId>),
+
+    /// Publish a post on the board. The node replies with the post id.
+    #[display("post_board({0})")]
+    PostBoard(BoardPostRequest),
+
+    /// Pin, unpin or remove a board post signed by a board moderator.
+    #[display("moderate_board({0})")]
+    ModerateBoard(SignedModeration),
+
     #[display("send({0})")]
     SendContainer(AddressedMsg<AppContainer>),
 
     #[display("receive({0})")]
     GetContainer(AddressedMsg<AppContainer>),
 
+    /// Start a long-running operation in background. The node replies with the job id.
+    #[display("submit_job({0})")]
+    SubmitJob(JobRequest),
+
+    /// Request status of a background job.
+    #[display("job_status({0:#018x})")]
+    JobStatus(JobId),
+
+    /// List background jobs known to the node.
+    #[display("list_jobs({0})")]
+    ListJobs(PageReq),
+
+    /// List sessions of the RPC clients connected to the node.
+    #[display("list_sessions({0})")]
+    ListSessions(PageReq),
+
+    /// Disconnect the client of the session, refusing its further requests.
+    #[display("disconnect_session({0:#018x})")]
+    DisconnectSession(SessionToken),
+
+    /// Request information about the node state.
+    #[display("get_info()")]
+    GetInfo,
+
+    /// List daemons registered with the node.
+    #[display("list_daemons({0})")]
+    ListDaemons(PageReq),
+
+    /// Request container request statistics collected by the node.
+    #[display("get_container_stats({0})")]
+    GetContainerStats(PageReq),
+
+    /// Request node activity counters, both for the node lifetime and since its start.
+    #[display("get_stats()")]
+    GetStats,
+
+    /// Request attestation of the software run by the remote peer, if the peer has sent it.
+    #[display("get_attestation({0})")]
+    GetAttestation(NodeId),
+
+    /// Request features advertised by the remote peer, if the peer has sent them.
+    #[display("get_peer_features({0})")]
+    GetPeerFeatures(NodeId),
+
+    /// Request progress of the container retrieval, computed from the container chunks kept in
+    /// the store.
+    #[display("get_transfer_progress({0})")]
+    GetTransferProgress(ContainerId),
+
+    /// Replicate the stored container to the storage peers, which retrieve and pin it and
+    /// confirm the replica.
+    #[display("replicate({0})")]
+    Replicate(ReplicateRequest),
+
+    /// Request status of the container replication to the storage peers.
+    #[display("get_replication({0})")]
+    GetReplication(ContainerId),
+
+    /// Request node health. Any reply proves that the node is alive; its content tells whether
+    /// the node is ready to serve requests.
+    #[display("get_health()")]
+    GetHealth,
+
+    /// Rebuild the container index from the container manifests kept in the store.
+    #[display("rebuild_index()")]
+    RebuildIndex,
+
+    /// Back up the container index and the pin set. The backup is taken from a consistent
+    /// snapshot and is written in background, without stopping the transfers.
+    #[display("create_backup()")]
+    CreateBackup,
+
+    /// Export a signed manifest of the containers and pins hosted by the node.
+    #[display("export_manifest()")]
+    ExportManifest,
+
+    /// Flag the remote peer, quarantining containers retrieved from it.
+    #[display("flag_peer({0})")]
+    FlagPeer(NodeId),
+
+    /// Remove flag from the remote peer. Containers which are already quarantined remain so.
+    #[display("unflag_peer({0})")]
+    UnflagPeer(NodeId),
+
+    /// List containers retrieved from the flagged peers.
+    #[display("list_quarantine({0})")]
+    ListQuarantine(PageReq),
+
+    /// Release container from the quarantine, making it available to the peers, apps and RPC
+    /// clients.
+    #[display("release_container({0})")]
+    ReleaseContainer(ContainerId),
+
+    /// Ban the remote peer: its posts and topic proposals are dropped before reaching the apps.
+    #[display("ban_peer({0})")]
+    BanPeer(NodeId),
+
+    /// Remove the remote peer from the ban list.
+    #[display("unban_peer({0})")]
+    UnbanPeer(NodeId),
+
+    /// List peers banned by the operator or for repeated spam.
+    #[display("list_bans()")]
+    ListBans,
+
+    /// Request rate limits of the chunks sent to the remote peers.
+    #[display("get_rate_limits()")]
+    GetRateLimits,
+
+    /// Change rate limit of the chunks sent to the remote peers, effective immediately.
+    #[display("set_rate_limit({0})")]
+    SetRateLimit(RateLimitUpdate),
+
+    /// Request memory accounted by the node against its budget.
+    #[display("get_memory()")]
+    GetMemory,
+
+    /// Request faults injected by the chaos testing hooks.
+    #[display("get_faults()")]
+    GetFaults,
+
+    /// Inject fault into a subsystem of the node, or disable it with zero rate. Rejected unless
+    /// the node is built with the `chaos` feature.
+    #[display("set_fault({0})")]
+    SetFault(Fault),
+
+    /// Retrieve deferred chunks of the container which was fetched metadata-first.
+    #[display("materialize({0})")]
+    Materialize(ContainerId),
+
+    /// Request storage used by the containers of each app.
+    #[display("get_app_usage({0})")]
+    GetAppUsage(PageReq),
+
+    /// Request latency and throughput measured by the node for the remote peers.
+    #[display("get_peer_stats({0})")]
+    GetPeerStats(PageReq),
+
+    /// Ask the node to publish events of the given kinds on its notification bus. The events
+    /// are published to all the subscribers, which filter them by the kinds they are interested
+    /// in.
+    #[display("subscribe(...)")]
+    Subscribe(BTreeSet<EventKind>),
+
+    /// Subscribe the client to the node events matching the filter, which are sent to the client
+    /// alone over the RPC bus. Replaces the previous filtered subscription of the client.
+    #[display("subscribe_filtered({0})")]
+    SubscribeFiltered(EventFilter),
+
+    /// Cancel the filtered subscription of the client.
+    #[display("unsubscribe()")]
+    Unsubscribe,
+
+    /// Wait until the node publishes an event matching the filter, or until the timeout. The
+    /// node replies with [`RpcMsg::PolledEvents`] right away if a matching event was published
+    /// after the cursor of the request; the timeout is checked as the node handles other messages.
+    #[display("wait_event({0})")]
+    WaitEvent(WaitEvent),
+
+    /// Register interest of the node in the containers and messages of an app with the remote
+    /// peer, which then sends only the matching announcements and posts.
+    #[display("register_interest({0})")]
+    RegisterInterest(AddressedMsg<Interest>),
+
+    /// Ask the remote peer for the list of the Storm apps it runs.
+    #[display("list_peer_apps({0})")]
+    ListPeerApps(Paged<NodeId>),
+
+    /// Ask the remote peer for the list of the topics of the app.
+    #[display("list_peer_topics({0})")]
+    ListPeerTopics(Paged<AddressedMsg<StormApp>>),
+
+    /// Issue management command to another node of the operator, which must list this node with
+    /// `--admin`. The reply is sent once the managed node answers.
+    #[display("administer({0})")]
+    Administer(AddressedMsg<AdminCommand>),
+
+    /// List containers known to the node matching the query.
+    #[display("list_containers({0})")]
+    ListContainers(ListQuery),
+
+    /// List container transfers which are in progress or are interrupted, matching the query.
+    #[display("list_transfers({0})")]
+    ListTransfers(ListQuery),
+
+    /// Declare container to be a new version of another container. When the container is
+    /// retrieved, only the chunks which differ from the previous version are requested from the
+    /// remote peer.
+    #[display("declare_version({0})")]
+    DeclareVersion(ContainerVersion),
+
+    /// Mutating request carrying a client-supplied id. The node executes request with the same id
+    /// only once, replying to the retries with the cached result.
+    #[display("idempotent({0})")]
+    Idempotent(Idempotent),
+
+    /// Pin containers, excluding them from cache eviction. Containers which are not retrieved yet
+    /// are pinned ahead of the retrieval.
+    #[display("pin_many(...)")]
+    PinMany(Vec<ContainerId>),
+
+    /// Remove pins from the containers.
+    #[display("unpin_many(...)")]
+    UnpinMany(ContainerBatch),
+
+    /// Evict containers from the node storage, so they are no longer listed and served to the
+    /// remote peers. The batch is applied only if all of the containers are known to the node
+    /// and none of them is pinned.
+    #[display("evict_many(...)")]
+    EvictMany(ContainerBatch),
+
+    /// Collect the chunks not referenced by any of the stored containers for the grace period.
+    /// With the dry run flag set, the node only reports the chunks which would be collected.
+    #[display("collect_garbage(dry_run: {0})")]
+    CollectGarbage(bool),
+
+    /// Stop the child daemons and the node, persisting their state.
+    #[display("shutdown()")]
+    Shutdown,
+
+    /// Put the node into maintenance, rejecting new transfer requests of the remote peers with
+    /// the given retry hint, in seconds, while the transfers in progress drain.
+    #[display("enter_maintenance({0})")]
+    EnterMaintenance(u32),
+
+    /// Resume serving the remote peers after maintenance.
+    #[display("exit_maintenance()")]
+    ExitMaintenance,
+
+    /// Replace the key signing the manifests and the attestations of the node.
+    #[display("rotate_manifest_key()")]
+    RotateManifestKey,
+
+    /// List admin actions awaiting approvals.
+    #[display("list_proposals({0})")]
+    ListProposals(PageReq),
+
+    /// Approve admin action with the signature of an operator key. The action is executed once
+    /// it is approved by the required number of keys.
+    #[display("approve(...)")]
+    Approve(Approval),
+
+    /// List records of the audit log of the admin actions.
+    #[display("list_audit({0})")]
+    ListAudit(PageReq),
+
+    /// Create a share token granting read access to a container.
+    #[display("create_share({0})")]
+    CreateShare(ShareRequest),
+
+    /// Revoke previously issued share token with a given id.
+    #[display("revoke_share({0:#018x})")]
+    RevokeShare(u64),
+
+    /// List all share tokens issued by the node.
+    #[display("list_shares({0})")]
+    ListShares(PageReq),
+
+    /// Verify share token presented by a third party.
+    #[display("verify_share(...)")]
+    VerifyShare(ShareToken),
+
     // Responses to CLI
     // ----------------
+    #[display("node_info({0})")]
+    NodeInfo(NodeInfo),
+
+    #[display("daemons({0})")]
+    Daemons(Page<DaemonInfo>),
+
+    #[display("peer_apps({0})")]
+    PeerApps(Page<StormApp>),
+
+    #[display("peer_topics({0})")]
+    PeerTopics(Page<TopicId>),
+
+    #[display("admin_outcome({0})")]
+    AdminOutcome(AdminOutcome),
+
+    #[display("batch_result({0})")]
+    BatchResult(BatchResult),
+
+    #[display("gc_report({0})")]
+    GcReport(GcReport),
+
+    #[display("proposals({0})")]
+    Proposals(Page<ProposalInfo>),
+
+    #[display("approval_outcome({0})")]
+    ApprovalOutcome(ApprovalOutcome),
+
+    #[display("audit_log({0})")]
+    AuditLog(Page<AuditRecord>),
+
+    #[display("polled_events({0})")]
+    PolledEvents(PolledEvents),
+
+    #[display("job({0:#018x})")]
+    Job(JobId),
+
+    #[display("job_info({0})")]
+    JobInfo(JobInfo),
+
+    #[display("jobs({0})")]
+    Jobs(Page<JobInfo>),
+
+    #[display("sessions({0})")]
+    Sessions(Page<SessionInfo>),
+
+    #[display("container_stats({0})")]
+    ContainerStats(Page<ContainerStats>),
+
+    #[display("containers({0})")]
+    Containers(Page<ContainerEntry>),
+
+    #[display("transfers({0})")]
+    Transfers(Page<TransferEntry>),
+
+    #[display("peer_stats({0})")]
+    PeerStats(Page<PeerStats>),
+
+    #[display("stats({0})")]
+    Stats(NodeStats),
+
+    #[display("health({0})")]
+    Health(Health),
+
+    #[display("transfer_progress({0})")]
+    TransferProgress(TransferProgress),
+
+    #[display("replication_status(...)")]
+    ReplicationStatus(ReplicationStatus),
+
+    #[display("peer_attestation(...)")]
+    PeerAttestation(Option<SignedAttestation>),
+
+    #[display("peer_features(...)")]
+    PeerFeatures(Option<NodeFeatures>),
+
+    #[display("quarantine({0})")]
+    Quarantine(Page<QuarantinedContainer>),
+
+    #[display("bans(...)")]
+    Bans(BTreeSet<NodeId>),
+
+    #[display("rate_limits(...)")]
+    RateLimits(RateLimits),
+
+    #[display("memory({0})")]
+    Memory(MemoryReport),
+
+    #[display("faults(...)")]
+    Faults(Vec<Fault>),
+
+    #[display("app_usage({0})")]
+    AppUsage(Page<AppUsage>),
+
+    #[display("backup({0})")]
+    Backup(BackupInfo),
+
+    #[display("manifest({0})")]
+    Manifest(ManifestInfo),
+
+    #[display("chat_notify(...)")]
+    ChatNotify(BTreeMap<NodeId, NotifyPref>),
+
+    #[display("chat_contacts(...)")]
+    ChatContacts(Vec<ChatContact>),
+
+    #[display("chat_safety({0})")]
+    ChatSafety(String),
+
+    #[display("chat_blocked(...)")]
+    ChatBlocked(BTreeSet<NodeId>),
+
+    #[display("chat_draft(...)")]
+    ChatDraft(Option<String>),
+
+    #[display("chat_matches({0})")]
+    ChatMatches(Page<ChatMatch>),
+
+    #[display("chat_entries({0})")]
+    ChatEntries(Page<ChatEntry>),
+
+    #[display("mail_sent({0})")]
+    MailSent(MailId),
+
+    #[display("mail_list({0})")]
+    MailList(Page<MailEntry>),
+
+    #[display("mail_read({0})")]
+    MailRead(MailEntry),
+
+    #[display("board_created({0})")]
+    BoardCreated(TopicId),
+
+    #[display("boards({0})")]
+    Boards(Page<BoardSummary>),
+
+    #[display("board_posts({0})")]
+    BoardPosts(Page<BoardPost>),
+
+    #[display("board_posted({0})")]
+    BoardPosted(PostId),
+
+    #[display("share_token(...)")]
+    ShareToken(ShareToken),
+
+    #[display("shares({0})")]
+    Shares(Page<ShareInfo>),
+
     #[display("progress(\"{0}\")")]
     #[from]
     Progress(String),
@@ -84,7 +645,11 @@ pub enum RpcMsg {
 pub enum RadioMsg {
     #[display("recv_chat({0})")]
     #[from]
-    Received(AddressedMsg<String>),
+    Received(AddressedMsg<ChatEntry>),
+
+    /// The key bound to a chat contact has changed.
+    #[display("key_changed({0})")]
+    KeyChanged(KeyChange),
 }
 
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, NetworkEncode, NetworkDecode)]
@@ -122,6 +687,428 @@ pub struct AppContainer {
     pub container_id: ContainerFullId,
 }
 
+/// Client-supplied identifier of a mutating RPC request.
+pub type RequestId = u64;
+
+/// RPC request wrapped with a client-supplied request id.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display("{request_id:#018x}, {request}")]
+pub struct Idempotent {
+    pub request_id: RequestId,
+    pub request: Box<RpcMsg>,
+}
+
+// Since `RpcMsg` nests itself, we have to use trait objects to stop generic type recursion
+impl StrictEncode for Idempotent {
+    fn strict_encode<E: io::Write>(&self, e: E) -> Result<usize, strict_encoding::Error> {
+        let mut e: Box<dyn io::Write + '_> = Box::new(e);
+        Ok(strict_encode_list!(e; self.request_id, self.request.as_ref()))
+    }
+}
+
+impl StrictDecode for Idempotent {
+    fn strict_decode<D: io::Read>(d: D) -> Result<Self, strict_encoding::Error> {
+        let mut d: Box<dyn io::Read + '_> = Box::new(d);
+        Ok(Idempotent {
+            request_id: StrictDecode::strict_decode(&mut d)?,
+            request: Box::new(RpcMsg::strict_decode(&mut d)?),
+        })
+    }
+}
+
+/// Information about the node state.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(
+    "stormd {version}, up for {uptime} seconds, {transfer_daemons} transfer daemons, {p2p_queue} \
+     queued p2p messages"
+)]
+pub struct NodeInfo {
+    /// Version of the node.
+    pub version: String,
+    /// Number of seconds since the node start.
+    pub uptime: u64,
+    /// Applications registered with the node.
+    pub apps: BTreeSet<StormApp>,
+    /// Number of connected transfer daemons.
+    pub transfer_daemons: u16,
+    /// Daemons which have connected to the node.
+    pub daemons: Vec<ServiceId>,
+    /// Number of the remote peers the node has exchanged messages with.
+    pub peers: u32,
+    /// Number of the indexed containers.
+    pub containers: u32,
+    /// Total size of the indexed containers, in bytes.
+    pub storage: u64,
+    /// Whether the connection to the LNP node is lost. In this state outbound messages to the
+    /// remote peers are queued until the connection is restored.
+    pub degraded: bool,
+    /// Number of outbound p2p messages waiting for the connection to the LNP node.
+    pub p2p_queue: u32,
+    /// Summary of the store repair, if it was run on the node start after an unclean shutdown.
+    pub repair: Option<RepairSummary>,
+    /// Features advertised by the node to the remote peers.
+    pub features: NodeFeatures,
+    /// Whether the node is in maintenance, rejecting new transfer requests of the remote peers.
+    pub maintenance: bool,
+}
+
+/// Progress of a container retrieval.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id}: {stored} of {chunks} chunks are stored")]
+pub struct TransferProgress {
+    pub container_id: ContainerId,
+    /// Number of the container chunks; zero if the container manifest was not received yet.
+    pub chunks: u32,
+    /// Number of the container chunks kept in the store.
+    pub stored: u32,
+    /// Whether the container is being retrieved by a transfer daemon.
+    pub active: bool,
+    /// Whether the retrieval was interrupted and is resumed once the remote peer shows up.
+    pub interrupted: bool,
+}
+
+/// Container transfer to or from a remote peer.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct TransferEntry {
+    pub container_id: ContainerId,
+    /// Application which requested or sent the container, if known.
+    pub app: Option<StormApp>,
+    /// Remote peer the container is retrieved from or sent to, if known.
+    pub peer: Option<NodeId>,
+    /// Size of the container data, in bytes; zero if the container manifest was not received yet.
+    pub size: u64,
+    /// UNIX timestamp of the transfer start, or zero if unknown.
+    pub timestamp: u64,
+    /// Whether the retrieval was interrupted and is resumed once the remote peer shows up.
+    pub interrupted: bool,
+}
+
+impl Display for TransferEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} bytes", self.container_id, self.size)?;
+        if self.interrupted {
+            f.write_str(", interrupted")?;
+        }
+        Ok(())
+    }
+}
+
+impl Listed for TransferEntry {
+    fn app(&self) -> Option<StormApp> { self.app }
+    fn peer(&self) -> Option<NodeId> { self.peer }
+    fn size(&self) -> u64 { self.size }
+    fn timestamp(&self) -> u64 { self.timestamp }
+}
+
+/// Daemon registered with the node.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{service_id} since {since}, last seen at {last_seen}")]
+pub struct DaemonInfo {
+    pub service_id: ServiceId,
+    /// UNIX timestamp of the daemon registration.
+    pub since: u64,
+    /// UNIX timestamp of the last message received from the daemon.
+    pub last_seen: u64,
+}
+
+/// Information about the backup of the node index.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{path}: {containers} containers and {pins} pins at {timestamp}")]
+pub struct BackupInfo {
+    /// Path to the backup file on the node host.
+    pub path: String,
+    /// UNIX timestamp of the index snapshot.
+    pub timestamp: u64,
+    /// Number of the containers in the snapshot.
+    pub containers: u32,
+    /// Number of the pinned containers in the snapshot.
+    pub pins: u32,
+}
+
+/// Summary of the store repair run after an unclean shutdown of the node.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Default, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(
+    "{headers_restored} headers restored, {partial_discarded} partial writes discarded, \
+     {incomplete} incomplete containers, {corrupted_chunks} corrupted and {orphan_chunks} orphan \
+     chunks"
+)]
+pub struct RepairSummary {
+    /// Container headers restored from the containers stored without them.
+    pub headers_restored: u32,
+    /// Containers with a header, but without valid container data, excluded from the index.
+    pub partial_discarded: u32,
+    /// Containers missing some of their chunks.
+    pub incomplete: u32,
+    /// Chunks which data does not match their ids.
+    pub corrupted_chunks: u32,
+    /// Chunks not referenced by any of the stored containers.
+    pub orphan_chunks: u32,
+}
+
+/// Result of the garbage collection of the orphaned chunks.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Default, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(
+    "{chunks} chunks with {references} references, {orphaned} orphaned, {collected} collected \
+     ({collected_bytes} bytes)"
+)]
+pub struct GcReport {
+    /// Chunks kept in the store.
+    pub chunks: u32,
+    /// References to the chunks from the stored containers.
+    pub references: u64,
+    /// Chunks not referenced by any of the containers, which are within the grace period.
+    pub orphaned: u32,
+    /// Chunks collected by this run.
+    pub collected: u32,
+    /// Size of the collected chunk data, in bytes.
+    pub collected_bytes: u64,
+    /// Ids of the collected chunks.
+    pub collected_chunks: Vec<ChunkId>,
+}
+
+/// Result of a single node readiness check.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct HealthCheck {
+    pub name: String,
+    pub passed: bool,
+    /// Reason of the check failure.
+    pub details: Option<String>,
+}
+
+impl Display for HealthCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, if self.passed { "ok" } else { "failed" })?;
+        if let Some(details) = &self.details {
+            write!(f, " ({})", details)?;
+        }
+        Ok(())
+    }
+}
+
+/// Node health. The node is ready when all of its checks have passed.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Health {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl Display for Health {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.is_ready() { "ready" } else { "not ready" })
+    }
+}
+
+impl Health {
+    pub fn is_ready(&self) -> bool { self.checks.iter().all(|check| check.passed) }
+}
+
+/// Aggregate counters of the node activity.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Default, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(
+    "{messages_received} messages received ({bytes_received} bytes), {messages_sent} sent \
+     ({bytes_sent} bytes), {transfers_completed} transfers completed, {transfers_failed} failed, \
+     uptime {uptime} s"
+)]
+pub struct NodeCounters {
+    /// Number of p2p messages received from the remote peers.
+    pub messages_received: u64,
+    /// Number of p2p messages sent to the remote peers.
+    pub messages_sent: u64,
+    /// Size of the p2p messages received from the remote peers, in bytes.
+    pub bytes_received: u64,
+    /// Size of the p2p messages sent to the remote peers, in bytes.
+    pub bytes_sent: u64,
+    /// Number of container transfers which were completed.
+    pub transfers_completed: u64,
+    /// Number of container transfers which have failed.
+    pub transfers_failed: u64,
+    /// Time the node was running, in seconds.
+    pub uptime: u64,
+}
+
+impl NodeCounters {
+    /// Adds up two sets of counters.
+    pub fn sum(self, other: NodeCounters) -> NodeCounters {
+        NodeCounters {
+            messages_received: self.messages_received + other.messages_received,
+            messages_sent: self.messages_sent + other.messages_sent,
+            bytes_received: self.bytes_received + other.bytes_received,
+            bytes_sent: self.bytes_sent + other.bytes_sent,
+            transfers_completed: self.transfers_completed + other.transfers_completed,
+            transfers_failed: self.transfers_failed + other.transfers_failed,
+            uptime: self.uptime + other.uptime,
+        }
+    }
+}
+
+/// Node activity counters accumulated over the node lifetime, which persist across restarts,
+/// and since the node start.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("lifetime: {lifetime}; since boot: {since_boot}")]
+pub struct NodeStats {
+    pub lifetime: NodeCounters,
+    pub since_boot: NodeCounters,
+}
+
+/// Request statistics for a single container, used by the node to drive caching decisions.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id}: {requests} requests, {chunk_requests} chunk requests")]
+pub struct ContainerStats {
+    pub container_id: ContainerId,
+    /// Number of times the container was requested by remote peers or local clients.
+    pub requests: u64,
+    /// Number of chunk pull requests for the container chunks.
+    pub chunk_requests: u64,
+    /// UNIX timestamp of the most recent request.
+    pub last_request: u64,
+    /// Whether the container is kept in the node cache on behalf of other peers.
+    pub cached: bool,
+    /// Whether the container was pinned, i.e. is excluded from cache eviction.
+    pub pinned: bool,
+}
+
+/// Performance of a remote peer measured by the node.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct PeerStats {
+    pub remote_id: NodeId,
+    /// Smoothed round-trip time, in milliseconds, if it was measured.
+    pub rtt: Option<u32>,
+    /// Smoothed chunk transfer throughput, in bytes per second, if it was measured.
+    pub throughput: Option<u64>,
+    /// Number of probes sent to the peer.
+    pub probes: u32,
+    /// Number of probes the peer has replied to.
+    pub replies: u32,
+    /// Number of chunks received from the peer which have failed the integrity verification.
+    pub corrupt_chunks: u32,
+}
+
+impl Display for PeerStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.remote_id)?;
+        match self.rtt {
+            Some(rtt) => write!(f, "rtt {} ms", rtt)?,
+            None => f.write_str("rtt unknown")?,
+        }
+        if let Some(throughput) = self.throughput {
+            write!(f, ", {} bytes/s", throughput)?;
+        }
+        write!(f, ", {}/{} probes replied", self.replies, self.probes)?;
+        if self.corrupt_chunks > 0 {
+            write!(f, ", {} corrupt chunks", self.corrupt_chunks)?;
+        }
+        Ok(())
+    }
+}
+
+/// Container retrieved from a flagged peer, which is hidden until the node operator releases it.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id} from {peer}, quarantined at {timestamp}")]
+pub struct QuarantinedContainer {
+    pub container_id: ContainerId,
+    pub peer: NodeId,
+    /// UNIX timestamp of the container retrieval.
+    pub timestamp: u64,
+}
+
+/// Declaration of the previous version of a container.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id} based on {base}")]
+pub struct ContainerVersion {
+    pub container_id: ContainerId,
+    /// Previous version of the container.
+    pub base: ContainerId,
+}
+
+/// Storage used by the containers referenced by an app.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{app}: {containers} containers, {bytes} bytes ({shared_bytes} bytes shared)")]
+pub struct AppUsage {
+    pub app: StormApp,
+    /// Number of the containers referenced by the app.
+    pub containers: u32,
+    /// Total size of the containers referenced by the app.
+    pub bytes: u64,
+    /// Size of the containers which are also referenced by other apps. These containers are
+    /// stored once, but are accounted to each of the apps.
+    pub shared_bytes: u64,
+}
+
+/// Message published to the topic of an external (e.g. MQTT) broker.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct BridgeMsg {
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+impl Display for BridgeMsg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {} bytes", self.topic, self.payload.len())
+    }
+}
+
+/// Change of a file in a folder replicated by the folder sync daemon.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct FolderChange {
+    pub folder: String,
+    /// Path of the file relative to the folder, with `/` as a separator.
+    pub path: String,
+    /// Container with the new file content, or `None` if the file was removed.
+    pub container_id: Option<ContainerId>,
+    /// UNIX timestamp of the change.
+    pub modified: u64,
+}
+
+impl Display for FolderChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.folder, self.path)?;
+        match self.container_id {
+            Some(container_id) => write!(f, " changed to {}", container_id),
+            None => f.write_str(" removed"),
+        }
+    }
+}
+
+/// Information about a container known to the node.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id}: {size} bytes")]
+pub struct ContainerEntry {
+    pub container_id: ContainerId,
+    /// Application which requested or sent the container, if known.
+    pub app: Option<StormApp>,
+    /// Remote peer the container was retrieved from or sent to, if known.
+    pub peer: Option<NodeId>,
+    /// Size of the container data, in bytes.
+    pub size: u64,
+    /// UNIX timestamp of the last transfer of the container, or zero if unknown.
+    pub timestamp: u64,
+}
+
+impl Listed for ContainerEntry {
+    fn app(&self) -> Option<StormApp> { self.app }
+    fn peer(&self) -> Option<NodeId> { self.peer }
+    fn size(&self) -> u64 { self.size }
+    fn timestamp(&self) -> u64 { self.timestamp }
+}
+
 impl From<presentation::Error> for RpcMsg {
     fn from(err: presentation::Error) -> Self {
         RpcMsg::Failure(rpc::Failure {
@@ -0,0 +1,186 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+
+use strict_encoding::{StrictDecode, StrictEncode};
+
+/// Number of items returned in a page when the client does not specify a limit.
+pub const DEFAULT_PAGE_SIZE: u16 = 100;
+/// Maximal number of items which may be returned in a single page.
+pub const MAX_PAGE_SIZE: u16 = 1000;
+
+/// Opaque position inside a list returned by the node. Clients must not interpret the cursor and
+/// should only pass it back to the node to retrieve the next page.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Cursor(Vec<u8>);
+
+impl Cursor {
+    pub(crate) fn with(key: &impl StrictEncode) -> Self {
+        Cursor(key.strict_serialize().expect("memory encoding of cursor key does not fail"))
+    }
+
+    fn key<K: StrictDecode>(&self) -> Option<K> { K::strict_deserialize(&self.0).ok() }
+}
+
+/// Page request parameters for list-returning RPC calls.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct PageReq {
+    /// Cursor returned with the previous page; `None` for the first page.
+    pub cursor: Option<Cursor>,
+    /// Maximum number of items in the page; zero means [`DEFAULT_PAGE_SIZE`]. Values above
+    /// [`MAX_PAGE_SIZE`] are capped by the node.
+    pub limit: u16,
+}
+
+impl Display for PageReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "limit {}", self.limit())?;
+        if self.cursor.is_some() {
+            f.write_str(", continued")?;
+        }
+        Ok(())
+    }
+}
+
+impl PageReq {
+    /// Request for the page following the given one. Returns `None` if there are no more pages.
+    pub fn next<T>(&self, page: &Page<T>) -> Option<PageReq>
+    where T: StrictEncode + StrictDecode {
+        page.next.clone().map(|cursor| PageReq {
+            cursor: Some(cursor),
+            limit: self.limit,
+        })
+    }
+
+    /// Effective page size.
+    pub fn limit(&self) -> usize {
+        let limit = match self.limit {
+            0 => DEFAULT_PAGE_SIZE,
+            limit => limit.min(MAX_PAGE_SIZE),
+        };
+        limit as usize
+    }
+}
+
+/// Parameters of a list-returning RPC call together with the page request.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Paged<T>
+where T: StrictEncode + StrictDecode
+{
+    pub query: T,
+    pub page: PageReq,
+}
+
+impl<T> Display for Paged<T>
+where T: Display + StrictEncode + StrictDecode
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.query, self.page)
+    }
+}
+
+impl<T> Paged<T>
+where T: StrictEncode + StrictDecode
+{
+    /// Request for the first page.
+    pub fn with(query: T) -> Self {
+        Paged {
+            query,
+            page: PageReq::default(),
+        }
+    }
+
+    /// Request for the page following the given one. Returns `None` if there are no more pages.
+    pub fn next<U>(&self, page: &Page<U>) -> Option<Paged<T>>
+    where
+        T: Clone,
+        U: StrictEncode + StrictDecode,
+    {
+        self.page.next(page).map(|req| Paged {
+            query: self.query.clone(),
+            page: req,
+        })
+    }
+}
+
+/// Single page of a list-returning RPC call reply.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Page<T>
+where T: StrictEncode + StrictDecode
+{
+    pub items: Vec<T>,
+    /// Cursor for the next page; `None` if this is the last page.
+    pub next: Option<Cursor>,
+}
+
+impl<T> Display for Page<T>
+where T: StrictEncode + StrictDecode
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} items", self.items.len())?;
+        if self.next.is_some() {
+            f.write_str(", more available")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Page<T>
+where T: StrictEncode + StrictDecode
+{
+    /// Constructs page out of items ordered by a unique key, starting after the key stored in
+    /// the request cursor.
+    pub fn by_key<K>(
+        items: impl IntoIterator<Item = T>,
+        req: &PageReq,
+        key: impl Fn(&T) -> K,
+    ) -> Self
+    where
+        K: Ord + StrictEncode + StrictDecode,
+    {
+        let after = req.cursor.as_ref().and_then(Cursor::key::<K>);
+        let items =
+            items.into_iter().filter(|item| after.as_ref().map_or(true, |k| key(item) > *k));
+        Self::with(items, req, |item| Cursor::with(&key(item)))
+    }
+
+    /// Constructs page out of items which have no unique ordering key, using the item position as
+    /// the cursor.
+    pub fn by_offset(items: impl IntoIterator<Item = T>, req: &PageReq) -> Self {
+        let offset = req.cursor.as_ref().and_then(Cursor::key::<u64>).unwrap_or_default();
+        let items = items.into_iter().skip(offset as usize);
+        let mut page = Self::with(items, req, |_| Cursor::default());
+        if page.next.is_some() {
+            page.next = Some(Cursor::with(&(offset + page.items.len() as u64)));
+        }
+        page
+    }
+
+    fn with(
+        items: impl IntoIterator<Item = T>,
+        req: &PageReq,
+        cursor: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let limit = req.limit();
+        let mut items = items.into_iter().take(limit + 1).collect::<Vec<_>>();
+        let next = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(cursor)
+        } else {
+            None
+        };
+        Page { items, next }
+    }
+}
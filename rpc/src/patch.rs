@@ -0,0 +1,182 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+
+use storm::{ContainerHeader, ContainerId};
+use strict_encoding::LargeVec;
+
+/// MIME type of the containers keeping a [`Patch`].
+pub const PATCH_MIME: &str = "application/x-storm-patch";
+
+/// Size of the blocks of the base data which are looked up in the patch target.
+pub const PATCH_BLOCK_SIZE: usize = 32;
+
+const HASH_BASE: u64 = 0x100000001b3;
+
+/// Operation reconstructing a part of the patch target.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub enum PatchOp {
+    /// Copy a range of the base data.
+    Copy { offset: u64, length: u64 },
+
+    /// Insert data absent from the base.
+    Insert(LargeVec<u8>),
+}
+
+/// Binary diff of a container against its base container, which is distributed as a container
+/// of [`PATCH_MIME`] type.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct Patch {
+    pub base: ContainerId,
+    /// Id of the container reconstructed by the patch.
+    pub target: ContainerId,
+    /// Header of the container reconstructed by the patch.
+    pub header: ContainerHeader,
+    pub ops: LargeVec<PatchOp>,
+}
+
+/// Errors applying [`Patch`] to the base data.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum PatchError {
+    /// patch copies {len} bytes at offset {offset} outside of the base data of {size} bytes
+    OutOfRange { offset: u64, len: u64, size: usize },
+
+    /// patch reconstructs {actual} bytes, while the target container has {expected} bytes
+    SizeMismatch { expected: u64, actual: usize },
+}
+
+impl Patch {
+    /// Computes patch reconstructing the `target` data from the `base` data.
+    ///
+    /// Blocks of the base data are indexed by their rolling hash and looked up at each position of
+    /// the target; matches are extended forward as long as the data coincide.
+    pub fn diff(
+        base_id: ContainerId,
+        base: &[u8],
+        target_id: ContainerId,
+        header: ContainerHeader,
+        target: &[u8],
+    ) -> Patch {
+        let mut index = HashMap::<u64, usize>::new();
+        for offset in (0..base.len().saturating_sub(PATCH_BLOCK_SIZE - 1)).step_by(PATCH_BLOCK_SIZE)
+        {
+            index.entry(block_hash(&base[offset..offset + PATCH_BLOCK_SIZE])).or_insert(offset);
+        }
+        // Weight of the byte leaving the rolling hash window
+        let lead = (1..PATCH_BLOCK_SIZE).fold(1u64, |acc, _| acc.wrapping_mul(HASH_BASE));
+
+        let mut ops = vec![];
+        let mut literal = 0usize;
+        let mut pos = 0usize;
+        let mut hash = None;
+        while pos + PATCH_BLOCK_SIZE <= target.len() {
+            let h = *hash.get_or_insert_with(|| block_hash(&target[pos..pos + PATCH_BLOCK_SIZE]));
+            let found = index.get(&h).copied().filter(|offset| {
+                base[*offset..*offset + PATCH_BLOCK_SIZE] == target[pos..pos + PATCH_BLOCK_SIZE]
+            });
+            match found {
+                Some(offset) => {
+                    let mut len = PATCH_BLOCK_SIZE;
+                    while offset + len < base.len()
+                        && pos + len < target.len()
+                        && base[offset + len] == target[pos + len]
+                    {
+                        len += 1;
+                    }
+                    push_insert(&mut ops, &target[literal..pos]);
+                    push_copy(&mut ops, offset as u64, len as u64);
+                    pos += len;
+                    literal = pos;
+                    hash = None;
+                }
+                None => {
+                    if pos + PATCH_BLOCK_SIZE < target.len() {
+                        hash = Some(
+                            h.wrapping_sub((target[pos] as u64).wrapping_mul(lead))
+                                .wrapping_mul(HASH_BASE)
+                                .wrapping_add(target[pos + PATCH_BLOCK_SIZE] as u64),
+                        );
+                    }
+                    pos += 1;
+                }
+            }
+        }
+        push_insert(&mut ops, &target[literal..]);
+
+        Patch {
+            base: base_id,
+            target: target_id,
+            header,
+            ops: LargeVec::try_from(ops).expect("number of patch operations is below u32::MAX"),
+        }
+    }
+
+    /// Reconstructs the patch target from the base data. The caller must check the id of the
+    /// container with the reconstructed data to match [`Patch::target`].
+    pub fn apply(&self, base: &[u8]) -> Result<Vec<u8>, PatchError> {
+        let mut data = Vec::with_capacity(self.header.size as usize);
+        for op in &self.ops {
+            match op {
+                PatchOp::Copy { offset, length } => {
+                    let range = usize::try_from(*offset)
+                        .ok()
+                        .zip(usize::try_from(*length).ok())
+                        .and_then(|(start, len)| Some(start..start.checked_add(len)?))
+                        .filter(|range| range.end <= base.len())
+                        .ok_or(PatchError::OutOfRange {
+                            offset: *offset,
+                            len: *length,
+                            size: base.len(),
+                        })?;
+                    data.extend_from_slice(&base[range]);
+                }
+                PatchOp::Insert(bytes) => data.extend(bytes),
+            }
+            if data.len() as u64 > self.header.size {
+                break;
+            }
+        }
+        if data.len() as u64 != self.header.size {
+            return Err(PatchError::SizeMismatch {
+                expected: self.header.size,
+                actual: data.len(),
+            });
+        }
+        Ok(data)
+    }
+}
+
+fn block_hash(block: &[u8]) -> u64 {
+    block.iter().fold(0u64, |hash, byte| hash.wrapping_mul(HASH_BASE).wrapping_add(*byte as u64))
+}
+
+fn push_insert(ops: &mut Vec<PatchOp>, bytes: &[u8]) {
+    for piece in bytes.chunks(u32::MAX as usize) {
+        ops.push(PatchOp::Insert(
+            LargeVec::try_from(piece.to_vec()).expect("piece size is below u32::MAX"),
+        ));
+    }
+}
+
+fn push_copy(ops: &mut Vec<PatchOp>, offset: u64, length: u64) {
+    if let Some(PatchOp::Copy {
+        offset: prev_offset,
+        length: prev_length,
+    }) = ops.last_mut()
+    {
+        if *prev_offset + *prev_length == offset {
+            *prev_length += length;
+            return;
+        }
+    }
+    ops.push(PatchOp::Copy { offset, length });
+}
@@ -0,0 +1,146 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use internet2::addr::NodeId;
+use storm::StormApp;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::{Page, PageReq};
+
+/// Item of a list which can be filtered and sorted by the node.
+pub trait Listed {
+    fn app(&self) -> Option<StormApp>;
+    fn peer(&self) -> Option<NodeId>;
+    fn size(&self) -> u64;
+    fn timestamp(&self) -> u64;
+}
+
+/// Filter applied by the node to list-returning RPC calls. Empty fields match any item.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct Filter {
+    pub app: Option<StormApp>,
+    pub peer: Option<NodeId>,
+    /// Minimal size of the item data, in bytes.
+    pub min_size: Option<u64>,
+    /// Maximal size of the item data, in bytes.
+    pub max_size: Option<u64>,
+    /// UNIX timestamp of the earliest matching item.
+    pub since: Option<u64>,
+    /// UNIX timestamp of the latest matching item.
+    pub until: Option<u64>,
+}
+
+impl Filter {
+    pub fn matches(&self, item: &impl Listed) -> bool {
+        (self.app.is_none() || self.app == item.app())
+            && (self.peer.is_none() || self.peer == item.peer())
+            && self.min_size.map_or(true, |size| item.size() >= size)
+            && self.max_size.map_or(true, |size| item.size() <= size)
+            && self.since.map_or(true, |ts| item.timestamp() >= ts)
+            && self.until.map_or(true, |ts| item.timestamp() <= ts)
+    }
+}
+
+/// Ordering of the list items.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum SortBy {
+    /// Natural order of the list, which is specific to each RPC call.
+    #[display("default")]
+    Default,
+
+    #[display("size")]
+    Size,
+
+    #[display("timestamp")]
+    Timestamp,
+}
+
+impl Default for SortBy {
+    fn default() -> Self { SortBy::Default }
+}
+
+/// Error parsing [`SortBy`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownSortOrder(String);
+
+impl Display for UnknownSortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown sort order `{}`; allowed values are `default`, `size` and `timestamp`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for SortBy {
+    type Err = UnknownSortOrder;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(SortBy::Default),
+            "size" => Ok(SortBy::Size),
+            "timestamp" => Ok(SortBy::Timestamp),
+            other => Err(UnknownSortOrder(other.to_owned())),
+        }
+    }
+}
+
+/// Filtering, sorting and pagination parameters of list-returning RPC calls.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ListQuery {
+    pub filter: Filter,
+    pub sort: SortBy,
+    pub descending: bool,
+    pub page: PageReq,
+}
+
+impl Display for ListQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "sort by {}", self.sort)?;
+        if self.descending {
+            f.write_str(" desc")?;
+        }
+        write!(f, ", {}", self.page)
+    }
+}
+
+impl ListQuery {
+    /// Query for the page following the given one. Returns `None` if there are no more pages.
+    pub fn next<T>(&self, page: &Page<T>) -> Option<ListQuery>
+    where T: StrictEncode + StrictDecode {
+        self.page.next(page).map(|req| ListQuery {
+            page: req,
+            ..self.clone()
+        })
+    }
+
+    /// Filters and sorts items given in their natural order, returning the requested page.
+    pub fn apply<T>(&self, items: impl IntoIterator<Item = T>) -> Page<T>
+    where T: Listed + StrictEncode + StrictDecode {
+        let mut items =
+            items.into_iter().filter(|item| self.filter.matches(item)).collect::<Vec<_>>();
+        match self.sort {
+            SortBy::Default => {}
+            SortBy::Size => items.sort_by_key(T::size),
+            SortBy::Timestamp => items.sort_by_key(T::timestamp),
+        }
+        if self.descending {
+            items.reverse();
+        }
+        Page::by_offset(items, &self.page)
+    }
+}
@@ -0,0 +1,92 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+
+/// Request to replicate the stored container to the storage peers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ReplicateRequest {
+    pub container_id: ContainerId,
+    /// Storage peers which are asked to keep the container
+    pub peers: BTreeSet<NodeId>,
+    /// Number of the confirmed replicas for the replication to succeed
+    pub min_replicas: u8,
+}
+
+impl Display for ReplicateRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {} of {} peers", self.container_id, self.min_replicas, self.peers.len())
+    }
+}
+
+/// State of the container replica on a storage peer.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum ReplicaState {
+    /// Peer is asked to keep the container and has not confirmed it yet.
+    #[display("pending")]
+    Pending,
+
+    /// Peer has retrieved and pinned the container.
+    #[display("confirmed")]
+    Confirmed,
+
+    /// Peer has refused to keep the container.
+    #[display("declined")]
+    Declined,
+
+    /// Peer has failed to retrieve the container, or has not confirmed it in time.
+    #[display("failed")]
+    Failed,
+}
+
+/// Status of the container replication to the storage peers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ReplicationStatus {
+    pub container_id: ContainerId,
+    pub min_replicas: u8,
+    pub replicas: BTreeMap<NodeId, ReplicaState>,
+}
+
+impl ReplicationStatus {
+    pub fn confirmed(&self) -> usize {
+        self.replicas.values().filter(|state| **state == ReplicaState::Confirmed).count()
+    }
+
+    /// Detects whether the container has reached the required number of the replicas.
+    pub fn is_satisfied(&self) -> bool { self.confirmed() >= self.min_replicas as usize }
+
+    /// Detects whether none of the replicas are pending, so the status won't change anymore.
+    pub fn is_settled(&self) -> bool {
+        !self.replicas.values().any(|state| *state == ReplicaState::Pending)
+    }
+}
+
+impl Display for ReplicationStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} of {} required replicas confirmed",
+            self.container_id,
+            self.confirmed(),
+            self.min_replicas
+        )?;
+        for (peer, state) in &self.replicas {
+            write!(f, "\n{} {}", peer, state)?;
+        }
+        Ok(())
+    }
+}
@@ -19,6 +19,16 @@ use strict_encoding::{strict_deserialize, strict_serialize};
 
 pub type DaemonId = u64;
 
+/// Code of the mail app shipped with the node. The app is not standardized by LNP/BP Standards
+/// Association, so it uses a code from the vendor-specific range.
+pub const STORM_APP_MAIL: u16 = 0x8001;
+
+/// Code of the public board app shipped with the node, from the vendor-specific range.
+pub const STORM_APP_BOARD: u16 = 0x8002;
+
+/// Code of the folder sync app shipped with the node, from the vendor-specific range.
+pub const STORM_APP_SYNC: u16 = 0x8003;
+
 /// Identifiers of daemons participating in LNP Node
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Display, From, StrictEncode, StrictDecode)]
 pub enum ServiceId {
@@ -62,6 +72,9 @@ impl ServiceId {
     pub fn stormd() -> ServiceId { ServiceId::MsgApp(BifrostApp::Storm) }
     pub fn chatd() -> ServiceId { ServiceId::StormApp(StormApp::Chat) }
     pub fn downpourd() -> ServiceId { ServiceId::StormApp(StormApp::FileTransfer) }
+    pub fn maild() -> ServiceId { ServiceId::StormApp(StormApp::Vendor(STORM_APP_MAIL)) }
+    pub fn boardd() -> ServiceId { ServiceId::StormApp(StormApp::Vendor(STORM_APP_BOARD)) }
+    pub fn syncd() -> ServiceId { ServiceId::StormApp(StormApp::Vendor(STORM_APP_SYNC)) }
 }
 
 impl esb::ServiceAddress for ServiceId {}
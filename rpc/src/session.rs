@@ -0,0 +1,30 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use microservices::esb::ClientId;
+
+/// Token issued by the node to an RPC client session.
+///
+/// Client ids are chosen by the clients themselves, so the sessions are addressed by the tokens
+/// issued by the node.
+pub type SessionToken = u64;
+
+/// Session of an RPC client connected to the node.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{token:#018x}: client {client_id:#018x}, connected at {connected}, {jobs} jobs")]
+pub struct SessionInfo {
+    pub token: SessionToken,
+    pub client_id: ClientId,
+    /// UNIX timestamp of the first request of the client.
+    pub connected: u64,
+    /// Number of the unfinished background jobs submitted by the client.
+    pub jobs: u16,
+}
@@ -0,0 +1,89 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::hex::{FromHex, ToHex};
+use amplify::Slice32;
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+/// Request to create a share token for a container.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{container_id}, {duration}s")]
+pub struct ShareRequest {
+    pub container_id: ContainerId,
+    /// Peer which is granted the access. If absent, the token may be presented by anyone via the
+    /// HTTP gateway.
+    pub grantee: Option<NodeId>,
+    /// Number of seconds the token remains valid.
+    pub duration: u64,
+}
+
+/// Capability token granting read access to a specific container until expiry. The token is
+/// signed by the issuing node and is verified only by it.
+///
+/// Human-readable representation of the token is a hex string of its strict encoding, which is
+/// suitable for use in share links.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct ShareToken {
+    pub id: u64,
+    pub container_id: ContainerId,
+    pub grantee: Option<NodeId>,
+    /// UNIX timestamp after which the token is no longer valid.
+    pub expiry: u64,
+    pub signature: Slice32,
+}
+
+impl ShareToken {
+    /// Returns data committed to by the token signature.
+    pub fn signed_data(&self) -> Result<Vec<u8>, strict_encoding::Error> {
+        let mut data = vec![];
+        strict_encode_list!(data; self.id, self.container_id, self.grantee, self.expiry);
+        Ok(data)
+    }
+}
+
+impl Display for ShareToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let data = self.strict_serialize().expect("memory encoding of share token does not fail");
+        f.write_str(&data.to_hex())
+    }
+}
+
+impl FromStr for ShareToken {
+    type Err = strict_encoding::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = Vec::<u8>::from_hex(s)
+            .map_err(|err| strict_encoding::Error::DataIntegrityError(err.to_string()))?;
+        ShareToken::strict_deserialize(data)
+    }
+}
+
+/// Audit information about an issued share token.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{token_id:#018x}: {container_id}, expires {expiry}, used {uses} times")]
+pub struct ShareInfo {
+    pub token_id: u64,
+    pub container_id: ContainerId,
+    pub grantee: Option<NodeId>,
+    pub expiry: u64,
+    pub revoked: bool,
+    /// Number of times the token was used to access the container.
+    pub uses: u64,
+    /// UNIX timestamp of the last use of the token, if any.
+    pub last_use: Option<u64>,
+}
@@ -0,0 +1,72 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::str::FromStr;
+
+use amplify::hex::FromHex;
+use storm::{MesgId, Topic};
+use strict_encoding::StrictDecode;
+
+/// Identifier of a Storm application topic.
+///
+/// Topics are committed to with the same hash as messages, so on the p2p wire their ids are
+/// represented with [`MesgId`]. Node APIs use this type, keeping the two kinds of identifiers
+/// distinct; conversion to and from the wire representation has to be explicit.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display(inner)]
+pub struct TopicId(MesgId);
+
+impl From<&Topic> for TopicId {
+    fn from(topic: &Topic) -> Self { TopicId(topic.mesg_id()) }
+}
+
+impl TopicId {
+    /// Interprets the id received from the p2p wire as a topic id.
+    pub fn from_wire(id: MesgId) -> Self { TopicId(id) }
+
+    /// Returns representation of the id used on the p2p wire.
+    pub fn to_wire(self) -> MesgId { self.0 }
+
+    /// Checks that the topic is committed to by this id.
+    pub fn verify(self, topic: &Topic) -> Result<(), TopicMismatch> {
+        let actual = TopicId::from(topic);
+        if actual != self {
+            return Err(TopicMismatch {
+                expected: self,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TopicId {
+    type Err = strict_encoding::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { mesg_id_from_str(s).map(TopicId) }
+}
+
+/// Parses message id from its hex representation.
+pub(crate) fn mesg_id_from_str(s: &str) -> Result<MesgId, strict_encoding::Error> {
+    let mut data = Vec::<u8>::from_hex(s)
+        .map_err(|err| strict_encoding::Error::DataIntegrityError(err.to_string()))?;
+    // Tagged hashes are displayed with the reversed byte order
+    data.reverse();
+    MesgId::strict_deserialize(data)
+}
+
+/// topic id {expected} does not match the topic with id {actual}
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub struct TopicMismatch {
+    pub expected: TopicId,
+    pub actual: TopicId,
+}
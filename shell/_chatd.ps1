@@ -27,6 +27,7 @@ Register-ArgumentCompleter -Native -CommandName 'chatd' -ScriptBlock {
             [CompletionResult]::new('--config', 'config', [CompletionResultType]::ParameterName, 'Path for the configuration file')
             [CompletionResult]::new('-M', 'M', [CompletionResultType]::ParameterName, 'ZMQ socket for peer message bus used to communicate with LNP node peerd service')
             [CompletionResult]::new('--msg', 'msg', [CompletionResultType]::ParameterName, 'ZMQ socket for peer message bus used to communicate with LNP node peerd service')
+            [CompletionResult]::new('--bulk-msg', 'bulk-msg', [CompletionResultType]::ParameterName, 'ZMQ socket for the dedicated peer message bus carrying chunk payloads')
             [CompletionResult]::new('-X', 'X', [CompletionResultType]::ParameterName, 'ZMQ socket for internal service control bus')
             [CompletionResult]::new('--ctl', 'ctl', [CompletionResultType]::ParameterName, 'ZMQ socket for internal service control bus')
             [CompletionResult]::new('-R', 'R', [CompletionResultType]::ParameterName, 'ZMQ socket name/address for Storm Node client-server RPC API')
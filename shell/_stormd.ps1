@@ -27,6 +27,7 @@ Register-ArgumentCompleter -Native -CommandName 'stormd' -ScriptBlock {
             [CompletionResult]::new('--config', 'config', [CompletionResultType]::ParameterName, 'Path for the configuration file')
             [CompletionResult]::new('-M', 'M', [CompletionResultType]::ParameterName, 'ZMQ socket for peer message bus used to communicate with LNP node peerd service')
             [CompletionResult]::new('--msg', 'msg', [CompletionResultType]::ParameterName, 'ZMQ socket for peer message bus used to communicate with LNP node peerd service')
+            [CompletionResult]::new('--bulk-msg', 'bulk-msg', [CompletionResultType]::ParameterName, 'ZMQ socket for the dedicated peer message bus carrying chunk payloads')
             [CompletionResult]::new('-X', 'X', [CompletionResultType]::ParameterName, 'ZMQ socket for internal service control bus')
             [CompletionResult]::new('--ctl', 'ctl', [CompletionResultType]::ParameterName, 'ZMQ socket for internal service control bus')
             [CompletionResult]::new('-R', 'R', [CompletionResultType]::ParameterName, 'ZMQ socket name/address for Storm Node client-server RPC API')
@@ -37,6 +38,42 @@ Register-ArgumentCompleter -Native -CommandName 'stormd' -ScriptBlock {
             [CompletionResult]::new('--store-endpoint', 'store-endpoint', [CompletionResultType]::ParameterName, 'ZMQ socket for connecting storage daemon')
             [CompletionResult]::new('-C', 'C', [CompletionResultType]::ParameterName, 'ZMQ socket for chat daemon PUB/SUB API')
             [CompletionResult]::new('--chat-endpoint', 'chat-endpoint', [CompletionResultType]::ParameterName, 'ZMQ socket for chat daemon PUB/SUB API')
+            [CompletionResult]::new('--cache-size', 'cache-size', [CompletionResultType]::ParameterName, 'Maximum number of containers cached on behalf of other peers')
+            [CompletionResult]::new('--cache-admission', 'cache-admission', [CompletionResultType]::ParameterName, 'Number of requests a container must receive before it is admitted into the cache')
+            [CompletionResult]::new('--auto-pin', 'auto-pin', [CompletionResultType]::ParameterName, 'Automatically pin containers after they were requested given number of times')
+            [CompletionResult]::new('--peer-msg', 'peer-msg', [CompletionResultType]::ParameterName, 'ZMQ sockets of additional peer connection services')
+            [CompletionResult]::new('--metrics-log', 'metrics-log', [CompletionResultType]::ParameterName, 'Record periodic snapshots of the node metrics into a file in the data directory')
+            [CompletionResult]::new('--metrics-interval', 'metrics-interval', [CompletionResultType]::ParameterName, 'Interval between the metrics snapshots, in seconds')
+            [CompletionResult]::new('--metrics-endpoint', 'metrics-endpoint', [CompletionResultType]::ParameterName, 'Serve the node metrics in the Prometheus text format on `GET /metrics` at the given address, e.g. `127.0.0.1:9464`')
+            [CompletionResult]::new('--event-digest', 'event-digest', [CompletionResultType]::ParameterName, 'Batch events of the given class into periodic digests instead of sending them to the extensions one by one')
+            [CompletionResult]::new('--digest-interval', 'digest-interval', [CompletionResultType]::ParameterName, 'Interval between the event digests, in seconds')
+            [CompletionResult]::new('--webhook', 'webhook', [CompletionResultType]::ParameterName, 'Post messages received by an app to a webhook, in `APP[/TOPIC]=URL` format')
+            [CompletionResult]::new('--webhook-secret', 'webhook-secret', [CompletionResultType]::ParameterName, 'Secret used to sign the webhook requests with HMAC-SHA256')
+            [CompletionResult]::new('--policy', 'policy', [CompletionResultType]::ParameterName, 'Program deciding on the messages and topics received from the remote peers')
+            [CompletionResult]::new('--node-id', 'node-id', [CompletionResultType]::ParameterName, 'LNP node id (public key) of this node')
+            [CompletionResult]::new('--pow-difficulty', 'pow-difficulty', [CompletionResultType]::ParameterName, 'Require unknown peers to present a proof-of-work stamp with the given number of leading zero bits before their messages are delivered to the apps')
+            [CompletionResult]::new('--pow-stamp', 'pow-stamp', [CompletionResultType]::ParameterName, 'Present a proof-of-work stamp with the given number of leading zero bits to each remote peer before the first message sent to it')
+            [CompletionResult]::new('--replication-window', 'replication-window', [CompletionResultType]::ParameterName, 'Run bulk replication (container transfers of downpourd and syncd) only during the given daily hours, in `START-END` format with UTC hours, like `22-6`')
+            [CompletionResult]::new('--replica', 'replica', [CompletionResultType]::ParameterName, 'Replication partner, given as the node id (public key), with which the pinned containers and the app topics are periodically reconciled')
+            [CompletionResult]::new('--admin', 'admin', [CompletionResultType]::ParameterName, 'Node of the operator allowed to manage this node over the Storm protocol, querying its status, pinning and replicating containers')
+            [CompletionResult]::new('--approver', 'approver', [CompletionResultType]::ParameterName, 'Operator key, given as the public key, which may approve the sensitive admin actions on the node co-operated by several operators')
+            [CompletionResult]::new('--approvals', 'approvals', [CompletionResultType]::ParameterName, 'Number of the approver keys which must sign the destructive admin actions before they are executed: eviction and unpinning of containers, garbage collection, rebuild of the container index, revocation of share tokens and manifest key rotation')
+            [CompletionResult]::new('--anti-entropy-interval', 'anti-entropy-interval', [CompletionResultType]::ParameterName, 'Interval between the reconciliations with the replication partners, in seconds')
+            [CompletionResult]::new('--replication-bandwidth', 'replication-bandwidth', [CompletionResultType]::ParameterName, 'Pause bulk replication while the node traffic is above the given number of bytes per second')
+            [CompletionResult]::new('--notify', 'notify', [CompletionResultType]::ParameterName, 'ZMQ socket on which the node publishes events for the subscribed clients, conventionally `0.0.0.0:64966`')
+            [CompletionResult]::new('--topic-sketch', 'topic-sketch', [CompletionResultType]::ParameterName, 'Reconcile the app topics with the remote peers using sketches sized for the given number of differences, up to 900, instead of exchanging the full topic lists')
+            [CompletionResult]::new('--max-client-jobs', 'max-client-jobs', [CompletionResultType]::ParameterName, 'Maximum number of unfinished background jobs a single RPC client may have')
+            [CompletionResult]::new('--quota', 'quota', [CompletionResultType]::ParameterName, 'Maximum size of all the containers stored by the node, in bytes')
+            [CompletionResult]::new('--peer-quota', 'peer-quota', [CompletionResultType]::ParameterName, 'Maximum size of the containers retrieved from a single remote peer, in bytes')
+            [CompletionResult]::new('--eviction', 'eviction', [CompletionResultType]::ParameterName, 'Policy selecting the containers evicted once a storage quota is exceeded')
+            [CompletionResult]::new('--gc-grace', 'gc-grace', [CompletionResultType]::ParameterName, 'Number of seconds a chunk must stay unreferenced by any of the stored containers before it is collected')
+            [CompletionResult]::new('--gc-interval', 'gc-interval', [CompletionResultType]::ParameterName, 'Interval between the garbage collections of the unreferenced chunks, in seconds')
+            [CompletionResult]::new('--spam-ban', 'spam-ban', [CompletionResultType]::ParameterName, 'Number of posts and topic proposals of a remote peer which the apps may decline as spam or abuse before the peer is banned')
+            [CompletionResult]::new('--rate-limit-bytes', 'rate-limit-bytes', [CompletionResultType]::ParameterName, 'Limit the rate of the chunks sent to all the remote peers together, in bytes per second')
+            [CompletionResult]::new('--rate-limit-chunks', 'rate-limit-chunks', [CompletionResultType]::ParameterName, 'Limit the rate of the chunks sent to all the remote peers together, in chunks per second')
+            [CompletionResult]::new('--peer-rate-limit-bytes', 'peer-rate-limit-bytes', [CompletionResultType]::ParameterName, 'Limit the rate of the chunks sent to each of the remote peers, in bytes per second')
+            [CompletionResult]::new('--peer-rate-limit-chunks', 'peer-rate-limit-chunks', [CompletionResultType]::ParameterName, 'Limit the rate of the chunks sent to each of the remote peers, in chunks per second')
+            [CompletionResult]::new('--memory-budget', 'memory-budget', [CompletionResultType]::ParameterName, 'Memory budget of the node, in bytes')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help information')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help information')
             [CompletionResult]::new('-V', 'V', [CompletionResultType]::ParameterName, 'Print version information')
@@ -44,9 +81,15 @@ Register-ArgumentCompleter -Native -CommandName 'stormd' -ScriptBlock {
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'Set verbosity level')
             [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'Set verbosity level')
             [CompletionResult]::new('--chat', 'chat', [CompletionResultType]::ParameterName, 'Run chat service')
+            [CompletionResult]::new('--mail', 'mail', [CompletionResultType]::ParameterName, 'Run mail service')
+            [CompletionResult]::new('--board', 'board', [CompletionResultType]::ParameterName, 'Run public board service')
             [CompletionResult]::new('--downpour', 'downpour', [CompletionResultType]::ParameterName, 'Run downpour (torrent-like) service')
             [CompletionResult]::new('-T', 'T', [CompletionResultType]::ParameterName, 'Spawn daemons as threads and not processes')
             [CompletionResult]::new('--threaded', 'threaded', [CompletionResultType]::ParameterName, 'Spawn daemons as threads and not processes')
+            [CompletionResult]::new('--attest', 'attest', [CompletionResultType]::ParameterName, 'Send attestation of the node software version and features, signed with the node manifest key, to the remote peers')
+            [CompletionResult]::new('--read-only', 'read-only', [CompletionResultType]::ParameterName, 'Run as a read-only replica, e.g. a public distribution mirror')
+            [CompletionResult]::new('--storage-peer', 'storage-peer', [CompletionResultType]::ParameterName, 'Keep replicas of the containers on the requests of the remote peers')
+            [CompletionResult]::new('--rebuild-index', 'rebuild-index', [CompletionResultType]::ParameterName, 'Rebuild the container index from the container manifests kept in the store, discarding the index saved in the data directory')
             break
         }
     })
@@ -19,7 +19,7 @@ _chatd() {
 
     case "${cmd}" in
         chatd)
-            opts="-h -V -v -d -c -M -X -R -E -S -C --help --version --verbose --data-dir --config --msg --ctl --rpc-endpoint --ext-endpoint --store-endpoint --chat-endpoint"
+            opts="-h -V -v -d -c -M -X -R -E -S -C --help --version --verbose --data-dir --config --msg --bulk-msg --ctl --rpc-endpoint --ext-endpoint --store-endpoint --chat-endpoint"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -49,6 +49,10 @@ _chatd() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --bulk-msg)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --ctl)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -15,7 +15,7 @@
 #[macro_use]
 extern crate log;
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use microservices::error::BootstrapError;
 use storm_node::downpourd::Opts;
 use storm_node::{downpourd, Config, LaunchError};
@@ -23,8 +23,10 @@ use storm_node::{downpourd, Config, LaunchError};
 fn main() -> Result<(), BootstrapError<LaunchError>> {
     println!("downpourd: file transfer microservice");
 
-    let mut opts = Opts::parse();
+    let matches = Opts::command().get_matches();
+    let mut opts = Opts::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
     trace!("Command-line arguments: {:?}", opts);
+    opts.shared.apply_config_file(&matches).map_err(LaunchError::from)?;
     opts.process();
     trace!("Processed arguments: {:?}", opts);
 
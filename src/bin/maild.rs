@@ -0,0 +1,53 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+#![recursion_limit = "256"]
+
+//! Mail daemon for Storm node.
+
+#[macro_use]
+extern crate log;
+
+use clap::{CommandFactory, FromArgMatches};
+use microservices::error::BootstrapError;
+use storm_node::maild::Opts;
+use storm_node::{maild, Config, LaunchError};
+
+fn main() -> Result<(), BootstrapError<LaunchError>> {
+    println!("maild: mail microservice");
+
+    let matches = Opts::command().get_matches();
+    let mut opts = Opts::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    trace!("Command-line arguments: {:?}", opts);
+    opts.shared.apply_config_file(&matches).map_err(LaunchError::from)?;
+    opts.process();
+    trace!("Processed arguments: {:?}", opts);
+
+    let config: Config = opts.clone().into();
+    trace!("Daemon configuration: {:?}", config);
+    debug!("CTL socket {}", config.ctl_endpoint);
+    debug!("RPC socket {}", config.rpc_endpoint);
+    debug!("STORM socket {}", config.ext_endpoint);
+    debug!("STORE socket {}", config.store_endpoint);
+
+    /*
+    use self::internal::ResultExt;
+    let (config_from_file, _) =
+        internal::Config::custom_args_and_optional_files(std::iter::empty::<
+            &str,
+        >())
+        .unwrap_or_exit();
+     */
+
+    debug!("Starting runtime ...");
+    maild::run(config).expect("running maild runtime");
+
+    unreachable!()
+}
@@ -0,0 +1,42 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+#![recursion_limit = "256"]
+
+//! MQTT bridge daemon for Storm node.
+
+#[macro_use]
+extern crate log;
+
+use clap::Parser;
+use microservices::error::BootstrapError;
+use storm_node::mqttd::{self, Opts};
+use storm_node::{Config, LaunchError};
+
+fn main() -> Result<(), BootstrapError<LaunchError>> {
+    println!("mqttd: MQTT bridge microservice");
+
+    let mut opts = Opts::parse();
+    trace!("Command-line arguments: {:?}", opts);
+    opts.process();
+    trace!("Processed arguments: {:?}", opts);
+
+    let config: Config<mqttd::Config> = opts.clone().into();
+    trace!("Daemon configuration: {:?}", config);
+    debug!("CTL socket {}", config.ctl_endpoint);
+    debug!("RPC socket {}", config.rpc_endpoint);
+    debug!("STORM socket {}", config.ext_endpoint);
+    debug!("MQTT broker {}", config.ext.broker);
+
+    debug!("Starting runtime ...");
+    mqttd::run(config).expect("running mqttd runtime");
+
+    unreachable!()
+}
@@ -0,0 +1,42 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+#![recursion_limit = "256"]
+
+//! Folder sync daemon for Storm node.
+
+#[macro_use]
+extern crate log;
+
+use clap::Parser;
+use microservices::error::BootstrapError;
+use storm_node::syncd::{self, Opts};
+use storm_node::{Config, LaunchError};
+
+fn main() -> Result<(), BootstrapError<LaunchError>> {
+    println!("syncd: folder sync microservice");
+
+    let mut opts = Opts::parse();
+    trace!("Command-line arguments: {:?}", opts);
+    opts.process();
+    trace!("Processed arguments: {:?}", opts);
+
+    let config: Config<syncd::Config> = opts.clone().into();
+    trace!("Daemon configuration: {:?}", config);
+    debug!("CTL socket {}", config.ctl_endpoint);
+    debug!("RPC socket {}", config.rpc_endpoint);
+    debug!("STORM socket {}", config.ext_endpoint);
+    debug!("STORE socket {}", config.store_endpoint);
+
+    debug!("Starting runtime ...");
+    syncd::run(config).expect("running syncd runtime");
+
+    unreachable!()
+}
@@ -15,20 +15,22 @@
 #[macro_use]
 extern crate log;
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use microservices::error::BootstrapError;
-use storm_node::chatd::Opts;
+use storm_node::transferd::Opts;
 use storm_node::{transferd, Config, LaunchError};
 
 fn main() -> Result<(), BootstrapError<LaunchError>> {
     println!("transfer: container transfer microservice");
 
-    let mut opts = Opts::parse();
+    let matches = Opts::command().get_matches();
+    let mut opts = Opts::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
     trace!("Command-line arguments: {:?}", opts);
+    opts.shared.apply_config_file(&matches).map_err(LaunchError::from)?;
     opts.process();
     trace!("Processed arguments: {:?}", opts);
 
-    let config: Config = opts.clone().into();
+    let config: Config<transferd::Config> = opts.clone().into();
     trace!("Daemon configuration: {:?}", config);
     debug!("CTL socket {}", config.ctl_endpoint);
     debug!("RPC socket {}", config.rpc_endpoint);
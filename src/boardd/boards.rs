@@ -0,0 +1,306 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::Topic;
+use storm_rpc::{
+    BoardAction, BoardInfo, BoardModeration, BoardPost, BoardSummary, PostId, SignedModeration,
+    TopicId,
+};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::post_id;
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the boards the node is subscribed to.
+pub const STORM_NODE_BOARDS_FILE: &str = "boards.dat";
+
+/// Board known to the node, together with its history and the peers the node exchanges the
+/// board posts with.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct Board {
+    pub topic: Topic,
+    pub info: BoardInfo,
+    /// Peer the node is subscribed through.
+    pub upstream: Option<NodeId>,
+    /// Peers subscribed to the board through this node.
+    pub subscribers: BTreeSet<NodeId>,
+    /// Posts ordered by their timestamps.
+    pub posts: Vec<BoardPost>,
+    /// Last moderator action applied to each of the posts, which are synced to the subscribers
+    /// together with the posts.
+    pub moderations: BTreeMap<PostId, SignedModeration>,
+}
+
+impl Board {
+    fn with(topic: Topic, upstream: Option<NodeId>) -> Result<Self, strict_encoding::Error> {
+        let info = BoardInfo::strict_deserialize(&topic.body)?;
+        Ok(Board {
+            topic,
+            info,
+            upstream,
+            subscribers: empty!(),
+            posts: empty!(),
+            moderations: empty!(),
+        })
+    }
+
+    pub fn summary(&self, id: TopicId) -> BoardSummary {
+        BoardSummary {
+            id,
+            info: self.info.clone(),
+            upstream: self.upstream,
+            subscribers: self.subscribers.len() as u32,
+            posts: self.posts.iter().filter(|post| !post.removed).count() as u32,
+        }
+    }
+
+    /// Checks whether the peer may post to the board through this node.
+    pub fn is_member(&self, peer: NodeId) -> bool {
+        self.upstream == Some(peer) || self.subscribers.contains(&peer)
+    }
+
+    /// Checks whether the moderator action on the board with the given id is signed by one of
+    /// the board moderators. Signed actions are applied whichever peer has relayed them.
+    pub fn is_authentic(&self, id: TopicId, moderation: &SignedModeration) -> bool {
+        moderation.moderation.board == id
+            && self.info.moderators.contains(&moderation.moderation.moderator)
+            && moderation.verify().is_ok()
+    }
+
+    /// Returns the peers the board messages are exchanged with, except the given one.
+    pub fn peers(&self, except: Option<NodeId>) -> Vec<NodeId> {
+        self.upstream
+            .iter()
+            .chain(&self.subscribers)
+            .copied()
+            .filter(|peer| Some(*peer) != except)
+            .collect()
+    }
+
+    /// Returns posts which are not removed, pinned posts first.
+    pub fn visible(&self) -> Vec<BoardPost> {
+        let mut posts = self.posts.iter().filter(|post| !post.removed).cloned().collect::<Vec<_>>();
+        posts.sort_by_key(|post| !post.pinned);
+        posts
+    }
+
+    fn insert(&mut self, post: BoardPost) -> bool {
+        if self.posts.iter().any(|known| known.id == post.id) {
+            return false;
+        }
+        let pos = self.posts.partition_point(|known| known.timestamp <= post.timestamp);
+        self.posts.insert(pos, post);
+        true
+    }
+
+    /// Applies moderator action, returning whether the post was changed, or `None` if the post
+    /// is not known. Removed posts and actions not newer than the last one applied to the post
+    /// are ignored, so the actions replayed by the peers do not revert the later ones.
+    fn apply(&mut self, moderation: SignedModeration) -> Option<bool> {
+        let BoardModeration {
+            post_id,
+            action,
+            timestamp,
+            ..
+        } = moderation.moderation;
+        let post = self.posts.iter_mut().find(|post| post.id == post_id)?;
+        let outdated = self
+            .moderations
+            .get(&post_id)
+            .map(|last| last.moderation.timestamp >= timestamp)
+            .unwrap_or_default();
+        if post.removed || outdated {
+            return Some(false);
+        }
+        self.moderations.insert(post_id, moderation);
+        let prev = post.clone();
+        match action {
+            BoardAction::Pin => post.pinned = true,
+            BoardAction::Unpin => post.pinned = false,
+            BoardAction::Remove => {
+                post.pinned = false;
+                post.removed = true;
+                post.text = s!("");
+            }
+        }
+        Some(*post != prev)
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+struct BoardsData {
+    boards: BTreeMap<TopicId, Board>,
+    /// Peers which were asked for the board history, which is not yet received
+    pending: BTreeMap<TopicId, NodeId>,
+}
+
+/// Public boards created by the node or which the node is subscribed to.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Boards {
+    path: PathBuf,
+    data: BoardsData,
+}
+
+impl Boards {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_BOARDS_FILE);
+        let data = if path.exists() {
+            BoardsData::strict_decode(fs::File::open(&path)?)?
+        } else {
+            none!()
+        };
+        Ok(Boards { path, data })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.data)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: TopicId) -> Option<&Board> { self.data.boards.get(&id) }
+
+    pub fn list(&self) -> impl Iterator<Item = BoardSummary> + '_ {
+        self.data.boards.iter().map(|(id, board)| board.summary(*id))
+    }
+
+    pub fn create(&mut self, info: BoardInfo) -> Result<TopicId, strict_encoding::Error> {
+        let topic = Topic {
+            body: info.strict_serialize()?,
+            container_ids: empty!(),
+        };
+        let id = TopicId::from(&topic);
+        if let Entry::Vacant(entry) = self.data.boards.entry(id) {
+            entry.insert(Board::with(topic, None)?);
+            self.save()?;
+        }
+        Ok(id)
+    }
+
+    /// Records that the board history is requested from the peer.
+    pub fn request(&mut self, id: TopicId, peer: NodeId) -> Result<(), strict_encoding::Error> {
+        self.data.pending.insert(id, peer);
+        self.save()
+    }
+
+    /// Accepts the board history from the peer it was requested from, subscribing the node to the
+    /// board through the peer. Returns whether the history was requested from the peer.
+    ///
+    /// Pins and removals of the posts are restored only from the moderator actions signed by the
+    /// board moderators.
+    pub fn sync(
+        &mut self,
+        peer: NodeId,
+        id: TopicId,
+        topic: Topic,
+        posts: Vec<BoardPost>,
+        moderations: Vec<SignedModeration>,
+    ) -> Result<bool, strict_encoding::Error> {
+        if self.data.pending.get(&id) != Some(&peer) || id.verify(&topic).is_err() {
+            return Ok(false);
+        }
+        self.data.pending.remove(&id);
+        let board = match self.data.boards.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Board::with(topic, None)?),
+        };
+        board.upstream = Some(peer);
+        let moderations = moderations
+            .into_iter()
+            .filter(|moderation| board.is_authentic(id, moderation))
+            .collect::<Vec<_>>();
+        for mut post in posts {
+            if post.removed {
+                // Text of the removed posts is erased, so their ids can't be checked; instead,
+                // they have to be removed by a moderator
+                let removed = moderations.iter().any(|moderation| {
+                    moderation.moderation.post_id == post.id
+                        && moderation.moderation.action == BoardAction::Remove
+                });
+                if !removed {
+                    continue;
+                }
+            } else if post_id(id, post.author, post.timestamp, &post.text)? != post.id {
+                continue;
+            }
+            post.pinned = false;
+            post.removed = false;
+            board.insert(post);
+        }
+        for moderation in moderations {
+            board.apply(moderation);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Subscribes the peer to the board, returning the board if it is known.
+    pub fn subscribe(
+        &mut self,
+        id: TopicId,
+        peer: NodeId,
+    ) -> Result<Option<&Board>, strict_encoding::Error> {
+        match self.data.boards.get_mut(&id) {
+            Some(board) if board.upstream != Some(peer) => {
+                if board.subscribers.insert(peer) {
+                    self.save()?;
+                }
+                Ok(self.data.boards.get(&id))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn unsubscribe(&mut self, id: TopicId, peer: NodeId) -> Result<(), strict_encoding::Error> {
+        if let Some(board) = self.data.boards.get_mut(&id) {
+            if board.subscribers.remove(&peer) {
+                self.save()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Forgets the board, returning it if it was known.
+    pub fn remove(&mut self, id: TopicId) -> Result<Option<Board>, strict_encoding::Error> {
+        self.data.pending.remove(&id);
+        let board = self.data.boards.remove(&id);
+        self.save()?;
+        Ok(board)
+    }
+
+    /// Adds post to the board history, returning whether the post is new.
+    pub fn post(&mut self, id: TopicId, post: BoardPost) -> Result<bool, strict_encoding::Error> {
+        let added =
+            self.data.boards.get_mut(&id).map(|board| board.insert(post)).unwrap_or_default();
+        if added {
+            self.save()?;
+        }
+        Ok(added)
+    }
+
+    /// Applies moderator action to the board post, returning whether the post was changed, or
+    /// `None` if the board or the post is not known. The action must be checked with
+    /// [`Board::is_authentic`] beforehand.
+    pub fn moderate(
+        &mut self,
+        id: TopicId,
+        moderation: SignedModeration,
+    ) -> Result<Option<bool>, strict_encoding::Error> {
+        let changed = self.data.boards.get_mut(&id).and_then(|board| board.apply(moderation));
+        if changed == Some(true) {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+}
@@ -0,0 +1,442 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::thread;
+use std::time::Duration;
+
+use internet2::addr::NodeId;
+use internet2::ZmqSocketType;
+use microservices::error::BootstrapError;
+use microservices::esb::{self, ClientId, EndpointList, Error};
+use microservices::node::TryService;
+use storm::Mesg;
+use storm_ext::ExtMsg;
+use storm_rpc::{
+    AddressedMsg, BoardModeration, BoardPost, BoardPostRequest, Page, Paged, PostId, RpcMsg,
+    ServiceId, SignedModeration, TopicId,
+};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::{post_id, BoardMsg, Boards};
+use crate::bus::{terminate, BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::stormd::unix_time;
+use crate::{Config, DaemonError, LaunchError};
+
+pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
+    let rpc_endpoint = config.rpc_endpoint.clone();
+    let ctl_endpoint = config.ctl_endpoint.clone();
+    let ext_endpoint = config.ext_endpoint.clone();
+    let runtime = Runtime::init(config)?;
+
+    debug!("Connecting to service buses {}, {}, {}", rpc_endpoint, ctl_endpoint, ext_endpoint);
+    let controller = esb::Controller::with(
+        map! {
+            ServiceBus::Storm => esb::BusConfig::with_addr(
+                ext_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Rpc => esb::BusConfig::with_addr(
+                rpc_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Ctl => esb::BusConfig::with_addr(
+                ctl_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            )
+        },
+        runtime,
+    )
+    .map_err(|_| LaunchError::BusSetupFailure)?;
+
+    controller.run_or_panic("boardd");
+
+    unreachable!()
+}
+
+pub struct Runtime {
+    pub(super) boards: Boards,
+}
+
+impl Runtime {
+    pub fn init(config: Config) -> Result<Self, BootstrapError<LaunchError>> {
+        let boards = Boards::load(&config.data_dir).map_err(LaunchError::from)?;
+
+        info!("Board runtime started successfully");
+
+        Ok(Self { boards })
+    }
+}
+
+impl Responder for Runtime {}
+
+impl esb::Handler<ServiceBus> for Runtime {
+    type Request = BusMsg;
+    type Error = DaemonError;
+
+    fn identity(&self) -> ServiceId { ServiceId::boardd() }
+
+    fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
+        thread::sleep(Duration::from_millis(100));
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        endpoints: &mut EndpointList<ServiceBus>,
+        bus_id: ServiceBus,
+        source: ServiceId,
+        request: Self::Request,
+    ) -> Result<(), Self::Error> {
+        match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                self.handle_storm(endpoints, msg)
+            }
+            (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
+                self.handle_rpc(endpoints, client_id, msg)
+            }
+            (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
+            (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        }
+    }
+
+    fn handle_err(
+        &mut self,
+        _endpoints: &mut EndpointList<ServiceBus>,
+        _error: Error<ServiceId>,
+    ) -> Result<(), Self::Error> {
+        // We do nothing and do not propagate error; it's already being reported
+        // with `error!` macro by the controller. If we propagate error here
+        // this will make whole daemon panic
+        Ok(())
+    }
+}
+
+impl Runtime {
+    fn handle_storm(
+        &mut self,
+        endpoints: &mut Endpoints,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let board = TopicId::from_wire(data.parent_id);
+                let msg = match BoardMsg::strict_deserialize(&data.body) {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        warn!("Invalid board message from {}: {}", remote_id, err);
+                        return Ok(());
+                    }
+                };
+                self.handle_board(endpoints, remote_id, board, msg)?;
+            }
+
+            ExtMsg::Negotiate(_) => {}
+
+            wrong_msg => {
+                error!("Request is not supported by the Storm interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_board(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        board: TopicId,
+        msg: BoardMsg,
+    ) -> Result<(), DaemonError> {
+        match msg {
+            BoardMsg::Subscribe => match self.boards.subscribe(board, remote_id)? {
+                Some(known) => {
+                    info!("Peer {} subscribed to board {}", remote_id, board);
+                    let sync = BoardMsg::Sync(
+                        known.topic.clone(),
+                        known.posts.clone(),
+                        known.moderations.values().copied().collect(),
+                    );
+                    self.send(endpoints, remote_id, board, &sync)?;
+                }
+                None => warn!("Peer {} subscribes to unknown board {}", remote_id, board),
+            },
+
+            BoardMsg::Unsubscribe => {
+                info!("Peer {} unsubscribed from board {}", remote_id, board);
+                self.boards.unsubscribe(board, remote_id)?;
+            }
+
+            BoardMsg::Sync(topic, posts, moderations) => {
+                let len = posts.len();
+                if self.boards.sync(remote_id, board, topic, posts, moderations)? {
+                    info!(
+                        "Subscribed to board {} through {}, {} posts synced",
+                        board, remote_id, len
+                    );
+                } else {
+                    warn!("Ignoring unrequested history of board {} from {}", board, remote_id);
+                }
+            }
+
+            BoardMsg::Post(mut post) => {
+                match self.boards.get(board) {
+                    Some(known) if known.is_member(remote_id) => {}
+                    _ => {
+                        warn!("Ignoring post to board {} from non-subscriber {}", board, remote_id);
+                        return Ok(());
+                    }
+                }
+                if post_id(board, post.author, post.timestamp, &post.text)? != post.id {
+                    warn!("Post {} from {} does not match its id", post.id, remote_id);
+                    return Ok(());
+                }
+                // Posts are published non-moderated
+                post.pinned = false;
+                post.removed = false;
+                let id = post.id;
+                if self.boards.post(board, post.clone())? {
+                    debug!("New post {} on board {} from {}", id, board, remote_id);
+                    self.broadcast(endpoints, board, Some(remote_id), &BoardMsg::Post(post))?;
+                }
+            }
+
+            BoardMsg::Moderate(moderation) => {
+                let BoardModeration {
+                    post_id,
+                    moderator,
+                    action,
+                    ..
+                } = moderation.moderation;
+                match self.boards.get(board) {
+                    Some(known) if known.is_authentic(board, &moderation) => {}
+                    _ => {
+                        warn!(
+                            "Ignoring {} of post {} by {} received from {}, which is not signed \
+                             by a board moderator",
+                            action, post_id, moderator, remote_id
+                        );
+                        return Ok(());
+                    }
+                }
+                if self.boards.moderate(board, moderation)? == Some(true) {
+                    info!("Moderator {} did {} of post {}", moderator, action, post_id);
+                    // Moderator actions are signed, so they are relayed to all the peers
+                    // exchanging the board
+                    let msg = BoardMsg::Moderate(moderation);
+                    self.broadcast(endpoints, board, Some(remote_id), &msg)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        message: RpcMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            RpcMsg::CreateBoard(info) => {
+                let reply = match self.boards.create(info) {
+                    Ok(board) => {
+                        info!("Created board {}", board);
+                        RpcMsg::BoardCreated(board)
+                    }
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::SubscribeBoard(AddressedMsg { remote_id, data }) => {
+                let reply = match self.boards.request(data, remote_id) {
+                    Ok(()) => {
+                        self.send(endpoints, remote_id, data, &BoardMsg::Subscribe)?;
+                        RpcMsg::Success(None.into())
+                    }
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::UnsubscribeBoard(board) => {
+                let reply = match self.boards.remove(board) {
+                    Ok(Some(known)) => {
+                        if let Some(upstream) = known.upstream {
+                            self.send(endpoints, upstream, board, &BoardMsg::Unsubscribe)?;
+                        }
+                        RpcMsg::Success(None.into())
+                    }
+                    Ok(None) => DaemonError::UnknownTopic(board).into(),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ListBoards(page) => {
+                let boards = Page::by_key(self.boards.list(), &page, |summary| summary.id);
+                self.send_rpc(endpoints, client_id, RpcMsg::Boards(boards))?;
+            }
+
+            RpcMsg::ReadBoard(Paged { query: board, page }) => {
+                let reply = match self.boards.get(board) {
+                    Some(known) => RpcMsg::BoardPosts(Page::by_offset(known.visible(), &page)),
+                    None => DaemonError::UnknownTopic(board).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::PostBoard(request) => {
+                let reply = match self.publish(endpoints, request) {
+                    Ok(id) => RpcMsg::BoardPosted(id),
+                    Err(err) => err.into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ModerateBoard(moderation) => {
+                let reply = match self.moderate(endpoints, moderation) {
+                    Ok(()) => RpcMsg::Success(None.into()),
+                    Err(err) => err.into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_ctl(
+        &mut self,
+        endpoints: &mut Endpoints,
+        _source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            CtlMsg::Ping(nonce) => {
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Pong(nonce))?;
+                Ok(())
+            }
+            CtlMsg::Shutdown => {
+                // All the daemon state is saved as soon as it changes
+                info!("Shutting down");
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ShutdownComplete)?;
+                terminate();
+                Ok(())
+            }
+            wrong_msg => {
+                error!("Request is not supported by the CTL interface");
+                Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &wrong_msg))
+            }
+        }
+    }
+
+    fn publish(
+        &mut self,
+        endpoints: &mut Endpoints,
+        request: BoardPostRequest,
+    ) -> Result<PostId, DaemonError> {
+        let BoardPostRequest {
+            board,
+            author,
+            text,
+        } = request;
+        if self.boards.get(board).is_none() {
+            return Err(DaemonError::UnknownTopic(board));
+        }
+        let timestamp = unix_time();
+        let post = BoardPost {
+            id: post_id(board, author, timestamp, &text)?,
+            author,
+            timestamp,
+            text,
+            pinned: false,
+            removed: false,
+        };
+        let id = post.id;
+        self.boards.post(board, post.clone())?;
+        self.broadcast(endpoints, board, None, &BoardMsg::Post(post))?;
+        Ok(id)
+    }
+
+    fn moderate(
+        &mut self,
+        endpoints: &mut Endpoints,
+        moderation: SignedModeration,
+    ) -> Result<(), DaemonError> {
+        let BoardModeration {
+            board,
+            post_id,
+            moderator,
+            action,
+            ..
+        } = moderation.moderation;
+        let known = self.boards.get(board).ok_or(DaemonError::UnknownTopic(board))?;
+        if !known.info.moderators.contains(&moderator) {
+            return Err(DaemonError::NotModerator(moderator));
+        }
+        moderation.verify()?;
+        match self.boards.moderate(board, moderation)? {
+            None => Err(DaemonError::UnknownPost(post_id)),
+            Some(false) => Ok(()),
+            Some(true) => {
+                info!("Applied {} of post {} on board {}", action, post_id, board);
+                self.broadcast(endpoints, board, None, &BoardMsg::Moderate(moderation))
+            }
+        }
+    }
+
+    /// Sends the message to all peers the board messages are exchanged with, except the given one.
+    fn broadcast(
+        &mut self,
+        endpoints: &mut Endpoints,
+        board: TopicId,
+        except: Option<NodeId>,
+        msg: &BoardMsg,
+    ) -> Result<(), DaemonError> {
+        let peers = self.boards.get(board).map(|known| known.peers(except)).unwrap_or_default();
+        for remote_id in peers {
+            self.send(endpoints, remote_id, board, msg)?;
+        }
+        Ok(())
+    }
+
+    fn send(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        board: TopicId,
+        msg: &BoardMsg,
+    ) -> Result<(), DaemonError> {
+        let addressed_msg = AddressedMsg {
+            remote_id,
+            data: Mesg {
+                parent_id: board.to_wire(),
+                container_ids: empty!(),
+                body: msg.strict_serialize()?,
+            },
+        };
+        self.send_ext(endpoints, None, ExtMsg::Post(addressed_msg))?;
+        Ok(())
+    }
+}
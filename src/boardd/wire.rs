@@ -0,0 +1,50 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use internet2::addr::NodeId;
+use storm::{Mesg, Topic};
+use storm_rpc::{BoardPost, PostId, SignedModeration, TopicId};
+use strict_encoding::StrictEncode;
+
+/// Message body exchanged between the board daemons of the peers. The messages are posted with
+/// the id of the board topic as their parent id.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub enum BoardMsg {
+    /// Subscription of the sender to the board through the recipient.
+    Subscribe,
+
+    Unsubscribe,
+
+    /// Board topic, the board history and the last moderator actions on the posts, synced to a
+    /// new subscriber.
+    Sync(Topic, Vec<BoardPost>, Vec<SignedModeration>),
+
+    /// New post, which is forwarded to the other peers subscribed to the board.
+    Post(BoardPost),
+
+    /// Moderator action on the post, which is relayed to the other peers exchanging the board.
+    Moderate(SignedModeration),
+}
+
+/// Computes id of the post with the given content.
+pub fn post_id(
+    board: TopicId,
+    author: NodeId,
+    timestamp: u64,
+    text: &str,
+) -> Result<PostId, strict_encoding::Error> {
+    let mut body = vec![];
+    strict_encode_list!(body; author, timestamp, text);
+    let mesg = Mesg {
+        parent_id: board.to_wire(),
+        body,
+        container_ids: empty!(),
+    };
+    Ok(PostId::from(&mesg))
+}
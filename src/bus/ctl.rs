@@ -10,6 +10,9 @@
 
 use std::collections::BTreeSet;
 use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
 
 use internet2::addr::NodeId;
 use microservices::esb::ClientId;
@@ -18,6 +21,8 @@ use storm::{Chunk, ChunkId, Container, ContainerId, StormApp};
 use storm_rpc::{AddressedMsg, AppContainer};
 use strict_encoding::{StrictDecode, StrictEncode};
 
+use super::Sample;
+
 /// RPC API requests over CTL message bus between RGB Node daemons.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, From)]
 #[derive(NetworkEncode, NetworkDecode)]
@@ -29,6 +34,10 @@ pub enum CtlMsg {
     #[display("get({0})")]
     GetContainer(AddressedClientMsg<AppContainer>),
 
+    /// Retrieve only the container header and manifest, deferring the chunks.
+    #[display("get_metadata({0})")]
+    GetMetadata(AddressedClientMsg<AppContainer>),
+
     #[display("announce({0})")]
     AnnounceContainer(AddressedClientMsg<AppContainer>),
 
@@ -41,14 +50,103 @@ pub enum CtlMsg {
     #[display("send_chunks({0})")]
     SendChunks(AddressedMsg<ChunkSend>),
 
-    #[display("chunk(...)")]
-    ProcessChunk(Chunk),
+    #[display("chunk({0})")]
+    ProcessChunk(ChunkReceived),
+
+    /// Report of the transfer daemon on a chunk from the remote peer which has failed the
+    /// integrity verification.
+    #[display("corrupt_chunk({0})")]
+    CorruptChunk(AddressedMsg<ChunkId>),
+
+    /// Report of the transfer daemon on the number of bytes of the chunks sent to the remote
+    /// peer, charged against the rate limits.
+    #[display("chunks_sent({0})")]
+    ChunksSent(AddressedMsg<u64>),
+
+    /// Rejection by the remote peer of the container or chunk request of the transfer.
+    #[display("rejected({0})")]
+    Rejected(AddressedMsg<ContainerId>),
 
     #[display("processing_complete()")]
     ProcessingComplete,
 
     #[display("processing_failed()")]
     ProcessingFailed,
+
+    #[display("peer_paths({0})")]
+    PeerPaths(PeerPaths),
+
+    /// Pin containers, excluding them from eviction.
+    #[display("pin_many(...)")]
+    PinMany(Vec<ContainerId>),
+
+    #[display("unpin_many(...)")]
+    UnpinMany(Vec<ContainerId>),
+
+    /// Evict containers from the node storage.
+    #[display("evict_many(...)")]
+    EvictMany(Vec<ContainerId>),
+
+    /// Current values of the metrics of the child daemon.
+    #[display("metrics(...)")]
+    Metrics(Vec<Sample>),
+
+    /// Memory used by the caches and buffers of the child daemon, in bytes.
+    #[display("memory_usage({0})")]
+    MemoryUsage(u64),
+
+    /// Request to the child daemon to release the memory of its caches, since the node memory
+    /// budget is exhausted.
+    #[display("shrink_caches()")]
+    ShrinkCaches,
+
+    /// Request to the child daemon to persist its state and stop.
+    #[display("shutdown()")]
+    Shutdown,
+
+    /// Reply of the child daemon which has persisted its state and stops.
+    #[display("shutdown_complete()")]
+    ShutdownComplete,
+
+    /// Heartbeat sent to the supervised child daemon, which has to answer with [`CtlMsg::Pong`].
+    #[display("ping({0})")]
+    Ping(u64),
+
+    #[display("pong({0})")]
+    Pong(u64),
+}
+
+/// Whether the child daemons run in the threads of the stormd process.
+static THREADED: AtomicBool = AtomicBool::new(false);
+
+/// Marks the child daemons as running in the threads of the stormd process.
+pub fn set_threaded() { THREADED.store(true, Ordering::Relaxed) }
+
+/// Stops the child daemon once it has replied to [`CtlMsg::Shutdown`]. Daemons running in the
+/// threads of stormd share its process, which exits once all of them have stopped.
+pub fn terminate() {
+    if THREADED.load(Ordering::Relaxed) {
+        return;
+    }
+    // ZMQ delivers the messages in background, so we give it time to send the reply
+    thread::sleep(Duration::from_millis(100));
+    std::process::exit(0);
+}
+
+/// Peer connection services through which a remote peer is reachable.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub struct PeerPaths {
+    pub remote_id: NodeId,
+    /// Numbers of the peer connection services: `0` for the main message bus and `n` for the
+    /// message bus of the `n`-th additional service.
+    pub services: BTreeSet<u8>,
+}
+
+impl Display for PeerPaths {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} via {} paths", self.remote_id, self.services.len())
+    }
 }
 
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
@@ -58,6 +156,21 @@ pub struct ChunkSend {
     pub storm_app: StormApp,
     pub container_id: ContainerId,
     pub chunk_ids: BTreeSet<ChunkId>,
+    /// Whether the chunks should be sent over the dedicated bulk data bus
+    pub bulk: bool,
+}
+
+/// Chunk pushed by the remote peer, which is yet to be verified against its declared id and the
+/// container manifest.
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{chunk_id} from {remote_id}")]
+pub struct ChunkReceived {
+    pub remote_id: NodeId,
+    pub container_id: ContainerId,
+    /// Id under which the chunk is pushed
+    pub chunk_id: ChunkId,
+    pub chunk: Chunk,
 }
 
 #[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug, NetworkEncode, NetworkDecode)]
@@ -0,0 +1,116 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::VecDeque;
+
+use storm::StormApp;
+use storm_ext::{ExtLane, ExtMsg};
+
+/// Maximal number of bulk messages delivered to application extensions per poll iteration.
+pub const EXT_BULK_BUDGET: usize = 16;
+
+/// Outgoing queue of messages to application extensions, keeping control-plane messages apart
+/// from bulk data notifications, such that the former are always delivered first.
+///
+/// The queue persists across the poll iterations: the control lane is drained completely on each
+/// iteration, while the bulk lane is drained only up to [`EXT_BULK_BUDGET`] messages, leaving the
+/// rest behind the control messages queued by the next iterations.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExtLanes {
+    control: VecDeque<(Option<StormApp>, ExtMsg)>,
+    bulk: VecDeque<(Option<StormApp>, ExtMsg)>,
+    bulk_budget: usize,
+}
+
+impl ExtLanes {
+    pub fn push(&mut self, app_id: Option<StormApp>, message: impl Into<ExtMsg>) {
+        let message = message.into();
+        match message.lane() {
+            ExtLane::Control => self.control.push_back((app_id, message)),
+            ExtLane::Bulk => self.bulk.push_back((app_id, message)),
+        }
+    }
+
+    /// Starts a new poll iteration, renewing the budget of the bulk lane.
+    pub fn renew(&mut self) { self.bulk_budget = EXT_BULK_BUDGET; }
+
+    /// Number of bulk messages deferred to the next poll iterations.
+    pub fn deferred(&self) -> usize { self.bulk.len() }
+
+    /// Takes next message for delivery; bulk messages are returned only once the control lane is
+    /// empty and while the budget of the current poll iteration is not exhausted.
+    pub fn pop(&mut self) -> Option<(Option<StormApp>, ExtMsg)> {
+        if let Some(entry) = self.control.pop_front() {
+            return Some(entry);
+        }
+        if self.bulk_budget == 0 {
+            return None;
+        }
+        let entry = self.bulk.pop_front()?;
+        self.bulk_budget -= 1;
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::iter;
+
+    use storm::{Mesg, MesgId};
+    use storm_ext::MultiPost;
+
+    use super::*;
+
+    fn post() -> ExtMsg {
+        ExtMsg::PostMany(MultiPost {
+            recipients: empty!(),
+            mesg: Mesg {
+                parent_id: MesgId::default(),
+                body: b"bulk".to_vec(),
+                container_ids: empty!(),
+            },
+        })
+    }
+
+    fn poll(lanes: &mut ExtLanes) -> Vec<ExtMsg> {
+        lanes.renew();
+        iter::from_fn(|| lanes.pop()).map(|(_, msg)| msg).collect()
+    }
+
+    #[test]
+    fn bulk_is_budgeted() {
+        let mut lanes = ExtLanes::default();
+        for _ in 0..EXT_BULK_BUDGET * 2 + 1 {
+            lanes.push(None, post());
+        }
+        assert_eq!(poll(&mut lanes).len(), EXT_BULK_BUDGET);
+        assert_eq!(poll(&mut lanes).len(), EXT_BULK_BUDGET);
+        assert_eq!(poll(&mut lanes).len(), 1);
+        assert!(poll(&mut lanes).is_empty());
+    }
+
+    #[test]
+    fn control_overtakes_queued_bulk() {
+        let mut lanes = ExtLanes::default();
+        for _ in 0..EXT_BULK_BUDGET * 3 {
+            lanes.push(None, post());
+        }
+        let first = poll(&mut lanes);
+        assert!(first.iter().all(|msg| msg.lane() == ExtLane::Bulk));
+        assert_eq!(lanes.deferred(), EXT_BULK_BUDGET * 2);
+
+        // Control message queued after the bulk traffic is delivered before the rest of it
+        lanes.push(None, ExtMsg::Heartbeat(0));
+        let second = poll(&mut lanes);
+        assert_eq!(second[0].lane(), ExtLane::Control);
+        assert_eq!(second.len(), EXT_BULK_BUDGET + 1);
+        assert_eq!(lanes.deferred(), EXT_BULK_BUDGET);
+    }
+}
@@ -0,0 +1,141 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Interval between the metrics reports sent by the child daemons to stormd.
+pub const METRICS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Names and descriptions of the metrics exported by the node.
+const METRICS_HELP: &[(&str, &str)] = &[
+    ("storm_p2p_messages_received_total", "Storm p2p messages received from the remote peers"),
+    ("storm_p2p_messages_sent_total", "Storm p2p messages sent to the remote peers"),
+    ("storm_p2p_bytes_received_total", "Payload bytes received from the remote peers"),
+    ("storm_p2p_bytes_sent_total", "Payload bytes sent to the remote peers"),
+    ("storm_p2p_queue", "Outbound p2p messages waiting for the connection to the LNP node"),
+    ("storm_lnp_up", "Whether the connection to the LNP node is alive"),
+    ("storm_transfers_active", "Container transfers in progress"),
+    ("storm_transfers_completed_total", "Container transfers completed successfully"),
+    ("storm_transfers_failed_total", "Container transfers which have failed"),
+    ("storm_chunks_stored_total", "Container chunks received and stored by the transfer daemon"),
+    ("storm_chunk_bytes_stored_total", "Size of the stored container chunks, in bytes"),
+    ("storm_containers", "Containers known to the node"),
+    ("storm_apps_registered", "Storm apps registered with the node"),
+    ("storm_daemon_up", "Whether the daemon has connected to stormd"),
+    ("storm_daemon_last_seen_seconds", "UNIX timestamp of the last message from the daemon"),
+];
+
+/// Kind of the metric, as defined by the Prometheus exposition format.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+pub enum MetricKind {
+    /// Value which only grows, except resetting on the daemon restart
+    #[display("counter")]
+    Counter,
+
+    /// Value which may go up and down
+    #[display("gauge")]
+    Gauge,
+}
+
+/// Value of the metric with the given name and labels.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(NetworkEncode, NetworkDecode)]
+#[display("{name} {value}")]
+pub struct Sample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub kind: MetricKind,
+    pub value: u64,
+}
+
+/// Registry of the counters and gauges of a daemon.
+///
+/// Child daemons report their registries to stormd with [`crate::bus::CtlMsg::Metrics`]; stormd
+/// merges them into its own registry, labelling the samples with the daemon id, and exports the
+/// result in the Prometheus text format.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct MetricsRegistry {
+    samples: BTreeMap<(String, BTreeMap<String, String>), (MetricKind, u64)>,
+}
+
+impl MetricsRegistry {
+    /// Increments counter by the given value.
+    pub fn inc(&mut self, name: &str, labels: &[(&str, &str)], by: u64) {
+        let value = self.samples.entry(key(name, labels)).or_insert((MetricKind::Counter, 0));
+        value.1 += by;
+    }
+
+    /// Sets the counter to an absolute value, for the counters maintained elsewhere.
+    pub fn count(&mut self, name: &str, labels: &[(&str, &str)], value: u64) {
+        self.samples.insert(key(name, labels), (MetricKind::Counter, value));
+    }
+
+    pub fn set(&mut self, name: &str, labels: &[(&str, &str)], value: u64) {
+        self.samples.insert(key(name, labels), (MetricKind::Gauge, value));
+    }
+
+    pub fn samples(&self) -> Vec<Sample> {
+        self.samples
+            .iter()
+            .map(|((name, labels), (kind, value))| Sample {
+                name: name.clone(),
+                labels: labels.clone(),
+                kind: *kind,
+                value: *value,
+            })
+            .collect()
+    }
+
+    /// Replaces samples of the daemon with the ones it has reported.
+    pub fn merge(&mut self, daemon: &str, samples: Vec<Sample>) {
+        for mut sample in samples {
+            sample.labels.insert(s!("daemon"), daemon.to_owned());
+            self.samples.insert((sample.name, sample.labels), (sample.kind, sample.value));
+        }
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+        let mut last_name = None;
+        for ((name, labels), (kind, value)) in &self.samples {
+            if last_name != Some(name) {
+                if let Some((_, help)) = METRICS_HELP.iter().find(|(known, _)| known == name) {
+                    let _ = writeln!(text, "# HELP {} {}", name, help);
+                }
+                let _ = writeln!(text, "# TYPE {} {}", name, kind);
+                last_name = Some(name);
+            }
+            text.push_str(name);
+            if !labels.is_empty() {
+                let labels = labels
+                    .iter()
+                    .map(|(label, value)| format!("{}=\"{}\"", label, escape(value)))
+                    .collect::<Vec<_>>();
+                let _ = write!(text, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(text, " {}", value);
+        }
+        text
+    }
+}
+
+fn key(name: &str, labels: &[(&str, &str)]) -> (String, BTreeMap<String, String>) {
+    let labels =
+        labels.iter().map(|(label, value)| (label.to_string(), value.to_string())).collect();
+    (name.to_owned(), labels)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -10,13 +10,25 @@
 
 mod services;
 mod ctl;
+mod lanes;
+mod metrics;
 
+use std::ops::Deref;
+
+use internet2::{presentation, CreateUnmarshaller, TypedEnum, Unmarshall};
 use lnp2p::bifrost;
 use microservices::rpc;
 use storm_ext::ExtMsg;
-use storm_rpc::{RadioMsg, RpcMsg};
+use storm_rpc::{
+    Compressed, DictCompressed, Dictionary, Event, RadioMsg, RpcMsg, COMPRESSION_THRESHOLD,
+    DICT_COMPRESSION_THRESHOLD,
+};
 
-pub use self::ctl::{AddressedClientMsg, ChunkSend, CtlMsg};
+pub use self::ctl::{
+    set_threaded, terminate, AddressedClientMsg, ChunkReceived, ChunkSend, CtlMsg, PeerPaths,
+};
+pub(crate) use self::lanes::ExtLanes;
+pub use self::metrics::{MetricKind, MetricsRegistry, Sample, METRICS_REPORT_INTERVAL};
 pub(crate) use self::services::{DaemonId, Endpoints, Responder, ServiceBus};
 
 /// Service controller messages
@@ -52,6 +64,71 @@ pub(crate) enum BusMsg {
     #[display(inner)]
     #[from]
     Chat(RadioMsg),
+
+    /// Node event notifications
+    #[api(type = 0x82)]
+    #[display(inner)]
+    #[from]
+    Notify(Event),
+
+    /// Compressed encoding of another message
+    #[api(type = 0x7f)]
+    #[display(inner)]
+    #[from]
+    Compressed(Compressed),
+
+    /// Encoding of another message compressed with the dictionary of the app
+    #[api(type = 0x7e)]
+    #[display(inner)]
+    #[from]
+    DictCompressed(DictCompressed),
 }
 
 impl rpc::Request for BusMsg {}
+
+impl BusMsg {
+    /// Replaces the message with its compressed encoding if the message size exceeds
+    /// [`COMPRESSION_THRESHOLD`], or with its encoding compressed with the app dictionary, if the
+    /// dictionary is given and the message size is at least [`DICT_COMPRESSION_THRESHOLD`].
+    pub fn compress(self, dict: Option<&Dictionary>) -> BusMsg {
+        let data = self.serialize();
+        let res = match dict {
+            _ if data.len() > COMPRESSION_THRESHOLD => {
+                Compressed::deflate(&data).map(BusMsg::Compressed)
+            }
+            // The dictionary message itself must be readable by the side not knowing it yet
+            Some(_) if matches!(self, BusMsg::Storm(ExtMsg::Dictionary(_))) => return self,
+            Some(dict) if data.len() >= DICT_COMPRESSION_THRESHOLD => {
+                dict.deflate(&data).map(BusMsg::DictCompressed)
+            }
+            _ => return self,
+        };
+        match res {
+            Ok(compressed) => compressed,
+            Err(err) => {
+                warn!("Unable to compress {}: {}", self, err);
+                self
+            }
+        }
+    }
+
+    pub fn decompress(compressed: &Compressed) -> Result<BusMsg, presentation::Error> {
+        BusMsg::unpack(&compressed.inflate()?)
+    }
+
+    pub fn decompress_with(
+        compressed: &DictCompressed,
+        dict: &Dictionary,
+    ) -> Result<BusMsg, presentation::Error> {
+        BusMsg::unpack(&compressed.inflate(dict)?)
+    }
+
+    fn unpack(data: &[u8]) -> Result<BusMsg, presentation::Error> {
+        match BusMsg::create_unmarshaller().unmarshall(data)?.deref() {
+            BusMsg::Compressed(_) | BusMsg::DictCompressed(_) => {
+                Err(presentation::Error::InvalidValue)
+            }
+            msg => Ok(msg.clone()),
+        }
+    }
+}
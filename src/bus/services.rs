@@ -15,8 +15,9 @@ use lnp2p::bifrost::BifrostApp;
 use microservices::esb::ClientId;
 use microservices::{esb, rpc};
 use storm::{p2p, StormApp};
-use storm_ext::ExtMsg;
-use storm_rpc::{RadioMsg, RpcMsg, ServiceId};
+use storm_ext::{ExtMsg, OpaqueEnvelope, BIFROST_APP_STORM_OPAQUE};
+use storm_rpc::{BusFeatures, Dictionary, Event, RadioMsg, RpcMsg, ServiceId};
+use strict_encoding::StrictEncode;
 
 use crate::bus::{BusMsg, CtlMsg};
 
@@ -43,9 +44,41 @@ pub(crate) enum ServiceBus {
     #[display("MSG")]
     Msg,
 
+    /// Dedicated LN P2P (Bifrost) message bus for chunk payloads
+    #[display("BULK")]
+    Bulk,
+
+    /// LN P2P (Bifrost) message bus of an additional peer connection service
+    #[display("MSG{0}")]
+    PeerMsg(u8),
+
     /// Pub/sub bus used for chat daemon
     #[display("CHAT")]
     Chat,
+
+    /// Pub/sub bus with the node event notifications for the clients
+    #[display("NOTIFY")]
+    Notify,
+}
+
+impl ServiceBus {
+    /// Message bus of the peer connection service with the given number, where `0` stands for
+    /// the main message bus.
+    pub fn peer_service(no: u8) -> ServiceBus {
+        match no {
+            0 => ServiceBus::Msg,
+            no => ServiceBus::PeerMsg(no),
+        }
+    }
+
+    /// Number of the peer connection service for the message buses.
+    pub fn peer_service_no(self) -> Option<u8> {
+        match self {
+            ServiceBus::Msg => Some(0),
+            ServiceBus::PeerMsg(no) => Some(no),
+            _ => None,
+        }
+    }
 }
 
 impl esb::BusId for ServiceBus {
@@ -57,6 +90,29 @@ where
     Self: esb::Handler<ServiceBus>,
     esb::Error<ServiceId>: From<Self::Error>,
 {
+    /// Features negotiated with the service in the connection handshake.
+    #[inline]
+    fn bus_features(&self, _service_id: &ServiceId) -> BusFeatures { none!() }
+
+    /// Compression dictionary known to both this daemon and the service.
+    #[inline]
+    fn dictionary(&self, _service_id: &ServiceId) -> Option<&Dictionary> { None }
+
+    /// Message bus through which the remote peer is reachable.
+    #[inline]
+    fn msg_bus(&self, _remote_id: NodeId) -> ServiceBus { ServiceBus::Msg }
+
+    #[inline]
+    fn pack(&self, service_id: &ServiceId, message: BusMsg) -> BusMsg {
+        let features = self.bus_features(service_id);
+        if features.compression {
+            let dict = if features.dictionaries { self.dictionary(service_id) } else { None };
+            message.compress(dict)
+        } else {
+            message
+        }
+    }
+
     #[inline]
     fn send_p2p_reporting_client(
         &self,
@@ -100,13 +156,60 @@ where
         endpoints: &mut Endpoints,
         remote_id: NodeId,
         message: impl Into<p2p::Messages>,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        self.send_p2p_via(endpoints, self.msg_bus(remote_id), remote_id, message)
+    }
+
+    /// Sends message through the given message bus, which may be other than the bus through
+    /// which the remote peer is usually reached.
+    #[inline]
+    fn send_p2p_via(
+        &self,
+        endpoints: &mut Endpoints,
+        bus: ServiceBus,
+        remote_id: NodeId,
+        message: impl Into<p2p::Messages>,
     ) -> Result<(), esb::Error<ServiceId>> {
         let payload = message.into().serialize();
         let message = BusMsg::Bifrost(bifrost::Messages::Message(bifrost::Msg {
             app: BifrostApp::Storm,
             payload: Box::from(payload),
         }));
-        endpoints.send_to(ServiceBus::Msg, self.identity(), ServiceId::Peer(remote_id), message)
+        endpoints.send_to(bus, self.identity(), ServiceId::Peer(remote_id), message)
+    }
+
+    /// Sends app-opaque message, which is not a part of the Storm p2p protocol.
+    fn send_opaque(
+        &self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        envelope: OpaqueEnvelope,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        let payload = envelope
+            .strict_serialize()
+            .expect("app-opaque message payload size is checked by the node");
+        let message = BusMsg::Bifrost(bifrost::Messages::Message(bifrost::Msg {
+            app: BifrostApp::Vendor(BIFROST_APP_STORM_OPAQUE),
+            payload: Box::from(payload),
+        }));
+        let bus = self.msg_bus(remote_id);
+        endpoints.send_to(bus, self.identity(), ServiceId::Peer(remote_id), message)
+    }
+
+    /// Sends message over the dedicated bulk data bus.
+    #[inline]
+    fn send_bulk(
+        &self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        message: impl Into<p2p::Messages>,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        let payload = message.into().serialize();
+        let message = BusMsg::Bifrost(bifrost::Messages::Message(bifrost::Msg {
+            app: BifrostApp::Storm,
+            payload: Box::from(payload),
+        }));
+        endpoints.send_to(ServiceBus::Bulk, self.identity(), ServiceId::Peer(remote_id), message)
     }
 
     #[inline]
@@ -116,12 +219,9 @@ where
         client_id: ClientId,
         message: impl Into<RpcMsg>,
     ) -> Result<(), esb::Error<ServiceId>> {
-        endpoints.send_to(
-            ServiceBus::Rpc,
-            self.identity(),
-            ServiceId::Client(client_id),
-            BusMsg::Rpc(message.into()),
-        )
+        let service_id = ServiceId::Client(client_id);
+        let message = self.pack(&service_id, BusMsg::Rpc(message.into()));
+        endpoints.send_to(ServiceBus::Rpc, self.identity(), service_id, message)
     }
 
     #[inline]
@@ -141,12 +241,9 @@ where
         app_id: Option<StormApp>,
         message: impl Into<ExtMsg>,
     ) -> Result<(), esb::Error<ServiceId>> {
-        endpoints.send_to(
-            ServiceBus::Storm,
-            self.identity(),
-            app_id.map(ServiceId::StormApp).unwrap_or(ServiceId::stormd()),
-            BusMsg::Storm(message.into()),
-        )
+        let service_id = app_id.map(ServiceId::StormApp).unwrap_or(ServiceId::stormd());
+        let message = self.pack(&service_id, BusMsg::Storm(message.into()));
+        endpoints.send_to(ServiceBus::Storm, self.identity(), service_id, message)
     }
 
     #[inline]
@@ -163,4 +260,30 @@ where
             BusMsg::Chat(message.into()),
         )
     }
+
+    #[inline]
+    fn send_event(
+        &self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        event: Event,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        let service_id = ServiceId::Client(client_id);
+        let message = self.pack(&service_id, BusMsg::Notify(event));
+        endpoints.send_to(ServiceBus::Rpc, self.identity(), service_id, message)
+    }
+
+    #[inline]
+    fn send_notify(
+        &self,
+        endpoints: &mut Endpoints,
+        event: Event,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        endpoints.send_to(
+            ServiceBus::Notify,
+            self.identity(),
+            ServiceId::stormd(),
+            BusMsg::Notify(event),
+        )
+    }
 }
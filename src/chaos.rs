@@ -0,0 +1,79 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Chaos testing hooks injecting faults into the node subsystems, so that the recovery paths
+//! get exercised in the staging environments.
+//!
+//! Faults are toggled at runtime over RPC and are injected only by the builds with the `chaos`
+//! feature; other builds never inject them and refuse to toggle them. Faults are kept per
+//! process and are not persisted.
+
+use std::sync::atomic::{AtomicU16, AtomicU32, Ordering};
+use std::time::Duration;
+use std::{io, thread};
+
+use rand::Rng;
+use storm_rpc::{Fault, FaultTarget};
+
+/// Probabilities of the faults, in per-mille, indexed by [`FaultTarget`].
+static RATES: [AtomicU16; 3] = [AtomicU16::new(0), AtomicU16::new(0), AtomicU16::new(0)];
+
+/// Maximal delay of the bus messages, in milliseconds.
+static MAX_DELAY: AtomicU32 = AtomicU32::new(1000);
+
+/// Detects whether the node is built with the chaos testing hooks.
+pub fn is_supported() -> bool { cfg!(feature = "chaos") }
+
+pub fn set(fault: Fault) {
+    RATES[fault.target as usize].store(fault.rate.min(1000), Ordering::Relaxed);
+    if fault.target == FaultTarget::BusDelay {
+        MAX_DELAY.store(fault.max_delay_ms, Ordering::Relaxed);
+    }
+}
+
+pub fn faults() -> Vec<Fault> {
+    FaultTarget::ALL
+        .into_iter()
+        .map(|target| Fault {
+            target,
+            rate: RATES[target as usize].load(Ordering::Relaxed),
+            max_delay_ms: MAX_DELAY.load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+/// Decides whether the fault has to be injected now.
+pub fn strikes(target: FaultTarget) -> bool {
+    if !is_supported() {
+        return false;
+    }
+    let rate = RATES[target as usize].load(Ordering::Relaxed);
+    rate > 0 && rand::thread_rng().gen_range(0..1000) < rate
+}
+
+/// Delays handling of the bus message, if the fault strikes.
+pub fn delay() {
+    if !strikes(FaultTarget::BusDelay) {
+        return;
+    }
+    let max_delay = MAX_DELAY.load(Ordering::Relaxed) as u64;
+    let delay = rand::thread_rng().gen_range(0..=max_delay);
+    trace!("Chaos testing delays the bus message by {} ms", delay);
+    thread::sleep(Duration::from_millis(delay));
+}
+
+/// Fails sync of the file to the disk, if the fault strikes.
+pub fn fsync() -> io::Result<()> {
+    if !strikes(FaultTarget::FsyncFailure) {
+        return Ok(());
+    }
+    warn!("Chaos testing fails the disk sync");
+    Err(io::Error::new(io::ErrorKind::Other, "disk sync failure injected by chaos testing"))
+}
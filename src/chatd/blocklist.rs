@@ -0,0 +1,58 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use strict_encoding::StrictDecode;
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the blocked chat peers.
+pub const STORM_NODE_CHAT_BLOCKLIST_FILE: &str = "chat_blocked.dat";
+
+/// Peers which chat messages are silently dropped by chatd.
+///
+/// The block list applies to the chat only; the peers remain connected to the node and may use
+/// other apps.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Blocklist {
+    path: PathBuf,
+    blocked: BTreeSet<NodeId>,
+}
+
+impl Blocklist {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_BLOCKLIST_FILE);
+        let blocked =
+            if path.exists() { BTreeSet::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(Blocklist { path, blocked })
+    }
+
+    pub fn is_blocked(&self, remote_id: NodeId) -> bool { self.blocked.contains(&remote_id) }
+
+    /// Blocks or unblocks the peer, returning whether the block list was changed.
+    pub fn block(
+        &mut self,
+        remote_id: NodeId,
+        blocked: bool,
+    ) -> Result<bool, strict_encoding::Error> {
+        let changed =
+            if blocked { self.blocked.insert(remote_id) } else { self.blocked.remove(&remote_id) };
+        if changed {
+            checkpoint(&self.path, &self.blocked)?;
+        }
+        Ok(changed)
+    }
+
+    pub fn list(&self) -> &BTreeSet<NodeId> { &self.blocked }
+}
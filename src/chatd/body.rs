@@ -0,0 +1,48 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use storm_rpc::LinkPreview;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+/// Byte separating message text from the strict-encoded message metadata in the message body.
+///
+/// Messages without metadata consist of the text only, so they remain readable by the nodes which
+/// do not support it.
+pub const META_SEPARATOR: u8 = 0x00;
+
+/// Metadata attached by the sender to the chat message.
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+pub struct ChatMeta {
+    /// Previews of the links mentioned in the message text.
+    pub previews: Vec<LinkPreview>,
+    /// Number of seconds after which the messages of the conversation disappear, or zero if they
+    /// are kept. Peers adopt the setting of the received message, so either side may change it.
+    pub disappear_after: u64,
+}
+
+/// Composes chat message body from the text and its metadata.
+pub fn encode_body(text: String, meta: Option<&ChatMeta>) -> Vec<u8> {
+    let mut body = text.into_bytes();
+    if let Some(meta) = meta {
+        body.push(META_SEPARATOR);
+        meta.strict_encode(&mut body).expect("in-memory encoding");
+    }
+    body
+}
+
+/// Splits chat message body into the text and the metadata attached by the sender.
+pub fn decode_body(body: &[u8]) -> (String, Option<ChatMeta>) {
+    if let Some(pos) = body.iter().position(|b| *b == META_SEPARATOR) {
+        if let Ok(meta) = ChatMeta::strict_deserialize(&body[pos + 1..]) {
+            return (String::from_utf8_lossy(&body[..pos]).to_string(), Some(meta));
+        }
+    }
+    (String::from_utf8_lossy(body).to_string(), None)
+}
@@ -0,0 +1,123 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use internet2::addr::NodeId;
+use storm_rpc::{ChatContact, KeyChange};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the chat contacts.
+pub const STORM_NODE_CHAT_CONTACTS_FILE: &str = "chat_contacts.dat";
+
+/// Named chat contacts, binding a name known to the user to the peer key.
+///
+/// Binding a name to another key, as well as the change of the chat encryption key of the peer,
+/// resets the contact verification, so a substituted key can't pass for the verified one.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Contacts {
+    path: PathBuf,
+    contacts: BTreeMap<String, ChatContact>,
+}
+
+impl Contacts {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_CONTACTS_FILE);
+        let contacts =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(Contacts { path, contacts })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.contacts)?;
+        Ok(())
+    }
+
+    /// Binds the name to the peer key, returning the key change if the name was bound to another
+    /// key before.
+    pub fn set(
+        &mut self,
+        name: String,
+        remote_id: NodeId,
+    ) -> Result<Option<KeyChange>, strict_encoding::Error> {
+        let prev = self.contacts.insert(name.clone(), ChatContact {
+            name: name.clone(),
+            remote_id,
+            verified: false,
+        });
+        let change = match prev {
+            Some(prev) if prev.remote_id == remote_id => {
+                self.contacts.insert(name, prev);
+                return Ok(None);
+            }
+            Some(prev) => Some(KeyChange {
+                name,
+                old: prev.remote_id,
+                new: remote_id,
+            }),
+            None => None,
+        };
+        self.save()?;
+        Ok(change)
+    }
+
+    /// Resets verification of the contacts bound to the peer whose chat encryption key has
+    /// changed, returning the key changes to report for them.
+    pub fn chat_key_changed(
+        &mut self,
+        remote_id: NodeId,
+    ) -> Result<Vec<KeyChange>, strict_encoding::Error> {
+        let mut changes = vec![];
+        for contact in self.contacts.values_mut().filter(|contact| contact.remote_id == remote_id) {
+            contact.verified = false;
+            changes.push(KeyChange {
+                name: contact.name.clone(),
+                old: remote_id,
+                new: remote_id,
+            });
+        }
+        if !changes.is_empty() {
+            self.save()?;
+        }
+        Ok(changes)
+    }
+
+    /// Marks contact as verified, returning whether the contact is known.
+    pub fn verify(&mut self, name: &str) -> Result<bool, strict_encoding::Error> {
+        match self.contacts.get_mut(name) {
+            Some(contact) => contact.verified = true,
+            None => return Ok(false),
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = &ChatContact> { self.contacts.values() }
+}
+
+/// Computes verification code of the conversation between two peers. The code does not depend on
+/// the side computing it, so the users may compare it over an independent channel.
+pub fn safety_number(local_id: NodeId, remote_id: NodeId) -> String {
+    let (first, second) =
+        if local_id <= remote_id { (local_id, remote_id) } else { (remote_id, local_id) };
+    let mut engine = sha256::Hash::engine();
+    engine.input(&first.strict_serialize().expect("in-memory encoding"));
+    engine.input(&second.strict_serialize().expect("in-memory encoding"));
+    let hash = sha256::Hash::from_engine(engine);
+    let mut num = [0u8; 8];
+    num.copy_from_slice(&hash[..8]);
+    let digits = format!("{:012}", u64::from_le_bytes(num) % 1_000_000_000_000);
+    format!("{} {} {}", &digits[..4], &digits[4..8], &digits[8..])
+}
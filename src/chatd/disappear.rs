@@ -0,0 +1,57 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use strict_encoding::StrictDecode;
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the disappearing message policies.
+pub const STORM_NODE_CHAT_DISAPPEAR_FILE: &str = "chat_disappear.dat";
+
+/// Number of seconds after which the messages of each chat conversation disappear. Messages of
+/// the conversations which are not listed are kept.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DisappearPolicies {
+    path: PathBuf,
+    policies: BTreeMap<NodeId, u64>,
+}
+
+impl DisappearPolicies {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_DISAPPEAR_FILE);
+        let policies =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(DisappearPolicies { path, policies })
+    }
+
+    /// Returns number of seconds after which the messages disappear, or zero if they are kept.
+    pub fn get(&self, remote_id: NodeId) -> u64 {
+        self.policies.get(&remote_id).copied().unwrap_or_default()
+    }
+
+    /// Sets the policy, returning whether it was changed.
+    pub fn set(&mut self, remote_id: NodeId, after: u64) -> Result<bool, strict_encoding::Error> {
+        if self.get(remote_id) == after {
+            return Ok(false);
+        }
+        if after == 0 {
+            self.policies.remove(&remote_id);
+        } else {
+            self.policies.insert(remote_id, after);
+        }
+        checkpoint(&self.path, &self.policies)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,50 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use strict_encoding::StrictDecode;
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the chat drafts.
+pub const STORM_NODE_CHAT_DRAFTS_FILE: &str = "chat_drafts.dat";
+
+/// Unsent messages of the chat conversations, shared by all clients connected to the node.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Drafts {
+    path: PathBuf,
+    drafts: BTreeMap<NodeId, String>,
+}
+
+impl Drafts {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_DRAFTS_FILE);
+        let drafts =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(Drafts { path, drafts })
+    }
+
+    pub fn get(&self, remote_id: NodeId) -> Option<&String> { self.drafts.get(&remote_id) }
+
+    /// Saves the draft, removing it if the draft is empty.
+    pub fn save(&mut self, remote_id: NodeId, draft: String) -> Result<(), strict_encoding::Error> {
+        if draft.is_empty() {
+            self.drafts.remove(&remote_id);
+        } else {
+            self.drafts.insert(remote_id, draft);
+        }
+        checkpoint(&self.path, &self.drafts)?;
+        Ok(())
+    }
+}
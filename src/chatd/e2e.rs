@@ -0,0 +1,214 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use internet2::addr::NodeId;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the chat encryption key.
+pub const STORM_NODE_CHAT_KEY_FILE: &str = "chat.key";
+
+/// Name of the file inside the data directory keeping the chat encryption keys of the peers.
+pub const STORM_NODE_CHAT_PEER_KEYS_FILE: &str = "chat_peer_keys.dat";
+
+/// Byte prefixing chat message body carrying [`KeyAgreement`].
+pub const KEY_AGREEMENT_TAG: u8 = 0x01;
+
+/// Byte prefixing encrypted chat message body, which is followed by the nonce and the
+/// ciphertext.
+pub const CIPHERTEXT_TAG: u8 = 0x02;
+
+/// Maximal number of chat messages to a peer held until the key agreement.
+pub const MAX_HELD_MESSAGES: usize = 64;
+
+const NONCE_LEN: usize = 12;
+
+/// Tag of the conversation key derivation from the ECDH shared secret.
+const CONVERSATION_KEY_TAG: &[u8] = b"storm:chat:e2e";
+
+/// Announcement of the chat encryption key to the peer.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct KeyAgreement {
+    pub chat_key: PublicKey,
+    /// Whether the agreement answers the agreement of the peer, and must not be answered
+    pub reply: bool,
+}
+
+impl KeyAgreement {
+    pub fn to_body(&self) -> Vec<u8> {
+        let mut body = vec![KEY_AGREEMENT_TAG];
+        self.strict_encode(&mut body).expect("in-memory encoding");
+        body
+    }
+}
+
+/// Chat message body as it is received from the peer.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Envelope<'body> {
+    KeyAgreement(KeyAgreement),
+    Ciphertext(&'body [u8]),
+    /// Body sent by the nodes which do not support encryption. It is rejected from the peers
+    /// which have agreed the chat key.
+    Plaintext(&'body [u8]),
+}
+
+impl<'body> Envelope<'body> {
+    pub fn parse(body: &'body [u8]) -> Self {
+        match body.split_first() {
+            Some((&KEY_AGREEMENT_TAG, data)) => match KeyAgreement::strict_deserialize(data) {
+                Ok(agreement) => Envelope::KeyAgreement(agreement),
+                Err(_) => Envelope::Plaintext(body),
+            },
+            Some((&CIPHERTEXT_TAG, data)) => Envelope::Ciphertext(data),
+            _ => Envelope::Plaintext(body),
+        }
+    }
+}
+
+/// End-to-end encryption of the chat messages.
+///
+/// Each side has a chat key, generated on the first start and persisted in the data directory,
+/// which it announces to the peer with [`KeyAgreement`]. The conversation key is derived from the
+/// ECDH secret of the own chat key and the one of the peer, so the message content is never seen
+/// by the nodes relaying and storing the messages. Chat key of the peer is bound to its node id,
+/// which is authenticated by the transport. Messages to the peers with unknown key are held until
+/// the key agreement, for the daemon lifetime and up to [`MAX_HELD_MESSAGES`] per peer. Once the
+/// key is agreed, plaintext messages from the peer are not accepted anymore, so a relaying node
+/// can't inject them into the conversation.
+#[derive(Clone, Debug)]
+pub struct ChatKeys {
+    path: PathBuf,
+    secret_key: SecretKey,
+    chat_key: PublicKey,
+    peers: BTreeMap<NodeId, PublicKey>,
+    held: BTreeMap<NodeId, Vec<Vec<u8>>>,
+}
+
+impl ChatKeys {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let key_path = data_dir.join(STORM_NODE_CHAT_KEY_FILE);
+        let secret_key = if key_path.exists() {
+            SecretKey::strict_decode(fs::File::open(&key_path)?)?
+        } else {
+            debug!("Generating chat encryption key");
+            let secret_key = SecretKey::new(&mut rand::thread_rng());
+            checkpoint(&key_path, &secret_key)?;
+            secret_key
+        };
+        let chat_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+        let path = data_dir.join(STORM_NODE_CHAT_PEER_KEYS_FILE);
+        let peers =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(ChatKeys {
+            path,
+            secret_key,
+            chat_key,
+            peers,
+            held: empty!(),
+        })
+    }
+
+    pub fn agreement(&self, reply: bool) -> KeyAgreement {
+        KeyAgreement {
+            chat_key: self.chat_key,
+            reply,
+        }
+    }
+
+    /// Returns whether the chat key of the peer is known.
+    pub fn is_agreed(&self, remote_id: NodeId) -> bool { self.peers.contains_key(&remote_id) }
+
+    /// Binds chat key to the peer, returning whether it replaces another key.
+    pub fn accept(
+        &mut self,
+        remote_id: NodeId,
+        chat_key: PublicKey,
+    ) -> Result<bool, strict_encoding::Error> {
+        let prev = self.peers.insert(remote_id, chat_key);
+        if prev == Some(chat_key) {
+            return Ok(false);
+        }
+        checkpoint(&self.path, &self.peers)?;
+        Ok(prev.is_some())
+    }
+
+    /// Holds message body until the key agreement with the peer, returning `false` if too many
+    /// messages are held for the peer already and the body is dropped.
+    pub fn hold(&mut self, remote_id: NodeId, body: Vec<u8>) -> bool {
+        let held = self.held.entry(remote_id).or_default();
+        if held.len() >= MAX_HELD_MESSAGES {
+            return false;
+        }
+        held.push(body);
+        true
+    }
+
+    /// Returns message bodies held until the key agreement with the peer.
+    pub fn release(&mut self, remote_id: NodeId) -> Vec<Vec<u8>> {
+        self.held.remove(&remote_id).unwrap_or_default()
+    }
+
+    /// Encrypts message body for the peer, returning `None` if the peer chat key is unknown.
+    pub fn encrypt(&self, remote_id: NodeId, body: &[u8]) -> Option<Vec<u8>> {
+        let peer_key = self.peers.get(&remote_id)?;
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let aad = associated_data(&self.chat_key, peer_key);
+        let ciphertext = self
+            .cipher(peer_key)
+            .encrypt(Nonce::from_slice(&nonce), Payload {
+                msg: body,
+                aad: &aad,
+            })
+            .expect("in-memory encryption");
+        let mut envelope = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        envelope.push(CIPHERTEXT_TAG);
+        envelope.extend(nonce);
+        envelope.extend(ciphertext);
+        Some(envelope)
+    }
+
+    /// Decrypts message body received from the peer, returning `None` if the peer chat key is
+    /// unknown or the ciphertext is not authentic.
+    pub fn decrypt(&self, remote_id: NodeId, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        let peer_key = self.peers.get(&remote_id)?;
+        if ciphertext.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, msg) = ciphertext.split_at(NONCE_LEN);
+        let aad = associated_data(peer_key, &self.chat_key);
+        self.cipher(peer_key).decrypt(Nonce::from_slice(nonce), Payload { msg, aad: &aad }).ok()
+    }
+
+    fn cipher(&self, peer_key: &PublicKey) -> ChaCha20Poly1305 {
+        let secret = SharedSecret::new(peer_key, &self.secret_key);
+        let mut engine = sha256::Hash::engine();
+        engine.input(CONVERSATION_KEY_TAG);
+        engine.input(&secret.secret_bytes());
+        let key = sha256::Hash::from_engine(engine);
+        ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+    }
+}
+
+/// Binds the ciphertext to the chat keys of the sender and the receiver.
+fn associated_data(sender: &PublicKey, receiver: &PublicKey) -> Vec<u8> {
+    let mut aad = sender.serialize().to_vec();
+    aad.extend(receiver.serialize());
+    aad
+}
@@ -0,0 +1,196 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm_rpc::{AddressedMsg, ChatEntry, ChatHistoryReq, ChatMatch, LinkPreview, Page, TopicId};
+use strict_encoding::StrictDecode;
+
+use crate::stormd::{checkpoint, unix_time};
+
+/// Name of the file inside the data directory keeping the chat history.
+pub const STORM_NODE_CHAT_HISTORY_FILE: &str = "chat_history.dat";
+
+/// Name of the file inside the data directory keeping the chat history of the topics.
+pub const STORM_NODE_CHAT_TOPICS_FILE: &str = "chat_topics.dat";
+
+/// Messages sent and received in each of the chat conversations.
+///
+/// Direct conversations are kept per remote peer. Messages replying to a topic belong to the
+/// topic conversation, which is shared by all the peers posting to the topic.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct History {
+    path: PathBuf,
+    conversations: BTreeMap<NodeId, Vec<ChatEntry>>,
+    topics_path: PathBuf,
+    topics: BTreeMap<TopicId, Vec<AddressedMsg<ChatEntry>>>,
+}
+
+impl History {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_HISTORY_FILE);
+        let conversations =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        let topics_path = data_dir.join(STORM_NODE_CHAT_TOPICS_FILE);
+        let topics = if topics_path.exists() {
+            BTreeMap::strict_decode(fs::File::open(&topics_path)?)?
+        } else {
+            empty!()
+        };
+        Ok(History {
+            path,
+            conversations,
+            topics_path,
+            topics,
+        })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.conversations)?;
+        Ok(())
+    }
+
+    fn save_topics(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.topics_path, &self.topics)?;
+        Ok(())
+    }
+
+    /// Adds message to the conversation history, returning the recorded entry.
+    pub fn record(
+        &mut self,
+        remote_id: NodeId,
+        topic: Option<TopicId>,
+        incoming: bool,
+        text: String,
+        previews: Vec<LinkPreview>,
+        disappear_after: u64,
+    ) -> Result<ChatEntry, strict_encoding::Error> {
+        let timestamp = unix_time();
+        let entry = ChatEntry {
+            timestamp,
+            incoming,
+            text,
+            previews,
+            expires: Some(timestamp + disappear_after).filter(|_| disappear_after > 0),
+        };
+        match topic {
+            Some(topic) => {
+                self.topics.entry(topic).or_default().push(AddressedMsg {
+                    remote_id,
+                    data: entry.clone(),
+                });
+                self.save_topics()?;
+            }
+            None => {
+                self.conversations.entry(remote_id).or_default().push(entry.clone());
+                self.save()?;
+            }
+        }
+        Ok(entry)
+    }
+
+    /// Returns messages of the conversation starting from the requested time, oldest first.
+    pub fn page(&self, req: &ChatHistoryReq) -> Page<ChatEntry> {
+        match req.topic {
+            Some(topic) => Page::by_offset(
+                self.topics
+                    .get(&topic)
+                    .into_iter()
+                    .flatten()
+                    .filter(|msg| msg.remote_id == req.peer && msg.data.timestamp >= req.from)
+                    .map(|msg| msg.data.clone()),
+                &req.page,
+            ),
+            None => Page::by_offset(
+                self.conversations
+                    .get(&req.peer)
+                    .into_iter()
+                    .flatten()
+                    .filter(|entry| entry.timestamp >= req.from)
+                    .cloned(),
+                &req.page,
+            ),
+        }
+    }
+
+    /// Deletes messages which have expired.
+    pub fn expire(&mut self) -> Result<(), strict_encoding::Error> {
+        let now = unix_time();
+        let mut expired = 0usize;
+        for entries in self.conversations.values_mut() {
+            let len = entries.len();
+            entries.retain(|entry| entry.expires.map(|expires| expires > now).unwrap_or(true));
+            expired += len - entries.len();
+        }
+        let mut expired_topics = 0usize;
+        for entries in self.topics.values_mut() {
+            let len = entries.len();
+            entries.retain(|msg| msg.data.expires.map(|expires| expires > now).unwrap_or(true));
+            expired_topics += len - entries.len();
+        }
+        if expired + expired_topics == 0 {
+            return Ok(());
+        }
+        debug!("Deleting {} expired chat messages", expired + expired_topics);
+        if expired > 0 {
+            self.conversations.retain(|_, entries| !entries.is_empty());
+            self.save()?;
+        }
+        if expired_topics > 0 {
+            self.topics.retain(|_, entries| !entries.is_empty());
+            self.save_topics()?;
+        }
+        Ok(())
+    }
+
+    /// Returns messages of the conversation containing the query, ignoring ASCII case.
+    pub fn search(&self, remote_id: NodeId, query: &str) -> Vec<ChatMatch> {
+        if query.is_empty() {
+            return vec![];
+        }
+        let entries = match self.conversations.get(&remote_id) {
+            Some(entries) => entries,
+            None => return vec![],
+        };
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let offsets = match_offsets(&entry.text, query);
+                if offsets.is_empty() {
+                    return None;
+                }
+                Some(ChatMatch {
+                    entry: entry.clone(),
+                    offsets,
+                    len: query.len() as u32,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Finds non-overlapping matches of the query in the text, ignoring ASCII case.
+fn match_offsets(text: &str, query: &str) -> Vec<u32> {
+    let (text, query) = (text.as_bytes(), query.as_bytes());
+    let mut offsets = vec![];
+    let mut pos = 0usize;
+    while pos + query.len() <= text.len() {
+        if text[pos..pos + query.len()].eq_ignore_ascii_case(query) {
+            offsets.push(pos as u32);
+            pos += query.len();
+        } else {
+            pos += 1;
+        }
+    }
+    offsets
+}
@@ -8,10 +8,28 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+mod blocklist;
+mod body;
+mod contacts;
+mod disappear;
+mod drafts;
+mod e2e;
+mod history;
+mod notify;
+#[cfg(feature = "link-preview")]
+mod preview;
 mod service;
 #[cfg(feature = "server")]
 mod opts;
 
+pub use blocklist::Blocklist;
+pub use body::{ChatMeta, META_SEPARATOR};
+pub use contacts::{safety_number, Contacts};
+pub use disappear::DisappearPolicies;
+pub use drafts::Drafts;
+pub use e2e::{ChatKeys, Envelope, KeyAgreement, CIPHERTEXT_TAG, KEY_AGREEMENT_TAG};
+pub use history::History;
+pub use notify::NotifyPrefs;
 #[cfg(feature = "server")]
 pub use opts::Opts;
 pub use service::{run, Runtime};
@@ -0,0 +1,59 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm_rpc::NotifyPref;
+use strict_encoding::StrictDecode;
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the chat notification preferences.
+pub const STORM_NODE_CHAT_NOTIFY_FILE: &str = "chat_notify.dat";
+
+/// Notification preferences of the chat conversations. Conversations which are not listed use
+/// the default preference.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NotifyPrefs {
+    path: PathBuf,
+    prefs: BTreeMap<NodeId, NotifyPref>,
+}
+
+impl NotifyPrefs {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_CHAT_NOTIFY_FILE);
+        let prefs =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(NotifyPrefs { path, prefs })
+    }
+
+    pub fn get(&self, remote_id: NodeId) -> NotifyPref {
+        self.prefs.get(&remote_id).copied().unwrap_or_default()
+    }
+
+    pub fn set(
+        &mut self,
+        remote_id: NodeId,
+        pref: NotifyPref,
+    ) -> Result<(), strict_encoding::Error> {
+        if pref == NotifyPref::default() {
+            self.prefs.remove(&remote_id);
+        } else {
+            self.prefs.insert(remote_id, pref);
+        }
+        checkpoint(&self.path, &self.prefs)?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> &BTreeMap<NodeId, NotifyPref> { &self.prefs }
+}
@@ -28,7 +28,7 @@ impl Options for Opts {
 
     fn shared(&self) -> &crate::opts::Opts { &self.shared }
 
-    fn config(&self) -> Self::Conf { () }
+    fn config(&self) -> Self::Conf {}
 }
 
 #[cfg(feature = "server")]
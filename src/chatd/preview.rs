@@ -0,0 +1,221 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use internet2::addr::{NodeId, ServiceAddr};
+use storm_rpc::{AddressedMsg, LinkPreview, PreviewedChat};
+
+/// Maximal number of links in a message for which the previews are generated.
+pub const MAX_PREVIEWS: usize = 4;
+
+/// Maximal number of bytes of the linked page which are read looking for the metadata.
+pub const MAX_PAGE_SIZE: u64 = 256 * 1024;
+
+pub const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time within which the previews of all the links of a message must be fetched; the message is
+/// sent with the previews fetched by then.
+pub const PREVIEW_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Number of messages waiting for the preview worker, above which messages are sent without the
+/// previews.
+pub const PREVIEW_QUEUE: usize = 64;
+
+/// Delay before the preview worker reconnects to the RPC interface.
+const RPC_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Fetches link previews of the sent messages in a background thread, so slow pages do not
+/// block the chat daemon.
+///
+/// The worker passes the message together with its previews back to the daemon over the RPC
+/// interface, so the daemon sends it to the peer afterwards. Messages to the peer which has
+/// messages waiting for the previews are passed through the worker as well, keeping them in order.
+#[derive(Debug)]
+pub struct Previewer {
+    sender: mpsc::SyncSender<AddressedMsg<String>>,
+    pending: BTreeMap<NodeId, usize>,
+}
+
+impl Previewer {
+    pub fn start(rpc_endpoint: ServiceAddr, chat_endpoint: ServiceAddr) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(PREVIEW_QUEUE);
+        thread::spawn(move || work(receiver, rpc_endpoint, chat_endpoint));
+        Previewer {
+            sender,
+            pending: empty!(),
+        }
+    }
+
+    /// Passes message to the worker, returning it back if it must be sent right away.
+    pub fn submit(&mut self, remote_id: NodeId, text: String) -> Option<String> {
+        let pending = self.pending.entry(remote_id).or_default();
+        if *pending == 0 && links(&text).next().is_none() {
+            return Some(text);
+        }
+        match self.sender.try_send(AddressedMsg {
+            remote_id,
+            data: text,
+        }) {
+            Ok(()) => {
+                *pending += 1;
+                None
+            }
+            Err(mpsc::TrySendError::Full(msg) | mpsc::TrySendError::Disconnected(msg)) => {
+                warn!("Link preview worker is unavailable, sending message without previews");
+                Some(msg.data)
+            }
+        }
+    }
+
+    /// Registers that the worker has fetched the previews of a message to the peer.
+    pub fn finished(&mut self, remote_id: NodeId) {
+        if let Some(pending) = self.pending.get_mut(&remote_id) {
+            *pending = pending.saturating_sub(1);
+            if *pending == 0 {
+                self.pending.remove(&remote_id);
+            }
+        }
+    }
+}
+
+fn work(
+    receiver: mpsc::Receiver<AddressedMsg<String>>,
+    rpc_endpoint: ServiceAddr,
+    chat_endpoint: ServiceAddr,
+) {
+    let mut client = None;
+    for AddressedMsg { remote_id, data } in receiver {
+        let previews = previews(&data, Instant::now() + PREVIEW_DEADLINE);
+        let chat = PreviewedChat {
+            text: data,
+            previews,
+        };
+        loop {
+            if client.is_none() {
+                match storm_rpc::Client::with(
+                    rpc_endpoint.clone(),
+                    chat_endpoint.clone(),
+                    s!("chatd"),
+                ) {
+                    Ok(c) => client = Some(c),
+                    Err(err) => {
+                        warn!("Unable to connect to RPC interface: {}", err);
+                        thread::sleep(RPC_RECONNECT_DELAY);
+                        continue;
+                    }
+                }
+            }
+            match client.as_mut().map(|client| client.chat_previewed(remote_id, chat.clone())) {
+                Some(Err(err)) => {
+                    warn!("Unable to pass previewed chat message to the daemon: {}", err);
+                    client = None;
+                    thread::sleep(RPC_RECONNECT_DELAY);
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+fn links(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace().filter(|word| word.starts_with("http://")).take(MAX_PREVIEWS)
+}
+
+/// Fetches OpenGraph metadata of the pages linked from the message text, until the deadline.
+///
+/// Only plain HTTP links are fetched, since the node has no TLS client; links which can't be
+/// fetched in time or have no title are left without a preview.
+pub fn previews(text: &str, deadline: Instant) -> Vec<LinkPreview> {
+    links(text)
+        .filter_map(|url| match fetch(url, deadline) {
+            Ok(page) => parse(url, &page),
+            Err(err) => {
+                debug!("Unable to fetch link preview for {}: {}", url, err);
+                None
+            }
+        })
+        .collect()
+}
+
+fn fetch(url: &str, deadline: Instant) -> Result<String, io::Error> {
+    let timeout = || {
+        deadline
+            .checked_duration_since(Instant::now())
+            .filter(|left| !left.is_zero())
+            .map(|left| left.min(FETCH_TIMEOUT))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::TimedOut))
+    };
+    let rest = url.trim_start_matches("http://");
+    let (host, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') { host.to_owned() } else { format!("{}:80", host) };
+    let addr = addr.to_socket_addrs()?.next().ok_or(io::ErrorKind::NotFound)?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout()?)?;
+    stream.set_write_timeout(Some(timeout()?))?;
+    write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: text/html\r\n\r\n", path, host)?;
+    let mut page = vec![];
+    let mut buf = [0u8; 4096];
+    // Each read is limited by the deadline, so a slow page can't hold the worker past it
+    while (page.len() as u64) < MAX_PAGE_SIZE {
+        stream.set_read_timeout(Some(timeout()?))?;
+        match stream.read(&mut buf)? {
+            0 => break,
+            len => page.extend_from_slice(&buf[..len]),
+        }
+    }
+    page.truncate(MAX_PAGE_SIZE as usize);
+    Ok(String::from_utf8_lossy(&page).to_string())
+}
+
+fn parse(url: &str, page: &str) -> Option<LinkPreview> {
+    let mut title = None;
+    let mut description = None;
+    let mut image = None;
+    for tag in page.split("<meta").skip(1) {
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+        let content = match attr(tag, "content") {
+            Some(content) => content,
+            None => continue,
+        };
+        match attr(tag, "property").or_else(|| attr(tag, "name")).as_deref() {
+            Some("og:title") => title = Some(content),
+            Some("og:description") => description = Some(content),
+            Some("description") if description.is_none() => description = Some(content),
+            Some("og:image") => image = Some(content),
+            _ => {}
+        }
+    }
+    let title = title.or_else(|| {
+        let start = page.find("<title>")? + "<title>".len();
+        let end = start + page[start..].find("</title>")?;
+        Some(page[start..end].trim().to_owned())
+    })?;
+    Some(LinkPreview {
+        url: url.to_owned(),
+        title,
+        description,
+        image,
+    })
+}
+
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=", name))? + name.len() + 1;
+    let quote = tag[start..].chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &tag[start + 1..];
+    Some(value[..value.find(quote)?].to_owned())
+}
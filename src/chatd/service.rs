@@ -11,15 +11,25 @@
 use std::thread;
 use std::time::Duration;
 
+use internet2::addr::NodeId;
 use internet2::ZmqSocketType;
 use microservices::error::BootstrapError;
 use microservices::esb::{self, ClientId, EndpointList, Error};
 use microservices::node::TryService;
-use storm::Mesg;
+use storm::{Mesg, MesgId};
 use storm_ext::ExtMsg;
-use storm_rpc::{AddressedMsg, RpcMsg, ServiceId};
+use storm_rpc::{
+    AddressedMsg, BusFeatures, Dictionary, KeyChange, LinkPreview, NotifyPref, Page, Paged,
+    RadioMsg, RpcMsg, ServiceId, TopicId,
+};
 
-use crate::bus::{BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+#[cfg(feature = "link-preview")]
+use super::preview;
+use super::{
+    body, safety_number, Blocklist, ChatKeys, ChatMeta, Contacts, DisappearPolicies, Drafts,
+    Envelope, History, KeyAgreement, NotifyPrefs,
+};
+use crate::bus::{terminate, BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
 use crate::{Config, DaemonError, LaunchError};
 
 pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
@@ -68,6 +78,19 @@ pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
 pub struct Runtime {
     #[allow(dead_code)]
     pub(super) store: store_rpc::Client,
+    pub(super) notify: NotifyPrefs,
+    pub(super) drafts: Drafts,
+    pub(super) history: History,
+    pub(super) disappear: DisappearPolicies,
+    pub(super) contacts: Contacts,
+    pub(super) blocklist: Blocklist,
+    pub(super) keys: ChatKeys,
+    /// Features negotiated with the node
+    pub(super) bus_features: BusFeatures,
+    /// Compression dictionary of the chat app sent by the node
+    pub(super) dictionary: Option<Dictionary>,
+    #[cfg(feature = "link-preview")]
+    pub(super) previewer: preview::Previewer,
 }
 
 impl Runtime {
@@ -75,14 +98,49 @@ impl Runtime {
         debug!("Connecting to store service at {}", config.store_endpoint);
 
         let store = store_rpc::Client::with(&config.store_endpoint).map_err(LaunchError::from)?;
+        let notify = NotifyPrefs::load(&config.data_dir).map_err(LaunchError::from)?;
+        let drafts = Drafts::load(&config.data_dir).map_err(LaunchError::from)?;
+        let history = History::load(&config.data_dir).map_err(LaunchError::from)?;
+        let disappear = DisappearPolicies::load(&config.data_dir).map_err(LaunchError::from)?;
+        let contacts = Contacts::load(&config.data_dir).map_err(LaunchError::from)?;
+        let blocklist = Blocklist::load(&config.data_dir).map_err(LaunchError::from)?;
+        let keys = ChatKeys::load(&config.data_dir).map_err(LaunchError::from)?;
+        #[cfg(feature = "link-preview")]
+        let previewer =
+            preview::Previewer::start(config.rpc_endpoint.clone(), config.chat_endpoint.clone());
 
         info!("Chat runtime started successfully");
 
-        Ok(Self { store })
+        Ok(Self {
+            store,
+            notify,
+            drafts,
+            history,
+            disappear,
+            contacts,
+            blocklist,
+            keys,
+            bus_features: none!(),
+            dictionary: None,
+            #[cfg(feature = "link-preview")]
+            previewer,
+        })
     }
 }
 
-impl Responder for Runtime {}
+impl Responder for Runtime {
+    fn bus_features(&self, service_id: &ServiceId) -> BusFeatures {
+        if *service_id == ServiceId::stormd() {
+            self.bus_features
+        } else {
+            none!()
+        }
+    }
+
+    fn dictionary(&self, service_id: &ServiceId) -> Option<&Dictionary> {
+        self.dictionary.as_ref().filter(|_| *service_id == ServiceId::stormd())
+    }
+}
 
 impl esb::Handler<ServiceBus> for Runtime {
     type Request = BusMsg;
@@ -93,6 +151,7 @@ impl esb::Handler<ServiceBus> for Runtime {
     fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
         thread::sleep(Duration::from_millis(100));
         self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        self.send_ext(endpoints, None, ExtMsg::Negotiate(BusFeatures::supported()))?;
         Ok(())
     }
 
@@ -103,7 +162,28 @@ impl esb::Handler<ServiceBus> for Runtime {
         source: ServiceId,
         request: Self::Request,
     ) -> Result<(), Self::Error> {
+        // The controller has no timers, so expired messages are deleted when other messages are
+        // handled
+        if let Err(err) = self.history.expire() {
+            warn!("Unable to delete expired chat messages: {}", err);
+        }
         match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Compressed(compressed), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                let request = BusMsg::decompress(&compressed)?;
+                self.handle(endpoints, bus_id, service_id, request)
+            }
+            (ServiceBus::Storm, BusMsg::DictCompressed(compressed), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                let dict = self
+                    .dictionary
+                    .as_ref()
+                    .ok_or(DaemonError::UnknownDictionary(compressed.dict_id))?;
+                let request = BusMsg::decompress_with(&compressed, dict)?;
+                self.handle(endpoints, bus_id, service_id, request)
+            }
             (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
                 if service_id == ServiceId::stormd() =>
             {
@@ -136,10 +216,80 @@ impl Runtime {
         message: ExtMsg,
     ) -> Result<(), DaemonError> {
         match message {
+            ExtMsg::Negotiate(features) => {
+                debug!("Negotiated bus features: {}", features);
+                self.bus_features = features;
+            }
+            ExtMsg::Dictionary(dict) => {
+                debug!("Using {} for the messages to the node", dict);
+                self.dictionary = Some(dict);
+            }
+            ExtMsg::Post(AddressedMsg { remote_id, .. })
+                if self.blocklist.is_blocked(remote_id) =>
+            {
+                trace!("Dropping chat message from blocked peer {}", remote_id);
+            }
             ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let plaintext = match Envelope::parse(&data.body) {
+                    Envelope::KeyAgreement(agreement) => {
+                        return self.key_agreed(endpoints, remote_id, agreement);
+                    }
+                    Envelope::Ciphertext(ciphertext) => {
+                        match self.keys.decrypt(remote_id, ciphertext) {
+                            Some(plaintext) => plaintext,
+                            None => {
+                                // The peer may use our key while we have lost its one, so we
+                                // announce the key again to have the peer key in reply
+                                warn!("Unable to decrypt chat message from {}", remote_id);
+                                let body = self.keys.agreement(false).to_body();
+                                return self.send_post(endpoints, remote_id, body);
+                            }
+                        }
+                    }
+                    Envelope::Plaintext(_) if self.keys.is_agreed(remote_id) => {
+                        warn!(
+                            "Dropping unencrypted chat message from {} which has agreed the chat \
+                             key",
+                            remote_id
+                        );
+                        return Ok(());
+                    }
+                    Envelope::Plaintext(plaintext) => {
+                        warn!("Chat message from {} is not encrypted", remote_id);
+                        plaintext.to_vec()
+                    }
+                };
+                let (text, meta) = body::decode_body(&plaintext);
+                let meta = meta.unwrap_or_default();
+                if self.disappear.set(remote_id, meta.disappear_after)? {
+                    info!(
+                        "Peer {} set disappearing messages after {} s",
+                        remote_id, meta.disappear_after
+                    );
+                }
+                if text.is_empty() {
+                    // Policy update without a message
+                    return Ok(());
+                }
+                let disappear_after = self.disappear.get(remote_id);
+                // Chat messages replying to a topic belong to the topic conversation
+                let topic = Some(TopicId::from_wire(data.parent_id))
+                    .filter(|_| data.parent_id != MesgId::default());
+                let entry = self.history.record(
+                    remote_id,
+                    topic,
+                    true,
+                    text,
+                    meta.previews,
+                    disappear_after,
+                )?;
+                if self.notify.get(remote_id) == NotifyPref::Mute {
+                    debug!("Conversation with {} is muted, not publishing the message", remote_id);
+                    return Ok(());
+                }
                 let chat_msg = AddressedMsg {
                     remote_id,
-                    data: String::from_utf8_lossy(&data.body).to_string(),
+                    data: entry,
                 };
                 self.send_radio(endpoints, chat_msg)?;
             }
@@ -155,20 +305,134 @@ impl Runtime {
     fn handle_rpc(
         &mut self,
         endpoints: &mut Endpoints,
-        _client_id: ClientId,
+        client_id: ClientId,
         message: RpcMsg,
     ) -> Result<(), DaemonError> {
         match message {
             RpcMsg::SendChat(AddressedMsg { remote_id, data }) => {
-                let addressed_msg = AddressedMsg {
-                    remote_id,
-                    data: Mesg {
-                        parent_id: none!(),
-                        body: data.into_bytes(),
-                        container_ids: empty!(),
-                    },
+                // Messages with links are sent once the preview worker has fetched the previews
+                #[cfg(feature = "link-preview")]
+                let data = match self.previewer.submit(remote_id, data) {
+                    Some(data) => data,
+                    None => return Ok(()),
                 };
-                self.send_ext(endpoints, None, ExtMsg::Post(addressed_msg))?;
+                self.send_chat(endpoints, remote_id, data, vec![])?;
+            }
+
+            #[cfg(feature = "link-preview")]
+            RpcMsg::ChatPreviewed(AddressedMsg { remote_id, data }) => {
+                self.previewer.finished(remote_id);
+                self.send_chat(endpoints, remote_id, data.text, data.previews)?;
+            }
+
+            RpcMsg::SetChatDisappear(AddressedMsg { remote_id, data }) => {
+                info!("Setting disappearing messages for {} after {} s", remote_id, data);
+                let reply = match self.disappear.set(remote_id, data) {
+                    Ok(_) => RpcMsg::Success(None.into()),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                let meta = ChatMeta {
+                    previews: vec![],
+                    disappear_after: data,
+                };
+                self.post(endpoints, remote_id, body::encode_body(s!(""), Some(&meta)))?;
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::SetChatContact(AddressedMsg { remote_id, data }) => {
+                let reply = match self.contacts.set(data, remote_id) {
+                    Ok(Some(change)) => {
+                        warn!("Key of chat contact {} has changed", change);
+                        self.send_radio(endpoints, RadioMsg::KeyChanged(change))?;
+                        RpcMsg::Success(None.into())
+                    }
+                    Ok(None) => RpcMsg::Success(None.into()),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::VerifyChatContact(name) => {
+                let reply = match self.contacts.verify(&name) {
+                    Ok(true) => RpcMsg::Success(None.into()),
+                    Ok(false) => DaemonError::UnknownContact(name).into(),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ListChatContacts => {
+                let contacts = self.contacts.list().cloned().collect();
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatContacts(contacts))?;
+            }
+
+            RpcMsg::GetChatSafety(AddressedMsg { remote_id, data }) => {
+                let code = safety_number(data, remote_id);
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatSafety(code))?;
+            }
+
+            RpcMsg::ChatBlock(remote_id) | RpcMsg::ChatUnblock(remote_id) => {
+                let blocked = matches!(message, RpcMsg::ChatBlock(_));
+                let reply = match self.blocklist.block(remote_id, blocked) {
+                    Ok(changed) => {
+                        if changed {
+                            info!(
+                                "Chat peer {} is {}",
+                                remote_id,
+                                if blocked { "blocked" } else { "unblocked" }
+                            );
+                        }
+                        RpcMsg::Success(None.into())
+                    }
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ListChatBlocked => {
+                let blocked = self.blocklist.list().clone();
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatBlocked(blocked))?;
+            }
+
+            RpcMsg::SetChatNotify(AddressedMsg { remote_id, data }) => {
+                info!("Setting notification preference for {} to {}", remote_id, data);
+                let reply = match self.notify.set(remote_id, data) {
+                    Ok(()) => RpcMsg::Success(None.into()),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ListChatNotify => {
+                let prefs = self.notify.list().clone();
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatNotify(prefs))?;
+            }
+
+            RpcMsg::ChatSaveDraft(AddressedMsg { remote_id, data }) => {
+                debug!("Saving draft for {}", remote_id);
+                let reply = match self.drafts.save(remote_id, data) {
+                    Ok(()) => RpcMsg::Success(None.into()),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ChatSearch(Paged {
+                query: AddressedMsg { remote_id, data },
+                page,
+            }) => {
+                let matches = Page::by_offset(self.history.search(remote_id, &data), &page);
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatMatches(matches))?;
+            }
+
+            RpcMsg::ChatHistory(req) => {
+                let entries = self.history.page(&req);
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatEntries(entries))?;
+            }
+
+            RpcMsg::ChatGetDraft(remote_id) => {
+                let draft = self.drafts.get(remote_id).cloned();
+                self.send_rpc(endpoints, client_id, RpcMsg::ChatDraft(draft))?;
             }
 
             wrong_msg => {
@@ -180,17 +444,122 @@ impl Runtime {
         Ok(())
     }
 
+    fn send_chat(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        text: String,
+        previews: Vec<LinkPreview>,
+    ) -> Result<(), DaemonError> {
+        let meta = ChatMeta {
+            previews,
+            disappear_after: self.disappear.get(remote_id),
+        };
+        let body = body::encode_body(
+            text.clone(),
+            Some(&meta).filter(|meta| !meta.previews.is_empty() || meta.disappear_after > 0),
+        );
+        self.history.record(remote_id, None, false, text, meta.previews, meta.disappear_after)?;
+        self.post(endpoints, remote_id, body)?;
+        if self.drafts.get(remote_id).is_some() {
+            self.drafts.save(remote_id, s!(""))?;
+        }
+        Ok(())
+    }
+
+    fn key_agreed(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        agreement: KeyAgreement,
+    ) -> Result<(), DaemonError> {
+        if self.keys.accept(remote_id, agreement.chat_key)? {
+            warn!("Chat encryption key of {} has changed", remote_id);
+            let mut changes = self.contacts.chat_key_changed(remote_id)?;
+            if changes.is_empty() {
+                changes.push(KeyChange {
+                    name: remote_id.to_string(),
+                    old: remote_id,
+                    new: remote_id,
+                });
+            }
+            for change in changes {
+                self.send_radio(endpoints, RadioMsg::KeyChanged(change))?;
+            }
+        } else {
+            debug!("Agreed chat encryption key with {}", remote_id);
+        }
+        if !agreement.reply {
+            let body = self.keys.agreement(true).to_body();
+            self.send_post(endpoints, remote_id, body)?;
+        }
+        for body in self.keys.release(remote_id) {
+            self.post(endpoints, remote_id, body)?;
+        }
+        Ok(())
+    }
+
+    /// Sends chat message body encrypted for the peer; if the peer key is not known yet, holds
+    /// the message and starts the key agreement.
+    fn post(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        body: Vec<u8>,
+    ) -> Result<(), DaemonError> {
+        match self.keys.encrypt(remote_id, &body) {
+            Some(ciphertext) => self.send_post(endpoints, remote_id, ciphertext),
+            None => {
+                if self.keys.hold(remote_id, body) {
+                    debug!("Holding chat message to {} until the key agreement", remote_id);
+                } else {
+                    warn!("Dropping chat message to {} awaiting the key agreement", remote_id);
+                }
+                let body = self.keys.agreement(false).to_body();
+                self.send_post(endpoints, remote_id, body)
+            }
+        }
+    }
+
+    fn send_post(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        body: Vec<u8>,
+    ) -> Result<(), DaemonError> {
+        let addressed_msg = AddressedMsg {
+            remote_id,
+            data: Mesg {
+                parent_id: none!(),
+                body,
+                container_ids: empty!(),
+            },
+        };
+        self.send_ext(endpoints, None, ExtMsg::Post(addressed_msg))?;
+        Ok(())
+    }
+
     fn handle_ctl(
         &mut self,
-        _endpoints: &mut Endpoints,
+        endpoints: &mut Endpoints,
         _source: ServiceId,
         message: CtlMsg,
     ) -> Result<(), DaemonError> {
         match message {
+            CtlMsg::Ping(nonce) => {
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Pong(nonce))?;
+            }
+            CtlMsg::Shutdown => {
+                // All the daemon state is saved as soon as it changes
+                info!("Shutting down");
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ShutdownComplete)?;
+                terminate();
+            }
             wrong_msg => {
                 error!("Request is not supported by the CTL interface");
                 return Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &wrong_msg));
             }
         }
+        Ok(())
     }
 }
@@ -30,6 +30,9 @@ where Ext: Clone + Eq + Debug
     /// ZMQ socket for lightning peer network message bus
     pub msg_endpoint: ServiceAddr,
 
+    /// ZMQ socket for the dedicated peer message bus carrying chunk payloads
+    pub bulk_endpoint: Option<ServiceAddr>,
+
     /// ZMQ socket for internal service control bus
     pub ctl_endpoint: ServiceAddr,
 
@@ -58,6 +61,7 @@ where Ext: Clone + Eq + Debug
             data_dir: orig.data_dir,
             rpc_endpoint: orig.rpc_endpoint,
             msg_endpoint: orig.msg_endpoint,
+            bulk_endpoint: orig.bulk_endpoint,
             ext_endpoint: orig.ext_endpoint,
             ctl_endpoint: orig.ctl_endpoint,
             store_endpoint: orig.store_endpoint,
@@ -80,6 +84,7 @@ where
             data_dir: opts.data_dir.clone(),
             rpc_endpoint: opts.rpc_endpoint.clone(),
             msg_endpoint: opts.msg_endpoint.clone(),
+            bulk_endpoint: opts.bulk_endpoint.clone(),
             ext_endpoint: opts.ext_endpoint.clone(),
             store_endpoint: opts.store_endpoint.clone(),
             chat_endpoint: opts.chat_endpoint.clone(),
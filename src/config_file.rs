@@ -0,0 +1,159 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use settings::{FileFormat, Source, Value};
+
+use crate::error::ConfigFileError;
+use crate::opts::{Opts, STORM_NODE_CONFIG};
+
+/// Keys of the configuration file applying to all the daemons.
+const SHARED_KEYS: &[&str] = &[
+    "data_dir",
+    "log_level",
+    "msg_endpoint",
+    "bulk_endpoint",
+    "ctl_endpoint",
+    "rpc_endpoint",
+    "ext_endpoint",
+    "store_endpoint",
+    "chat_endpoint",
+];
+
+/// Keys of the configuration file applying only to stormd.
+const STORMD_KEYS: &[&str] =
+    &["run_chat", "run_mail", "run_board", "run_downpour", "quota", "peer_quota", "eviction"];
+
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+
+/// Values of the TOML configuration file, layered under the command-line arguments and the
+/// environment variables.
+///
+/// All the daemons read the same file, since stormd passes `--config` and `--data-dir` to the
+/// daemons it launches; the daemons apply the shared keys and ignore the ones of stormd.
+#[derive(Clone, Debug)]
+pub struct ConfigFile {
+    values: BTreeMap<String, Value>,
+}
+
+impl ConfigFile {
+    /// Reads the file given with `--config`, or `stormd.toml` inside the data directory if it
+    /// exists.
+    pub fn load(opts: &Opts) -> Result<Option<Self>, ConfigFileError> {
+        let path = match &opts.config {
+            Some(path) => path.clone(),
+            None => {
+                let data_dir = opts.data_dir.to_string_lossy();
+                let path = STORM_NODE_CONFIG.replace("{data_dir}", &data_dir);
+                let path = PathBuf::from(shellexpand::tilde(&path).to_string());
+                if !path.exists() {
+                    return Ok(None);
+                }
+                path
+            }
+        };
+        let values = settings::File::from(path.as_path())
+            .format(FileFormat::Toml)
+            .collect()
+            .map_err(|err| ConfigFileError::Read(path.display().to_string(), err.to_string()))?;
+        if let Some(key) = values.keys().find(|key| {
+            !SHARED_KEYS.contains(&key.as_str()) && !STORMD_KEYS.contains(&key.as_str())
+        }) {
+            return Err(ConfigFileError::UnknownKey(key.clone()));
+        }
+        Ok(Some(ConfigFile {
+            values: values.into_iter().collect(),
+        }))
+    }
+
+    /// Takes value of the key, unless the argument `arg` is given on the command line or with
+    /// an environment variable. The value is validated in both cases.
+    pub fn take<T>(
+        &mut self,
+        key: &str,
+        arg: &str,
+        matches: &ArgMatches,
+    ) -> Result<Option<T>, ConfigFileError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = match self.values.remove(key) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let invalid = |details: String| ConfigFileError::InvalidValue(key.to_owned(), details);
+        let value = value.into_str().map_err(|err| invalid(err.to_string()))?;
+        let value = value.parse().map_err(|err: T::Err| invalid(err.to_string()))?;
+        match matches.value_source(arg) {
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable) => Ok(None),
+            _ => Ok(Some(value)),
+        }
+    }
+}
+
+impl Opts {
+    /// Applies the shared keys of the configuration file, returning the file with the values
+    /// left for the daemon-specific options.
+    ///
+    /// Must be called before [`Opts::process`], which expands the paths.
+    pub fn apply_config_file(
+        &mut self,
+        matches: &ArgMatches,
+    ) -> Result<Option<ConfigFile>, ConfigFileError> {
+        let mut file = match ConfigFile::load(self)? {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        if let Some(data_dir) = file.take("data_dir", "data_dir", matches)? {
+            self.data_dir = data_dir;
+        }
+        if let Some(level) = file.take::<String>("log_level", "verbose", matches)? {
+            self.verbose = LOG_LEVELS.iter().position(|known| *known == level).ok_or_else(|| {
+                ConfigFileError::InvalidValue(
+                    s!("log_level"),
+                    format!(
+                        "unknown log level `{}`; allowed values are {}",
+                        level,
+                        LOG_LEVELS.join(", ")
+                    ),
+                )
+            })? as u8;
+        }
+        if let Some(endpoint) = file.take("msg_endpoint", "msg_endpoint", matches)? {
+            self.msg_endpoint = endpoint;
+        }
+        if let Some(endpoint) = file.take("bulk_endpoint", "bulk_endpoint", matches)? {
+            self.bulk_endpoint = Some(endpoint);
+        }
+        if let Some(endpoint) = file.take("ctl_endpoint", "ctl_endpoint", matches)? {
+            self.ctl_endpoint = endpoint;
+        }
+        if let Some(endpoint) = file.take("rpc_endpoint", "rpc_endpoint", matches)? {
+            self.rpc_endpoint = endpoint;
+        }
+        if let Some(endpoint) = file.take("ext_endpoint", "ext_endpoint", matches)? {
+            self.ext_endpoint = endpoint;
+        }
+        if let Some(endpoint) = file.take("store_endpoint", "store_endpoint", matches)? {
+            self.store_endpoint = endpoint;
+        }
+        if let Some(endpoint) = file.take("chat_endpoint", "chat_endpoint", matches)? {
+            self.chat_endpoint = endpoint;
+        }
+        Ok(Some(file))
+    }
+}
@@ -0,0 +1,214 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+
+use amplify::num::u24;
+use microservices::rpc::ServerError;
+use storm::{Chunk, ChunkId, Container, ContainerHeader, ContainerId};
+use storm_rpc::{
+    InlineChunks, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS, DB_TABLE_CONTAINER_HEADERS,
+    DB_TABLE_INLINE_CHUNKS,
+};
+use strict_encoding::{MediumVec, StrictDecode, StrictEncode};
+
+use crate::DaemonError;
+
+/// Splits the data into chunks and saves them into the store as a container with the given
+/// header. Size of the data must match the size in the header.
+pub(crate) fn containerize(
+    store: &mut store_rpc::Client,
+    header: ContainerHeader,
+    data: &[u8],
+) -> Result<ContainerId, DaemonError> {
+    let container = manifest(header, data)?;
+    store_container(store, &container, data)?;
+    Ok(container.container_id())
+}
+
+/// Computes manifest of the container with the given header and data, without storing them.
+pub(crate) fn manifest(header: ContainerHeader, data: &[u8]) -> Result<Container, DaemonError> {
+    debug_assert_eq!(header.size, data.len() as u64);
+    let mut chunk_ids = MediumVec::new();
+    for piece in data.chunks(u24::MAX.into_usize()) {
+        chunk_ids.push(Chunk::try_from(piece)?.chunk_id())?;
+    }
+    Ok(Container {
+        header,
+        chunks: chunk_ids,
+    })
+}
+
+/// Saves the container with its data into the store.
+pub(crate) fn store_container(
+    store: &mut store_rpc::Client,
+    container: &Container,
+    data: &[u8],
+) -> Result<(), DaemonError> {
+    let id = container.container_id();
+    for piece in data.chunks(u24::MAX.into_usize()) {
+        store_chunk(store, id, &Chunk::try_from(piece)?)?;
+    }
+    let header_chunk = Chunk::try_from(container.header.strict_serialize()?)?;
+    let container_chunk = Chunk::try_from(container.strict_serialize()?)?;
+    store.store(DB_TABLE_CONTAINER_HEADERS, id, &header_chunk)?;
+    store.store(DB_TABLE_CONTAINERS, id, &container_chunk)?;
+    Ok(())
+}
+
+/// Reads manifest of the container from the store. Containers evicted from the store are reported
+/// as absent.
+pub(crate) fn retrieve_manifest(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<Option<Container>, DaemonError> {
+    match store.retrieve_chunk(DB_TABLE_CONTAINERS, container_id)? {
+        Some(chunk) if is_evicted(&chunk) => Ok(None),
+        Some(chunk) => Ok(Some(Container::strict_deserialize(chunk)?)),
+        None => Ok(None),
+    }
+}
+
+/// Evicts the container from the store. Since the store does not support removal of the records,
+/// the manifest and the inline chunks of the container are replaced with empty records, leaving
+/// the chunks of the container orphaned for the garbage collector. The header is kept, so the
+/// container metadata remain known.
+pub(crate) fn evict(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<(), DaemonError> {
+    if retrieve_manifest(store, container_id)?.is_none() {
+        return Ok(());
+    }
+    store.store(DB_TABLE_INLINE_CHUNKS, container_id, &InlineChunks::default().to_record()?)?;
+    store.store(DB_TABLE_CONTAINERS, container_id, &Chunk::try_from(vec![])?)?;
+    Ok(())
+}
+
+/// Detects the empty record left in the store in place of the manifest of the evicted container.
+pub(crate) fn is_evicted(manifest: &Chunk) -> bool { manifest.is_empty() }
+
+/// Reads the small chunks kept inline with the container.
+fn inline_chunks(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<InlineChunks, ServerError<store_rpc::FailureCode>> {
+    Ok(store
+        .retrieve_chunk(DB_TABLE_INLINE_CHUNKS, container_id)?
+        .map(InlineChunks::from_record)
+        .unwrap_or_default())
+}
+
+/// Saves the chunk of the container into the store.
+///
+/// Small chunks are kept inline with the container, sparing the chunk table a record for each
+/// of them. Once the inline chunks of the container outgrow [`storm_rpc::INLINE_RECORD_LIMIT`],
+/// they are promoted to the chunk table together with the new one.
+pub(crate) fn store_chunk(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+    chunk: &Chunk,
+) -> Result<(), DaemonError> {
+    let chunk_id = chunk.chunk_id();
+    if InlineChunks::is_inline(chunk) {
+        let mut inline = inline_chunks(store, container_id)?;
+        if inline.contains(chunk_id) {
+            return Ok(());
+        }
+        if inline.insert(chunk_id, chunk.clone()) {
+            store.store(DB_TABLE_INLINE_CHUNKS, container_id, &inline.to_record()?)?;
+            return Ok(());
+        }
+        if !inline.is_empty() {
+            debug!("Promoting inline chunks of {} to the chunk table", container_id);
+            // Chunks are saved to the chunk table before the record is emptied, so they remain
+            // readable if the promotion is interrupted
+            for (chunk_id, chunk) in inline.promote() {
+                store.store(DB_TABLE_CHUNKS, chunk_id, &chunk)?;
+            }
+            store.store(DB_TABLE_INLINE_CHUNKS, container_id, &inline.to_record()?)?;
+        }
+    }
+    store.store(DB_TABLE_CHUNKS, chunk_id, chunk)?;
+    Ok(())
+}
+
+/// Reads the chunk of the container from the store, whether it is kept in the chunk table or
+/// inline with the container. Chunks collected by the garbage collector are reported as absent.
+pub(crate) fn retrieve_chunk(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+    chunk_id: ChunkId,
+) -> Result<Option<Chunk>, ServerError<store_rpc::FailureCode>> {
+    if let Some(chunk) = store.retrieve_chunk(DB_TABLE_CHUNKS, chunk_id)? {
+        if !is_collected(&chunk, chunk_id) {
+            return Ok(Some(chunk));
+        }
+    }
+    Ok(inline_chunks(store, container_id)?.get(chunk_id).cloned())
+}
+
+/// Detects the empty record left in the store in place of the chunk collected by the garbage
+/// collector.
+pub(crate) fn is_collected(chunk: &Chunk, chunk_id: ChunkId) -> bool {
+    chunk.is_empty() && chunk.chunk_id() != chunk_id
+}
+
+/// Returns the chunks of the container which are not present in the store, including the
+/// collected ones.
+pub(crate) fn missing_chunks(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+    chunk_ids: BTreeSet<ChunkId>,
+) -> Result<BTreeSet<ChunkId>, ServerError<store_rpc::FailureCode>> {
+    let mut missing = store.filter_unknown(DB_TABLE_CHUNKS, chunk_ids.clone())?;
+    for chunk_id in chunk_ids {
+        if missing.contains(&chunk_id) {
+            continue;
+        }
+        if let Some(chunk) = store.retrieve_chunk(DB_TABLE_CHUNKS, chunk_id)? {
+            if is_collected(&chunk, chunk_id) {
+                missing.insert(chunk_id);
+            }
+        }
+    }
+    if !missing.is_empty() {
+        let inline = inline_chunks(store, container_id)?;
+        missing.retain(|chunk_id| !inline.contains(*chunk_id));
+    }
+    Ok(missing)
+}
+
+/// Reads header of the container from the store.
+pub(crate) fn header(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<ContainerHeader, DaemonError> {
+    let header_chunk = store
+        .retrieve_chunk(DB_TABLE_CONTAINER_HEADERS, container_id)?
+        .ok_or(DaemonError::UnknownContainer(container_id))?;
+    Ok(ContainerHeader::strict_deserialize(header_chunk)?)
+}
+
+/// Reads data of the container from the store.
+pub(crate) fn assemble(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<Vec<u8>, DaemonError> {
+    let container = retrieve_manifest(store, container_id)?
+        .ok_or(DaemonError::UnknownContainer(container_id))?;
+    let mut data = Vec::with_capacity(container.header.size as usize);
+    for chunk_id in container.chunks {
+        let chunk = retrieve_chunk(store, container_id, chunk_id)?
+            .ok_or(DaemonError::UnknownContainer(container_id))?;
+        data.extend_from_slice(chunk.as_slice());
+    }
+    Ok(data)
+}
@@ -18,7 +18,7 @@ use microservices::node::TryService;
 use storm_ext::ExtMsg;
 use storm_rpc::{AddressedMsg, RpcMsg, ServiceId};
 
-use crate::bus::{BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::bus::{terminate, BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
 use crate::{Config, DaemonError, LaunchError};
 
 pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
@@ -164,25 +164,31 @@ impl Runtime {
         _client_id: ClientId,
         message: RpcMsg,
     ) -> Result<(), DaemonError> {
-        match message {
-            wrong_msg => {
-                error!("Request is not supported by the RPC interface");
-                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
-            }
-        }
+        error!("Request is not supported by the RPC interface");
+        Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &message))
     }
 
     fn handle_ctl(
         &mut self,
-        _endpoints: &mut Endpoints,
+        endpoints: &mut Endpoints,
         _source: ServiceId,
         message: CtlMsg,
     ) -> Result<(), DaemonError> {
         match message {
+            CtlMsg::Ping(nonce) => {
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Pong(nonce))?;
+            }
+            CtlMsg::Shutdown => {
+                // All the daemon state is saved as soon as it changes
+                info!("Shutting down");
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ShutdownComplete)?;
+                terminate();
+            }
             wrong_msg => {
                 error!("Request is not supported by the CTL interface");
                 return Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &wrong_msg));
             }
         }
+        Ok(())
     }
 }
@@ -8,14 +8,22 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use std::io;
+use std::net::SocketAddr;
+
+use amplify::IoError;
+use internet2::addr::NodeId;
 use internet2::presentation;
 use microservices::rpc::ServerError;
 use microservices::{esb, rpc, LauncherError};
 use storm::ContainerId;
-use storm_rpc::{FailureCode, RpcMsg, ServiceId};
+use storm_rpc::{
+    FailureCode, JobId, MailFolder, MailId, ModerationError, PatchError, PostId, RpcMsg, ServiceId,
+    SessionToken, TopicId,
+};
 
 use crate::bus::ServiceBus;
-use crate::stormd::Daemon;
+use crate::stormd::{ApprovalRejection, Daemon, MigrationError, ShareError};
 use crate::transferd;
 
 #[derive(Clone, Debug, Display, Error, From)]
@@ -24,9 +32,53 @@ pub enum LaunchError {
     /// error setting up ESB controller; can't connect one of message buses
     BusSetupFailure,
 
+    /// too many peer connection services are specified ({0}); at most 255 are supported
+    TooManyPeerServices(usize),
+
     /// can't connect to store service. Details: {0}
     #[from]
     StoreConnection(ServerError<store_rpc::FailureCode>),
+
+    /// unable to load node state from the data directory. Details: {0}
+    #[from]
+    State(strict_encoding::Error),
+
+    /// can't connect to the bridged MQTT broker. Details: {0}
+    BrokerConnection(String),
+
+    /// Nostr secret key is not a valid secp256k1 secret key
+    InvalidNostrKey,
+
+    /// can't serve metrics on {0}. Details: {1}
+    MetricsEndpoint(SocketAddr, String),
+
+    /// can't install termination signal handlers. Details: {0}
+    SignalHandlers(nix::errno::Errno),
+
+    /// approval threshold {0} exceeds the number of the configured approvers ({1})
+    ApprovalThreshold(u8, usize),
+
+    /// unable to upgrade the data directory. Details: {0}
+    #[from]
+    Migration(MigrationError),
+
+    #[from]
+    #[display(inner)]
+    ConfigFile(ConfigFileError),
+}
+
+/// Errors in the TOML configuration file.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ConfigFileError {
+    /// can't read configuration file {0}. Details: {1}
+    Read(String, String),
+
+    /// unknown key `{0}` in the configuration file
+    UnknownKey(String),
+
+    /// invalid value of `{0}` in the configuration file. Details: {1}
+    InvalidValue(String, String),
 }
 
 impl microservices::error::Error for LaunchError {}
@@ -38,6 +90,11 @@ pub(crate) enum DaemonError {
     #[display(inner)]
     Encoding(strict_encoding::Error),
 
+    /// I/O error: {0}
+    #[from]
+    #[from(io::Error)]
+    Io(IoError),
+
     /// ESB error: {0}
     #[from]
     Esb(esb::Error<ServiceId>),
@@ -60,6 +117,9 @@ pub(crate) enum DaemonError {
     #[from]
     StormEncoding(presentation::Error),
 
+    /// message is compressed with an unknown dictionary {0:#010x}
+    UnknownDictionary(u32),
+
     /// request `{1}` is not supported on {0} message bus
     RequestNotSupported(ServiceBus, String),
 
@@ -68,6 +128,91 @@ pub(crate) enum DaemonError {
 
     /// container {0} is not known
     UnknownContainer(ContainerId),
+
+    /// job {0:#018x} is not known
+    UnknownJob(JobId),
+
+    /// topic {0} is not known
+    UnknownTopic(TopicId),
+
+    /// container {0} is not quarantined
+    NotQuarantined(ContainerId),
+
+    /// container {0} has no deferred chunks
+    NotDeferred(ContainerId),
+
+    /// container transfers are in progress
+    TransfersInProgress,
+
+    /// container {0} can't be a previous version of itself
+    SelfVersion(ContainerId),
+
+    #[from]
+    #[display(inner)]
+    Patch(PatchError),
+
+    /// patch reconstructs container {1} instead of {0}
+    PatchMismatch(ContainerId, ContainerId),
+
+    /// chat contact `{0}` is not known
+    UnknownContact(String),
+
+    /// mail {0} is not known
+    UnknownMail(MailId),
+
+    /// mail can't be moved to the {0} folder
+    WrongMailFolder(MailFolder),
+
+    /// board post {0} is not known
+    UnknownPost(PostId),
+
+    /// node {0} is not a moderator of the board
+    NotModerator(NodeId),
+
+    #[from]
+    #[display(inner)]
+    Moderation(ModerationError),
+
+    /// app-opaque message payload of {0} bytes exceeds the size limit
+    OpaqueOversized(usize),
+
+    /// node does not publish events; it must be run with the notification endpoint
+    NotificationsDisabled,
+
+    /// session {0:#018x} is not known
+    UnknownSession(SessionToken),
+
+    /// client session was closed by the node operator
+    SessionClosed,
+
+    /// client has reached the limit of {0} unfinished jobs
+    JobLimit(u16),
+
+    /// node {0} has not answered the admin command
+    AdminTimeout(NodeId),
+
+    #[from]
+    #[display(inner)]
+    Share(ShareError),
+
+    /// node is a read-only replica and refuses writes
+    ReadOnly,
+
+    /// action is not executed until approved by {1} operator keys; proposal {0:#018x} is created
+    ApprovalRequired(u64, u8),
+
+    #[from]
+    #[display(inner)]
+    Approval(ApprovalRejection),
+
+    /// container {0} is not replicated to the storage peers
+    UnknownReplication(ContainerId),
+
+    /// {0} replicas are required, while {1} storage peers are given
+    ReplicasUnreachable(u8, usize),
+
+    /// node is built without the chaos testing hooks
+    ChaosUnsupported,
 }
 
 impl microservices::error::Error for DaemonError {}
@@ -79,7 +224,10 @@ impl From<DaemonError> for esb::Error<ServiceId> {
 impl From<DaemonError> for RpcMsg {
     fn from(err: DaemonError) -> Self {
         let code = match err {
-            DaemonError::StormEncoding(_) | DaemonError::Encoding(_) => FailureCode::Encoding,
+            DaemonError::StormEncoding(_)
+            | DaemonError::Encoding(_)
+            | DaemonError::UnknownDictionary(_) => FailureCode::Encoding,
+            DaemonError::Io(_) => FailureCode::Unknown,
             DaemonError::Esb(_) => FailureCode::Esb,
             DaemonError::RequestNotSupported(_, _) | DaemonError::SourceNotSupported(_, _, _) => {
                 FailureCode::UnexpectedRequest
@@ -88,6 +236,34 @@ impl From<DaemonError> for RpcMsg {
             DaemonError::DaemonLaunch(_) => FailureCode::Launch,
             DaemonError::Store(_) => FailureCode::Store,
             DaemonError::UnknownContainer(_) => FailureCode::UnknownContainer,
+            DaemonError::UnknownJob(_) => FailureCode::UnknownJob,
+            DaemonError::NotQuarantined(_) => FailureCode::UnknownContainer,
+            DaemonError::NotDeferred(_) => FailureCode::UnknownContainer,
+            DaemonError::SelfVersion(_) => FailureCode::UnexpectedRequest,
+            DaemonError::TransfersInProgress => FailureCode::UnexpectedRequest,
+            DaemonError::Patch(_) | DaemonError::PatchMismatch(_, _) => FailureCode::Unknown,
+            DaemonError::UnknownTopic(_) => FailureCode::UnknownTopic,
+            DaemonError::UnknownContact(_) => FailureCode::UnknownContact,
+            DaemonError::UnknownMail(_) => FailureCode::UnknownMail,
+            DaemonError::WrongMailFolder(_) => FailureCode::UnexpectedRequest,
+            DaemonError::UnknownPost(_) => FailureCode::UnknownPost,
+            DaemonError::NotModerator(_) | DaemonError::Moderation(_) => {
+                FailureCode::UnexpectedRequest
+            }
+            DaemonError::OpaqueOversized(_) => FailureCode::UnexpectedRequest,
+            DaemonError::NotificationsDisabled => FailureCode::UnexpectedRequest,
+            DaemonError::UnknownSession(_) | DaemonError::SessionClosed => {
+                FailureCode::UnknownSession
+            }
+            DaemonError::JobLimit(_) => FailureCode::JobLimit,
+            DaemonError::AdminTimeout(_) => FailureCode::RemoteTimeout,
+            DaemonError::Share(_) => FailureCode::ShareToken,
+            DaemonError::ReadOnly => FailureCode::ReadOnly,
+            DaemonError::ApprovalRequired(_, _) => FailureCode::ApprovalRequired,
+            DaemonError::Approval(_) => FailureCode::Approval,
+            DaemonError::UnknownReplication(_) => FailureCode::UnknownContainer,
+            DaemonError::ReplicasUnreachable(_, _) => FailureCode::UnexpectedRequest,
+            DaemonError::ChaosUnsupported => FailureCode::UnexpectedRequest,
         };
         RpcMsg::Failure(rpc::Failure {
             code: code.into(),
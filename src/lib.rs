@@ -8,6 +8,9 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+// ESB errors are large, but are returned by all the bus handlers
+#![allow(clippy::result_large_err)]
+
 #[macro_use]
 extern crate amplify;
 #[macro_use]
@@ -17,16 +20,29 @@ extern crate log;
 #[macro_use]
 extern crate strict_encoding;
 
+mod chaos;
 mod config;
+#[cfg(feature = "server")]
+mod config_file;
+mod containers;
 mod error;
 pub mod stormd;
 pub mod transferd;
 pub mod downpourd;
+pub mod mqttd;
+pub mod nostrd;
 pub mod chatd;
+pub mod maild;
+pub mod boardd;
+pub mod syncd;
 pub mod bus;
+#[cfg(feature = "simulation")]
+pub mod sim;
 #[cfg(feature = "server")]
 pub mod opts;
 
 pub use config::Config;
+#[cfg(feature = "server")]
+pub use config_file::ConfigFile;
 pub(crate) use error::DaemonError;
-pub use error::LaunchError;
+pub use error::{ConfigFileError, LaunchError};
@@ -0,0 +1,42 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use internet2::addr::NodeId;
+use storm_rpc::Mail;
+
+/// Mail message body exchanged between the mail daemons of the peers.
+///
+/// Each mail carries a random id chosen by its sender, which is used to acknowledge the mail
+/// receipt and to drop the duplicates of the mails which were re-sent.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub enum Envelope {
+    /// Mail sent directly to the recipient.
+    Direct(u64, Mail),
+
+    /// Mail sent to a relay node, which keeps the mail and forwards it to the recipient.
+    Relay(u64, NodeId, Mail),
+
+    /// Mail forwarded by a relay node on behalf of the original sender.
+    Forwarded(u64, NodeId, Mail),
+
+    /// Acknowledgement of the mail receipt by the recipient or the relay.
+    Ack(u64),
+}
+
+impl Envelope {
+    pub fn attachments(&self) -> &[storm::ContainerId] {
+        match self {
+            Envelope::Direct(_, mail)
+            | Envelope::Relay(_, _, mail)
+            | Envelope::Forwarded(_, _, mail) => &mail.attachments,
+            Envelope::Ack(_) => &[],
+        }
+    }
+}
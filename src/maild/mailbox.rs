@@ -0,0 +1,240 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::{Mail, MailEntry, MailFolder, MailId, MailRequest};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::Envelope;
+use crate::stormd::{checkpoint, unix_time};
+
+/// Name of the file inside the data directory keeping the mailbox.
+pub const STORM_NODE_MAILBOX_FILE: &str = "mailbox.dat";
+
+/// Mail which is not yet acknowledged by the recipient or the relay.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+struct Outgoing {
+    /// Peer the mail is sent to: either the relay or the recipient
+    target: NodeId,
+    envelope: Envelope,
+    /// UNIX timestamp of the last delivery attempt
+    last_attempt: u64,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+struct MailboxData {
+    next_id: MailId,
+    mails: BTreeMap<MailId, MailEntry>,
+    outbox: BTreeMap<MailId, Outgoing>,
+    /// Senders of the received mails by the mail ids chosen by the senders
+    received: BTreeMap<u64, NodeId>,
+}
+
+/// Mails sent and received by the node, arranged into folders.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Mailbox {
+    path: PathBuf,
+    data: MailboxData,
+}
+
+impl Mailbox {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_MAILBOX_FILE);
+        let data = if path.exists() {
+            MailboxData::strict_decode(fs::File::open(&path)?)?
+        } else {
+            none!()
+        };
+        Ok(Mailbox { path, data })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.data)?;
+        Ok(())
+    }
+
+    fn insert(&mut self, entry: MailEntry) {
+        self.data.next_id = entry.id + 1;
+        self.data.mails.insert(entry.id, entry);
+    }
+
+    /// Puts mail into the outbox, returning its id and the peer and the envelope which should be
+    /// sent.
+    pub fn compose(
+        &mut self,
+        request: MailRequest,
+    ) -> Result<(MailId, NodeId, Envelope), strict_encoding::Error> {
+        let id = self.data.next_id;
+        let wire_id = rand::random();
+        let (target, envelope) = match request.relay {
+            Some(relay) => {
+                (relay, Envelope::Relay(wire_id, request.recipient, request.mail.clone()))
+            }
+            None => (request.recipient, Envelope::Direct(wire_id, request.mail.clone())),
+        };
+        self.insert(MailEntry {
+            id,
+            folder: MailFolder::Outbox,
+            timestamp: unix_time(),
+            peer: request.recipient,
+            incoming: false,
+            relay: request.relay,
+            read: true,
+            mail: request.mail,
+        });
+        self.data.outbox.insert(id, Outgoing {
+            target,
+            envelope: envelope.clone(),
+            last_attempt: unix_time(),
+        });
+        self.save()?;
+        Ok((id, target, envelope))
+    }
+
+    /// Moves mail acknowledged by the peer from the outbox to the sent folder, returning its id.
+    pub fn acknowledge(
+        &mut self,
+        remote_id: NodeId,
+        wire_id: u64,
+    ) -> Result<Option<MailId>, strict_encoding::Error> {
+        let id = self.data.outbox.iter().find_map(|(id, outgoing)| {
+            let acked = match outgoing.envelope {
+                Envelope::Direct(id, _) | Envelope::Relay(id, _, _) => id == wire_id,
+                _ => false,
+            };
+            Some(*id).filter(|_| acked && outgoing.target == remote_id)
+        });
+        let id = match id {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+        self.data.outbox.remove(&id);
+        if let Some(entry) = self.data.mails.get_mut(&id) {
+            entry.folder = MailFolder::Sent;
+        }
+        self.save()?;
+        Ok(Some(id))
+    }
+
+    /// Puts received mail into the inbox, returning the new entry, or `None` if the mail was
+    /// already received.
+    pub fn receive(
+        &mut self,
+        sender: NodeId,
+        relay: Option<NodeId>,
+        wire_id: u64,
+        mail: Mail,
+    ) -> Result<Option<MailEntry>, strict_encoding::Error> {
+        if self.data.received.get(&wire_id) == Some(&sender) {
+            return Ok(None);
+        }
+        let entry = MailEntry {
+            id: self.data.next_id,
+            folder: MailFolder::Inbox,
+            timestamp: unix_time(),
+            peer: sender,
+            incoming: true,
+            relay,
+            read: false,
+            mail,
+        };
+        self.insert(entry.clone());
+        self.data.received.insert(wire_id, sender);
+        self.save()?;
+        Ok(Some(entry))
+    }
+
+    /// Checks whether the container is attached to a mail sent to the peer or through it.
+    pub fn is_attached(&self, peer: NodeId, container_id: ContainerId) -> bool {
+        self.data.mails.values().any(|entry| {
+            !entry.incoming
+                && (entry.peer == peer || entry.relay == Some(peer))
+                && entry.mail.attachments.contains(&container_id)
+        })
+    }
+
+    pub fn list(&self, folder: MailFolder) -> impl Iterator<Item = &MailEntry> + '_ {
+        self.data.mails.values().filter(move |entry| entry.folder == folder)
+    }
+
+    /// Returns the mail, marking it as read.
+    pub fn read(&mut self, id: MailId) -> Result<Option<MailEntry>, strict_encoding::Error> {
+        let entry = match self.data.mails.get_mut(&id) {
+            Some(entry) if !entry.read => {
+                entry.read = true;
+                entry.clone()
+            }
+            Some(entry) => return Ok(Some(entry.clone())),
+            None => return Ok(None),
+        };
+        self.save()?;
+        Ok(Some(entry))
+    }
+
+    /// Moves the mail to another folder. Mails are moved to and from the outbox and the sent
+    /// folder by the node only, and only received mails may be moved to the inbox.
+    pub fn move_to(
+        &mut self,
+        id: MailId,
+        folder: MailFolder,
+    ) -> Result<Option<Result<(), MailFolder>>, strict_encoding::Error> {
+        let entry = match self.data.mails.get_mut(&id) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        match folder {
+            MailFolder::Outbox | MailFolder::Sent => return Ok(Some(Err(folder))),
+            MailFolder::Inbox if !entry.incoming => return Ok(Some(Err(folder))),
+            _ if entry.folder == MailFolder::Outbox => return Ok(Some(Err(folder))),
+            _ => entry.folder = folder,
+        }
+        self.save()?;
+        Ok(Some(Ok(())))
+    }
+
+    /// Deletes the mail, cancelling its delivery if it is in the outbox. Returns whether the mail
+    /// was known.
+    pub fn delete(&mut self, id: MailId) -> Result<bool, strict_encoding::Error> {
+        if self.data.mails.remove(&id).is_none() {
+            return Ok(false);
+        }
+        self.data.outbox.remove(&id);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Returns the outbox mails which delivery attempt is due, as pairs of the target peer and the
+    /// envelope.
+    pub fn due(
+        &mut self,
+        interval: u64,
+    ) -> Result<Vec<(NodeId, Envelope)>, strict_encoding::Error> {
+        let now = unix_time();
+        let due = self
+            .data
+            .outbox
+            .values_mut()
+            .filter(|outgoing| outgoing.last_attempt + interval <= now)
+            .map(|outgoing| {
+                outgoing.last_attempt = now;
+                (outgoing.target, outgoing.envelope.clone())
+            })
+            .collect::<Vec<_>>();
+        if !due.is_empty() {
+            self.save()?;
+        }
+        Ok(due)
+    }
+}
@@ -0,0 +1,23 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+mod envelope;
+mod mailbox;
+mod relay;
+mod service;
+#[cfg(feature = "server")]
+mod opts;
+
+pub use envelope::Envelope;
+pub use mailbox::Mailbox;
+#[cfg(feature = "server")]
+pub use opts::Opts;
+pub use relay::{RelayQueue, MAIL_RELAY_TTL};
+pub use service::{run, Runtime, MAIL_RETRY_INTERVAL};
@@ -0,0 +1,141 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::Mail;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::Envelope;
+use crate::stormd::{checkpoint, unix_time};
+
+/// Name of the file inside the data directory keeping the mails relayed by the node.
+pub const STORM_NODE_MAIL_RELAY_FILE: &str = "mail_relay.dat";
+
+/// Number of seconds during which the relay attempts to deliver a mail.
+pub const MAIL_RELAY_TTL: u64 = 7 * 24 * 3600;
+
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+struct Relayed {
+    sender: NodeId,
+    recipient: NodeId,
+    mail: Mail,
+    /// UNIX timestamp of the mail receipt from the sender
+    received: u64,
+    /// UNIX timestamp of the last delivery attempt
+    last_attempt: u64,
+}
+
+/// Mails which the node keeps on behalf of other peers until their recipients acknowledge them.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RelayQueue {
+    path: PathBuf,
+    /// Relayed mails by the mail ids chosen by the senders
+    mails: BTreeMap<u64, Relayed>,
+}
+
+impl RelayQueue {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_MAIL_RELAY_FILE);
+        let mails =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(RelayQueue { path, mails })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.mails)?;
+        Ok(())
+    }
+
+    /// Keeps mail for forwarding, returning the envelope which should be forwarded to the
+    /// recipient, or `None` if the mail is already kept.
+    pub fn hold(
+        &mut self,
+        wire_id: u64,
+        sender: NodeId,
+        recipient: NodeId,
+        mail: Mail,
+    ) -> Result<Option<Envelope>, strict_encoding::Error> {
+        if self.mails.contains_key(&wire_id) {
+            return Ok(None);
+        }
+        let envelope = Envelope::Forwarded(wire_id, sender, mail.clone());
+        self.mails.insert(wire_id, Relayed {
+            sender,
+            recipient,
+            mail,
+            received: unix_time(),
+            last_attempt: unix_time(),
+        });
+        self.save()?;
+        Ok(Some(envelope))
+    }
+
+    /// Removes mail acknowledged by its recipient, returning whether the mail was kept.
+    pub fn release(
+        &mut self,
+        recipient: NodeId,
+        wire_id: u64,
+    ) -> Result<bool, strict_encoding::Error> {
+        match self.mails.get(&wire_id) {
+            Some(relayed) if relayed.recipient == recipient => {}
+            _ => return Ok(false),
+        }
+        self.mails.remove(&wire_id);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Checks whether the container is attached to a mail kept for the recipient.
+    pub fn is_attached(&self, recipient: NodeId, container_id: ContainerId) -> bool {
+        self.mails.values().any(|relayed| {
+            relayed.recipient == recipient && relayed.mail.attachments.contains(&container_id)
+        })
+    }
+
+    /// Returns the mails which delivery attempt is due, as pairs of the recipient and the
+    /// envelope, dropping the mails which were not delivered during [`MAIL_RELAY_TTL`].
+    pub fn due(
+        &mut self,
+        interval: u64,
+    ) -> Result<Vec<(NodeId, Envelope)>, strict_encoding::Error> {
+        let now = unix_time();
+        let len = self.mails.len();
+        self.mails.retain(|wire_id, relayed| {
+            let keep = relayed.received + MAIL_RELAY_TTL > now;
+            if !keep {
+                warn!("Relayed mail {:#018x} to {} is expired", wire_id, relayed.recipient);
+            }
+            keep
+        });
+        let mut changed = len != self.mails.len();
+        let due = self
+            .mails
+            .iter_mut()
+            .filter(|(_, relayed)| relayed.last_attempt + interval <= now)
+            .map(|(wire_id, relayed)| {
+                relayed.last_attempt = now;
+                (
+                    relayed.recipient,
+                    Envelope::Forwarded(*wire_id, relayed.sender, relayed.mail.clone()),
+                )
+            })
+            .collect::<Vec<_>>();
+        changed |= !due.is_empty();
+        if changed {
+            self.save()?;
+        }
+        Ok(due)
+    }
+}
@@ -0,0 +1,366 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::thread;
+use std::time::Duration;
+
+use internet2::addr::NodeId;
+use internet2::ZmqSocketType;
+use microservices::error::BootstrapError;
+use microservices::esb::{self, ClientId, EndpointList, Error};
+use microservices::node::TryService;
+use storm::{ContainerFullId, Mesg, MesgId};
+use storm_ext::ExtMsg;
+use storm_rpc::{AddressedMsg, MailMove, MailQuery, RpcMsg, ServiceId};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::{Envelope, Mailbox, RelayQueue};
+use crate::bus::{terminate, BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::{Config, DaemonError, LaunchError};
+
+/// Interval between the delivery attempts of the mails which are not acknowledged, in seconds.
+pub const MAIL_RETRY_INTERVAL: u64 = 60;
+
+pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
+    let rpc_endpoint = config.rpc_endpoint.clone();
+    let ctl_endpoint = config.ctl_endpoint.clone();
+    let ext_endpoint = config.ext_endpoint.clone();
+    let runtime = Runtime::init(config)?;
+
+    debug!("Connecting to service buses {}, {}, {}", rpc_endpoint, ctl_endpoint, ext_endpoint);
+    let controller = esb::Controller::with(
+        map! {
+            ServiceBus::Storm => esb::BusConfig::with_addr(
+                ext_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Rpc => esb::BusConfig::with_addr(
+                rpc_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Ctl => esb::BusConfig::with_addr(
+                ctl_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            )
+        },
+        runtime,
+    )
+    .map_err(|_| LaunchError::BusSetupFailure)?;
+
+    controller.run_or_panic("maild");
+
+    unreachable!()
+}
+
+pub struct Runtime {
+    pub(super) mailbox: Mailbox,
+    pub(super) relay: RelayQueue,
+}
+
+impl Runtime {
+    pub fn init(config: Config) -> Result<Self, BootstrapError<LaunchError>> {
+        let mailbox = Mailbox::load(&config.data_dir).map_err(LaunchError::from)?;
+        let relay = RelayQueue::load(&config.data_dir).map_err(LaunchError::from)?;
+
+        info!("Mail runtime started successfully");
+
+        Ok(Self { mailbox, relay })
+    }
+}
+
+impl Responder for Runtime {}
+
+impl esb::Handler<ServiceBus> for Runtime {
+    type Request = BusMsg;
+    type Error = DaemonError;
+
+    fn identity(&self) -> ServiceId { ServiceId::maild() }
+
+    fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
+        thread::sleep(Duration::from_millis(100));
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        endpoints: &mut EndpointList<ServiceBus>,
+        bus_id: ServiceBus,
+        source: ServiceId,
+        request: Self::Request,
+    ) -> Result<(), Self::Error> {
+        // The controller has no timers, so the undelivered mails are re-sent when other messages
+        // are handled
+        if let Err(err) = self.retry(endpoints) {
+            warn!("Unable to re-send undelivered mails: {}", err);
+        }
+        match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                self.handle_storm(endpoints, msg)
+            }
+            (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
+                self.handle_rpc(endpoints, client_id, msg)
+            }
+            (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
+            (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        }
+    }
+
+    fn handle_err(
+        &mut self,
+        _endpoints: &mut EndpointList<ServiceBus>,
+        _error: Error<ServiceId>,
+    ) -> Result<(), Self::Error> {
+        // We do nothing and do not propagate error; it's already being reported
+        // with `error!` macro by the controller. If we propagate error here
+        // this will make whole daemon panic
+        Ok(())
+    }
+}
+
+impl Runtime {
+    fn handle_storm(
+        &mut self,
+        endpoints: &mut Endpoints,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let envelope = match Envelope::strict_deserialize(&data.body) {
+                    Ok(envelope) => envelope,
+                    Err(err) => {
+                        warn!("Invalid mail from {}: {}", remote_id, err);
+                        return Ok(());
+                    }
+                };
+                self.handle_envelope(endpoints, remote_id, data.mesg_id(), envelope)?;
+            }
+
+            ExtMsg::RetrieveContainer(AddressedMsg { remote_id, data }) => {
+                // Attachments are served only to the peers the mails were sent to
+                if self.mailbox.is_attached(remote_id, data.container_id)
+                    || self.relay.is_attached(remote_id, data.container_id)
+                {
+                    self.send_ext(
+                        endpoints,
+                        None,
+                        ExtMsg::SendContainer(AddressedMsg { remote_id, data }),
+                    )?;
+                } else {
+                    warn!(
+                        "Peer {} requests container {} not attached to its mails",
+                        remote_id, data.container_id
+                    );
+                }
+            }
+
+            ExtMsg::ContainerRetrieved(container_id) => {
+                debug!("Mail attachment {} is retrieved", container_id);
+            }
+
+            ExtMsg::Negotiate(_) => {}
+
+            wrong_msg => {
+                error!("Request is not supported by the Storm interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_envelope(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        mesg_id: MesgId,
+        envelope: Envelope,
+    ) -> Result<(), DaemonError> {
+        match envelope {
+            Envelope::Direct(wire_id, mail) => {
+                self.retrieve_attachments(endpoints, remote_id, mesg_id, &mail.attachments)?;
+                if let Some(entry) = self.mailbox.receive(remote_id, None, wire_id, mail)? {
+                    info!("Received mail {} from {}", entry.id, remote_id);
+                }
+                self.post(endpoints, remote_id, Envelope::Ack(wire_id))?;
+            }
+
+            Envelope::Relay(wire_id, recipient, mail) => {
+                let attachments = mail.attachments.clone();
+                if let Some(forward) = self.relay.hold(wire_id, remote_id, recipient, mail)? {
+                    info!("Relaying mail from {} to {}", remote_id, recipient);
+                    self.retrieve_attachments(endpoints, remote_id, mesg_id, &attachments)?;
+                    self.post(endpoints, recipient, forward)?;
+                }
+                self.post(endpoints, remote_id, Envelope::Ack(wire_id))?;
+            }
+
+            Envelope::Forwarded(wire_id, sender, mail) => {
+                self.retrieve_attachments(endpoints, remote_id, mesg_id, &mail.attachments)?;
+                if let Some(entry) = self.mailbox.receive(sender, Some(remote_id), wire_id, mail)? {
+                    info!("Received mail {} from {} through relay {}", entry.id, sender, remote_id);
+                }
+                self.post(endpoints, remote_id, Envelope::Ack(wire_id))?;
+            }
+
+            Envelope::Ack(wire_id) => {
+                if let Some(id) = self.mailbox.acknowledge(remote_id, wire_id)? {
+                    info!("Mail {} is acknowledged by {}", id, remote_id);
+                } else if self.relay.release(remote_id, wire_id)? {
+                    info!("Relayed mail {:#018x} is delivered to {}", wire_id, remote_id);
+                } else {
+                    trace!("Ignoring repeated acknowledgement from {}", remote_id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        message: RpcMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            RpcMsg::SendMail(request) => {
+                let reply = match self.mailbox.compose(request) {
+                    Ok((id, target, envelope)) => {
+                        self.post(endpoints, target, envelope)?;
+                        RpcMsg::MailSent(id)
+                    }
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::ListMail(MailQuery { folder, query }) => {
+                let mails = query.apply(self.mailbox.list(folder).cloned());
+                self.send_rpc(endpoints, client_id, RpcMsg::MailList(mails))?;
+            }
+
+            RpcMsg::ReadMail(id) => {
+                let reply = match self.mailbox.read(id) {
+                    Ok(Some(entry)) => RpcMsg::MailRead(entry),
+                    Ok(None) => DaemonError::UnknownMail(id).into(),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::MoveMail(MailMove { id, folder }) => {
+                let reply = match self.mailbox.move_to(id, folder) {
+                    Ok(Some(Ok(()))) => RpcMsg::Success(None.into()),
+                    Ok(Some(Err(folder))) => DaemonError::WrongMailFolder(folder).into(),
+                    Ok(None) => DaemonError::UnknownMail(id).into(),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            RpcMsg::DeleteMail(id) => {
+                let reply = match self.mailbox.delete(id) {
+                    Ok(true) => RpcMsg::Success(None.into()),
+                    Ok(false) => DaemonError::UnknownMail(id).into(),
+                    Err(err) => DaemonError::from(err).into(),
+                };
+                self.send_rpc(endpoints, client_id, reply)?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_ctl(
+        &mut self,
+        endpoints: &mut Endpoints,
+        _source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            CtlMsg::Ping(nonce) => {
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Pong(nonce))?;
+                Ok(())
+            }
+            CtlMsg::Shutdown => {
+                // All the daemon state is saved as soon as it changes
+                info!("Shutting down");
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ShutdownComplete)?;
+                terminate();
+                Ok(())
+            }
+            wrong_msg => {
+                error!("Request is not supported by the CTL interface");
+                Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &wrong_msg))
+            }
+        }
+    }
+
+    /// Re-sends the mails which are not acknowledged by their recipients or relays.
+    fn retry(&mut self, endpoints: &mut Endpoints) -> Result<(), DaemonError> {
+        let due = self.mailbox.due(MAIL_RETRY_INTERVAL)?;
+        let relayed = self.relay.due(MAIL_RETRY_INTERVAL)?;
+        for (remote_id, envelope) in due.into_iter().chain(relayed) {
+            debug!("Re-sending undelivered mail to {}", remote_id);
+            self.post(endpoints, remote_id, envelope)?;
+        }
+        Ok(())
+    }
+
+    /// Requests retrieval of the mail attachments from the peer which delivered the mail.
+    fn retrieve_attachments(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        message_id: MesgId,
+        attachments: &[storm::ContainerId],
+    ) -> Result<(), DaemonError> {
+        for container_id in attachments {
+            let request = ExtMsg::RetrieveContainer(AddressedMsg {
+                remote_id,
+                data: ContainerFullId {
+                    message_id,
+                    container_id: *container_id,
+                },
+            });
+            self.send_ext(endpoints, None, request)?;
+        }
+        Ok(())
+    }
+
+    fn post(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        envelope: Envelope,
+    ) -> Result<(), DaemonError> {
+        let addressed_msg = AddressedMsg {
+            remote_id,
+            data: Mesg {
+                parent_id: none!(),
+                container_ids: envelope.attachments().to_vec(),
+                body: envelope.strict_serialize()?,
+            },
+        };
+        self.send_ext(endpoints, None, ExtMsg::Post(addressed_msg))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "server")]
+use amplify::Wrapper;
+use internet2::addr::NodeId;
+#[cfg(feature = "server")]
+use storm::MesgId;
+use storm::StormApp;
+use storm_rpc::TopicId;
+
+#[cfg(feature = "server")]
+use super::Opts;
+#[cfg(feature = "server")]
+use crate::opts::Options;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Config {
+    /// Storm app which messages are bridged
+    pub app: StormApp,
+    /// Address of the MQTT broker
+    pub broker: String,
+    pub client_id: String,
+    /// Keep-alive interval of the MQTT connection, in seconds
+    pub keep_alive: u16,
+    /// MQTT topics for each of the bridged Storm topics
+    pub mappings: BTreeMap<TopicId, String>,
+    /// Storm peers receiving messages published to the MQTT topics
+    pub peers: BTreeSet<NodeId>,
+}
+
+#[cfg(feature = "server")]
+impl Options for Opts {
+    type Conf = Config;
+
+    fn shared(&self) -> &crate::opts::Opts { &self.shared }
+
+    fn config(&self) -> Self::Conf {
+        Config {
+            app: StormApp::from(self.app),
+            broker: self.broker.clone(),
+            client_id: self.mqtt_client_id.clone(),
+            keep_alive: self.keep_alive,
+            mappings: self
+                .mappings
+                .iter()
+                .map(|spec| {
+                    let (topic, mqtt_topic) =
+                        spec.split_once('=').expect("mapping is validated by clap");
+                    let id = topic.parse().expect("mapping topic is validated by clap");
+                    (TopicId::from_wire(MesgId::from_inner(id)), mqtt_topic.to_owned())
+                })
+                .collect(),
+            peers: self.peers.iter().copied().collect(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Minimal MQTT 3.1.1 client, supporting QoS 0 publishing and subscriptions only.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const PACKET_CONNECT: u8 = 0x10;
+const PACKET_CONNACK: u8 = 0x20;
+const PACKET_PUBLISH: u8 = 0x30;
+const PACKET_SUBSCRIBE: u8 = 0x82;
+const PACKET_PINGREQ: u8 = 0xC0;
+
+/// Protocol level of MQTT 3.1.1
+const PROTOCOL_LEVEL: u8 = 4;
+
+/// Connect flags requesting a clean session
+const FLAG_CLEAN_SESSION: u8 = 0x02;
+
+/// Writing half of the broker connection, which may be shared between threads.
+#[derive(Clone, Debug)]
+pub struct MqttClient {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+/// Reading half of the broker connection, which also keeps the connection alive.
+#[derive(Debug)]
+pub struct MqttReader {
+    stream: TcpStream,
+    client: MqttClient,
+    keep_alive: Duration,
+}
+
+impl MqttClient {
+    /// Connects to the broker, returning the client and the reader of the published messages.
+    pub fn connect(
+        broker: &str,
+        client_id: &str,
+        keep_alive: u16,
+    ) -> Result<(MqttClient, MqttReader), io::Error> {
+        let mut stream = TcpStream::connect(broker)?;
+
+        let mut packet = vec![];
+        put_str(&mut packet, "MQTT");
+        packet.push(PROTOCOL_LEVEL);
+        packet.push(FLAG_CLEAN_SESSION);
+        packet.extend(keep_alive.to_be_bytes());
+        put_str(&mut packet, client_id);
+        write_packet(&mut stream, PACKET_CONNECT, &packet)?;
+
+        let (kind, body) = read_packet(&mut stream)?;
+        if kind & 0xF0 != PACKET_CONNACK || body.len() != 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "broker did not acknowledge"));
+        }
+        if body[1] != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("broker refused connection with code {}", body[1]),
+            ));
+        }
+
+        let client = MqttClient {
+            stream: Arc::new(Mutex::new(stream.try_clone()?)),
+        };
+        let reader = MqttReader {
+            stream,
+            client: client.clone(),
+            keep_alive: Duration::from_secs(keep_alive.max(2) as u64),
+        };
+        Ok((client, reader))
+    }
+
+    fn send(&self, kind: u8, body: &[u8]) -> Result<(), io::Error> {
+        let mut stream = self.stream.lock().expect("MQTT connection lock is poisoned");
+        write_packet(&mut *stream, kind, body)
+    }
+
+    pub fn subscribe<'a>(
+        &self,
+        topics: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), io::Error> {
+        let mut packet = 1u16.to_be_bytes().to_vec();
+        for topic in topics {
+            put_str(&mut packet, topic);
+            // Requested QoS
+            packet.push(0);
+        }
+        self.send(PACKET_SUBSCRIBE, &packet)
+    }
+
+    pub fn publish(&self, topic: &str, payload: &[u8]) -> Result<(), io::Error> {
+        let mut packet = vec![];
+        put_str(&mut packet, topic);
+        packet.extend(payload);
+        self.send(PACKET_PUBLISH, &packet)
+    }
+}
+
+impl MqttReader {
+    /// Waits for the next message published by the broker, returning its topic and payload.
+    pub fn next_publish(&mut self) -> Result<(String, Vec<u8>), io::Error> {
+        loop {
+            // Pinging the broker if the connection is idle for half of the keep-alive period
+            self.stream.set_read_timeout(Some(self.keep_alive / 2))?;
+            let mut kind = [0u8; 1];
+            match self.stream.read_exact(&mut kind) {
+                Err(err)
+                    if err.kind() == io::ErrorKind::WouldBlock
+                        || err.kind() == io::ErrorKind::TimedOut =>
+                {
+                    self.client.send(PACKET_PINGREQ, &[])?;
+                    continue;
+                }
+                result => result?,
+            }
+            self.stream.set_read_timeout(None)?;
+            let body = read_body(&mut self.stream)?;
+            if kind[0] & 0xF0 != PACKET_PUBLISH {
+                // Acknowledgements and ping responses
+                continue;
+            }
+            let qos = (kind[0] >> 1) & 0x03;
+            let (topic, mut rest) = get_str(&body)?;
+            if qos > 0 {
+                // Skipping packet identifier
+                rest = rest.get(2..).ok_or(io::ErrorKind::UnexpectedEof)?;
+            }
+            return Ok((topic, rest.to_vec()));
+        }
+    }
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend((s.len() as u16).to_be_bytes());
+    buf.extend(s.as_bytes());
+}
+
+fn get_str(buf: &[u8]) -> Result<(String, &[u8]), io::Error> {
+    let len = buf.get(..2).ok_or(io::ErrorKind::UnexpectedEof)?;
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    let s = buf.get(2..2 + len).ok_or(io::ErrorKind::UnexpectedEof)?;
+    Ok((String::from_utf8_lossy(s).to_string(), &buf[2 + len..]))
+}
+
+fn write_packet(stream: &mut impl Write, kind: u8, body: &[u8]) -> Result<(), io::Error> {
+    let mut packet = vec![kind];
+    // Remaining length is encoded with 7 bits per byte, least significant first
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    packet.extend(body);
+    stream.write_all(&packet)
+}
+
+fn read_body(stream: &mut impl Read) -> Result<Vec<u8>, io::Error> {
+    let mut len = 0usize;
+    for shift in (0..28).step_by(7) {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte)?;
+        len |= ((byte[0] & 0x7F) as usize) << shift;
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body)?;
+            return Ok(body);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "malformed MQTT packet length"))
+}
+
+fn read_packet(stream: &mut impl Read) -> Result<(u8, Vec<u8>), io::Error> {
+    let mut kind = [0u8; 1];
+    stream.read_exact(&mut kind)?;
+    Ok((kind[0], read_body(stream)?))
+}
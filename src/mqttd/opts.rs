@@ -0,0 +1,72 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use clap::Parser;
+use internet2::addr::NodeId;
+
+use crate::opts::Opts as SharedOpts;
+
+/// Command-line arguments
+#[derive(Parser)]
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[clap(author, version, name = "mqttd", about = "Storm node MQTT bridge service")]
+pub struct Opts {
+    /// These params can be read also from the configuration file, not just
+    /// command-line args or environment variables
+    #[clap(flatten)]
+    pub shared: SharedOpts,
+
+    /// Numeric id of the Storm app which messages are bridged.
+    #[clap(long)]
+    pub app: u16,
+
+    /// Address of the MQTT broker, in `HOST:PORT` format. Only plain TCP connections are
+    /// supported.
+    #[clap(long, default_value = "127.0.0.1:1883")]
+    pub broker: String,
+
+    /// Client id used to connect to the MQTT broker.
+    #[clap(long, default_value = "storm-mqttd")]
+    pub mqtt_client_id: String,
+
+    /// Keep-alive interval of the MQTT connection, in seconds.
+    #[clap(long, default_value = "60")]
+    pub keep_alive: u16,
+
+    /// Map Storm topic to MQTT topic, in `TOPIC=MQTT_TOPIC` format.
+    ///
+    /// `TOPIC` is the hex id of the Storm topic. Replies to the topic are published to the MQTT
+    /// topic, and messages published to the MQTT topic are posted to the Storm peers as replies to
+    /// the topic. The argument can be given multiple times.
+    #[clap(long = "map", multiple_occurrences = true, validator = validate_mapping)]
+    pub mappings: Vec<String>,
+
+    /// Storm peer receiving messages published to the mapped MQTT topics. The argument can be
+    /// given multiple times.
+    #[clap(long = "peer", multiple_occurrences = true)]
+    pub peers: Vec<NodeId>,
+}
+
+fn validate_mapping(spec: &str) -> Result<(), String> {
+    let (topic, mqtt_topic) =
+        spec.split_once('=').ok_or("mapping must be given as `TOPIC=MQTT_TOPIC`")?;
+    if topic.len() != 64 || !topic.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("invalid topic id `{}`", topic));
+    }
+    if mqtt_topic.is_empty() || mqtt_topic.contains(['+', '#']) {
+        return Err(format!("invalid MQTT topic `{}`; wildcards are not supported", mqtt_topic));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl Opts {
+    pub fn process(&mut self) { self.shared.process(); }
+}
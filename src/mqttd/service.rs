@@ -0,0 +1,298 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::thread;
+use std::time::Duration;
+
+use bitcoin_hashes::{sha256, Hash};
+use internet2::addr::{NodeId, ServiceAddr};
+use internet2::ZmqSocketType;
+use microservices::error::BootstrapError;
+use microservices::esb::{self, ClientId, EndpointList, Error};
+use microservices::node::TryService;
+use storm::{Mesg, StormApp};
+use storm_ext::ExtMsg;
+use storm_rpc::{AddressedMsg, BridgeMsg, RpcMsg, ServiceId, TopicId};
+
+use super::mqtt::{MqttClient, MqttReader};
+use crate::bus::{BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::{Config, DaemonError, LaunchError};
+
+/// Maximal number of the messages published to the broker which echo is expected back.
+pub const MQTT_ECHO_LIMIT: usize = 1024;
+
+/// Delay before reconnecting to the RPC interface after it has failed, in seconds.
+const RPC_RECONNECT_DELAY: u64 = 5;
+
+pub fn run(config: Config<super::Config>) -> Result<(), BootstrapError<LaunchError>> {
+    let rpc_endpoint = config.rpc_endpoint.clone();
+    let ctl_endpoint = config.ctl_endpoint.clone();
+    let ext_endpoint = config.ext_endpoint.clone();
+    let runtime = Runtime::init(config)?;
+
+    debug!("Connecting to service buses {}, {}, {}", rpc_endpoint, ctl_endpoint, ext_endpoint);
+    let controller = esb::Controller::with(
+        map! {
+            ServiceBus::Storm => esb::BusConfig::with_addr(
+                ext_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Rpc => esb::BusConfig::with_addr(
+                rpc_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Ctl => esb::BusConfig::with_addr(
+                ctl_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            )
+        },
+        runtime,
+    )
+    .map_err(|_| LaunchError::BusSetupFailure)?;
+
+    controller.run_or_panic("mqttd");
+
+    unreachable!()
+}
+
+/// Bridge between the topics of a Storm app and the topics of an MQTT broker.
+///
+/// Replies to the mapped Storm topics are published to the MQTT broker. Messages published to the
+/// mapped MQTT topics are read by a background thread and passed to the bridge through the RPC
+/// interface, after which the bridge posts them to the configured Storm peers.
+pub struct Runtime {
+    app: StormApp,
+    mqtt: MqttClient,
+    /// MQTT topics for each of the bridged Storm topics
+    mappings: BTreeMap<TopicId, String>,
+    /// Storm peers receiving messages published to the MQTT topics
+    peers: Vec<NodeId>,
+    /// Messages published to the broker by the bridge itself, which are delivered back to the
+    /// bridge by the broker and must not be posted to the Storm peers
+    echoes: VecDeque<(String, sha256::Hash)>,
+}
+
+impl Runtime {
+    pub fn init(config: Config<super::Config>) -> Result<Self, BootstrapError<LaunchError>> {
+        let ext = config.ext;
+        debug!("Connecting to MQTT broker at {}", ext.broker);
+
+        let (mqtt, reader) = MqttClient::connect(&ext.broker, &ext.client_id, ext.keep_alive)
+            .map_err(|err| LaunchError::BrokerConnection(err.to_string()))?;
+        mqtt.subscribe(ext.mappings.values().map(String::as_str))
+            .map_err(|err| LaunchError::BrokerConnection(err.to_string()))?;
+
+        let app = ext.app;
+        let rpc_endpoint = config.rpc_endpoint.clone();
+        let chat_endpoint = config.chat_endpoint.clone();
+        thread::spawn(move || inbound(app, reader, rpc_endpoint, chat_endpoint));
+
+        info!("MQTT bridge runtime started successfully");
+
+        Ok(Self {
+            app,
+            mqtt,
+            mappings: ext.mappings,
+            peers: ext.peers.into_iter().collect(),
+            echoes: empty!(),
+        })
+    }
+}
+
+/// Reads messages published to the MQTT topics and passes them to the bridge daemon.
+fn inbound(app: StormApp, mut reader: MqttReader, rpc: ServiceAddr, chat: ServiceAddr) {
+    let bridge = ServiceId::StormApp(app);
+    let mut client = None;
+    loop {
+        let (topic, payload) = match reader.next_publish() {
+            Ok(publish) => publish,
+            Err(err) => {
+                // Without the broker connection the bridge is useless, so we stop the daemon and
+                // let the operator restart it
+                error!("MQTT broker connection is lost: {}", err);
+                std::process::exit(1);
+            }
+        };
+        if client.is_none() {
+            match storm_rpc::Client::with(rpc.clone(), chat.clone(), s!("mqttd")) {
+                Ok(c) => client = Some(c),
+                Err(err) => {
+                    warn!("Unable to connect to RPC interface: {}", err);
+                    thread::sleep(Duration::from_secs(RPC_RECONNECT_DELAY));
+                    continue;
+                }
+            }
+        }
+        let msg = BridgeMsg { topic, payload };
+        if let Some(Err(err)) =
+            client.as_mut().map(|client| client.bridge_inbound(bridge.clone(), msg))
+        {
+            warn!("Unable to pass MQTT message to the bridge: {}", err);
+            client = None;
+        }
+    }
+}
+
+impl Responder for Runtime {}
+
+impl esb::Handler<ServiceBus> for Runtime {
+    type Request = BusMsg;
+    type Error = DaemonError;
+
+    fn identity(&self) -> ServiceId { ServiceId::StormApp(self.app) }
+
+    fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
+        thread::sleep(Duration::from_millis(100));
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        self.send_ext(endpoints, None, ExtMsg::RegisterApp(self.app))?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        endpoints: &mut EndpointList<ServiceBus>,
+        bus_id: ServiceBus,
+        source: ServiceId,
+        request: Self::Request,
+    ) -> Result<(), Self::Error> {
+        match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                self.handle_storm(endpoints, msg)
+            }
+            (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
+                self.handle_rpc(endpoints, client_id, msg)
+            }
+            (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
+            (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        }
+    }
+
+    fn handle_err(
+        &mut self,
+        _endpoints: &mut EndpointList<ServiceBus>,
+        _error: Error<ServiceId>,
+    ) -> Result<(), Self::Error> {
+        // We do nothing and do not propagate error; it's already being reported
+        // with `error!` macro by the controller. If we propagate error here
+        // this will make whole daemon panic
+        Ok(())
+    }
+}
+
+impl Runtime {
+    fn handle_storm(
+        &mut self,
+        endpoints: &mut Endpoints,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let topic = TopicId::from_wire(data.parent_id);
+                let mqtt_topic = match self.mappings.get(&topic) {
+                    Some(mqtt_topic) => mqtt_topic.clone(),
+                    None => {
+                        trace!("Ignoring message from {} outside of the bridged topics", remote_id);
+                        return Ok(());
+                    }
+                };
+                debug!("Publishing message from {} to MQTT topic {}", remote_id, mqtt_topic);
+                if let Err(err) = self.mqtt.publish(&mqtt_topic, &data.body) {
+                    error!("Unable to publish message to MQTT topic {}: {}", mqtt_topic, err);
+                    return Ok(());
+                }
+                if self.echoes.len() >= MQTT_ECHO_LIMIT {
+                    self.echoes.pop_front();
+                }
+                self.echoes.push_back((mqtt_topic, sha256::Hash::hash(&data.body)));
+            }
+
+            ExtMsg::Negotiate(_) => {}
+
+            ExtMsg::Heartbeat(nonce) => {
+                self.send_ext(endpoints, None, ExtMsg::Heartbeat(nonce))?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the Storm interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        message: RpcMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            RpcMsg::BridgeInbound(msg) => {
+                self.post(endpoints, msg);
+                self.send_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_ctl(
+        &mut self,
+        _endpoints: &mut Endpoints,
+        _source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        error!("Request is not supported by the CTL interface");
+        Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &message))
+    }
+
+    /// Posts message published to an MQTT topic to the Storm peers as a reply to the mapped
+    /// topic.
+    fn post(&mut self, endpoints: &mut Endpoints, msg: BridgeMsg) {
+        let echo = (msg.topic, sha256::Hash::hash(&msg.payload));
+        if let Some(pos) = self.echoes.iter().position(|published| *published == echo) {
+            self.echoes.remove(pos);
+            return;
+        }
+        let (mqtt_topic, _) = echo;
+        let topic = match self.mappings.iter().find(|(_, mapped)| **mapped == mqtt_topic) {
+            Some((topic, _)) => *topic,
+            None => {
+                warn!("Message from unmapped MQTT topic {} is ignored", mqtt_topic);
+                return;
+            }
+        };
+        for remote_id in &self.peers {
+            let post = ExtMsg::Post(AddressedMsg {
+                remote_id: *remote_id,
+                data: Mesg {
+                    parent_id: topic.to_wire(),
+                    body: msg.payload.clone(),
+                    container_ids: empty!(),
+                },
+            });
+            if let Err(err) = self.send_ext(endpoints, None, post) {
+                warn!("Unable to post MQTT message to {}: {}", remote_id, err);
+            }
+        }
+    }
+}
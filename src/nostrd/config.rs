@@ -0,0 +1,75 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "server")]
+use amplify::Wrapper;
+use internet2::addr::NodeId;
+#[cfg(feature = "server")]
+use storm::MesgId;
+use storm::StormApp;
+use storm_rpc::TopicId;
+
+#[cfg(feature = "server")]
+use super::Opts;
+#[cfg(feature = "server")]
+use crate::opts::Options;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Config {
+    /// Storm app which messages are bridged
+    pub app: StormApp,
+    /// URLs of the Nostr relays
+    pub relays: Vec<String>,
+    /// Nostr hashtags for each of the bridged Storm topics
+    pub mappings: BTreeMap<TopicId, String>,
+    /// Nostr public keys of the Storm nodes
+    pub keys: BTreeMap<NodeId, String>,
+    /// Storm peers receiving notes mirrored from Nostr
+    pub peers: BTreeSet<NodeId>,
+    /// Hex secret key signing notes mirrored to Nostr
+    pub secret_key: String,
+}
+
+#[cfg(feature = "server")]
+impl Options for Opts {
+    type Conf = Config;
+
+    fn shared(&self) -> &crate::opts::Opts { &self.shared }
+
+    fn config(&self) -> Self::Conf {
+        Config {
+            app: StormApp::from(self.app),
+            relays: self.relays.clone(),
+            mappings: self
+                .mappings
+                .iter()
+                .map(|spec| {
+                    let (topic, hashtag) =
+                        spec.split_once('=').expect("mapping is validated by clap");
+                    let id = topic.parse().expect("mapping topic is validated by clap");
+                    (TopicId::from_wire(MesgId::from_inner(id)), hashtag.to_lowercase())
+                })
+                .collect(),
+            keys: self
+                .keys
+                .iter()
+                .map(|spec| {
+                    let (node_id, pubkey) = spec.split_once('=').expect("key is validated by clap");
+                    let node_id = node_id.parse().expect("node id is validated by clap");
+                    (node_id, pubkey.to_lowercase())
+                })
+                .collect(),
+            peers: self.peers.iter().copied().collect(),
+            secret_key: self.nostr_key.clone(),
+        }
+    }
+}
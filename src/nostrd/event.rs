@@ -0,0 +1,134 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::str::FromStr;
+
+use amplify::hex::ToHex;
+use bitcoin_hashes::{sha256, Hash};
+use secp256k1::{schnorr, KeyPair, Message, Secp256k1, XOnlyPublicKey};
+
+use super::Json;
+
+/// Kind of the Nostr events carrying short text notes.
+pub const NOSTR_KIND_TEXT_NOTE: u64 = 1;
+
+/// Nostr event (NIP-01).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Event {
+    pub id: String,
+    pub pubkey: String,
+    pub created_at: u64,
+    pub kind: u64,
+    pub tags: Vec<Vec<String>>,
+    pub content: String,
+    pub sig: String,
+}
+
+impl Event {
+    /// Creates text note signed with the key.
+    pub fn text_note(
+        keypair: &KeyPair,
+        created_at: u64,
+        tags: Vec<Vec<String>>,
+        content: String,
+    ) -> Event {
+        let secp = Secp256k1::signing_only();
+        let (pubkey, _) = keypair.x_only_public_key();
+        let mut event = Event {
+            id: s!(""),
+            pubkey: pubkey.to_string(),
+            created_at,
+            kind: NOSTR_KIND_TEXT_NOTE,
+            tags,
+            content,
+            sig: s!(""),
+        };
+        let id = event.compute_id();
+        let msg = Message::from_slice(&id[..]).expect("hash has the size of a message");
+        event.id = id.to_hex();
+        event.sig = secp.sign_schnorr(&msg, keypair).to_string();
+        event
+    }
+
+    /// Computes event id, which is the hash of the event serialization.
+    fn compute_id(&self) -> sha256::Hash {
+        let serialization = Json::Array(vec![
+            Json::Number(s!("0")),
+            Json::str(&self.pubkey),
+            Json::Number(self.created_at.to_string()),
+            Json::Number(self.kind.to_string()),
+            tags_json(&self.tags),
+            Json::str(&self.content),
+        ]);
+        sha256::Hash::hash(serialization.to_string().as_bytes())
+    }
+
+    /// Checks that the event id matches the event data and that the event is signed by its
+    /// author.
+    pub fn verify(&self) -> bool {
+        let id = self.compute_id();
+        if id.to_hex() != self.id {
+            return false;
+        }
+        let (pubkey, sig) =
+            match (XOnlyPublicKey::from_str(&self.pubkey), schnorr::Signature::from_str(&self.sig))
+            {
+                (Ok(pubkey), Ok(sig)) => (pubkey, sig),
+                _ => return false,
+            };
+        let msg = Message::from_slice(&id[..]).expect("hash has the size of a message");
+        Secp256k1::verification_only().verify_schnorr(&sig, &msg, &pubkey).is_ok()
+    }
+
+    /// Returns values of the tags with the given name.
+    pub fn tag_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags
+            .iter()
+            .filter(move |tag| tag.first().map(String::as_str) == Some(name))
+            .filter_map(|tag| tag.get(1))
+            .map(String::as_str)
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            (s!("id"), Json::str(&self.id)),
+            (s!("pubkey"), Json::str(&self.pubkey)),
+            (s!("created_at"), Json::Number(self.created_at.to_string())),
+            (s!("kind"), Json::Number(self.kind.to_string())),
+            (s!("tags"), tags_json(&self.tags)),
+            (s!("content"), Json::str(&self.content)),
+            (s!("sig"), Json::str(&self.sig)),
+        ])
+    }
+
+    pub fn from_json(json: &Json) -> Option<Event> {
+        let tags = json
+            .get("tags")?
+            .as_array()?
+            .iter()
+            .map(|tag| {
+                tag.as_array()?.iter().map(|item| item.as_str().map(str::to_owned)).collect()
+            })
+            .collect::<Option<_>>()?;
+        Some(Event {
+            id: json.get("id")?.as_str()?.to_owned(),
+            pubkey: json.get("pubkey")?.as_str()?.to_owned(),
+            created_at: json.get("created_at")?.as_u64()?,
+            kind: json.get("kind")?.as_u64()?,
+            tags,
+            content: json.get("content")?.as_str()?.to_owned(),
+            sig: json.get("sig")?.as_str()?.to_owned(),
+        })
+    }
+}
+
+fn tags_json(tags: &[Vec<String>]) -> Json {
+    Json::Array(tags.iter().map(|tag| Json::Array(tag.iter().map(Json::str).collect())).collect())
+}
@@ -0,0 +1,277 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Minimal JSON values, sufficient for the Nostr relay protocol.
+
+use std::fmt::{self, Display, Formatter, Write};
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// Number in its original text representation
+    Number(String),
+    Str(String),
+    Array(Vec<Json>),
+    /// Object fields in the original order
+    Object(Vec<(String, Json)>),
+}
+
+/// Error parsing JSON.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display("invalid JSON at position {0}")]
+pub struct JsonError(usize);
+
+impl Json {
+    pub fn parse(s: &str) -> Result<Json, JsonError> {
+        let mut parser = Parser {
+            s: s.as_bytes(),
+            pos: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_ws();
+        if parser.pos != s.len() {
+            return Err(JsonError(parser.pos));
+        }
+        Ok(value)
+    }
+
+    pub fn str(s: impl ToString) -> Json { Json::Str(s.to_string()) }
+
+    pub fn get(&self, field: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(name, _)| name == field).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => f.write_str(n),
+            Json::Str(s) => write_str(f, s),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (no, item) in items.iter().enumerate() {
+                    if no > 0 {
+                        f.write_char(',')?;
+                    }
+                    Display::fmt(item, f)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (no, (name, value)) in fields.iter().enumerate() {
+                    if no > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_str(f, name)?;
+                    f.write_char(':')?;
+                    Display::fmt(value, f)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Writes string escaping only the characters which must be escaped, as required for the
+/// serialization of Nostr events.
+fn write_str(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0C}' => f.write_str("\\f")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn err(&self) -> JsonError { JsonError(self.pos) }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.s.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), JsonError> {
+        if self.s[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.err())
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, JsonError> {
+        self.skip_ws();
+        match self.s.get(self.pos) {
+            Some(b'n') => self.expect("null").map(|_| Json::Null),
+            Some(b't') => self.expect("true").map(|_| Json::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::Str),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                self.skip_ws();
+                if self.s.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_ws();
+                    match self.s.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Json::Array(items));
+                        }
+                        _ => return Err(self.err()),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields = vec![];
+                self.skip_ws();
+                if self.s.get(self.pos) == Some(&b'}') {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                loop {
+                    self.skip_ws();
+                    let name = self.string()?;
+                    self.skip_ws();
+                    self.expect(":")?;
+                    fields.push((name, self.value()?));
+                    self.skip_ws();
+                    match self.s.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Json::Object(fields));
+                        }
+                        _ => return Err(self.err()),
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9') => {
+                let start = self.pos;
+                while matches!(
+                    self.s.get(self.pos),
+                    Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                ) {
+                    self.pos += 1;
+                }
+                let n = String::from_utf8_lossy(&self.s[start..self.pos]).to_string();
+                Ok(Json::Number(n))
+            }
+            _ => Err(self.err()),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let hex = self.s.get(self.pos..self.pos + 4).ok_or_else(|| self.err())?;
+        let hex = std::str::from_utf8(hex).map_err(|_| self.err())?;
+        let code = u32::from_str_radix(hex, 16).map_err(|_| self.err())?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect("\"")?;
+        let mut s = vec![];
+        loop {
+            match self.s.get(self.pos) {
+                None => return Err(self.err()),
+                Some(b'"') => {
+                    self.pos += 1;
+                    return String::from_utf8(s).map_err(|_| self.err());
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.s.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{08}',
+                        Some(b'f') => '\u{0C}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let mut code = self.hex4()?;
+                            if (0xD800..0xDC00).contains(&code) {
+                                // Surrogate pair
+                                self.expect("\\u")?;
+                                let low = self.hex4()?;
+                                code = 0x10000
+                                    + ((code - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            let c = char::from_u32(code).ok_or_else(|| self.err())?;
+                            s.extend(c.encode_utf8(&mut [0u8; 4]).as_bytes());
+                            continue;
+                        }
+                        _ => return Err(self.err()),
+                    };
+                    self.pos += 1;
+                    s.extend(c.encode_utf8(&mut [0u8; 4]).as_bytes());
+                }
+                Some(byte) => {
+                    s.push(*byte);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+}
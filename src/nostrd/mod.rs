@@ -0,0 +1,25 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+mod config;
+mod event;
+mod json;
+mod service;
+mod ws;
+#[cfg(feature = "server")]
+mod opts;
+
+pub use config::Config;
+pub use event::{Event, NOSTR_KIND_TEXT_NOTE};
+pub use json::{Json, JsonError};
+#[cfg(feature = "server")]
+pub use opts::Opts;
+pub use service::{run, Runtime, NOSTR_SEEN_LIMIT, NOSTR_TAG_STORM};
+pub use ws::{WsReader, WsWriter, WS_MESSAGE_LIMIT};
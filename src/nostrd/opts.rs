@@ -0,0 +1,102 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::str::FromStr;
+
+use clap::Parser;
+use internet2::addr::NodeId;
+
+use crate::opts::Opts as SharedOpts;
+
+/// Command-line arguments
+#[derive(Parser)]
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[clap(author, version, name = "nostrd", about = "Storm node Nostr bridge service")]
+pub struct Opts {
+    /// These params can be read also from the configuration file, not just
+    /// command-line args or environment variables
+    #[clap(flatten)]
+    pub shared: SharedOpts,
+
+    /// Numeric id of the Storm app which messages are bridged.
+    #[clap(long)]
+    pub app: u16,
+
+    /// Nostr relay to mirror the topics to and from. Only plain `ws://` URLs are supported. The
+    /// argument can be given multiple times.
+    #[clap(long = "relay", required = true, multiple_occurrences = true, validator = validate_relay)]
+    pub relays: Vec<String>,
+
+    /// Map public Storm topic to Nostr hashtag, in `TOPIC=HASHTAG` format.
+    ///
+    /// `TOPIC` is the hex id of the Storm topic. Replies to the topic are published to the relays
+    /// as text notes tagged with the hashtag, and text notes with the hashtag are posted to the
+    /// Storm peers as replies to the topic. The argument can be given multiple times.
+    #[clap(long = "map", multiple_occurrences = true, validator = validate_mapping)]
+    pub mappings: Vec<String>,
+
+    /// Map Storm node to Nostr public key, in `NODE_ID=PUBKEY` format.
+    ///
+    /// Only text notes from the mapped public keys are mirrored into Storm; notes mirrored from
+    /// Storm mention the public key of their author, if it is mapped. `PUBKEY` is the hex x-only
+    /// public key. The argument can be given multiple times.
+    #[clap(long = "key", multiple_occurrences = true, validator = validate_key)]
+    pub keys: Vec<String>,
+
+    /// Storm peer receiving notes mirrored from Nostr. The argument can be given multiple times.
+    #[clap(long = "peer", multiple_occurrences = true)]
+    pub peers: Vec<NodeId>,
+
+    /// Hex secret key signing notes mirrored to Nostr.
+    #[clap(long, env = "STORM_NOSTR_KEY", hide_env_values = true, validator = validate_secret)]
+    pub nostr_key: String,
+}
+
+fn is_hex32(s: &str) -> bool { s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) }
+
+fn validate_relay(url: &str) -> Result<(), String> {
+    if !url.starts_with("ws://") {
+        return Err(format!("unsupported relay URL `{}`; only ws:// URLs are supported", url));
+    }
+    Ok(())
+}
+
+fn validate_mapping(spec: &str) -> Result<(), String> {
+    let (topic, hashtag) =
+        spec.split_once('=').ok_or("mapping must be given as `TOPIC=HASHTAG`")?;
+    if !is_hex32(topic) {
+        return Err(format!("invalid topic id `{}`", topic));
+    }
+    if hashtag.is_empty() || hashtag.starts_with('#') || hashtag.contains(char::is_whitespace) {
+        return Err(format!("invalid hashtag `{}`; it must be given without `#`", hashtag));
+    }
+    Ok(())
+}
+
+fn validate_key(spec: &str) -> Result<(), String> {
+    let (node_id, pubkey) = spec.split_once('=').ok_or("key must be given as `NODE_ID=PUBKEY`")?;
+    NodeId::from_str(node_id).map_err(|_| format!("invalid node id `{}`", node_id))?;
+    if !is_hex32(pubkey) {
+        return Err(format!("invalid Nostr public key `{}`", pubkey));
+    }
+    Ok(())
+}
+
+fn validate_secret(key: &str) -> Result<(), String> {
+    if !is_hex32(key) {
+        return Err("Nostr secret key must be given as 32 hex-encoded bytes".to_owned());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl Opts {
+    pub fn process(&mut self) { self.shared.process(); }
+}
@@ -0,0 +1,418 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use internet2::addr::{NodeId, ServiceAddr};
+use internet2::ZmqSocketType;
+use microservices::error::BootstrapError;
+use microservices::esb::{self, ClientId, EndpointList, Error};
+use microservices::node::TryService;
+use secp256k1::{KeyPair, Secp256k1};
+use storm::{Mesg, StormApp};
+use storm_ext::ExtMsg;
+use storm_rpc::{AddressedMsg, BridgeMsg, RpcMsg, ServiceId, TopicId};
+
+use super::{ws, Event, Json, WsWriter, NOSTR_KIND_TEXT_NOTE};
+use crate::bus::{BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::stormd::unix_time;
+use crate::{Config, DaemonError, LaunchError};
+
+/// Number of the recently mirrored note ids remembered to skip the same note received from
+/// several relays.
+pub const NOSTR_SEEN_LIMIT: usize = 4096;
+
+/// Name of the tag carrying id of the Storm node which authored the mirrored note.
+pub const NOSTR_TAG_STORM: &str = "storm";
+
+/// Id of the relay subscription requested by the bridge.
+const SUBSCRIPTION_ID: &str = "storm";
+
+/// Delay before reconnecting to a relay or to the RPC interface after a failure, in seconds.
+const RECONNECT_DELAY: u64 = 10;
+
+pub fn run(config: Config<super::Config>) -> Result<(), BootstrapError<LaunchError>> {
+    let rpc_endpoint = config.rpc_endpoint.clone();
+    let ctl_endpoint = config.ctl_endpoint.clone();
+    let ext_endpoint = config.ext_endpoint.clone();
+    let runtime = Runtime::init(config)?;
+
+    debug!("Connecting to service buses {}, {}, {}", rpc_endpoint, ctl_endpoint, ext_endpoint);
+    let controller = esb::Controller::with(
+        map! {
+            ServiceBus::Storm => esb::BusConfig::with_addr(
+                ext_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Rpc => esb::BusConfig::with_addr(
+                rpc_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Ctl => esb::BusConfig::with_addr(
+                ctl_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            )
+        },
+        runtime,
+    )
+    .map_err(|_| LaunchError::BusSetupFailure)?;
+
+    controller.run_or_panic("nostrd");
+
+    unreachable!()
+}
+
+/// Connection to a relay, which is re-established by the relay thread once it is lost.
+type RelaySlot = Arc<Mutex<Option<WsWriter>>>;
+
+/// Bridge mirroring public topics of a Storm app to Nostr relays and back.
+///
+/// Replies to the mapped Storm topics are published to the relays as text notes tagged with the
+/// mapped hashtags and signed by the bridge key. Each relay is read by a background thread, which
+/// passes notes with the mapped hashtags from the mapped Nostr keys to the bridge through the RPC
+/// interface, after which the bridge posts them to the configured Storm peers.
+pub struct Runtime {
+    app: StormApp,
+    keypair: KeyPair,
+    relays: Vec<(String, RelaySlot)>,
+    /// Nostr hashtags for each of the bridged Storm topics
+    mappings: BTreeMap<TopicId, String>,
+    /// Nostr public keys of the Storm nodes
+    keys: BTreeMap<NodeId, String>,
+    /// Storm peers receiving notes mirrored from Nostr
+    peers: Vec<NodeId>,
+}
+
+impl Runtime {
+    pub fn init(config: Config<super::Config>) -> Result<Self, BootstrapError<LaunchError>> {
+        let ext = config.ext;
+        let keypair = KeyPair::from_seckey_str(&Secp256k1::new(), &ext.secret_key)
+            .map_err(|_| LaunchError::InvalidNostrKey)?;
+        let (pubkey, _) = keypair.x_only_public_key();
+        info!("Mirroring notes to Nostr as {}", pubkey);
+
+        let filter = Json::Object(vec![
+            (s!("kinds"), Json::Array(vec![Json::Number(NOSTR_KIND_TEXT_NOTE.to_string())])),
+            (s!("#t"), Json::Array(ext.mappings.values().map(Json::str).collect())),
+            (s!("authors"), Json::Array(ext.keys.values().map(Json::str).collect())),
+        ]);
+        let relay = RelayReader {
+            bridge: ServiceId::StormApp(ext.app),
+            request: Json::Array(vec![Json::str("REQ"), Json::str(SUBSCRIPTION_ID), filter])
+                .to_string(),
+            hashtags: ext.mappings.values().cloned().collect(),
+            authors: ext.keys.values().cloned().collect(),
+            seen: Arc::new(Mutex::new(VecDeque::new())),
+            rpc_endpoint: config.rpc_endpoint.clone(),
+            chat_endpoint: config.chat_endpoint.clone(),
+        };
+        let relays = ext
+            .relays
+            .into_iter()
+            .map(|url| {
+                let slot = RelaySlot::default();
+                let (reader, thread_url, thread_slot) = (relay.clone(), url.clone(), slot.clone());
+                thread::spawn(move || reader.run(thread_url, thread_slot));
+                (url, slot)
+            })
+            .collect();
+
+        info!("Nostr bridge runtime started successfully");
+
+        Ok(Self {
+            app: ext.app,
+            keypair,
+            relays,
+            mappings: ext.mappings,
+            keys: ext.keys,
+            peers: ext.peers.into_iter().collect(),
+        })
+    }
+}
+
+/// Reader of a relay passing the mirrored notes to the bridge daemon.
+#[derive(Clone, Debug)]
+struct RelayReader {
+    bridge: ServiceId,
+    /// Subscription request sent to the relay
+    request: String,
+    hashtags: BTreeSet<String>,
+    authors: BTreeSet<String>,
+    /// Ids of the recently mirrored notes, shared by the readers of all relays
+    seen: Arc<Mutex<VecDeque<String>>>,
+    rpc_endpoint: ServiceAddr,
+    chat_endpoint: ServiceAddr,
+}
+
+impl RelayReader {
+    fn run(self, url: String, slot: RelaySlot) {
+        let mut client = None;
+        loop {
+            let (writer, mut reader) = match ws::connect(&url) {
+                Ok(conn) => conn,
+                Err(err) => {
+                    warn!("Unable to connect to Nostr relay {}: {}", url, err);
+                    thread::sleep(Duration::from_secs(RECONNECT_DELAY));
+                    continue;
+                }
+            };
+            info!("Connected to Nostr relay {}", url);
+            if let Err(err) = writer.send(&self.request) {
+                warn!("Unable to subscribe to Nostr relay {}: {}", url, err);
+                continue;
+            }
+            *slot.lock().expect("relay lock is poisoned") = Some(writer);
+
+            loop {
+                let text = match reader.recv() {
+                    Ok(text) => text,
+                    Err(err) => {
+                        warn!("Connection to Nostr relay {} is lost: {}", url, err);
+                        break;
+                    }
+                };
+                let msg = match self.mirrored(&text) {
+                    Some(msg) => msg,
+                    None => continue,
+                };
+                if client.is_none() {
+                    match storm_rpc::Client::with(
+                        self.rpc_endpoint.clone(),
+                        self.chat_endpoint.clone(),
+                        s!("nostrd"),
+                    ) {
+                        Ok(c) => client = Some(c),
+                        Err(err) => {
+                            warn!("Unable to connect to RPC interface: {}", err);
+                            continue;
+                        }
+                    }
+                }
+                if let Some(Err(err)) =
+                    client.as_mut().map(|client| client.bridge_inbound(self.bridge.clone(), msg))
+                {
+                    warn!("Unable to pass Nostr note to the bridge: {}", err);
+                    client = None;
+                }
+            }
+
+            *slot.lock().expect("relay lock is poisoned") = None;
+            thread::sleep(Duration::from_secs(RECONNECT_DELAY));
+        }
+    }
+
+    /// Parses relay message, returning note which should be mirrored into Storm.
+    fn mirrored(&self, text: &str) -> Option<BridgeMsg> {
+        let json = Json::parse(text).ok()?;
+        let items = json.as_array()?;
+        match (items.first()?.as_str()?, items.get(1)?.as_str()?) {
+            ("EVENT", SUBSCRIPTION_ID) => {}
+            ("NOTICE", notice) => {
+                info!("Nostr relay notice: {}", notice);
+                return None;
+            }
+            _ => return None,
+        }
+        let event = Event::from_json(items.get(2)?)?;
+        if event.kind != NOSTR_KIND_TEXT_NOTE || !self.authors.contains(&event.pubkey) {
+            return None;
+        }
+        let hashtag = event
+            .tag_values("t")
+            .map(str::to_lowercase)
+            .find(|hashtag| self.hashtags.contains(hashtag))?;
+        if !event.verify() {
+            warn!("Nostr note {} has invalid signature", event.id);
+            return None;
+        }
+        let mut seen = self.seen.lock().expect("seen notes lock is poisoned");
+        if seen.contains(&event.id) {
+            return None;
+        }
+        if seen.len() >= NOSTR_SEEN_LIMIT {
+            seen.pop_front();
+        }
+        seen.push_back(event.id);
+        Some(BridgeMsg {
+            topic: hashtag,
+            payload: event.content.into_bytes(),
+        })
+    }
+}
+
+impl Responder for Runtime {}
+
+impl esb::Handler<ServiceBus> for Runtime {
+    type Request = BusMsg;
+    type Error = DaemonError;
+
+    fn identity(&self) -> ServiceId { ServiceId::StormApp(self.app) }
+
+    fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
+        thread::sleep(Duration::from_millis(100));
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        self.send_ext(endpoints, None, ExtMsg::RegisterApp(self.app))?;
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        endpoints: &mut EndpointList<ServiceBus>,
+        bus_id: ServiceBus,
+        source: ServiceId,
+        request: Self::Request,
+    ) -> Result<(), Self::Error> {
+        match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                self.handle_storm(endpoints, msg)
+            }
+            (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
+                self.handle_rpc(endpoints, client_id, msg)
+            }
+            (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
+            (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        }
+    }
+
+    fn handle_err(
+        &mut self,
+        _endpoints: &mut EndpointList<ServiceBus>,
+        _error: Error<ServiceId>,
+    ) -> Result<(), Self::Error> {
+        // We do nothing and do not propagate error; it's already being reported
+        // with `error!` macro by the controller. If we propagate error here
+        // this will make whole daemon panic
+        Ok(())
+    }
+}
+
+impl Runtime {
+    fn handle_storm(
+        &mut self,
+        endpoints: &mut Endpoints,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let topic = TopicId::from_wire(data.parent_id);
+                if let Some(hashtag) = self.mappings.get(&topic) {
+                    self.publish(remote_id, hashtag.clone(), data.body);
+                }
+            }
+
+            ExtMsg::Negotiate(_) => {}
+
+            ExtMsg::Heartbeat(nonce) => {
+                self.send_ext(endpoints, None, ExtMsg::Heartbeat(nonce))?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the Storm interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        message: RpcMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            RpcMsg::BridgeInbound(msg) => {
+                self.post(endpoints, msg);
+                self.send_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_ctl(
+        &mut self,
+        _endpoints: &mut Endpoints,
+        _source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        error!("Request is not supported by the CTL interface");
+        Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &message))
+    }
+
+    /// Publishes reply to a Storm topic to the relays as a note tagged with the mapped hashtag.
+    fn publish(&mut self, remote_id: NodeId, hashtag: String, body: Vec<u8>) {
+        let content = match String::from_utf8(body) {
+            Ok(content) => content,
+            Err(_) => {
+                debug!("Message from {} is not a text and is not mirrored to Nostr", remote_id);
+                return;
+            }
+        };
+        let mut tags =
+            vec![vec![s!("t"), hashtag], vec![NOSTR_TAG_STORM.to_owned(), remote_id.to_string()]];
+        if let Some(pubkey) = self.keys.get(&remote_id) {
+            tags.push(vec![s!("p"), pubkey.clone()]);
+        }
+        let event = Event::text_note(&self.keypair, unix_time(), tags, content);
+        let request = Json::Array(vec![Json::str("EVENT"), event.to_json()]).to_string();
+        debug!("Mirroring message from {} to Nostr as note {}", remote_id, event.id);
+        for (url, slot) in &self.relays {
+            let res = match &*slot.lock().expect("relay lock is poisoned") {
+                Some(writer) => writer.send(&request),
+                None => {
+                    warn!("Nostr relay {} is not connected; note {} is skipped", url, event.id);
+                    continue;
+                }
+            };
+            if let Err(err) = res {
+                warn!("Unable to publish note {} to Nostr relay {}: {}", event.id, url, err);
+            }
+        }
+    }
+
+    /// Posts note mirrored from Nostr to the Storm peers as a reply to the mapped topic.
+    fn post(&mut self, endpoints: &mut Endpoints, msg: BridgeMsg) {
+        let topic = match self.mappings.iter().find(|(_, hashtag)| **hashtag == msg.topic) {
+            Some((topic, _)) => *topic,
+            None => {
+                warn!("Note with unmapped hashtag {} is ignored", msg.topic);
+                return;
+            }
+        };
+        for remote_id in &self.peers {
+            let post = ExtMsg::Post(AddressedMsg {
+                remote_id: *remote_id,
+                data: Mesg {
+                    parent_id: topic.to_wire(),
+                    body: msg.payload.clone(),
+                    container_ids: empty!(),
+                },
+            });
+            if let Err(err) = self.send_ext(endpoints, None, post) {
+                warn!("Unable to post Nostr note to {}: {}", remote_id, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Minimal WebSocket client over plain TCP, exchanging text messages only.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+const FLAG_FIN: u8 = 0x80;
+const FLAG_MASK: u8 = 0x80;
+
+/// Maximal size of a message accepted from the server.
+pub const WS_MESSAGE_LIMIT: usize = 1 << 20;
+
+/// Writing half of the WebSocket connection, which may be shared between threads.
+#[derive(Clone, Debug)]
+pub struct WsWriter {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+/// Reading half of the WebSocket connection.
+#[derive(Debug)]
+pub struct WsReader {
+    stream: BufReader<TcpStream>,
+    writer: WsWriter,
+}
+
+/// Connects to a `ws://` URL, returning the writing and the reading halves of the connection.
+pub fn connect(url: &str) -> Result<(WsWriter, WsReader), io::Error> {
+    let rest = url.strip_prefix("ws://").ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "only ws:// URLs are supported")
+    })?;
+    let (host, path) = match rest.find('/') {
+        Some(pos) => rest.split_at(pos),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') { host.to_owned() } else { format!("{}:80", host) };
+    let mut stream = TcpStream::connect(addr)?;
+
+    let key = base64(&rand::random::<[u8; 16]>());
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: \
+         Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+        path, host, key
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut status = String::new();
+    reader.read_line(&mut status)?;
+    if status.split_whitespace().nth(1) != Some("101") {
+        return Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!("WebSocket upgrade is refused: {}", status.trim()),
+        ));
+    }
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let writer = WsWriter {
+        stream: Arc::new(Mutex::new(stream)),
+    };
+    Ok((writer.clone(), WsReader {
+        stream: reader,
+        writer,
+    }))
+}
+
+impl WsWriter {
+    pub fn send(&self, text: &str) -> Result<(), io::Error> {
+        self.frame(OPCODE_TEXT, text.as_bytes())
+    }
+
+    fn frame(&self, opcode: u8, payload: &[u8]) -> Result<(), io::Error> {
+        let mut frame = vec![FLAG_FIN | opcode];
+        match payload.len() {
+            len if len < 126 => frame.push(FLAG_MASK | len as u8),
+            len if len <= u16::MAX as usize => {
+                frame.push(FLAG_MASK | 126);
+                frame.extend((len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(FLAG_MASK | 127);
+                frame.extend((len as u64).to_be_bytes());
+            }
+        }
+        // Client frames are always masked
+        let mask = rand::random::<[u8; 4]>();
+        frame.extend(mask);
+        frame.extend(payload.iter().enumerate().map(|(no, byte)| byte ^ mask[no % 4]));
+        let mut stream = self.stream.lock().expect("WebSocket connection lock is poisoned");
+        stream.write_all(&frame)
+    }
+}
+
+impl WsReader {
+    /// Waits for the next text message from the server, replying to pings.
+    pub fn recv(&mut self) -> Result<String, io::Error> {
+        let mut message = vec![];
+        loop {
+            let mut header = [0u8; 2];
+            self.stream.read_exact(&mut header)?;
+            let fin = header[0] & FLAG_FIN != 0;
+            let opcode = header[0] & 0x0F;
+            let len = match header[1] & 0x7F {
+                126 => {
+                    let mut len = [0u8; 2];
+                    self.stream.read_exact(&mut len)?;
+                    u16::from_be_bytes(len) as usize
+                }
+                127 => {
+                    let mut len = [0u8; 8];
+                    self.stream.read_exact(&mut len)?;
+                    u64::from_be_bytes(len) as usize
+                }
+                len => len as usize,
+            };
+            let mask = if header[1] & FLAG_MASK != 0 {
+                let mut mask = [0u8; 4];
+                self.stream.read_exact(&mut mask)?;
+                Some(mask)
+            } else {
+                None
+            };
+            if message.len() + len > WS_MESSAGE_LIMIT {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "WebSocket message is too large",
+                ));
+            }
+            let mut payload = vec![0u8; len];
+            self.stream.read_exact(&mut payload)?;
+            if let Some(mask) = mask {
+                payload.iter_mut().enumerate().for_each(|(no, byte)| *byte ^= mask[no % 4]);
+            }
+            match opcode {
+                OPCODE_PING => self.writer.frame(OPCODE_PONG, &payload)?,
+                OPCODE_PONG => {}
+                OPCODE_CLOSE => return Err(io::ErrorKind::ConnectionAborted.into()),
+                OPCODE_TEXT | OPCODE_CONTINUATION => {
+                    message.extend(payload);
+                    if fin {
+                        return String::from_utf8(message)
+                            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+                    }
+                }
+                // Binary messages are not used by the protocols we speak
+                _ => message.clear(),
+            }
+        }
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut s = String::new();
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (no, byte)| n | (*byte as u32) << (16 - 8 * no));
+        for no in 0..4 {
+            if no <= chunk.len() {
+                s.push(ALPHABET[(n >> (18 - 6 * no) & 0x3F) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
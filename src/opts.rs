@@ -17,6 +17,8 @@ use store_rpc::STORED_RPC_ENDPOINT;
 use storm_ext::{STORM_NODE_DATA_DIR, STORM_NODE_EXT_ENDPOINT};
 use storm_rpc::{CHATD_RPC_ENDPOINT, STORM_NODE_RPC_ENDPOINT};
 
+pub const LNP_NODE_MSG_ENDPOINT: &str = "{data_dir}/msg";
+
 pub const STORM_NODE_CTL_ENDPOINT: &str = "{data_dir}/ctl";
 
 pub const STORM_NODE_CONFIG: &str = "{data_dir}/stormd.toml";
@@ -60,6 +62,8 @@ pub struct Opts {
 
     /// Path for the configuration file.
     ///
+    /// Defaults to `stormd.toml` inside `--data-dir` directory, which is read only if it exists.
+    ///
     /// NB: Command-line options override configuration file values.
     #[clap(
         short,
@@ -85,10 +89,24 @@ pub struct Opts {
         short = 'M',
         long = "msg",
         env = "LNP_NODE_MSG_ENDPOINT",
+        default_value = LNP_NODE_MSG_ENDPOINT,
         value_hint = ValueHint::FilePath
     )]
     pub msg_endpoint: ServiceAddr,
 
+    /// ZMQ socket for the dedicated peer message bus carrying chunk payloads.
+    ///
+    /// If given, chunks are sent over this bus to the peers for which LNP node has negotiated
+    /// bulk data sub-channel, so the bulk data does not interleave with latency-sensitive
+    /// lightning messages on the main message bus.
+    #[clap(
+        long = "bulk-msg",
+        global = true,
+        env = "LNP_NODE_BULK_ENDPOINT",
+        value_hint = ValueHint::FilePath
+    )]
+    pub bulk_endpoint: Option<ServiceAddr>,
+
     /// ZMQ socket for internal service control bus.
     ///
     /// A user needs to specify this socket usually if it likes to distribute daemons
@@ -0,0 +1,48 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::time::Duration;
+
+/// Faults injected into the simulated network. Probabilities are applied independently to each
+/// delivered message.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FaultModel {
+    /// Probability of a message being lost.
+    pub loss: f64,
+    /// Probability of a message being delivered twice.
+    pub duplication: f64,
+    /// Probability of a message being delayed by up to [`FaultModel::max_delay`], which may
+    /// reorder it with the messages sent later.
+    pub reordering: f64,
+    /// Maximum extra delay of a reordered message.
+    pub max_delay: Duration,
+    /// Probability of the receiving node crashing before processing a message.
+    pub crash: f64,
+    /// Time after which a crashed node is restarted.
+    pub restart_after: Duration,
+}
+
+impl Default for FaultModel {
+    fn default() -> Self {
+        FaultModel {
+            loss: 0.0,
+            duplication: 0.0,
+            reordering: 0.0,
+            max_delay: Duration::from_millis(500),
+            crash: 0.0,
+            restart_after: Duration::from_secs(5),
+        }
+    }
+}
+
+impl FaultModel {
+    /// Fault model with no faults, where the network delivers all messages in order.
+    pub fn reliable() -> Self { FaultModel::default() }
+}
@@ -0,0 +1,27 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Deterministic simulation framework running protocol models over an in-memory network with a
+//! virtual clock and injectable faults.
+//!
+//! Models taking part in the simulation implement [`SimNode`]; they must not use system time or
+//! randomness other than provided by the harness, so that the same seed always reproduces the
+//! same run.
+//!
+//! The framework does not run the daemon runtimes: they are bound to the ZMQ service buses, the
+//! store service and the system clock, and none of them implements [`SimNode`]. Protocol changes
+//! are thus checked against their models, like the gossip model of the framework tests, rather
+//! than against the real message handling of the daemons.
+
+mod fault;
+mod network;
+
+pub use fault::FaultModel;
+pub use network::{Envelope, SimNode, SimReport, Simulation};
@@ -0,0 +1,352 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::FaultModel;
+
+/// Message sent by a simulated node.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Envelope<Id, M> {
+    pub to: Id,
+    pub msg: M,
+}
+
+/// Protocol model of a node taking part in a simulation. All methods receive current virtual time
+/// and return messages which the node sends in response.
+pub trait SimNode {
+    type Id: Copy + Ord + Debug;
+    type Msg: Clone + Debug;
+
+    /// Called when the node starts and each time it is restarted after a crash.
+    fn start(&mut self, now: Duration) -> Vec<Envelope<Self::Id, Self::Msg>>;
+
+    fn handle(
+        &mut self,
+        now: Duration,
+        from: Self::Id,
+        msg: Self::Msg,
+    ) -> Vec<Envelope<Self::Id, Self::Msg>>;
+
+    /// Called on a virtual timer tick, letting the node to execute periodic tasks.
+    fn tick(&mut self, _now: Duration) -> Vec<Envelope<Self::Id, Self::Msg>> { vec![] }
+
+    /// Drops all the state which is not persisted by the node.
+    fn crash(&mut self);
+}
+
+#[derive(Clone, Debug)]
+enum Event<Id, M> {
+    Deliver { from: Id, to: Id, msg: M },
+    Restart(Id),
+    Tick,
+}
+
+/// Statistics of a simulation run.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SimReport {
+    pub delivered: u64,
+    pub lost: u64,
+    pub duplicated: u64,
+    pub reordered: u64,
+    pub crashes: u64,
+    /// Messages addressed to unknown or crashed nodes.
+    pub undeliverable: u64,
+}
+
+impl Display for SimReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} delivered, {} lost, {} duplicated, {} reordered, {} undeliverable, {} crashes",
+            self.delivered,
+            self.lost,
+            self.duplicated,
+            self.reordered,
+            self.undeliverable,
+            self.crashes
+        )
+    }
+}
+
+/// Deterministic discrete-event simulation of multiple nodes.
+pub struct Simulation<N: SimNode> {
+    now: Duration,
+    latency: Duration,
+    tick: Duration,
+    faults: FaultModel,
+    rng: StdRng,
+    nodes: BTreeMap<N::Id, N>,
+    crashed: BTreeSet<N::Id>,
+    /// Scheduled events ordered by their virtual time and sequence number
+    events: BTreeMap<(Duration, u64), Event<N::Id, N::Msg>>,
+    seq: u64,
+    report: SimReport,
+}
+
+impl<N: SimNode> Simulation<N> {
+    /// Constructs simulation where messages are delivered with a given latency and nodes receive
+    /// timer ticks with a given period. Runs with the same seed are identical.
+    pub fn new(seed: u64, faults: FaultModel, latency: Duration, tick: Duration) -> Self {
+        let mut sim = Simulation {
+            now: Duration::ZERO,
+            latency,
+            tick,
+            faults,
+            rng: StdRng::seed_from_u64(seed),
+            nodes: empty!(),
+            crashed: empty!(),
+            events: empty!(),
+            seq: 0,
+            report: SimReport::default(),
+        };
+        sim.schedule(tick, Event::Tick);
+        sim
+    }
+
+    pub fn now(&self) -> Duration { self.now }
+
+    pub fn report(&self) -> SimReport { self.report }
+
+    pub fn node(&self, id: N::Id) -> Option<&N> { self.nodes.get(&id) }
+
+    pub fn add_node(&mut self, id: N::Id, mut node: N) {
+        let out = node.start(self.now);
+        self.nodes.insert(id, node);
+        self.send(id, out);
+    }
+
+    fn schedule(&mut self, delay: Duration, event: Event<N::Id, N::Msg>) {
+        self.seq += 1;
+        self.events.insert((self.now + delay, self.seq), event);
+    }
+
+    fn send(&mut self, from: N::Id, out: Vec<Envelope<N::Id, N::Msg>>) {
+        for Envelope { to, msg } in out {
+            if self.rng.gen_bool(self.faults.loss) {
+                self.report.lost += 1;
+                continue;
+            }
+            let mut delay = self.latency;
+            if self.rng.gen_bool(self.faults.reordering) {
+                let max = self.faults.max_delay.as_micros().max(1) as u64;
+                delay += Duration::from_micros(self.rng.gen_range(0..max));
+                self.report.reordered += 1;
+            }
+            if self.rng.gen_bool(self.faults.duplication) {
+                self.report.duplicated += 1;
+                self.schedule(delay, Event::Deliver {
+                    from,
+                    to,
+                    msg: msg.clone(),
+                });
+            }
+            self.schedule(delay, Event::Deliver { from, to, msg });
+        }
+    }
+
+    /// Processes next scheduled event. Returns `false` if there are no more events.
+    pub fn step(&mut self) -> bool {
+        let ((time, _), event) = match self.events.iter().next() {
+            Some((key, _)) => {
+                let key = *key;
+                (key, self.events.remove(&key).expect("key is just taken from the map"))
+            }
+            None => return false,
+        };
+        self.now = time;
+
+        match event {
+            Event::Tick => {
+                let ids = self.nodes.keys().copied().collect::<Vec<_>>();
+                for id in ids {
+                    if self.crashed.contains(&id) {
+                        continue;
+                    }
+                    let now = self.now;
+                    let out = self.nodes.get_mut(&id).expect("id is taken from the map").tick(now);
+                    self.send(id, out);
+                }
+                self.schedule(self.tick, Event::Tick);
+            }
+            Event::Restart(id) => {
+                self.crashed.remove(&id);
+                let now = self.now;
+                if let Some(node) = self.nodes.get_mut(&id) {
+                    let out = node.start(now);
+                    self.send(id, out);
+                }
+            }
+            Event::Deliver { from, to, msg } => {
+                if self.crashed.contains(&to) || !self.nodes.contains_key(&to) {
+                    self.report.undeliverable += 1;
+                    return true;
+                }
+                if self.rng.gen_bool(self.faults.crash) {
+                    self.report.crashes += 1;
+                    self.crashed.insert(to);
+                    self.nodes.get_mut(&to).expect("presence is checked").crash();
+                    self.schedule(self.faults.restart_after, Event::Restart(to));
+                    return true;
+                }
+                self.report.delivered += 1;
+                let now = self.now;
+                let out =
+                    self.nodes.get_mut(&to).expect("presence is checked").handle(now, from, msg);
+                self.send(to, out);
+            }
+        }
+        true
+    }
+
+    /// Runs the simulation until the virtual time reaches the deadline.
+    pub fn run_until(&mut self, deadline: Duration) -> SimReport {
+        while self.events.keys().next().map_or(false, |(time, _)| *time <= deadline) {
+            self.step();
+        }
+        self.now = self.now.max(deadline);
+        self.report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    enum Gossip {
+        Announce(u64),
+        Pull(u64),
+        Push(u64, Vec<u8>),
+    }
+
+    /// Node keeping containers on disk and announcing them to its neighbours on each tick,
+    /// pulling the announced containers it does not have yet.
+    struct GossipNode {
+        neighbours: Vec<u8>,
+        containers: BTreeMap<u64, Vec<u8>>,
+    }
+
+    impl GossipNode {
+        fn with(neighbours: Vec<u8>) -> Self {
+            GossipNode {
+                neighbours,
+                containers: empty!(),
+            }
+        }
+
+        fn announce(&self) -> Vec<Envelope<u8, Gossip>> {
+            self.containers
+                .keys()
+                .flat_map(|id| {
+                    self.neighbours.iter().map(move |to| Envelope {
+                        to: *to,
+                        msg: Gossip::Announce(*id),
+                    })
+                })
+                .collect()
+        }
+    }
+
+    impl SimNode for GossipNode {
+        type Id = u8;
+        type Msg = Gossip;
+
+        fn start(&mut self, _now: Duration) -> Vec<Envelope<u8, Gossip>> { self.announce() }
+
+        fn handle(&mut self, _now: Duration, from: u8, msg: Gossip) -> Vec<Envelope<u8, Gossip>> {
+            match msg {
+                Gossip::Announce(id) if !self.containers.contains_key(&id) => {
+                    vec![Envelope {
+                        to: from,
+                        msg: Gossip::Pull(id),
+                    }]
+                }
+                Gossip::Announce(_) => vec![],
+                Gossip::Pull(id) => match self.containers.get(&id) {
+                    Some(data) => vec![Envelope {
+                        to: from,
+                        msg: Gossip::Push(id, data.clone()),
+                    }],
+                    None => vec![],
+                },
+                Gossip::Push(id, data) => {
+                    self.containers.insert(id, data);
+                    vec![]
+                }
+            }
+        }
+
+        fn tick(&mut self, _now: Duration) -> Vec<Envelope<u8, Gossip>> { self.announce() }
+
+        // Containers are persisted, so nothing is lost
+        fn crash(&mut self) {}
+    }
+
+    /// Builds a chain of nodes where only the first one has the container.
+    fn chain(seed: u64, faults: FaultModel, len: u8) -> Simulation<GossipNode> {
+        let mut sim =
+            Simulation::new(seed, faults, Duration::from_millis(50), Duration::from_secs(1));
+        for id in 0..len {
+            let neighbours = [id.checked_sub(1), (id + 1 < len).then(|| id + 1)];
+            let mut node = GossipNode::with(neighbours.into_iter().flatten().collect());
+            if id == 0 {
+                node.containers.insert(1, b"container".to_vec());
+            }
+            sim.add_node(id, node);
+        }
+        sim
+    }
+
+    fn propagated(sim: &Simulation<GossipNode>, len: u8) -> bool {
+        (0..len).all(|id| sim.node(id).expect("node is added").containers.contains_key(&1))
+    }
+
+    #[test]
+    fn container_propagates() {
+        let mut sim = chain(0, FaultModel::reliable(), 5);
+        let report = sim.run_until(Duration::from_secs(10));
+        assert!(propagated(&sim, 5));
+        assert_eq!(report.lost, 0);
+        assert_eq!(report.crashes, 0);
+    }
+
+    #[test]
+    fn container_propagates_over_faulty_network() {
+        let faults = FaultModel {
+            loss: 0.2,
+            duplication: 0.1,
+            reordering: 0.2,
+            crash: 0.05,
+            ..FaultModel::default()
+        };
+        let mut sim = chain(7, faults, 5);
+        let report = sim.run_until(Duration::from_secs(120));
+        assert!(propagated(&sim, 5), "container is not propagated: {}", report);
+        assert!(report.lost > 0);
+    }
+
+    #[test]
+    fn runs_are_deterministic() {
+        let faults = FaultModel {
+            loss: 0.3,
+            reordering: 0.3,
+            ..FaultModel::default()
+        };
+        let first = chain(42, faults, 4).run_until(Duration::from_secs(30));
+        let second = chain(42, faults, 4).run_until(Duration::from_secs(30));
+        assert_eq!(first, second);
+    }
+}
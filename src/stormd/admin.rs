@@ -0,0 +1,138 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::time::{Duration, Instant};
+
+use internet2::addr::NodeId;
+use microservices::esb::ClientId;
+use storm::ContainerId;
+use storm_rpc::{AdminCommand, AdminOutcome};
+
+/// Type of the node-level app-opaque message carrying [`AdminRequest`].
+pub const ADMIN_REQUEST_MSG_TYPE: u16 = 0x0008;
+
+/// Type of the node-level app-opaque message carrying [`AdminReply`].
+pub const ADMIN_REPLY_MSG_TYPE: u16 = 0x0009;
+
+/// Time the managed node is given to answer the admin command.
+pub const ADMIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Admin command sent to the managed node.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct AdminRequest {
+    /// Number of the request, which is repeated in the reply
+    pub id: u64,
+    pub command: AdminCommand,
+}
+
+/// Outcome of the admin command sent back by the managed node.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct AdminReply {
+    pub id: u64,
+    pub outcome: AdminOutcome,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct Pending {
+    remote_id: NodeId,
+    client_id: ClientId,
+    deadline: Instant,
+}
+
+/// Management of the nodes of the same operator over the Storm protocol.
+///
+/// Node accepts admin commands only from the nodes given with `--admin`, which are
+/// authenticated by their node id with the transport, so the operator manages the nodes without
+/// exposing their RPC sockets. The commands are issued by the RPC clients of the operator node
+/// and relayed to the managed node; the client is answered once the managed node replies or
+/// the request times out. Timeouts are checked as the node handles other messages.
+#[derive(Clone, Debug)]
+pub struct AdminChannel {
+    admins: BTreeSet<NodeId>,
+    last_id: u64,
+    pending: BTreeMap<u64, Pending>,
+    /// Containers retrieved by the admin command, which are pinned once retrieved
+    replications: HashSet<ContainerId>,
+}
+
+impl AdminChannel {
+    pub fn with(admins: impl IntoIterator<Item = NodeId>) -> Self {
+        AdminChannel {
+            admins: admins.into_iter().collect(),
+            last_id: 0,
+            pending: empty!(),
+            replications: empty!(),
+        }
+    }
+
+    pub fn is_admin(&self, remote_id: NodeId) -> bool { self.admins.contains(&remote_id) }
+
+    /// Registers command issued by the client to the managed node, returning the request to
+    /// send.
+    pub fn issue(
+        &mut self,
+        remote_id: NodeId,
+        client_id: ClientId,
+        command: AdminCommand,
+    ) -> AdminRequest {
+        self.last_id += 1;
+        self.pending.insert(self.last_id, Pending {
+            remote_id,
+            client_id,
+            deadline: Instant::now() + ADMIN_TIMEOUT,
+        });
+        AdminRequest {
+            id: self.last_id,
+            command,
+        }
+    }
+
+    /// Returns client which has to be answered with the reply of the managed node, unless the
+    /// reply is unsolicited or arrives from another node.
+    pub fn answered(&mut self, remote_id: NodeId, id: u64) -> Option<ClientId> {
+        match self.pending.get(&id) {
+            Some(pending) if pending.remote_id == remote_id => {
+                self.pending.remove(&id).map(|pending| pending.client_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes requests which have timed out, returning the clients and the nodes which have
+    /// not answered them.
+    pub fn expire(&mut self) -> Vec<(ClientId, NodeId)> {
+        let now = Instant::now();
+        let expired =
+            self.pending.iter().filter(|(_, pending)| pending.deadline <= now).map(|(id, _)| *id);
+        expired
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|id| self.pending.remove(&id))
+            .map(|pending| (pending.client_id, pending.remote_id))
+            .collect()
+    }
+
+    /// Drops requests of the client which session is closed.
+    pub fn forget(&mut self, client_id: ClientId) {
+        self.pending.retain(|_, pending| pending.client_id != client_id);
+    }
+
+    /// Registers container which is retrieved by the admin command.
+    pub fn replicate(&mut self, container_id: ContainerId) {
+        self.replications.insert(container_id);
+    }
+
+    /// Detects whether the retrieved container was requested by the admin command and has to
+    /// be pinned.
+    pub fn replicated(&mut self, container_id: ContainerId) -> bool {
+        self.replications.remove(&container_id)
+    }
+}
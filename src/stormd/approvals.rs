@@ -0,0 +1,190 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use internet2::addr::NodeId;
+use storm_rpc::{
+    AdminAction, Approval, ApprovalError, AuditEvent, AuditRecord, Proposal, ProposalInfo,
+};
+
+use super::journal::Journal;
+use super::unix_time;
+
+/// Name of the file inside the data directory keeping the audit log of the admin actions.
+pub const STORM_NODE_AUDIT_FILE: &str = "audit.wal";
+
+/// Errors registering [`Approval`].
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ApprovalRejection {
+    /// no pending proposal {0:#018x}
+    UnknownProposal(u64),
+
+    /// key {0} is not configured as an approver of the node
+    NotApprover(NodeId),
+
+    #[from]
+    #[display(inner)]
+    Invalid(ApprovalError),
+
+    /// unable to record the approval in the audit log: {0}
+    #[from]
+    Audit(strict_encoding::Error),
+}
+
+/// Progress of a proposal after an approval is registered.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Approved {
+    /// Number of approvals still missing.
+    Pending(u8),
+    /// Proposal has collected enough approvals and has to be executed.
+    Complete(Proposal),
+}
+
+/// Approval mode of a node co-operated by several operators.
+///
+/// When the threshold is set with `--approvals`, sensitive admin actions requested over RPC are
+/// not executed immediately but turned into proposals, which are executed once signed by the
+/// given number of the keys configured with `--approver`. Every step of a proposal is appended
+/// to the audit log, from which the pending proposals are also restored when the node starts.
+#[derive(Debug)]
+pub struct Approvals {
+    approvers: BTreeSet<NodeId>,
+    threshold: u8,
+    pending: BTreeMap<u64, ProposalInfo>,
+    audit: Vec<AuditRecord>,
+    journal: Journal<AuditRecord>,
+}
+
+impl Approvals {
+    pub fn load(
+        data_dir: &Path,
+        approvers: impl IntoIterator<Item = NodeId>,
+        threshold: u8,
+    ) -> Result<Self, strict_encoding::Error> {
+        let (journal, audit) = Journal::<AuditRecord>::open(&data_dir.join(STORM_NODE_AUDIT_FILE))?;
+        let mut pending = BTreeMap::<u64, ProposalInfo>::new();
+        for record in &audit {
+            match &record.event {
+                AuditEvent::Proposed => {
+                    pending.insert(record.proposal_id, ProposalInfo {
+                        proposal: Proposal {
+                            id: record.proposal_id,
+                            action: record.action.clone(),
+                            timestamp: record.timestamp,
+                        },
+                        approvers: empty!(),
+                        threshold,
+                    });
+                }
+                AuditEvent::Approved(approver) => {
+                    if let Some(info) = pending.get_mut(&record.proposal_id) {
+                        info.approvers.insert(*approver);
+                    }
+                }
+                AuditEvent::Executed(_) | AuditEvent::Failed(_) => {
+                    pending.remove(&record.proposal_id);
+                }
+            }
+        }
+        Ok(Approvals {
+            approvers: approvers.into_iter().collect(),
+            threshold,
+            pending,
+            audit,
+            journal,
+        })
+    }
+
+    /// Detects whether the sensitive admin actions have to be approved before execution.
+    pub fn is_required(&self) -> bool { self.threshold > 0 }
+
+    pub fn threshold(&self) -> u8 { self.threshold }
+
+    fn record(
+        &mut self,
+        proposal: &Proposal,
+        event: AuditEvent,
+    ) -> Result<(), strict_encoding::Error> {
+        let record = AuditRecord {
+            no: self.audit.len() as u64 + 1,
+            timestamp: unix_time(),
+            proposal_id: proposal.id,
+            action: proposal.action.clone(),
+            event,
+        };
+        self.journal.append(&record)?;
+        self.audit.push(record);
+        Ok(())
+    }
+
+    /// Registers admin action awaiting approvals.
+    pub fn propose(&mut self, action: AdminAction) -> Result<Proposal, strict_encoding::Error> {
+        let mut id = rand::random::<u64>();
+        while self.pending.contains_key(&id) {
+            id = rand::random();
+        }
+        let proposal = Proposal {
+            id,
+            action,
+            timestamp: unix_time(),
+        };
+        self.record(&proposal, AuditEvent::Proposed)?;
+        self.pending.insert(id, ProposalInfo {
+            proposal: proposal.clone(),
+            approvers: empty!(),
+            threshold: self.threshold,
+        });
+        Ok(proposal)
+    }
+
+    pub fn proposals(&self) -> Vec<ProposalInfo> { self.pending.values().cloned().collect() }
+
+    /// Verifies and registers approval. Repeated approvals of the same key are not counted.
+    pub fn approve(&mut self, approval: &Approval) -> Result<Approved, ApprovalRejection> {
+        if !self.approvers.contains(&approval.approver) {
+            return Err(ApprovalRejection::NotApprover(approval.approver));
+        }
+        let info = self
+            .pending
+            .get(&approval.proposal_id)
+            .ok_or(ApprovalRejection::UnknownProposal(approval.proposal_id))?;
+        approval.verify(&info.proposal)?;
+        let proposal = info.proposal.clone();
+        if !info.approvers.contains(&approval.approver) {
+            self.record(&proposal, AuditEvent::Approved(approval.approver))?;
+        }
+        let info = self.pending.get_mut(&proposal.id).expect("pending proposal");
+        info.approvers.insert(approval.approver);
+        let approved = info.approvers.len() as u8;
+        if approved < self.threshold {
+            return Ok(Approved::Pending(self.threshold - approved));
+        }
+        self.pending.remove(&proposal.id);
+        Ok(Approved::Complete(proposal))
+    }
+
+    /// Records outcome of the proposal execution in the audit log.
+    pub fn executed(
+        &mut self,
+        proposal: &Proposal,
+        outcome: Result<String, String>,
+    ) -> Result<(), strict_encoding::Error> {
+        let event = match outcome {
+            Ok(details) => AuditEvent::Executed(details),
+            Err(err) => AuditEvent::Failed(err),
+        };
+        self.record(proposal, event)
+    }
+
+    pub fn audit(&self) -> &[AuditRecord] { &self.audit }
+}
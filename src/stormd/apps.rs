@@ -0,0 +1,135 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use storm::StormApp;
+
+/// Interval between the heartbeats sent to the registered apps.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time after the last message of a registered app after which the app is considered gone and
+/// is unregistered.
+pub const APP_LIVENESS_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Route of the messages received from the remote peers for an app.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum AppRoute {
+    /// The app is registered and alive, so the messages are delivered to it.
+    Registered,
+
+    /// The app was registered but has unregistered or stopped responding, so the messages are
+    /// declined.
+    Departed,
+
+    /// The app has never registered; the messages are delivered to the app bus.
+    Unknown,
+}
+
+/// Routing table of the apps registered with the node, tracking their liveness with the
+/// heartbeats.
+#[derive(Clone, Debug)]
+pub struct AppRoutes {
+    /// Registered apps with the time of their last message
+    registered: BTreeMap<StormApp, Instant>,
+    departed: BTreeSet<StormApp>,
+    /// Apps unregistered on the liveness timeout, which are registered again once they respond
+    expired: BTreeSet<StormApp>,
+    last_heartbeat: Instant,
+    nonce: u64,
+}
+
+impl Default for AppRoutes {
+    fn default() -> Self {
+        AppRoutes {
+            registered: empty!(),
+            departed: empty!(),
+            expired: empty!(),
+            last_heartbeat: Instant::now(),
+            nonce: 0,
+        }
+    }
+}
+
+impl AppRoutes {
+    pub fn register(&mut self, app: StormApp) {
+        self.departed.remove(&app);
+        self.expired.remove(&app);
+        self.registered.insert(app, Instant::now());
+    }
+
+    /// Unregisters the app, returning whether it was registered.
+    pub fn unregister(&mut self, app: StormApp) -> bool {
+        if self.registered.remove(&app).is_none() {
+            return false;
+        }
+        self.departed.insert(app);
+        true
+    }
+
+    /// Records message received from the app, registering it again if it was unregistered on
+    /// the liveness timeout. Returns whether the app was registered again.
+    pub fn seen(&mut self, app: StormApp) -> bool {
+        if let Some(last_seen) = self.registered.get_mut(&app) {
+            *last_seen = Instant::now();
+            return false;
+        }
+        if !self.expired.contains(&app) {
+            return false;
+        }
+        self.register(app);
+        true
+    }
+
+    pub fn route(&self, app: StormApp) -> AppRoute {
+        if self.registered.contains_key(&app) {
+            AppRoute::Registered
+        } else if self.departed.contains(&app) {
+            AppRoute::Departed
+        } else {
+            AppRoute::Unknown
+        }
+    }
+
+    pub fn contains(&self, app: StormApp) -> bool { self.registered.contains_key(&app) }
+
+    pub fn registered(&self) -> impl Iterator<Item = StormApp> + '_ {
+        self.registered.keys().copied()
+    }
+
+    pub fn to_set(&self) -> BTreeSet<StormApp> { self.registered().collect() }
+
+    /// Unregisters apps which have not responded within the liveness timeout, returning them.
+    pub fn expire(&mut self) -> Vec<StormApp> {
+        let expired = self
+            .registered
+            .iter()
+            .filter(|(_, last_seen)| last_seen.elapsed() > APP_LIVENESS_TIMEOUT)
+            .map(|(app, _)| *app)
+            .collect::<Vec<_>>();
+        for app in &expired {
+            self.unregister(*app);
+            self.expired.insert(*app);
+        }
+        expired
+    }
+
+    /// Returns nonce of the heartbeat which has to be sent to the registered apps, if it is due.
+    pub fn heartbeat_due(&mut self) -> Option<u64> {
+        if self.registered.is_empty() || self.last_heartbeat.elapsed() < HEARTBEAT_INTERVAL {
+            return None;
+        }
+        self.last_heartbeat = Instant::now();
+        self.nonce += 1;
+        Some(self.nonce)
+    }
+}
@@ -0,0 +1,73 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm_rpc::SignedAttestation;
+use strict_encoding::StrictDecode;
+
+use super::journal::checkpoint;
+
+/// Name of the file inside the data directory keeping the attestations of the remote peers.
+pub const STORM_NODE_ATTESTATIONS_FILE: &str = "attestations.dat";
+
+/// Type of the node-level app-opaque message carrying [`SignedAttestation`]. Node-level messages
+/// are sent on behalf of [`storm::StormApp::System`].
+pub const ATTESTATION_MSG_TYPE: u16 = 0x0001;
+
+/// Attestations of the software run by the remote peers, persisted in the data directory.
+///
+/// The node sends its own attestation to each remote peer once after the node start, when it
+/// first receives a message from the peer.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Attestations {
+    path: PathBuf,
+    peers: BTreeMap<NodeId, SignedAttestation>,
+    /// Peers which were sent the attestation of this node
+    sent: HashSet<NodeId>,
+}
+
+impl Attestations {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_ATTESTATIONS_FILE);
+        let peers =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(Attestations {
+            path,
+            peers,
+            sent: empty!(),
+        })
+    }
+
+    /// Detects whether the attestation has to be sent to the peer, marking it as sent.
+    pub fn is_due(&mut self, remote_id: NodeId) -> bool { self.sent.insert(remote_id) }
+
+    /// Keeps attestation received from the peer, if its signature is valid.
+    pub fn register(
+        &mut self,
+        remote_id: NodeId,
+        attestation: SignedAttestation,
+    ) -> Result<(), strict_encoding::Error> {
+        if let Err(err) = attestation.verify() {
+            warn!("Peer {} has sent invalid attestation: {}", remote_id, err);
+            return Ok(());
+        }
+        info!("Peer {} attests {}", remote_id, attestation.attestation);
+        self.peers.insert(remote_id, attestation);
+        checkpoint(&self.path, &self.peers)
+    }
+
+    pub fn get(&self, remote_id: NodeId) -> Option<&SignedAttestation> {
+        self.peers.get(&remote_id)
+    }
+}
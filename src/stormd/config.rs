@@ -8,17 +8,103 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use amplify::Wrapper;
+#[cfg(feature = "server")]
+use clap::ArgMatches;
+use internet2::addr::{NodeId, ServiceAddr};
+use storm::{MesgId, StormApp};
+use storm_ext::EventClass;
+use storm_rpc::{RateLimit, TopicId};
+
+#[cfg(feature = "server")]
+use super::EvictionPolicy;
 #[cfg(feature = "server")]
 use super::Opts;
+use super::{CachePolicy, MetricsConfig, ReplicationWindow, StorageQuota, WebhookConfig};
+#[cfg(feature = "server")]
+use crate::error::ConfigFileError;
 #[cfg(feature = "server")]
 use crate::opts::Options;
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Config {
     pub run_chat: bool,
+    pub run_mail: bool,
+    pub run_board: bool,
     pub run_downpour: bool,
     /// Indicates whether deamons should be spawned as threads (true) or as child processes (false)
     pub threaded: bool,
+    /// Policy for caching containers on behalf of other peers
+    pub cache: CachePolicy,
+    /// Message buses of the peer connection services in addition to the main one
+    pub peer_msg_endpoints: Vec<ServiceAddr>,
+    /// Recording of the metrics snapshots into the data directory, if enabled
+    pub metrics: Option<MetricsConfig>,
+    /// Address of the HTTP endpoint exporting the node metrics, if enabled
+    pub metrics_endpoint: Option<SocketAddr>,
+    /// Classes of the events which are batched into digests
+    pub event_digest: BTreeSet<EventClass>,
+    /// Interval between the event digests, in seconds
+    pub digest_interval: u64,
+    /// Webhooks receiving messages of the apps
+    pub webhooks: Vec<WebhookConfig>,
+    /// Secret used to sign the webhook requests
+    pub webhook_secret: Option<String>,
+    /// Program deciding on the messages received from the remote peers
+    pub policy: Option<PathBuf>,
+    /// LNP node id of this node, for which the stamps of the unknown peers have to be minted
+    pub node_id: Option<NodeId>,
+    /// Work required from the proof-of-work stamps of the unknown peers
+    pub pow_difficulty: Option<u8>,
+    /// Work of the proof-of-work stamps presented to the remote peers
+    pub pow_stamp: Option<u8>,
+    /// Whether the attestation of the node software is sent to the remote peers
+    pub attest: bool,
+    /// Whether the node is a read-only replica refusing writes
+    pub read_only: bool,
+    /// Whether the node keeps replicas of the containers of the remote peers
+    pub storage_peer: bool,
+    /// Whether the container index is rebuilt from the store on the start
+    pub rebuild_index: bool,
+    /// Daily hours during which the bulk replication runs
+    pub replication_window: Option<ReplicationWindow>,
+    /// Replication partners with which the pins and the topics are reconciled
+    pub replicas: Vec<NodeId>,
+    /// Nodes of the operator allowed to issue admin commands to this node
+    pub admins: Vec<NodeId>,
+    /// Operator keys which may approve the sensitive admin actions
+    pub approvers: Vec<NodeId>,
+    /// Number of approvals required to execute the sensitive admin actions, if any
+    pub approval_threshold: u8,
+    /// Interval between the reconciliations with the replication partners, in seconds
+    pub anti_entropy_interval: u64,
+    /// Node traffic, in bytes per second, above which the bulk replication is paused
+    pub replication_bandwidth: Option<u64>,
+    /// Socket on which the node events are published to the subscribed clients
+    pub notify_endpoint: Option<ServiceAddr>,
+    /// Number of differences the topic sketches are sized for, if topics are reconciled with
+    /// sketches
+    pub topic_sketch: Option<u16>,
+    /// Maximum number of unfinished background jobs of a single RPC client
+    pub max_client_jobs: u16,
+    /// Storage quotas and the eviction policy
+    pub quota: StorageQuota,
+    /// Number of seconds a chunk must stay unreferenced before it is collected
+    pub gc_grace: u64,
+    /// Interval between automatic garbage collections, in seconds; zero disables them
+    pub gc_interval: u64,
+    /// Number of spam declines after which a remote peer is banned; zero disables the bans
+    pub spam_ban_threshold: u32,
+    /// Limit of the rate of the chunks sent to all the remote peers together
+    pub rate_limit: RateLimit,
+    /// Limit of the rate of the chunks sent to each of the remote peers
+    pub peer_rate_limit: RateLimit,
+    /// Memory budget of the node, in bytes, above which it sheds the load
+    pub memory_budget: Option<u64>,
 }
 
 #[cfg(feature = "server")]
@@ -30,17 +116,94 @@ impl Options for Opts {
     fn config(&self) -> Self::Conf {
         Config {
             run_chat: self.chat,
+            run_mail: self.mail,
+            run_board: self.board,
             run_downpour: self.downpour,
             threaded: self.threaded_daemons,
+            cache: CachePolicy {
+                cache_size: self.cache_size,
+                admission_threshold: self.cache_admission,
+                auto_pin_threshold: self.auto_pin,
+            },
+            peer_msg_endpoints: self.peer_msg_endpoints.clone(),
+            metrics: self.metrics_log.as_ref().map(|format| MetricsConfig {
+                format: format.parse().expect("metrics format is validated by clap"),
+                interval: self.metrics_interval,
+            }),
+            metrics_endpoint: self.metrics_endpoint,
+            event_digest: self
+                .event_digest
+                .iter()
+                .map(|class| class.parse().expect("event class is validated by clap"))
+                .collect(),
+            digest_interval: self.digest_interval,
+            webhooks: self.webhook.iter().map(|spec| webhook_config(spec)).collect(),
+            webhook_secret: self.webhook_secret.clone(),
+            policy: self.policy.as_ref().map(PathBuf::from),
+            node_id: self.node_id,
+            pow_difficulty: self.pow_difficulty,
+            pow_stamp: self.pow_stamp,
+            attest: self.attest,
+            read_only: self.read_only,
+            storage_peer: self.storage_peer,
+            rebuild_index: self.rebuild_index,
+            replication_window: self
+                .replication_window
+                .as_ref()
+                .map(|window| window.parse().expect("replication window is validated by clap")),
+            replicas: self.replica.clone(),
+            admins: self.admin.clone(),
+            approvers: self.approver.clone(),
+            approval_threshold: self.approvals,
+            anti_entropy_interval: self.anti_entropy_interval,
+            replication_bandwidth: self.replication_bandwidth,
+            notify_endpoint: self.notify_endpoint.clone(),
+            topic_sketch: self.topic_sketch,
+            max_client_jobs: self.max_client_jobs,
+            quota: StorageQuota {
+                total: self.quota,
+                per_peer: self.peer_quota,
+                eviction: self.eviction.parse().expect("eviction policy is validated by clap"),
+            },
+            gc_grace: self.gc_grace,
+            gc_interval: self.gc_interval,
+            spam_ban_threshold: self.spam_ban,
+            rate_limit: RateLimit {
+                bytes_per_sec: self.rate_limit_bytes,
+                chunks_per_sec: self.rate_limit_chunks,
+            },
+            peer_rate_limit: RateLimit {
+                bytes_per_sec: self.peer_rate_limit_bytes,
+                chunks_per_sec: self.peer_rate_limit_chunks,
+            },
+            memory_budget: self.memory_budget,
         }
     }
 }
 
+#[cfg(feature = "server")]
+fn webhook_config(spec: &str) -> WebhookConfig {
+    let (target, url) = spec.split_once('=').expect("webhook is validated by clap");
+    let (app, topic) = match target.split_once('/') {
+        Some((app, topic)) => (app, Some(topic)),
+        None => (target, None),
+    };
+    WebhookConfig {
+        app: StormApp::from(app.parse::<u16>().expect("webhook app is validated by clap")),
+        topic: topic.map(|topic| {
+            let id = topic.parse().expect("webhook topic is validated by clap");
+            TopicId::from_wire(MesgId::from_inner(id))
+        }),
+        url: url.to_owned(),
+    }
+}
+
 impl From<crate::Config<Config>> for crate::Config<()> {
     fn from(config: crate::Config<Config>) -> Self {
         crate::Config {
             data_dir: config.data_dir,
             msg_endpoint: config.msg_endpoint,
+            bulk_endpoint: config.bulk_endpoint,
             ctl_endpoint: config.ctl_endpoint,
             rpc_endpoint: config.rpc_endpoint,
             ext_endpoint: config.ext_endpoint,
@@ -50,3 +213,37 @@ impl From<crate::Config<Config>> for crate::Config<()> {
         }
     }
 }
+
+#[cfg(feature = "server")]
+impl Opts {
+    /// Layers the configuration file under the command-line arguments and the environment
+    /// variables.
+    pub fn apply_config_file(&mut self, matches: &ArgMatches) -> Result<(), ConfigFileError> {
+        let mut file = match self.shared.apply_config_file(matches)? {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        if let Some(run) = file.take("run_chat", "chat", matches)? {
+            self.chat = run;
+        }
+        if let Some(run) = file.take("run_mail", "mail", matches)? {
+            self.mail = run;
+        }
+        if let Some(run) = file.take("run_board", "board", matches)? {
+            self.board = run;
+        }
+        if let Some(run) = file.take("run_downpour", "downpour", matches)? {
+            self.downpour = run;
+        }
+        if let Some(quota) = file.take("quota", "quota", matches)? {
+            self.quota = Some(quota);
+        }
+        if let Some(quota) = file.take("peer_quota", "peer_quota", matches)? {
+            self.peer_quota = Some(quota);
+        }
+        if let Some(policy) = file.take::<EvictionPolicy>("eviction", "eviction", matches)? {
+            self.eviction = policy.to_string();
+        }
+        Ok(())
+    }
+}
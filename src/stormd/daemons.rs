@@ -12,9 +12,66 @@ use std::process::Command;
 
 use microservices::error::BootstrapError;
 use microservices::{DaemonHandle, Launcher, LauncherError};
+use storm_rpc::ServiceId;
 
 use super::Runtime;
-use crate::{chatd, downpourd, stormd, transferd, LaunchError};
+use crate::bus::set_threaded;
+use crate::{boardd, chatd, downpourd, maild, stormd, transferd, LaunchError};
+
+/// Stormd-specific flags which must not be passed to the child daemons
+const STORMD_ARGS: &[&str] = &[
+    "--threaded",
+    "--chat",
+    "--mail",
+    "--board",
+    "--downpour",
+    "--attest",
+    "--rebuild-index",
+    "--read-only",
+    "--storage-peer",
+];
+
+/// Stormd-specific arguments taking a value which must not be passed to the child daemons
+const STORMD_VALUE_ARGS: &[&str] = &[
+    "--cache-size",
+    "--cache-admission",
+    "--auto-pin",
+    "--peer-msg",
+    "--metrics-log",
+    "--metrics-interval",
+    "--metrics-endpoint",
+    "--event-digest",
+    "--digest-interval",
+    "--webhook",
+    "--webhook-secret",
+    "--policy",
+    "--pow-difficulty",
+    "--pow-stamp",
+    "--replication-window",
+    "--replica",
+    "--admin",
+    "--approver",
+    "--approvals",
+    "--anti-entropy-interval",
+    "--replication-bandwidth",
+    "--notify",
+    "--topic-sketch",
+    "--max-client-jobs",
+    "--quota",
+    "--peer-quota",
+    "--eviction",
+    "--gc-grace",
+    "--gc-interval",
+    "--spam-ban",
+    "--rate-limit-bytes",
+    "--rate-limit-chunks",
+    "--peer-rate-limit-bytes",
+    "--peer-rate-limit-chunks",
+    "--memory-budget",
+];
+
+/// Stormd argument which is passed to the transfer daemon only
+const TRANSFERD_VALUE_ARG: &str = "--peer-msg";
 
 /// Daemons that can be launched by lnpd
 #[derive(Clone, Eq, PartialEq, Debug, Display)]
@@ -25,35 +82,87 @@ pub enum Daemon {
     #[display("chatd")]
     Chatd,
 
+    #[display("maild")]
+    Maild,
+
+    #[display("boardd")]
+    Boardd,
+
     #[display("downpourd")]
     Downpourd,
 }
 
+impl Daemon {
+    /// Returns identity of the daemon on the message buses. Transfer daemons are launched with
+    /// identities assigned on their start.
+    pub fn service_id(&self) -> Option<ServiceId> {
+        match self {
+            Daemon::Transferd => None,
+            Daemon::Chatd => Some(ServiceId::chatd()),
+            Daemon::Maild => Some(ServiceId::maild()),
+            Daemon::Boardd => Some(ServiceId::boardd()),
+            Daemon::Downpourd => Some(ServiceId::downpourd()),
+        }
+    }
+
+    /// Detects whether the service is a daemon launched by stormd.
+    pub fn is_child(service_id: &ServiceId) -> bool {
+        matches!(service_id, ServiceId::Transfer(_))
+            || [ServiceId::chatd(), ServiceId::maild(), ServiceId::boardd(), ServiceId::downpourd()]
+                .contains(service_id)
+    }
+}
+
 impl Launcher for Daemon {
     type RunError = BootstrapError<LaunchError>;
-    type Config = crate::Config;
+    type Config = crate::Config<stormd::Config>;
 
     fn bin_name(&self) -> &'static str {
         match self {
             Daemon::Transferd => "transferd",
             Daemon::Chatd => "chatd",
+            Daemon::Maild => "maild",
+            Daemon::Boardd => "boardd",
             Daemon::Downpourd => "downpourd",
         }
     }
 
     fn cmd_args(&self, cmd: &mut Command) -> Result<(), LauncherError<Self>> {
-        cmd.args(std::env::args().skip(1).filter(|arg| {
-            !["--threaded", "--chat", "--downpour"].iter().any(|pat| arg.starts_with(pat))
-        }));
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if *self == Daemon::Transferd && arg.starts_with(TRANSFERD_VALUE_ARG) {
+                // Transfer daemon connects to the same peer connection services as stormd
+                if arg == TRANSFERD_VALUE_ARG {
+                    cmd.arg(arg);
+                    if let Some(value) = args.next() {
+                        cmd.arg(value);
+                    }
+                } else {
+                    cmd.arg(arg);
+                }
+                continue;
+            }
+            if STORMD_VALUE_ARGS.contains(&arg.as_str()) {
+                // Skipping the argument value as well
+                args.next();
+                continue;
+            }
+            if STORMD_ARGS.iter().chain(STORMD_VALUE_ARGS).any(|pat| arg.starts_with(pat)) {
+                continue;
+            }
+            cmd.arg(arg);
+        }
 
         Ok(())
     }
 
-    fn run_impl(self, config: crate::Config) -> Result<(), Self::RunError> {
+    fn run_impl(self, config: crate::Config<stormd::Config>) -> Result<(), Self::RunError> {
         match self {
-            Daemon::Transferd => transferd::run(config),
-            Daemon::Chatd => chatd::run(config),
-            Daemon::Downpourd => downpourd::run(config),
+            Daemon::Transferd => transferd::run(config.into()),
+            Daemon::Chatd => chatd::run(config.into()),
+            Daemon::Maild => maild::run(config.into()),
+            Daemon::Boardd => boardd::run(config.into()),
+            Daemon::Downpourd => downpourd::run(config.into()),
         }
     }
 }
@@ -65,7 +174,8 @@ impl Runtime {
         config: crate::Config<stormd::Config>,
     ) -> Result<DaemonHandle<Daemon>, LauncherError<Daemon>> {
         if self.config.ext.threaded {
-            daemon.thread_daemon(config.into())
+            set_threaded();
+            daemon.thread_daemon(config)
         } else {
             daemon.exec_daemon()
         }
@@ -0,0 +1,126 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use storm::StormApp;
+use storm_rpc::{Dictionary, COMPRESSION_THRESHOLD, DICT_COMPRESSION_THRESHOLD};
+
+use super::journal::write_atomic;
+
+/// Name of the directory inside the data directory keeping the compression dictionaries of the
+/// apps, as `<app code>.dict` files with the hex app code.
+pub const STORM_NODE_DICT_DIR: &str = "dictionaries";
+
+/// Number of the app messages sampled before the node learns the app dictionary.
+pub const DICT_TRAIN_SAMPLES: usize = 256;
+
+/// Compression dictionaries of the apps.
+///
+/// Dictionaries may be shipped as files in the [`STORM_NODE_DICT_DIR`] directory; for the apps
+/// without a shipped dictionary the node learns one from the small messages the app sends, and
+/// saves it to the same directory. The learned dictionaries are not re-trained: to learn a new
+/// dictionary the file has to be removed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Dictionaries {
+    dir: PathBuf,
+    dicts: BTreeMap<StormApp, Dictionary>,
+    /// Messages collected from the apps which do not have a dictionary yet
+    samples: BTreeMap<StormApp, Vec<Vec<u8>>>,
+    /// Apps which were sent their dictionary after negotiating dictionary compression
+    delivered: BTreeSet<StormApp>,
+}
+
+impl Dictionaries {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let dir = data_dir.join(STORM_NODE_DICT_DIR);
+        let mut dicts = BTreeMap::new();
+        if dir.exists() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("dict") {
+                    continue;
+                }
+                let app = match path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| u16::from_str_radix(stem, 16).ok())
+                {
+                    Some(code) => StormApp::from(code),
+                    None => {
+                        warn!("Ignoring dictionary file {} not named by an app", path.display());
+                        continue;
+                    }
+                };
+                let dict = Dictionary::with(&fs::read(&path)?);
+                debug!("Loaded {} for {}", dict, app);
+                dicts.insert(app, dict);
+            }
+        }
+        Ok(Dictionaries {
+            dir,
+            dicts,
+            samples: empty!(),
+            delivered: empty!(),
+        })
+    }
+
+    fn path(&self, app: StormApp) -> PathBuf {
+        self.dir.join(format!("{:04x}.dict", app.app_code()))
+    }
+
+    /// Returns dictionary of the app, if it was delivered to the app.
+    pub fn get(&self, app: StormApp) -> Option<&Dictionary> {
+        self.dicts.get(&app).filter(|_| self.delivered.contains(&app))
+    }
+
+    /// Returns dictionary of the app, even if the app does not know it yet.
+    pub fn known(&self, app: StormApp) -> Option<&Dictionary> { self.dicts.get(&app) }
+
+    /// Marks the dictionary as known (`true`) or unknown (`false`) to the app.
+    pub fn set_delivered(&mut self, app: StormApp, delivered: bool) {
+        if delivered {
+            self.delivered.insert(app);
+        } else {
+            self.delivered.remove(&app);
+        }
+    }
+
+    /// Records encoded message sent by the app, returning whether the app dictionary was learned
+    /// from the collected samples.
+    pub fn sample(&mut self, app: StormApp, data: &[u8]) -> Result<bool, strict_encoding::Error> {
+        if self.dicts.contains_key(&app)
+            || !(DICT_COMPRESSION_THRESHOLD..=COMPRESSION_THRESHOLD).contains(&data.len())
+        {
+            return Ok(false);
+        }
+        let samples = self.samples.entry(app).or_default();
+        samples.push(data.to_vec());
+        if samples.len() < DICT_TRAIN_SAMPLES {
+            return Ok(false);
+        }
+
+        let dict = Dictionary::train(samples);
+        self.samples.remove(&app);
+        fs::create_dir_all(&self.dir)?;
+        write_atomic(&self.path(app), dict.as_bytes())?;
+        info!("Learned {} for {}", dict, app);
+        self.dicts.insert(app, dict);
+        Ok(true)
+    }
+
+    /// Number of bytes of the collected samples.
+    pub fn sampled(&self) -> usize { self.samples.values().flatten().map(Vec::len).sum() }
+
+    /// Drops the collected samples, so the apps start collecting them anew.
+    pub fn shrink(&mut self) { self.samples.clear() }
+}
@@ -0,0 +1,123 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::time::{Duration, Instant};
+
+use bitcoin_hashes::{sha256, Hash};
+use internet2::addr::NodeId;
+use storm::{ContainerId, StormApp};
+use storm_rpc::TopicId;
+use strict_encoding::StrictEncode;
+
+/// Type of the node-level app-opaque message carrying [`ReplicaDigest`].
+pub const ENTROPY_DIGEST_MSG_TYPE: u16 = 0x0003;
+
+/// Type of the node-level app-opaque message carrying [`ReplicaSet`].
+pub const ENTROPY_SET_MSG_TYPE: u16 = 0x0004;
+
+/// Pinned containers and topic indexes of a node, which are reconciled with its replication
+/// partners.
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+pub struct ReplicaSet {
+    /// Pinned containers with the apps they belong to
+    pub pins: BTreeMap<ContainerId, StormApp>,
+    /// Topics kept by the apps
+    pub topics: BTreeMap<StormApp, BTreeSet<TopicId>>,
+    /// Whether the set is sent in reply to the set of the partner, and must not be answered
+    pub reply: bool,
+}
+
+/// Digest of [`ReplicaSet`], which is exchanged first, so the full sets are sent only when the
+/// partners diverge.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct ReplicaDigest(pub sha256::Hash);
+
+impl ReplicaSet {
+    pub fn digest(&self) -> ReplicaDigest {
+        let mut engine = sha256::Hash::engine();
+        self.pins.strict_encode(&mut engine).expect("in-memory set encoding");
+        self.topics.strict_encode(&mut engine).expect("in-memory set encoding");
+        ReplicaDigest(sha256::Hash::from_engine(engine))
+    }
+
+    /// Returns part of the other set which is absent from this set.
+    pub fn missing(&self, other: &ReplicaSet) -> ReplicaSet {
+        let pins = other
+            .pins
+            .iter()
+            .filter(|(container_id, _)| !self.pins.contains_key(*container_id))
+            .map(|(container_id, app)| (*container_id, *app))
+            .collect();
+        let topics = other
+            .topics
+            .iter()
+            .map(|(app, topics)| {
+                let ours = self.topics.get(app).cloned().unwrap_or_default();
+                (*app, topics.difference(&ours).copied().collect::<BTreeSet<_>>())
+            })
+            .filter(|(_, topics)| !topics.is_empty())
+            .collect();
+        ReplicaSet {
+            pins,
+            topics,
+            reply: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.pins.is_empty() && self.topics.is_empty() }
+}
+
+/// Periodic anti-entropy reconciliation with the replication partners.
+///
+/// Each interval the node sends the digest of its [`ReplicaSet`] to the partners. A partner with
+/// a different digest answers with its full set, and each side repairs the containers and topics
+/// it is missing, retrieving them from the other one. Reconciliation only adds pins and topics;
+/// containers unpinned on one of the partners are pinned again by the other one.
+#[derive(Clone, Debug)]
+pub struct AntiEntropy {
+    partners: BTreeSet<NodeId>,
+    interval: Duration,
+    last: Option<Instant>,
+    /// Containers retrieved from the partners, which are pinned once retrieved
+    repairs: HashSet<ContainerId>,
+}
+
+impl AntiEntropy {
+    pub fn with(partners: impl IntoIterator<Item = NodeId>, interval: Duration) -> Self {
+        AntiEntropy {
+            partners: partners.into_iter().collect(),
+            interval,
+            last: None,
+            repairs: empty!(),
+        }
+    }
+
+    pub fn is_partner(&self, remote_id: NodeId) -> bool { self.partners.contains(&remote_id) }
+
+    /// Returns partners which have to be sent the digest, if the reconciliation is due.
+    pub fn due(&mut self) -> Vec<NodeId> {
+        if self.partners.is_empty()
+            || matches!(self.last, Some(last) if last.elapsed() < self.interval)
+        {
+            return vec![];
+        }
+        self.last = Some(Instant::now());
+        self.partners.iter().copied().collect()
+    }
+
+    /// Registers container which is retrieved from the partner for the repair.
+    pub fn repair(&mut self, container_id: ContainerId) { self.repairs.insert(container_id); }
+
+    /// Detects whether the retrieved container was repaired and has to be pinned.
+    pub fn repaired(&mut self, container_id: ContainerId) -> bool {
+        self.repairs.remove(&container_id)
+    }
+}
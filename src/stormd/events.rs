@@ -0,0 +1,70 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use storm_ext::{EventClass, EventDigest, NodeEvent};
+
+use super::popularity::unix_time;
+
+/// Batches events of the configured classes into periodic digests.
+///
+/// Since the controller has no timers, the digests are emitted when the node handles other
+/// messages after the digest interval has passed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct EventDigests {
+    classes: BTreeSet<EventClass>,
+    interval: Duration,
+    /// Digests collected since the given moment
+    pending: BTreeMap<EventClass, (Instant, EventDigest)>,
+}
+
+impl EventDigests {
+    pub fn with(classes: BTreeSet<EventClass>, interval: Duration) -> Self {
+        EventDigests {
+            classes,
+            interval,
+            pending: empty!(),
+        }
+    }
+
+    /// Adds event to the digest of its class, if the class is digested, or returns the event
+    /// otherwise.
+    pub fn push(&mut self, event: NodeEvent) -> Option<NodeEvent> {
+        let class = event.class();
+        if !self.classes.contains(&class) {
+            return Some(event);
+        }
+        let (_, digest) = self.pending.entry(class).or_insert_with(|| {
+            (Instant::now(), EventDigest {
+                class,
+                since: unix_time(),
+                events: vec![],
+            })
+        });
+        digest.events.push(event);
+        None
+    }
+
+    /// Takes digests which were collected for longer than the digest interval.
+    pub fn due(&mut self) -> Vec<EventDigest> {
+        let due = self
+            .pending
+            .iter()
+            .filter(|(_, (started, _))| started.elapsed() >= self.interval)
+            .map(|(class, _)| *class)
+            .collect::<Vec<_>>();
+        due.into_iter()
+            .filter_map(|class| self.pending.remove(&class))
+            .map(|(_, digest)| digest)
+            .collect()
+    }
+}
@@ -0,0 +1,59 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{HashMap, HashSet};
+
+use internet2::addr::NodeId;
+use storm_rpc::NodeFeatures;
+
+/// Type of the node-level app-opaque message carrying [`NodeFeatures`].
+pub const FEATURES_MSG_TYPE: u16 = 0x000A;
+
+/// Features of this node and the ones advertised by the remote peers.
+///
+/// Like the attestation, the features are sent to each remote peer once after the node start,
+/// when it first receives a message from the peer. Nodes with no features don't advertise them.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct PeerFeatures {
+    local: NodeFeatures,
+    peers: HashMap<NodeId, NodeFeatures>,
+    /// Peers which were sent the features of this node
+    sent: HashSet<NodeId>,
+}
+
+impl PeerFeatures {
+    pub fn with(local: NodeFeatures) -> Self {
+        PeerFeatures {
+            local,
+            peers: empty!(),
+            sent: empty!(),
+        }
+    }
+
+    pub fn local(&self) -> NodeFeatures { self.local }
+
+    /// Returns features which have to be advertised to the peer, marking them as sent.
+    pub fn due(&mut self, remote_id: NodeId) -> Option<NodeFeatures> {
+        if self.local == NodeFeatures::default() || !self.sent.insert(remote_id) {
+            return None;
+        }
+        Some(self.local)
+    }
+
+    pub fn register(&mut self, remote_id: NodeId, features: NodeFeatures) {
+        if self.peers.insert(remote_id, features) != Some(features) {
+            info!("Peer {} advertises features {}", remote_id, features);
+        }
+    }
+
+    pub fn get(&self, remote_id: NodeId) -> Option<NodeFeatures> {
+        self.peers.get(&remote_id).copied()
+    }
+}
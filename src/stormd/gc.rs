@@ -0,0 +1,144 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256t, Hash};
+use storm::{Chunk, ChunkId, Container, ContainerId};
+use storm_rpc::{GcReport, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS};
+use strict_encoding::StrictDecode;
+
+use super::journal::checkpoint;
+use super::popularity::unix_time;
+use crate::containers::{is_collected, is_evicted};
+use crate::DaemonError;
+
+/// Name of the file inside the data directory keeping the orphaned chunks awaiting collection.
+pub const STORM_NODE_GC_FILE: &str = "gc.dat";
+
+/// Collector of the chunks left in the store by the declined and aborted transfers.
+///
+/// Reference counts of the chunks are computed from the manifests of the stored containers on
+/// each run. Chunks not referenced by any of the containers are collected once they stay orphaned
+/// for the grace period, which protects the chunks of the transfers in progress. Since the store
+/// does not support removal of the records, data of a collected chunk are replaced with an empty
+/// record, which does not match the chunk id and is reported as missing to the transfers.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ChunkGc {
+    path: PathBuf,
+    /// Number of seconds a chunk must stay orphaned before it is collected
+    grace: u64,
+    interval: Option<Duration>,
+    last_run: Instant,
+    /// Orphaned chunks with UNIX timestamp of the run which has found them orphaned first
+    orphans: BTreeMap<ChunkId, u64>,
+}
+
+impl ChunkGc {
+    pub fn load(
+        data_dir: &Path,
+        grace: u64,
+        interval: Option<Duration>,
+    ) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_GC_FILE);
+        let orphans =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(ChunkGc {
+            path,
+            grace,
+            interval,
+            last_run: Instant::now(),
+            orphans,
+        })
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.interval.map(|interval| self.last_run.elapsed() >= interval).unwrap_or_default()
+    }
+
+    /// Collects orphaned chunks which have reached the grace period. Nothing is collected if
+    /// some of the stored containers can't be read, since their chunks can't be accounted.
+    ///
+    /// Dry run only reports the chunks which would be collected, keeping both the store and the
+    /// collector state intact.
+    pub fn run(
+        &mut self,
+        store: &mut store_rpc::Client,
+        dry_run: bool,
+    ) -> Result<GcReport, DaemonError> {
+        if !dry_run {
+            self.last_run = Instant::now();
+        }
+        let now = unix_time();
+        let mut report = GcReport::default();
+
+        let mut refs = BTreeMap::<ChunkId, u32>::new();
+        let mut accounted = true;
+        for id in store.ids(DB_TABLE_CONTAINERS)? {
+            let container_id = ContainerId::from_inner(sha256t::Hash::from_inner(id.into_inner()));
+            let container = match store.retrieve_chunk(DB_TABLE_CONTAINERS, container_id)? {
+                // Chunks of the evicted containers are not referenced
+                Some(chunk) if is_evicted(&chunk) => continue,
+                Some(chunk) => Container::strict_deserialize(chunk).ok(),
+                None => None,
+            };
+            match container {
+                Some(container) => {
+                    for chunk_id in container.chunks.iter() {
+                        *refs.entry(*chunk_id).or_default() += 1;
+                    }
+                }
+                None => {
+                    warn!(
+                        "Stored container {} is corrupted; no chunks are collected",
+                        container_id
+                    );
+                    accounted = false;
+                }
+            }
+        }
+        report.references = refs.values().map(|count| *count as u64).sum();
+
+        let mut orphans = BTreeMap::new();
+        for chunk_id in store.ids(DB_TABLE_CHUNKS)? {
+            report.chunks += 1;
+            if refs.contains_key(&chunk_id) {
+                continue;
+            }
+            let chunk = match store.retrieve_chunk(DB_TABLE_CHUNKS, chunk_id)? {
+                Some(chunk) if !is_collected(&chunk, chunk_id) => chunk,
+                _ => continue,
+            };
+            let orphaned = self.orphans.get(&chunk_id).copied().unwrap_or(now);
+            if !accounted || now.saturating_sub(orphaned) < self.grace {
+                report.orphaned += 1;
+                orphans.insert(chunk_id, orphaned);
+                continue;
+            }
+            report.collected_bytes += chunk.len() as u64;
+            report.collected += 1;
+            report.collected_chunks.push(chunk_id);
+            if !dry_run {
+                store.store(DB_TABLE_CHUNKS, chunk_id, &Chunk::try_from(vec![])?)?;
+                trace!("Orphaned chunk {} is collected", chunk_id);
+            }
+        }
+        if dry_run {
+            return Ok(report);
+        }
+        self.orphans = orphans;
+        checkpoint(&self.path, &self.orphans)?;
+        Ok(report)
+    }
+}
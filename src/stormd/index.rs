@@ -0,0 +1,316 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256t, Hash};
+use internet2::addr::NodeId;
+use microservices::rpc::ServerError;
+use storm::{Chunk, Container, ContainerHeader, ContainerId, StormApp};
+use storm_rpc::{AppUsage, ContainerEntry, DB_TABLE_CONTAINERS, DB_TABLE_CONTAINER_HEADERS};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::{checkpoint, Journal};
+use super::popularity::unix_time;
+use crate::containers::is_evicted;
+use crate::{DaemonError, LaunchError};
+
+/// Name of the file inside the data directory keeping the checkpoint of the container index.
+pub const STORM_NODE_INDEX_FILE: &str = "index.dat";
+
+/// Name of the file inside the data directory keeping the journal of the container index
+/// changes.
+pub const STORM_NODE_INDEX_JOURNAL: &str = "index.wal";
+
+/// Change of the container index recorded in the journal.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+enum IndexChange {
+    Register(ContainerEntry),
+    Discard(ContainerId),
+}
+
+type Entries = BTreeMap<ContainerId, ContainerEntry>;
+type Refs = BTreeMap<ContainerId, BTreeSet<StormApp>>;
+
+fn apply(entries: &mut Entries, refs: &mut Refs, change: IndexChange) {
+    match change {
+        IndexChange::Register(entry) => {
+            if let Some(app) = entry.app {
+                refs.entry(entry.container_id).or_default().insert(app);
+            }
+            entries.insert(entry.container_id, entry);
+        }
+        IndexChange::Discard(container_id) => {
+            entries.remove(&container_id);
+            refs.remove(&container_id);
+        }
+    }
+}
+
+/// Index of the containers known to the node, used to filter and sort container lists.
+///
+/// Apps, peers and transfer times of the containers are not kept in the store. Their changes,
+/// together with the container sizes, are written to a journal, which is replayed over the index
+/// checkpoint when the node starts; thus the store is read only for the containers missed in the
+/// index.
+///
+/// The store keeps each container once, even if it is referenced by multiple apps. The index
+/// tracks the apps referencing each container, attributing container size to each of them.
+#[derive(Debug)]
+pub struct ContainerIndex {
+    path: PathBuf,
+    /// Index entries, shared with the snapshots taken from the index
+    entries: Arc<Entries>,
+    /// Apps which have transferred each of the containers
+    refs: Refs,
+    journal: Journal<IndexChange>,
+}
+
+impl ContainerIndex {
+    /// Restores index from its checkpoint and journal kept in the data directory.
+    pub fn open(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_INDEX_FILE);
+        let (mut entries, mut refs): (Entries, Refs) = if path.exists() {
+            StrictDecode::strict_decode(fs::File::open(&path)?)?
+        } else {
+            empty!()
+        };
+        let (journal, changes) = Journal::open(&data_dir.join(STORM_NODE_INDEX_JOURNAL))?;
+        for change in changes {
+            apply(&mut entries, &mut refs, change);
+        }
+        Ok(ContainerIndex {
+            path,
+            entries: Arc::new(entries),
+            refs,
+            journal,
+        })
+    }
+
+    /// Creates empty index, discarding the checkpoint and the journal kept in the data directory.
+    /// Used to rebuild the index which can't be restored.
+    pub fn create(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let journal_path = data_dir.join(STORM_NODE_INDEX_JOURNAL);
+        if journal_path.exists() {
+            fs::remove_file(&journal_path)?;
+        }
+        let (journal, _) = Journal::open(&journal_path)?;
+        Ok(ContainerIndex {
+            path: data_dir.join(STORM_NODE_INDEX_FILE),
+            entries: empty!(),
+            refs: empty!(),
+            journal,
+        })
+    }
+
+    /// Reconciles index with the container headers kept in the store. Apps and peers of the
+    /// containers missed in the index remain unknown until they get transferred again. Headers
+    /// stored without the container are left by the interrupted transfers and are not indexed.
+    ///
+    /// Only the containers missed in the index are read from the store, and they are journaled
+    /// once read. Evicted containers are never indexed, so their records are read on each start.
+    pub fn load(&mut self, store: &mut store_rpc::Client) -> Result<(), LaunchError> {
+        let containers = store.ids(DB_TABLE_CONTAINERS)?;
+        let stored = store
+            .ids(DB_TABLE_CONTAINER_HEADERS)?
+            .into_iter()
+            .filter(|id| containers.contains(id))
+            .map(|id| ContainerId::from_inner(sha256t::Hash::from_inner(id.into_inner())))
+            .collect::<BTreeSet<_>>();
+        Arc::make_mut(&mut self.entries).retain(|container_id, _| stored.contains(container_id));
+        self.refs.retain(|container_id, _| stored.contains(container_id));
+        let missed = stored.into_iter().filter(|container_id| !self.contains(*container_id));
+        let mut added = 0usize;
+        for container_id in missed.collect::<Vec<_>>() {
+            // Evicted containers are discarded from the index, but their records stay in the store
+            let size = match store.retrieve_chunk(DB_TABLE_CONTAINERS, container_id)? {
+                Some(chunk) if is_evicted(&chunk) => continue,
+                Some(chunk) => match Container::strict_deserialize(chunk) {
+                    Ok(container) => container.header.size,
+                    Err(err) => {
+                        warn!("Invalid manifest of container {}: {}", container_id, err);
+                        0
+                    }
+                },
+                None => continue,
+            };
+            let mut entry = *self.entry(container_id);
+            entry.size = size;
+            self.record(IndexChange::Register(entry))?;
+            added += 1;
+        }
+        debug!("Indexed {} containers, {} of them new", self.entries.len(), added);
+        Ok(())
+    }
+
+    /// Rebuilds index from the container manifests kept in the store, returning number of the
+    /// indexed containers. Headers missed for the valid containers are restored from their
+    /// manifests; corrupted containers are not indexed. Apps and peers are kept for the
+    /// containers which were already indexed.
+    ///
+    /// The rebuilt index is not journaled and must be saved with [`ContainerIndex::checkpoint`].
+    pub fn rebuild(
+        &mut self,
+        store: &mut store_rpc::Client,
+    ) -> Result<usize, ServerError<store_rpc::FailureCode>> {
+        let headers = store.ids(DB_TABLE_CONTAINER_HEADERS)?;
+        let mut entries = Entries::new();
+        for id in store.ids(DB_TABLE_CONTAINERS)? {
+            let container_id = ContainerId::from_inner(sha256t::Hash::from_inner(id.into_inner()));
+            let container = match store.retrieve_chunk(DB_TABLE_CONTAINERS, container_id)? {
+                Some(chunk) if is_evicted(&chunk) => continue,
+                Some(chunk) => Container::strict_deserialize(chunk).ok(),
+                None => None,
+            };
+            let container = match container {
+                Some(container) if container.container_id() == container_id => container,
+                _ => {
+                    warn!("Stored container {} is corrupted and is not indexed", container_id);
+                    continue;
+                }
+            };
+            if !headers.contains(&id) {
+                let header_chunk = container
+                    .header
+                    .strict_serialize()
+                    .ok()
+                    .and_then(|data| Chunk::try_from(data).ok());
+                match header_chunk {
+                    Some(chunk) => {
+                        store.store(DB_TABLE_CONTAINER_HEADERS, container_id, &chunk)?;
+                    }
+                    None => warn!("Unable to restore header of container {}", container_id),
+                }
+            }
+            let mut entry = self.entries.get(&container_id).copied().unwrap_or(ContainerEntry {
+                container_id,
+                app: None,
+                peer: None,
+                size: 0,
+                timestamp: 0,
+            });
+            entry.size = container.header.size;
+            entries.insert(container_id, entry);
+        }
+        self.refs.retain(|container_id, _| entries.contains_key(container_id));
+        self.entries = Arc::new(entries);
+        info!("Index is rebuilt with {} containers", self.entries.len());
+        Ok(self.entries.len())
+    }
+
+    /// Saves checkpoint of the index and truncates its journal.
+    pub fn checkpoint(&mut self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &(self.entries.as_ref().clone(), self.refs.clone()))?;
+        self.journal.truncate()
+    }
+
+    fn record(&mut self, change: IndexChange) -> Result<(), strict_encoding::Error> {
+        self.journal.append(&change)?;
+        apply(Arc::make_mut(&mut self.entries), &mut self.refs, change);
+        if self.journal.is_checkpoint_due() {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    fn entry(&mut self, container_id: ContainerId) -> &mut ContainerEntry {
+        Arc::make_mut(&mut self.entries).entry(container_id).or_insert(ContainerEntry {
+            container_id,
+            app: None,
+            peer: None,
+            size: 0,
+            timestamp: 0,
+        })
+    }
+
+    /// Registers container transfer to or from a remote peer.
+    pub fn register(
+        &mut self,
+        container_id: ContainerId,
+        app: StormApp,
+        peer: NodeId,
+    ) -> Result<(), strict_encoding::Error> {
+        let mut entry = *self.entry(container_id);
+        entry.app = Some(app);
+        entry.peer = Some(peer);
+        entry.timestamp = unix_time();
+        self.record(IndexChange::Register(entry))
+    }
+
+    /// Reads container size from its header in the store, if the header is present.
+    pub(crate) fn update_size(
+        &mut self,
+        store: &mut store_rpc::Client,
+        container_id: ContainerId,
+    ) -> Result<(), DaemonError> {
+        let chunk = match store.retrieve_chunk(DB_TABLE_CONTAINER_HEADERS, container_id)? {
+            Some(chunk) => chunk,
+            None => return Ok(()),
+        };
+        match ContainerHeader::strict_deserialize(chunk) {
+            Ok(header) => {
+                let mut entry = *self.entry(container_id);
+                entry.size = header.size;
+                self.record(IndexChange::Register(entry))?;
+            }
+            Err(err) => warn!("Invalid header of container {}: {}", container_id, err),
+        }
+        Ok(())
+    }
+
+    /// Removes container from the index.
+    pub fn discard(&mut self, container_id: ContainerId) -> Result<(), strict_encoding::Error> {
+        self.record(IndexChange::Discard(container_id))
+    }
+
+    pub fn contains(&self, container_id: ContainerId) -> bool {
+        self.entries.contains_key(&container_id)
+    }
+
+    /// Detects whether any of the apps references the container.
+    pub fn is_referenced(&self, container_id: ContainerId) -> bool {
+        self.refs.get(&container_id).map_or(false, |apps| !apps.is_empty())
+    }
+
+    /// Accounts size of the containers referenced by each of the apps. Each app is attributed the
+    /// full size of the containers it references, including the ones shared with other apps.
+    pub fn usage(&self) -> Vec<AppUsage> {
+        let mut usage = BTreeMap::<StormApp, AppUsage>::new();
+        for (container_id, apps) in &self.refs {
+            let size = self.entries.get(container_id).map(|entry| entry.size).unwrap_or_default();
+            for app in apps {
+                let app_usage = usage.entry(*app).or_insert(AppUsage {
+                    app: *app,
+                    containers: 0,
+                    bytes: 0,
+                    shared_bytes: 0,
+                });
+                app_usage.containers += 1;
+                app_usage.bytes += size;
+                if apps.len() > 1 {
+                    app_usage.shared_bytes += size;
+                }
+            }
+        }
+        usage.into_values().collect()
+    }
+
+    /// Takes point-in-time snapshot of the index entries. The snapshot shares data with the index
+    /// until the index gets changed.
+    pub fn snapshot(&self) -> Arc<BTreeMap<ContainerId, ContainerEntry>> { self.entries.clone() }
+
+    pub fn entries(&self) -> impl Iterator<Item = ContainerEntry> + '_ {
+        self.entries.values().copied()
+    }
+}
@@ -0,0 +1,132 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256, Hash};
+use internet2::addr::NodeId;
+use storm::p2p::{AppMsg, Messages};
+use storm::StormApp;
+
+/// Type of the node-level app-opaque message carrying [`InterestFilter`].
+pub const INTEREST_MSG_TYPE: u16 = 0x0007;
+
+/// Number of filter bits per registered id, giving around 1% of false positives.
+const BLOOM_BITS_PER_ID: usize = 10;
+
+/// Number of filter bits set for each id.
+const BLOOM_HASHES: u8 = 7;
+
+/// Maximal number of hashes accepted from the filters of the remote peers.
+const BLOOM_HASHES_LIMIT: u8 = 32;
+
+/// Bloom filter of the container and message ids.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct BloomFilter {
+    bits: Vec<u8>,
+    hashes: u8,
+}
+
+impl BloomFilter {
+    pub fn with<'a>(ids: impl ExactSizeIterator<Item = &'a [u8; 32]>) -> Self {
+        let len = (ids.len() * BLOOM_BITS_PER_ID + 7) / 8;
+        let mut filter = BloomFilter {
+            bits: vec![0u8; len.max(1)],
+            hashes: BLOOM_HASHES,
+        };
+        for id in ids {
+            for bit in filter.bits(id) {
+                filter.bits[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+        filter
+    }
+
+    pub fn contains(&self, id: &[u8; 32]) -> bool {
+        self.bits(id).into_iter().all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !self.bits.is_empty() && self.hashes > 0 && self.hashes <= BLOOM_HASHES_LIMIT
+    }
+
+    /// Returns filter bits of the id, derived with the double hashing from its SHA256 hash.
+    fn bits(&self, id: &[u8; 32]) -> Vec<usize> {
+        let hash = sha256::Hash::hash(id).into_inner();
+        let mut first = [0u8; 8];
+        let mut second = [0u8; 8];
+        first.copy_from_slice(&hash[..8]);
+        second.copy_from_slice(&hash[8..16]);
+        let first = u64::from_le_bytes(first);
+        let second = u64::from_le_bytes(second);
+        let len = self.bits.len() as u64 * 8;
+        (0..self.hashes as u64)
+            .map(|no| (first.wrapping_add(no.wrapping_mul(second)) % len) as usize)
+            .collect()
+    }
+}
+
+/// Interest of the remote peer app in the containers and messages, registered with the node.
+/// Filter is absent when the peer withdraws its interest.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct InterestFilter {
+    pub app: StormApp,
+    pub filter: Option<BloomFilter>,
+}
+
+/// Interests registered with the node by the remote peers.
+///
+/// Peers which have registered an interest for an app are sent only the announcements of the
+/// containers and the posts matching the interest: a container matches by its id and a post by
+/// its id, the id of its parent or the ids of the containers it refers to. Interests are kept
+/// for the node lifetime.
+#[derive(Clone, Debug, Default)]
+pub struct Interests {
+    filters: HashMap<(NodeId, StormApp), BloomFilter>,
+}
+
+impl Interests {
+    pub fn register(&mut self, remote_id: NodeId, interest: InterestFilter) {
+        match interest.filter {
+            Some(filter) if filter.is_valid() => {
+                debug!("Peer {} has registered interest for {}", remote_id, interest.app);
+                self.filters.insert((remote_id, interest.app), filter);
+            }
+            Some(_) => warn!("Peer {} has registered malformed interest filter", remote_id),
+            None => {
+                debug!("Peer {} has withdrawn interest for {}", remote_id, interest.app);
+                self.filters.remove(&(remote_id, interest.app));
+            }
+        }
+    }
+
+    /// Detects whether the message has to be sent to the remote peer.
+    pub fn matches(&self, remote_id: NodeId, message: &Messages) -> bool {
+        let (app, ids) = match message {
+            Messages::AnnounceContainer(AppMsg { app, data }) => {
+                (app, vec![data.id.container_id.into_inner().into_inner()])
+            }
+            Messages::Post(AppMsg { app, data }) => {
+                let mut ids = vec![
+                    data.mesg_id().into_inner().into_inner(),
+                    data.parent_id.into_inner().into_inner(),
+                ];
+                ids.extend(data.container_ids.iter().map(|id| id.into_inner().into_inner()));
+                (app, ids)
+            }
+            _ => return true,
+        };
+        match self.filters.get(&(remote_id, *app)) {
+            Some(filter) => ids.iter().any(|id| filter.contains(id)),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::{JobId, JobInfo, JobKind, JobState};
+
+use super::popularity::unix_time;
+
+/// Number of seconds during which the finished jobs are kept for the status requests.
+pub const JOB_RETENTION: u64 = 24 * 3600;
+
+/// Registry of long-running operations executed in background on behalf of RPC clients.
+///
+/// Several jobs may operate on the same container, in which case they share the transfer and
+/// change their state together. Finished jobs are forgotten after [`JOB_RETENTION`].
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Jobs {
+    jobs: BTreeMap<JobId, JobInfo>,
+}
+
+impl Jobs {
+    pub fn submit(&mut self, kind: JobKind, remote_id: NodeId, container_id: ContainerId) -> JobId {
+        self.prune();
+        let now = unix_time();
+        let id = rand::random();
+        self.jobs.insert(id, JobInfo {
+            id,
+            kind,
+            container_id,
+            remote_id,
+            state: JobState::Queued,
+            created: now,
+            updated: now,
+        });
+        id
+    }
+
+    pub fn get(&self, job_id: JobId) -> Option<JobInfo> { self.jobs.get(&job_id).copied() }
+
+    pub fn list(&self) -> impl Iterator<Item = JobInfo> + '_ { self.jobs.values().copied() }
+
+    /// Marks the jobs operating on the container as running.
+    pub fn start(&mut self, container_id: ContainerId) {
+        self.update(container_id, JobState::Running);
+    }
+
+    /// Marks the jobs operating on the container as finished, returning their ids.
+    pub fn finish(&mut self, container_id: ContainerId, success: bool) -> Vec<JobId> {
+        let state = if success { JobState::Completed } else { JobState::Failed };
+        self.update(container_id, state)
+    }
+
+    /// Forgets the jobs finished more than [`JOB_RETENTION`] ago.
+    fn prune(&mut self) {
+        let now = unix_time();
+        self.jobs.retain(|_, job| {
+            !job.state.is_finished() || now.saturating_sub(job.updated) < JOB_RETENTION
+        });
+    }
+
+    fn update(&mut self, container_id: ContainerId, state: JobState) -> Vec<JobId> {
+        let now = unix_time();
+        self.jobs
+            .values_mut()
+            .filter(|job| job.container_id == container_id && !job.state.is_finished())
+            .map(|job| {
+                debug!("Job {:#018x} is {}", job.id, state);
+                job.state = state;
+                job.updated = now;
+                job.id
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use amplify::Wrapper;
+    use bitcoin_hashes::{sha256t, Hash};
+
+    use super::*;
+
+    fn peer() -> NodeId {
+        NodeId::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .expect("valid node id")
+    }
+
+    fn container(no: u8) -> ContainerId {
+        ContainerId::from_inner(sha256t::Hash::from_inner([no; 32]))
+    }
+
+    #[test]
+    fn jobs_share_container() {
+        let mut jobs = Jobs::default();
+        let first = jobs.submit(JobKind::Pull, peer(), container(1));
+        let second = jobs.submit(JobKind::Pull, peer(), container(1));
+        let other = jobs.submit(JobKind::Pull, peer(), container(2));
+
+        jobs.start(container(1));
+        assert_eq!(jobs.get(first).unwrap().state, JobState::Running);
+        assert_eq!(jobs.get(second).unwrap().state, JobState::Running);
+        assert_eq!(jobs.get(other).unwrap().state, JobState::Queued);
+
+        let mut finished = jobs.finish(container(1), true);
+        finished.sort_unstable();
+        let mut expected = vec![first, second];
+        expected.sort_unstable();
+        assert_eq!(finished, expected);
+        assert_eq!(jobs.get(first).unwrap().state, JobState::Completed);
+
+        // Finished jobs are not restarted by the later transfers of the container
+        let third = jobs.submit(JobKind::Pull, peer(), container(1));
+        assert_eq!(jobs.finish(container(1), false), vec![third]);
+        assert_eq!(jobs.get(first).unwrap().state, JobState::Completed);
+    }
+
+    #[test]
+    fn finished_jobs_are_pruned() {
+        let mut jobs = Jobs::default();
+        let old = jobs.submit(JobKind::Pull, peer(), container(1));
+        let running = jobs.submit(JobKind::Pull, peer(), container(2));
+        jobs.finish(container(1), true);
+        for id in [old, running] {
+            jobs.jobs.get_mut(&id).unwrap().updated -= JOB_RETENTION;
+        }
+
+        jobs.submit(JobKind::Pull, peer(), container(3));
+        assert_eq!(jobs.get(old), None);
+        assert!(jobs.get(running).is_some());
+    }
+}
@@ -0,0 +1,142 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use bitcoin_hashes::{sha256, Hash};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::chaos;
+
+/// Number of journal records after which the journaled state is checkpointed and the journal is
+/// truncated.
+pub const JOURNAL_CHECKPOINT_LIMIT: usize = 256;
+
+/// Write-ahead journal of the mutations of a state persisted in the data directory.
+///
+/// Each mutation is appended to the journal and synced to the disk before it is applied, so the
+/// state can be restored after a crash by replaying the journal over the last checkpoint. Records
+/// are framed with their length and checksum; a torn record at the end of the journal, left by
+/// an interrupted write, is discarded on replay.
+#[derive(Debug)]
+pub struct Journal<R>
+where R: StrictEncode + StrictDecode
+{
+    path: PathBuf,
+    file: fs::File,
+    records: usize,
+    _phantom: PhantomData<R>,
+}
+
+impl<R> Journal<R>
+where R: StrictEncode + StrictDecode
+{
+    /// Opens the journal, returning records which has to be replayed over the last checkpoint.
+    pub fn open(path: &Path) -> Result<(Self, Vec<R>), strict_encoding::Error> {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+
+        let mut records = vec![];
+        let mut pos = 0usize;
+        while let Some(record) = Self::read_record(&data[pos..]) {
+            records.push(record.1);
+            pos += record.0;
+        }
+        if pos < data.len() {
+            warn!(
+                "Discarding {} bytes of an incomplete record at the end of journal {}",
+                data.len() - pos,
+                path.display()
+            );
+            file.set_len(pos as u64)?;
+            file.sync_data()?;
+        }
+        file.seek(SeekFrom::Start(pos as u64))?;
+
+        let journal = Journal {
+            path: path.to_owned(),
+            file,
+            records: records.len(),
+            _phantom: PhantomData,
+        };
+        Ok((journal, records))
+    }
+
+    fn read_record(data: &[u8]) -> Option<(usize, R)> {
+        if data.len() < 8 {
+            return None;
+        }
+        let len = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let payload = data.get(8..8 + len)?;
+        if sha256::Hash::hash(payload)[..4] != data[4..8] {
+            return None;
+        }
+        R::strict_deserialize(payload).ok().map(|record| (8 + len, record))
+    }
+
+    /// Appends record to the journal, returning after it is synced to the disk.
+    pub fn append(&mut self, record: &R) -> Result<(), strict_encoding::Error> {
+        let payload = record.strict_serialize()?;
+        let mut data = Vec::with_capacity(payload.len() + 8);
+        data.extend((payload.len() as u32).to_le_bytes());
+        data.extend(&sha256::Hash::hash(&payload)[..4]);
+        data.extend(payload);
+        self.file.write_all(&data)?;
+        chaos::fsync()?;
+        self.file.sync_data()?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Detects whether the journal has grown enough to checkpoint the state.
+    pub fn is_checkpoint_due(&self) -> bool { self.records >= JOURNAL_CHECKPOINT_LIMIT }
+
+    /// Truncates the journal once the state is checkpointed.
+    pub fn truncate(&mut self) -> Result<(), strict_encoding::Error> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        chaos::fsync()?;
+        self.file.sync_data()?;
+        self.records = 0;
+        debug!("Journal {} is truncated", self.path.display());
+        Ok(())
+    }
+}
+
+/// Writes the checkpoint of a state atomically, such that a crash leaves either the previous or
+/// the new checkpoint in place.
+pub fn checkpoint(path: &Path, state: &impl StrictEncode) -> Result<(), strict_encoding::Error> {
+    let tmp = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp)?;
+    state.strict_encode(&mut file)?;
+    chaos::fsync()?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Writes file content atomically, in the same way as [`checkpoint`].
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<(), io::Error> {
+    let tmp = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(data)?;
+    chaos::fsync()?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)
+}
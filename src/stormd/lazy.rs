@@ -0,0 +1,100 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::{ContainerFullId, ContainerId, StormApp};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::checkpoint;
+
+/// Name of the file inside the data directory keeping the containers with deferred chunks.
+pub const STORM_NODE_LAZY_FILE: &str = "lazy.dat";
+
+/// Source of the container chunks which were not retrieved yet.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct DeferredChunks {
+    pub app: StormApp,
+    pub remote_id: NodeId,
+    pub id: ContainerFullId,
+}
+
+/// Containers fetched metadata-first.
+///
+/// Apps browsing large catalogs retrieve only the container headers and manifests; the chunks
+/// are retrieved from the same peer once the container is first read, i.e. requested by the app
+/// in full or pulled by a remote peer, or once a client materializes it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LazyFetches {
+    path: PathBuf,
+    deferred: BTreeMap<ContainerId, DeferredChunks>,
+    /// Metadata retrievals in progress
+    fetching: BTreeMap<ContainerId, DeferredChunks>,
+    /// Deferred containers which chunks are being retrieved
+    materializing: BTreeSet<ContainerId>,
+}
+
+impl LazyFetches {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_LAZY_FILE);
+        let deferred =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(LazyFetches {
+            path,
+            deferred,
+            fetching: empty!(),
+            materializing: empty!(),
+        })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.deferred)?;
+        Ok(())
+    }
+
+    pub fn is_deferred(&self, container_id: ContainerId) -> bool {
+        self.deferred.contains_key(&container_id)
+    }
+
+    /// Registers retrieval of the container metadata.
+    pub fn fetch(&mut self, app: StormApp, remote_id: NodeId, id: ContainerFullId) {
+        self.fetching.insert(id.container_id, DeferredChunks { app, remote_id, id });
+    }
+
+    /// Registers completion of the container retrieval, returning the app which requested the
+    /// metadata if only the metadata were retrieved.
+    pub fn finished(
+        &mut self,
+        container_id: ContainerId,
+        success: bool,
+    ) -> Result<Option<StormApp>, strict_encoding::Error> {
+        if let Some(deferred) = self.fetching.remove(&container_id) {
+            if success {
+                self.deferred.insert(container_id, deferred);
+                self.save()?;
+            }
+            return Ok(Some(deferred.app));
+        }
+        self.materializing.remove(&container_id);
+        if success && self.deferred.remove(&container_id).is_some() {
+            self.save()?;
+        }
+        Ok(None)
+    }
+
+    /// Returns source of the deferred container chunks, unless they are already being retrieved.
+    pub fn materialize(&mut self, container_id: ContainerId) -> Option<DeferredChunks> {
+        let deferred = *self.deferred.get(&container_id)?;
+        self.materializing.insert(container_id).then(|| deferred)
+    }
+}
@@ -0,0 +1,114 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use internet2::addr::NodeId;
+use storm::p2p::Messages;
+
+/// Maximum number of outbound p2p messages kept while the connection to the LNP node is lost.
+/// When the queue is full, the oldest messages are dropped.
+pub const P2P_QUEUE_LIMIT: usize = 1024;
+
+/// Delay before the first attempt to restore the connection to the LNP node.
+const BACKOFF_MIN: Duration = Duration::from_secs(1);
+
+/// Maximum delay between the attempts to restore the connection to the LNP node.
+const BACKOFF_MAX: Duration = Duration::from_secs(64);
+
+/// State of the message bus connection to the LNP node.
+///
+/// ZMQ restores the transport connection by itself, so the link becomes degraded when a message
+/// can't be delivered to the LNP node and gets restored once the queued messages are flushed.
+/// Since the controller has no timers, the flush attempts are made when the node handles other
+/// messages, but not earlier than the current backoff delay allows.
+#[derive(Clone, Debug)]
+pub struct MsgLink {
+    queue: VecDeque<(NodeId, Messages)>,
+    degraded: bool,
+    backoff: Duration,
+    retry_at: Option<Instant>,
+    dropped: u64,
+}
+
+impl Default for MsgLink {
+    fn default() -> Self {
+        MsgLink {
+            queue: empty!(),
+            degraded: false,
+            backoff: BACKOFF_MIN,
+            retry_at: None,
+            dropped: 0,
+        }
+    }
+}
+
+impl MsgLink {
+    pub fn is_degraded(&self) -> bool { self.degraded }
+
+    pub fn queued(&self) -> usize { self.queue.len() }
+
+    /// Number of messages dropped due to the queue overflow since the link was degraded.
+    pub fn dropped(&self) -> u64 { self.dropped }
+
+    /// Queues message for the delivery once the link is restored.
+    pub fn enqueue(&mut self, remote_id: NodeId, message: Messages) {
+        if self.queue.len() >= P2P_QUEUE_LIMIT {
+            self.queue.pop_front();
+            self.dropped += 1;
+        }
+        self.queue.push_back((remote_id, message));
+    }
+
+    /// Returns message from the front of the queue, if the link is degraded and the backoff
+    /// delay has passed.
+    pub fn take_due(&mut self, force: bool) -> Option<(NodeId, Messages)> {
+        if !self.degraded {
+            return None;
+        }
+        match self.retry_at {
+            Some(retry_at) if !force && Instant::now() < retry_at => None,
+            _ => self.queue.pop_front(),
+        }
+    }
+
+    /// Returns message which failed to be delivered to the front of the queue.
+    pub fn requeue(&mut self, remote_id: NodeId, message: Messages) {
+        self.queue.push_front((remote_id, message));
+    }
+
+    /// Registers delivery failure, scheduling the next attempt with exponential backoff.
+    /// Returns `true` if the link just became degraded.
+    pub fn fail(&mut self) -> bool {
+        let was_degraded = self.degraded;
+        if was_degraded {
+            self.backoff = (self.backoff * 2).min(BACKOFF_MAX);
+        } else {
+            self.backoff = BACKOFF_MIN;
+            self.dropped = 0;
+        }
+        self.degraded = true;
+        self.retry_at = Some(Instant::now() + self.backoff);
+        !was_degraded
+    }
+
+    /// Marks link as restored if no queued messages are left. Returns `true` if the link just
+    /// became restored.
+    pub fn restore(&mut self) -> bool {
+        if !self.degraded || !self.queue.is_empty() {
+            return false;
+        }
+        self.degraded = false;
+        self.backoff = BACKOFF_MIN;
+        self.retry_at = None;
+        true
+    }
+}
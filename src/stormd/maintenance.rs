@@ -0,0 +1,49 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+/// Type of the node-level app-opaque message carrying [`MaintenanceNotice`].
+pub const MAINTENANCE_MSG_TYPE: u16 = 0x000B;
+
+/// Notice sent to the remote peer together with the rejection of its request while the node is
+/// in maintenance.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct MaintenanceNotice {
+    /// Number of seconds after which the peer may retry the request.
+    pub retry_after: u32,
+}
+
+/// Maintenance mode of the node, entered before upgrades.
+///
+/// In maintenance the node rejects new container requests and pushes of the remote peers,
+/// hinting them when to retry, while the transfers in progress are drained. The mode is not
+/// persisted, so a restarted node serves the requests again.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Maintenance {
+    /// Retry hint given to the remote peers, if the node is in maintenance
+    retry_after: Option<u32>,
+}
+
+impl Maintenance {
+    /// Enters maintenance, returning whether the node was not in maintenance before.
+    pub fn enter(&mut self, retry_after: u32) -> bool {
+        self.retry_after.replace(retry_after).is_none()
+    }
+
+    /// Leaves maintenance, returning whether the node was in maintenance.
+    pub fn exit(&mut self) -> bool { self.retry_after.take().is_some() }
+
+    pub fn is_active(&self) -> bool { self.retry_after.is_some() }
+
+    /// Returns notice for the remote peer which request is rejected, if the node is in
+    /// maintenance.
+    pub fn notice(&self) -> Option<MaintenanceNotice> {
+        self.retry_after.map(|retry_after| MaintenanceNotice { retry_after })
+    }
+}
@@ -0,0 +1,126 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use internet2::addr::NodeId;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use storm::StormApp;
+use storm_rpc::{Attestation, Manifest, ManifestInfo, SignedAttestation};
+use strict_encoding::StrictDecode;
+
+use super::journal::checkpoint;
+use super::{unix_time, IndexSnapshot, Quarantine};
+
+/// Name of the file inside the data directory keeping the manifest signing key.
+pub const STORM_NODE_MANIFEST_KEY_FILE: &str = "manifest.key";
+
+/// Name of the directory inside the data directory where the signed manifests are exported.
+pub const STORM_NODE_MANIFEST_DIR: &str = "manifests";
+
+/// Key signing the manifests of the hosted content and the attestations of the node software,
+/// persisted in the data directory.
+///
+/// The key is generated by the node on its first start; the LNP node key is not available to the
+/// Storm node, so the counterparts must learn the manifest key from the node operator.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ManifestKey {
+    secret_key: SecretKey,
+    signer: NodeId,
+}
+
+impl ManifestKey {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_MANIFEST_KEY_FILE);
+        let secret_key = if path.exists() {
+            SecretKey::strict_decode(fs::File::open(&path)?)?
+        } else {
+            debug!("Generating manifest signing key");
+            let secret_key = SecretKey::new(&mut rand::thread_rng());
+            checkpoint(&path, &secret_key)?;
+            secret_key
+        };
+        Ok(ManifestKey::with(secret_key))
+    }
+
+    /// Replaces the key in the data directory with a newly generated one. Manifests and
+    /// attestations signed before remain verifiable with the previous key only.
+    pub fn rotate(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        checkpoint(&data_dir.join(STORM_NODE_MANIFEST_KEY_FILE), &secret_key)?;
+        Ok(ManifestKey::with(secret_key))
+    }
+
+    fn with(secret_key: SecretKey) -> Self {
+        let signer = NodeId::from(PublicKey::from_secret_key(&Secp256k1::new(), &secret_key));
+        ManifestKey { secret_key, signer }
+    }
+
+    pub fn signer(&self) -> NodeId { self.signer }
+
+    /// Signs attestation of the node software version and features.
+    pub fn attest(&self, apps: BTreeSet<StormApp>) -> SignedAttestation {
+        let features = [
+            ("server", cfg!(feature = "server")),
+            ("embedded", cfg!(feature = "embedded")),
+            ("simulation", cfg!(feature = "simulation")),
+            ("link-preview", cfg!(feature = "link-preview")),
+            ("chaos", cfg!(feature = "chaos")),
+        ];
+        Attestation {
+            signer: self.signer,
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            features: features
+                .into_iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(feature, _)| feature.to_owned())
+                .collect(),
+            apps,
+            timestamp: unix_time(),
+        }
+        .sign(&self.secret_key)
+    }
+
+    /// Signs manifest of the containers from the index snapshot, except the quarantined ones,
+    /// and exports it into the data directory.
+    pub fn export(
+        &self,
+        data_dir: &Path,
+        snapshot: &IndexSnapshot,
+        quarantine: &Quarantine,
+    ) -> Result<ManifestInfo, strict_encoding::Error> {
+        let manifest = Manifest {
+            signer: self.signer,
+            timestamp: snapshot.timestamp,
+            containers: snapshot
+                .containers
+                .values()
+                .filter(|entry| !quarantine.is_held(entry.container_id))
+                .map(|entry| (entry.container_id, entry.size))
+                .collect(),
+            pins: snapshot.pins.as_ref().clone(),
+        };
+        let dir = data_dir.join(STORM_NODE_MANIFEST_DIR);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("manifest-{}.dat", manifest.timestamp));
+        let info = ManifestInfo {
+            path: path.display().to_string(),
+            signer: self.signer,
+            timestamp: manifest.timestamp,
+            containers: manifest.containers.len() as u32,
+            pins: manifest.pins.len() as u32,
+        };
+        checkpoint(&path, &manifest.sign(&self.secret_key))?;
+        info!("Manifest {} is exported", path.display());
+        Ok(info)
+    }
+}
@@ -0,0 +1,106 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use nix::unistd::{sysconf, SysconfVar};
+use storm_rpc::{MemoryPressure, MemoryReport};
+
+/// Interval between the checks of the memory use against the budget.
+pub const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Share of the memory budget, in percents, above which the memory pressure is high.
+pub const MEMORY_HIGH_WATERMARK: u64 = 90;
+
+/// Estimated number of bytes taken by an entry of the node queues, which size is not known
+/// exactly.
+pub const QUEUED_ENTRY_SIZE: u64 = 1024;
+
+/// Returns resident memory of the process, in bytes, unless the platform does not report it.
+fn resident_memory() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    let page_size = match sysconf(SysconfVar::PAGE_SIZE) {
+        Ok(Some(size)) if size > 0 => size as u64,
+        _ => 4096,
+    };
+    Some(pages * page_size)
+}
+
+/// Accounting of the memory used by the caches, buffers and queues of the node daemons against
+/// the memory budget.
+///
+/// The components report their memory use, which is estimated from the sizes of their
+/// collections rather than measured. The pressure is decided by the larger of the accounted
+/// memory and the resident memory of stormd, which covers all of the daemons when they run
+/// as threads. Like the other periodic checks, the memory is sampled when the node handles
+/// other messages.
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    budget: Option<u64>,
+    components: BTreeMap<String, u64>,
+    resident: Option<u64>,
+    pressure: MemoryPressure,
+    sampled: Instant,
+}
+
+impl MemoryBudget {
+    pub fn with(budget: Option<u64>) -> Self {
+        MemoryBudget {
+            budget,
+            components: empty!(),
+            resident: None,
+            pressure: MemoryPressure::Normal,
+            sampled: Instant::now(),
+        }
+    }
+
+    pub fn is_due(&self) -> bool { self.sampled.elapsed() >= MEMORY_SAMPLE_INTERVAL }
+
+    /// Records memory used by the component, in bytes.
+    pub fn record(&mut self, component: impl ToString, bytes: u64) {
+        self.components.insert(component.to_string(), bytes);
+    }
+
+    pub fn accounted(&self) -> u64 { self.components.values().sum() }
+
+    pub fn pressure(&self) -> MemoryPressure { self.pressure }
+
+    /// Detects whether new transfers have to wait until the memory use goes down.
+    pub fn is_exhausted(&self) -> bool { self.pressure == MemoryPressure::Critical }
+
+    /// Measures the memory use against the budget, returning the previous and the new pressure
+    /// if it has changed.
+    pub fn sample(&mut self) -> Option<(MemoryPressure, MemoryPressure)> {
+        self.sampled = Instant::now();
+        self.resident = resident_memory();
+        let used = self.accounted().max(self.resident.unwrap_or_default());
+        let pressure = match self.budget {
+            Some(budget) if used >= budget => MemoryPressure::Critical,
+            Some(budget) if used >= budget / 100 * MEMORY_HIGH_WATERMARK => MemoryPressure::High,
+            _ => MemoryPressure::Normal,
+        };
+        let prev = self.pressure;
+        self.pressure = pressure;
+        (prev != pressure).then(|| (prev, pressure))
+    }
+
+    pub fn report(&self) -> MemoryReport {
+        MemoryReport {
+            budget: self.budget,
+            accounted: self.accounted(),
+            resident: self.resident,
+            pressure: self.pressure,
+            components: self.components.clone(),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use storm_rpc::NodeCounters;
+
+use crate::bus::MetricsRegistry;
+
+/// Base name of the metrics file inside the data directory; the extension depends on the format.
+pub const STORM_NODE_METRICS_FILE: &str = "metrics";
+
+/// Size of the metrics file after which it is rotated.
+const METRICS_FILE_LIMIT: u64 = 8 * 1024 * 1024;
+
+/// Number of rotated metrics files kept in addition to the current one.
+const METRICS_FILES_KEPT: usize = 4;
+
+/// Format of the metrics file.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+pub enum MetricsFormat {
+    /// Comma-separated values with a header line
+    #[display("csv")]
+    Csv,
+
+    /// One JSON object per line
+    #[display("jsonl")]
+    JsonLines,
+}
+
+/// Error parsing [`MetricsFormat`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownMetricsFormat(String);
+
+impl Display for UnknownMetricsFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown metrics format `{}`; allowed values are `csv` and `jsonl`", self.0)
+    }
+}
+
+impl FromStr for MetricsFormat {
+    type Err = UnknownMetricsFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(MetricsFormat::Csv),
+            "jsonl" => Ok(MetricsFormat::JsonLines),
+            other => Err(UnknownMetricsFormat(other.to_owned())),
+        }
+    }
+}
+
+/// Configuration of the metrics recorder.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct MetricsConfig {
+    pub format: MetricsFormat,
+    /// Interval between the snapshots, in seconds
+    pub interval: u64,
+}
+
+/// Snapshot of the key node metrics.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct MetricsSnapshot {
+    /// Lifetime activity counters
+    pub counters: NodeCounters,
+    /// Number of outbound p2p messages waiting for the connection to the LNP node
+    pub p2p_queue: u32,
+    pub degraded: bool,
+    /// Number of the container transfers in progress
+    pub transfers: u32,
+}
+
+/// Names of the metrics file fields, used as CSV header and JSON keys.
+const FIELDS: [&str; 11] = [
+    "timestamp",
+    "messages_received",
+    "messages_sent",
+    "bytes_received",
+    "bytes_sent",
+    "transfers_completed",
+    "transfers_failed",
+    "uptime",
+    "p2p_queue",
+    "degraded",
+    "transfers",
+];
+
+impl MetricsSnapshot {
+    fn values(&self, timestamp: u64) -> [String; 11] {
+        let c = self.counters;
+        [
+            timestamp.to_string(),
+            c.messages_received.to_string(),
+            c.messages_sent.to_string(),
+            c.bytes_received.to_string(),
+            c.bytes_sent.to_string(),
+            c.transfers_completed.to_string(),
+            c.transfers_failed.to_string(),
+            c.uptime.to_string(),
+            self.p2p_queue.to_string(),
+            self.degraded.to_string(),
+            self.transfers.to_string(),
+        ]
+    }
+
+    fn line(&self, format: MetricsFormat, timestamp: u64) -> String {
+        let values = self.values(timestamp);
+        match format {
+            MetricsFormat::Csv => values.join(","),
+            MetricsFormat::JsonLines => {
+                let fields = FIELDS
+                    .iter()
+                    .zip(values)
+                    .map(|(name, value)| format!("\"{}\":{}", name, value))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", fields.join(","))
+            }
+        }
+    }
+}
+
+/// Writes periodic snapshots of the node metrics into a file in the data directory, rotating it
+/// once it grows over [`METRICS_FILE_LIMIT`].
+///
+/// Since the controller has no timers, snapshots are taken when the node handles other messages,
+/// so the actual interval between them may be longer than the configured one on an idle node.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MetricsRecorder {
+    path: PathBuf,
+    format: MetricsFormat,
+    interval: Duration,
+    recorded: Option<Instant>,
+}
+
+impl MetricsRecorder {
+    pub fn with(data_dir: &Path, config: MetricsConfig) -> Self {
+        let path = data_dir.join(format!("{}.{}", STORM_NODE_METRICS_FILE, config.format));
+        MetricsRecorder {
+            path,
+            format: config.format,
+            interval: Duration::from_secs(config.interval.max(1)),
+            recorded: None,
+        }
+    }
+
+    /// Detects whether the next snapshot has to be taken.
+    pub fn is_due(&self) -> bool {
+        self.recorded.map_or(true, |recorded| recorded.elapsed() >= self.interval)
+    }
+
+    /// Appends snapshot to the metrics file.
+    pub fn record(&mut self, snapshot: MetricsSnapshot) -> Result<(), io::Error> {
+        self.recorded = Some(Instant::now());
+        self.rotate()?;
+
+        let is_new = !self.path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if is_new && self.format == MetricsFormat::Csv {
+            writeln!(file, "{}", FIELDS.join(","))?;
+        }
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        writeln!(file, "{}", snapshot.line(self.format, timestamp))
+    }
+
+    fn rotated(&self, no: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", no));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> Result<(), io::Error> {
+        match fs::metadata(&self.path) {
+            Ok(meta) if meta.len() >= METRICS_FILE_LIMIT => {}
+            _ => return Ok(()),
+        }
+        for no in (1..METRICS_FILES_KEPT).rev() {
+            let from = self.rotated(no);
+            if from.exists() {
+                fs::rename(from, self.rotated(no + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+}
+
+/// Time the metrics exporter waits for the request of a connected client.
+const EXPORTER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves the metrics registry in the Prometheus text format on `GET /metrics` at the given
+/// address.
+///
+/// Requests are served one by one by a background thread from the registry shared with the node,
+/// so scraping does not block the node. Only plain HTTP is supported.
+pub fn serve_metrics(
+    addr: SocketAddr,
+    registry: Arc<Mutex<MetricsRegistry>>,
+) -> Result<(), io::Error> {
+    let listener = TcpListener::bind(addr)?;
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let res = stream.and_then(|stream| respond(stream, &registry));
+            if let Err(err) = res {
+                debug!("Unable to serve metrics request: {}", err);
+            }
+        }
+    });
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, registry: &Mutex<MetricsRegistry>) -> Result<(), io::Error> {
+    stream.set_read_timeout(Some(EXPORTER_TIMEOUT))?;
+    stream.set_write_timeout(Some(EXPORTER_TIMEOUT))?;
+    // Only the request line matters; headers and body are ignored
+    let mut buf = [0u8; 1024];
+    let len = stream.read(&mut buf)?;
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let mut parts = request.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            ("200 OK", registry.lock().expect("metrics registry lock is poisoned").render())
+        }
+        _ => ("404 Not Found", s!("")),
+    };
+    write!(
+        stream,
+        "HTTP/1.0 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
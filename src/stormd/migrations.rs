@@ -0,0 +1,221 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use strict_encoding::StrictDecode;
+
+use super::index::{STORM_NODE_INDEX_FILE, STORM_NODE_INDEX_JOURNAL};
+use super::journal::checkpoint;
+use super::popularity::unix_time;
+use super::snapshot::STORM_NODE_BACKUP_DIR;
+
+/// Name of the file inside the data directory keeping the versions of its on-disk formats.
+pub const STORM_NODE_FORMATS_FILE: &str = "formats.dat";
+
+/// On-disk format of the node data, versioned independently of the others.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(StrictEncode, StrictDecode)]
+pub enum DataFormat {
+    /// Layout of the container and chunk tables in the store. Version 2 keeps the small chunks
+    /// inline with their containers.
+    #[display("store")]
+    Store,
+
+    /// Container index with its journal.
+    #[display("index")]
+    Index,
+
+    /// State files of stormd and of the child daemons.
+    #[display("state")]
+    State,
+}
+
+impl DataFormat {
+    pub const ALL: [DataFormat; 3] = [DataFormat::Store, DataFormat::Index, DataFormat::State];
+
+    /// Version of the format written by this node. Data directories created before the formats
+    /// were versioned have version 0.
+    pub fn current(self) -> u16 {
+        match self {
+            DataFormat::Store => 2,
+            DataFormat::Index => 1,
+            DataFormat::State => 1,
+        }
+    }
+}
+
+/// Errors upgrading on-disk formats of the data directory.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MigrationError {
+    /// {0} format of the data directory has version {1}, while this node supports versions up
+    /// to {2}; the data were written by a newer node
+    TooNew(DataFormat, u16, u16),
+
+    /// no migration of {0} format from version {1} is known
+    Unsupported(DataFormat, u16),
+
+    /// unable to back up {0} before the migration. Details: {1}
+    Backup(String, String),
+
+    /// migration of {0} format from version {1} has failed. Details: {2}
+    Failed(DataFormat, u16, String),
+
+    /// unable to access format versions of the data directory. Details: {0}
+    Versions(String),
+}
+
+/// Upgrade of an on-disk format from a version to the next one.
+pub struct Migration {
+    pub format: DataFormat,
+    pub from: u16,
+    pub description: &'static str,
+    /// Files and directories inside the data directory which are modified by the migration and
+    /// have to be backed up before it
+    pub files: &'static [&'static str],
+    pub run: fn(&Path, &mut store_rpc::Client) -> Result<(), String>,
+}
+
+/// Known migrations, ordered by the format and the version they upgrade from.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        format: DataFormat::Store,
+        from: 0,
+        description: "stamp unversioned store layout",
+        files: &[],
+        run: stamp,
+    },
+    Migration {
+        format: DataFormat::Store,
+        from: 1,
+        description: "add table of the chunks kept inline with their containers",
+        files: &[],
+        run: extend,
+    },
+    Migration {
+        format: DataFormat::Index,
+        from: 0,
+        description: "stamp unversioned container index",
+        files: &[],
+        run: stamp,
+    },
+    Migration {
+        format: DataFormat::State,
+        from: 0,
+        description: "stamp unversioned state files",
+        files: &[],
+        run: stamp,
+    },
+];
+
+/// Migration of the data which were written in the format of the next version, but were not
+/// stamped with it.
+fn stamp(_: &Path, _: &mut store_rpc::Client) -> Result<(), String> { Ok(()) }
+
+/// Migration to the format which only extends the data written in the previous one, keeping
+/// them readable. Older nodes are still refused to start on the upgraded data, since they can't
+/// read the extension.
+fn extend(_: &Path, _: &mut store_rpc::Client) -> Result<(), String> { Ok(()) }
+
+fn save(data_dir: &Path, versions: &BTreeMap<DataFormat, u16>) -> Result<(), MigrationError> {
+    checkpoint(&data_dir.join(STORM_NODE_FORMATS_FILE), versions)
+        .map_err(|err| MigrationError::Versions(err.to_string()))
+}
+
+/// Copies the files modified by the migrations into a backup directory, returning its path.
+fn backup(data_dir: &Path, files: &BTreeSet<&str>) -> Result<String, MigrationError> {
+    let dir = data_dir.join(STORM_NODE_BACKUP_DIR).join(format!("migration-{}", unix_time()));
+    let failed = |path: &Path, err: std::io::Error| {
+        MigrationError::Backup(path.display().to_string(), err.to_string())
+    };
+    fs::create_dir_all(&dir).map_err(|err| failed(&dir, err))?;
+    for file in files {
+        let src = data_dir.join(file);
+        if src.is_file() {
+            fs::copy(&src, dir.join(file)).map_err(|err| failed(&src, err))?;
+        } else if src.is_dir() {
+            let target = dir.join(file);
+            fs::create_dir_all(&target).map_err(|err| failed(&target, err))?;
+            for entry in fs::read_dir(&src).map_err(|err| failed(&src, err))? {
+                let entry = entry.map_err(|err| failed(&src, err))?;
+                if entry.path().is_file() {
+                    fs::copy(entry.path(), target.join(entry.file_name()))
+                        .map_err(|err| failed(&entry.path(), err))?;
+                }
+            }
+        }
+    }
+    Ok(dir.display().to_string())
+}
+
+/// Upgrades on-disk formats of the data directory to the versions written by this node.
+///
+/// Must be run on the node start, before any of the data is read. The files touched by the
+/// pending migrations are backed up first; the version of each format is saved once its
+/// migration completes, so an interrupted upgrade continues on the next start. A new data
+/// directory is stamped with the current versions, and the node refuses to start on data written
+/// by a newer node.
+pub fn migrate(data_dir: &Path, store: &mut store_rpc::Client) -> Result<(), MigrationError> {
+    let path = data_dir.join(STORM_NODE_FORMATS_FILE);
+    let mut versions = if path.exists() {
+        fs::File::open(&path)
+            .map_err(strict_encoding::Error::from)
+            .and_then(BTreeMap::<DataFormat, u16>::strict_decode)
+            .map_err(|err| MigrationError::Versions(err.to_string()))?
+    } else if !data_dir.join(STORM_NODE_INDEX_FILE).exists()
+        && !data_dir.join(STORM_NODE_INDEX_JOURNAL).exists()
+    {
+        let versions = DataFormat::ALL.iter().map(|format| (*format, format.current())).collect();
+        return save(data_dir, &versions);
+    } else {
+        empty!()
+    };
+
+    let mut pending = vec![];
+    for format in DataFormat::ALL {
+        let current = format.current();
+        let mut version = versions.get(&format).copied().unwrap_or_default();
+        if version > current {
+            return Err(MigrationError::TooNew(format, version, current));
+        }
+        while version < current {
+            let migration = MIGRATIONS
+                .iter()
+                .find(|migration| migration.format == format && migration.from == version)
+                .ok_or(MigrationError::Unsupported(format, version))?;
+            pending.push(migration);
+            version += 1;
+        }
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let files: BTreeSet<&str> =
+        pending.iter().flat_map(|migration| migration.files.iter().copied()).collect();
+    if !files.is_empty() {
+        let dir = backup(data_dir, &files)?;
+        info!("Data files are backed up to {} before the migration", dir);
+    }
+    for migration in pending {
+        info!(
+            "Migrating {} format from version {}: {}",
+            migration.format, migration.from, migration.description
+        );
+        (migration.run)(data_dir, store)
+            .map_err(|err| MigrationError::Failed(migration.format, migration.from, err))?;
+        versions.insert(migration.format, migration.from + 1);
+        save(data_dir, &versions)?;
+    }
+    Ok(())
+}
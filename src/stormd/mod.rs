@@ -9,13 +9,133 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 mod service;
-pub(self) mod daemons;
+mod daemons;
 #[cfg(feature = "server")]
 mod opts;
+mod admin;
+mod apps;
+mod approvals;
+mod attestations;
 mod config;
+mod popularity;
+mod dictionaries;
+mod entropy;
+mod events;
+mod features;
+mod gc;
+mod index;
+mod interests;
+mod journal;
+mod jobs;
+mod lazy;
+mod maintenance;
+mod link;
+mod manifest;
+mod memory;
+mod metrics;
+mod migrations;
+mod moderation;
+mod peers;
+mod patches;
+mod pins;
+mod policy;
+mod polls;
+mod quarantine;
+mod quotas;
+mod ratelimit;
+mod registry;
+mod replicator;
+mod repair;
+mod requests;
+mod resume;
+mod routes;
+mod schedule;
+mod sessions;
+mod shares;
+mod shutdown;
+mod sketch;
+mod snapshot;
+mod stamps;
+mod stats;
+mod supervisor;
+mod topics;
+mod webhooks;
 
+pub use admin::{
+    AdminChannel, AdminReply, AdminRequest, ADMIN_REPLY_MSG_TYPE, ADMIN_REQUEST_MSG_TYPE,
+    ADMIN_TIMEOUT,
+};
+pub use approvals::{ApprovalRejection, Approvals, Approved, STORM_NODE_AUDIT_FILE};
+pub use apps::{AppRoute, AppRoutes, APP_LIVENESS_TIMEOUT, HEARTBEAT_INTERVAL};
+pub use attestations::{Attestations, ATTESTATION_MSG_TYPE, STORM_NODE_ATTESTATIONS_FILE};
 pub use config::Config;
 pub use daemons::Daemon;
+pub use dictionaries::{Dictionaries, DICT_TRAIN_SAMPLES, STORM_NODE_DICT_DIR};
+pub use entropy::{
+    AntiEntropy, ReplicaDigest, ReplicaSet, ENTROPY_DIGEST_MSG_TYPE, ENTROPY_SET_MSG_TYPE,
+};
+pub use events::EventDigests;
+pub use features::{PeerFeatures, FEATURES_MSG_TYPE};
+pub(crate) use gc::ChunkGc;
+pub use gc::STORM_NODE_GC_FILE;
+pub use index::ContainerIndex;
+pub use interests::{BloomFilter, InterestFilter, Interests, INTEREST_MSG_TYPE};
+pub use jobs::Jobs;
+pub(crate) use journal::checkpoint;
+pub use lazy::{DeferredChunks, LazyFetches, STORM_NODE_LAZY_FILE};
+pub use link::{MsgLink, P2P_QUEUE_LIMIT};
+pub use maintenance::{Maintenance, MaintenanceNotice, MAINTENANCE_MSG_TYPE};
+pub use manifest::{ManifestKey, STORM_NODE_MANIFEST_DIR, STORM_NODE_MANIFEST_KEY_FILE};
+pub use memory::{MemoryBudget, MEMORY_HIGH_WATERMARK, MEMORY_SAMPLE_INTERVAL, QUEUED_ENTRY_SIZE};
+pub use metrics::{
+    serve_metrics, MetricsConfig, MetricsFormat, MetricsRecorder, MetricsSnapshot,
+    UnknownMetricsFormat,
+};
 #[cfg(feature = "server")]
+pub use migrations::{
+    migrate, DataFormat, Migration, MigrationError, MIGRATIONS, STORM_NODE_FORMATS_FILE,
+};
+pub use moderation::{
+    DeclineNotice, Moderation, DECLINE_REASONS_LIMIT, DECLINE_REASON_MSG_TYPE, STORM_NODE_BANS_FILE,
+};
 pub use opts::Opts;
+pub use peers::{PeerMonitor, PROBE_INTERVAL};
+pub use pins::Pins;
+pub use policy::{Policy, PolicySubject, Verdict, POLICY_TIMEOUT};
+pub use polls::{EventPolls, EVENT_LOG_CAPACITY, MAX_WAIT_TIMEOUT};
+pub(crate) use popularity::unix_time;
+pub use popularity::{CachePolicy, Popularity, RequestKind};
+pub use quarantine::{Quarantine, Released};
+pub use quotas::{
+    EvictionPolicy, Quotas, StorageQuota, UnknownEvictionPolicy, STORM_NODE_QUOTAS_FILE,
+};
+pub use ratelimit::{RateLimiter, RATE_LIMIT_QUEUE};
+pub use registry::DaemonRegistry;
+pub use repair::{repair, DirtyMarker};
+pub use replicator::{
+    ReplicaAck, ReplicaRequest, Replicator, REPLICA_ACK_MSG_TYPE, REPLICA_REQUEST_MSG_TYPE,
+    REPLICA_TIMEOUT,
+};
+pub use requests::RequestCache;
+pub use resume::{Resumes, RESUME_ATTEMPTS, STORM_NODE_RESUME_FILE};
+pub(crate) use routes::PeerRoutes;
+pub use schedule::{
+    InvalidReplicationWindow, ReplicationSchedule, ReplicationWindow, BANDWIDTH_SAMPLE_INTERVAL,
+};
 pub use service::{run, Runtime};
+pub use sessions::{Sessions, SESSION_IDLE_TIMEOUT};
+pub use shares::ShareError;
+pub(crate) use shares::{Access, Shares};
+pub use shutdown::{watch_signals, Shutdown, SHUTDOWN_TIMEOUT};
+pub use sketch::{
+    SketchReply, SketchRequest, TopicSketch, TOPIC_DIFF_MSG_TYPE, TOPIC_SKETCH_MSG_TYPE,
+};
+pub use snapshot::IndexSnapshot;
+pub use stamps::{PowStamp, Stamps, MAX_HELD_MESSAGES, POW_STAMP_MSG_TYPE, STAMP_VALIDITY};
+pub use stats::Stats;
+pub use supervisor::{
+    Supervisor, DAEMON_HEARTBEAT_INTERVAL, DAEMON_LIVENESS_TIMEOUT, RESTART_BACKOFF,
+    RESTART_BACKOFF_LIMIT, RESTART_STABLE_PERIOD,
+};
+pub(crate) use topics::Topics;
+pub use webhooks::{WebhookConfig, Webhooks};
@@ -0,0 +1,129 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::{MesgId, StormApp};
+use storm_ext::DeclineReason;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::checkpoint;
+
+/// Name of the file inside the data directory keeping the banned peers.
+pub const STORM_NODE_BANS_FILE: &str = "bans.dat";
+
+/// Type of the node-level app-opaque message carrying [`DeclineNotice`].
+pub const DECLINE_REASON_MSG_TYPE: u16 = 0x000C;
+
+/// Maximal number of the decline reasons received from the remote peers which are kept until
+/// the declines themselves arrive.
+pub const DECLINE_REASONS_LIMIT: usize = 1024;
+
+/// Reason of a decline, sent to the remote peer right before the decline itself, since the
+/// Storm p2p protocol has no place for it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct DeclineNotice {
+    pub app: StormApp,
+    /// Id of the declined message, or the wire id of the declined topic
+    pub id: MesgId,
+    pub reason: DeclineReason,
+}
+
+/// Moderation of the posts and topic proposals of the remote peers.
+///
+/// Peers are banned by the node operator, or automatically once the apps decline the given
+/// number of their messages as spam or abuse. Posts and topic proposals of the banned peers are
+/// dropped before they reach the apps. Only the ban list is persisted; the spam counts start
+/// from zero after the node restart.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Moderation {
+    path: PathBuf,
+    banned: BTreeSet<NodeId>,
+    /// Number of spam declines after which the peer is banned; zero disables automatic bans
+    spam_threshold: u32,
+    /// Spam declines of the peers which are not banned yet
+    strikes: HashMap<NodeId, u32>,
+    /// Reasons sent by the remote peers for the declines which have not arrived yet
+    reasons: HashMap<(NodeId, StormApp, MesgId), DeclineReason>,
+}
+
+impl Moderation {
+    pub fn load(data_dir: &Path, spam_threshold: u32) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_BANS_FILE);
+        let banned =
+            if path.exists() { BTreeSet::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(Moderation {
+            path,
+            banned,
+            spam_threshold,
+            strikes: empty!(),
+            reasons: empty!(),
+        })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.banned)?;
+        Ok(())
+    }
+
+    pub fn is_banned(&self, peer: NodeId) -> bool { self.banned.contains(&peer) }
+
+    /// Bans or unbans the peer, returning whether the ban list was changed.
+    pub fn ban(&mut self, peer: NodeId, banned: bool) -> Result<bool, strict_encoding::Error> {
+        self.strikes.remove(&peer);
+        let changed = if banned { self.banned.insert(peer) } else { self.banned.remove(&peer) };
+        if changed {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+
+    pub fn banned(&self) -> &BTreeSet<NodeId> { &self.banned }
+
+    /// Registers decline of the message of the peer by an app, returning whether the peer is
+    /// banned as a result.
+    pub fn declined(
+        &mut self,
+        peer: NodeId,
+        reason: DeclineReason,
+    ) -> Result<bool, strict_encoding::Error> {
+        if !reason.is_spam() || self.spam_threshold == 0 || self.is_banned(peer) {
+            return Ok(false);
+        }
+        let strikes = self.strikes.entry(peer).or_default();
+        *strikes += 1;
+        if *strikes < self.spam_threshold {
+            return Ok(false);
+        }
+        self.ban(peer, true)
+    }
+
+    /// Keeps reason of the decline announced by the remote peer until the decline arrives.
+    pub fn reason_received(&mut self, peer: NodeId, notice: DeclineNotice) {
+        if self.reasons.len() >= DECLINE_REASONS_LIMIT {
+            debug!("Dropping decline reason from {}: too many reasons are pending", peer);
+            return;
+        }
+        self.reasons.insert((peer, notice.app, notice.id), notice.reason);
+    }
+
+    /// Returns reason announced by the remote peer for the decline which has arrived.
+    pub fn take_reason(
+        &mut self,
+        peer: NodeId,
+        app: StormApp,
+        id: MesgId,
+    ) -> Option<DeclineReason> {
+        self.reasons.remove(&(peer, app, id))
+    }
+}
@@ -8,7 +8,10 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use clap::Parser;
+use std::net::SocketAddr;
+
+use clap::{Parser, ValueHint};
+use internet2::addr::{NodeId, ServiceAddr};
 
 /// Lightning storm daemon; part of Storm Node.
 ///
@@ -25,6 +28,14 @@ pub struct Opts {
     #[clap(long)]
     pub chat: bool,
 
+    /// Run mail service.
+    #[clap(long)]
+    pub mail: bool,
+
+    /// Run public board service.
+    #[clap(long)]
+    pub board: bool,
+
     /// Run downpour (torrent-like) service.
     #[clap(long)]
     pub downpour: bool,
@@ -32,6 +43,312 @@ pub struct Opts {
     /// Spawn daemons as threads and not processes
     #[clap(short = 'T', long = "threaded")]
     pub threaded_daemons: bool,
+
+    /// Maximum number of containers cached on behalf of other peers.
+    ///
+    /// Zero disables caching of containers which were not explicitly requested.
+    #[clap(long, default_value = "0")]
+    pub cache_size: u32,
+
+    /// Number of requests a container must receive before it is admitted into the cache.
+    #[clap(long, default_value = "3")]
+    pub cache_admission: u32,
+
+    /// Automatically pin containers after they were requested given number of times.
+    #[clap(long)]
+    pub auto_pin: Option<u32>,
+
+    /// ZMQ sockets of additional peer connection services.
+    ///
+    /// Large nodes may shard peer connections across several LNP node or peerd processes, each of
+    /// which exposes its own message bus. The argument can be given multiple times.
+    #[clap(long = "peer-msg", multiple_occurrences = true, value_hint = ValueHint::FilePath)]
+    pub peer_msg_endpoints: Vec<ServiceAddr>,
+
+    /// Record periodic snapshots of the node metrics into a file in the data directory.
+    ///
+    /// Possible formats are `csv` and `jsonl` (one JSON object per line). The file is rotated
+    /// once it grows over 8 MiB.
+    #[clap(long, possible_values = &["csv", "jsonl"])]
+    pub metrics_log: Option<String>,
+
+    /// Interval between the metrics snapshots, in seconds.
+    #[clap(long, default_value = "60")]
+    pub metrics_interval: u64,
+
+    /// Serve the node metrics in the Prometheus text format on `GET /metrics` at the given
+    /// address, e.g. `127.0.0.1:9464`.
+    #[clap(long)]
+    pub metrics_endpoint: Option<SocketAddr>,
+
+    /// Batch events of the given class into periodic digests instead of sending them to the
+    /// extensions one by one.
+    ///
+    /// The argument can be given multiple times.
+    #[clap(
+        long,
+        multiple_occurrences = true,
+        possible_values = &["transfer", "cache", "quarantine"]
+    )]
+    pub event_digest: Vec<String>,
+
+    /// Interval between the event digests, in seconds.
+    #[clap(long, default_value = "300")]
+    pub digest_interval: u64,
+
+    /// Post messages received by an app to a webhook, in `APP[/TOPIC]=URL` format.
+    ///
+    /// `APP` is the numeric app id, and `TOPIC` is the hex id of the topic limiting the posted
+    /// messages to its replies. Only plain `http://` URLs are supported. The argument can be
+    /// given multiple times.
+    #[clap(long, multiple_occurrences = true, validator = validate_webhook)]
+    pub webhook: Vec<String>,
+
+    /// Secret used to sign the webhook requests with HMAC-SHA256.
+    #[clap(long, env = "STORM_WEBHOOK_SECRET", hide_env_values = true)]
+    pub webhook_secret: Option<String>,
+
+    /// Program deciding on the messages and topics received from the remote peers.
+    ///
+    /// The program is run for each post and topic proposal with the JSON description of the
+    /// message given to its standard input, and must print `accept`, `decline` or `pass` to its
+    /// standard output. Accepted and declined messages are answered by the node on behalf of the
+    /// app; declined messages are not delivered to the app.
+    #[clap(long, value_hint = ValueHint::ExecutablePath)]
+    pub policy: Option<String>,
+
+    /// LNP node id (public key) of this node.
+    ///
+    /// Proof-of-work stamps presented by the remote peers are accepted only if they were minted
+    /// for this node id.
+    #[clap(long)]
+    pub node_id: Option<NodeId>,
+
+    /// Require unknown peers to present a proof-of-work stamp with the given number of leading
+    /// zero bits before their messages are delivered to the apps.
+    ///
+    /// Peers contacted by this node first are not required to present the stamp. Requires
+    /// `--node-id`.
+    #[clap(long, validator = validate_pow_bits, requires = "node-id")]
+    pub pow_difficulty: Option<u8>,
+
+    /// Present a proof-of-work stamp with the given number of leading zero bits to each remote
+    /// peer before the first message sent to it.
+    ///
+    /// Each additional bit doubles the time the stamp takes to mint.
+    #[clap(long, validator = validate_pow_bits)]
+    pub pow_stamp: Option<u8>,
+
+    /// Send attestation of the node software version and features, signed with the node manifest
+    /// key, to the remote peers.
+    #[clap(long)]
+    pub attest: bool,
+
+    /// Run as a read-only replica, e.g. a public distribution mirror.
+    ///
+    /// The node serves the hosted containers and answers topic queries, but refuses new pins and
+    /// the containers pushed, posts and topics proposed by the remote peers. Content is retrieved
+    /// only on the requests of the node operator and from the replication partners given with
+    /// `--replica`. The mode is advertised to the remote peers.
+    #[clap(long)]
+    pub read_only: bool,
+
+    /// Keep replicas of the containers on the requests of the remote peers.
+    ///
+    /// The node retrieves the containers it is asked to replicate, pins them and confirms the
+    /// replicas to the requesting peers. Requests of the replication partners given with
+    /// `--replica` are served even without this flag. The mode is advertised to the remote
+    /// peers.
+    #[clap(long, conflicts_with = "read-only")]
+    pub storage_peer: bool,
+
+    /// Rebuild the container index from the container manifests kept in the store, discarding
+    /// the index saved in the data directory.
+    ///
+    /// Apps and peers of the containers are lost, so this recovers nodes whose index was lost or
+    /// corrupted.
+    #[clap(long)]
+    pub rebuild_index: bool,
+
+    /// Run bulk replication (container transfers of downpourd and syncd) only during the given
+    /// daily hours, in `START-END` format with UTC hours, like `22-6`.
+    ///
+    /// Transfers requested outside of the window are deferred until it opens.
+    #[clap(long, validator = validate_replication_window)]
+    pub replication_window: Option<String>,
+
+    /// Replication partner, given as the node id (public key), with which the pinned containers
+    /// and the app topics are periodically reconciled.
+    ///
+    /// Containers and topics missing on this node are retrieved from the partner. The argument
+    /// can be given multiple times.
+    #[clap(long, multiple_occurrences = true)]
+    pub replica: Vec<NodeId>,
+
+    /// Node of the operator allowed to manage this node over the Storm protocol, querying its
+    /// status, pinning and replicating containers.
+    ///
+    /// The argument can be given multiple times.
+    #[clap(long, multiple_occurrences = true)]
+    pub admin: Vec<NodeId>,
+
+    /// Operator key, given as the public key, which may approve the sensitive admin actions on
+    /// the node co-operated by several operators.
+    ///
+    /// The argument can be given multiple times.
+    #[clap(long, multiple_occurrences = true)]
+    pub approver: Vec<NodeId>,
+
+    /// Number of the approver keys which must sign the destructive admin actions before they are
+    /// executed: eviction and unpinning of containers, garbage collection, rebuild of the
+    /// container index, revocation of share tokens and manifest key rotation.
+    ///
+    /// The number can't exceed the number of the `--approver` keys.
+    ///
+    /// With zero the actions are executed as they are requested.
+    #[clap(long, default_value = "0")]
+    pub approvals: u8,
+
+    /// Interval between the reconciliations with the replication partners, in seconds.
+    #[clap(long, default_value = "600")]
+    pub anti_entropy_interval: u64,
+
+    /// Pause bulk replication while the node traffic is above the given number of bytes per
+    /// second.
+    #[clap(long)]
+    pub replication_bandwidth: Option<u64>,
+
+    /// ZMQ socket on which the node publishes events for the subscribed clients, conventionally
+    /// `0.0.0.0:64966`.
+    ///
+    /// Clients subscribe to the event kinds they are interested in over RPC and receive the
+    /// transfer completions, received messages and proposed topics without polling.
+    #[clap(long = "notify", value_hint = ValueHint::FilePath)]
+    pub notify_endpoint: Option<ServiceAddr>,
+
+    /// Reconcile the app topics with the remote peers using sketches sized for the given number
+    /// of differences, up to 900, instead of exchanging the full topic lists.
+    ///
+    /// The traffic depends on the number of differences and not on the number of topics. If
+    /// the topics differ more, the full topic lists are exchanged. Remote peers must support node
+    /// messages of this version.
+    #[clap(long, validator = validate_sketch_capacity)]
+    pub topic_sketch: Option<u16>,
+
+    /// Maximum number of unfinished background jobs a single RPC client may have.
+    ///
+    /// Jobs submitted by a client above the limit are refused until some of its jobs finish.
+    #[clap(long, default_value = "16")]
+    pub max_client_jobs: u16,
+
+    /// Maximum size of all the containers stored by the node, in bytes.
+    ///
+    /// Once the quota is exceeded, containers are evicted according to the eviction policy.
+    #[clap(long)]
+    pub quota: Option<u64>,
+
+    /// Maximum size of the containers retrieved from a single remote peer, in bytes.
+    #[clap(long)]
+    pub peer_quota: Option<u64>,
+
+    /// Policy selecting the containers evicted once a storage quota is exceeded.
+    ///
+    /// With `lru` the least recently used containers which are not pinned are evicted. With
+    /// `explicit` containers are removed only by the node operator, and a retrieved container
+    /// exceeding the quota is evicted right away. Pinned containers are never evicted.
+    #[clap(long, default_value = "lru", possible_values = &["lru", "explicit"])]
+    pub eviction: String,
+
+    /// Number of seconds a chunk must stay unreferenced by any of the stored containers before
+    /// it is collected.
+    ///
+    /// The period protects chunks of the container transfers in progress.
+    #[clap(long, default_value = "86400")]
+    pub gc_grace: u64,
+
+    /// Interval between the garbage collections of the unreferenced chunks, in seconds.
+    ///
+    /// Zero disables automatic collection; it may still be triggered over RPC.
+    #[clap(long, default_value = "3600")]
+    pub gc_interval: u64,
+
+    /// Number of posts and topic proposals of a remote peer which the apps may decline as spam
+    /// or abuse before the peer is banned.
+    ///
+    /// Zero disables automatic bans; peers may still be banned over RPC.
+    #[clap(long, default_value = "3")]
+    pub spam_ban: u32,
+
+    /// Limit the rate of the chunks sent to all the remote peers together, in bytes per second.
+    #[clap(long)]
+    pub rate_limit_bytes: Option<u64>,
+
+    /// Limit the rate of the chunks sent to all the remote peers together, in chunks per second.
+    #[clap(long)]
+    pub rate_limit_chunks: Option<u32>,
+
+    /// Limit the rate of the chunks sent to each of the remote peers, in bytes per second.
+    ///
+    /// Limits of the individual peers may be changed over RPC.
+    #[clap(long)]
+    pub peer_rate_limit_bytes: Option<u64>,
+
+    /// Limit the rate of the chunks sent to each of the remote peers, in chunks per second.
+    #[clap(long)]
+    pub peer_rate_limit_chunks: Option<u32>,
+
+    /// Memory budget of the node, in bytes.
+    ///
+    /// Once the memory used by the caches, buffers and queues of the daemons approaches the
+    /// budget the node shrinks its caches; above the budget it stops starting new transfers
+    /// until the memory use goes down.
+    #[clap(long)]
+    pub memory_budget: Option<u64>,
+}
+
+fn validate_sketch_capacity(capacity: &str) -> Result<(), String> {
+    match capacity.parse::<u16>() {
+        Ok(1..=900) => Ok(()),
+        _ => Err(format!("invalid topic sketch capacity `{}`; must be 1 to 900", capacity)),
+    }
+}
+
+fn validate_replication_window(spec: &str) -> Result<(), String> {
+    let err = || format!("replication window `{}` must be given as `START-END` hours", spec);
+    let (start, end) = spec.split_once('-').ok_or_else(err)?;
+    let start = start.parse::<u8>().map_err(|_| err())?;
+    let end = end.parse::<u8>().map_err(|_| err())?;
+    if start > 24 || end > 24 || start == end {
+        return Err(err());
+    }
+    Ok(())
+}
+
+fn validate_pow_bits(bits: &str) -> Result<(), String> {
+    match bits.parse::<u8>() {
+        // Each bit doubles the minting time, which takes seconds already at the upper limit
+        Ok(1..=24) => Ok(()),
+        _ => Err(format!("invalid proof-of-work difficulty `{}`; must be 1 to 24 bits", bits)),
+    }
+}
+
+fn validate_webhook(spec: &str) -> Result<(), String> {
+    let (target, url) = spec.split_once('=').ok_or("webhook must be given as `APP[/TOPIC]=URL`")?;
+    let (app, topic) = match target.split_once('/') {
+        Some((app, topic)) => (app, Some(topic)),
+        None => (target, None),
+    };
+    app.parse::<u16>().map_err(|_| format!("invalid webhook app id `{}`", app))?;
+    match topic {
+        Some(topic) if topic.len() != 64 || !topic.chars().all(|c| c.is_ascii_hexdigit()) => {
+            return Err(format!("invalid webhook topic id `{}`", topic));
+        }
+        _ => {}
+    }
+    if !url.starts_with("http://") {
+        return Err(format!("unsupported webhook URL `{}`; only http:// URLs are supported", url));
+    }
+    Ok(())
 }
 
 #[cfg(feature = "server")]
@@ -0,0 +1,39 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use storm::ContainerId;
+use storm_rpc::{Patch, PATCH_MIME};
+use strict_encoding::StrictDecode;
+
+use crate::containers::{assemble, header, manifest, store_container};
+use crate::DaemonError;
+
+/// Reconstructs container from the patch container, if the container is a patch, returning the
+/// id of the reconstructed container.
+///
+/// The reconstructed container is saved into the store only if its id matches the patch target.
+pub fn reconstruct(
+    store: &mut store_rpc::Client,
+    container_id: ContainerId,
+) -> Result<Option<ContainerId>, DaemonError> {
+    if header(store, container_id)?.mime.as_str() != PATCH_MIME {
+        return Ok(None);
+    }
+    let patch = Patch::strict_deserialize(assemble(store, container_id)?)?;
+    let base = assemble(store, patch.base)?;
+    let data = patch.apply(&base)?;
+    let container = manifest(patch.header.clone(), &data)?;
+    let target = container.container_id();
+    if target != patch.target {
+        return Err(DaemonError::PatchMismatch(patch.target, target));
+    }
+    store_container(store, &container, &data)?;
+    Ok(Some(target))
+}
@@ -0,0 +1,150 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use internet2::addr::NodeId;
+use storm_rpc::PeerStats;
+
+/// Minimal interval between latency probes sent to the same peer.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time after which an unanswered probe is considered lost.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of consecutive lost probes after which the peer is not probed until it shows activity.
+const MAX_LOST_PROBES: u8 = 3;
+
+/// Duration of the window over which chunk transfer throughput is sampled.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+struct PeerState {
+    stats: PeerStats,
+    last_probe: Option<Instant>,
+    pending_probe: Option<Instant>,
+    lost_probes: u8,
+    /// Start of the current throughput sampling window and number of bytes received within it
+    window: Option<(Instant, u64)>,
+}
+
+/// Measures latency and throughput of the remote peers.
+///
+/// Latency is measured with the lightweight `ListApps` request, which is answered by each Storm
+/// node with `ActiveApps`. Since the controller has no timers, the probes are sent when the node
+/// handles other messages, but not more frequently than [`PROBE_INTERVAL`]. Throughput is
+/// sampled from the chunks received during the transfers, which also reveal the peers sending
+/// corrupt chunks.
+#[derive(Clone, Debug, Default)]
+pub struct PeerMonitor {
+    peers: BTreeMap<NodeId, PeerState>,
+}
+
+/// Exponentially-weighted moving average with 1/4 weight of the new sample.
+fn smooth(prev: Option<u64>, sample: u64) -> u64 {
+    match prev {
+        Some(prev) => (prev * 3 + sample) / 4,
+        None => sample,
+    }
+}
+
+impl PeerMonitor {
+    fn state(&mut self, remote_id: NodeId) -> &mut PeerState {
+        self.peers.entry(remote_id).or_insert_with(|| PeerState {
+            stats: PeerStats {
+                remote_id,
+                rtt: None,
+                throughput: None,
+                probes: 0,
+                replies: 0,
+                corrupt_chunks: 0,
+            },
+            last_probe: None,
+            pending_probe: None,
+            lost_probes: 0,
+            window: None,
+        })
+    }
+
+    /// Registers activity of the peer, making it eligible for probing.
+    pub fn seen(&mut self, remote_id: NodeId) { self.state(remote_id).lost_probes = 0; }
+
+    /// Returns peers which are due for a latency probe, marking probes as sent.
+    pub fn probes_due(&mut self) -> Vec<NodeId> {
+        let now = Instant::now();
+        let mut due = vec![];
+        for peer in self.peers.values_mut() {
+            match peer.pending_probe {
+                Some(sent) if now - sent < PROBE_TIMEOUT => continue,
+                Some(_) => {
+                    peer.pending_probe = None;
+                    peer.lost_probes = peer.lost_probes.saturating_add(1);
+                }
+                None => {}
+            }
+            if peer.lost_probes >= MAX_LOST_PROBES
+                || peer.last_probe.map_or(false, |last| now - last < PROBE_INTERVAL)
+            {
+                continue;
+            }
+            peer.last_probe = Some(now);
+            peer.pending_probe = Some(now);
+            peer.stats.probes += 1;
+            due.push(peer.stats.remote_id);
+        }
+        due
+    }
+
+    /// Registers reply to the latency probe.
+    pub fn echo(&mut self, remote_id: NodeId) {
+        let peer = self.state(remote_id);
+        if let Some(sent) = peer.pending_probe.take() {
+            let rtt = sent.elapsed().as_millis().min(u32::MAX as u128) as u64;
+            peer.stats.rtt = Some(smooth(peer.stats.rtt.map(u64::from), rtt) as u32);
+            peer.stats.replies += 1;
+        }
+    }
+
+    /// Registers chunk data received from the peer during a transfer.
+    pub fn received(&mut self, remote_id: NodeId, bytes: usize) {
+        let now = Instant::now();
+        let peer = self.state(remote_id);
+        let (start, total) = peer.window.get_or_insert((now, 0));
+        *total += bytes as u64;
+        let elapsed = now - *start;
+        if elapsed >= THROUGHPUT_WINDOW {
+            let sample = *total * 1000 / elapsed.as_millis().max(1) as u64;
+            peer.stats.throughput = Some(smooth(peer.stats.throughput, sample));
+            peer.window = None;
+        }
+    }
+
+    /// Registers chunk received from the peer which has failed the integrity verification.
+    pub fn corrupt(&mut self, remote_id: NodeId) {
+        let stats = &mut self.state(remote_id).stats;
+        stats.corrupt_chunks = stats.corrupt_chunks.saturating_add(1);
+    }
+
+    /// Selects the best-performing peer among the candidates: with the highest throughput, or
+    /// the lowest latency if the throughput was not measured. Peers with no measurements are
+    /// selected last, followed by the peers which have sent corrupt chunks.
+    pub fn best(&self, candidates: impl IntoIterator<Item = NodeId>) -> Option<NodeId> {
+        candidates.into_iter().min_by_key(|remote_id| {
+            let stats = self.peers.get(remote_id).map(|peer| peer.stats);
+            let corrupt = stats.map_or(false, |stats| stats.corrupt_chunks > 0);
+            let throughput = stats.and_then(|stats| stats.throughput);
+            let rtt = stats.and_then(|stats| stats.rtt);
+            (corrupt, throughput.is_none(), std::cmp::Reverse(throughput), rtt.is_none(), rtt)
+        })
+    }
+
+    pub fn stats(&self) -> Vec<PeerStats> { self.peers.values().map(|peer| peer.stats).collect() }
+}
@@ -0,0 +1,112 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use storm::ContainerId;
+use strict_encoding::StrictDecode;
+
+use super::journal::{checkpoint, Journal};
+
+/// Name of the file inside the data directory keeping the set of pinned containers.
+pub const STORM_NODE_PINS_FILE: &str = "pins.dat";
+
+/// Name of the file inside the data directory keeping the journal of the pin set changes.
+pub const STORM_NODE_PINS_JOURNAL: &str = "pins.wal";
+
+/// Change of the pin set recorded in the journal.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+enum PinChange {
+    Pin(BTreeSet<ContainerId>),
+    Unpin(BTreeSet<ContainerId>),
+}
+
+/// Set of containers pinned by the node operator or automatically, persisted in the data
+/// directory.
+///
+/// Changes of the set are written to a journal, which is replayed over the set checkpoint when
+/// the node starts.
+#[derive(Debug)]
+pub struct Pins {
+    path: PathBuf,
+    pins: Arc<BTreeSet<ContainerId>>,
+    journal: Journal<PinChange>,
+}
+
+impl Pins {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_PINS_FILE);
+        let mut pins =
+            if path.exists() { BTreeSet::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        let (journal, changes) = Journal::open(&data_dir.join(STORM_NODE_PINS_JOURNAL))?;
+        for change in changes {
+            match change {
+                PinChange::Pin(ids) => pins.extend(ids),
+                PinChange::Unpin(ids) => pins.retain(|id| !ids.contains(id)),
+            }
+        }
+        Ok(Pins {
+            path,
+            pins: Arc::new(pins),
+            journal,
+        })
+    }
+
+    /// Journals the change and replaces the pin set with its changed version.
+    fn apply(
+        &mut self,
+        change: PinChange,
+        pins: BTreeSet<ContainerId>,
+    ) -> Result<(), strict_encoding::Error> {
+        self.journal.append(&change)?;
+        self.pins = Arc::new(pins);
+        if self.journal.is_checkpoint_due() {
+            checkpoint(&self.path, self.pins.as_ref())?;
+            self.journal.truncate()?;
+        }
+        Ok(())
+    }
+
+    /// Takes point-in-time snapshot of the pin set.
+    pub fn snapshot(&self) -> Arc<BTreeSet<ContainerId>> { self.pins.clone() }
+
+    pub fn iter(&self) -> impl Iterator<Item = ContainerId> + '_ { self.pins.iter().copied() }
+
+    /// Adds pins to the containers, returning per-container flags whether the pin is new.
+    pub fn pin(
+        &mut self,
+        container_ids: impl IntoIterator<Item = ContainerId>,
+    ) -> Result<Vec<bool>, strict_encoding::Error> {
+        let container_ids = container_ids.into_iter().collect::<Vec<_>>();
+        let mut pins = self.pins.as_ref().clone();
+        let added = container_ids.iter().map(|id| pins.insert(*id)).collect::<Vec<_>>();
+        if added.contains(&true) {
+            self.apply(PinChange::Pin(container_ids.into_iter().collect()), pins)?;
+        }
+        Ok(added)
+    }
+
+    /// Removes pins from the containers, returning per-container flags whether the pin existed.
+    pub fn unpin(
+        &mut self,
+        container_ids: impl IntoIterator<Item = ContainerId>,
+    ) -> Result<Vec<bool>, strict_encoding::Error> {
+        let container_ids = container_ids.into_iter().collect::<Vec<_>>();
+        let mut pins = self.pins.as_ref().clone();
+        let removed = container_ids.iter().map(|id| pins.remove(id)).collect::<Vec<_>>();
+        if removed.contains(&true) {
+            self.apply(PinChange::Unpin(container_ids.into_iter().collect()), pins)?;
+        }
+        Ok(removed)
+    }
+}
@@ -0,0 +1,143 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use internet2::addr::NodeId;
+use storm::{Mesg, StormApp, Topic};
+use storm_rpc::TopicId;
+
+use super::popularity::unix_time;
+
+/// Time the policy program is given to return its verdict, after which the message is passed to
+/// the app.
+pub const POLICY_TIMEOUT: Duration = Duration::from_secs(2);
+
+const POLICY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Decision of the policy program on a message received from a remote peer.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Verdict {
+    /// The node accepts the message on behalf of the app and delivers it to the app.
+    Accept,
+
+    /// The node declines the message on behalf of the app, which does not receive it.
+    Decline,
+
+    /// The message is delivered to the app, which decides on it.
+    Pass,
+}
+
+/// Message on which the policy program decides.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PolicySubject<'a> {
+    Post(&'a Mesg),
+    Topic(&'a Topic),
+}
+
+/// Policy program deciding whether the messages and topics received from the remote peers are
+/// accepted or declined.
+///
+/// The program is run for each message with JSON rendering of the message context written to
+/// its standard input, and must print `accept`, `decline` or `pass` to its standard output. The
+/// node waits for the verdict up to [`POLICY_TIMEOUT`]; failing and slow programs pass the
+/// messages to the apps.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Policy {
+    program: PathBuf,
+}
+
+impl Policy {
+    pub fn with(program: PathBuf) -> Self { Policy { program } }
+
+    pub fn judge(&self, app: StormApp, remote_id: NodeId, subject: PolicySubject) -> Verdict {
+        let context = render(app, remote_id, subject);
+        match self.run(&context) {
+            Ok(output) => match output.trim() {
+                "accept" => Verdict::Accept,
+                "decline" => Verdict::Decline,
+                "pass" => Verdict::Pass,
+                other => {
+                    warn!("Policy program {} replied with `{}`", self.program.display(), other);
+                    Verdict::Pass
+                }
+            },
+            Err(err) => {
+                warn!("Unable to run policy program {}: {}", self.program.display(), err);
+                Verdict::Pass
+            }
+        }
+    }
+
+    fn run(&self, context: &str) -> Result<String, io::Error> {
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(context.as_bytes())?;
+        }
+        let started = Instant::now();
+        while child.try_wait()?.is_none() {
+            if started.elapsed() > POLICY_TIMEOUT {
+                child.kill()?;
+                child.wait()?;
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "no verdict was returned"));
+            }
+            thread::sleep(POLICY_POLL_INTERVAL);
+        }
+        let mut output = String::new();
+        if let Some(mut stdout) = child.stdout.take() {
+            stdout.read_to_string(&mut output)?;
+        }
+        Ok(output)
+    }
+}
+
+fn render(app: StormApp, remote_id: NodeId, subject: PolicySubject) -> String {
+    let (kind, id, parent_id, size, container_ids) = match subject {
+        PolicySubject::Post(mesg) => (
+            "post",
+            mesg.mesg_id().to_string(),
+            Some(mesg.parent_id.to_string()),
+            mesg.body.len(),
+            &mesg.container_ids,
+        ),
+        PolicySubject::Topic(topic) => (
+            "topic",
+            TopicId::from(topic).to_string(),
+            None,
+            topic.body.len(),
+            &topic.container_ids,
+        ),
+    };
+    let container_ids = container_ids.iter().map(|id| format!("\"{}\"", id)).collect::<Vec<_>>();
+    let mut fields = vec![
+        ("timestamp", unix_time().to_string()),
+        ("kind", format!("\"{}\"", kind)),
+        ("app", format!("\"{}\"", app)),
+        ("remote_id", format!("\"{}\"", remote_id)),
+        ("id", format!("\"{}\"", id)),
+        ("size", size.to_string()),
+        ("container_ids", format!("[{}]", container_ids.join(","))),
+    ];
+    if let Some(parent_id) = parent_id {
+        fields.push(("parent_id", format!("\"{}\"", parent_id)));
+    }
+    let fields =
+        fields.iter().map(|(name, value)| format!("\"{}\":{}", name, value)).collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
@@ -0,0 +1,121 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use microservices::esb::ClientId;
+use storm_rpc::{Event, EventFilter, PolledEvents, WaitEvent};
+
+/// Number of the recently published events kept for the long-poll requests.
+pub const EVENT_LOG_CAPACITY: usize = 1024;
+
+/// Maximal time a long-poll request waits for the events.
+pub const MAX_WAIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Waiter {
+    filter: EventFilter,
+    deadline: Instant,
+}
+
+/// Long-poll requests of the clients waiting for the node events.
+///
+/// Events are numbered in the order they are published, starting anew with each daemon start;
+/// the cursor exceeding the number of the last event is considered to be issued before the
+/// restart, so all the recent events are returned for it. Each client has at most one request
+/// waiting.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct EventPolls {
+    /// Sequence number of the last published event
+    seq: u64,
+    /// Recently published events with their sequence numbers
+    log: VecDeque<(u64, Event)>,
+    waiters: BTreeMap<ClientId, Waiter>,
+}
+
+impl EventPolls {
+    /// Registers long-poll request of the client, returning the events to reply with right away
+    /// if there are matching events after the cursor or the request does not wait.
+    pub fn wait(&mut self, client_id: ClientId, request: WaitEvent) -> Option<PolledEvents> {
+        let since = match request.since {
+            Some(since) if since <= self.seq => since,
+            Some(_) => 0,
+            None => self.seq,
+        };
+        let events = self
+            .log
+            .iter()
+            .filter(|(seq, event)| *seq > since && request.filter.matches(event))
+            .map(|(_, event)| event.clone())
+            .collect::<Vec<_>>();
+        if !events.is_empty() || request.timeout == 0 {
+            return Some(PolledEvents {
+                cursor: self.seq,
+                events,
+            });
+        }
+        let timeout = Duration::from_secs(request.timeout as u64).min(MAX_WAIT_TIMEOUT);
+        self.waiters.insert(client_id, Waiter {
+            filter: request.filter,
+            deadline: Instant::now() + timeout,
+        });
+        None
+    }
+
+    /// Records published event, returning replies to the requests waiting for it.
+    pub fn publish(&mut self, event: &Event) -> Vec<(ClientId, PolledEvents)> {
+        self.seq += 1;
+        if self.log.len() >= EVENT_LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back((self.seq, event.clone()));
+
+        let ready = self
+            .waiters
+            .iter()
+            .filter(|(_, waiter)| waiter.filter.matches(event))
+            .map(|(client_id, _)| *client_id)
+            .collect::<Vec<_>>();
+        ready
+            .into_iter()
+            .map(|client_id| {
+                self.waiters.remove(&client_id);
+                (client_id, PolledEvents {
+                    cursor: self.seq,
+                    events: vec![event.clone()],
+                })
+            })
+            .collect()
+    }
+
+    /// Removes requests which have timed out, returning empty replies to them.
+    pub fn expire(&mut self) -> Vec<(ClientId, PolledEvents)> {
+        let now = Instant::now();
+        let expired = self
+            .waiters
+            .iter()
+            .filter(|(_, waiter)| waiter.deadline <= now)
+            .map(|(client_id, _)| *client_id)
+            .collect::<Vec<_>>();
+        expired
+            .into_iter()
+            .map(|client_id| {
+                self.waiters.remove(&client_id);
+                (client_id, PolledEvents {
+                    cursor: self.seq,
+                    events: vec![],
+                })
+            })
+            .collect()
+    }
+
+    pub fn forget(&mut self, client_id: ClientId) { self.waiters.remove(&client_id); }
+}
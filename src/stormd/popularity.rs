@@ -0,0 +1,243 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::ContainerStats;
+
+/// Number of seconds after which the weight of past requests is halved when computing container
+/// popularity score.
+const POPULARITY_HALF_LIFE: u64 = 3600;
+
+/// Cache admission, eviction and auto-pinning policy for the containers which are kept by the
+/// node on behalf of other peers.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct CachePolicy {
+    /// Maximum number of containers kept in cache; zero disables caching.
+    pub cache_size: u32,
+    /// Number of requests a container must receive before being admitted into the cache.
+    pub admission_threshold: u32,
+    /// Number of requests after which a container gets automatically pinned.
+    pub auto_pin_threshold: Option<u32>,
+}
+
+/// Kind of the tracked container request.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+pub enum RequestKind {
+    #[display("container")]
+    Container,
+
+    #[display("chunk")]
+    Chunk,
+}
+
+/// Tracks request frequency per container and makes cache decisions from it.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Popularity {
+    stats: BTreeMap<ContainerId, ContainerStats>,
+    /// Peers which announced a container; used as sources for cache admission.
+    sources: HashMap<ContainerId, BTreeSet<NodeId>>,
+}
+
+pub(crate) fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+impl Popularity {
+    fn entry(&mut self, container_id: ContainerId) -> &mut ContainerStats {
+        self.stats.entry(container_id).or_insert(ContainerStats {
+            container_id,
+            requests: 0,
+            chunk_requests: 0,
+            last_request: 0,
+            cached: false,
+            pinned: false,
+        })
+    }
+
+    pub fn record(&mut self, container_id: ContainerId, kind: RequestKind) -> ContainerStats {
+        let stats = self.entry(container_id);
+        match kind {
+            RequestKind::Container => stats.requests += 1,
+            RequestKind::Chunk => stats.chunk_requests += 1,
+        }
+        stats.last_request = unix_time();
+        trace!("Container {} got {} request; {}", container_id, kind, stats);
+        *stats
+    }
+
+    pub fn register_source(&mut self, container_id: ContainerId, remote_id: NodeId) {
+        self.sources.entry(container_id).or_default().insert(remote_id);
+    }
+
+    pub fn sources(&self, container_id: ContainerId) -> impl Iterator<Item = NodeId> + '_ {
+        self.sources.get(&container_id).into_iter().flatten().copied()
+    }
+
+    /// Decides whether a container should be brought into the cache.
+    pub fn should_admit(&self, container_id: ContainerId, policy: CachePolicy) -> bool {
+        policy.cache_size > 0
+            && self.stats.get(&container_id).map_or(false, |stats| {
+                !stats.cached && stats.requests >= policy.admission_threshold as u64
+            })
+    }
+
+    /// Marks container as cached, choosing the least popular non-pinned containers for eviction
+    /// if the cache exceeds its capacity. Returns list of the containers which have to be evicted
+    /// from the store.
+    pub fn admit(&mut self, container_id: ContainerId, policy: CachePolicy) -> Vec<ContainerId> {
+        if let Some(stats) = self.stats.get_mut(&container_id) {
+            stats.cached = true;
+        }
+
+        let now = unix_time();
+        let mut candidates = self
+            .stats
+            .values()
+            .filter(|stats| stats.cached && !stats.pinned && stats.container_id != container_id)
+            .map(|stats| (score(stats, now), stats.container_id))
+            .collect::<Vec<_>>();
+        candidates.sort();
+
+        let cached = self.stats.values().filter(|stats| stats.cached).count();
+        let excess = cached.saturating_sub(policy.cache_size as usize);
+        let evicted = candidates.into_iter().take(excess).map(|(_, id)| id).collect::<Vec<_>>();
+        for id in &evicted {
+            if let Some(stats) = self.stats.get_mut(id) {
+                stats.cached = false;
+            }
+        }
+        evicted
+    }
+
+    /// Pins the container if it has reached auto-pinning threshold. Returns `true` if the
+    /// container was pinned by this call.
+    pub fn auto_pin(&mut self, container_id: ContainerId, policy: CachePolicy) -> bool {
+        let threshold = match policy.auto_pin_threshold {
+            Some(threshold) => threshold as u64,
+            None => return false,
+        };
+        match self.stats.get_mut(&container_id) {
+            Some(stats) if !stats.pinned && stats.requests >= threshold => {
+                stats.pinned = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// UNIX timestamp of the last request of the container, or zero if it was never requested.
+    pub fn last_request(&self, container_id: ContainerId) -> u64 {
+        self.stats.get(&container_id).map(|stats| stats.last_request).unwrap_or_default()
+    }
+
+    pub fn set_pinned(&mut self, container_id: ContainerId, pinned: bool) {
+        self.entry(container_id).pinned = pinned;
+    }
+
+    /// Registers eviction of the container from the store.
+    pub fn evicted(&mut self, container_id: ContainerId) {
+        if let Some(stats) = self.stats.get_mut(&container_id) {
+            stats.cached = false;
+        }
+    }
+
+    /// Returns statistics for all known containers, most popular first.
+    pub fn stats(&self) -> Vec<ContainerStats> {
+        let now = unix_time();
+        let mut stats = self.stats.values().copied().collect::<Vec<_>>();
+        stats.sort_by_key(|stats| std::cmp::Reverse(score(stats, now)));
+        stats
+    }
+}
+
+/// Popularity score of a container: number of requests decaying with the age of the last request.
+fn score(stats: &ContainerStats, now: u64) -> u64 {
+    let age = now.saturating_sub(stats.last_request);
+    let halvings = (age / POPULARITY_HALF_LIFE).min(63) as u32;
+    (stats.requests.saturating_mul(2) + stats.chunk_requests) >> halvings
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::Wrapper;
+    use bitcoin_hashes::{sha256t, Hash};
+
+    use super::*;
+
+    fn container(no: u8) -> ContainerId {
+        ContainerId::from_inner(sha256t::Hash::from_inner([no; 32]))
+    }
+
+    fn policy(cache_size: u32) -> CachePolicy {
+        CachePolicy {
+            cache_size,
+            admission_threshold: 2,
+            auto_pin_threshold: Some(5),
+        }
+    }
+
+    fn request(popularity: &mut Popularity, container_id: ContainerId, times: u32) {
+        for _ in 0..times {
+            popularity.record(container_id, RequestKind::Container);
+        }
+    }
+
+    #[test]
+    fn admission_threshold() {
+        let mut popularity = Popularity::default();
+        request(&mut popularity, container(1), 1);
+        assert!(!popularity.should_admit(container(1), policy(2)));
+        request(&mut popularity, container(1), 1);
+        assert!(popularity.should_admit(container(1), policy(2)));
+        assert!(!popularity.should_admit(container(1), policy(0)));
+
+        assert!(popularity.admit(container(1), policy(2)).is_empty());
+        assert!(!popularity.should_admit(container(1), policy(2)));
+    }
+
+    #[test]
+    fn evicts_least_popular() {
+        let mut popularity = Popularity::default();
+        request(&mut popularity, container(1), 4);
+        request(&mut popularity, container(2), 2);
+        request(&mut popularity, container(3), 3);
+        assert!(popularity.admit(container(1), policy(2)).is_empty());
+        assert!(popularity.admit(container(2), policy(2)).is_empty());
+        assert_eq!(popularity.admit(container(3), policy(2)), vec![container(2)]);
+
+        // Evicted container may be admitted again
+        popularity.evicted(container(2));
+        assert!(popularity.should_admit(container(2), policy(2)));
+    }
+
+    #[test]
+    fn pinned_are_not_evicted() {
+        let mut popularity = Popularity::default();
+        request(&mut popularity, container(1), 2);
+        request(&mut popularity, container(2), 3);
+        popularity.set_pinned(container(1), true);
+        assert!(popularity.admit(container(1), policy(1)).is_empty());
+        assert!(popularity.admit(container(2), policy(1)).is_empty());
+    }
+
+    #[test]
+    fn auto_pins_once() {
+        let mut popularity = Popularity::default();
+        request(&mut popularity, container(1), 4);
+        assert!(!popularity.auto_pin(container(1), policy(1)));
+        request(&mut popularity, container(1), 1);
+        assert!(popularity.auto_pin(container(1), policy(1)));
+        assert!(!popularity.auto_pin(container(1), policy(1)));
+    }
+}
@@ -0,0 +1,126 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::QuarantinedContainer;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::checkpoint;
+use super::popularity::unix_time;
+
+/// Name of the file inside the data directory keeping the flagged peers and the quarantined
+/// containers.
+pub const STORM_NODE_QUARANTINE_FILE: &str = "quarantine.dat";
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+struct QuarantineData {
+    flagged: BTreeSet<NodeId>,
+    held: BTreeMap<ContainerId, QuarantinedContainer>,
+    /// Held containers which must be pinned once released.
+    pin_on_release: BTreeSet<ContainerId>,
+}
+
+/// Container released from the quarantine.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Released {
+    /// Peer from which the container was retrieved.
+    pub peer: NodeId,
+    /// Whether the container was retrieved by a repair or replication and must be pinned.
+    pub pin: bool,
+}
+
+/// Containers retrieved from the peers flagged by the node operator.
+///
+/// Quarantined containers are kept in the store, but are not listed to RPC clients, are not
+/// served to the peers and the apps are not notified about them until the operator releases
+/// them.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Quarantine {
+    path: PathBuf,
+    data: QuarantineData,
+}
+
+impl Quarantine {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_QUARANTINE_FILE);
+        let data = if path.exists() {
+            QuarantineData::strict_decode(fs::File::open(&path)?)?
+        } else {
+            none!()
+        };
+        Ok(Quarantine { path, data })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.data)?;
+        Ok(())
+    }
+
+    pub fn is_flagged(&self, peer: NodeId) -> bool { self.data.flagged.contains(&peer) }
+
+    /// Flags or unflags the peer, returning whether the flag was changed.
+    pub fn flag(&mut self, peer: NodeId, flagged: bool) -> Result<bool, strict_encoding::Error> {
+        let changed =
+            if flagged { self.data.flagged.insert(peer) } else { self.data.flagged.remove(&peer) };
+        if changed {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+
+    /// Holds the container retrieved from the peer. Containers which got `pin` are pinned once
+    /// they are released.
+    pub fn hold(
+        &mut self,
+        container_id: ContainerId,
+        peer: NodeId,
+        pin: bool,
+    ) -> Result<(), strict_encoding::Error> {
+        self.data.held.insert(container_id, QuarantinedContainer {
+            container_id,
+            peer,
+            timestamp: unix_time(),
+        });
+        if pin {
+            self.data.pin_on_release.insert(container_id);
+        }
+        self.save()
+    }
+
+    pub fn is_held(&self, container_id: ContainerId) -> bool {
+        self.data.held.contains_key(&container_id)
+    }
+
+    /// Releases container from the quarantine, returning `None` if it was not quarantined.
+    pub fn release(
+        &mut self,
+        container_id: ContainerId,
+    ) -> Result<Option<Released>, strict_encoding::Error> {
+        let held = match self.data.held.remove(&container_id) {
+            Some(held) => held,
+            None => return Ok(None),
+        };
+        let pin = self.data.pin_on_release.remove(&container_id);
+        self.save()?;
+        Ok(Some(Released {
+            peer: held.peer,
+            pin,
+        }))
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = QuarantinedContainer> + '_ {
+        self.data.held.values().copied()
+    }
+}
@@ -0,0 +1,205 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::ContainerEntry;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::checkpoint;
+
+/// Name of the file inside the data directory keeping the container sources and the evicted
+/// containers.
+pub const STORM_NODE_QUOTAS_FILE: &str = "quotas.dat";
+
+/// Policy selecting the containers evicted once the storage quota is exceeded.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+pub enum EvictionPolicy {
+    /// The least recently used containers which are not pinned are evicted
+    #[display("lru")]
+    Lru,
+
+    /// Only the containers explicitly evicted by the node operator are removed; the retrieved
+    /// container which exceeds the quota is evicted right away, unless it is pinned
+    #[display("explicit")]
+    Explicit,
+}
+
+/// Error parsing [`EvictionPolicy`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct UnknownEvictionPolicy(String);
+
+impl Display for UnknownEvictionPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown eviction policy `{}`; allowed values are `lru` and `explicit`", self.0)
+    }
+}
+
+impl FromStr for EvictionPolicy {
+    type Err = UnknownEvictionPolicy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lru" => Ok(EvictionPolicy::Lru),
+            "explicit" => Ok(EvictionPolicy::Explicit),
+            other => Err(UnknownEvictionPolicy(other.to_owned())),
+        }
+    }
+}
+
+/// Storage quotas of the node.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct StorageQuota {
+    /// Maximum size of all the stored containers, in bytes
+    pub total: Option<u64>,
+    /// Maximum size of the containers retrieved from a single remote peer, in bytes
+    pub per_peer: Option<u64>,
+    pub eviction: EvictionPolicy,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, StrictEncode, StrictDecode)]
+struct QuotaState {
+    /// Remote peers from which the stored containers were retrieved
+    sources: BTreeMap<ContainerId, NodeId>,
+    evicted: BTreeSet<ContainerId>,
+}
+
+/// Accounting of the container storage against the quotas.
+///
+/// Containers are accounted by the size recorded in the container index. Evicted containers are
+/// removed from the index and are neither listed nor served to the remote peers; since the store
+/// does not support removal of the records, their data remain in the store and are reused once
+/// the container is retrieved again.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Quotas {
+    path: PathBuf,
+    quota: StorageQuota,
+    state: QuotaState,
+}
+
+impl Quotas {
+    pub fn load(data_dir: &Path, quota: StorageQuota) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_QUOTAS_FILE);
+        let state = if path.exists() {
+            QuotaState::strict_decode(fs::File::open(&path)?)?
+        } else {
+            QuotaState::default()
+        };
+        Ok(Quotas { path, quota, state })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.state)?;
+        Ok(())
+    }
+
+    pub fn is_evicted(&self, container_id: ContainerId) -> bool {
+        self.state.evicted.contains(&container_id)
+    }
+
+    pub fn evicted(&self) -> impl Iterator<Item = ContainerId> + '_ {
+        self.state.evicted.iter().copied()
+    }
+
+    /// Registers container stored by the node, returning the containers which have to be evicted
+    /// to keep the storage within the quotas.
+    ///
+    /// `last_used` gives UNIX timestamp of the last use of the container, ordering the candidates
+    /// for the LRU eviction.
+    pub fn stored(
+        &mut self,
+        container_id: ContainerId,
+        source: Option<NodeId>,
+        entries: impl Iterator<Item = ContainerEntry>,
+        pinned: &BTreeSet<ContainerId>,
+        last_used: impl Fn(&ContainerEntry) -> u64,
+    ) -> Result<Vec<ContainerId>, strict_encoding::Error> {
+        let mut changed = self.state.evicted.remove(&container_id);
+        if let Some(remote_id) = source {
+            changed |= self.state.sources.insert(container_id, remote_id) != Some(remote_id);
+        }
+        if changed {
+            self.save()?;
+        }
+
+        let entries = entries.collect::<Vec<_>>();
+        let mut candidates = entries
+            .iter()
+            .filter(|entry| !pinned.contains(&entry.container_id))
+            .filter(|entry| {
+                self.quota.eviction == EvictionPolicy::Lru || entry.container_id == container_id
+            })
+            .collect::<Vec<_>>();
+        // The container which has just been stored is the most recently used one
+        candidates.sort_by_key(|entry| (entry.container_id == container_id, last_used(entry)));
+        let mut evicted = vec![];
+
+        if let (Some(limit), Some(remote_id)) = (self.quota.per_peer, source) {
+            let from_peer = |entry: &&ContainerEntry| {
+                self.state.sources.get(&entry.container_id) == Some(&remote_id)
+            };
+            let usage = entries.iter().filter(from_peer).map(|entry| entry.size).sum();
+            let candidates = candidates.iter().copied().filter(from_peer);
+            if reduce(usage, limit, candidates, &mut evicted) > limit {
+                warn!("Storage quota of peer {} can't be kept by evicting containers", remote_id);
+            }
+        }
+
+        if let Some(limit) = self.quota.total {
+            let usage = entries.iter().map(|entry| entry.size).sum::<u64>()
+                - evicted_size(&entries, &evicted);
+            if reduce(usage, limit, candidates.into_iter(), &mut evicted) > limit {
+                warn!("Storage quota can't be kept by evicting containers");
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    /// Records eviction of the container.
+    pub fn evict(&mut self, container_id: ContainerId) -> Result<(), strict_encoding::Error> {
+        self.state.sources.remove(&container_id);
+        self.state.evicted.insert(container_id);
+        self.save()
+    }
+}
+
+/// Evicts candidates until the usage gets within the limit, returning the remaining usage.
+fn reduce<'entry>(
+    mut usage: u64,
+    limit: u64,
+    candidates: impl Iterator<Item = &'entry ContainerEntry>,
+    evicted: &mut Vec<ContainerId>,
+) -> u64 {
+    for entry in candidates {
+        if usage <= limit {
+            break;
+        }
+        if !evicted.contains(&entry.container_id) {
+            usage = usage.saturating_sub(entry.size);
+            evicted.push(entry.container_id);
+        }
+    }
+    usage
+}
+
+fn evicted_size(entries: &[ContainerEntry], evicted: &[ContainerId]) -> u64 {
+    entries
+        .iter()
+        .filter(|entry| evicted.contains(&entry.container_id))
+        .map(|entry| entry.size)
+        .sum()
+}
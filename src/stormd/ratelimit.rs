@@ -0,0 +1,225 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+use internet2::addr::NodeId;
+use storm_rpc::{AddressedMsg, LimitScope, RateLimit, RateLimitUpdate, RateLimits};
+
+use crate::bus::ChunkSend;
+
+/// Maximal number of the chunk requests of the remote peers kept while they are deferred by the
+/// rate limits. Requests above it are dropped, and the peers have to repeat them.
+pub const RATE_LIMIT_QUEUE: usize = 1024;
+
+/// Token bucket holding up to one second worth of the traffic.
+///
+/// The size of the chunks is known only once they are sent, so the bucket may go into debt,
+/// which has to be repaid before the traffic continues.
+#[derive(Copy, Clone, Debug)]
+struct TokenBucket {
+    /// Tokens added per second
+    rate: u64,
+    tokens: i64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn with(rate: u64) -> Self {
+        TokenBucket {
+            rate,
+            tokens: rate as i64,
+            refilled: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let added = self.refilled.elapsed().as_micros() * self.rate as u128 / 1_000_000;
+        if added == 0 {
+            return;
+        }
+        self.tokens = (self.tokens.saturating_add(added as i64)).min(self.rate as i64);
+        self.refilled = Instant::now();
+    }
+
+    fn is_available(&mut self) -> bool {
+        self.refill();
+        self.tokens > 0
+    }
+
+    fn take(&mut self, amount: u64) { self.tokens = self.tokens.saturating_sub(amount as i64); }
+}
+
+/// Token buckets enforcing a [`RateLimit`].
+#[derive(Copy, Clone, Debug)]
+struct Buckets {
+    bytes: Option<TokenBucket>,
+    chunks: Option<TokenBucket>,
+}
+
+impl Buckets {
+    fn with(limit: RateLimit) -> Self {
+        Buckets {
+            bytes: limit.bytes_per_sec.map(TokenBucket::with),
+            chunks: limit.chunks_per_sec.map(u64::from).map(TokenBucket::with),
+        }
+    }
+
+    fn is_available(&mut self) -> bool {
+        self.bytes.as_mut().map_or(true, TokenBucket::is_available)
+            && self.chunks.as_mut().map_or(true, TokenBucket::is_available)
+    }
+}
+
+/// Limits the rate at which the chunks are sent to the remote peers, both for all of them
+/// together and for each of them.
+///
+/// Chunk requests of the peers are admitted while the limits have tokens left and deferred
+/// otherwise. The numbers of the chunks are charged on admission, while their sizes are charged
+/// once the transfer daemon reports them as sent. Like the replication schedule, the deferred
+/// requests are released when the node handles other messages. The limits changed over RPC are
+/// not persisted.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    global: RateLimit,
+    per_peer: RateLimit,
+    /// Own limits of the remote peers, overriding the per-peer limit
+    peers: BTreeMap<NodeId, RateLimit>,
+    global_buckets: Buckets,
+    peer_buckets: HashMap<NodeId, Buckets>,
+    deferred: VecDeque<AddressedMsg<ChunkSend>>,
+}
+
+impl RateLimiter {
+    pub fn with(global: RateLimit, per_peer: RateLimit) -> Self {
+        RateLimiter {
+            global,
+            per_peer,
+            peers: empty!(),
+            global_buckets: Buckets::with(global),
+            peer_buckets: empty!(),
+            deferred: empty!(),
+        }
+    }
+
+    fn limit(&self, remote_id: NodeId) -> RateLimit {
+        self.peers.get(&remote_id).copied().unwrap_or(self.per_peer)
+    }
+
+    fn peer_buckets(&mut self, remote_id: NodeId) -> Option<&mut Buckets> {
+        let limit = self.limit(remote_id);
+        if limit.is_unlimited() {
+            return None;
+        }
+        Some(self.peer_buckets.entry(remote_id).or_insert_with(|| Buckets::with(limit)))
+    }
+
+    fn is_allowed(&mut self, remote_id: NodeId) -> bool {
+        self.global_buckets.is_available()
+            && self.peer_buckets(remote_id).map_or(true, Buckets::is_available)
+    }
+
+    fn charge(&mut self, remote_id: NodeId, apply: impl Fn(&mut Buckets)) {
+        apply(&mut self.global_buckets);
+        if let Some(buckets) = self.peer_buckets(remote_id) {
+            apply(buckets);
+        }
+    }
+
+    fn charge_chunks(&mut self, request: &AddressedMsg<ChunkSend>) {
+        let count = request.data.chunk_ids.len() as u64;
+        self.charge(request.remote_id, |buckets| {
+            if let Some(bucket) = &mut buckets.chunks {
+                bucket.take(count)
+            }
+        });
+    }
+
+    /// Returns the chunk request back if the limits allow it now, or keeps it until they do.
+    /// Requests of a peer are kept in order.
+    pub fn admit(&mut self, request: AddressedMsg<ChunkSend>) -> Option<AddressedMsg<ChunkSend>> {
+        let remote_id = request.remote_id;
+        if !self.deferred.iter().any(|deferred| deferred.remote_id == remote_id)
+            && self.is_allowed(remote_id)
+        {
+            self.charge_chunks(&request);
+            return Some(request);
+        }
+        if self.deferred.len() >= RATE_LIMIT_QUEUE {
+            debug!("Dropping chunk request from {}: too many requests are deferred", remote_id);
+            return None;
+        }
+        self.deferred.push_back(request);
+        None
+    }
+
+    /// Registers the number of bytes sent to the remote peer.
+    pub fn sent(&mut self, remote_id: NodeId, bytes: u64) {
+        self.charge(remote_id, |buckets| {
+            if let Some(bucket) = &mut buckets.bytes {
+                bucket.take(bytes)
+            }
+        });
+    }
+
+    /// Takes the deferred chunk requests which the limits allow now.
+    pub fn due(&mut self) -> Vec<AddressedMsg<ChunkSend>> {
+        let mut due = vec![];
+        let mut blocked = HashSet::new();
+        let mut deferred = VecDeque::with_capacity(self.deferred.len());
+        while let Some(request) = self.deferred.pop_front() {
+            if !blocked.contains(&request.remote_id) && self.is_allowed(request.remote_id) {
+                self.charge_chunks(&request);
+                due.push(request);
+            } else {
+                blocked.insert(request.remote_id);
+                deferred.push_back(request);
+            }
+        }
+        self.deferred = deferred;
+        due
+    }
+
+    pub fn deferred(&self) -> usize { self.deferred.len() }
+
+    pub fn limits(&self) -> RateLimits {
+        RateLimits {
+            global: self.global,
+            per_peer: self.per_peer,
+            peers: self.peers.clone(),
+            deferred: self.deferred.len() as u32,
+        }
+    }
+
+    /// Changes the limit, starting its token buckets anew.
+    pub fn set(&mut self, update: RateLimitUpdate) {
+        let RateLimitUpdate { scope, limit } = update;
+        match scope {
+            LimitScope::Global => {
+                self.global = limit;
+                self.global_buckets = Buckets::with(limit);
+            }
+            LimitScope::PerPeer => {
+                self.per_peer = limit;
+                let peers = &self.peers;
+                self.peer_buckets.retain(|remote_id, _| peers.contains_key(remote_id));
+            }
+            LimitScope::Peer(remote_id) => {
+                if limit.is_unlimited() {
+                    self.peers.remove(&remote_id);
+                } else {
+                    self.peers.insert(remote_id, limit);
+                }
+                self.peer_buckets.remove(&remote_id);
+            }
+        }
+    }
+}
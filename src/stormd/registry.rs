@@ -0,0 +1,58 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+
+use storm_rpc::{DaemonInfo, ServiceId};
+
+use super::popularity::unix_time;
+
+/// Daemons which registered with the node by sending `Hello` over the CTL bus.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct DaemonRegistry {
+    /// UNIX timestamps of the registration and of the last message of each daemon
+    daemons: HashMap<ServiceId, (u64, u64)>,
+}
+
+impl DaemonRegistry {
+    /// Registers daemon, returning `false` if it was already registered, i.e. it has reconnected.
+    pub fn register(&mut self, service_id: ServiceId) -> bool {
+        let now = unix_time();
+        self.daemons.insert(service_id, (now, now)).is_none()
+    }
+
+    /// Records message received from the daemon, if it is registered.
+    pub fn seen(&mut self, service_id: &ServiceId) {
+        if let Some((_, last_seen)) = self.daemons.get_mut(service_id) {
+            *last_seen = unix_time();
+        }
+    }
+
+    /// Forgets daemon which has crashed.
+    pub fn remove(&mut self, service_id: &ServiceId) { self.daemons.remove(service_id); }
+
+    pub fn contains(&self, service_id: &ServiceId) -> bool { self.daemons.contains_key(service_id) }
+
+    pub fn service_ids(&self) -> impl Iterator<Item = &ServiceId> { self.daemons.keys() }
+
+    pub fn list(&self) -> Vec<DaemonInfo> {
+        let mut list = self
+            .daemons
+            .iter()
+            .map(|(service_id, (since, last_seen))| DaemonInfo {
+                service_id: service_id.clone(),
+                since: *since,
+                last_seen: *last_seen,
+            })
+            .collect::<Vec<_>>();
+        list.sort_by_key(|info| (info.since, info.service_id.to_string()));
+        list
+    }
+}
@@ -0,0 +1,146 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256t, Hash};
+use microservices::rpc::ServerError;
+use storm::{Chunk, Container, ContainerId};
+use storm_rpc::{RepairSummary, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS, DB_TABLE_CONTAINER_HEADERS};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::containers::{is_collected, is_evicted, missing_chunks};
+
+/// Name of the file inside the data directory marking that the store is being written to.
+pub const STORM_NODE_DIRTY_FILE: &str = "store.dirty";
+
+/// Marker of the container transfers in progress, persisted in the data directory.
+///
+/// Transfer daemons write to the store only while they are busy with a transfer, so the marker
+/// is set when any of them gets a task and is cleared once all of them are free. If the marker is
+/// found on the node start, the node was stopped in the middle of a transfer and the store must be
+/// repaired.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DirtyMarker {
+    path: PathBuf,
+    set: bool,
+}
+
+impl DirtyMarker {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(STORM_NODE_DIRTY_FILE);
+        let set = path.exists();
+        DirtyMarker { path, set }
+    }
+
+    /// Detects whether the previous node run has ended with unfinished store writes.
+    pub fn is_set(&self) -> bool { self.set }
+
+    pub fn set(&mut self) -> Result<(), strict_encoding::Error> {
+        if !self.set {
+            fs::File::create(&self.path)?;
+            self.set = true;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) -> Result<(), strict_encoding::Error> {
+        if self.set {
+            fs::remove_file(&self.path)?;
+            self.set = false;
+        }
+        Ok(())
+    }
+}
+
+/// Reconciles the store tables after an unclean shutdown.
+///
+/// Containers stored without their headers get the headers restored. Headers stored without a
+/// valid container are the partial writes: since the store does not support removal, they are
+/// returned to be excluded from the container index together with the headers of the evicted
+/// containers. Chunks are checked against their ids and
+/// against the containers referencing them.
+pub fn repair(
+    store: &mut store_rpc::Client,
+) -> Result<(RepairSummary, BTreeSet<ContainerId>), ServerError<store_rpc::FailureCode>> {
+    let mut summary = RepairSummary::default();
+    let headers = store.ids(DB_TABLE_CONTAINER_HEADERS)?;
+    let mut valid = BTreeSet::new();
+    let mut referenced = BTreeSet::new();
+    let mut evicted = BTreeSet::new();
+
+    for id in store.ids(DB_TABLE_CONTAINERS)? {
+        let container_id = ContainerId::from_inner(sha256t::Hash::from_inner(id.into_inner()));
+        let container = match store.retrieve_chunk(DB_TABLE_CONTAINERS, container_id)? {
+            Some(chunk) if is_evicted(&chunk) => {
+                evicted.insert(id);
+                continue;
+            }
+            Some(chunk) => Container::strict_deserialize(chunk).ok(),
+            None => None,
+        };
+        let container = match container {
+            Some(container) if container.container_id() == container_id => container,
+            _ => {
+                warn!("Stored container {} is corrupted", container_id);
+                continue;
+            }
+        };
+        valid.insert(id);
+
+        if !headers.contains(&id) {
+            let header_chunk = container
+                .header
+                .strict_serialize()
+                .ok()
+                .and_then(|data| Chunk::try_from(data).ok());
+            match header_chunk {
+                Some(chunk) => {
+                    store.store(DB_TABLE_CONTAINER_HEADERS, container_id, &chunk)?;
+                    summary.headers_restored += 1;
+                }
+                None => warn!("Unable to restore header of container {}", container_id),
+            }
+        }
+
+        let chunk_ids = container.chunks.iter().copied().collect::<BTreeSet<_>>();
+        if !missing_chunks(store, container_id, chunk_ids.clone())?.is_empty() {
+            summary.incomplete += 1;
+        }
+        referenced.extend(chunk_ids);
+    }
+
+    let discarded = headers
+        .difference(&valid)
+        .map(|id| ContainerId::from_inner(sha256t::Hash::from_inner(id.into_inner())))
+        .collect::<BTreeSet<_>>();
+    summary.partial_discarded = discarded.len().saturating_sub(evicted.len()) as u32;
+
+    for chunk_id in store.ids(DB_TABLE_CHUNKS)? {
+        match store.retrieve_chunk(DB_TABLE_CHUNKS, chunk_id)? {
+            Some(chunk) if chunk.chunk_id() == chunk_id => {}
+            // Collected by the garbage collector
+            Some(chunk) if is_collected(&chunk, chunk_id) => continue,
+            _ => {
+                warn!("Stored chunk {} is corrupted", chunk_id);
+                summary.corrupted_chunks += 1;
+                continue;
+            }
+        }
+        if !referenced.contains(&chunk_id) {
+            summary.orphan_chunks += 1;
+        }
+    }
+
+    Ok((summary, discarded))
+}
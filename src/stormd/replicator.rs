@@ -0,0 +1,157 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use internet2::addr::NodeId;
+use storm::{ContainerId, StormApp};
+use storm_rpc::{ReplicaState, ReplicationStatus};
+
+/// Type of the node-level app-opaque message carrying [`ReplicaRequest`].
+pub const REPLICA_REQUEST_MSG_TYPE: u16 = 0x000D;
+
+/// Type of the node-level app-opaque message carrying [`ReplicaAck`].
+pub const REPLICA_ACK_MSG_TYPE: u16 = 0x000E;
+
+/// Time within which a storage peer has to confirm the replica.
+pub const REPLICA_TIMEOUT: Duration = Duration::from_secs(1800);
+
+/// Request to the storage peer to retrieve the container from the node and keep it pinned.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct ReplicaRequest {
+    pub app: StormApp,
+    pub container_id: ContainerId,
+}
+
+/// Answer of the storage peer to [`ReplicaRequest`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct ReplicaAck {
+    pub container_id: ContainerId,
+    /// Whether the container is retrieved and pinned; `false` if the request is declined or the
+    /// retrieval has failed.
+    pub stored: bool,
+    /// Whether the request is declined by the storage peer
+    pub declined: bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Replication {
+    min_replicas: u8,
+    replicas: BTreeMap<NodeId, ReplicaState>,
+    started: Instant,
+}
+
+/// Replication of the stored containers to the storage peers, and of the containers of the
+/// remote peers to this node.
+///
+/// The node asks each of the storage peers to retrieve the container, tracking their
+/// acknowledgements; peers which don't confirm the replica within [`REPLICA_TIMEOUT`] are
+/// considered failed. Like the other timeouts, they are checked when the node handles other
+/// messages. The tracking is not persisted, so a restarted node has to be asked to replicate the
+/// containers again.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Replicator {
+    replications: BTreeMap<ContainerId, Replication>,
+    /// Remote peers which have asked the node to keep their containers, awaiting the
+    /// acknowledgements
+    requesters: HashMap<ContainerId, BTreeSet<NodeId>>,
+}
+
+impl Replicator {
+    /// Starts replication of the container to the peers. Replicas already confirmed by the
+    /// peers are kept, while the other peers are asked again.
+    pub fn start(
+        &mut self,
+        container_id: ContainerId,
+        peers: BTreeSet<NodeId>,
+        min_replicas: u8,
+    ) -> ReplicationStatus {
+        let confirmed = self
+            .replications
+            .remove(&container_id)
+            .map(|replication| replication.replicas)
+            .unwrap_or_default();
+        let replicas = peers
+            .into_iter()
+            .map(|peer| match confirmed.get(&peer) {
+                Some(ReplicaState::Confirmed) => (peer, ReplicaState::Confirmed),
+                _ => (peer, ReplicaState::Pending),
+            })
+            .collect();
+        self.replications.insert(container_id, Replication {
+            min_replicas,
+            replicas,
+            started: Instant::now(),
+        });
+        self.status(container_id).expect("replication is just started")
+    }
+
+    pub fn status(&self, container_id: ContainerId) -> Option<ReplicationStatus> {
+        self.replications.get(&container_id).map(|replication| ReplicationStatus {
+            container_id,
+            min_replicas: replication.min_replicas,
+            replicas: replication.replicas.clone(),
+        })
+    }
+
+    /// Detects whether the peer is asked to replicate the container and has not confirmed it
+    /// yet, so it may retrieve the container.
+    pub fn is_pending(&self, container_id: ContainerId, peer: NodeId) -> bool {
+        self.replications.get(&container_id).map_or(false, |replication| {
+            replication.replicas.get(&peer) == Some(&ReplicaState::Pending)
+        })
+    }
+
+    /// Registers acknowledgement of the storage peer, returning the replication status if the
+    /// replica was pending.
+    pub fn acknowledged(&mut self, peer: NodeId, ack: ReplicaAck) -> Option<ReplicationStatus> {
+        let state = self
+            .replications
+            .get_mut(&ack.container_id)?
+            .replicas
+            .get_mut(&peer)
+            .filter(|state| **state == ReplicaState::Pending)?;
+        *state = match ack {
+            ReplicaAck { stored: true, .. } => ReplicaState::Confirmed,
+            ReplicaAck { declined: true, .. } => ReplicaState::Declined,
+            _ => ReplicaState::Failed,
+        };
+        self.status(ack.container_id)
+    }
+
+    /// Fails the pending replicas which were not confirmed in time, returning them.
+    pub fn expire(&mut self) -> Vec<(ContainerId, NodeId)> {
+        let mut expired = vec![];
+        for (container_id, replication) in &mut self.replications {
+            if replication.started.elapsed() < REPLICA_TIMEOUT {
+                continue;
+            }
+            for (peer, state) in &mut replication.replicas {
+                if *state == ReplicaState::Pending {
+                    *state = ReplicaState::Failed;
+                    expired.push((*container_id, *peer));
+                }
+            }
+        }
+        expired
+    }
+
+    /// Registers request of the remote peer to keep its container on this node.
+    pub fn requested(&mut self, container_id: ContainerId, peer: NodeId) {
+        self.requesters.entry(container_id).or_default().insert(peer);
+    }
+
+    /// Returns remote peers which have asked to keep the retrieved container and have to be
+    /// acknowledged.
+    pub fn retrieved(&mut self, container_id: ContainerId) -> BTreeSet<NodeId> {
+        self.requesters.remove(&container_id).unwrap_or_default()
+    }
+}
@@ -0,0 +1,73 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+
+use microservices::esb::ClientId;
+use storm_rpc::{RequestId, RpcMsg};
+
+use super::popularity::unix_time;
+
+/// Number of seconds the results of idempotent requests are kept for replying to retries.
+const REQUEST_CACHE_TTL: u64 = 600;
+
+/// Short-lived cache of the results of idempotent RPC requests.
+///
+/// Request ids are chosen by the clients, so the requests are told apart by the id of the client
+/// which has sent them: a request of one client is never replied with the result of another's.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RequestCache {
+    /// Request execution time and the reply, if the request was completed synchronously.
+    entries: HashMap<(ClientId, RequestId), (u64, Option<RpcMsg>)>,
+}
+
+impl RequestCache {
+    /// Returns `None` if the request is not known; otherwise returns the cached reply, if any.
+    pub fn get(&mut self, client_id: ClientId, request_id: RequestId) -> Option<Option<RpcMsg>> {
+        let now = unix_time();
+        self.entries.retain(|_, (time, _)| *time + REQUEST_CACHE_TTL > now);
+        self.entries.get(&(client_id, request_id)).map(|(_, reply)| reply.clone())
+    }
+
+    pub fn start(&mut self, client_id: ClientId, request_id: RequestId) {
+        self.entries.insert((client_id, request_id), (unix_time(), None));
+    }
+
+    /// Records the reply to the request; only the first reply is kept.
+    pub fn complete(&mut self, client_id: ClientId, request_id: RequestId, reply: &RpcMsg) {
+        if let Some((_, cached @ None)) = self.entries.get_mut(&(client_id, request_id)) {
+            *cached = Some(reply.clone());
+        }
+    }
+
+    pub fn forget(&mut self, client_id: ClientId, request_id: RequestId) {
+        self.entries.remove(&(client_id, request_id));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn requests_of_clients_are_apart() {
+        let mut cache = RequestCache::default();
+        cache.start(1, 42);
+        cache.complete(1, 42, &RpcMsg::Success(None.into()));
+        assert_eq!(cache.get(1, 42), Some(Some(RpcMsg::Success(None.into()))));
+        assert_eq!(cache.get(2, 42), None);
+
+        cache.start(2, 42);
+        assert_eq!(cache.get(2, 42), Some(None));
+        cache.forget(2, 42);
+        assert_eq!(cache.get(2, 42), None);
+        assert!(cache.get(1, 42).is_some());
+    }
+}
@@ -0,0 +1,154 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use internet2::addr::NodeId;
+use storm::{ContainerFullId, ContainerId, StormApp};
+use storm_rpc::AppContainer;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal::checkpoint;
+use crate::bus::{AddressedClientMsg, CtlMsg};
+
+/// Number of times an interrupted container retrieval is resumed before it is given up.
+pub const RESUME_ATTEMPTS: u8 = 5;
+
+/// Name of the file inside the data directory keeping the retrievals interrupted by the node
+/// shutdown.
+pub const STORM_NODE_RESUME_FILE: &str = "resume.dat";
+
+/// Interrupted retrieval saved on the node shutdown.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+struct Checkpoint {
+    remote_id: NodeId,
+    app: StormApp,
+    id: ContainerFullId,
+}
+
+/// Container retrievals which were interrupted before all the container chunks were received.
+///
+/// Received chunks are kept in the store, so the transfer checkpoint is the store itself: a
+/// resumed retrieval pulls only the chunks which are still missing. Retrievals are resumed once
+/// the remote peer shows up again, which is detected from the messages it sends. Retrievals
+/// interrupted by the node shutdown are saved and resumed after the node restart.
+#[derive(Clone, Debug)]
+pub struct Resumes {
+    path: PathBuf,
+    /// Retrievals assigned to the transfer daemons
+    running: HashMap<ContainerId, AddressedClientMsg<AppContainer>>,
+    interrupted: HashMap<ContainerId, AddressedClientMsg<AppContainer>>,
+    /// Number of the failed attempts of the retrievals
+    attempts: HashMap<ContainerId, u8>,
+}
+
+impl Resumes {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_RESUME_FILE);
+        let checkpoints: BTreeMap<ContainerId, Checkpoint> = if path.exists() {
+            StrictDecode::strict_decode(fs::File::open(&path)?)?
+        } else {
+            empty!()
+        };
+        let interrupted = checkpoints
+            .into_iter()
+            .map(|(container_id, checkpoint)| {
+                (container_id, AddressedClientMsg {
+                    remote_id: checkpoint.remote_id,
+                    client_id: None,
+                    data: AppContainer {
+                        storm_app: checkpoint.app,
+                        container_id: checkpoint.id,
+                    },
+                })
+            })
+            .collect();
+        Ok(Resumes {
+            path,
+            running: empty!(),
+            interrupted,
+            attempts: empty!(),
+        })
+    }
+
+    /// Saves the running and interrupted retrievals, which are resumed after the node restart.
+    pub fn save(&self) -> Result<(), strict_encoding::Error> {
+        let checkpoints = self
+            .running
+            .iter()
+            .chain(&self.interrupted)
+            .map(|(container_id, request)| {
+                (*container_id, Checkpoint {
+                    remote_id: request.remote_id,
+                    app: request.data.storm_app,
+                    id: request.data.container_id,
+                })
+            })
+            .collect::<BTreeMap<_, _>>();
+        checkpoint(&self.path, &checkpoints)?;
+        Ok(())
+    }
+
+    /// Registers retrieval assigned to a transfer daemon.
+    pub fn started(&mut self, request: AddressedClientMsg<AppContainer>) {
+        let container_id = request.data.container_id.container_id;
+        self.interrupted.remove(&container_id);
+        self.running.insert(container_id, request);
+    }
+
+    /// Registers completion of the retrieval, keeping it for resumption if it has failed.
+    pub fn finished(&mut self, container_id: ContainerId, success: bool) {
+        let mut request = match self.running.remove(&container_id) {
+            Some(request) => request,
+            None => return,
+        };
+        if success {
+            self.attempts.remove(&container_id);
+            return;
+        }
+        let attempts = self.attempts.entry(container_id).or_default();
+        *attempts += 1;
+        if *attempts >= RESUME_ATTEMPTS {
+            warn!("Retrieval of {} has failed {} times; giving up", container_id, attempts);
+            self.attempts.remove(&container_id);
+            return;
+        }
+        // The client which has requested the container is not waiting for the resumed retrieval
+        request.client_id = None;
+        self.interrupted.insert(container_id, request);
+    }
+
+    pub fn is_interrupted(&self, container_id: ContainerId) -> bool {
+        self.interrupted.contains_key(&container_id)
+    }
+
+    /// Lists interrupted retrievals with the apps and the remote peers they were requested from.
+    pub fn interrupted(&self) -> impl Iterator<Item = (ContainerId, StormApp, NodeId)> + '_ {
+        self.interrupted.iter().map(|(container_id, request)| {
+            (*container_id, request.data.storm_app, request.remote_id)
+        })
+    }
+
+    /// Takes retrievals from the remote peer which have to be resumed.
+    pub fn due(&mut self, remote_id: NodeId) -> Vec<CtlMsg> {
+        let due = self
+            .interrupted
+            .iter()
+            .filter(|(_, request)| request.remote_id == remote_id)
+            .map(|(container_id, _)| *container_id)
+            .collect::<Vec<_>>();
+        due.into_iter()
+            .filter_map(|container_id| self.interrupted.remove(&container_id))
+            .map(CtlMsg::GetContainer)
+            .collect()
+    }
+}
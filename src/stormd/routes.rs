@@ -0,0 +1,53 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeSet, HashMap};
+
+use internet2::addr::NodeId;
+
+use crate::bus::ServiceBus;
+
+/// Tracks which of the peer connection services reaches each of the remote peers.
+///
+/// The route is learned from the bus on which the messages of the peer arrive; peers which were
+/// not seen yet are reached through the main message bus. All the buses on which the peer was
+/// seen are kept as the alternative paths to the peer.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct PeerRoutes {
+    routes: HashMap<NodeId, ServiceBus>,
+    /// Numbers of the peer connection services through which the peer was seen
+    paths: HashMap<NodeId, BTreeSet<u8>>,
+}
+
+impl PeerRoutes {
+    /// Registers bus through which the peer is reachable. Returns `true` if the route has
+    /// changed.
+    pub fn register(&mut self, remote_id: NodeId, bus: ServiceBus) -> bool {
+        if let Some(no) = bus.peer_service_no() {
+            self.paths.entry(remote_id).or_default().insert(no);
+        }
+        self.routes.insert(remote_id, bus) != Some(bus)
+    }
+
+    /// Returns numbers of the peer connection services through which the peer is reachable.
+    pub fn paths(&self, remote_id: NodeId) -> BTreeSet<u8> {
+        match self.paths.get(&remote_id) {
+            Some(paths) => paths.clone(),
+            None => self.route(remote_id).peer_service_no().into_iter().collect(),
+        }
+    }
+
+    /// Number of the peers which have been seen.
+    pub fn len(&self) -> usize { self.routes.len() }
+
+    pub fn route(&self, remote_id: NodeId) -> ServiceBus {
+        self.routes.get(&remote_id).copied().unwrap_or(ServiceBus::Msg)
+    }
+}
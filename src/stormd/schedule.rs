@@ -0,0 +1,151 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use storm::StormApp;
+use storm_rpc::STORM_APP_SYNC;
+
+use super::popularity::unix_time;
+use crate::bus::CtlMsg;
+
+/// Period over which the bandwidth usage of the node is measured.
+pub const BANDWIDTH_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Daily range of hours, in UTC, during which the bulk replication runs. The range wraps around
+/// midnight if its start is past its end.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct ReplicationWindow {
+    pub start: u8,
+    pub end: u8,
+}
+
+impl ReplicationWindow {
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&hour)
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+impl Display for ReplicationWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:00-{:02}:00 UTC", self.start, self.end)
+    }
+}
+
+/// Error parsing [`ReplicationWindow`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Error)]
+pub struct InvalidReplicationWindow(String);
+
+impl Display for InvalidReplicationWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid replication window `{}`; it must be given as `START-END` hours from 0 to 24",
+            self.0
+        )
+    }
+}
+
+impl FromStr for ReplicationWindow {
+    type Err = InvalidReplicationWindow;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidReplicationWindow(s.to_owned());
+        let (start, end) = s.split_once('-').ok_or_else(err)?;
+        let start = start.parse::<u8>().map_err(|_| err())?;
+        let end = end.parse::<u8>().map_err(|_| err())?;
+        if start > 24 || end > 24 || start == end {
+            return Err(err());
+        }
+        Ok(ReplicationWindow {
+            start: start % 24,
+            end: end % 24,
+        })
+    }
+}
+
+/// Defers container transfers of the bulk replication apps (downpourd and syncd) until the
+/// replication window opens and the node bandwidth usage drops below the threshold.
+///
+/// Since the controller has no timers, the bandwidth is measured, and the deferred transfers are
+/// released, when the node handles other messages.
+#[derive(Clone, Debug)]
+pub struct ReplicationSchedule {
+    window: Option<ReplicationWindow>,
+    /// Bandwidth usage, in bytes per second, above which the bulk replication is paused
+    bandwidth_limit: Option<u64>,
+    /// Start of the current bandwidth sample and the node traffic at that moment
+    sample: (Instant, u64),
+    /// Bandwidth usage, in bytes per second, measured over the last sample
+    usage: u64,
+    deferred: VecDeque<CtlMsg>,
+}
+
+impl ReplicationSchedule {
+    pub fn with(window: Option<ReplicationWindow>, bandwidth_limit: Option<u64>) -> Self {
+        ReplicationSchedule {
+            window,
+            bandwidth_limit,
+            sample: (Instant::now(), 0),
+            usage: 0,
+            deferred: empty!(),
+        }
+    }
+
+    /// Detects whether transfers of the app are a subject of the schedule.
+    pub fn is_bulk(app: StormApp) -> bool {
+        matches!(app, StormApp::FileTransfer) || app == StormApp::Vendor(STORM_APP_SYNC)
+    }
+
+    /// Updates bandwidth usage from the total traffic of the node, in bytes.
+    pub fn measure(&mut self, traffic: u64) {
+        let (started, start_traffic) = self.sample;
+        let elapsed = started.elapsed();
+        if elapsed < BANDWIDTH_SAMPLE_INTERVAL {
+            return;
+        }
+        self.usage = traffic.saturating_sub(start_traffic) / elapsed.as_secs().max(1);
+        self.sample = (Instant::now(), traffic);
+    }
+
+    /// Detects whether the bulk replication may run now.
+    pub fn is_open(&self) -> bool {
+        let hour = (unix_time() % 86400 / 3600) as u8;
+        self.window.map_or(true, |window| window.contains(hour))
+            && self.bandwidth_limit.map_or(true, |limit| self.usage < limit)
+    }
+
+    /// Returns the transfer request back if it may run now, or keeps it until the schedule
+    /// allows it.
+    pub fn admit(&mut self, request: CtlMsg) -> Option<CtlMsg> {
+        if self.is_open() && self.deferred.is_empty() {
+            return Some(request);
+        }
+        self.deferred.push_back(request);
+        None
+    }
+
+    /// Takes the deferred transfer requests, if the schedule allows them.
+    pub fn due(&mut self) -> Vec<CtlMsg> {
+        if !self.is_open() {
+            return vec![];
+        }
+        self.deferred.drain(..).collect()
+    }
+
+    pub fn deferred(&self) -> usize { self.deferred.len() }
+}
@@ -8,11 +8,15 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256t, Hash};
 use internet2::addr::NodeId;
-use internet2::{Unmarshall, ZmqSocketType};
+use internet2::{TypedEnum, Unmarshall, ZmqSocketType};
 use lnp2p::bifrost;
 use lnp2p::bifrost::{BifrostApp, Messages as LnMsg};
 use microservices::cli::LogStyle;
@@ -20,29 +24,69 @@ use microservices::error::BootstrapError;
 use microservices::esb::{self, ClientId, EndpointList, Error};
 use microservices::node::TryService;
 use storm::p2p::{AppMsg, ChunkPull, ChunkPush, Messages, STORM_P2P_UNMARSHALLER};
-use storm::{ContainerId, StormApp};
-use storm_ext::{ExtMsg, StormExtMsg};
+use storm::{Chunk, ContainerFullId, ContainerId, MesgId, StormApp};
+use storm_ext::{
+    DeclineReason, Declined, DeliveryReport, DeliveryStatus, ExtMsg, MultiPost, NodeEvent,
+    OpaqueEnvelope, OpaqueMsg, StormExtMsg, TopicsDelta, BIFROST_APP_STORM_OPAQUE,
+};
 use storm_rpc::{
-    AddressedMsg, AppContainer, RpcMsg, ServiceId, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS,
-    DB_TABLE_CONTAINER_HEADERS,
+    AddressedMsg, AdminAction, AdminCommand, AdminOutcome, AppContainer, ApprovalOutcome,
+    BatchResult, BusFeatures, ContainerBatch, ContainerEntry, ContainerVersion, Dictionary, Event,
+    EventFilter, EventKind, FaultTarget, GcReport, Health, HealthCheck, Idempotent, ItemStatus,
+    JobRequest, MemoryPressure, NodeFeatures, NodeInfo, Page, PageReq, Paged, RepairSummary,
+    ReplicateRequest, Replication, RequestId, RpcMsg, ServiceId, SignedAttestation, TopicId,
+    TransferEntry, TransferProgress, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS,
+    DB_TABLE_CONTAINER_HEADERS, DB_TABLE_CONTAINER_VERSIONS, DB_TABLE_INLINE_CHUNKS,
 };
+use strict_encoding::{StrictDecode, StrictEncode};
 
 use crate::bus::{
-    AddressedClientMsg, BusMsg, ChunkSend, CtlMsg, DaemonId, Endpoints, Responder, ServiceBus,
+    AddressedClientMsg, BusMsg, ChunkReceived, ChunkSend, CtlMsg, DaemonId, Endpoints, ExtLanes,
+    MetricsRegistry, PeerPaths, Responder, ServiceBus,
+};
+use crate::stormd::{
+    migrate, patches, repair, serve_metrics, unix_time, watch_signals, Access, AdminChannel,
+    AdminReply, AdminRequest, AntiEntropy, AppRoute, AppRoutes, Approvals, Approved, Attestations,
+    BloomFilter, ChunkGc, ContainerIndex, Daemon, DaemonRegistry, DeclineNotice, Dictionaries,
+    DirtyMarker, EventDigests, EventPolls, IndexSnapshot, InterestFilter, Interests, Jobs,
+    LazyFetches, Maintenance, MaintenanceNotice, ManifestKey, MemoryBudget, MetricsRecorder,
+    MetricsSnapshot, Moderation, MsgLink, PeerFeatures, PeerMonitor, PeerRoutes, Pins, Policy,
+    PolicySubject, Popularity, PowStamp, Quarantine, Quotas, RateLimiter, Released, ReplicaAck,
+    ReplicaDigest, ReplicaRequest, ReplicaSet, ReplicationSchedule, Replicator, RequestCache,
+    RequestKind, Resumes, Sessions, Shares, Shutdown, SketchReply, SketchRequest, Stamps, Stats,
+    Supervisor, TopicSketch, Topics, Verdict, Webhooks, ADMIN_REPLY_MSG_TYPE,
+    ADMIN_REQUEST_MSG_TYPE, ATTESTATION_MSG_TYPE, DECLINE_REASON_MSG_TYPE, ENTROPY_DIGEST_MSG_TYPE,
+    ENTROPY_SET_MSG_TYPE, FEATURES_MSG_TYPE, INTEREST_MSG_TYPE, MAINTENANCE_MSG_TYPE,
+    POW_STAMP_MSG_TYPE, QUEUED_ENTRY_SIZE, REPLICA_ACK_MSG_TYPE, REPLICA_REQUEST_MSG_TYPE,
+    TOPIC_DIFF_MSG_TYPE, TOPIC_SKETCH_MSG_TYPE,
 };
-use crate::stormd::Daemon;
-use crate::{Config, DaemonError, LaunchError};
+use crate::transferd::missing_page;
+use crate::{chaos, containers, Config, DaemonError, LaunchError};
+
+/// Number of attempts to send a message to each of the recipients of a multi-recipient post.
+const POST_FANOUT_ATTEMPTS: u8 = 3;
 
 pub fn run(config: Config<super::Config>) -> Result<(), BootstrapError<LaunchError>> {
     let msg_endpoint = config.msg_endpoint.clone();
     let rpc_endpoint = config.rpc_endpoint.clone();
     let ctl_endpoint = config.ctl_endpoint.clone();
     let ext_endpoint = config.ext_endpoint.clone();
+    let bulk_endpoint = config.bulk_endpoint.clone();
+    let notify_endpoint = config.ext.notify_endpoint.clone();
+    let chat_endpoint = config.chat_endpoint.clone();
+    let peer_msg_endpoints = config.ext.peer_msg_endpoints.clone();
+    if peer_msg_endpoints.len() > u8::MAX as usize {
+        return Err(LaunchError::TooManyPeerServices(peer_msg_endpoints.len()).into());
+    }
+    // Repeated approver keys are counted once, since each key approves a proposal only once
+    let approvers = config.ext.approvers.iter().collect::<BTreeSet<_>>().len();
+    if config.ext.approval_threshold as usize > approvers {
+        return Err(LaunchError::ApprovalThreshold(config.ext.approval_threshold, approvers).into());
+    }
     let runtime = Runtime::init(config)?;
 
     debug!("Connecting to service bus {}", msg_endpoint);
-    let controller = esb::Controller::with(
-        map! {
+    let mut buses = map! {
             ServiceBus::Storm => esb::BusConfig::with_addr(
                 ext_endpoint,
                 ZmqSocketType::RouterBind,
@@ -59,35 +103,180 @@ pub fn run(config: Config<super::Config>) -> Result<(), BootstrapError<LaunchErr
                 Some(ServiceId::Lnp)
             ),
             ServiceBus::Rpc => esb::BusConfig::with_addr(
-                rpc_endpoint,
+                rpc_endpoint.clone(),
                 ZmqSocketType::RouterBind,
                 None
             )
-        },
-        runtime,
-    )
-    .map_err(|_| LaunchError::BusSetupFailure)?;
+    };
+    if let Some(endpoint) = bulk_endpoint {
+        debug!("Connecting to bulk data bus {}", endpoint);
+        buses.insert(
+            ServiceBus::Bulk,
+            esb::BusConfig::with_addr(endpoint, ZmqSocketType::RouterConnect, Some(ServiceId::Lnp)),
+        );
+    }
+    for (no, endpoint) in peer_msg_endpoints.into_iter().enumerate() {
+        debug!("Connecting to peer service bus {}", endpoint);
+        buses.insert(
+            ServiceBus::PeerMsg(no as u8 + 1),
+            esb::BusConfig::with_addr(endpoint, ZmqSocketType::RouterConnect, Some(ServiceId::Lnp)),
+        );
+    }
+    if let Some(endpoint) = notify_endpoint {
+        debug!("Publishing node events on {}", endpoint);
+        buses.insert(
+            ServiceBus::Notify,
+            esb::BusConfig::with_subscription(endpoint, ZmqSocketType::Pub, None),
+        );
+    }
+    let controller =
+        esb::Controller::with(buses, runtime).map_err(|_| LaunchError::BusSetupFailure)?;
 
+    watch_signals(rpc_endpoint, chat_endpoint).map_err(LaunchError::SignalHandlers)?;
     controller.run_or_panic("stormd");
 
     unreachable!()
 }
 
+/// Name of the p2p message type, labelling the message counters.
+fn message_type(message: &Messages) -> String {
+    let name = message.to_string();
+    name.split('(').next().unwrap_or_default().to_owned()
+}
+
+fn dry_run_note(dry_run: bool) -> &'static str {
+    if dry_run {
+        " (dry run)"
+    } else {
+        ""
+    }
+}
+
 pub struct Runtime {
     pub(super) config: Config<super::Config>,
-    pub(super) registered_apps: BTreeSet<StormApp>,
+    /// Routing table of the apps registered with the node
+    pub(super) apps: AppRoutes,
 
-    // We use store connection on bootstrap to initialize tables; we maintain it after even thought
-    // we do not use it
-    #[allow(dead_code)]
+    // We use store connection on bootstrap to initialize tables and to check presence of the
+    // containers during cache admission
     pub(crate) store: store_rpc::Client,
 
     pub(crate) transferd_free: VecDeque<DaemonId>,
     pub(crate) transferd_busy: HashSet<DaemonId>,
+    /// Daemons which have connected to the node
+    pub(crate) daemons: DaemonRegistry,
+    /// Long-running child daemons restarted once they crash
+    pub(crate) supervisor: Supervisor,
+    /// Clients waiting for the list of apps of the remote peers, with the pages they requested
+    pub(crate) peer_app_requests: HashMap<NodeId, Vec<(ClientId, PageReq)>>,
+    /// Clients waiting for the list of topics of the remote peer apps, with the pages they
+    /// requested
+    pub(crate) peer_topic_requests: HashMap<(NodeId, StormApp), Vec<(ClientId, PageReq)>>,
     /// Tracks known apps which must be notified on complete container downloads
     pub(crate) container_apps: HashMap<ContainerId, StormApp>,
     pub(crate) container_transfers: HashMap<ContainerId, DaemonId>,
     pub(crate) ctl_queue: VecDeque<CtlMsg>,
+    /// Index of known containers used for container listing
+    pub(crate) containers: ContainerIndex,
+    /// Background jobs submitted by RPC clients
+    pub(crate) jobs: Jobs,
+    /// Sessions of the RPC clients and the jobs they have submitted
+    pub(crate) sessions: Sessions,
+    /// Long-poll requests waiting for the node events
+    pub(crate) polls: EventPolls,
+    /// Request statistics for the containers
+    pub(crate) popularity: Popularity,
+    /// Containers which are being retrieved to be admitted into the cache
+    pub(crate) cache_admissions: HashSet<ContainerId>,
+    /// Messages to application extensions awaiting delivery
+    pub(crate) ext_lanes: ExtLanes,
+    /// Results of the recently executed idempotent requests
+    pub(crate) request_cache: RequestCache,
+    /// Id of the idempotent request which is being processed
+    pub(crate) current_request: Option<RequestId>,
+    /// Features negotiated with clients and application extensions
+    pub(crate) bus_features: HashMap<ServiceId, BusFeatures>,
+    /// Compression dictionaries of the apps
+    pub(crate) dictionaries: Dictionaries,
+    /// Message buses through which the remote peers are reachable
+    pub(crate) peer_routes: PeerRoutes,
+    /// Latency and throughput measured for the remote peers
+    pub(crate) peer_monitor: PeerMonitor,
+    /// Peers reachable through the dedicated bulk data bus
+    pub(crate) bulk_peers: HashSet<NodeId>,
+    /// Topics proposed or listed by the local apps and the remote peers
+    pub(crate) topics: Topics,
+    /// State of the connection to the LNP node and messages queued while it is lost
+    pub(crate) msg_link: MsgLink,
+    /// Share tokens issued for the containers kept by this node
+    pub(crate) shares: Shares,
+    /// Containers excluded from cache eviction
+    pub(crate) pins: Pins,
+    /// Node activity counters, persisted across restarts
+    pub(crate) stats: Stats,
+    /// Recorder of the metrics snapshots, if enabled
+    pub(crate) metrics: Option<MetricsRecorder>,
+    /// Metrics exported on the HTTP endpoint, if enabled
+    pub(crate) registry: Option<Arc<Mutex<MetricsRegistry>>>,
+    /// Marker of the store writes in progress
+    pub(crate) dirty: DirtyMarker,
+    /// Peers flagged by the node operator and containers retrieved from them
+    pub(crate) quarantine: Quarantine,
+    /// Peers banned from posting and proposing topics
+    pub(crate) moderation: Moderation,
+    /// Containers fetched metadata-first, which chunks are deferred
+    pub(crate) lazy: LazyFetches,
+    /// Accounting of the stored containers against the storage quotas
+    pub(crate) quotas: Quotas,
+    /// Collector of the chunks not referenced by the stored containers
+    pub(crate) gc: ChunkGc,
+    /// Remote peers from which the containers are being retrieved
+    pub(crate) container_sources: HashMap<ContainerId, NodeId>,
+    /// Digests of the low-priority events broadcasted to the extensions
+    pub(crate) events: EventDigests,
+
+    /// Container transfers of the bulk replication apps deferred by the replication schedule
+    pub(crate) replication: ReplicationSchedule,
+    /// Chunk requests of the remote peers deferred by the rate limits
+    pub(crate) rate_limits: RateLimiter,
+    /// Dispatcher of the received messages to the webhooks
+    pub(crate) webhooks: Webhooks,
+    /// Program deciding on the messages received from the remote peers
+    pub(crate) policy: Option<Policy>,
+    /// Proof-of-work stamps required from the unknown peers and presented to the remote peers
+    pub(crate) stamps: Stamps,
+    /// Reconciliation of the pins and topics with the replication partners
+    pub(crate) entropy: AntiEntropy,
+    /// Admin commands exchanged with the other nodes of the operator
+    pub(crate) admin: AdminChannel,
+    /// Sensitive admin actions awaiting approvals of the operator keys, and their audit log
+    pub(crate) approvals: Approvals,
+    /// Summary of the store repair run on the node start
+    pub(crate) repaired: Option<RepairSummary>,
+    /// UNIX timestamp of the node start
+    pub(crate) started: u64,
+    /// Key signing the exported manifests of the hosted content
+    pub(crate) manifest_key: ManifestKey,
+    /// Container retrievals resumed once the remote peer shows up again
+    pub(crate) resumes: Resumes,
+    /// Attestations of the software run by the remote peers
+    pub(crate) attestations: Attestations,
+    /// Features of the node and the ones advertised by the remote peers
+    pub(crate) features: PeerFeatures,
+    /// Maintenance mode, in which new transfer requests of the remote peers are rejected
+    pub(crate) maintenance: Maintenance,
+    /// Replicas of the containers kept by the storage peers and kept for the remote peers
+    pub(crate) replicator: Replicator,
+    /// Accounting of the daemon memory against the memory budget
+    pub(crate) memory: MemoryBudget,
+    /// Event kinds the clients have subscribed to
+    pub(crate) subscribed: BTreeSet<EventKind>,
+    /// Filtered subscriptions of the clients, which receive the events over the RPC bus
+    pub(crate) subscriptions: BTreeMap<ClientId, EventFilter>,
+    /// Interests in the containers and messages registered by the remote peers
+    pub(crate) interests: Interests,
+    /// Child daemons which are being stopped on the node shutdown
+    pub(crate) shutdown: Shutdown,
 }
 
 impl Runtime {
@@ -97,26 +286,209 @@ impl Runtime {
         let mut store =
             store_rpc::Client::with(&config.store_endpoint).map_err(LaunchError::from)?;
 
-        for table in [DB_TABLE_CONTAINER_HEADERS, DB_TABLE_CONTAINERS, DB_TABLE_CHUNKS] {
+        for table in [
+            DB_TABLE_CONTAINER_HEADERS,
+            DB_TABLE_CONTAINERS,
+            DB_TABLE_CHUNKS,
+            DB_TABLE_CONTAINER_VERSIONS,
+            DB_TABLE_INLINE_CHUNKS,
+        ] {
             store.use_table(table.to_owned()).map_err(LaunchError::from)?;
         }
+        migrate(&config.data_dir, &mut store).map_err(LaunchError::from)?;
+
+        let mut containers = if config.ext.rebuild_index {
+            warn!("Rebuilding container index from the store");
+            let mut containers =
+                ContainerIndex::create(&config.data_dir).map_err(LaunchError::from)?;
+            containers.rebuild(&mut store).map_err(LaunchError::from)?;
+            containers.checkpoint().map_err(LaunchError::from)?;
+            containers
+        } else {
+            let mut containers =
+                ContainerIndex::open(&config.data_dir).map_err(LaunchError::from)?;
+            containers.load(&mut store)?;
+            containers
+        };
+        let mut dirty = DirtyMarker::load(&config.data_dir);
+        let repaired = if dirty.is_set() {
+            warn!("Node was stopped during container transfers; repairing the store");
+            let (summary, discarded) = repair(&mut store).map_err(LaunchError::from)?;
+            for container_id in discarded {
+                containers.discard(container_id).map_err(LaunchError::from)?;
+            }
+            info!("Store repair complete: {}", summary);
+            dirty.clear().map_err(LaunchError::from)?;
+            Some(summary)
+        } else {
+            None
+        };
+        let shares = Shares::load(&config.data_dir).map_err(LaunchError::from)?;
+        let manifest_key = ManifestKey::load(&config.data_dir).map_err(LaunchError::from)?;
+        let attestations = Attestations::load(&config.data_dir).map_err(LaunchError::from)?;
+        let pins = Pins::load(&config.data_dir).map_err(LaunchError::from)?;
+        let stats = Stats::load(&config.data_dir).map_err(LaunchError::from)?;
+        let quarantine = Quarantine::load(&config.data_dir).map_err(LaunchError::from)?;
+        let moderation = Moderation::load(&config.data_dir, config.ext.spam_ban_threshold)
+            .map_err(LaunchError::from)?;
+        let lazy = LazyFetches::load(&config.data_dir).map_err(LaunchError::from)?;
+        let resumes = Resumes::load(&config.data_dir).map_err(LaunchError::from)?;
+        let quotas = Quotas::load(&config.data_dir, config.ext.quota).map_err(LaunchError::from)?;
+        let gc_interval =
+            Some(config.ext.gc_interval).filter(|interval| *interval > 0).map(Duration::from_secs);
+        let gc = ChunkGc::load(&config.data_dir, config.ext.gc_grace, gc_interval)
+            .map_err(LaunchError::from)?;
+        // Data of the evicted containers remain in the store, so the index picks them up
+        for container_id in quotas.evicted() {
+            if containers.contains(container_id) {
+                containers.discard(container_id).map_err(LaunchError::from)?;
+            }
+        }
+        let dictionaries = Dictionaries::load(&config.data_dir).map_err(LaunchError::from)?;
+        let metrics = config.ext.metrics.map(|metrics| {
+            info!("Recording metrics in {} format every {} s", metrics.format, metrics.interval);
+            MetricsRecorder::with(&config.data_dir, metrics)
+        });
+        let registry = match config.ext.metrics_endpoint {
+            Some(addr) => {
+                let registry = Arc::new(Mutex::new(MetricsRegistry::default()));
+                serve_metrics(addr, registry.clone())
+                    .map_err(|err| LaunchError::MetricsEndpoint(addr, err.to_string()))?;
+                info!("Serving metrics on http://{}/metrics", addr);
+                Some(registry)
+            }
+            None => None,
+        };
+        let events = EventDigests::with(
+            config.ext.event_digest.clone(),
+            Duration::from_secs(config.ext.digest_interval),
+        );
+        let replication = ReplicationSchedule::with(
+            config.ext.replication_window,
+            config.ext.replication_bandwidth,
+        );
+        if let Some(window) = config.ext.replication_window {
+            info!("Bulk replication runs during {}", window);
+        }
+        let rate_limits = RateLimiter::with(config.ext.rate_limit, config.ext.peer_rate_limit);
+        let memory = MemoryBudget::with(config.ext.memory_budget);
+        let webhooks =
+            Webhooks::with(config.ext.webhooks.clone(), config.ext.webhook_secret.clone());
+        let policy = config.ext.policy.clone().map(Policy::with);
+        let stamps =
+            Stamps::with(config.ext.pow_difficulty, config.ext.pow_stamp, config.ext.node_id);
+        let entropy = AntiEntropy::with(
+            config.ext.replicas.clone(),
+            Duration::from_secs(config.ext.anti_entropy_interval),
+        );
+        let admin = AdminChannel::with(config.ext.admins.clone());
+        let mut features = NodeFeatures::default();
+        if config.ext.read_only {
+            info!("Node runs as a read-only replica");
+            features.set(NodeFeatures::READ_ONLY);
+        }
+        if config.ext.storage_peer {
+            info!("Node keeps replicas for the remote peers");
+            features.set(NodeFeatures::STORAGE);
+        }
+        let approvals = Approvals::load(
+            &config.data_dir,
+            config.ext.approvers.clone(),
+            config.ext.approval_threshold,
+        )
+        .map_err(LaunchError::from)?;
+        let mut popularity = Popularity::default();
+        for container_id in pins.iter() {
+            popularity.set_pinned(container_id, true);
+        }
+
+        if chaos::is_supported() {
+            warn!("Node is built with the chaos testing hooks and must not be used in production");
+        }
 
         info!("Stormd runtime started successfully");
 
         Ok(Self {
             config,
             store,
-            registered_apps: empty!(),
+            apps: empty!(),
             transferd_free: empty!(),
             transferd_busy: empty!(),
+            daemons: empty!(),
+            supervisor: empty!(),
+            peer_app_requests: empty!(),
+            peer_topic_requests: empty!(),
             container_apps: empty!(),
             container_transfers: empty!(),
             ctl_queue: empty!(),
+            containers,
+            jobs: empty!(),
+            sessions: empty!(),
+            popularity,
+            cache_admissions: empty!(),
+            ext_lanes: empty!(),
+            request_cache: empty!(),
+            current_request: None,
+            bus_features: empty!(),
+            dictionaries,
+            peer_routes: empty!(),
+            peer_monitor: empty!(),
+            bulk_peers: empty!(),
+            topics: empty!(),
+            msg_link: empty!(),
+            shares,
+            pins,
+            stats,
+            metrics,
+            registry,
+            dirty,
+            quarantine,
+            moderation,
+            lazy,
+            quotas,
+            gc,
+            container_sources: empty!(),
+            events,
+            replication,
+            rate_limits,
+            webhooks,
+            policy,
+            stamps,
+            entropy,
+            admin,
+            approvals,
+            repaired,
+            started: unix_time(),
+            manifest_key,
+            resumes,
+            attestations,
+            features: PeerFeatures::with(features),
+            maintenance: empty!(),
+            replicator: empty!(),
+            memory,
+            subscribed: empty!(),
+            subscriptions: empty!(),
+            polls: empty!(),
+            interests: empty!(),
+            shutdown: empty!(),
         })
     }
 }
 
-impl Responder for Runtime {}
+impl Responder for Runtime {
+    fn msg_bus(&self, remote_id: NodeId) -> ServiceBus { self.peer_routes.route(remote_id) }
+
+    fn bus_features(&self, service_id: &ServiceId) -> BusFeatures {
+        self.bus_features.get(service_id).copied().unwrap_or_default()
+    }
+
+    fn dictionary(&self, service_id: &ServiceId) -> Option<&Dictionary> {
+        match service_id {
+            ServiceId::StormApp(app) => self.dictionaries.get(*app),
+            _ => None,
+        }
+    }
+}
 
 impl esb::Handler<ServiceBus> for Runtime {
     type Request = BusMsg;
@@ -127,11 +499,19 @@ impl esb::Handler<ServiceBus> for Runtime {
     fn on_ready(&mut self, _senders: &mut Endpoints) -> Result<(), Self::Error> {
         if self.config.ext.run_chat {
             info!("Starting chat daemon...");
-            self.launch_daemon(Daemon::Chatd, self.config.clone())?;
+            self.start_daemon(Daemon::Chatd)?;
+        }
+        if self.config.ext.run_mail {
+            info!("Starting mail daemon...");
+            self.start_daemon(Daemon::Maild)?;
+        }
+        if self.config.ext.run_board {
+            info!("Starting board daemon...");
+            self.start_daemon(Daemon::Boardd)?;
         }
         if self.config.ext.run_downpour {
             info!("Starting downpour daemon...");
-            self.launch_daemon(Daemon::Downpourd, self.config.clone())?;
+            self.start_daemon(Daemon::Downpourd)?;
         }
         Ok(())
     }
@@ -143,8 +523,80 @@ impl esb::Handler<ServiceBus> for Runtime {
         source: ServiceId,
         request: Self::Request,
     ) -> Result<(), Self::Error> {
-        match (bus_id, request, source) {
-            (ServiceBus::Msg, BusMsg::Bifrost(msg), ServiceId::Peer(remote_id)) => {
+        chaos::delay();
+        self.daemons.seen(&source);
+        if self.supervisor.seen(&source) {
+            info!("{} daemon is responding again after the restart", source);
+        }
+        // Any message from the LNP node proves that the connection is alive
+        self.flush_msg(
+            endpoints,
+            matches!(bus_id, ServiceBus::Msg | ServiceBus::PeerMsg(_) | ServiceBus::Bulk),
+        );
+        self.present_stamps(endpoints);
+        if let Err(err) = self.stats.save_due() {
+            warn!("Unable to save node statistics: {}", err);
+        }
+        if let Err(err) = self.shares.save_due() {
+            warn!("Unable to save share token uses: {}", err);
+        }
+        self.record_metrics();
+        self.update_metrics();
+        self.flush_digests();
+        self.release_replication(endpoints);
+        self.release_chunk_requests(endpoints);
+        self.account_memory(endpoints);
+        self.reconcile_replicas(endpoints);
+        self.check_apps();
+        self.supervise(endpoints);
+        if self.gc.is_due() && self.transferd_busy.is_empty() {
+            if let Err(err) = self.collect_garbage(false) {
+                error!("Unable to collect orphaned chunks: {}", err);
+            }
+        }
+        for client_id in self.sessions.expire() {
+            self.forget_client(client_id);
+        }
+        self.expire_polls(endpoints);
+        self.expire_admin(endpoints);
+        for (container_id, peer) in self.replicator.expire() {
+            warn!("Storage peer {} has not confirmed replica of {}", peer, container_id);
+        }
+        for remote_id in self.peer_monitor.probes_due() {
+            trace!("Probing latency of {}", remote_id);
+            self.send_msg(endpoints, remote_id, Messages::ListApps);
+        }
+
+        if let (ServiceBus::Storm, BusMsg::Storm(_), ServiceId::StormApp(app)) =
+            (bus_id, &request, &source)
+        {
+            if self.apps.seen(*app) {
+                info!("Application {} is responding again and is registered", app);
+            }
+            self.learn_dictionary(*app, &request);
+        }
+
+        let res = match (bus_id, request, source) {
+            (bus_id, BusMsg::Compressed(compressed), source) => {
+                let request = BusMsg::decompress(&compressed)?;
+                return self.handle(endpoints, bus_id, source, request);
+            }
+            (bus_id, BusMsg::DictCompressed(compressed), ServiceId::StormApp(app)) => {
+                let dict = self
+                    .dictionaries
+                    .known(app)
+                    .ok_or(DaemonError::UnknownDictionary(compressed.dict_id))?;
+                let request = BusMsg::decompress_with(&compressed, dict)?;
+                return self.handle(endpoints, bus_id, ServiceId::StormApp(app), request);
+            }
+            (
+                bus @ (ServiceBus::Msg | ServiceBus::PeerMsg(_)),
+                BusMsg::Bifrost(msg),
+                ServiceId::Peer(remote_id),
+            ) => {
+                if self.peer_routes.register(remote_id, bus) {
+                    debug!("Peer {} is reachable through {} bus", remote_id, bus);
+                }
                 self.handle_p2p(endpoints, remote_id, msg)
             }
             (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
@@ -152,13 +604,29 @@ impl esb::Handler<ServiceBus> for Runtime {
                 self.handle_app(endpoints, app_id, msg)
             }
             (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
-                self.handle_rpc(endpoints, client_id, msg)
+                if self.sessions.seen(client_id) {
+                    self.handle_rpc(endpoints, client_id, msg)
+                } else {
+                    debug!("Refusing {} from disconnected client {:#018x}", msg, client_id);
+                    self.send_rpc(endpoints, client_id, DaemonError::SessionClosed)?;
+                    Ok(())
+                }
             }
             (ServiceBus::Rpc, BusMsg::Storm(msg), other_source) => {
                 self.handle_others(endpoints, other_source, msg)
             }
+            (ServiceBus::Bulk, BusMsg::Bifrost(msg), ServiceId::Peer(remote_id)) => {
+                // LNP node uses bulk data bus only for the peers with which it has negotiated the
+                // bulk sub-channel
+                if self.bulk_peers.insert(remote_id) {
+                    info!("Peer {} supports bulk data channel", remote_id);
+                }
+                self.handle_p2p(endpoints, remote_id, msg)
+            }
             (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
-        }
+        };
+        self.flush_ext(endpoints);
+        res
     }
 
     fn handle_err(
@@ -185,12 +653,125 @@ impl Runtime {
             payload,
         }) = &message
         {
+            self.stats.received(payload.len());
             let mesg = STORM_P2P_UNMARSHALLER.unmarshall(&**payload)?.deref().clone();
+            self.observe(|registry| {
+                let ty = message_type(&mesg);
+                registry.inc("storm_p2p_messages_received_total", &[("type", &ty)], 1);
+            });
+            self.peer_active(endpoints, remote_id)?;
+
+            if let Some((app, id, kind)) = match &mesg {
+                Messages::PullContainer(AppMsg { app, data }) => {
+                    Some((*app, *data, RequestKind::Container))
+                }
+                Messages::PullChunk(ChunkPull {
+                    app,
+                    message_id,
+                    container_id,
+                    ..
+                }) => Some((
+                    *app,
+                    ContainerFullId {
+                        message_id: *message_id,
+                        container_id: *container_id,
+                    },
+                    RequestKind::Chunk,
+                )),
+                _ => None,
+            } {
+                if self.quotas.is_evicted(id.container_id) {
+                    debug!("Peer {} requests evicted container {}", remote_id, id);
+                    self.send_msg(endpoints, remote_id, Messages::Reject(AppMsg { app, data: id }));
+                    return Ok(());
+                }
+                if self.quarantine.is_held(id.container_id) {
+                    debug!("Peer {} requests quarantined container {}", remote_id, id);
+                    self.send_msg(endpoints, remote_id, Messages::Reject(AppMsg { app, data: id }));
+                    return Ok(());
+                }
+                // Chunks of the containers requested before the maintenance are still served
+                if kind == RequestKind::Container && self.maintenance.is_active() {
+                    debug!("Rejecting request of {} from {} in maintenance", id, remote_id);
+                    self.send_msg(endpoints, remote_id, Messages::Reject(AppMsg { app, data: id }));
+                    self.notify_maintenance(endpoints, remote_id);
+                    return Ok(());
+                }
+                match self.shares.authorize(id.container_id, remote_id) {
+                    Access::Public => {}
+                    Access::Granted(token_id) if kind == RequestKind::Container => {
+                        self.shares.register_use(token_id)?;
+                    }
+                    Access::Granted(_) => {}
+                    // Storage peers retrieve the replicas they are asked to keep
+                    Access::Denied if self.replicator.is_pending(id.container_id, remote_id) => {}
+                    Access::Denied => {
+                        warn!("Peer {} has no share token for container {}", remote_id, id);
+                        self.send_msg(
+                            endpoints,
+                            remote_id,
+                            Messages::Reject(AppMsg { app, data: id }),
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+
+            match &mesg {
+                Messages::PullContainer(AppMsg { app, data }) => {
+                    self.track_request(endpoints, *app, *data, RequestKind::Container)?;
+                }
+                Messages::PullChunk(ChunkPull {
+                    app,
+                    message_id,
+                    container_id,
+                    ..
+                }) => {
+                    let id = ContainerFullId {
+                        message_id: *message_id,
+                        container_id: *container_id,
+                    };
+                    self.track_request(endpoints, *app, id, RequestKind::Chunk)?;
+                    // The first read of a container fetched metadata-first retrieves its chunks
+                    self.materialize(endpoints, *container_id)?;
+                }
+                Messages::AnnounceContainer(AppMsg { data, .. }) => {
+                    self.popularity.register_source(data.id.container_id, remote_id);
+                }
+                Messages::ProposeTopic(_)
+                    if self.config.ext.read_only || self.moderation.is_banned(remote_id) => {}
+                Messages::ProposeTopic(AppMsg { app, data }) => {
+                    let topic_id = TopicId::from(data);
+                    self.topics.register(*app, topic_id);
+                    self.publish(endpoints, Event::TopicProposed {
+                        app: *app,
+                        remote_id,
+                        topic_id,
+                    });
+                }
+                Messages::AppTopics(AppMsg { app, data }) => {
+                    let topics = data.iter().copied().map(TopicId::from_wire).collect();
+                    let delta = self.topics.reconcile(*app, remote_id, &topics);
+                    let clients = self.peer_topic_requests.remove(&(remote_id, *app));
+                    for (client_id, page) in clients.unwrap_or_default() {
+                        let page =
+                            Page::by_key(topics.iter().copied(), &page, |topic_id| *topic_id);
+                        let reply = RpcMsg::PeerTopics(page);
+                        if self.send_rpc(endpoints, client_id, reply).is_err() {
+                            warn!("Client {} is disconnected", client_id);
+                        }
+                    }
+                    for topic_id in topics {
+                        self.topics.register(*app, topic_id);
+                    }
+                    if let Some(delta) = delta {
+                        self.topics_synced(endpoints, *app, remote_id, delta);
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
 
-            /* Messages::PullContainer(_) => {} */
-            // Messages::Reject(_) => {}
-            // Messages::PullChunk(_) => {}
-            // Messages::PushChunk(_) => {}
             if matches!(
                 mesg,
                 Messages::PushContainer(_) | Messages::PullChunk(_) | Messages::PushChunk(_)
@@ -219,25 +800,68 @@ impl Runtime {
                                 storm_app: app,
                                 container_id,
                                 chunk_ids,
+                                bulk: self.bulk_peers.contains(&remote_id),
                             },
                         }),
                     ),
-                    // TODO: Ensure that the incoming chunks references correct app id and chunk id
+                    // Chunks are verified against the container manifest by the transfer service
                     Messages::PushChunk(ChunkPush {
                         app: _,
                         container_id,
-                        chunk_id: _,
+                        chunk_id,
                         chunk,
-                    }) => (container_id, CtlMsg::ProcessChunk(chunk)),
+                    }) => {
+                        if chaos::strikes(FaultTarget::ChunkDrop) {
+                            debug!("Chaos testing drops chunk {} from {}", chunk_id, remote_id);
+                            return Ok(());
+                        }
+                        self.peer_monitor.received(remote_id, chunk.len());
+                        (
+                            container_id,
+                            CtlMsg::ProcessChunk(ChunkReceived {
+                                remote_id,
+                                container_id,
+                                chunk_id,
+                                chunk,
+                            }),
+                        )
+                    }
                     _ => unreachable!(),
                 };
 
+                let instr = match instr {
+                    CtlMsg::SendChunks(request) => match self.rate_limits.admit(request) {
+                        Some(request) => CtlMsg::SendChunks(request),
+                        None => {
+                            debug!(
+                                "Chunk request from {} is deferred by the rate limits ({} \
+                                 deferred)",
+                                remote_id,
+                                self.rate_limits.deferred()
+                            );
+                            return Ok(());
+                        }
+                    },
+                    instr => instr,
+                };
+
                 if let Some(daemon_id) = self.container_transfers.get(&container_id) {
                     self.send_ctl(endpoints, ServiceId::Transfer(*daemon_id), instr)?;
-                } else if matches!(instr, CtlMsg::ProcessContainer(_)) {
-                    self.ctl_queue.push_back(instr);
-                    self.pick_or_start(endpoints, None)?;
-                } else if matches!(instr, CtlMsg::SendChunks(_)) {
+                } else if self.config.ext.read_only && matches!(instr, CtlMsg::ProcessContainer(_))
+                {
+                    debug!(
+                        "Dropping container {} pushed by {} to read-only node",
+                        container_id, remote_id
+                    );
+                } else if self.maintenance.is_active()
+                    && matches!(instr, CtlMsg::ProcessContainer(_))
+                {
+                    debug!(
+                        "Dropping container {} pushed by {} in maintenance",
+                        container_id, remote_id
+                    );
+                    self.notify_maintenance(endpoints, remote_id);
+                } else if matches!(instr, CtlMsg::ProcessContainer(_) | CtlMsg::SendChunks(_)) {
                     self.ctl_queue.push_back(instr);
                     self.pick_or_start(endpoints, None)?;
                 } else {
@@ -248,22 +872,101 @@ impl Runtime {
             }
 
             match mesg.storm_ext_msg(remote_id) {
-                Ok((app, storm_msg)) => self.send_ext(endpoints, Some(app), storm_msg)?,
+                Ok((app, storm_msg)) => {
+                    if !self.stamps.is_allowed(remote_id) {
+                        debug!("Dropping message from {} which has presented no stamp", remote_id);
+                        return Ok(());
+                    }
+                    if self.moderation.is_banned(remote_id)
+                        && matches!(storm_msg, ExtMsg::Post(_) | ExtMsg::ProposeTopic(_))
+                    {
+                        debug!("Dropping {} from banned peer {}", storm_msg, remote_id);
+                        return Ok(());
+                    }
+                    if self.apps.route(app) == AppRoute::Departed
+                        || self.supervisor.is_down(&ServiceId::StormApp(app))
+                    {
+                        self.decline_ext(endpoints, app, &storm_msg, "it has gone away");
+                        return Ok(());
+                    }
+                    if self.config.ext.read_only
+                        && matches!(storm_msg, ExtMsg::Post(_) | ExtMsg::ProposeTopic(_))
+                    {
+                        self.decline_ext(endpoints, app, &storm_msg, "node is read-only");
+                        return Ok(());
+                    }
+                    if !self.judge(endpoints, app, &storm_msg) {
+                        return Ok(());
+                    }
+                    if let ExtMsg::Post(AddressedMsg {
+                        remote_id,
+                        ref data,
+                    }) = storm_msg
+                    {
+                        let dropped = self.webhooks.dispatch(app, remote_id, data);
+                        if dropped > 0 {
+                            self.observe(|registry| {
+                                registry.inc("storm_webhook_dropped_total", &[], dropped)
+                            });
+                        }
+                        self.publish(endpoints, Event::MessageReceived {
+                            app,
+                            remote_id,
+                            mesg_id: data.mesg_id(),
+                        });
+                    }
+                    let storm_msg = self.resolve_topic(app, storm_msg);
+                    self.ext_lanes.push(Some(app), storm_msg)
+                }
 
                 // Messages we process ourselves
                 Err(Messages::ListApps) => {
-                    self.send_p2p(
-                        endpoints,
-                        remote_id,
-                        Messages::ActiveApps(self.registered_apps.clone()),
-                    )?;
+                    let apps = self.apps.to_set();
+                    self.send_msg(endpoints, remote_id, Messages::ActiveApps(apps));
+                }
+
+                // A remote peer described list of apps; we use this as a reply to our latency
+                // probe and to the clients which asked for the peer apps.
+                Err(Messages::ActiveApps(apps)) => {
+                    self.peer_monitor.echo(remote_id);
+                    let clients = self.peer_app_requests.remove(&remote_id);
+                    for (client_id, page) in clients.unwrap_or_default() {
+                        let reply =
+                            RpcMsg::PeerApps(Page::by_key(apps.iter().copied(), &page, |app| *app));
+                        if self.send_rpc(endpoints, client_id, reply).is_err() {
+                            warn!("Client {} is disconnected", client_id);
+                        }
+                    }
                 }
 
-                // A remote peer described list of apps. We need to report that to a client.
-                Err(Messages::ActiveApps(_)) => {}
+                // A remote peer has rejected our container or chunk request; the transfer
+                // retrieving the container from it fails
+                Err(Messages::Reject(AppMsg { data, .. })) => {
+                    let container_id = data.container_id;
+                    if let Some(daemon_id) = self.container_transfers.get(&container_id) {
+                        let report = CtlMsg::Rejected(AddressedMsg {
+                            remote_id,
+                            data: container_id,
+                        });
+                        self.send_ctl(endpoints, ServiceId::Transfer(*daemon_id), report)?;
+                    } else {
+                        debug!("Peer {} has rejected {} which is not retrieved", remote_id, data);
+                    }
+                }
 
                 _ => {}
             }
+        } else if let LnMsg::Message(bifrost::Msg {
+            app: BifrostApp::Vendor(BIFROST_APP_STORM_OPAQUE),
+            payload,
+        }) = &message
+        {
+            self.stats.received(payload.len());
+            self.observe(|registry| {
+                registry.inc("storm_p2p_messages_received_total", &[("type", "opaque")], 1);
+            });
+            self.peer_active(endpoints, remote_id)?;
+            self.handle_opaque(endpoints, remote_id, payload)?;
         } else {
             error!("Request is not supported by the RPC interface");
             return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &message));
@@ -278,6 +981,40 @@ impl Runtime {
         message: RpcMsg,
     ) -> Result<(), DaemonError> {
         match message {
+            RpcMsg::Idempotent(Idempotent {
+                request_id,
+                request,
+            }) => {
+                if let Some(reply) = self.request_cache.get(client_id, request_id) {
+                    debug!("Request {:#018x} was already executed; skipping it", request_id);
+                    let reply = reply.unwrap_or_else(|| {
+                        RpcMsg::Success(
+                            Some(format!("request {:#018x} was already accepted", request_id))
+                                .into(),
+                        )
+                    });
+                    self.send_rpc(endpoints, client_id, reply)?;
+                    return Ok(());
+                }
+                if matches!(*request, RpcMsg::Idempotent(_)) {
+                    return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &request));
+                }
+                self.request_cache.start(client_id, request_id);
+                self.current_request = Some(request_id);
+                let res = self.handle_rpc(endpoints, client_id, *request);
+                self.current_request = None;
+                if res.is_err() {
+                    self.request_cache.forget(client_id, request_id);
+                }
+                res
+            }
+
+            RpcMsg::Negotiate(features) => {
+                let features = self.negotiate(ServiceId::Client(client_id), features);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Negotiate(features))?;
+                Ok(())
+            }
+
             RpcMsg::SendContainer(container) => {
                 self.ctl_queue.push_back(CtlMsg::AnnounceContainer(AddressedClientMsg {
                     remote_id: container.remote_id,
@@ -288,6 +1025,8 @@ impl Runtime {
             }
 
             RpcMsg::GetContainer(container) => {
+                self.popularity
+                    .record(container.data.container_id.container_id, RequestKind::Container);
                 self.ctl_queue.push_back(CtlMsg::GetContainer(AddressedClientMsg {
                     remote_id: container.remote_id,
                     client_id: Some(client_id),
@@ -296,213 +1035,2867 @@ impl Runtime {
                 self.pick_or_start(endpoints, Some(client_id))
             }
 
-            wrong_msg => {
-                error!("Request is not supported by the RPC interface");
-                Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg))
+            RpcMsg::SubmitJob(JobRequest { kind, container }) => {
+                let limit = self.config.ext.max_client_jobs;
+                if self.sessions.jobs(client_id) >= limit as usize {
+                    warn!("Client {:#018x} has reached the limit of unfinished jobs", client_id);
+                    self.reply_rpc(endpoints, client_id, DaemonError::JobLimit(limit).into())?;
+                    return Ok(());
+                }
+                let remote_id = container.remote_id;
+                let container_id = container.data.container_id.container_id;
+                let job_id = self.jobs.submit(kind, remote_id, container_id);
+                self.sessions.job_submitted(client_id, job_id);
+                info!("Job {:#018x} to {} {} is submitted", job_id, kind, container_id);
+                self.popularity.record(container_id, RequestKind::Container);
+                self.ctl_queue.push_back(CtlMsg::GetContainer(AddressedClientMsg {
+                    remote_id,
+                    client_id: None,
+                    data: container.data,
+                }));
+                self.reply_rpc(endpoints, client_id, RpcMsg::Job(job_id))?;
+                self.pick_or_start(endpoints, None)
             }
-        }
-    }
 
-    fn handle_ctl(
-        &mut self,
-        endpoints: &mut Endpoints,
-        source: ServiceId,
-        message: CtlMsg,
-    ) -> Result<(), DaemonError> {
-        match &message {
-            CtlMsg::Hello => {
-                if matches!(source, ServiceId::Transfer(_)) {
-                    self.accept_daemon(source)?;
-                    self.pick_task(endpoints)?;
-                }
-                // TODO: Register other daemons
+            RpcMsg::JobStatus(job_id) => {
+                let reply = match self.jobs.get(job_id) {
+                    Some(info) => RpcMsg::JobInfo(info),
+                    None => DaemonError::UnknownJob(job_id).into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
             }
 
-            CtlMsg::ProcessingFailed | CtlMsg::ProcessingComplete => {
-                if let ServiceId::Transfer(daemon_id) = source {
-                    if let Some(container_id) = self
-                        .container_transfers
-                        .iter()
-                        .find(|(_, id)| daemon_id == **id)
-                        .map(|(a, _)| a)
-                        .copied()
-                    {
-                        self.container_transfers.remove(&container_id);
-                        if let Some(app) = self.container_apps.get(&container_id) {
-                            // Notify client on complete process
-                            let _ = self.send_ext(
-                                endpoints,
-                                Some(*app),
-                                ExtMsg::ContainerRetrieved(container_id),
-                            );
-                        }
-                    }
-                    self.transferd_busy.remove(&daemon_id);
-                    self.transferd_free.push_back(daemon_id);
-                    self.pick_task(endpoints)?;
-                }
+            RpcMsg::ListJobs(page) => {
+                let jobs = Page::by_key(self.jobs.list(), &page, |info| info.id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Jobs(jobs))?;
+                Ok(())
             }
 
-            wrong_msg => {
-                error!("Request is not supported by the CTL interface");
-                return Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, wrong_msg));
+            RpcMsg::ListSessions(page) => {
+                let sessions = Page::by_key(self.sessions.list(), &page, |info| info.client_id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Sessions(sessions))?;
+                Ok(())
             }
-        }
 
-        Ok(())
-    }
+            RpcMsg::DisconnectSession(token) => {
+                let reply = match self.sessions.disconnect(token) {
+                    Some(disconnected) => {
+                        info!("Client {:#018x} is disconnected", disconnected);
+                        self.forget_client(disconnected);
+                        RpcMsg::Success(None.into())
+                    }
+                    None => DaemonError::UnknownSession(token).into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
 
-    fn handle_app(
-        &mut self,
-        endpoints: &mut Endpoints,
-        app: StormApp,
-        message: ExtMsg,
-    ) -> Result<(), DaemonError> {
-        match message {
-            ExtMsg::RegisterApp(app_id) => {
-                if app == app_id {
-                    info!("Application {} is registered", app_id);
-                    self.registered_apps.insert(app_id);
-                } else {
-                    error!(
-                        "Request on application {} registration issued by a non-application \
-                         daemon {}",
-                        app,
-                        ServiceId::StormApp(app_id)
+            RpcMsg::EnterMaintenance(retry_after) => {
+                if self.maintenance.enter(retry_after) {
+                    info!(
+                        "Node enters maintenance; {} transfers are draining",
+                        self.container_transfers.len()
                     );
-                    return Err(DaemonError::wrong_esb_msg_source(
-                        ServiceBus::Storm,
-                        &message,
-                        ServiceId::StormApp(app_id),
-                    ));
                 }
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
             }
 
-            ExtMsg::RetrieveContainer(container) => {
-                self.container_apps.insert(container.data.container_id, app);
-                self.ctl_queue.push_back(CtlMsg::GetContainer(AddressedClientMsg {
-                    remote_id: container.remote_id,
-                    client_id: None,
-                    data: AppContainer {
-                        storm_app: app,
-                        container_id: container.data,
-                    },
-                }));
-                self.pick_or_start(endpoints, None)?;
+            RpcMsg::ExitMaintenance => {
+                if self.maintenance.exit() {
+                    info!("Node leaves maintenance");
+                }
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
             }
 
-            ExtMsg::SendContainer(container) => {
-                self.ctl_queue.push_back(CtlMsg::SendContainer(AddressedClientMsg {
-                    remote_id: container.remote_id,
-                    client_id: None,
-                    data: AppContainer {
-                        storm_app: app,
-                        container_id: container.data,
-                    },
-                }));
-                self.pick_or_start(endpoints, None)?;
+            RpcMsg::Shutdown => {
+                self.start_shutdown(endpoints);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                self.stop_if_complete();
+                Ok(())
             }
 
-            // We need to the rest of the messages to the Bifrost network
-            forward => {
-                self.send_p2p(endpoints, forward.remote_id(), forward.p2p_message(app))?;
+            RpcMsg::ListDaemons(page) => {
+                let daemons = Page::by_key(self.daemons.list(), &page, |info| {
+                    (info.since, info.service_id.to_string())
+                });
+                self.reply_rpc(endpoints, client_id, RpcMsg::Daemons(daemons))?;
+                Ok(())
             }
-        }
 
-        Ok(())
-    }
+            RpcMsg::GetInfo => {
+                let info = self.node_info();
+                self.reply_rpc(endpoints, client_id, RpcMsg::NodeInfo(info))?;
+                Ok(())
+            }
 
-    fn handle_others(
-        &mut self,
-        endpoints: &mut Endpoints,
-        source: ServiceId,
-        message: ExtMsg,
-    ) -> Result<(), DaemonError> {
-        match message {
-            ExtMsg::ContainerAnnouncement(container) => {
-                info!("Receive a container announcement from {}", source);
-                self.ctl_queue.push_back(CtlMsg::AnnounceContainer(AddressedClientMsg {
-                    remote_id: container.remote_id,
-                    client_id: None,
-                    data: AppContainer {
-                        storm_app: StormApp::FileTransfer,
-                        container_id: container.data.id,
-                    },
-                }));
-                self.pick_or_start(endpoints, None)?;
+            RpcMsg::GetPeerStats(page) => {
+                let stats = Page::by_key(self.peer_monitor.stats(), &page, |stats| stats.remote_id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::PeerStats(stats))?;
+                Ok(())
             }
 
-            wrong_msg => {
-                error!("Request is not supported by the RPC interface");
-                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            RpcMsg::ListPeerApps(Paged {
+                query: remote_id,
+                page,
+            }) => {
+                // Reply is sent once the peer answers with `ActiveApps`
+                self.peer_app_requests.entry(remote_id).or_default().push((client_id, page));
+                self.send_msg(endpoints, remote_id, Messages::ListApps);
+                Ok(())
             }
-        }
 
-        Ok(())
-    }
-}
+            RpcMsg::ListPeerTopics(Paged {
+                query:
+                    AddressedMsg {
+                        remote_id,
+                        data: app,
+                    },
+                page,
+            }) => {
+                // Reply is sent once the peer answers with `AppTopics`
+                self.peer_topic_requests
+                    .entry((remote_id, app))
+                    .or_default()
+                    .push((client_id, page));
+                self.send_msg(endpoints, remote_id, Messages::ListTopics(AppMsg { app, data: () }));
+                Ok(())
+            }
+
+            RpcMsg::Administer(AddressedMsg {
+                remote_id,
+                data: command,
+            }) => {
+                // Reply is sent once the managed node answers or the request times out
+                let request = self.admin.issue(remote_id, client_id, command);
+                self.send_node_msg(endpoints, remote_id, ADMIN_REQUEST_MSG_TYPE, &request)?;
+                Ok(())
+            }
+
+            RpcMsg::Subscribe(events) => {
+                let reply = match self.config.ext.notify_endpoint {
+                    Some(_) => {
+                        debug!("Client {} subscribes to {} event kinds", client_id, events.len());
+                        self.subscribed.extend(events);
+                        RpcMsg::Success(None.into())
+                    }
+                    None => DaemonError::NotificationsDisabled.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::SubscribeFiltered(filter) => {
+                debug!("Client {} subscribes to events with filter {}", client_id, filter);
+                self.subscriptions.insert(client_id, filter);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::Unsubscribe => {
+                if self.subscriptions.remove(&client_id).is_some() {
+                    debug!("Client {} unsubscribes from events", client_id);
+                }
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::WaitEvent(request) => {
+                trace!("Client {} waits for events {}", client_id, request);
+                // Otherwise the reply is sent once a matching event is published or the request
+                // times out
+                if let Some(polled) = self.polls.wait(client_id, request) {
+                    self.reply_rpc(endpoints, client_id, RpcMsg::PolledEvents(polled))?;
+                }
+                Ok(())
+            }
+
+            RpcMsg::RegisterInterest(AddressedMsg {
+                remote_id,
+                data: interest,
+            }) => {
+                let ids = interest
+                    .containers
+                    .iter()
+                    .map(|id| id.into_inner().into_inner())
+                    .chain(interest.messages.iter().map(|id| id.into_inner().into_inner()))
+                    .collect::<Vec<_>>();
+                let filter = InterestFilter {
+                    app: interest.app,
+                    filter: (!interest.is_empty()).then(|| BloomFilter::with(ids.iter())),
+                };
+                self.send_node_msg(endpoints, remote_id, INTEREST_MSG_TYPE, &filter)?;
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::GetAttestation(remote_id) => {
+                let attestation = self.attestations.get(remote_id).cloned();
+                self.reply_rpc(endpoints, client_id, RpcMsg::PeerAttestation(attestation))?;
+                Ok(())
+            }
+
+            RpcMsg::GetPeerFeatures(remote_id) => {
+                let features = self.features.get(remote_id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::PeerFeatures(features))?;
+                Ok(())
+            }
+
+            RpcMsg::ListTransfers(query) => {
+                let transfers = query.apply(self.transfers());
+                self.reply_rpc(endpoints, client_id, RpcMsg::Transfers(transfers))?;
+                Ok(())
+            }
+
+            RpcMsg::GetTransferProgress(container_id) => {
+                let progress = self.transfer_progress(container_id)?;
+                self.reply_rpc(endpoints, client_id, RpcMsg::TransferProgress(progress))?;
+                Ok(())
+            }
+
+            RpcMsg::Replicate(ReplicateRequest {
+                container_id,
+                peers,
+                min_replicas,
+            }) => {
+                let app = match self.containers.snapshot().get(&container_id) {
+                    Some(entry) => entry.app.unwrap_or(StormApp::System),
+                    None => {
+                        let err = DaemonError::UnknownContainer(container_id);
+                        self.reply_rpc(endpoints, client_id, err.into())?;
+                        return Ok(());
+                    }
+                };
+                if min_replicas as usize > peers.len() {
+                    let err = DaemonError::ReplicasUnreachable(min_replicas, peers.len());
+                    self.reply_rpc(endpoints, client_id, err.into())?;
+                    return Ok(());
+                }
+                let status = self.replicator.start(container_id, peers, min_replicas);
+                info!("Replicating {} to {} storage peers", container_id, status.replicas.len());
+                let request = ReplicaRequest { app, container_id };
+                for peer in status.replicas.keys() {
+                    if self.replicator.is_pending(container_id, *peer) {
+                        self.send_node_msg(endpoints, *peer, REPLICA_REQUEST_MSG_TYPE, &request)?;
+                    }
+                }
+                self.reply_rpc(endpoints, client_id, RpcMsg::ReplicationStatus(status))?;
+                Ok(())
+            }
+
+            RpcMsg::GetReplication(container_id) => {
+                let reply = match self.replicator.status(container_id) {
+                    Some(status) => RpcMsg::ReplicationStatus(status),
+                    None => DaemonError::UnknownReplication(container_id).into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::GetHealth => {
+                let health = self.health();
+                self.reply_rpc(endpoints, client_id, RpcMsg::Health(health))?;
+                Ok(())
+            }
+
+            RpcMsg::FlagPeer(peer) | RpcMsg::UnflagPeer(peer) => {
+                let flagged = matches!(message, RpcMsg::FlagPeer(_));
+                if self.quarantine.flag(peer, flagged)? {
+                    info!("Peer {} is {}", peer, if flagged { "flagged" } else { "unflagged" });
+                }
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::BanPeer(peer) | RpcMsg::UnbanPeer(peer) => {
+                let banned = matches!(message, RpcMsg::BanPeer(_));
+                if self.moderation.ban(peer, banned)? {
+                    info!("Peer {} is {}", peer, if banned { "banned" } else { "unbanned" });
+                }
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::ListBans => {
+                let banned = self.moderation.banned().clone();
+                self.reply_rpc(endpoints, client_id, RpcMsg::Bans(banned))?;
+                Ok(())
+            }
+
+            RpcMsg::GetRateLimits => {
+                let limits = self.rate_limits.limits();
+                self.reply_rpc(endpoints, client_id, RpcMsg::RateLimits(limits))?;
+                Ok(())
+            }
+
+            RpcMsg::SetRateLimit(update) => {
+                info!("Setting rate limit {}", update);
+                self.rate_limits.set(update);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::GetMemory => {
+                let report = self.memory.report();
+                self.reply_rpc(endpoints, client_id, RpcMsg::Memory(report))?;
+                Ok(())
+            }
+
+            RpcMsg::GetFaults => {
+                self.reply_rpc(endpoints, client_id, RpcMsg::Faults(chaos::faults()))?;
+                Ok(())
+            }
+
+            RpcMsg::SetFault(_) if !chaos::is_supported() => {
+                self.reply_rpc(endpoints, client_id, DaemonError::ChaosUnsupported.into())?;
+                Ok(())
+            }
+
+            RpcMsg::SetFault(fault) => {
+                warn!("Chaos testing fault is set: {}", fault);
+                chaos::set(fault);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+                Ok(())
+            }
+
+            RpcMsg::ListQuarantine(page) => {
+                let held = Page::by_key(self.quarantine.list(), &page, |held| held.container_id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Quarantine(held))?;
+                Ok(())
+            }
+
+            RpcMsg::ReleaseContainer(container_id) => {
+                let reply = match self.quarantine.release(container_id)? {
+                    Some(released) => {
+                        info!("Container {} is released from the quarantine", container_id);
+                        self.complete_released(endpoints, container_id, released)?;
+                        RpcMsg::Success(None.into())
+                    }
+                    None => DaemonError::NotQuarantined(container_id).into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::Materialize(container_id) => {
+                let reply = if self.lazy.is_deferred(container_id) {
+                    self.materialize(endpoints, container_id)?;
+                    RpcMsg::Success(None.into())
+                } else {
+                    DaemonError::NotDeferred(container_id).into()
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::GetAppUsage(page) => {
+                let usage = Page::by_key(self.containers.usage(), &page, |usage| usage.app);
+                self.reply_rpc(endpoints, client_id, RpcMsg::AppUsage(usage))?;
+                Ok(())
+            }
+
+            RpcMsg::RebuildIndex if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::RebuildIndex)
+            }
+
+            RpcMsg::RebuildIndex => {
+                let reply = match self.rebuild_index() {
+                    Ok(count) => {
+                        let report = format!("{} containers are indexed", count);
+                        RpcMsg::Success(Some(report).into())
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::CreateBackup => {
+                let snapshot =
+                    IndexSnapshot::with(self.containers.snapshot(), self.pins.snapshot());
+                let info = snapshot.backup(&self.config.data_dir)?;
+                self.reply_rpc(endpoints, client_id, RpcMsg::Backup(info))?;
+                Ok(())
+            }
+
+            RpcMsg::ExportManifest => {
+                let snapshot =
+                    IndexSnapshot::with(self.containers.snapshot(), self.pins.snapshot());
+                let info =
+                    self.manifest_key.export(&self.config.data_dir, &snapshot, &self.quarantine)?;
+                self.reply_rpc(endpoints, client_id, RpcMsg::Manifest(info))?;
+                Ok(())
+            }
+
+            RpcMsg::GetStats => {
+                let stats = self.stats.stats();
+                self.reply_rpc(endpoints, client_id, RpcMsg::Stats(stats))?;
+                Ok(())
+            }
+
+            RpcMsg::GetContainerStats(page) => {
+                let stats = Page::by_offset(self.popularity.stats(), &page);
+                self.reply_rpc(endpoints, client_id, RpcMsg::ContainerStats(stats))?;
+                Ok(())
+            }
+
+            RpcMsg::ListContainers(query) => {
+                let containers = query.apply(
+                    self.containers
+                        .entries()
+                        .filter(|entry| !self.quarantine.is_held(entry.container_id)),
+                );
+                self.reply_rpc(endpoints, client_id, RpcMsg::Containers(containers))?;
+                Ok(())
+            }
+
+            RpcMsg::DeclareVersion(ContainerVersion { container_id, base }) => {
+                let reply = if container_id == base {
+                    DaemonError::SelfVersion(container_id).into()
+                } else {
+                    // Declarations are kept in the store, where transferd looks them up once the
+                    // container manifest is received
+                    let chunk = Chunk::try_from(base.strict_serialize()?)?;
+                    self.store.store(DB_TABLE_CONTAINER_VERSIONS, container_id, &chunk)?;
+                    info!("Container {} is declared to be based on {}", container_id, base);
+                    RpcMsg::Success(None.into())
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::PinMany(_) if self.config.ext.read_only => {
+                self.reply_rpc(endpoints, client_id, DaemonError::ReadOnly.into())?;
+                Ok(())
+            }
+
+            RpcMsg::PinMany(container_ids) => {
+                let reply = match self.pin_many(container_ids, true, false) {
+                    Ok(result) => {
+                        info!("Batch pin: {}", result);
+                        RpcMsg::BatchResult(result)
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::UnpinMany(ContainerBatch {
+                container_ids,
+                dry_run: false,
+            }) if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::Unpin(container_ids))
+            }
+
+            RpcMsg::UnpinMany(ContainerBatch {
+                container_ids,
+                dry_run,
+            }) => {
+                let reply = match self.pin_many(container_ids, false, dry_run) {
+                    Ok(result) => {
+                        info!("Batch unpin{}: {}", dry_run_note(dry_run), result);
+                        RpcMsg::BatchResult(result)
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::EvictMany(ContainerBatch {
+                container_ids,
+                dry_run: false,
+            }) if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::Evict(container_ids))
+            }
+
+            RpcMsg::CollectGarbage(false) if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::CollectGarbage)
+            }
+
+            RpcMsg::RotateManifestKey if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::RotateManifestKey)
+            }
+
+            RpcMsg::RotateManifestKey => {
+                let reply = match self.execute(AdminAction::RotateManifestKey) {
+                    Ok(details) => RpcMsg::Success(Some(details).into()),
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::ListProposals(page) => {
+                let proposals =
+                    Page::by_key(self.approvals.proposals(), &page, |info| info.proposal.id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Proposals(proposals))?;
+                Ok(())
+            }
+
+            RpcMsg::Approve(approval) => {
+                let reply = match self.approvals.approve(&approval) {
+                    Ok(Approved::Pending(missing)) => {
+                        info!(
+                            "Proposal {:#018x} is approved by {}",
+                            approval.proposal_id, approval.approver
+                        );
+                        RpcMsg::ApprovalOutcome(ApprovalOutcome::Pending(missing))
+                    }
+                    Ok(Approved::Complete(proposal)) => {
+                        info!("Proposal {} has collected enough approvals; executing", proposal);
+                        let outcome =
+                            self.execute(proposal.action.clone()).map_err(|err| err.to_string());
+                        self.approvals.executed(&proposal, outcome.clone())?;
+                        match outcome {
+                            Ok(details) => {
+                                RpcMsg::ApprovalOutcome(ApprovalOutcome::Executed(details))
+                            }
+                            Err(err) => {
+                                error!("Approved proposal {} has failed: {}", proposal, err);
+                                RpcMsg::ApprovalOutcome(ApprovalOutcome::Failed(err))
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Rejecting approval of proposal {:#018x}: {}",
+                            approval.proposal_id, err
+                        );
+                        DaemonError::from(err).into()
+                    }
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::ListAudit(page) => {
+                let records =
+                    Page::by_key(self.approvals.audit().iter().cloned(), &page, |record| record.no);
+                self.reply_rpc(endpoints, client_id, RpcMsg::AuditLog(records))?;
+                Ok(())
+            }
+
+            RpcMsg::EvictMany(ContainerBatch {
+                container_ids,
+                dry_run,
+            }) => {
+                let reply = match self.evict_many(container_ids, dry_run) {
+                    Ok(result) => {
+                        info!("Batch eviction{}: {}", dry_run_note(dry_run), result);
+                        RpcMsg::BatchResult(result)
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::CollectGarbage(dry_run) => {
+                // Dry run changes nothing, so it does not interfere with the transfers
+                let reply = if !dry_run && !self.transferd_busy.is_empty() {
+                    DaemonError::TransfersInProgress.into()
+                } else {
+                    match self.collect_garbage(dry_run) {
+                        Ok(report) => RpcMsg::GcReport(report),
+                        Err(err) => err.into(),
+                    }
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::CreateShare(request) => {
+                let reply = match self.shares.create(request) {
+                    Ok(token) => {
+                        info!("Issued share token {:#018x} for {}", token.id, token.container_id);
+                        RpcMsg::ShareToken(token)
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::RevokeShare(token_id) if self.approvals.is_required() => {
+                self.propose(endpoints, client_id, AdminAction::RevokeShare(token_id))
+            }
+
+            RpcMsg::RevokeShare(token_id) => {
+                let reply = match self.shares.revoke(token_id) {
+                    Ok(()) => {
+                        info!("Share token {:#018x} is revoked", token_id);
+                        RpcMsg::Success(None.into())
+                    }
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            RpcMsg::ListShares(page) => {
+                let shares = Page::by_key(self.shares.list(), &page, |info| info.token_id);
+                self.reply_rpc(endpoints, client_id, RpcMsg::Shares(shares))?;
+                Ok(())
+            }
+
+            RpcMsg::VerifyShare(token) => {
+                let reply = match self.shares.verify(token) {
+                    Ok(()) => RpcMsg::Success(None.into()),
+                    Err(err) => err.into(),
+                };
+                self.reply_rpc(endpoints, client_id, reply)?;
+                Ok(())
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg))
+            }
+        }
+    }
+
+    fn handle_ctl(
+        &mut self,
+        endpoints: &mut Endpoints,
+        source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        match &message {
+            CtlMsg::Hello => {
+                if !self.daemons.register(source.clone()) {
+                    info!("{} daemon is {}", source.ended(), "reconnected".ended());
+                } else if matches!(source, ServiceId::Transfer(_)) {
+                    self.accept_daemon(source)?;
+                    self.pick_task(endpoints)?;
+                } else {
+                    info!("{} daemon is {}", source.ended(), "connected".ended());
+                }
+            }
+
+            CtlMsg::ProcessingFailed | CtlMsg::ProcessingComplete => {
+                if let ServiceId::Transfer(daemon_id) = source {
+                    if let Some(container_id) = self
+                        .container_transfers
+                        .iter()
+                        .find(|(_, id)| daemon_id == **id)
+                        .map(|(a, _)| a)
+                        .copied()
+                    {
+                        self.container_transfers.remove(&container_id);
+                        let success = matches!(message, CtlMsg::ProcessingComplete);
+                        let metadata_only = self.lazy.finished(container_id, success)?.is_some();
+                        self.stats.transfer_finished(success);
+                        let source = self.container_sources.remove(&container_id);
+                        let repaired = self.entropy.repaired(container_id);
+                        let replicated = self.admin.replicated(container_id);
+                        let quarantined = match source {
+                            Some(peer) if success && self.quarantine.is_flagged(peer) => {
+                                warn!(
+                                    "Container {} is retrieved from flagged peer {} and is \
+                                     quarantined",
+                                    container_id, peer
+                                );
+                                self.quarantine.hold(container_id, peer, repaired || replicated)?;
+                                self.emit(NodeEvent::Quarantined { container_id, peer });
+                                true
+                            }
+                            _ => false,
+                        };
+                        // Completion of quarantined containers is announced on their release
+                        if !quarantined {
+                            self.emit(NodeEvent::TransferComplete {
+                                container_id,
+                                success,
+                            });
+                            self.publish(endpoints, Event::TransferComplete {
+                                container_id,
+                                success,
+                            });
+                        }
+                        for job_id in self.jobs.finish(container_id, success) {
+                            self.sessions.job_finished(job_id);
+                        }
+                        self.resumes.finished(container_id, success);
+                        if success {
+                            self.containers.update_size(&mut self.store, container_id)?;
+                        }
+                        let evicted = success
+                            && !quarantined
+                            && !metadata_only
+                            && self.keep_quotas(container_id, source)?;
+                        if self.cache_admissions.remove(&container_id)
+                            && matches!(message, CtlMsg::ProcessingComplete)
+                        {
+                            self.admit_container(container_id)?;
+                        }
+                        if let Some(app) = self
+                            .container_apps
+                            .get(&container_id)
+                            .filter(|_| !quarantined && !evicted)
+                        {
+                            // Notify client on complete process
+                            let retrieved = if metadata_only {
+                                ExtMsg::MetadataRetrieved(container_id)
+                            } else {
+                                ExtMsg::ContainerRetrieved(container_id)
+                            };
+                            self.ext_lanes.push(Some(*app), retrieved);
+                        }
+                        if success && !quarantined && !metadata_only && !evicted {
+                            self.apply_patch(container_id, source)?;
+                        }
+                        if (repaired || replicated) && success && !quarantined {
+                            if repaired {
+                                info!(
+                                    "Container {} is repaired from replication partner",
+                                    container_id
+                                );
+                            } else {
+                                info!("Container {} is replicated by admin command", container_id);
+                            }
+                            self.pins.pin([container_id])?;
+                            self.popularity.set_pinned(container_id, true);
+                        }
+                        let requesters = self.replicator.retrieved(container_id);
+                        if !requesters.is_empty() {
+                            let stored = success && !quarantined && !evicted;
+                            if stored {
+                                info!(
+                                    "Container {} is replicated for {:?}",
+                                    container_id, requesters
+                                );
+                                self.pins.pin([container_id])?;
+                                self.popularity.set_pinned(container_id, true);
+                            }
+                            for peer in requesters {
+                                self.acknowledge_replica(
+                                    endpoints,
+                                    peer,
+                                    container_id,
+                                    stored,
+                                    false,
+                                );
+                            }
+                        }
+                    }
+                    self.transferd_busy.remove(&daemon_id);
+                    self.transferd_free.push_back(daemon_id);
+                    self.pick_task(endpoints)?;
+                    if self.transferd_busy.is_empty() {
+                        if let Err(err) = self.dirty.clear() {
+                            error!("Unable to clear store dirty marker: {}", err);
+                        }
+                    }
+                }
+            }
+
+            CtlMsg::PinMany(container_ids) => {
+                let result = self.pin_many(container_ids.clone(), true, false)?;
+                info!("Batch pin requested by {}: {}", source, result);
+            }
+
+            CtlMsg::UnpinMany(container_ids) => {
+                let result = self.pin_many(container_ids.clone(), false, false)?;
+                info!("Batch unpin requested by {}: {}", source, result);
+            }
+
+            CtlMsg::EvictMany(container_ids) => {
+                let result = self.evict_many(container_ids.clone(), false)?;
+                info!("Batch eviction requested by {}: {}", source, result);
+            }
+
+            CtlMsg::Metrics(samples) => {
+                let daemon = source.to_string();
+                self.observe(|registry| registry.merge(&daemon, samples.clone()));
+            }
+
+            CtlMsg::CorruptChunk(AddressedMsg {
+                remote_id,
+                data: chunk_id,
+            }) => {
+                warn!("Peer {} has sent corrupt chunk {}", remote_id, chunk_id);
+                self.peer_monitor.corrupt(*remote_id);
+                self.observe(|registry| registry.inc("storm_corrupt_chunks_total", &[], 1));
+            }
+
+            CtlMsg::ChunksSent(AddressedMsg {
+                remote_id,
+                data: bytes,
+            }) => {
+                self.rate_limits.sent(*remote_id, *bytes);
+            }
+
+            CtlMsg::MemoryUsage(bytes) => {
+                self.memory.record(&source, *bytes);
+            }
+
+            // Liveness of the daemon is already recorded
+            CtlMsg::Pong(_) => {}
+
+            CtlMsg::ShutdownComplete => {
+                info!("{} daemon is {}", source.ended(), "stopped".ended());
+                self.shutdown.stopped(&source);
+                self.stop_if_complete();
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the CTL interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_app(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match &message {
+            ExtMsg::ProposeTopic(AddressedMsg { data, .. }) => {
+                self.topics.register_local(app, TopicId::from(data));
+            }
+            ExtMsg::Topics(AddressedMsg { data, .. }) => {
+                for topic_id in data {
+                    self.topics.register_local(app, *topic_id);
+                }
+            }
+            ExtMsg::AcceptTopic(AddressedMsg { data, .. })
+            | ExtMsg::DeclineTopic(AddressedMsg { data, .. })
+                if !self.topics.contains(app, *data) =>
+            {
+                return Err(DaemonError::UnknownTopic(*data));
+            }
+            ExtMsg::AcceptTopic(AddressedMsg { data, .. }) => {
+                self.topics.register_local(app, *data);
+            }
+            _ => {}
+        }
+
+        match message {
+            ExtMsg::UnregisterApp(app_id) => {
+                if app != app_id {
+                    error!(
+                        "Request on application {} deregistration issued by a non-application \
+                         daemon {}",
+                        app,
+                        ServiceId::StormApp(app_id)
+                    );
+                    return Err(DaemonError::wrong_esb_msg_source(
+                        ServiceBus::Storm,
+                        &message,
+                        ServiceId::StormApp(app_id),
+                    ));
+                }
+                if self.apps.unregister(app_id) {
+                    info!("Application {} is unregistered", app_id);
+                }
+            }
+
+            ExtMsg::Heartbeat(_) => {
+                // Liveness of the app is recorded for each of its messages
+            }
+
+            ExtMsg::RegisterApp(app_id) => {
+                if app == app_id {
+                    info!("Application {} is registered", app_id);
+                    self.apps.register(app_id);
+                } else {
+                    error!(
+                        "Request on application {} registration issued by a non-application \
+                         daemon {}",
+                        app,
+                        ServiceId::StormApp(app_id)
+                    );
+                    return Err(DaemonError::wrong_esb_msg_source(
+                        ServiceBus::Storm,
+                        &message,
+                        ServiceId::StormApp(app_id),
+                    ));
+                }
+            }
+
+            ExtMsg::Negotiate(features) => {
+                let features = self.negotiate(ServiceId::StormApp(app), features);
+                self.dictionaries.set_delivered(app, false);
+                self.ext_lanes.push(Some(app), ExtMsg::Negotiate(features));
+                self.offer_dictionary(app);
+            }
+
+            ExtMsg::RetrieveContainer(container) => {
+                self.container_apps.insert(container.data.container_id, app);
+                self.schedule_transfer(
+                    endpoints,
+                    app,
+                    CtlMsg::GetContainer(AddressedClientMsg {
+                        remote_id: container.remote_id,
+                        client_id: None,
+                        data: AppContainer {
+                            storm_app: app,
+                            container_id: container.data,
+                        },
+                    }),
+                )?;
+            }
+
+            ExtMsg::RetrieveMetadata(container) => {
+                let container_id = container.data.container_id;
+                self.container_apps.insert(container_id, app);
+                if self.store.retrieve_chunk(DB_TABLE_CONTAINER_HEADERS, container_id)?.is_some() {
+                    // Metadata are known from a previous retrieval
+                    self.ext_lanes.push(Some(app), ExtMsg::MetadataRetrieved(container_id));
+                    return Ok(());
+                }
+                // Metadata are small, so their retrieval is not deferred by the replication
+                // schedule
+                self.lazy.fetch(app, container.remote_id, container.data);
+                self.ctl_queue.push_back(CtlMsg::GetMetadata(AddressedClientMsg {
+                    remote_id: container.remote_id,
+                    client_id: None,
+                    data: AppContainer {
+                        storm_app: app,
+                        container_id: container.data,
+                    },
+                }));
+                self.pick_or_start(endpoints, None)?;
+            }
+
+            ExtMsg::SendContainer(container) => {
+                self.schedule_transfer(
+                    endpoints,
+                    app,
+                    CtlMsg::SendContainer(AddressedClientMsg {
+                        remote_id: container.remote_id,
+                        client_id: None,
+                        data: AppContainer {
+                            storm_app: app,
+                            container_id: container.data,
+                        },
+                    }),
+                )?;
+            }
+
+            ExtMsg::SyncTopics(remote_id) => {
+                // Sync completes once the peer answers with its topics or with the difference
+                // to the sketch of our topics
+                self.topics.start_sync(app, remote_id);
+                match self.config.ext.topic_sketch {
+                    Some(capacity) => {
+                        let topics = self.topics.locals().get(&app).into_iter().flatten();
+                        let request = SketchRequest {
+                            app,
+                            sketch: TopicSketch::with(capacity, topics.copied()),
+                        };
+                        self.send_node_msg(endpoints, remote_id, TOPIC_SKETCH_MSG_TYPE, &request)?;
+                    }
+                    None => self.send_msg(
+                        endpoints,
+                        remote_id,
+                        Messages::ListTopics(AppMsg { app, data: () }),
+                    ),
+                }
+            }
+
+            ExtMsg::PostMany(post) => {
+                let report = self.post_many(endpoints, app, post);
+                info!("Multi-recipient post delivery: {}", report);
+                self.ext_lanes.push(Some(app), ExtMsg::DeliveryReport(report));
+            }
+
+            ExtMsg::AcceptMany(accepts) => {
+                let result = self.accept_many(endpoints, app, accepts);
+                info!("Batch accept: {}", result);
+                self.ext_lanes.push(Some(app), ExtMsg::BatchResult(result));
+            }
+
+            ExtMsg::Opaque(AddressedMsg { remote_id, data }) => {
+                if data.is_oversized() {
+                    return Err(DaemonError::OpaqueOversized(data.payload.len()));
+                }
+                let len = data.payload.len();
+                let envelope = OpaqueEnvelope { app, msg: data };
+                match self.send_opaque(endpoints, remote_id, envelope) {
+                    Ok(()) => self.stats.sent(len),
+                    Err(err) => {
+                        warn!("Unable to send app-opaque message to {}: {}", remote_id, err);
+                        self.link_failed();
+                    }
+                }
+            }
+
+            ExtMsg::DeclineWithReason(AddressedMsg { remote_id, data }) => {
+                self.decline(endpoints, app, remote_id, data)?;
+            }
+
+            // These are sent by the node to the apps and never the other way around
+            wrong_msg @ (ExtMsg::ContainerRetrieved(_)
+            | ExtMsg::MetadataRetrieved(_)
+            | ExtMsg::DeliveryReport(_)
+            | ExtMsg::BatchResult(_)
+            | ExtMsg::Degraded(_)
+            | ExtMsg::Event(_)
+            | ExtMsg::EventDigest(_)
+            | ExtMsg::Dictionary(_)
+            | ExtMsg::TopicsSynced(_)) => {
+                error!("Application {} sent a message which only the node may send", app);
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+
+            // We need to the rest of the messages to the Bifrost network
+            forward => {
+                let remote_id = forward.remote_id();
+                let message = forward.p2p_message(app);
+                if !self.interests.matches(remote_id, &message) {
+                    debug!("Skipping {} to {} which is not interested in it", message, remote_id);
+                    return Ok(());
+                }
+                self.send_msg(endpoints, remote_id, message);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_others(
+        &mut self,
+        endpoints: &mut Endpoints,
+        source: ServiceId,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::ContainerAnnouncement(container) => {
+                info!("Receive a container announcement from {}", source);
+                self.ctl_queue.push_back(CtlMsg::AnnounceContainer(AddressedClientMsg {
+                    remote_id: container.remote_id,
+                    client_id: None,
+                    data: AppContainer {
+                        storm_app: StormApp::FileTransfer,
+                        container_id: container.data.id,
+                    },
+                }));
+                self.pick_or_start(endpoints, None)?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+}
 
 impl Runtime {
     fn accept_daemon(&mut self, source: ServiceId) -> Result<(), esb::Error<ServiceId>> {
         info!("{} daemon is {}", source.ended(), "connected".ended());
 
-        match source {
-            service_id if service_id == ServiceId::stormd() => {
-                error!("{}", "Unexpected another Stormd instance connection".err());
+        match source {
+            service_id if service_id == ServiceId::stormd() => {
+                error!("{}", "Unexpected another Stormd instance connection".err());
+            }
+            ServiceId::Transfer(daemon_id) => {
+                self.transferd_free.push_back(daemon_id);
+                info!(
+                    "Transfer service {} is registered; total {} container processors are known",
+                    daemon_id,
+                    self.transferd_free.len() + self.transferd_busy.len()
+                );
+            }
+            _ => {
+                // Ignoring the rest of daemon/client types
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pick_task(&mut self, endpoints: &mut Endpoints) -> Result<bool, esb::Error<ServiceId>> {
+        if self.ctl_queue.is_empty() {
+            return Ok(true);
+        }
+        if self.memory.is_exhausted() {
+            debug!("Memory budget is exhausted; {} tasks wait in the queue", self.ctl_queue.len());
+            return Ok(true);
+        }
+
+        let (service, daemon_id) = match self.transferd_free.front() {
+            Some(damon_id) => (ServiceId::Transfer(*damon_id), *damon_id),
+            None => return Ok(false),
+        };
+
+        let msg = match self.ctl_queue.pop_front() {
+            None => return Ok(true),
+            Some(req) => req,
+        };
+
+        debug!("Assigning task {} to {}", msg, service);
+
+        if let CtlMsg::GetContainer(request) = msg {
+            self.resumes.started(request);
+        }
+
+        let container_id = match msg {
+            CtlMsg::GetContainer(AddressedClientMsg {
+                remote_id,
+                data:
+                    AppContainer {
+                        storm_app,
+                        container_id,
+                    },
+                ..
+            }) => {
+                // Transfer daemon stripes chunk requests across all paths to the peer
+                self.send_ctl(
+                    endpoints,
+                    service.clone(),
+                    CtlMsg::PeerPaths(PeerPaths {
+                        remote_id,
+                        services: self.peer_routes.paths(remote_id),
+                    }),
+                )?;
+                self.jobs.start(container_id.container_id);
+                self.container_sources.insert(container_id.container_id, remote_id);
+                self.containers
+                    .register(container_id.container_id, storm_app, remote_id)
+                    .map_err(DaemonError::from)?;
+                Some(container_id.container_id)
+            }
+            CtlMsg::GetMetadata(AddressedClientMsg {
+                remote_id,
+                data:
+                    AppContainer {
+                        storm_app,
+                        container_id,
+                    },
+                ..
+            }) => {
+                self.container_sources.insert(container_id.container_id, remote_id);
+                self.containers
+                    .register(container_id.container_id, storm_app, remote_id)
+                    .map_err(DaemonError::from)?;
+                Some(container_id.container_id)
+            }
+            CtlMsg::SendContainer(AddressedClientMsg {
+                remote_id,
+                data:
+                    AppContainer {
+                        storm_app,
+                        container_id,
+                    },
+                ..
+            }) => {
+                self.containers
+                    .register(container_id.container_id, storm_app, remote_id)
+                    .map_err(DaemonError::from)?;
+                Some(container_id.container_id)
+            }
+            _ => None,
+        };
+        self.send_ctl(endpoints, service, msg)?;
+
+        if let Some(container_id) = container_id {
+            self.container_transfers.insert(container_id, daemon_id);
+        }
+        self.transferd_free.pop_front();
+        self.transferd_busy.insert(daemon_id);
+        if let Err(err) = self.dirty.set() {
+            error!("Unable to set store dirty marker: {}", err);
+        }
+
+        Ok(true)
+    }
+
+    fn track_request(
+        &mut self,
+        endpoints: &mut Endpoints,
+        storm_app: StormApp,
+        id: ContainerFullId,
+        kind: RequestKind,
+    ) -> Result<(), DaemonError> {
+        let container_id = id.container_id;
+        let policy = self.config.ext.cache;
+        self.popularity.record(container_id, kind);
+
+        if self.popularity.auto_pin(container_id, policy) {
+            info!("Popular container {} is pinned", container_id);
+            self.pins.pin([container_id])?;
+        }
+
+        if !self.popularity.should_admit(container_id, policy)
+            || self.cache_admissions.contains(&container_id)
+            || self.container_transfers.contains_key(&container_id)
+        {
+            return Ok(());
+        }
+        if self.containers.contains(container_id) {
+            // We already have the container data locally; unless it belongs to the apps, it is
+            // kept on behalf of the peers and is admitted into the cache without retrieval
+            if !self.containers.is_referenced(container_id) {
+                self.admit_container(container_id)?;
+            }
+            return Ok(());
+        }
+        let remote_id = match self.peer_monitor.best(self.popularity.sources(container_id)) {
+            Some(remote_id) => remote_id,
+            None => return Ok(()),
+        };
+
+        debug!("Admitting container {} into the cache; requesting it from {}", id, remote_id);
+        self.cache_admissions.insert(container_id);
+        self.ctl_queue.push_back(CtlMsg::GetContainer(AddressedClientMsg {
+            remote_id,
+            client_id: None,
+            data: AppContainer {
+                storm_app,
+                container_id: id,
+            },
+        }));
+        self.pick_or_start(endpoints, None)
+    }
+
+    fn post_many(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        post: MultiPost,
+    ) -> DeliveryReport {
+        let mesg_id = post.mesg.mesg_id();
+        let mut statuses = BTreeMap::new();
+        for remote_id in post.recipients {
+            let mut attempt = 0u8;
+            let status = loop {
+                attempt += 1;
+                let msg = Messages::Post(AppMsg {
+                    app,
+                    data: post.mesg.clone(),
+                });
+                match self.deliver_p2p(endpoints, remote_id, msg) {
+                    Ok(_) => break DeliveryStatus::Sent(attempt),
+                    Err(err) if attempt >= POST_FANOUT_ATTEMPTS => {
+                        warn!("Unable to deliver message {} to {}: {}", mesg_id, remote_id, err);
+                        self.link_failed();
+                        break DeliveryStatus::Failed(attempt);
+                    }
+                    Err(err) => {
+                        debug!("Retrying delivery of message {} to {}: {}", mesg_id, remote_id, err)
+                    }
+                }
+            };
+            statuses.insert(remote_id, status);
+        }
+        DeliveryReport { mesg_id, statuses }
+    }
+
+    /// Sends message to a remote peer, queueing it if the connection to the LNP node is lost.
+    /// Checks whether the node is ready to serve requests: the LNP node is connected, the store
+    /// service responds, the data directory is writable and the configured app daemons are up.
+    fn health(&mut self) -> Health {
+        fn check(name: &str, res: Result<(), String>) -> HealthCheck {
+            HealthCheck {
+                name: name.to_owned(),
+                passed: res.is_ok(),
+                details: res.err(),
+            }
+        }
+
+        let mut checks = vec![
+            check(
+                "lnp",
+                if self.msg_link.is_degraded() {
+                    Err(format!("connection is lost, {} messages queued", self.msg_link.queued()))
+                } else {
+                    Ok(())
+                },
+            ),
+            check(
+                "store",
+                self.store.count(DB_TABLE_CONTAINERS).map(|_| ()).map_err(|err| err.to_string()),
+            ),
+            check("data_dir", self.stats.save().map_err(|err| err.to_string())),
+        ];
+        if self.maintenance.is_active() {
+            let draining = self.container_transfers.len();
+            checks.push(check(
+                "maintenance",
+                Err(format!("node is in maintenance, {} transfers are draining", draining)),
+            ));
+        }
+        for (run, daemon) in [
+            (self.config.ext.run_chat, ServiceId::chatd()),
+            (self.config.ext.run_mail, ServiceId::maild()),
+            (self.config.ext.run_board, ServiceId::boardd()),
+            (self.config.ext.run_downpour, ServiceId::downpourd()),
+        ] {
+            if run {
+                let res = if self.daemons.contains(&daemon) {
+                    Ok(())
+                } else {
+                    Err(s!("daemon has not connected"))
+                };
+                checks.push(check(&daemon.to_string(), res));
+            }
+        }
+        Health { checks }
+    }
+
+    fn record_metrics(&mut self) {
+        let recorder = match &mut self.metrics {
+            Some(recorder) if recorder.is_due() => recorder,
+            _ => return,
+        };
+        let snapshot = MetricsSnapshot {
+            counters: self.stats.stats().lifetime,
+            p2p_queue: self.msg_link.queued() as u32,
+            degraded: self.msg_link.is_degraded(),
+            transfers: self.container_transfers.len() as u32,
+        };
+        if let Err(err) = recorder.record(snapshot) {
+            warn!("Unable to record node metrics: {}", err);
+        }
+    }
+
+    fn observe(&self, f: impl FnOnce(&mut MetricsRegistry)) {
+        if let Some(registry) = &self.registry {
+            f(&mut registry.lock().expect("metrics registry lock is poisoned"));
+        }
+    }
+
+    /// Updates the exported gauges and the counters kept in the node statistics.
+    fn update_metrics(&mut self) {
+        if self.registry.is_none() {
+            return;
+        }
+        let counters = self.stats.stats().lifetime;
+        let mut daemons = self
+            .daemons
+            .list()
+            .into_iter()
+            .map(|info| (info.service_id.to_string(), Some(info.last_seen)))
+            .collect::<Vec<_>>();
+        for (run, daemon) in [
+            (self.config.ext.run_chat, ServiceId::chatd()),
+            (self.config.ext.run_mail, ServiceId::maild()),
+            (self.config.ext.run_board, ServiceId::boardd()),
+            (self.config.ext.run_downpour, ServiceId::downpourd()),
+        ] {
+            if run && !self.daemons.contains(&daemon) {
+                daemons.push((daemon.to_string(), None));
+            }
+        }
+        let p2p_queue = self.msg_link.queued() as u64;
+        let lnp_up = !self.msg_link.is_degraded() as u64;
+        let transfers = self.container_transfers.len() as u64;
+        let containers = self.containers.snapshot().len() as u64;
+        let apps = self.apps.registered().count() as u64;
+        let memory_accounted = self.memory.accounted();
+        let memory_pressure = self.memory.pressure() as u64;
+        self.observe(|registry| {
+            registry.count("storm_p2p_bytes_received_total", &[], counters.bytes_received);
+            registry.count("storm_p2p_bytes_sent_total", &[], counters.bytes_sent);
+            registry.count("storm_transfers_completed_total", &[], counters.transfers_completed);
+            registry.count("storm_transfers_failed_total", &[], counters.transfers_failed);
+            registry.set("storm_p2p_queue", &[], p2p_queue);
+            registry.set("storm_lnp_up", &[], lnp_up);
+            registry.set("storm_transfers_active", &[], transfers);
+            registry.set("storm_containers", &[], containers);
+            registry.set("storm_apps_registered", &[], apps);
+            registry.set("storm_memory_accounted_bytes", &[], memory_accounted);
+            registry.set("storm_memory_pressure", &[], memory_pressure);
+            for (daemon, last_seen) in daemons {
+                let labels = [("daemon", daemon.as_str())];
+                registry.set("storm_daemon_up", &labels, last_seen.is_some() as u64);
+                if let Some(last_seen) = last_seen {
+                    registry.set("storm_daemon_last_seen_seconds", &labels, last_seen);
+                }
+            }
+        });
+    }
+
+    /// Sends p2p message, accounting it in the node statistics.
+    fn deliver_p2p(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        message: Messages,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        // Replies and probes do not make the peer known to this node
+        let message = if matches!(
+            message,
+            Messages::ListApps | Messages::ActiveApps(_) | Messages::Decline(_)
+        ) {
+            message
+        } else {
+            match self.stamps.contacted(remote_id, message) {
+                Some(message) => message,
+                // The message is sent after the stamp by `present_stamps`
+                None => return Ok(()),
+            }
+        };
+        let len = message.serialize().len();
+        let ty = self.registry.is_some().then(|| message_type(&message));
+        self.send_p2p(endpoints, remote_id, message)?;
+        self.stats.sent(len);
+        if let Some(ty) = ty {
+            self.observe(|registry| {
+                registry.inc("storm_p2p_messages_sent_total", &[("type", &ty)], 1);
+            });
+        }
+        Ok(())
+    }
+
+    /// Runs the policy program on the post or topic proposal received from a remote peer,
+    /// answering the peer on behalf of the app. Returns whether the message has to be delivered
+    /// to the app.
+    fn judge(&mut self, endpoints: &mut Endpoints, app: StormApp, storm_msg: &ExtMsg) -> bool {
+        let policy = match self.policy {
+            Some(ref policy) => policy,
+            None => return true,
+        };
+        let (remote_id, subject, id) = match storm_msg {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                (*remote_id, PolicySubject::Post(data), data.mesg_id())
+            }
+            ExtMsg::ProposeTopic(AddressedMsg { remote_id, data }) => {
+                (*remote_id, PolicySubject::Topic(data), TopicId::from(data).to_wire())
+            }
+            _ => return true,
+        };
+        let verdict = policy.judge(app, remote_id, subject);
+        debug!("Policy verdict on {} from {} is {}", id, remote_id, verdict);
+        match verdict {
+            Verdict::Accept => {
+                self.send_msg(endpoints, remote_id, Messages::Accept(AppMsg { app, data: id }));
+                true
+            }
+            Verdict::Decline => {
+                let declined = Declined {
+                    id,
+                    reason: DeclineReason::Policy,
+                };
+                if let Err(err) = self.decline(endpoints, app, remote_id, declined) {
+                    warn!("Unable to decline {} from {}: {}", id, remote_id, err);
+                }
+                false
+            }
+            Verdict::Pass => true,
+        }
+    }
+
+    /// Declines message or topic proposal of the remote peer, telling it the reason, and bans the
+    /// peer if it has spammed the apps too many times.
+    fn decline(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        remote_id: NodeId,
+        declined: Declined,
+    ) -> Result<(), DaemonError> {
+        let Declined { id, reason } = declined;
+        let notice = DeclineNotice { app, id, reason };
+        if let Err(err) = self.send_node_msg(endpoints, remote_id, DECLINE_REASON_MSG_TYPE, &notice)
+        {
+            warn!("Unable to send decline reason to {}: {}", remote_id, err);
+        }
+        self.send_msg(endpoints, remote_id, Messages::Decline(AppMsg { app, data: id }));
+        if self.moderation.declined(remote_id, reason)? {
+            warn!("Peer {} is banned after repeated spam", remote_id);
+        }
+        Ok(())
+    }
+
+    fn send_msg(&mut self, endpoints: &mut Endpoints, remote_id: NodeId, message: Messages) {
+        if self.msg_link.is_degraded() {
+            self.msg_link.enqueue(remote_id, message);
+            return;
+        }
+        if let Err(err) = self.deliver_p2p(endpoints, remote_id, message.clone()) {
+            warn!("Unable to send message to {}: {}", remote_id, err);
+            self.msg_link.enqueue(remote_id, message);
+            self.link_failed();
+        }
+    }
+
+    fn link_failed(&mut self) {
+        if self.msg_link.fail() {
+            warn!("{}", "Connection to LNP node is lost, entering degraded state".err());
+            self.notify_degraded(true);
+        }
+    }
+
+    /// Presents the stamps minted since the last call to the remote peers, sending the messages
+    /// held until then.
+    fn present_stamps(&mut self, endpoints: &mut Endpoints) {
+        while let Some((remote_id, stamp, held)) = self.stamps.take_minted() {
+            let envelope = OpaqueEnvelope {
+                app: StormApp::System,
+                msg: OpaqueMsg {
+                    ty: POW_STAMP_MSG_TYPE,
+                    payload: stamp.strict_serialize().expect("in-memory stamp encoding"),
+                },
+            };
+            if let Err(err) = self.send_opaque(endpoints, remote_id, envelope) {
+                warn!("Unable to present stamp to {}: {}", remote_id, err);
+            }
+            for message in held {
+                self.send_msg(endpoints, remote_id, message);
+            }
+        }
+    }
+
+    /// Tries to deliver messages queued while the connection to the LNP node was lost. Unless
+    /// `force` is set, the attempt is made only after the backoff delay.
+    fn flush_msg(&mut self, endpoints: &mut Endpoints, force: bool) {
+        while let Some((remote_id, message)) = self.msg_link.take_due(force) {
+            if let Err(err) = self.deliver_p2p(endpoints, remote_id, message.clone()) {
+                debug!("LNP node is still unavailable: {}", err);
+                self.msg_link.requeue(remote_id, message);
+                self.msg_link.fail();
+                return;
+            }
+        }
+        if self.msg_link.restore() {
+            if self.msg_link.dropped() > 0 {
+                warn!(
+                    "{} messages to remote peers were dropped while LNP node was unavailable",
+                    self.msg_link.dropped()
+                );
+            }
+            info!("{}", "Connection to LNP node is restored".ended());
+            self.notify_degraded(false);
+        }
+    }
+
+    /// Broadcasts event to the registered extensions, unless the event class is digested.
+    fn emit(&mut self, event: NodeEvent) {
+        debug!("Node event {}", event);
+        if let Some(event) = self.events.push(event) {
+            for app in self.apps.registered() {
+                self.ext_lanes.push(Some(app), ExtMsg::Event(event.clone()));
+            }
+        }
+    }
+
+    /// Publishes event to the subscribed clients. Clients with filtered subscriptions are sent
+    /// the matching events directly; subscriptions of the clients which can't be reached are
+    /// dropped.
+    fn publish(&mut self, endpoints: &mut Endpoints, event: Event) {
+        for (client_id, polled) in self.polls.publish(&event) {
+            if let Err(err) = self.send_rpc(endpoints, client_id, RpcMsg::PolledEvents(polled)) {
+                warn!("Unable to send node event to client {}: {}", client_id, err);
+            }
+        }
+        let recipients = self
+            .subscriptions
+            .iter()
+            .filter(|(_, filter)| filter.matches(&event))
+            .map(|(client_id, _)| *client_id)
+            .collect::<Vec<_>>();
+        for client_id in recipients {
+            if let Err(err) = self.send_event(endpoints, client_id, event.clone()) {
+                warn!("Unable to send node event to client {}: {}", client_id, err);
+                self.subscriptions.remove(&client_id);
+            }
+        }
+        if self.config.ext.notify_endpoint.is_none() || !self.subscribed.contains(&event.kind()) {
+            return;
+        }
+        if let Err(err) = self.send_notify(endpoints, event) {
+            warn!("Unable to publish node event: {}", err);
+        }
+    }
+
+    /// Registers activity of the remote peer, resuming interrupted retrievals from it and sending
+    /// it the node attestation.
+    fn peer_active(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+    ) -> Result<(), DaemonError> {
+        self.peer_monitor.seen(remote_id);
+        self.resume_transfers(endpoints, remote_id)?;
+        if self.config.ext.attest && self.attestations.is_due(remote_id) {
+            let attestation = self.manifest_key.attest(self.apps.to_set());
+            if let Err(err) =
+                self.send_node_msg(endpoints, remote_id, ATTESTATION_MSG_TYPE, &attestation)
+            {
+                warn!("Unable to send attestation to {}: {}", remote_id, err);
+            }
+        }
+        if let Some(features) = self.features.due(remote_id) {
+            if let Err(err) = self.send_node_msg(endpoints, remote_id, FEATURES_MSG_TYPE, &features)
+            {
+                warn!("Unable to send node features to {}: {}", remote_id, err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Tells the remote peer which request is rejected in maintenance when to retry it.
+    fn notify_maintenance(&mut self, endpoints: &mut Endpoints, remote_id: NodeId) {
+        if let Some(notice) = self.maintenance.notice() {
+            if let Err(err) =
+                self.send_node_msg(endpoints, remote_id, MAINTENANCE_MSG_TYPE, &notice)
+            {
+                warn!("Unable to send maintenance notice to {}: {}", remote_id, err);
+            }
+        }
+    }
+
+    /// Serves request of the remote peer to keep replica of its container, retrieving the
+    /// container from the peer unless it is already stored.
+    fn keep_replica(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        request: ReplicaRequest,
+    ) -> Result<(), DaemonError> {
+        let ReplicaRequest { app, container_id } = request;
+        let accepted = self.config.ext.replicas.contains(&remote_id)
+            || (self.config.ext.storage_peer && !self.moderation.is_banned(remote_id));
+        if !accepted || self.maintenance.is_active() {
+            debug!("Declining request of {} to keep replica of {}", remote_id, container_id);
+            self.acknowledge_replica(endpoints, remote_id, container_id, false, true);
+            return Ok(());
+        }
+        if self.containers.contains(container_id) {
+            self.pins.pin([container_id])?;
+            self.popularity.set_pinned(container_id, true);
+            self.acknowledge_replica(endpoints, remote_id, container_id, true, false);
+            return Ok(());
+        }
+        info!("Retrieving replica of {} from {}", container_id, remote_id);
+        self.replicator.requested(container_id, remote_id);
+        if !self.container_transfers.contains_key(&container_id) {
+            self.queue_retrieval(remote_id, app, container_id);
+            self.pick_or_start(endpoints, None)?;
+        }
+        Ok(())
+    }
+
+    fn acknowledge_replica(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        container_id: ContainerId,
+        stored: bool,
+        declined: bool,
+    ) {
+        let ack = ReplicaAck {
+            container_id,
+            stored,
+            declined,
+        };
+        if let Err(err) = self.send_node_msg(endpoints, remote_id, REPLICA_ACK_MSG_TYPE, &ack) {
+            warn!("Unable to acknowledge replica of {} to {}: {}", container_id, remote_id, err);
+        }
+    }
+
+    /// Drops the state kept for the client which session is closed.
+    fn forget_client(&mut self, client_id: ClientId) {
+        self.subscriptions.remove(&client_id);
+        self.polls.forget(client_id);
+        self.admin.forget(client_id);
+        self.bus_features.remove(&ServiceId::Client(client_id));
+    }
+
+    /// Fails admin commands which the managed nodes have not answered in time.
+    fn expire_admin(&mut self, endpoints: &mut Endpoints) {
+        for (client_id, remote_id) in self.admin.expire() {
+            warn!("Node {} has not answered the admin command", remote_id);
+            if let Err(err) =
+                self.send_rpc(endpoints, client_id, DaemonError::AdminTimeout(remote_id))
+            {
+                warn!("Unable to reply to client {}: {}", client_id, err);
+            }
+        }
+    }
+
+    /// Replies to the long-poll requests which have timed out.
+    fn expire_polls(&mut self, endpoints: &mut Endpoints) {
+        for (client_id, polled) in self.polls.expire() {
+            if let Err(err) = self.send_rpc(endpoints, client_id, RpcMsg::PolledEvents(polled)) {
+                warn!("Unable to reply to client {}: {}", client_id, err);
+            }
+        }
+    }
+
+    /// Unregisters apps which have stopped responding and sends heartbeats to the registered
+    /// ones.
+    /// Launches long-running child daemon under supervision.
+    fn start_daemon(&mut self, daemon: Daemon) -> Result<(), DaemonError> {
+        let handle = self.launch_daemon(daemon.clone(), self.config.clone())?;
+        self.supervisor.launched(daemon, handle);
+        Ok(())
+    }
+
+    /// Sends heartbeats to the supervised daemons and restarts the crashed ones.
+    fn supervise(&mut self, endpoints: &mut Endpoints) {
+        // Daemons stopping on the node shutdown are not restarted
+        if self.shutdown.is_requested() {
+            return;
+        }
+        if let Some((nonce, service_ids)) = self.supervisor.heartbeat_due() {
+            for service_id in service_ids {
+                if let Err(err) = self.send_ctl(endpoints, service_id.clone(), CtlMsg::Ping(nonce))
+                {
+                    warn!("Unable to send heartbeat to {} daemon: {}", service_id, err);
+                    self.daemon_crashed(&service_id, "heartbeats are not delivered");
+                }
+            }
+        }
+        for (service_id, reason) in self.supervisor.failed() {
+            self.daemon_crashed(&service_id, reason);
+        }
+        for daemon in self.supervisor.restarts_due() {
+            info!("Restarting {} daemon...", daemon);
+            let handle = match self.launch_daemon(daemon.clone(), self.config.clone()) {
+                Ok(handle) => handle,
+                Err(err) => {
+                    if let Some(delay) = self.supervisor.launch_failed(&daemon) {
+                        error!(
+                            "Unable to restart {} daemon: {}; retrying in {} s",
+                            daemon,
+                            err,
+                            delay.as_secs()
+                        );
+                    }
+                    continue;
+                }
+            };
+            self.supervisor.launched(daemon, handle);
+        }
+    }
+
+    /// Registers crash of the supervised daemon, scheduling its restart.
+    fn daemon_crashed(&mut self, service_id: &ServiceId, reason: &str) {
+        if self.shutdown.is_requested() {
+            return;
+        }
+        if let Some(delay) = self.supervisor.crashed(service_id) {
+            error!(
+                "{} daemon has crashed: {}; restarting in {} s",
+                service_id,
+                reason,
+                delay.as_secs()
+            );
+            self.daemons.remove(service_id);
+            self.bus_features.remove(service_id);
+        }
+    }
+
+    fn check_apps(&mut self) {
+        for app in self.apps.expire() {
+            warn!("Application {} is not responding and is unregistered", app);
+        }
+        if let Some(nonce) = self.apps.heartbeat_due() {
+            for app in self.apps.registered() {
+                self.ext_lanes.push(Some(app), ExtMsg::Heartbeat(nonce));
+            }
+        }
+    }
+
+    /// Declines post or topic proposal received from a remote peer for an app which can't accept
+    /// it. Other messages for the app are dropped.
+    fn decline_ext(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        storm_msg: &ExtMsg,
+        reason: &str,
+    ) {
+        let (remote_id, id) = match storm_msg {
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => (*remote_id, data.mesg_id()),
+            ExtMsg::ProposeTopic(AddressedMsg { remote_id, data }) => {
+                (*remote_id, TopicId::from(data).to_wire())
+            }
+            _ => {
+                debug!("Dropping {} for application {}: {}", storm_msg, app, reason);
+                return;
+            }
+        };
+        debug!("Declining {} from {} for application {}: {}", id, remote_id, app, reason);
+        self.send_msg(endpoints, remote_id, Messages::Decline(AppMsg { app, data: id }));
+    }
+
+    /// Sends digests of the pins and topics to the replication partners, if the reconciliation is
+    /// due.
+    fn reconcile_replicas(&mut self, endpoints: &mut Endpoints) {
+        let partners = self.entropy.due();
+        if partners.is_empty() {
+            return;
+        }
+        let digest = self.replica_set().digest();
+        for remote_id in partners {
+            trace!("Reconciling pins and topics with {}", remote_id);
+            if let Err(err) =
+                self.send_node_msg(endpoints, remote_id, ENTROPY_DIGEST_MSG_TYPE, &digest)
+            {
+                warn!("Unable to reconcile with replication partner {}: {}", remote_id, err);
+            }
+        }
+    }
+
+    fn node_info(&self) -> NodeInfo {
+        NodeInfo {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            uptime: unix_time().saturating_sub(self.started),
+            apps: self.apps.to_set(),
+            transfer_daemons: (self.transferd_free.len() + self.transferd_busy.len()) as u16,
+            daemons: self.daemons.list().into_iter().map(|daemon| daemon.service_id).collect(),
+            peers: self.peer_routes.len() as u32,
+            containers: self.containers.entries().count() as u32,
+            storage: self.containers.entries().map(|entry| entry.size).sum(),
+            degraded: self.msg_link.is_degraded(),
+            p2p_queue: self.msg_link.queued() as u32,
+            repair: self.repaired,
+            features: self.features.local(),
+            maintenance: self.maintenance.is_active(),
+        }
+    }
+
+    /// Carries out admin command received from another node of the operator.
+    fn administer(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        command: AdminCommand,
+    ) -> Result<AdminOutcome, DaemonError> {
+        if !self.admin.is_admin(remote_id) {
+            warn!("Refusing admin command {} from {}, which is not an admin", command, remote_id);
+            return Ok(AdminOutcome::Refused(s!("the sender is not an admin of the node")));
+        }
+        info!("Admin {} issues {}", remote_id, command);
+        let outcome = match command {
+            AdminCommand::Status => AdminOutcome::Status(self.node_info()),
+            AdminCommand::Pin(_) | AdminCommand::Replicate(_) if self.config.ext.read_only => {
+                AdminOutcome::Refused(DaemonError::ReadOnly.to_string())
+            }
+            AdminCommand::Pin(container_ids) => {
+                AdminOutcome::Pinned(self.pin_many(container_ids, true, false)?)
+            }
+            AdminCommand::Replicate(Replication { container_id, .. })
+                if self.containers.contains(container_id) =>
+            {
+                AdminOutcome::Pinned(self.pin_many(vec![container_id], true, false)?)
+            }
+            AdminCommand::Replicate(Replication {
+                app,
+                container_id,
+                source,
+            }) => {
+                self.admin.replicate(container_id);
+                if !self.container_transfers.contains_key(&container_id) {
+                    self.queue_retrieval(source, app, container_id);
+                    self.pick_or_start(endpoints, None)?;
+                }
+                AdminOutcome::Replicating
+            }
+        };
+        Ok(outcome)
+    }
+
+    /// Queues retrieval of the container which is not attached to a message.
+    fn queue_retrieval(&mut self, remote_id: NodeId, app: StormApp, container_id: ContainerId) {
+        self.ctl_queue.push_back(CtlMsg::GetContainer(AddressedClientMsg {
+            remote_id,
+            client_id: None,
+            data: AppContainer {
+                storm_app: app,
+                container_id: ContainerFullId {
+                    message_id: MesgId::from_inner(sha256t::Hash::all_zeros()),
+                    container_id,
+                },
+            },
+        }));
+    }
+
+    fn replica_set(&self) -> ReplicaSet {
+        let entries = self.containers.snapshot();
+        let pins = self
+            .pins
+            .iter()
+            .map(|container_id| {
+                let app = entries.get(&container_id).and_then(|entry| entry.app);
+                (container_id, app.unwrap_or(StormApp::System))
+            })
+            .collect();
+        ReplicaSet {
+            pins,
+            topics: self.topics.locals().clone(),
+            reply: false,
+        }
+    }
+
+    /// Retrieves pinned containers and topics which the replication partner has and this node
+    /// lacks, answering the partner with the set of this node if the partner lacks some of its
+    /// pins or topics.
+    fn repair_replica(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        remote: ReplicaSet,
+    ) -> Result<(), DaemonError> {
+        let mut local = self.replica_set();
+        let missing = local.missing(&remote);
+        if !missing.is_empty() {
+            info!(
+                "Repairing {} pins and {} topics from replication partner {}",
+                missing.pins.len(),
+                missing.topics.values().map(BTreeSet::len).sum::<usize>(),
+                remote_id
+            );
+        }
+        for (container_id, app) in missing.pins {
+            if self.containers.contains(container_id) {
+                self.pins.pin([container_id])?;
+                self.popularity.set_pinned(container_id, true);
+                continue;
+            }
+            if self.container_transfers.contains_key(&container_id) {
+                continue;
+            }
+            self.entropy.repair(container_id);
+            self.queue_retrieval(remote_id, app, container_id);
+        }
+        for (app, topics) in missing.topics {
+            for topic_id in topics {
+                let msg = Messages::Read(AppMsg {
+                    app,
+                    data: topic_id.to_wire(),
+                });
+                self.send_msg(endpoints, remote_id, msg);
+            }
+        }
+        if !remote.reply && !remote.missing(&local).is_empty() {
+            local.reply = true;
+            self.send_node_msg(endpoints, remote_id, ENTROPY_SET_MSG_TYPE, &local)?;
+        }
+        self.pick_or_start(endpoints, None)
+    }
+
+    /// Sends node-level app-opaque message to the remote peer.
+    fn send_node_msg(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        ty: u16,
+        msg: &impl StrictEncode,
+    ) -> Result<(), DaemonError> {
+        let envelope = OpaqueEnvelope {
+            app: StormApp::System,
+            msg: OpaqueMsg {
+                ty,
+                payload: msg.strict_serialize()?,
+            },
+        };
+        self.send_opaque(endpoints, remote_id, envelope)?;
+        Ok(())
+    }
+
+    /// Resumes container retrievals from the remote peer which were interrupted.
+    fn resume_transfers(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+    ) -> Result<(), DaemonError> {
+        for request in self.resumes.due(remote_id) {
+            if let CtlMsg::GetContainer(AddressedClientMsg { data, .. }) = request {
+                info!("Resuming retrieval of {} from {}", data.container_id, remote_id);
+                self.schedule_transfer(endpoints, data.storm_app, request)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes progress of the container retrieval from the container chunks kept in the store.
+    /// Lists active and interrupted container transfers, ordered by the container id.
+    fn transfers(&self) -> Vec<TransferEntry> {
+        let index = self.containers.snapshot();
+        let entry = |container_id: ContainerId, interrupted: bool| {
+            let known = index.get(&container_id);
+            TransferEntry {
+                container_id,
+                app: known.and_then(|entry| entry.app),
+                peer: known.and_then(|entry| entry.peer),
+                size: known.map(|entry| entry.size).unwrap_or_default(),
+                timestamp: known.map(|entry| entry.timestamp).unwrap_or_default(),
+                interrupted,
+            }
+        };
+        let mut transfers = self
+            .container_transfers
+            .keys()
+            .map(|container_id| (*container_id, entry(*container_id, false)))
+            .collect::<BTreeMap<_, _>>();
+        for (container_id, app, peer) in self.resumes.interrupted() {
+            let transfer =
+                transfers.entry(container_id).or_insert_with(|| entry(container_id, true));
+            transfer.app = transfer.app.or(Some(app));
+            transfer.peer = transfer.peer.or(Some(peer));
+        }
+        transfers.into_values().collect()
+    }
+
+    fn transfer_progress(
+        &mut self,
+        container_id: ContainerId,
+    ) -> Result<TransferProgress, DaemonError> {
+        let active = self.container_transfers.contains_key(&container_id);
+        let interrupted = self.resumes.is_interrupted(container_id);
+        let manifest = match containers::retrieve_manifest(&mut self.store, container_id)? {
+            Some(manifest) => manifest,
+            None if active || interrupted => {
+                return Ok(TransferProgress {
+                    container_id,
+                    chunks: 0,
+                    stored: 0,
+                    active,
+                    interrupted,
+                })
+            }
+            None => return Err(DaemonError::UnknownContainer(container_id)),
+        };
+        let mut stored = 0;
+        let mut cursor = 0;
+        while cursor < manifest.chunks.len() {
+            let (missing, next) = missing_page(&mut self.store, &manifest, cursor)?;
+            stored += next - cursor - missing.len();
+            cursor = next;
+        }
+        Ok(TransferProgress {
+            container_id,
+            chunks: manifest.chunks.len() as u32,
+            stored: stored as u32,
+            active,
+            interrupted,
+        })
+    }
+
+    /// Retrieves deferred chunks of the container fetched metadata-first, unless they are already
+    /// being retrieved.
+    fn materialize(
+        &mut self,
+        endpoints: &mut Endpoints,
+        container_id: ContainerId,
+    ) -> Result<(), DaemonError> {
+        let deferred = match self.lazy.materialize(container_id) {
+            Some(deferred) => deferred,
+            None => return Ok(()),
+        };
+        info!("Materializing container {} from {}", container_id, deferred.remote_id);
+        self.schedule_transfer(
+            endpoints,
+            deferred.app,
+            CtlMsg::GetContainer(AddressedClientMsg {
+                remote_id: deferred.remote_id,
+                client_id: None,
+                data: AppContainer {
+                    storm_app: deferred.app,
+                    container_id: deferred.id,
+                },
+            }),
+        )
+    }
+
+    /// Queues container transfer requested by an app, unless it is a bulk replication transfer
+    /// deferred by the replication schedule.
+    fn schedule_transfer(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        request: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        let request = if ReplicationSchedule::is_bulk(app) {
+            match self.replication.admit(request) {
+                Some(request) => request,
+                None => {
+                    debug!(
+                        "Transfer for {} is deferred by the replication schedule ({} deferred)",
+                        app,
+                        self.replication.deferred()
+                    );
+                    return Ok(());
+                }
+            }
+        } else {
+            request
+        };
+        self.ctl_queue.push_back(request);
+        self.pick_or_start(endpoints, None)
+    }
+
+    fn release_replication(&mut self, endpoints: &mut Endpoints) {
+        let since_boot = self.stats.stats().since_boot;
+        self.replication.measure(since_boot.bytes_received + since_boot.bytes_sent);
+        let due = self.replication.due();
+        if due.is_empty() {
+            return;
+        }
+        info!("Releasing {} bulk replication transfers", due.len());
+        self.ctl_queue.extend(due);
+        if let Err(err) = self.pick_or_start(endpoints, None) {
+            error!("Unable to start deferred replication transfers: {}", err);
+        }
+    }
+
+    fn release_chunk_requests(&mut self, endpoints: &mut Endpoints) {
+        let due = self.rate_limits.due();
+        if due.is_empty() {
+            return;
+        }
+        debug!("Releasing {} chunk requests deferred by the rate limits", due.len());
+        for request in due {
+            let container_id = request.data.container_id;
+            let instr = CtlMsg::SendChunks(request);
+            let res = match self.container_transfers.get(&container_id) {
+                Some(daemon_id) => self.send_ctl(endpoints, ServiceId::Transfer(*daemon_id), instr),
+                None => {
+                    self.ctl_queue.push_back(instr);
+                    Ok(())
+                }
+            };
+            if let Err(err) = res {
+                error!("Unable to send deferred chunks: {}", err);
             }
-            ServiceId::Transfer(daemon_id) => {
-                self.transferd_free.push_back(daemon_id);
-                info!(
-                    "Transfer service {} is registered; total {} container processors are known",
-                    daemon_id,
-                    self.transferd_free.len() + self.transferd_busy.len()
-                );
+        }
+        if let Err(err) = self.pick_or_start(endpoints, None) {
+            error!("Unable to start deferred chunk transfers: {}", err);
+        }
+    }
+
+    /// Measures memory of the node components against the memory budget, shedding the load once
+    /// the budget is approached.
+    fn account_memory(&mut self, endpoints: &mut Endpoints) {
+        if !self.memory.is_due() {
+            return;
+        }
+        let queued = self.ctl_queue.len()
+            + self.msg_link.queued()
+            + self.rate_limits.deferred()
+            + self.replication.deferred()
+            + self.ext_lanes.deferred();
+        let index = self.containers.snapshot().len() * std::mem::size_of::<ContainerEntry>();
+        self.memory.record("stormd.queues", queued as u64 * QUEUED_ENTRY_SIZE);
+        self.memory.record("stormd.index", index as u64);
+        self.memory.record("stormd.dictionary_samples", self.dictionaries.sampled() as u64);
+
+        let (prev, pressure) = match self.memory.sample() {
+            Some(change) => change,
+            None => return,
+        };
+        let report = self.memory.report();
+        match pressure {
+            MemoryPressure::Normal => info!("Memory use is back within the budget"),
+            _ => warn!(
+                "Memory pressure is {}: {} bytes accounted, {} bytes resident",
+                pressure,
+                report.accounted,
+                report.resident.unwrap_or_default()
+            ),
+        }
+        if prev == MemoryPressure::Normal {
+            info!("Shrinking caches of the node daemons");
+            self.dictionaries.shrink();
+            let daemons =
+                self.transferd_free.iter().chain(&self.transferd_busy).copied().collect::<Vec<_>>();
+            for daemon_id in daemons {
+                let service = ServiceId::Transfer(daemon_id);
+                if let Err(err) = self.send_ctl(endpoints, service.clone(), CtlMsg::ShrinkCaches) {
+                    warn!("Unable to shrink caches of {}: {}", service, err);
+                }
             }
-            _ => {
-                // Ignoring the rest of daemon/client types
+        }
+        if pressure == MemoryPressure::Critical {
+            warn!("New transfers are paused until the memory use goes down");
+        } else if prev == MemoryPressure::Critical {
+            info!("Resuming {} queued transfers", self.ctl_queue.len());
+            if let Err(err) = self.pick_or_start(endpoints, None) {
+                error!("Unable to resume queued transfers: {}", err);
             }
         }
+    }
 
-        Ok(())
+    fn flush_digests(&mut self) {
+        for digest in self.events.due() {
+            debug!("Event digest {}", digest);
+            for app in self.apps.registered() {
+                self.ext_lanes.push(Some(app), ExtMsg::EventDigest(digest.clone()));
+            }
+        }
     }
 
-    fn pick_task(&mut self, endpoints: &mut Endpoints) -> Result<bool, esb::Error<ServiceId>> {
-        if self.ctl_queue.is_empty() {
-            return Ok(true);
+    fn notify_degraded(&mut self, degraded: bool) {
+        for app in self.apps.registered() {
+            self.ext_lanes.push(Some(app), ExtMsg::Degraded(degraded));
         }
+    }
 
-        let (service, daemon_id) = match self.transferd_free.front() {
-            Some(damon_id) => (ServiceId::Transfer(*damon_id), *damon_id),
-            None => return Ok(false),
+    /// Rebuilds the container index out of the store, returning the number of the indexed
+    /// containers. The index is not rebuilt while there are transfers in progress.
+    fn rebuild_index(&mut self) -> Result<usize, DaemonError> {
+        if !self.container_transfers.is_empty() {
+            return Err(DaemonError::TransfersInProgress);
+        }
+        let count = self.containers.rebuild(&mut self.store)?;
+        self.containers.checkpoint()?;
+        Ok(count)
+    }
+
+    /// Pins or unpins a batch of containers. The batch is applied only if each of the containers
+    /// is known to the node; otherwise no changes are made.
+    fn pin_many(
+        &mut self,
+        container_ids: Vec<ContainerId>,
+        pin: bool,
+        dry_run: bool,
+    ) -> Result<BatchResult, DaemonError> {
+        let pinned = self.pins.iter().collect::<BTreeSet<_>>();
+        let entries = self.containers.snapshot();
+        let mut items = Vec::with_capacity(container_ids.len());
+        let mut seen = BTreeSet::new();
+        let mut bytes = 0u64;
+        for container_id in &container_ids {
+            // Containers which are not retrieved yet are pinned ahead of the retrieval
+            let status = if !seen.insert(*container_id) || pinned.contains(container_id) == pin {
+                ItemStatus::Unchanged
+            } else {
+                bytes += entries.get(container_id).map(|entry| entry.size).unwrap_or_default();
+                ItemStatus::Done
+            };
+            items.push(status);
+        }
+
+        let mut result = BatchResult {
+            applied: false,
+            items,
+            bytes,
         };
+        if dry_run || result.failed() > 0 {
+            return Ok(result);
+        }
 
-        let msg = match self.ctl_queue.pop_front() {
-            None => return Ok(true),
-            Some(req) => req,
+        if pin {
+            self.pins.pin(container_ids.iter().copied())?;
+        } else {
+            self.pins.unpin(container_ids.iter().copied())?;
+        }
+        for container_id in container_ids {
+            self.popularity.set_pinned(container_id, pin);
+        }
+        result.applied = true;
+        Ok(result)
+    }
+
+    fn evict_many(
+        &mut self,
+        container_ids: Vec<ContainerId>,
+        dry_run: bool,
+    ) -> Result<BatchResult, DaemonError> {
+        let pinned = self.pins.snapshot();
+        let entries = self.containers.snapshot();
+        let mut items = Vec::with_capacity(container_ids.len());
+        let mut seen = BTreeSet::new();
+        let mut bytes = 0u64;
+        for container_id in &container_ids {
+            let status = if !seen.insert(*container_id) {
+                ItemStatus::Unchanged
+            } else if let Some(entry) = entries.get(container_id) {
+                if pinned.contains(container_id) {
+                    ItemStatus::Failed(format!("container {} is pinned", container_id))
+                } else {
+                    bytes += entry.size;
+                    ItemStatus::Done
+                }
+            } else {
+                ItemStatus::Failed(format!("container {} is not known", container_id))
+            };
+            items.push(status);
+        }
+
+        let mut result = BatchResult {
+            applied: false,
+            items,
+            bytes,
         };
+        if dry_run || result.failed() > 0 {
+            return Ok(result);
+        }
 
-        debug!("Assigning task {} to {}", msg, service);
+        for container_id in seen {
+            self.evict_container(container_id)?;
+        }
+        result.applied = true;
+        Ok(result)
+    }
 
-        let container_id = match msg {
-            CtlMsg::GetContainer(AddressedClientMsg {
-                data: AppContainer { container_id, .. },
-                ..
+    /// Accounts container stored by the node against the storage quotas, evicting containers
+    /// exceeding them. Returns whether the stored container itself was evicted.
+    fn keep_quotas(
+        &mut self,
+        container_id: ContainerId,
+        source: Option<NodeId>,
+    ) -> Result<bool, DaemonError> {
+        let pinned = self.pins.snapshot();
+        let popularity = &self.popularity;
+        let evicted = self.quotas.stored(
+            container_id,
+            source,
+            self.containers.entries(),
+            &pinned,
+            |entry| entry.timestamp.max(popularity.last_request(entry.container_id)),
+        )?;
+        for id in &evicted {
+            self.evict_container(*id)?;
+        }
+        Ok(evicted.contains(&container_id))
+    }
+
+    /// Turns sensitive admin action into a proposal awaiting approvals of the operator keys.
+    fn propose(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        action: AdminAction,
+    ) -> Result<(), DaemonError> {
+        let proposal = self.approvals.propose(action)?;
+        info!("Admin action {} awaits approvals", proposal);
+        let reply = DaemonError::ApprovalRequired(proposal.id, self.approvals.threshold()).into();
+        self.reply_rpc(endpoints, client_id, reply)?;
+        Ok(())
+    }
+
+    /// Executes sensitive admin action, returning description of its outcome.
+    fn execute(&mut self, action: AdminAction) -> Result<String, DaemonError> {
+        match action {
+            AdminAction::Evict(container_ids) => {
+                let result = self.evict_many(container_ids, false)?;
+                info!("Batch eviction: {}", result);
+                Ok(result.to_string())
+            }
+            AdminAction::CollectGarbage => {
+                if !self.transferd_busy.is_empty() {
+                    return Err(DaemonError::TransfersInProgress);
+                }
+                self.collect_garbage(false).map(|report| report.to_string())
+            }
+            AdminAction::RotateManifestKey => {
+                let previous = self.manifest_key.signer();
+                self.manifest_key = ManifestKey::rotate(&self.config.data_dir)?;
+                info!("Manifest key {} is replaced with {}", previous, self.manifest_key.signer());
+                Ok(format!("manifest key is replaced with {}", self.manifest_key.signer()))
+            }
+            AdminAction::Unpin(container_ids) => {
+                let result = self.pin_many(container_ids, false, false)?;
+                info!("Batch unpin: {}", result);
+                Ok(result.to_string())
+            }
+            AdminAction::RebuildIndex => {
+                let count = self.rebuild_index()?;
+                Ok(format!("{} containers are indexed", count))
+            }
+            AdminAction::RevokeShare(token_id) => {
+                self.shares.revoke(token_id)?;
+                info!("Share token {:#018x} is revoked", token_id);
+                Ok(format!("share token {:#018x} is revoked", token_id))
+            }
+        }
+    }
+
+    fn collect_garbage(&mut self, dry_run: bool) -> Result<GcReport, DaemonError> {
+        let report = self.gc.run(&mut self.store, dry_run)?;
+        info!("Garbage collection{}: {}", dry_run_note(dry_run), report);
+        Ok(report)
+    }
+
+    /// Evicts the container from the store, leaving its chunks for the garbage collector.
+    fn evict_container(&mut self, container_id: ContainerId) -> Result<(), DaemonError> {
+        info!("Container {} is evicted from the storage", container_id);
+        containers::evict(&mut self.store, container_id)?;
+        self.containers.discard(container_id)?;
+        self.quotas.evict(container_id)?;
+        self.popularity.evicted(container_id);
+        self.emit(NodeEvent::CacheEvicted(container_id));
+        Ok(())
+    }
+
+    /// Routes app-opaque message received from a remote peer to the app with the same id.
+    fn handle_opaque(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        payload: &[u8],
+    ) -> Result<(), DaemonError> {
+        let OpaqueEnvelope { app, msg } = OpaqueEnvelope::strict_deserialize(payload)?;
+        if msg.is_oversized() {
+            warn!("Peer {} has sent oversized app-opaque message ({})", remote_id, msg);
+            return Ok(());
+        }
+        if app == StormApp::System {
+            // Node-level messages
+            match msg.ty {
+                ATTESTATION_MSG_TYPE => {
+                    let attestation = SignedAttestation::strict_deserialize(&msg.payload)?;
+                    self.attestations.register(remote_id, attestation)?;
+                }
+                FEATURES_MSG_TYPE => {
+                    let features = NodeFeatures::strict_deserialize(&msg.payload)?;
+                    self.features.register(remote_id, features);
+                }
+                REPLICA_REQUEST_MSG_TYPE => {
+                    let request = ReplicaRequest::strict_deserialize(&msg.payload)?;
+                    self.keep_replica(endpoints, remote_id, request)?;
+                }
+                REPLICA_ACK_MSG_TYPE => {
+                    let ack = ReplicaAck::strict_deserialize(&msg.payload)?;
+                    match self.replicator.acknowledged(remote_id, ack) {
+                        Some(status) => {
+                            let state = status.replicas.get(&remote_id).copied();
+                            info!(
+                                "Replica of {} on {} is {}",
+                                ack.container_id,
+                                remote_id,
+                                state.map(|state| state.to_string()).unwrap_or_default()
+                            );
+                            if ack.stored && status.confirmed() == status.min_replicas as usize {
+                                info!(
+                                    "Container {} has reached {} confirmed replicas",
+                                    ack.container_id, status.min_replicas
+                                );
+                            }
+                        }
+                        None => debug!(
+                            "Dropping unsolicited replica acknowledgement from {}",
+                            remote_id
+                        ),
+                    }
+                }
+                DECLINE_REASON_MSG_TYPE => {
+                    let notice = DeclineNotice::strict_deserialize(&msg.payload)?;
+                    self.moderation.reason_received(remote_id, notice);
+                }
+                MAINTENANCE_MSG_TYPE => {
+                    let notice = MaintenanceNotice::strict_deserialize(&msg.payload)?;
+                    info!(
+                        "Peer {} is in maintenance; requests to it may be retried in {} s",
+                        remote_id, notice.retry_after
+                    );
+                }
+                POW_STAMP_MSG_TYPE => {
+                    let stamp = PowStamp::strict_deserialize(&msg.payload)?;
+                    self.stamps.present(remote_id, stamp);
+                }
+                ENTROPY_DIGEST_MSG_TYPE | ENTROPY_SET_MSG_TYPE
+                    if !self.entropy.is_partner(remote_id) =>
+                {
+                    debug!("Dropping reconciliation request from non-partner {}", remote_id);
+                }
+                ENTROPY_DIGEST_MSG_TYPE => {
+                    let digest = ReplicaDigest::strict_deserialize(&msg.payload)?;
+                    let set = self.replica_set();
+                    if set.digest() == digest {
+                        debug!("Replication partner {} is in sync", remote_id);
+                    } else {
+                        self.send_node_msg(endpoints, remote_id, ENTROPY_SET_MSG_TYPE, &set)?;
+                    }
+                }
+                ENTROPY_SET_MSG_TYPE => {
+                    let remote = ReplicaSet::strict_deserialize(&msg.payload)?;
+                    self.repair_replica(endpoints, remote_id, remote)?;
+                }
+                ADMIN_REQUEST_MSG_TYPE => {
+                    let AdminRequest { id, command } =
+                        AdminRequest::strict_deserialize(&msg.payload)?;
+                    let outcome = self.administer(endpoints, remote_id, command)?;
+                    let reply = AdminReply { id, outcome };
+                    self.send_node_msg(endpoints, remote_id, ADMIN_REPLY_MSG_TYPE, &reply)?;
+                }
+                ADMIN_REPLY_MSG_TYPE => {
+                    let AdminReply { id, outcome } = AdminReply::strict_deserialize(&msg.payload)?;
+                    match self.admin.answered(remote_id, id) {
+                        Some(client_id) => {
+                            self.send_rpc(endpoints, client_id, RpcMsg::AdminOutcome(outcome))?;
+                        }
+                        None => debug!("Dropping unsolicited admin reply from {}", remote_id),
+                    }
+                }
+                INTEREST_MSG_TYPE => {
+                    let interest = InterestFilter::strict_deserialize(&msg.payload)?;
+                    self.interests.register(remote_id, interest);
+                }
+                TOPIC_SKETCH_MSG_TYPE => {
+                    let SketchRequest { app, sketch } =
+                        SketchRequest::strict_deserialize(&msg.payload)?;
+                    let topics = self.topics.locals().get(&app).into_iter().flatten();
+                    let reply = SketchReply {
+                        app,
+                        delta: sketch.diff(topics.copied()),
+                    };
+                    self.send_node_msg(endpoints, remote_id, TOPIC_DIFF_MSG_TYPE, &reply)?;
+                }
+                TOPIC_DIFF_MSG_TYPE => {
+                    let SketchReply { app, delta } = SketchReply::strict_deserialize(&msg.payload)?;
+                    if !self.topics.finish_sync(app, remote_id) {
+                        debug!("Dropping unsolicited topic difference from {}", remote_id);
+                        return Ok(());
+                    }
+                    match delta {
+                        Some(delta) => {
+                            for topic_id in &delta.missing {
+                                self.topics.register(app, *topic_id);
+                            }
+                            self.topics_synced(endpoints, app, remote_id, delta);
+                        }
+                        None => {
+                            debug!(
+                                "Topics of {} differ from {} beyond the sketch capacity; falling \
+                                 back to the full topic list",
+                                app, remote_id
+                            );
+                            self.topics.start_sync(app, remote_id);
+                            let msg = Messages::ListTopics(AppMsg { app, data: () });
+                            self.send_msg(endpoints, remote_id, msg);
+                        }
+                    }
+                }
+                ty => debug!("Dropping unknown node message type {:#06x}", ty),
+            }
+            return Ok(());
+        }
+        if !self.apps.contains(app) {
+            debug!("Dropping app-opaque message for app {} which is not registered", app);
+            return Ok(());
+        }
+        if !self.stamps.is_allowed(remote_id) {
+            debug!("Dropping app-opaque message from {} which has presented no stamp", remote_id);
+            return Ok(());
+        }
+        self.ext_lanes.push(
+            Some(app),
+            ExtMsg::Opaque(AddressedMsg {
+                remote_id,
+                data: msg,
+            }),
+        );
+        Ok(())
+    }
+
+    /// Retrieves topics missed by the app from the remote peer and reports the completed sync to
+    /// the app.
+    fn topics_synced(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        remote_id: NodeId,
+        delta: TopicsDelta,
+    ) {
+        info!("Topics of {} are synced with {}: {}", app, remote_id, delta);
+        for topic_id in &delta.missing {
+            let msg = Messages::Read(AppMsg {
+                app,
+                data: topic_id.to_wire(),
+            });
+            self.send_msg(endpoints, remote_id, msg);
+        }
+        let synced = ExtMsg::TopicsSynced(AddressedMsg {
+            remote_id,
+            data: delta,
+        });
+        self.ext_lanes.push(Some(app), synced);
+    }
+
+    /// Converts accept or decline received from a remote peer into a topic message, if it
+    /// refers to a known topic, attaching to the declines of messages the reasons sent by the
+    /// peer.
+    fn resolve_topic(&mut self, app: StormApp, message: ExtMsg) -> ExtMsg {
+        match message {
+            ExtMsg::Accept(AddressedMsg { remote_id, data }) => {
+                match self.topics.resolve(app, data) {
+                    Some(data) => ExtMsg::AcceptTopic(AddressedMsg { remote_id, data }),
+                    None => ExtMsg::Accept(AddressedMsg { remote_id, data }),
+                }
+            }
+            ExtMsg::Decline(AddressedMsg { remote_id, data }) => {
+                let reason = self.moderation.take_reason(remote_id, app, data);
+                match (self.topics.resolve(app, data), reason) {
+                    (Some(topic_id), reason) => {
+                        if let Some(reason) = reason {
+                            debug!("Peer {} declines topic {}: {}", remote_id, topic_id, reason);
+                        }
+                        ExtMsg::DeclineTopic(AddressedMsg {
+                            remote_id,
+                            data: topic_id,
+                        })
+                    }
+                    (None, Some(reason)) => ExtMsg::DeclineWithReason(AddressedMsg {
+                        remote_id,
+                        data: Declined { id: data, reason },
+                    }),
+                    (None, None) => ExtMsg::Decline(AddressedMsg { remote_id, data }),
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Accepts a batch of messages. Since accepts can't be revoked once sent to the remote peers,
+    /// the batch is validated first and nothing is sent if any of the items is invalid. Items which
+    /// pass the validation may still fail to be sent, in which case the rest of them are sent
+    /// nevertheless and the batch is reported as not applied.
+    fn accept_many(
+        &mut self,
+        endpoints: &mut Endpoints,
+        app: StormApp,
+        accepts: Vec<AddressedMsg<MesgId>>,
+    ) -> BatchResult {
+        let banned = self.moderation.banned();
+        let mut seen = BTreeSet::new();
+        let items = accepts
+            .iter()
+            .map(|accept| {
+                if banned.contains(&accept.remote_id) {
+                    ItemStatus::Failed(format!("peer {} is banned", accept.remote_id))
+                } else if seen.insert((accept.remote_id, accept.data)) {
+                    ItemStatus::Done
+                } else {
+                    ItemStatus::Unchanged
+                }
             })
-            | CtlMsg::SendContainer(AddressedClientMsg {
-                data: AppContainer { container_id, .. },
-                ..
-            }) => Some(container_id.container_id),
-            _ => None,
+            .collect::<Vec<_>>();
+        let mut result = BatchResult {
+            applied: false,
+            items,
+            bytes: 0,
         };
-        self.send_ctl(endpoints, service, msg)?;
+        if result.failed() > 0 {
+            warn!("Batch accept is not applied since some of its items are invalid");
+            return result;
+        }
+        for (accept, status) in accepts.into_iter().zip(&mut result.items) {
+            if *status != ItemStatus::Done {
+                continue;
+            }
+            let msg = Messages::Accept(AppMsg {
+                app,
+                data: accept.data,
+            });
+            if let Err(err) = self.deliver_p2p(endpoints, accept.remote_id, msg) {
+                warn!(
+                    "Unable to accept message {} from {}: {}",
+                    accept.data, accept.remote_id, err
+                );
+                *status = ItemStatus::Failed(err.to_string());
+                self.link_failed();
+            }
+        }
+        result.applied = result.failed() == 0;
+        result
+    }
 
-        if let Some(container_id) = container_id {
-            self.container_transfers.insert(container_id, daemon_id);
+    /// Replies to the client, caching the reply if it is a result of an idempotent request.
+    fn reply_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        reply: RpcMsg,
+    ) -> Result<(), esb::Error<ServiceId>> {
+        if let Some(request_id) = self.current_request {
+            self.request_cache.complete(client_id, request_id, &reply);
         }
-        self.transferd_free.pop_front();
-        self.transferd_busy.insert(daemon_id);
+        self.send_rpc(endpoints, client_id, reply)
+    }
 
-        Ok(true)
+    fn negotiate(&mut self, service_id: ServiceId, features: BusFeatures) -> BusFeatures {
+        let features = features.intersect(BusFeatures::supported());
+        debug!("Negotiated bus features with {}: {}", service_id, features);
+        self.bus_features.insert(service_id, features);
+        features
+    }
+
+    /// Delivers queued messages to application extensions, control lane first. Bulk messages
+    /// above the budget of the poll iteration are left for the next iterations.
+    fn flush_ext(&mut self, endpoints: &mut Endpoints) {
+        self.ext_lanes.renew();
+        while let Some((app_id, message)) = self.ext_lanes.pop() {
+            let dict_app = app_id.filter(|_| matches!(message, ExtMsg::Dictionary(_)));
+            match self.send_ext(endpoints, app_id, message) {
+                Err(err) => {
+                    warn!("Unable to deliver message to application extension: {}", err);
+                    if let Some(app) = app_id {
+                        self.daemon_crashed(
+                            &ServiceId::StormApp(app),
+                            "messages are not delivered",
+                        );
+                    }
+                }
+                // Messages to the app are compressed with the dictionary only once it is sent
+                Ok(_) => {
+                    if let Some(app) = dict_app {
+                        self.dictionaries.set_delivered(app, true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Samples message from the app for learning its compression dictionary, offering the
+    /// dictionary to the app once it is learned.
+    fn learn_dictionary(&mut self, app: StormApp, message: &BusMsg) {
+        if self.dictionaries.known(app).is_some() {
+            return;
+        }
+        match self.dictionaries.sample(app, &message.serialize()) {
+            Ok(true) => self.offer_dictionary(app),
+            Ok(false) => {}
+            Err(err) => warn!("Unable to save compression dictionary of {}: {}", app, err),
+        }
+    }
+
+    /// Sends the app its compression dictionary, if the app negotiated dictionary compression.
+    fn offer_dictionary(&mut self, app: StormApp) {
+        let features = self.bus_features(&ServiceId::StormApp(app));
+        if !features.compression || !features.dictionaries {
+            return;
+        }
+        if let Some(dict) = self.dictionaries.known(app) {
+            debug!("Sending {} to {}", dict, app);
+            self.ext_lanes.push(Some(app), ExtMsg::Dictionary(dict.clone()));
+        }
+    }
+
+    /// Reconstructs container from the retrieved container, if it is a patch, and notifies the app
+    /// which requested the patch about the reconstructed container.
+    fn apply_patch(
+        &mut self,
+        container_id: ContainerId,
+        source: Option<NodeId>,
+    ) -> Result<(), DaemonError> {
+        let target_id = match patches::reconstruct(&mut self.store, container_id) {
+            Ok(Some(target_id)) => target_id,
+            Ok(None) => return Ok(()),
+            Err(err) => {
+                error!("Unable to apply patch {}: {}", container_id, err);
+                return Ok(());
+            }
+        };
+        info!("Container {} is reconstructed from patch {}", target_id, container_id);
+        self.containers.update_size(&mut self.store, target_id)?;
+        if let Some(app) = self.container_apps.get(&container_id).copied() {
+            if let Some(peer) = source {
+                self.containers.register(target_id, app, peer)?;
+            }
+            self.container_apps.insert(target_id, app);
+            self.ext_lanes.push(Some(app), ExtMsg::ContainerRetrieved(target_id));
+        }
+        Ok(())
+    }
+
+    /// Runs the steps of the container retrieval which were skipped while the container was held
+    /// in the quarantine.
+    fn complete_released(
+        &mut self,
+        endpoints: &mut Endpoints,
+        container_id: ContainerId,
+        released: Released,
+    ) -> Result<(), DaemonError> {
+        self.emit(NodeEvent::TransferComplete {
+            container_id,
+            success: true,
+        });
+        self.publish(endpoints, Event::TransferComplete {
+            container_id,
+            success: true,
+        });
+        if released.pin {
+            info!("Container {} is pinned on its release", container_id);
+            self.pins.pin([container_id])?;
+            self.popularity.set_pinned(container_id, true);
+        }
+        let metadata_only = self.lazy.is_deferred(container_id);
+        if !metadata_only && self.keep_quotas(container_id, Some(released.peer))? {
+            return Ok(());
+        }
+        if let Some(app) = self.container_apps.get(&container_id) {
+            let retrieved = if metadata_only {
+                ExtMsg::MetadataRetrieved(container_id)
+            } else {
+                ExtMsg::ContainerRetrieved(container_id)
+            };
+            self.ext_lanes.push(Some(*app), retrieved);
+        }
+        if !metadata_only {
+            self.apply_patch(container_id, Some(released.peer))?;
+        }
+        Ok(())
+    }
+
+    /// Asks the child daemons to stop and persists the node state, so it is kept even if the
+    /// daemons do not stop in time.
+    fn start_shutdown(&mut self, endpoints: &mut Endpoints) {
+        if self.shutdown.is_requested() {
+            return;
+        }
+        let children = self
+            .daemons
+            .service_ids()
+            .filter(|service_id| Daemon::is_child(service_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        info!("Shutting down; stopping {} child daemons", children.len());
+        for service_id in &children {
+            if let Err(err) = self.send_ctl(endpoints, service_id.clone(), CtlMsg::Shutdown) {
+                warn!("Unable to stop {} daemon: {}", service_id, err);
+                self.shutdown.stopped(service_id);
+            }
+        }
+        self.shutdown.start(children);
+        self.persist();
+    }
+
+    /// Exits once all the child daemons have stopped. The state is persisted once again, since
+    /// the stopped transfer daemons report the interrupted transfers.
+    fn stop_if_complete(&mut self) {
+        if !self.shutdown.is_complete() {
+            return;
+        }
+        self.persist();
+        info!("Stormd is stopped");
+        std::process::exit(0);
+    }
+
+    fn persist(&mut self) {
+        if let Err(err) = self.stats.save() {
+            error!("Unable to save node statistics: {}", err);
+        }
+        if let Err(err) = self.containers.checkpoint() {
+            error!("Unable to save container index: {}", err);
+        }
+        if let Err(err) = self.resumes.save() {
+            error!("Unable to save interrupted transfers: {}", err);
+        }
+        if let Err(err) = self.shares.save() {
+            error!("Unable to save share tokens: {}", err);
+        }
+    }
+
+    fn admit_container(&mut self, container_id: ContainerId) -> Result<(), DaemonError> {
+        info!("Container {} is admitted into the cache", container_id);
+        self.emit(NodeEvent::CacheAdmitted(container_id));
+        let pinned = self.pins.snapshot();
+        for evicted in self.popularity.admit(container_id, self.config.ext.cache) {
+            if pinned.contains(&evicted) {
+                continue;
+            }
+            info!("Container {} is evicted from the cache", evicted);
+            self.evict_container(evicted)?;
+        }
+        Ok(())
     }
 
     fn pick_or_start(
@@ -510,6 +3903,16 @@ impl Runtime {
         endpoints: &mut Endpoints,
         client_id: Option<ClientId>,
     ) -> Result<(), DaemonError> {
+        if self.memory.is_exhausted() && !self.ctl_queue.is_empty() {
+            if let Some(client_id) = client_id {
+                let _ = self.send_rpc(
+                    endpoints,
+                    client_id,
+                    RpcMsg::Progress(s!("Transfer is queued until the node memory use goes down")),
+                );
+            }
+            return Ok(());
+        }
         if self.pick_task(endpoints)? {
             if let Some(client_id) = client_id {
                 let _ = self.send_rpc(
@@ -521,7 +3924,12 @@ impl Runtime {
             return Ok(());
         }
 
-        let config = self.config.clone().into();
+        if self.shutdown.is_requested() {
+            debug!("Node is shutting down; no transfer service is started");
+            return Ok(());
+        }
+
+        let config = self.config.clone();
         let _handle = self.launch_daemon(Daemon::Transferd, config)?;
         if let Some(client_id) = client_id {
             let _ = self.send_rpc(
@@ -0,0 +1,130 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use microservices::esb::ClientId;
+use storm_rpc::{JobId, SessionInfo, SessionToken};
+
+use super::popularity::unix_time;
+
+/// Time after the last request of a client without unfinished jobs after which its session is
+/// forgotten.
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(3600);
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Session {
+    token: SessionToken,
+    connected: u64,
+    last_seen: Instant,
+    jobs: BTreeSet<JobId>,
+}
+
+/// Sessions of the RPC clients, tracking the background jobs submitted by each client.
+///
+/// Session starts with the first request of the client. Clients disconnected by the node
+/// operator are refused any further requests for the daemon lifetime; their jobs are not
+/// cancelled.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Sessions {
+    sessions: BTreeMap<ClientId, Session>,
+    /// Clients which have submitted the unfinished jobs
+    job_clients: HashMap<JobId, ClientId>,
+    disconnected: HashSet<ClientId>,
+}
+
+impl Sessions {
+    /// Registers request of the client, returning whether the client may be served.
+    pub fn seen(&mut self, client_id: ClientId) -> bool {
+        if self.disconnected.contains(&client_id) {
+            return false;
+        }
+        let session = self.sessions.entry(client_id).or_insert_with(|| {
+            debug!("Client {:#018x} has started a session", client_id);
+            Session {
+                token: rand::random(),
+                connected: unix_time(),
+                last_seen: Instant::now(),
+                jobs: empty!(),
+            }
+        });
+        session.last_seen = Instant::now();
+        true
+    }
+
+    /// Number of the unfinished jobs submitted by the client.
+    pub fn jobs(&self, client_id: ClientId) -> usize {
+        self.sessions.get(&client_id).map(|session| session.jobs.len()).unwrap_or_default()
+    }
+
+    pub fn job_submitted(&mut self, client_id: ClientId, job_id: JobId) {
+        if let Some(session) = self.sessions.get_mut(&client_id) {
+            session.jobs.insert(job_id);
+            self.job_clients.insert(job_id, client_id);
+        }
+    }
+
+    pub fn job_finished(&mut self, job_id: JobId) {
+        let client_id = match self.job_clients.remove(&job_id) {
+            Some(client_id) => client_id,
+            None => return,
+        };
+        if let Some(session) = self.sessions.get_mut(&client_id) {
+            session.jobs.remove(&job_id);
+        }
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = SessionInfo> + '_ {
+        self.sessions.iter().map(|(client_id, session)| SessionInfo {
+            token: session.token,
+            client_id: *client_id,
+            connected: session.connected,
+            jobs: session.jobs.len().min(u16::MAX as usize) as u16,
+        })
+    }
+
+    /// Closes the session with the given token, returning the id of its client.
+    pub fn disconnect(&mut self, token: SessionToken) -> Option<ClientId> {
+        let client_id = self
+            .sessions
+            .iter()
+            .find(|(_, session)| session.token == token)
+            .map(|(client_id, _)| *client_id)?;
+        self.close(client_id);
+        self.disconnected.insert(client_id);
+        Some(client_id)
+    }
+
+    /// Forgets idle sessions without unfinished jobs, returning their clients.
+    pub fn expire(&mut self) -> Vec<ClientId> {
+        let expired = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| {
+                session.jobs.is_empty() && session.last_seen.elapsed() > SESSION_IDLE_TIMEOUT
+            })
+            .map(|(client_id, _)| *client_id)
+            .collect::<Vec<_>>();
+        for client_id in &expired {
+            debug!("Session of client {:#018x} has expired", client_id);
+            self.close(*client_id);
+        }
+        expired
+    }
+
+    fn close(&mut self, client_id: ClientId) {
+        if let Some(session) = self.sessions.remove(&client_id) {
+            for job_id in session.jobs {
+                self.job_clients.remove(&job_id);
+            }
+        }
+    }
+}
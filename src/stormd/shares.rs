@@ -0,0 +1,251 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use amplify::{Slice32, Wrapper};
+use bitcoin_hashes::{cmp, sha256, Hash, HashEngine, Hmac, HmacEngine};
+use internet2::addr::NodeId;
+use storm::ContainerId;
+use storm_rpc::{ShareInfo, ShareRequest, ShareToken};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::journal;
+use super::popularity::unix_time;
+use crate::DaemonError;
+
+/// Name of the file inside the data directory keeping share token signing key and issued tokens.
+pub const STORM_NODE_SHARES_FILE: &str = "shares.dat";
+
+/// Minimal interval between saving the use counters of the share tokens.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ShareError {
+    /// share token {0:#018x} is not known
+    Unknown(u64),
+
+    /// share token {0:#018x} has invalid signature
+    InvalidSignature(u64),
+
+    /// share token {0:#018x} has expired
+    Expired(u64),
+
+    /// share token {0:#018x} was revoked
+    Revoked(u64),
+}
+
+/// Result of the check whether a remote peer may read a container.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum Access {
+    /// The container was never shared, so it is readable by anyone.
+    Public,
+
+    /// Access is granted by the share token with the given id.
+    Granted(u64),
+
+    /// The container was shared, but none of its live shares is granted to the peer.
+    Denied,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+struct ShareData {
+    secret: Slice32,
+    shares: BTreeMap<u64, ShareInfo>,
+}
+
+/// Registry of share tokens issued by the node, persisted in the data directory.
+///
+/// Once shared, a container is restricted for good: it is read only by the grantees of its live
+/// shares, i.e. ones neither revoked nor expired, so revocation or expiry of the last share leaves
+/// the container readable by no peer. A share without a grantee is an anonymous token, which is
+/// presented via the HTTP gateway rather than by a peer; thus, it restricts reads of the container
+/// by the peers, but never grants them.
+///
+/// Issued and revoked shares are saved right away; the use counters are saved when the node
+/// handles other messages, but not more frequently than once per [`SAVE_INTERVAL`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Shares {
+    path: PathBuf,
+    data: ShareData,
+    /// Whether there are use counters which are not saved yet
+    dirty: bool,
+    saved: Instant,
+}
+
+impl Shares {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_SHARES_FILE);
+        let data = if path.exists() {
+            ShareData::strict_decode(fs::File::open(&path)?)?
+        } else {
+            debug!("Generating share token signing key");
+            ShareData {
+                secret: Slice32::from_inner(rand::random()),
+                shares: empty!(),
+            }
+        };
+        let mut shares = Shares {
+            path,
+            data,
+            dirty: false,
+            saved: Instant::now(),
+        };
+        shares.save()?;
+        Ok(shares)
+    }
+
+    pub fn save(&mut self) -> Result<(), strict_encoding::Error> {
+        journal::checkpoint(&self.path, &self.data)?;
+        self.dirty = false;
+        self.saved = Instant::now();
+        Ok(())
+    }
+
+    /// Saves the use counters if they have changed and were not saved during the last
+    /// [`SAVE_INTERVAL`].
+    pub fn save_due(&mut self) -> Result<(), strict_encoding::Error> {
+        if !self.dirty || self.saved.elapsed() < SAVE_INTERVAL {
+            return Ok(());
+        }
+        self.save()
+    }
+
+    fn sign(&self, token: &ShareToken) -> Result<Slice32, strict_encoding::Error> {
+        let mut engine = HmacEngine::<sha256::Hash>::new(self.data.secret.as_slice());
+        engine.input(&token.signed_data()?);
+        Ok(Slice32::from_inner(Hmac::from_engine(engine).into_inner()))
+    }
+
+    pub fn create(&mut self, request: ShareRequest) -> Result<ShareToken, DaemonError> {
+        let mut token = ShareToken {
+            id: rand::random(),
+            container_id: request.container_id,
+            grantee: request.grantee,
+            expiry: unix_time().saturating_add(request.duration),
+            signature: zero!(),
+        };
+        token.signature = self.sign(&token)?;
+        self.data.shares.insert(token.id, ShareInfo {
+            token_id: token.id,
+            container_id: token.container_id,
+            grantee: token.grantee,
+            expiry: token.expiry,
+            revoked: false,
+            uses: 0,
+            last_use: None,
+        });
+        self.save()?;
+        Ok(token)
+    }
+
+    pub fn revoke(&mut self, token_id: u64) -> Result<(), DaemonError> {
+        let info = self.data.shares.get_mut(&token_id).ok_or(ShareError::Unknown(token_id))?;
+        info.revoked = true;
+        self.save()?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> Vec<ShareInfo> { self.data.shares.values().copied().collect() }
+
+    /// Verifies token presented by a third party, registering its use on success.
+    pub fn verify(&mut self, token: ShareToken) -> Result<(), DaemonError> {
+        let signature = self.sign(&token)?;
+        if !cmp::fixed_time_eq(signature.as_slice(), token.signature.as_slice()) {
+            return Err(ShareError::InvalidSignature(token.id).into());
+        }
+        let info = self.data.shares.get(&token.id).ok_or(ShareError::Unknown(token.id))?;
+        if info.revoked {
+            return Err(ShareError::Revoked(token.id).into());
+        }
+        if info.expiry <= unix_time() {
+            return Err(ShareError::Expired(token.id).into());
+        }
+        self.register_use(token.id)
+    }
+
+    /// Checks whether a remote peer can read the container.
+    pub fn authorize(&self, container_id: ContainerId, remote_id: NodeId) -> Access {
+        let now = unix_time();
+        // Shares are never removed from the registry, so the revoked and expired ones keep the
+        // container restricted
+        let mut shares =
+            self.data.shares.values().filter(|info| info.container_id == container_id).peekable();
+        if shares.peek().is_none() {
+            return Access::Public;
+        }
+        shares
+            .find(|info| !info.revoked && info.expiry > now && info.grantee == Some(remote_id))
+            .map(|info| Access::Granted(info.token_id))
+            .unwrap_or(Access::Denied)
+    }
+
+    /// Registers use of the share token; the use counters are saved by [`Shares::save_due`].
+    pub fn register_use(&mut self, token_id: u64) -> Result<(), DaemonError> {
+        let info = self.data.shares.get_mut(&token_id).ok_or(ShareError::Unknown(token_id))?;
+        info.uses += 1;
+        info.last_use = Some(unix_time());
+        self.dirty = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+    use std::{env, process};
+
+    use bitcoin_hashes::sha256t;
+
+    use super::*;
+
+    fn peer(key: &str) -> NodeId { NodeId::from_str(key).expect("valid node id") }
+
+    fn grantee() -> NodeId {
+        peer("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+    }
+
+    fn stranger() -> NodeId {
+        peer("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+    }
+
+    fn container(no: u8) -> ContainerId {
+        ContainerId::from_inner(sha256t::Hash::from_inner([no; 32]))
+    }
+
+    #[test]
+    fn revoked_share_keeps_container_restricted() {
+        let data_dir = env::temp_dir().join(format!("storm-shares-{}", process::id()));
+        fs::create_dir_all(&data_dir).expect("temp dir");
+        let mut shares = Shares::load(&data_dir).expect("shares");
+
+        assert_eq!(shares.authorize(container(1), stranger()), Access::Public);
+        let token = shares
+            .create(ShareRequest {
+                container_id: container(1),
+                grantee: Some(grantee()),
+                duration: 3600,
+            })
+            .expect("share");
+        assert_eq!(shares.authorize(container(1), grantee()), Access::Granted(token.id));
+        assert_eq!(shares.authorize(container(1), stranger()), Access::Denied);
+
+        shares.revoke(token.id).expect("revoke");
+        assert_eq!(shares.authorize(container(1), grantee()), Access::Denied);
+        assert_eq!(shares.authorize(container(1), stranger()), Access::Denied);
+        assert_eq!(shares.authorize(container(2), stranger()), Access::Public);
+
+        fs::remove_dir_all(data_dir).expect("temp dir");
+    }
+}
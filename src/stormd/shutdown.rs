@@ -0,0 +1,116 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashSet;
+use std::os::raw::c_int;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use internet2::addr::ServiceAddr;
+use nix::errno::Errno;
+use nix::poll::{poll, PollFd, PollFlags};
+use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
+use nix::unistd::{pipe, read, write};
+use storm_rpc::ServiceId;
+
+/// Time given to the child daemons to persist their state, after which the node exits anyway.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Write end of the pipe through which the signal handler wakes up the signal watcher.
+static SIGNAL_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+extern "C" fn on_signal(_: c_int) {
+    // Only async-signal-safe calls may be made from the handler
+    let _ = write(SIGNAL_PIPE.load(Ordering::Relaxed), &[1]);
+}
+
+/// Handles SIGTERM and SIGINT by requesting the node to shut down over the RPC interface.
+///
+/// The controller of the node has no means to be interrupted, so the signals are watched by a
+/// background thread acting as an RPC client. The node exits once its child daemons have stopped;
+/// if they do not stop within [`SHUTDOWN_TIMEOUT`] or another signal arrives, the watcher exits
+/// the process itself.
+pub fn watch_signals(rpc_endpoint: ServiceAddr, chat_endpoint: ServiceAddr) -> nix::Result<()> {
+    let (reader, writer) = pipe()?;
+    SIGNAL_PIPE.store(writer, Ordering::Relaxed);
+    let action =
+        SigAction::new(SigHandler::Handler(on_signal), SaFlags::SA_RESTART, SigSet::empty());
+    for signal in [Signal::SIGTERM, Signal::SIGINT] {
+        // Safety: the handler only writes into the pipe
+        unsafe { sigaction(signal, &action)? };
+    }
+    thread::spawn(move || watch(reader, rpc_endpoint, chat_endpoint));
+    Ok(())
+}
+
+fn watch(reader: RawFd, rpc_endpoint: ServiceAddr, chat_endpoint: ServiceAddr) {
+    wait_signal(reader, None);
+    info!("Termination signal is received; shutting down");
+    let res = storm_rpc::Client::with(rpc_endpoint, chat_endpoint, s!("stormd"))
+        .and_then(|mut client| client.shutdown());
+    if let Err(err) = res {
+        error!("Unable to request node shutdown: {}", err);
+        std::process::exit(1);
+    }
+    if wait_signal(reader, Some(SHUTDOWN_TIMEOUT)) {
+        warn!("Termination signal is received again; exiting without waiting for the daemons");
+    } else {
+        warn!("Child daemons have not stopped in {} s; exiting", SHUTDOWN_TIMEOUT.as_secs());
+    }
+    std::process::exit(1);
+}
+
+/// Waits for a signal, returning `false` if the timeout has passed first.
+fn wait_signal(reader: RawFd, timeout: Option<Duration>) -> bool {
+    let timeout = timeout.map(|timeout| timeout.as_millis() as c_int).unwrap_or(-1);
+    let mut fds = [PollFd::new(reader, PollFlags::POLLIN)];
+    loop {
+        match poll(&mut fds, timeout) {
+            Ok(0) => return false,
+            Ok(_) => {
+                let mut buf = [0u8; 16];
+                let _ = read(reader, &mut buf);
+                return true;
+            }
+            Err(Errno::EINTR) => continue,
+            Err(err) => {
+                error!("Unable to wait for termination signals: {}", err);
+                return true;
+            }
+        }
+    }
+}
+
+/// Child daemons which were asked to stop and have not confirmed it yet.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Shutdown {
+    requested: bool,
+    pending: HashSet<ServiceId>,
+}
+
+impl Shutdown {
+    pub fn is_requested(&self) -> bool { self.requested }
+
+    /// Registers the child daemons which were asked to stop.
+    pub fn start(&mut self, daemons: impl IntoIterator<Item = ServiceId>) {
+        self.requested = true;
+        self.pending.extend(daemons);
+    }
+
+    /// Registers confirmation of the child daemon that it stops.
+    pub fn stopped(&mut self, service_id: &ServiceId) { self.pending.remove(service_id); }
+
+    /// Detects whether all the child daemons have stopped since the shutdown was requested.
+    pub fn is_complete(&self) -> bool { self.requested && self.pending.is_empty() }
+
+    pub fn pending(&self) -> usize { self.pending.len() }
+}
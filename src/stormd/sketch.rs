@@ -0,0 +1,145 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+
+use amplify::Wrapper;
+use bitcoin_hashes::{sha256, sha256t, Hash};
+use storm::{MesgId, StormApp};
+use storm_ext::TopicsDelta;
+use storm_rpc::TopicId;
+
+/// Type of the node-level app-opaque message carrying [`SketchRequest`].
+pub const TOPIC_SKETCH_MSG_TYPE: u16 = 0x0005;
+
+/// Type of the node-level app-opaque message carrying [`SketchReply`].
+pub const TOPIC_DIFF_MSG_TYPE: u16 = 0x0006;
+
+/// Number of cells each topic is added to, each in its own part of the sketch.
+const SKETCH_HASHES: usize = 3;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, StrictEncode, StrictDecode)]
+struct SketchCell {
+    count: i32,
+    id_sum: [u8; 32],
+    check_sum: u64,
+}
+
+impl SketchCell {
+    fn is_empty(&self) -> bool {
+        self.count == 0 && self.check_sum == 0 && self.id_sum == [0u8; 32]
+    }
+
+    fn toggle(&mut self, id: &[u8; 32], check: u64, sign: i32) {
+        self.count = self.count.wrapping_add(sign);
+        self.check_sum ^= check;
+        for (sum, byte) in self.id_sum.iter_mut().zip(id) {
+            *sum ^= byte;
+        }
+    }
+}
+
+/// Invertible sketch of a topic set.
+///
+/// Subtracting the sketches of two sets leaves only the topics present in one of them, which
+/// are recovered by peeling the cells containing a single topic. Thus the sketch size depends on
+/// the number of differences between the sets, and not on their size. If the sets differ more
+/// than the sketch was sized for, the difference can't be recovered.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct TopicSketch {
+    cells: Vec<SketchCell>,
+}
+
+impl TopicSketch {
+    /// Builds sketch of the topics, sized to recover up to `capacity` differences.
+    pub fn with(capacity: u16, topics: impl IntoIterator<Item = TopicId>) -> Self {
+        TopicSketch::with_parts(capacity as usize / 2 + 1, topics)
+    }
+
+    fn with_parts(part: usize, topics: impl IntoIterator<Item = TopicId>) -> Self {
+        let mut sketch = TopicSketch {
+            cells: vec![SketchCell::default(); part * SKETCH_HASHES],
+        };
+        for topic_id in topics {
+            sketch.toggle(topic_id, 1);
+        }
+        sketch
+    }
+
+    fn toggle(&mut self, topic_id: TopicId, sign: i32) {
+        let id = topic_id.to_wire().into_inner().into_inner();
+        let hash = sha256::Hash::hash(&id).into_inner();
+        let word = |no: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&hash[no * 8..no * 8 + 8]);
+            u64::from_le_bytes(buf)
+        };
+        let check = word(SKETCH_HASHES);
+        let part = self.cells.len() / SKETCH_HASHES;
+        for no in 0..SKETCH_HASHES {
+            let index = no * part + (word(no) % part as u64) as usize;
+            self.cells[index].toggle(&id, check, sign);
+        }
+    }
+
+    /// Recovers difference between the sketched topics and the given ones: `missing` are the
+    /// given topics absent from the sketch, and `extra` are the sketched topics which are not
+    /// given. Returns `None` if the sketch is malformed or the difference exceeds its capacity.
+    pub fn diff(&self, topics: impl IntoIterator<Item = TopicId>) -> Option<TopicsDelta> {
+        if self.cells.is_empty() || self.cells.len() % SKETCH_HASHES != 0 {
+            return None;
+        }
+        let other = TopicSketch::with_parts(self.cells.len() / SKETCH_HASHES, topics);
+        let mut diff = self.clone();
+        for (cell, other) in diff.cells.iter_mut().zip(&other.cells) {
+            cell.toggle(&other.id_sum, other.check_sum, other.count.wrapping_neg());
+        }
+
+        let mut delta = TopicsDelta::default();
+        let mut peeled = BTreeSet::new();
+        loop {
+            let pure = diff.cells.iter().find(|cell| {
+                matches!(cell.count, 1 | -1)
+                    && sha256::Hash::hash(&cell.id_sum)[24..] == cell.check_sum.to_le_bytes()
+            });
+            let cell = match pure {
+                Some(cell) => *cell,
+                None => break,
+            };
+            let topic_id =
+                TopicId::from_wire(MesgId::from_inner(sha256t::Hash::from_inner(cell.id_sum)));
+            if !peeled.insert(topic_id) {
+                return None;
+            }
+            diff.toggle(topic_id, -cell.count);
+            if cell.count > 0 {
+                delta.extra.insert(topic_id);
+            } else {
+                delta.missing.insert(topic_id);
+            }
+        }
+        diff.cells.iter().all(SketchCell::is_empty).then(|| delta)
+    }
+}
+
+/// Request to reconcile the app topics with the sketch of the topics of the sender.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct SketchRequest {
+    pub app: StormApp,
+    pub sketch: TopicSketch,
+}
+
+/// Reply to [`SketchRequest`] with the difference between the topics from the point of view of
+/// the requester, or `None` if the difference exceeds the sketch capacity.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct SketchReply {
+    pub app: StormApp,
+    pub delta: Option<TopicsDelta>,
+}
@@ -0,0 +1,74 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::{fs, io, thread};
+
+use storm::ContainerId;
+use storm_rpc::{BackupInfo, ContainerEntry};
+use strict_encoding::StrictEncode;
+
+use super::journal::checkpoint;
+use super::popularity::unix_time;
+
+/// Name of the directory inside the data directory where the node backups are written.
+pub const STORM_NODE_BACKUP_DIR: &str = "backups";
+
+/// Consistent point-in-time snapshot of the container index and the pin set.
+///
+/// Snapshot shares data with the live index, which copies the data on its next change, so taking
+/// a snapshot does not block the node while the snapshot is written.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct IndexSnapshot {
+    pub timestamp: u64,
+    pub containers: Arc<BTreeMap<ContainerId, ContainerEntry>>,
+    pub pins: Arc<BTreeSet<ContainerId>>,
+}
+
+impl StrictEncode for IndexSnapshot {
+    fn strict_encode<E: io::Write>(&self, e: E) -> Result<usize, strict_encoding::Error> {
+        let mut e: Box<dyn io::Write + '_> = Box::new(e);
+        Ok(strict_encode_list!(e; self.timestamp, self.containers.as_ref(), self.pins.as_ref()))
+    }
+}
+
+impl IndexSnapshot {
+    pub fn with(
+        containers: Arc<BTreeMap<ContainerId, ContainerEntry>>,
+        pins: Arc<BTreeSet<ContainerId>>,
+    ) -> Self {
+        IndexSnapshot {
+            timestamp: unix_time(),
+            containers,
+            pins,
+        }
+    }
+
+    /// Writes the snapshot as a backup into the data directory in a background thread, returning
+    /// information about the backup.
+    pub fn backup(self, data_dir: &Path) -> Result<BackupInfo, strict_encoding::Error> {
+        let dir = data_dir.join(STORM_NODE_BACKUP_DIR);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("index-{}.dat", self.timestamp));
+        let info = BackupInfo {
+            path: path.display().to_string(),
+            timestamp: self.timestamp,
+            containers: self.containers.len() as u32,
+            pins: self.pins.len() as u32,
+        };
+        thread::spawn(move || match checkpoint(&path, &self) {
+            Ok(()) => info!("Backup {} is written", path.display()),
+            Err(err) => error!("Unable to write backup {}: {}", path.display(), err),
+        });
+        Ok(info)
+    }
+}
@@ -0,0 +1,238 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::thread;
+
+use bitcoin_hashes::{sha256, Hash};
+use internet2::addr::NodeId;
+use storm::p2p::Messages;
+use strict_encoding::StrictEncode;
+
+use super::popularity::unix_time;
+
+/// Type of the node-level app-opaque message carrying [`PowStamp`].
+pub const POW_STAMP_MSG_TYPE: u16 = 0x0002;
+
+/// Time, in seconds, during which a stamp is accepted after it was minted.
+pub const STAMP_VALIDITY: u64 = 3600;
+
+/// Maximal number of messages to a remote peer held until the stamp for it is minted.
+pub const MAX_HELD_MESSAGES: usize = 256;
+
+/// Proof-of-work stamp presented by a peer before its first unsolicited message.
+///
+/// The stamp work is the number of leading zero bits of the SHA256 hash of the stamp encoding.
+/// A stamp is accepted only by its receiver, and only once.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, StrictEncode, StrictDecode)]
+pub struct PowStamp {
+    /// Node the stamp was minted for
+    pub receiver: NodeId,
+    /// UNIX timestamp of the stamp
+    pub timestamp: u64,
+    pub nonce: u64,
+}
+
+impl PowStamp {
+    /// Mints a stamp for the remote peer with at least the given work. Each additional bit
+    /// doubles the number of hashes computed on average.
+    pub fn mint(receiver: NodeId, bits: u8) -> Self {
+        let mut stamp = PowStamp {
+            receiver,
+            timestamp: unix_time(),
+            nonce: 0,
+        };
+        while stamp.work() < bits {
+            stamp.nonce += 1;
+        }
+        stamp
+    }
+
+    pub fn work(&self) -> u8 {
+        let data = self.strict_serialize().expect("in-memory stamp encoding");
+        let hash = sha256::Hash::hash(&data);
+        let mut work = 0u8;
+        for byte in hash.into_inner() {
+            work += byte.leading_zeros() as u8;
+            if byte != 0 {
+                break;
+            }
+        }
+        work
+    }
+}
+
+/// Worker thread minting the stamps for the remote peers off the node event loop.
+#[derive(Debug)]
+struct Minter {
+    bits: u8,
+    requests: mpsc::Sender<NodeId>,
+    minted: mpsc::Receiver<PowStamp>,
+}
+
+impl Minter {
+    fn spawn(bits: u8) -> Self {
+        let (requests, worker) = mpsc::channel::<NodeId>();
+        let (sender, minted) = mpsc::channel();
+        thread::spawn(move || {
+            for receiver in worker {
+                if sender.send(PowStamp::mint(receiver, bits)).is_err() {
+                    break;
+                }
+            }
+        });
+        Minter {
+            bits,
+            requests,
+            minted,
+        }
+    }
+}
+
+/// Tracks the peers which are allowed to send unsolicited messages to the node.
+///
+/// When the node requires proof of work, an unknown peer has to present a valid [`PowStamp`]
+/// minted for this node before its messages are delivered to the apps. Peers which the node has
+/// contacted first are known without a stamp.
+///
+/// Stamps presented to the remote peers are minted by a worker thread; messages to a peer are held
+/// until its stamp is minted and taken with [`Stamps::take_minted`].
+#[derive(Debug)]
+pub struct Stamps {
+    /// Work required from the stamps of the unknown peers, if any
+    required: Option<u8>,
+    /// Node id of this node, which the stamps of the unknown peers have to be minted for
+    local_id: Option<NodeId>,
+    minter: Option<Minter>,
+    known: HashSet<NodeId>,
+    /// Peers which were sent the stamp of this node, or whose stamp is being minted
+    sent: HashSet<NodeId>,
+    /// Messages to the peers whose stamps are being minted
+    held: HashMap<NodeId, Vec<Messages>>,
+    /// Stamps accepted during the validity period, with their timestamps
+    spent: HashMap<PowStamp, u64>,
+}
+
+impl Stamps {
+    pub fn with(required: Option<u8>, minted: Option<u8>, local_id: Option<NodeId>) -> Self {
+        Stamps {
+            required,
+            local_id,
+            minter: minted.map(Minter::spawn),
+            known: empty!(),
+            sent: empty!(),
+            held: empty!(),
+            spent: empty!(),
+        }
+    }
+
+    /// Detects whether messages from the remote peer can be delivered to the apps.
+    pub fn is_allowed(&self, remote_id: NodeId) -> bool {
+        self.required.is_none() || self.known.contains(&remote_id)
+    }
+
+    /// Registers message sent to the remote peer, returning it back if it can be sent right away.
+    /// If the node mints stamps and the stamp for the peer is not minted yet, the message is held
+    /// until [`Stamps::take_minted`] returns it.
+    pub fn contacted(&mut self, remote_id: NodeId, message: Messages) -> Option<Messages> {
+        self.known.insert(remote_id);
+        let minter = match &self.minter {
+            Some(minter) => minter,
+            None => return Some(message),
+        };
+        if self.sent.insert(remote_id) {
+            debug!("Minting stamp of {} bits for {}", minter.bits, remote_id);
+            if minter.requests.send(remote_id).is_err() {
+                error!("Stamp minting thread has stopped; sending message without stamp");
+                return Some(message);
+            }
+            self.held.insert(remote_id, empty!());
+        }
+        let held = match self.held.get_mut(&remote_id) {
+            Some(held) => held,
+            None => return Some(message),
+        };
+        if held.len() >= MAX_HELD_MESSAGES {
+            warn!("Dropping message to {} awaiting the stamp", remote_id);
+        } else {
+            held.push(message);
+        }
+        None
+    }
+
+    /// Takes stamp which was minted for a remote peer, together with the messages held until
+    /// the stamp is presented to it.
+    pub fn take_minted(&mut self) -> Option<(NodeId, PowStamp, Vec<Messages>)> {
+        let stamp = self.minter.as_ref()?.minted.try_recv().ok()?;
+        let held = self.held.remove(&stamp.receiver).unwrap_or_default();
+        Some((stamp.receiver, stamp, held))
+    }
+
+    /// Checks stamp presented by the remote peer, registering the peer as known if it is valid.
+    pub fn present(&mut self, remote_id: NodeId, stamp: PowStamp) -> bool {
+        let required = match self.required {
+            Some(required) => required,
+            None => return true,
+        };
+        let now = unix_time();
+        self.spent.retain(|_, timestamp| *timestamp + STAMP_VALIDITY > now);
+        if stamp.timestamp.saturating_add(STAMP_VALIDITY) <= now
+            || stamp.timestamp > now + STAMP_VALIDITY
+        {
+            warn!("Peer {} has presented expired stamp", remote_id);
+            return false;
+        }
+        if Some(stamp.receiver) != self.local_id {
+            warn!("Peer {} has presented stamp minted for {}", remote_id, stamp.receiver);
+            return false;
+        }
+        if stamp.work() < required {
+            warn!("Peer {} has presented stamp with insufficient work", remote_id);
+            return false;
+        }
+        if self.spent.insert(stamp, stamp.timestamp).is_some() {
+            warn!("Peer {} has presented already used stamp", remote_id);
+            return false;
+        }
+        debug!("Peer {} has presented valid stamp", remote_id);
+        self.known.insert(remote_id);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn node(key: &str) -> NodeId { NodeId::from_str(key).expect("valid node id") }
+
+    fn local() -> NodeId {
+        node("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+    }
+
+    fn other() -> NodeId {
+        node("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+    }
+
+    #[test]
+    fn stamp_is_bound_to_receiver() {
+        let mut stamps = Stamps::with(Some(8), None, Some(local()));
+        assert!(!stamps.present(other(), PowStamp::mint(other(), 8)));
+        assert!(!stamps.is_allowed(other()));
+
+        let stamp = PowStamp::mint(local(), 8);
+        assert!(stamps.present(other(), stamp));
+        assert!(stamps.is_allowed(other()));
+        assert!(!stamps.present(other(), stamp));
+    }
+}
@@ -0,0 +1,103 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use storm_rpc::{NodeCounters, NodeStats};
+use strict_encoding::StrictDecode;
+
+use super::journal::checkpoint;
+
+/// Name of the file inside the data directory keeping the node lifetime counters.
+pub const STORM_NODE_STATS_FILE: &str = "stats.dat";
+
+/// Minimal interval between saving the lifetime counters to the data directory.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Node activity counters since the node start, added up with the counters accumulated before the
+/// start, which are persisted in the data directory.
+///
+/// Since the controller has no timers, the counters are saved when the node handles other
+/// messages, but not more frequently than once per [`SAVE_INTERVAL`]. Thus, up to a minute of
+/// activity may be lost if the node is killed.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Stats {
+    path: PathBuf,
+    /// Counters accumulated before the node start
+    before_boot: NodeCounters,
+    since_boot: NodeCounters,
+    started: Instant,
+    saved: Instant,
+}
+
+impl Stats {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_STATS_FILE);
+        let before_boot = if path.exists() {
+            NodeCounters::strict_decode(fs::File::open(&path)?)?
+        } else {
+            none!()
+        };
+        let now = Instant::now();
+        Ok(Stats {
+            path,
+            before_boot,
+            since_boot: none!(),
+            started: now,
+            saved: now,
+        })
+    }
+
+    /// Saves the lifetime counters.
+    pub fn save(&mut self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.stats().lifetime)?;
+        self.saved = Instant::now();
+        Ok(())
+    }
+
+    /// Saves the lifetime counters if they were not saved during the last [`SAVE_INTERVAL`].
+    pub fn save_due(&mut self) -> Result<(), strict_encoding::Error> {
+        if self.saved.elapsed() < SAVE_INTERVAL {
+            return Ok(());
+        }
+        self.save()
+    }
+
+    pub fn received(&mut self, bytes: usize) {
+        self.since_boot.messages_received += 1;
+        self.since_boot.bytes_received += bytes as u64;
+    }
+
+    pub fn sent(&mut self, bytes: usize) {
+        self.since_boot.messages_sent += 1;
+        self.since_boot.bytes_sent += bytes as u64;
+    }
+
+    pub fn transfer_finished(&mut self, success: bool) {
+        if success {
+            self.since_boot.transfers_completed += 1;
+        } else {
+            self.since_boot.transfers_failed += 1;
+        }
+    }
+
+    pub fn stats(&self) -> NodeStats {
+        let since_boot = NodeCounters {
+            uptime: self.started.elapsed().as_secs(),
+            ..self.since_boot
+        };
+        NodeStats {
+            lifetime: self.before_boot.sum(since_boot),
+            since_boot,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use microservices::DaemonHandle;
+use storm_rpc::ServiceId;
+
+use super::Daemon;
+
+/// Interval between the heartbeats sent to the supervised child daemons.
+pub const DAEMON_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time after the last message of a supervised daemon after which it is considered crashed.
+pub const DAEMON_LIVENESS_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Delay before restarting a crashed daemon, which is doubled with each consecutive crash.
+pub const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximal delay before restarting a crashed daemon.
+pub const RESTART_BACKOFF_LIMIT: Duration = Duration::from_secs(300);
+
+/// Time a restarted daemon has to run for its crashes not to be considered consecutive.
+pub const RESTART_STABLE_PERIOD: Duration = Duration::from_secs(600);
+
+struct Supervised {
+    daemon: Daemon,
+    /// Handle of the running daemon, absent while it awaits the restart
+    handle: Option<DaemonHandle<Daemon>>,
+    started: Instant,
+    last_seen: Instant,
+    /// Number of consecutive crashes
+    crashes: u32,
+    /// Time of the restart of the crashed daemon
+    restart_at: Option<Instant>,
+    /// Whether the daemon has not sent any message since it was (re)started
+    silent: bool,
+}
+
+impl Supervised {
+    fn schedule_restart(&mut self) -> Duration {
+        let delay =
+            RESTART_BACKOFF.saturating_mul(1 << self.crashes.min(16)).min(RESTART_BACKOFF_LIMIT);
+        self.crashes += 1;
+        self.restart_at = Some(Instant::now() + delay);
+        delay
+    }
+}
+
+/// Supervision of the long-running child daemons launched by stormd.
+///
+/// A daemon is considered crashed if its process exits, it does not answer the heartbeats
+/// within [`DAEMON_LIVENESS_TIMEOUT`], or messages can't be delivered to it. Crashed daemons are
+/// restarted after a delay which grows exponentially with the consecutive crashes; while a
+/// daemon is down, the messages for it are declined instead of being sent to the dead service.
+/// Liveness is checked as the node handles other messages.
+pub struct Supervisor {
+    daemons: HashMap<ServiceId, Supervised>,
+    last_heartbeat: Instant,
+    nonce: u64,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Supervisor {
+            daemons: empty!(),
+            last_heartbeat: Instant::now(),
+            nonce: 0,
+        }
+    }
+}
+
+impl Supervisor {
+    /// Registers daemon launched by stormd, keeping the count of its crashes if it is restarted.
+    pub fn launched(&mut self, daemon: Daemon, handle: DaemonHandle<Daemon>) {
+        let service_id = match daemon.service_id() {
+            Some(service_id) => service_id,
+            None => return,
+        };
+        let now = Instant::now();
+        let crashes = self.daemons.get(&service_id).map(|supervised| supervised.crashes);
+        self.daemons.insert(service_id, Supervised {
+            daemon,
+            handle: Some(handle),
+            started: now,
+            last_seen: now,
+            crashes: crashes.unwrap_or_default(),
+            restart_at: None,
+            silent: true,
+        });
+    }
+
+    /// Records message received from the daemon, returning whether it has started responding
+    /// after the restart.
+    pub fn seen(&mut self, service_id: &ServiceId) -> bool {
+        match self.daemons.get_mut(service_id) {
+            Some(supervised) if supervised.handle.is_some() => {
+                supervised.last_seen = Instant::now();
+                let restarted = supervised.silent && supervised.crashes > 0;
+                supervised.silent = false;
+                restarted
+            }
+            _ => false,
+        }
+    }
+
+    /// Detects whether the daemon has crashed and has not responded since.
+    pub fn is_down(&self, service_id: &ServiceId) -> bool {
+        self.daemons
+            .get(service_id)
+            .map(|supervised| {
+                supervised.handle.is_none() || (supervised.silent && supervised.crashes > 0)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns nonce of the heartbeat which has to be sent to the running daemons, if it is due.
+    pub fn heartbeat_due(&mut self) -> Option<(u64, Vec<ServiceId>)> {
+        if self.daemons.is_empty() || self.last_heartbeat.elapsed() < DAEMON_HEARTBEAT_INTERVAL {
+            return None;
+        }
+        self.last_heartbeat = Instant::now();
+        self.nonce += 1;
+        let running = self
+            .daemons
+            .iter()
+            .filter(|(_, supervised)| supervised.handle.is_some())
+            .map(|(service_id, _)| service_id.clone())
+            .collect();
+        Some((self.nonce, running))
+    }
+
+    /// Returns running daemons which process has exited or which have not responded within the
+    /// liveness timeout, together with the reason.
+    pub fn failed(&mut self) -> Vec<(ServiceId, &'static str)> {
+        let mut failed = vec![];
+        for (service_id, supervised) in &mut self.daemons {
+            let handle = match &mut supervised.handle {
+                Some(handle) => handle,
+                None => continue,
+            };
+            if let DaemonHandle::Process(_, child) = handle {
+                if let Ok(Some(_)) = child.try_wait() {
+                    failed.push((service_id.clone(), "process has exited"));
+                    continue;
+                }
+            }
+            if supervised.last_seen.elapsed() > DAEMON_LIVENESS_TIMEOUT {
+                failed.push((service_id.clone(), "heartbeats are not answered"));
+            }
+        }
+        failed
+    }
+
+    /// Registers crash of the daemon, returning the delay after which it is restarted. Returns
+    /// `None` if the daemon is not supervised or its crash is already registered.
+    pub fn crashed(&mut self, service_id: &ServiceId) -> Option<Duration> {
+        let supervised = self.daemons.get_mut(service_id)?;
+        let handle = supervised.handle.take()?;
+        if let DaemonHandle::Process(_, mut child) = handle {
+            // Unresponsive daemon may still run, so it is stopped before the restart
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        if supervised.started.elapsed() > RESTART_STABLE_PERIOD {
+            supervised.crashes = 0;
+        }
+        Some(supervised.schedule_restart())
+    }
+
+    /// Returns crashed daemons which restart is due.
+    pub fn restarts_due(&mut self) -> Vec<Daemon> {
+        let now = Instant::now();
+        self.daemons
+            .values_mut()
+            .filter(|supervised| matches!(supervised.restart_at, Some(at) if at <= now))
+            .map(|supervised| {
+                supervised.restart_at = None;
+                supervised.daemon.clone()
+            })
+            .collect()
+    }
+
+    /// Schedules another restart of the daemon which has failed to launch.
+    pub fn launch_failed(&mut self, daemon: &Daemon) -> Option<Duration> {
+        let supervised = self.daemons.get_mut(&daemon.service_id()?)?;
+        Some(supervised.schedule_restart())
+    }
+}
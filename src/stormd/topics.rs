@@ -0,0 +1,84 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use internet2::addr::NodeId;
+use storm::{MesgId, StormApp};
+use storm_ext::TopicsDelta;
+use storm_rpc::TopicId;
+
+/// Topics known to the node, learned from the proposals and topic lists passing through it.
+///
+/// On the p2p wire topic ids are indistinguishable from message ids, so the registry is used to
+/// tell whether an accept or decline received from a remote peer refers to a topic.
+///
+/// Topics which the local apps have proposed, listed or accepted are also tracked separately, so
+/// they can be reconciled with the topics of the remote peers.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct Topics {
+    known: BTreeMap<StormApp, BTreeSet<TopicId>>,
+    local: BTreeMap<StormApp, BTreeSet<TopicId>>,
+    /// Topic syncs waiting for the list of topics of the remote peer
+    syncs: BTreeSet<(StormApp, NodeId)>,
+}
+
+impl Topics {
+    pub fn register(&mut self, app: StormApp, topic_id: TopicId) {
+        self.known.entry(app).or_default().insert(topic_id);
+    }
+
+    /// Registers topic which is kept by the local app.
+    pub fn register_local(&mut self, app: StormApp, topic_id: TopicId) {
+        self.register(app, topic_id);
+        self.local.entry(app).or_default().insert(topic_id);
+    }
+
+    /// Starts sync of the app topics with the remote peer.
+    pub fn start_sync(&mut self, app: StormApp, remote_id: NodeId) {
+        self.syncs.insert((app, remote_id));
+    }
+
+    /// Completes sync of the app topics with the remote peer, returning whether it was started.
+    pub fn finish_sync(&mut self, app: StormApp, remote_id: NodeId) -> bool {
+        self.syncs.remove(&(app, remote_id))
+    }
+
+    /// Completes sync of the app topics with the remote peer, if it was started, returning the
+    /// difference between the local topics and the topics of the peer.
+    pub fn reconcile(
+        &mut self,
+        app: StormApp,
+        remote_id: NodeId,
+        remote: &BTreeSet<TopicId>,
+    ) -> Option<TopicsDelta> {
+        if !self.finish_sync(app, remote_id) {
+            return None;
+        }
+        let local = self.local.get(&app).cloned().unwrap_or_default();
+        Some(TopicsDelta {
+            missing: remote.difference(&local).copied().collect(),
+            extra: local.difference(remote).copied().collect(),
+        })
+    }
+
+    /// Returns topics kept by the local apps.
+    pub fn locals(&self) -> &BTreeMap<StormApp, BTreeSet<TopicId>> { &self.local }
+
+    pub fn contains(&self, app: StormApp, topic_id: TopicId) -> bool {
+        self.known.get(&app).map_or(false, |topics| topics.contains(&topic_id))
+    }
+
+    /// Returns topic id if the id received from the wire refers to a known topic.
+    pub fn resolve(&self, app: StormApp, id: MesgId) -> Option<TopicId> {
+        let topic_id = TopicId::from_wire(id);
+        self.contains(app, topic_id).then(|| topic_id)
+    }
+}
@@ -0,0 +1,200 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use amplify::hex::ToHex;
+use bitcoin_hashes::{sha256, Hash, HashEngine, Hmac, HmacEngine};
+use internet2::addr::NodeId;
+use storm::{Mesg, MesgId, StormApp};
+use storm_rpc::TopicId;
+
+use super::popularity::unix_time;
+
+/// Number of attempts to deliver a message to the webhook before it is dropped.
+pub const WEBHOOK_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; each next retry waits twice as long.
+pub const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of deliveries waiting for the dispatcher thread, above which new messages are dropped.
+pub const WEBHOOK_QUEUE: usize = 1024;
+
+/// Name of the HTTP header carrying HMAC-SHA256 signature of the request body.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Storm-Signature";
+
+/// Webhook receiving messages of an app, optionally limited to a single topic.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct WebhookConfig {
+    pub app: StormApp,
+    /// Topic, which messages are posted to the webhook; all messages of the app are posted if
+    /// absent.
+    pub topic: Option<TopicId>,
+    /// URL of the webhook. Only plain HTTP URLs are supported.
+    pub url: String,
+}
+
+impl WebhookConfig {
+    fn matches(&self, app: StormApp, parent_id: MesgId) -> bool {
+        self.app == app && self.topic.map_or(true, |topic| topic.to_wire() == parent_id)
+    }
+}
+
+struct Delivery {
+    url: String,
+    body: String,
+}
+
+/// Posts JSON rendering of the messages received by the apps to the configured webhooks.
+///
+/// Requests are made by a background thread, so slow or failing webhooks do not block the node;
+/// failed requests are retried with exponential backoff up to [`WEBHOOK_ATTEMPTS`] times. When
+/// the webhooks can't keep up and [`WEBHOOK_QUEUE`] deliveries are waiting, new messages are
+/// dropped instead of growing the queue.
+#[derive(Debug)]
+pub struct Webhooks {
+    hooks: Vec<WebhookConfig>,
+    sender: Option<mpsc::SyncSender<Delivery>>,
+    dropped: u64,
+}
+
+impl Webhooks {
+    pub fn with(hooks: Vec<WebhookConfig>, secret: Option<String>) -> Self {
+        if hooks.is_empty() {
+            return Webhooks {
+                hooks,
+                sender: None,
+                dropped: 0,
+            };
+        }
+        let (sender, receiver) = mpsc::sync_channel::<Delivery>(WEBHOOK_QUEUE);
+        thread::spawn(move || {
+            for delivery in receiver {
+                deliver(&delivery, secret.as_deref());
+            }
+        });
+        Webhooks {
+            hooks,
+            sender: Some(sender),
+            dropped: 0,
+        }
+    }
+
+    /// Number of deliveries dropped due to the queue overflow since the node start.
+    pub fn dropped(&self) -> u64 { self.dropped }
+
+    /// Queues message received by the app for delivery to the matching webhooks, returning the
+    /// number of deliveries dropped since the queue is full.
+    pub fn dispatch(&mut self, app: StormApp, remote_id: NodeId, mesg: &Mesg) -> u64 {
+        let sender = match self.sender {
+            Some(ref sender) => sender,
+            None => return 0,
+        };
+        let mut dropped = 0;
+        let hooks = self.hooks.iter().filter(|hook| hook.matches(app, mesg.parent_id));
+        let mut body = None;
+        for hook in hooks {
+            let body = body.get_or_insert_with(|| render(app, remote_id, mesg)).clone();
+            let delivery = Delivery {
+                url: hook.url.clone(),
+                body,
+            };
+            match sender.try_send(delivery) {
+                Ok(()) => {}
+                Err(mpsc::TrySendError::Full(delivery)) => {
+                    warn!("Webhook queue is full, dropping message for {}", delivery.url);
+                    dropped += 1;
+                }
+                Err(mpsc::TrySendError::Disconnected(_)) => {
+                    error!("Webhook dispatcher thread has stopped");
+                }
+            }
+        }
+        self.dropped += dropped;
+        dropped
+    }
+}
+
+fn render(app: StormApp, remote_id: NodeId, mesg: &Mesg) -> String {
+    let container_ids =
+        mesg.container_ids.iter().map(|id| format!("\"{}\"", id)).collect::<Vec<_>>();
+    let fields = [
+        ("timestamp", unix_time().to_string()),
+        ("app", format!("\"{}\"", app)),
+        ("remote_id", format!("\"{}\"", remote_id)),
+        ("mesg_id", format!("\"{}\"", mesg.mesg_id())),
+        ("parent_id", format!("\"{}\"", mesg.parent_id)),
+        ("body", format!("\"{}\"", mesg.body.to_hex())),
+        ("container_ids", format!("[{}]", container_ids.join(","))),
+    ];
+    let fields =
+        fields.iter().map(|(name, value)| format!("\"{}\":{}", name, value)).collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn deliver(delivery: &Delivery, secret: Option<&str>) {
+    let signature = secret.map(|secret| {
+        let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+        engine.input(delivery.body.as_bytes());
+        Hmac::from_engine(engine).to_hex()
+    });
+    let mut delay = WEBHOOK_RETRY_DELAY;
+    for attempt in 1..=WEBHOOK_ATTEMPTS {
+        match post(&delivery.url, &delivery.body, signature.as_deref()) {
+            Ok(status) if (200..300).contains(&status) => return,
+            Ok(status) => warn!("Webhook {} replied with status {}", delivery.url, status),
+            Err(err) => warn!("Unable to post to webhook {}: {}", delivery.url, err),
+        }
+        if attempt < WEBHOOK_ATTEMPTS {
+            thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+    error!("Dropping message for webhook {} after {} attempts", delivery.url, WEBHOOK_ATTEMPTS);
+}
+
+/// Makes HTTP POST request, returning the response status code.
+fn post(url: &str, body: &str, signature: Option<&str>) -> Result<u16, io::Error> {
+    let rest = url.strip_prefix("http://").ok_or(io::ErrorKind::InvalidInput)?;
+    let (host, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') { host.to_owned() } else { format!("{}:80", host) };
+    let addr = addr.to_socket_addrs()?.next().ok_or(io::ErrorKind::NotFound)?;
+    let mut stream = TcpStream::connect_timeout(&addr, WEBHOOK_TIMEOUT)?;
+    stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+    stream.set_write_timeout(Some(WEBHOOK_TIMEOUT))?;
+    let mut request = format!(
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+        path,
+        host,
+        body.len()
+    );
+    if let Some(signature) = signature {
+        request.push_str(&format!("{}: sha256={}\r\n", WEBHOOK_SIGNATURE_HEADER, signature));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream.write_all(request.as_bytes())?;
+
+    // Status line is `HTTP/1.x <code> <reason>`
+    let mut head = [0u8; 12];
+    stream.read_exact(&mut head)?;
+    String::from_utf8_lossy(&head[9..12])
+        .parse()
+        .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))
+}
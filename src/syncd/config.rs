@@ -0,0 +1,50 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use internet2::addr::NodeId;
+
+#[cfg(feature = "server")]
+use super::Opts;
+#[cfg(feature = "server")]
+use crate::opts::Options;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Config {
+    /// Local directories of the replicated folders by the folder names
+    pub folders: BTreeMap<String, PathBuf>,
+    /// Storm peer the folders are replicated with
+    pub peer: NodeId,
+    /// Interval between the scans of the local directories, in seconds
+    pub scan_interval: u64,
+}
+
+#[cfg(feature = "server")]
+impl Options for Opts {
+    type Conf = Config;
+
+    fn shared(&self) -> &crate::opts::Opts { &self.shared }
+
+    fn config(&self) -> Self::Conf {
+        Config {
+            folders: self
+                .folders
+                .iter()
+                .map(|spec| {
+                    let (name, dir) = spec.split_once('=').expect("folder is validated by clap");
+                    (name.to_owned(), PathBuf::from(dir))
+                })
+                .collect(),
+            peer: self.peer,
+            scan_interval: self.scan_interval,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use std::path::{Component, Path, PathBuf};
+
+use storm::{ContainerHeader, ContainerId, Topic};
+use storm_rpc::TopicId;
+use strict_encoding::StrictEncode;
+
+/// Message body exchanged between the folder sync daemons of the peers. The messages are posted
+/// with the id of the folder topic as their parent id.
+///
+/// Concurrent changes of the same file are resolved in favour of the latest one.
+#[derive(Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub enum SyncMsg {
+    /// File was created or changed; its content is in the container attached to the message.
+    Changed {
+        path: String,
+        container_id: ContainerId,
+        modified: u64,
+    },
+
+    Removed {
+        path: String,
+        modified: u64,
+    },
+}
+
+/// Returns the private topic of the folder.
+pub fn folder_topic(name: &str) -> Result<TopicId, strict_encoding::Error> {
+    let topic = Topic {
+        body: name.strict_serialize()?,
+        container_ids: empty!(),
+    };
+    Ok(TopicId::from(&topic))
+}
+
+/// Resolves path received from the peer inside the folder directory, returning `None` if the
+/// path may point outside of the directory.
+pub fn local_path(dir: &Path, path: &str) -> Option<PathBuf> {
+    let rel = Path::new(path);
+    if path.is_empty() || !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(dir.join(rel))
+}
+
+/// Header of the container keeping content of the file at the `path` inside the folder.
+pub fn file_header(path: &str, size: usize) -> ContainerHeader {
+    ContainerHeader {
+        version: 0,
+        mime: "application/octet-stream".try_into().expect("static ASCII string"),
+        info: path.to_owned(),
+        size: size as u64,
+    }
+}
@@ -0,0 +1,104 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+use storm::ContainerId;
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use crate::stormd::checkpoint;
+
+/// Name of the file inside the data directory keeping the index of the replicated folders.
+pub const STORM_NODE_SYNC_INDEX_FILE: &str = "sync_index.dat";
+
+/// Index of the replicated folders shared between the daemon and the directory watcher.
+pub type SharedIndex = Arc<Mutex<FolderIndex>>;
+
+/// State of a replicated file.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, StrictEncode, StrictDecode)]
+pub struct FileState {
+    /// Size of the file on disk
+    pub size: u64,
+    /// Modification time of the file on disk, in nanoseconds since the UNIX epoch, which is used
+    /// together with the size to detect the local changes
+    pub mtime: u64,
+    /// UNIX timestamp of the change, ordering concurrent changes of the file between the peers
+    pub modified: u64,
+    pub container_id: ContainerId,
+}
+
+impl FileState {
+    /// Returns size and modification time of the file on disk.
+    pub fn stat(path: &Path) -> std::io::Result<(u64, u64)> {
+        let meta = fs::metadata(path)?;
+        let mtime = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_nanos() as u64)
+            .unwrap_or_default();
+        Ok((meta.len(), mtime))
+    }
+}
+
+/// Files of the replicated folders by the folder names and the file paths.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FolderIndex {
+    path: PathBuf,
+    files: BTreeMap<String, BTreeMap<String, FileState>>,
+}
+
+impl FolderIndex {
+    pub fn load(data_dir: &Path) -> Result<Self, strict_encoding::Error> {
+        let path = data_dir.join(STORM_NODE_SYNC_INDEX_FILE);
+        let files =
+            if path.exists() { BTreeMap::strict_decode(fs::File::open(&path)?)? } else { empty!() };
+        Ok(FolderIndex { path, files })
+    }
+
+    fn save(&self) -> Result<(), strict_encoding::Error> {
+        checkpoint(&self.path, &self.files)?;
+        Ok(())
+    }
+
+    pub fn get(&self, folder: &str, path: &str) -> Option<FileState> {
+        self.files.get(folder).and_then(|files| files.get(path)).copied()
+    }
+
+    pub fn files(&self, folder: &str) -> impl Iterator<Item = (&String, &FileState)> + '_ {
+        self.files.get(folder).into_iter().flatten()
+    }
+
+    /// Checks whether the container keeps content of any of the replicated files.
+    pub fn contains(&self, container_id: ContainerId) -> bool {
+        self.files.values().flat_map(BTreeMap::values).any(|file| file.container_id == container_id)
+    }
+
+    pub fn insert(
+        &mut self,
+        folder: &str,
+        path: &str,
+        file: FileState,
+    ) -> Result<(), strict_encoding::Error> {
+        self.files.entry(folder.to_owned()).or_default().insert(path.to_owned(), file);
+        self.save()
+    }
+
+    pub fn remove(&mut self, folder: &str, path: &str) -> Result<(), strict_encoding::Error> {
+        if let Some(files) = self.files.get_mut(folder) {
+            if files.remove(path).is_some() {
+                self.save()?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use clap::Parser;
+use internet2::addr::NodeId;
+
+use crate::opts::Opts as SharedOpts;
+
+/// Command-line arguments
+#[derive(Parser)]
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[clap(author, version, name = "syncd", about = "Storm node folder sync service")]
+pub struct Opts {
+    /// These params can be read also from the configuration file, not just
+    /// command-line args or environment variables
+    #[clap(flatten)]
+    pub shared: SharedOpts,
+
+    /// Replicate local directory, in `NAME=DIR` format.
+    ///
+    /// Folders are matched between the nodes by their names. The argument can be given multiple
+    /// times.
+    #[clap(long = "folder", required = true, multiple_occurrences = true, validator = validate_folder)]
+    pub folders: Vec<String>,
+
+    /// Storm peer the folders are replicated with.
+    #[clap(long)]
+    pub peer: NodeId,
+
+    /// Interval between the scans of the local directories for changes, in seconds.
+    #[clap(long, default_value = "10")]
+    pub scan_interval: u64,
+}
+
+fn validate_folder(spec: &str) -> Result<(), String> {
+    let (name, dir) = spec.split_once('=').ok_or("folder must be given as `NAME=DIR`")?;
+    if name.is_empty() {
+        return Err("folder name must not be empty".to_owned());
+    }
+    if dir.is_empty() {
+        return Err(format!("no directory is given for folder `{}`", name));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl Opts {
+    pub fn process(&mut self) { self.shared.process(); }
+}
@@ -0,0 +1,420 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{fs, thread};
+
+use internet2::addr::NodeId;
+use internet2::ZmqSocketType;
+use microservices::error::BootstrapError;
+use microservices::esb::{self, ClientId, EndpointList, Error};
+use microservices::node::TryService;
+use storm::{ContainerFullId, ContainerId, Mesg, MesgId};
+use storm_ext::ExtMsg;
+use storm_rpc::{AddressedMsg, FolderChange, RpcMsg, ServiceId, TopicId};
+use strict_encoding::{StrictDecode, StrictEncode};
+
+use super::folder::{folder_topic, local_path};
+use super::watcher::SYNC_TMP_SUFFIX;
+use super::{watch, FileState, FolderIndex, SharedIndex, SyncMsg};
+use crate::bus::{BusMsg, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::containers::assemble;
+use crate::{Config, DaemonError, LaunchError};
+
+pub fn run(config: Config<super::Config>) -> Result<(), BootstrapError<LaunchError>> {
+    let rpc_endpoint = config.rpc_endpoint.clone();
+    let ctl_endpoint = config.ctl_endpoint.clone();
+    let ext_endpoint = config.ext_endpoint.clone();
+    let runtime = Runtime::init(config)?;
+
+    debug!("Connecting to service buses {}, {}, {}", rpc_endpoint, ctl_endpoint, ext_endpoint);
+    let controller = esb::Controller::with(
+        map! {
+            ServiceBus::Storm => esb::BusConfig::with_addr(
+                ext_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Rpc => esb::BusConfig::with_addr(
+                rpc_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            ),
+            ServiceBus::Ctl => esb::BusConfig::with_addr(
+                ctl_endpoint,
+                ZmqSocketType::RouterConnect,
+                Some(ServiceId::stormd())
+            )
+        },
+        runtime,
+    )
+    .map_err(|_| LaunchError::BusSetupFailure)?;
+
+    controller.run_or_panic("syncd");
+
+    unreachable!()
+}
+
+/// Replicates the local directories with the directories of the same-named folders on the peer.
+///
+/// Changes of the local files are detected by a background thread scanning the directories,
+/// which saves the new file content into the store and passes the change to the daemon. The
+/// daemon announces the change to the peer on the private topic of the folder; the peer
+/// retrieves the container with the file content and writes it into its directory.
+pub struct Runtime {
+    peer: NodeId,
+    /// Folder names and directories by the ids of the folder topics
+    folders: BTreeMap<TopicId, (String, PathBuf)>,
+    index: SharedIndex,
+    store: store_rpc::Client,
+    /// Remote changes which containers are being retrieved, by the folder and file path
+    pending: BTreeMap<(TopicId, String), (ContainerId, u64)>,
+}
+
+impl Runtime {
+    pub fn init(config: Config<super::Config>) -> Result<Self, BootstrapError<LaunchError>> {
+        let store = store_rpc::Client::with(&config.store_endpoint).map_err(LaunchError::from)?;
+        let index = FolderIndex::load(&config.data_dir).map_err(LaunchError::from)?;
+        let index = Arc::new(Mutex::new(index));
+
+        let ext = config.ext;
+        let mut folders = bmap! {};
+        for (name, dir) in &ext.folders {
+            let topic = folder_topic(name).map_err(LaunchError::from)?;
+            debug!("Folder {} in {} is synced on topic {}", name, dir.display(), topic);
+            folders.insert(topic, (name.clone(), dir.clone()));
+        }
+
+        let watcher_index = index.clone();
+        let interval = Duration::from_secs(ext.scan_interval);
+        let store_endpoint = config.store_endpoint.clone();
+        let rpc_endpoint = config.rpc_endpoint.clone();
+        let chat_endpoint = config.chat_endpoint.clone();
+        thread::spawn(move || {
+            watch(ext.folders, interval, watcher_index, store_endpoint, rpc_endpoint, chat_endpoint)
+        });
+
+        info!("Folder sync runtime started successfully");
+
+        Ok(Self {
+            peer: ext.peer,
+            folders,
+            index,
+            store,
+            pending: empty!(),
+        })
+    }
+}
+
+impl Responder for Runtime {}
+
+impl esb::Handler<ServiceBus> for Runtime {
+    type Request = BusMsg;
+    type Error = DaemonError;
+
+    fn identity(&self) -> ServiceId { ServiceId::syncd() }
+
+    fn on_ready(&mut self, endpoints: &mut EndpointList<ServiceBus>) -> Result<(), Self::Error> {
+        thread::sleep(Duration::from_millis(100));
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Hello)?;
+        // Announcing the known files lets the peer catch up with the changes made while it was
+        // offline; the files which are already in sync are ignored by the peer
+        let announces = {
+            let index = self.index.lock().expect("folder index lock is poisoned");
+            self.folders
+                .iter()
+                .flat_map(|(topic, (name, _))| {
+                    index.files(name).map(move |(path, file)| {
+                        (*topic, SyncMsg::Changed {
+                            path: path.clone(),
+                            container_id: file.container_id,
+                            modified: file.modified,
+                        })
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        for (topic, msg) in announces {
+            self.post(endpoints, topic, msg)?;
+        }
+        Ok(())
+    }
+
+    fn handle(
+        &mut self,
+        endpoints: &mut EndpointList<ServiceBus>,
+        bus_id: ServiceBus,
+        source: ServiceId,
+        request: Self::Request,
+    ) -> Result<(), Self::Error> {
+        match (bus_id, request, source) {
+            (ServiceBus::Storm, BusMsg::Storm(msg), service_id)
+                if service_id == ServiceId::stormd() =>
+            {
+                self.handle_storm(endpoints, msg)
+            }
+            (ServiceBus::Rpc, BusMsg::Rpc(msg), ServiceId::Client(client_id)) => {
+                self.handle_rpc(endpoints, client_id, msg)
+            }
+            (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
+            (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        }
+    }
+
+    fn handle_err(
+        &mut self,
+        _endpoints: &mut EndpointList<ServiceBus>,
+        _error: Error<ServiceId>,
+    ) -> Result<(), Self::Error> {
+        // We do nothing and do not propagate error; it's already being reported
+        // with `error!` macro by the controller. If we propagate error here
+        // this will make whole daemon panic
+        Ok(())
+    }
+}
+
+impl Runtime {
+    fn handle_storm(
+        &mut self,
+        endpoints: &mut Endpoints,
+        message: ExtMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            ExtMsg::Post(AddressedMsg { remote_id, .. }) if remote_id != self.peer => {
+                warn!("Ignoring folder change from unknown peer {}", remote_id);
+            }
+
+            ExtMsg::Post(AddressedMsg { remote_id, data }) => {
+                let topic = TopicId::from_wire(data.parent_id);
+                if !self.folders.contains_key(&topic) {
+                    warn!("Ignoring change of unknown folder {} from {}", topic, remote_id);
+                    return Ok(());
+                }
+                let msg = match SyncMsg::strict_deserialize(&data.body) {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        warn!("Invalid folder change from {}: {}", remote_id, err);
+                        return Ok(());
+                    }
+                };
+                self.handle_change(endpoints, data.mesg_id(), topic, msg)?;
+            }
+
+            ExtMsg::RetrieveContainer(AddressedMsg { remote_id, data }) => {
+                let known = self
+                    .index
+                    .lock()
+                    .expect("folder index lock is poisoned")
+                    .contains(data.container_id);
+                if remote_id == self.peer && known {
+                    self.send_ext(
+                        endpoints,
+                        None,
+                        ExtMsg::SendContainer(AddressedMsg { remote_id, data }),
+                    )?;
+                } else {
+                    warn!(
+                        "Peer {} requests container {} of no synced file",
+                        remote_id, data.container_id
+                    );
+                }
+            }
+
+            ExtMsg::ContainerRetrieved(container_id) => self.apply(container_id)?,
+
+            ExtMsg::Negotiate(_) => {}
+
+            wrong_msg => {
+                error!("Request is not supported by the Storm interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Storm, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_change(
+        &mut self,
+        endpoints: &mut Endpoints,
+        mesg_id: MesgId,
+        topic: TopicId,
+        msg: SyncMsg,
+    ) -> Result<(), DaemonError> {
+        let (name, dir) = self.folders[&topic].clone();
+        let path = match &msg {
+            SyncMsg::Changed { path, .. } | SyncMsg::Removed { path, .. } => path.clone(),
+        };
+        let local = match local_path(&dir, &path) {
+            Some(local) => local,
+            None => {
+                warn!("Ignoring change of file {} outside of folder {}", path, name);
+                return Ok(());
+            }
+        };
+
+        let mut index = self.index.lock().expect("folder index lock is poisoned");
+        let known = index.get(&name, &path);
+        match msg {
+            SyncMsg::Changed {
+                container_id,
+                modified,
+                ..
+            } => {
+                match known {
+                    Some(file) if file.container_id == container_id => return Ok(()),
+                    Some(file) if file.modified > modified => {
+                        debug!("Local change of {}/{} is newer than the remote one", name, path);
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+                drop(index);
+                debug!("Retrieving content of {}/{} from the peer", name, path);
+                self.pending.insert((topic, path), (container_id, modified));
+                let request = ExtMsg::RetrieveContainer(AddressedMsg {
+                    remote_id: self.peer,
+                    data: ContainerFullId {
+                        message_id: mesg_id,
+                        container_id,
+                    },
+                });
+                self.send_ext(endpoints, None, request)?;
+            }
+
+            SyncMsg::Removed { modified, .. } => {
+                self.pending.remove(&(topic, path.clone()));
+                match known {
+                    Some(file) if file.modified <= modified => {
+                        info!("Removing {}/{} after the remote change", name, path);
+                        if let Err(err) = fs::remove_file(&local) {
+                            warn!("Unable to remove {}: {}", local.display(), err);
+                        }
+                        index.remove(&name, &path)?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_rpc(
+        &mut self,
+        endpoints: &mut Endpoints,
+        client_id: ClientId,
+        message: RpcMsg,
+    ) -> Result<(), DaemonError> {
+        match message {
+            RpcMsg::FolderChanged(FolderChange {
+                folder,
+                path,
+                container_id,
+                modified,
+            }) => {
+                let topic = folder_topic(&folder)?;
+                let msg = match container_id {
+                    Some(container_id) => SyncMsg::Changed {
+                        path,
+                        container_id,
+                        modified,
+                    },
+                    None => SyncMsg::Removed { path, modified },
+                };
+                self.post(endpoints, topic, msg)?;
+                self.send_rpc(endpoints, client_id, RpcMsg::Success(None.into()))?;
+            }
+
+            wrong_msg => {
+                error!("Request is not supported by the RPC interface");
+                return Err(DaemonError::wrong_esb_msg(ServiceBus::Rpc, &wrong_msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_ctl(
+        &mut self,
+        _endpoints: &mut Endpoints,
+        _source: ServiceId,
+        message: CtlMsg,
+    ) -> Result<(), DaemonError> {
+        error!("Request is not supported by the CTL interface");
+        Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &message))
+    }
+
+    /// Writes retrieved content into the files which are changed by the peer.
+    fn apply(&mut self, container_id: ContainerId) -> Result<(), DaemonError> {
+        let files = self
+            .pending
+            .iter()
+            .filter(|(_, (id, _))| *id == container_id)
+            .map(|(key, (_, modified))| (key.clone(), *modified))
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            return Ok(());
+        }
+        let data = assemble(&mut self.store, container_id)?;
+        for ((topic, path), modified) in files {
+            self.pending.remove(&(topic, path.clone()));
+            let (name, dir) = &self.folders[&topic];
+            let local =
+                local_path(dir, &path).expect("path is checked when the change is received");
+            // The index is locked while the file is written, so the watcher does not take the
+            // remote change for a local one
+            let mut index = self.index.lock().expect("folder index lock is poisoned");
+            let tmp = local.with_file_name(format!(
+                ".{}{}",
+                local.file_name().and_then(|name| name.to_str()).unwrap_or_default(),
+                SYNC_TMP_SUFFIX
+            ));
+            if let Some(parent) = local.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&tmp, &data)?;
+            fs::rename(&tmp, &local)?;
+            let (size, mtime) = FileState::stat(&local)?;
+            index.insert(name, &path, FileState {
+                size,
+                mtime,
+                modified,
+                container_id,
+            })?;
+            info!("Updated {}/{} after the remote change", name, path);
+        }
+        Ok(())
+    }
+
+    fn post(
+        &mut self,
+        endpoints: &mut Endpoints,
+        topic: TopicId,
+        msg: SyncMsg,
+    ) -> Result<(), DaemonError> {
+        let container_ids = match msg {
+            SyncMsg::Changed { container_id, .. } => vec![container_id],
+            SyncMsg::Removed { .. } => vec![],
+        };
+        let addressed_msg = AddressedMsg {
+            remote_id: self.peer,
+            data: Mesg {
+                parent_id: topic.to_wire(),
+                container_ids,
+                body: msg.strict_serialize()?,
+            },
+        };
+        self.send_ext(endpoints, None, ExtMsg::Post(addressed_msg))?;
+        Ok(())
+    }
+}
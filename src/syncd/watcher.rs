@@ -0,0 +1,158 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, thread};
+
+use internet2::addr::ServiceAddr;
+use storm_rpc::FolderChange;
+
+use super::folder::file_header;
+use super::{FileState, SharedIndex};
+use crate::containers::containerize;
+use crate::stormd::unix_time;
+use crate::DaemonError;
+
+/// Suffix of the temporary files written while the remote changes are applied, which are not
+/// replicated.
+pub const SYNC_TMP_SUFFIX: &str = ".syncd-tmp";
+
+/// Periodically scans the folder directories, saving content of the changed files into the store
+/// and passing the changes to the folder sync daemon.
+pub fn watch(
+    folders: BTreeMap<String, PathBuf>,
+    interval: Duration,
+    index: SharedIndex,
+    store: ServiceAddr,
+    rpc: ServiceAddr,
+    chat: ServiceAddr,
+) {
+    let mut clients = None;
+    loop {
+        if clients.is_none() {
+            let connected =
+                store_rpc::Client::with(&store).map_err(|err| err.to_string()).and_then(|store| {
+                    storm_rpc::Client::with(rpc.clone(), chat.clone(), s!("syncd"))
+                        .map(|client| (store, client))
+                        .map_err(|err| err.to_string())
+                });
+            match connected {
+                Ok(connected) => clients = Some(connected),
+                Err(err) => warn!("Unable to connect to the node services: {}", err),
+            }
+        }
+        if let Some((store, client)) = clients.as_mut() {
+            for (folder, dir) in &folders {
+                if let Err(err) = scan(folder, dir, &index, store, client) {
+                    warn!("Unable to sync folder {}: {}", folder, err);
+                }
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn scan(
+    folder: &str,
+    dir: &Path,
+    index: &SharedIndex,
+    store: &mut store_rpc::Client,
+    client: &mut storm_rpc::Client,
+) -> Result<(), DaemonError> {
+    let mut found = BTreeMap::new();
+    list(dir, "", &mut found)?;
+    let (changed, removed) = {
+        let index = index.lock().expect("folder index lock is poisoned");
+        let changed = found
+            .iter()
+            .filter(|(path, stat)| {
+                index.get(folder, path).map(|file| (file.size, file.mtime)) != Some(**stat)
+            })
+            .map(|(path, stat)| (path.clone(), *stat))
+            .collect::<Vec<_>>();
+        let removed = index
+            .files(folder)
+            .map(|(path, _)| path)
+            .filter(|path| !found.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        (changed, removed)
+    };
+
+    for (path, (size, mtime)) in changed {
+        let data = fs::read(dir.join(&path))?;
+        let container_id = containerize(store, file_header(&path, data.len()), &data)?;
+        let modified = mtime / 1_000_000_000;
+        let change = FolderChange {
+            folder: folder.to_owned(),
+            path: path.clone(),
+            container_id: Some(container_id),
+            modified,
+        };
+        debug!("Local change {}", change);
+        if let Err(err) = client.folder_changed(change) {
+            // The change is detected once again during the next scan
+            warn!("Unable to announce change of {}/{}: {}", folder, path, err);
+            continue;
+        }
+        let file = FileState {
+            size,
+            mtime,
+            modified,
+            container_id,
+        };
+        index.lock().expect("folder index lock is poisoned").insert(folder, &path, file)?;
+    }
+
+    for path in removed {
+        let change = FolderChange {
+            folder: folder.to_owned(),
+            path: path.clone(),
+            container_id: None,
+            modified: unix_time(),
+        };
+        debug!("Local change {}", change);
+        if let Err(err) = client.folder_changed(change) {
+            warn!("Unable to announce removal of {}/{}: {}", folder, path, err);
+            continue;
+        }
+        index.lock().expect("folder index lock is poisoned").remove(folder, &path)?;
+    }
+
+    Ok(())
+}
+
+/// Lists regular files in the directory recursively, together with their sizes and modification
+/// times. Symbolic links and files with non-UTF-8 names are not replicated.
+fn list(
+    dir: &Path,
+    prefix: &str,
+    found: &mut BTreeMap<String, (u64, u64)>,
+) -> Result<(), DaemonError> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let kind = entry.file_type()?;
+        let name = match entry.file_name().into_string() {
+            Ok(name) => name,
+            Err(name) => {
+                debug!("Skipping file with non-UTF-8 name {:?}", name);
+                continue;
+            }
+        };
+        let path = if prefix.is_empty() { name.clone() } else { format!("{}/{}", prefix, name) };
+        if kind.is_dir() {
+            list(&entry.path(), &path, found)?;
+        } else if kind.is_file() && !name.ends_with(SYNC_TMP_SUFFIX) {
+            found.insert(path, FileState::stat(&entry.path())?);
+        }
+    }
+    Ok(())
+}
@@ -9,115 +9,30 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use std::collections::BTreeSet;
-use std::fmt::Debug;
 
 use internet2::addr::NodeId;
 use microservices::esb::ClientId;
+use microservices::rpc;
 use storm::p2p::{ChunkPull, ChunkPush};
 use storm::{
     p2p, Chunk, ChunkId, Container, ContainerFullId, ContainerHeader, ContainerId, ContainerInfo,
     StormApp,
 };
 use storm_rpc::{
-    RpcMsg, ServiceId, DB_TABLE_CHUNKS, DB_TABLE_CONTAINERS, DB_TABLE_CONTAINER_HEADERS,
+    AddressedMsg, FailureCode, RpcMsg, ServiceId, DB_TABLE_CONTAINERS, DB_TABLE_CONTAINER_HEADERS,
+    DB_TABLE_CONTAINER_VERSIONS,
 };
 use strict_encoding::{StrictDecode, StrictEncode};
 
+use super::manifests::{missing_page, MANIFEST_PAGE_SIZE};
+use super::state::{Info, Phase, State};
 use super::Runtime;
-use crate::bus::{CtlMsg, Endpoints, Responder};
+use crate::bus::{ChunkReceived, CtlMsg, Endpoints, Responder, ServiceBus};
+use crate::containers::{missing_chunks, retrieve_chunk, store_chunk};
 use crate::DaemonError;
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
-#[display(doc_comments)]
-pub enum AutomationError {
-    /// the service is required to be in a {expected} state, while its state is {found}
-    InvalidState {
-        expected: StateName,
-        found: StateName,
-    },
-}
-
-pub type StateName = StateTy<ReceiveStateName>;
-pub type State = StateTy<ReceiveState>;
-
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
-#[display(Debug)]
-pub enum StateTy<R>
-where R: Debug
-{
-    Free,
-    Receive(R),
-}
-
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
-#[display(Debug)]
-pub enum ReceiveStateName {
-    AwaitingContainer,
-    ReceivingChunks,
-}
-
-#[derive(Debug)]
-pub enum ReceiveState {
-    AwaitingContainer {
-        info: Info,
-    },
-    ReceivingChunks {
-        info: Info,
-        total: usize,
-        pending: BTreeSet<ChunkId>,
-    },
-}
-
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
-pub struct Info {
-    pub app_id: StormApp,
-    pub client_id: Option<ClientId>,
-    pub remote_id: NodeId,
-    pub id: ContainerFullId,
-}
-
-impl ReceiveState {
-    pub fn state_name(&self) -> ReceiveStateName {
-        match self {
-            ReceiveState::AwaitingContainer { .. } => ReceiveStateName::AwaitingContainer,
-            ReceiveState::ReceivingChunks { .. } => ReceiveStateName::ReceivingChunks,
-        }
-    }
-
-    pub fn info(&self) -> Info {
-        match self {
-            ReceiveState::AwaitingContainer { info }
-            | ReceiveState::ReceivingChunks { info, .. } => *info,
-        }
-    }
-}
-
-impl State {
-    pub fn state_name(&self) -> StateName {
-        match self {
-            State::Receive(recv) => StateName::Receive(recv.state_name()),
-            State::Free => StateTy::Free,
-        }
-    }
-
-    pub fn info(&self) -> Option<Info> {
-        match self {
-            State::Free => None,
-            State::Receive(receive) => Some(receive.info()),
-        }
-    }
-
-    pub fn require_state(&self, expected: StateName) -> Result<(), AutomationError> {
-        if self.state_name() != expected {
-            Err(AutomationError::InvalidState {
-                expected: StateName::Free,
-                found: self.state_name(),
-            })
-        } else {
-            Ok(())
-        }
-    }
-}
+/// Number of corrupt chunks the remote peer may send during a transfer before it is failed.
+pub const CORRUPT_CHUNK_LIMIT: u16 = 16;
 
 // Receive workflow
 impl Runtime {
@@ -129,20 +44,25 @@ impl Runtime {
         storm_app: StormApp,
         remote_id: NodeId,
         id: ContainerFullId,
+        metadata_only: bool,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Free)?;
-
-        debug!("Receiving container {}", id.container_id);
+        debug!(
+            "Receiving {} {}",
+            if metadata_only { "metadata of container" } else { "container" },
+            id.container_id
+        );
 
-        // Switching the state
-        self.state = State::Receive(ReceiveState::AwaitingContainer {
+        self.corrupt_chunks = 0;
+        self.state.transition(State::Negotiating {
             info: Info {
                 app_id: storm_app,
                 client_id,
                 remote_id,
                 id,
+                base: None,
+                metadata_only,
             },
-        });
+        })?;
 
         // Request the remote peer container data
         let msg = p2p::AppMsg {
@@ -165,8 +85,8 @@ impl Runtime {
         endpoints: &mut Endpoints,
         container: Container,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Receive(ReceiveStateName::AwaitingContainer))?;
-        let info = self.state.info().expect("receive state always have metadata");
+        self.state.require_phase(Phase::Negotiating)?;
+        let mut info = self.state.info().expect("negotiating state always have metadata");
 
         debug!("Processing container info for {}", info.id.container_id);
 
@@ -181,39 +101,190 @@ impl Runtime {
         self.store.store(DB_TABLE_CONTAINER_HEADERS, id, &header_chunk)?;
         self.store.store(DB_TABLE_CONTAINERS, id, &container_chunk)?;
 
-        // Prepare list of missed chunks
-        let chunk_ids = self
-            .store
-            .filter_unknown(DB_TABLE_CHUNKS, container.chunks.iter().copied().collect())?;
-        let unknown_count = chunk_ids.len();
+        let manifest = self.manifests.insert(container);
+        if info.metadata_only {
+            // Chunks are retrieved once the container is read or materialized
+            self.state.transition(State::Complete { info })?;
+            info!("Transfer service completed retrieval of container {} metadata", id);
+            self.state.transition(State::Idle)?;
+            self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ProcessingComplete)?;
+            return Ok(());
+        }
+        let (cursor, queued, progress) = match self.delta(&manifest)? {
+            // Only the chunks which differ from the previous version are retrieved
+            Some((base, differ)) => {
+                info!(
+                    "Container {} differs from its base {} by {} of {} chunks",
+                    id,
+                    base,
+                    differ.len(),
+                    manifest.chunks.len()
+                );
+                info.base = Some(base);
+                let progress = format!(
+                    "Retrieving {} chunks which differ from {} of {} total",
+                    differ.len(),
+                    base,
+                    manifest.chunks.len()
+                );
+                (manifest.chunks.len(), differ, progress)
+            }
+            // Missed chunks are detected lazily, page by page of the container manifest
+            None => {
+                let progress =
+                    format!("Retrieving missed chunks of {} total", manifest.chunks.len());
+                (0, empty!(), progress)
+            }
+        };
         if let Some(client_id) = info.client_id {
-            self.send_rpc(
-                endpoints,
-                client_id,
-                RpcMsg::Progress(format!("Retrieving {} new chunks", unknown_count).into()),
-            )?;
+            self.send_rpc(endpoints, client_id, RpcMsg::Progress(progress))?;
+        }
+
+        self.state.transition(State::Transferring {
+            info,
+            manifest,
+            cursor,
+            pending: empty!(),
+            queued,
+        })?;
+
+        self.request_chunks(endpoints)
+    }
+
+    /// Detects chunks of the container which differ from its declared previous version and are not
+    /// present in the store. Returns `None` if no previous version is declared or if its manifest
+    /// is missing, in which case the container is retrieved in full.
+    fn delta(
+        &mut self,
+        manifest: &Container,
+    ) -> Result<Option<(ContainerId, BTreeSet<ChunkId>)>, DaemonError> {
+        let container_id = manifest.container_id();
+        let base = match self.store.retrieve_chunk(DB_TABLE_CONTAINER_VERSIONS, container_id)? {
+            Some(chunk) => ContainerId::strict_deserialize(chunk)?,
+            None => return Ok(None),
+        };
+        let base_manifest = match self.manifests.get(&mut self.store, base) {
+            Ok(base_manifest) => base_manifest,
+            Err(DaemonError::UnknownContainer(_)) => {
+                debug!("Base {} of {} is not known; retrieving it in full", base, container_id);
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        let known = base_manifest.chunks.iter().collect::<BTreeSet<_>>();
+        let differ =
+            manifest.chunks.iter().filter(|chunk_id| !known.contains(chunk_id)).collect::<Vec<_>>();
+        let mut missed = BTreeSet::new();
+        for page in differ.chunks(MANIFEST_PAGE_SIZE) {
+            let page = page.iter().copied().copied().collect();
+            missed.extend(missing_chunks(&mut self.store, container_id, page)?);
+        }
+        Ok(Some((base, missed)))
+    }
+
+    /// Requests next batch of chunks, sized according to the window of the remote peer, or
+    /// proceeds to the verification if all chunks were received.
+    fn request_chunks(&mut self, endpoints: &mut Endpoints) -> Result<(), DaemonError> {
+        loop {
+            let (manifest, cursor) = match &self.state {
+                State::Transferring {
+                    manifest,
+                    cursor,
+                    queued,
+                    ..
+                } if queued.is_empty() && *cursor < manifest.chunks.len() => {
+                    (manifest.clone(), *cursor)
+                }
+                _ => break,
+            };
+            let (missed, next) = missing_page(&mut self.store, &manifest, cursor)?;
+            if let State::Transferring { cursor, queued, .. } = &mut self.state {
+                *queued = missed;
+                *cursor = next;
+            }
         }
 
+        let (info, queued) = match &mut self.state {
+            State::Transferring {
+                info,
+                pending,
+                queued,
+                ..
+            } if pending.is_empty() => (*info, queued),
+            _ => unreachable!(),
+        };
+
+        if queued.is_empty() {
+            self.state.transition(State::Verifying { info })?;
+            return self.verify(endpoints);
+        }
+
+        let window = self.windows.entry(info.remote_id).or_default();
+        let chunk_ids = queued.iter().take(window.size()).copied().collect::<BTreeSet<_>>();
+        queued.retain(|chunk_id| !chunk_ids.contains(chunk_id));
+        window.start_batch();
+
         debug!("Requesting {} chunks", chunk_ids.len());
         trace!("Requested chunk ids: {:?}", chunk_ids);
 
-        // Switching the state
-        self.state = State::Receive(ReceiveState::ReceivingChunks {
-            info,
-            total: unknown_count,
-            pending: chunk_ids.clone(),
-        });
+        if let State::Transferring { pending, .. } = &mut self.state {
+            *pending = chunk_ids.clone();
+        }
 
-        self.send_p2p(
-            endpoints,
-            info.remote_id,
-            p2p::Messages::PullChunk(ChunkPull {
-                app: info.app_id,
-                message_id: info.id.message_id,
-                container_id: info.id.container_id,
-                chunk_ids: chunk_ids.clone(),
-            }),
-        )?;
+        self.pull_chunks(endpoints, info, chunk_ids)
+    }
+
+    /// Requests chunks striping them across the paths to the remote peer. If a request can't be
+    /// sent through one of the paths, the path is dropped and its chunks are requested through
+    /// the remaining ones.
+    fn pull_chunks(
+        &mut self,
+        endpoints: &mut Endpoints,
+        info: Info,
+        chunk_ids: BTreeSet<ChunkId>,
+    ) -> Result<(), DaemonError> {
+        let mut paths = match self.paths.get(&info.remote_id) {
+            Some(paths) if !paths.is_empty() => paths.clone(),
+            _ => vec![ServiceBus::Msg],
+        };
+        let mut unsent = chunk_ids;
+        while !unsent.is_empty() {
+            let mut stripes = vec![BTreeSet::new(); paths.len()];
+            for (no, chunk_id) in unsent.into_iter().enumerate() {
+                stripes[no % paths.len()].insert(chunk_id);
+            }
+            unsent = empty!();
+
+            let mut failed = vec![];
+            for (bus, stripe) in paths.iter().copied().zip(stripes) {
+                if stripe.is_empty() {
+                    continue;
+                }
+                let message = p2p::Messages::PullChunk(ChunkPull {
+                    app: info.app_id,
+                    message_id: info.id.message_id,
+                    container_id: info.id.container_id,
+                    chunk_ids: stripe.clone(),
+                });
+                if let Err(err) = self.send_p2p_via(endpoints, bus, info.remote_id, message) {
+                    if paths.len() == failed.len() + 1 {
+                        return Err(err.into());
+                    }
+                    warn!(
+                        "Unable to request chunks from {} through {} bus: {}; failing over to the \
+                         other paths",
+                        info.remote_id, bus, err
+                    );
+                    failed.push(bus);
+                    unsent.extend(stripe);
+                }
+            }
+            paths.retain(|bus| !failed.contains(bus));
+            if !failed.is_empty() {
+                self.paths.insert(info.remote_id, paths.clone());
+            }
+        }
 
         Ok(())
     }
@@ -221,32 +292,70 @@ impl Runtime {
     pub(super) fn handle_chunk(
         &mut self,
         endpoints: &mut Endpoints,
-        chunk: Chunk,
+        received: ChunkReceived,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Receive(ReceiveStateName::ReceivingChunks))?;
-        let info = self.state.info().expect("receive state always have metadata");
+        self.state.require_phase(Phase::Transferring)?;
+        let info = self.state.info().expect("transferring state always have metadata");
 
-        let chunk_id = chunk.chunk_id();
+        let ChunkReceived {
+            remote_id,
+            container_id,
+            chunk_id,
+            chunk,
+        } = received;
+        if container_id != info.id.container_id {
+            debug!("Ignoring chunk {} of {} which is not transferred", chunk_id, container_id);
+            return Ok(());
+        }
+        let (requested, listed) = match &self.state {
+            State::Transferring {
+                manifest, pending, ..
+            } => {
+                let requested = pending.contains(&chunk_id);
+                (requested, requested || manifest.chunks.contains(&chunk_id))
+            }
+            _ => unreachable!(),
+        };
+        if !listed {
+            warn!(
+                "Peer {} has sent chunk {} which is not a part of {}",
+                remote_id, chunk_id, container_id
+            );
+            return self.reject_chunk(endpoints, remote_id, chunk_id, false);
+        }
+        let hash = chunk.chunk_id();
+        if hash != chunk_id {
+            warn!("Chunk {} sent by {} has a different hash {}", chunk_id, remote_id, hash);
+            return self.reject_chunk(endpoints, remote_id, chunk_id, requested);
+        }
+        if !requested {
+            debug!("Ignoring chunk {} which is already received", chunk_id);
+            return Ok(());
+        }
         debug!("Processing chunk {}", chunk_id);
 
         if let Some(client_id) = info.client_id {
             self.send_rpc(
                 endpoints,
                 client_id,
-                RpcMsg::Progress(format!("Received chunk {}", chunk_id).into()),
+                RpcMsg::Progress(format!("Received chunk {}", chunk_id)),
             )?;
         }
 
-        self.store.store(DB_TABLE_CHUNKS, chunk_id, &chunk)?;
+        store_chunk(&mut self.store, container_id, &chunk)?;
+        self.metrics.inc("storm_chunks_stored_total", &[], 1);
+        self.metrics.inc("storm_chunk_bytes_stored_total", &[], chunk.len() as u64);
+        self.metrics_changed = true;
+
+        let window = self.windows.entry(info.remote_id).or_default();
+        window.received(chunk.len());
 
-        // Switching the state
         match &mut self.state {
-            State::Receive(ReceiveState::ReceivingChunks { pending, .. }) => {
+            State::Transferring { pending, .. } => {
                 pending.remove(&chunk_id);
                 if pending.is_empty() {
-                    info!("Transfer service completed its work");
-                    self.state = StateTy::Free;
-                    self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ProcessingComplete)?;
+                    window.complete_batch();
+                    self.request_chunks(endpoints)?;
                 }
             }
             _ => unreachable!(),
@@ -255,6 +364,161 @@ impl Runtime {
         Ok(())
     }
 
+    /// Fails the transfer whose container or chunk request was rejected by the peer it is
+    /// retrieved from, so the node may retrieve the container from other sources.
+    pub(super) fn handle_rejected(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        container_id: ContainerId,
+    ) -> Result<(), DaemonError> {
+        match self.state.info() {
+            Some(info)
+                if self.state.phase().is_active()
+                    && info.id.container_id == container_id
+                    && info.remote_id == remote_id =>
+            {
+                self.fail(endpoints, format!("peer {} has rejected the request", remote_id))
+            }
+            _ => {
+                debug!(
+                    "Ignoring rejection of {} which is not retrieved from {}",
+                    container_id, remote_id
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Drops chunk which has failed the verification, reporting the peer to stormd and requesting
+    /// the chunk again if it is still awaited. The transfer fails once the peer has sent too many
+    /// corrupt chunks.
+    fn reject_chunk(
+        &mut self,
+        endpoints: &mut Endpoints,
+        remote_id: NodeId,
+        chunk_id: ChunkId,
+        retry: bool,
+    ) -> Result<(), DaemonError> {
+        self.metrics.inc("storm_chunks_rejected_total", &[], 1);
+        self.metrics_changed = true;
+        let report = CtlMsg::CorruptChunk(AddressedMsg {
+            remote_id,
+            data: chunk_id,
+        });
+        self.send_ctl(endpoints, ServiceId::stormd(), report)?;
+
+        let info = self.state.info().expect("transferring state always have metadata");
+        if remote_id != info.remote_id {
+            return Ok(());
+        }
+        self.corrupt_chunks += 1;
+        if self.corrupt_chunks > CORRUPT_CHUNK_LIMIT {
+            return self
+                .fail(endpoints, format!("peer has sent {} corrupt chunks", self.corrupt_chunks));
+        }
+        if retry {
+            debug!("Requesting chunk {} again", chunk_id);
+            self.pull_chunks(endpoints, info, bset![chunk_id])?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the stored container and all of its chunks are present in the store,
+    /// completing the transfer.
+    fn verify(&mut self, endpoints: &mut Endpoints) -> Result<(), DaemonError> {
+        self.state.require_phase(Phase::Verifying)?;
+        let info = self.state.info().expect("verifying state always have metadata");
+        let container_id = info.id.container_id;
+
+        let manifest = match self.manifests.get(&mut self.store, container_id) {
+            Ok(manifest) => manifest,
+            Err(DaemonError::UnknownContainer(_)) => {
+                return self
+                    .fail(endpoints, s!("stored container is missed or has a different id"));
+            }
+            Err(err) => return Err(err),
+        };
+        let mut missed = 0usize;
+        let mut cursor = 0usize;
+        while cursor < manifest.chunks.len() {
+            let (page, next) = missing_page(&mut self.store, &manifest, cursor)?;
+            missed += page.len();
+            cursor = next;
+        }
+        if missed > 0 {
+            if let Some(base) = info.base {
+                warn!(
+                    "Base {} of {} misses {} chunks; retrieving the container in full",
+                    base, container_id, missed
+                );
+                self.state.transition(State::Transferring {
+                    info: Info { base: None, ..info },
+                    manifest,
+                    cursor: 0,
+                    pending: empty!(),
+                    queued: empty!(),
+                })?;
+                return self.request_chunks(endpoints);
+            }
+            return self
+                .fail(endpoints, format!("{} container chunks are missed in the store", missed));
+        }
+
+        self.state.transition(State::Complete { info })?;
+        info!("Transfer service completed its work");
+        if let Some(client_id) = info.client_id {
+            self.send_rpc(endpoints, client_id, RpcMsg::Progress("Container verified".into()))?;
+        }
+        self.state.transition(State::Idle)?;
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ProcessingComplete)?;
+
+        Ok(())
+    }
+
+    /// Fails the transfer in progress if its processing has returned an error. Errors caused by
+    /// the messages not matching the state of the transfer do not abort it.
+    pub(super) fn abort_on_error(
+        &mut self,
+        endpoints: &mut Endpoints,
+        res: Result<(), DaemonError>,
+    ) -> Result<(), DaemonError> {
+        match res {
+            Err(DaemonError::TransferAutomation(err)) => Err(err.into()),
+            Err(err) if self.state.phase().is_active() => self.fail(endpoints, err.to_string()),
+            res => res,
+        }
+    }
+
+    /// Aborts the transfer in progress, reporting the failure to the client and the node.
+    pub(super) fn fail(
+        &mut self,
+        endpoints: &mut Endpoints,
+        reason: String,
+    ) -> Result<(), DaemonError> {
+        let info = match self.state.info() {
+            Some(info) if self.state.phase().is_active() => info,
+            _ => return Ok(()),
+        };
+        error!("Transfer of {} has failed: {}", info.id.container_id, reason);
+
+        self.state.transition(State::Failed {
+            info,
+            reason: reason.clone(),
+        })?;
+        if let Some(client_id) = info.client_id {
+            let failure = rpc::Failure {
+                code: FailureCode::TransferAutomation.into(),
+                info: reason,
+            };
+            self.send_rpc(endpoints, client_id, RpcMsg::Failure(failure))?;
+        }
+        self.state.transition(State::Idle)?;
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ProcessingFailed)?;
+
+        Ok(())
+    }
+
     pub(super) fn handle_announce(
         &mut self,
         endpoints: &mut Endpoints,
@@ -263,7 +527,7 @@ impl Runtime {
         remote_id: NodeId,
         id: ContainerFullId,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Free)?;
+        self.state.require_phase(Phase::Idle)?;
 
         debug!("Sending announcement for {}", id.container_id);
 
@@ -297,18 +561,14 @@ impl Runtime {
         remote_id: NodeId,
         id: ContainerFullId,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Free)?;
+        self.state.require_phase(Phase::Idle)?;
 
         debug!("Got container {}, saving to storage", id.container_id);
 
-        let container_chunk = self
-            .store
-            .retrieve_chunk(DB_TABLE_CONTAINERS, id.container_id)?
-            .ok_or(DaemonError::UnknownContainer(id.container_id))?;
-        let container = Container::strict_deserialize(container_chunk)?;
+        let container = self.manifests.get(&mut self.store, id.container_id)?;
         let msg = p2p::AppMsg {
             app: storm_app,
-            data: container,
+            data: container.as_ref().clone(),
         };
         self.send_p2p_reporting_client(
             endpoints,
@@ -329,27 +589,62 @@ impl Runtime {
         remote_id: NodeId,
         container_id: ContainerId,
         chunk_ids: BTreeSet<ChunkId>,
+        bulk: bool,
     ) -> Result<(), DaemonError> {
-        self.state.require_state(StateName::Free)?;
+        self.state.require_phase(Phase::Idle)?;
 
+        let bulk = bulk && self.bulk;
         debug!("Got request for {} chunks for {}", chunk_ids.len(), container_id);
         trace!("Requested chunks: {:?}", chunk_ids);
 
+        // Access of the peer is authorized for the container it names, so only the chunks of
+        // that container are served; chunks are stored by their ids and may belong to others
+        let manifest = self.manifests.get(&mut self.store, container_id).ok();
+        let requested = chunk_ids.len();
+        let chunk_ids = chunk_ids
+            .into_iter()
+            .filter(|chunk_id| {
+                manifest
+                    .as_ref()
+                    .map(|container| container.chunks.contains(chunk_id))
+                    .unwrap_or_default()
+            })
+            .collect::<BTreeSet<_>>();
+        if chunk_ids.len() < requested {
+            warn!(
+                "Peer {} requests {} chunks not belonging to container {}",
+                remote_id,
+                requested - chunk_ids.len(),
+                container_id
+            );
+        }
+
+        let mut sent = 0u64;
         for chunk_id in chunk_ids {
             // We ignore failed chunks
-            if let Ok(Some(chunk)) = self.store.retrieve_chunk(DB_TABLE_CHUNKS, chunk_id) {
-                let _ = self.send_p2p(
-                    endpoints,
-                    remote_id,
-                    p2p::Messages::PushChunk(ChunkPush {
-                        app: storm_app,
-                        container_id,
-                        chunk_id,
-                        chunk,
-                    }),
-                );
+            if let Ok(Some(chunk)) = retrieve_chunk(&mut self.store, container_id, chunk_id) {
+                let len = chunk.len() as u64;
+                let msg = p2p::Messages::PushChunk(ChunkPush {
+                    app: storm_app,
+                    container_id,
+                    chunk_id,
+                    chunk,
+                });
+                let res = if bulk {
+                    self.send_bulk(endpoints, remote_id, msg)
+                } else {
+                    self.send_p2p(endpoints, remote_id, msg)
+                };
+                if res.is_ok() {
+                    sent += len;
+                }
             }
         }
+        let report = CtlMsg::ChunksSent(AddressedMsg {
+            remote_id,
+            data: sent,
+        });
+        self.send_ctl(endpoints, ServiceId::stormd(), report)?;
         self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ProcessingComplete)?;
 
         Ok(())
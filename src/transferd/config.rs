@@ -0,0 +1,45 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use internet2::addr::ServiceAddr;
+
+#[cfg(feature = "server")]
+use super::Opts;
+#[cfg(feature = "server")]
+use crate::opts::Options;
+use crate::stormd;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Config {
+    /// Message buses of the peer connection services in addition to the main one
+    pub peer_msg_endpoints: Vec<ServiceAddr>,
+}
+
+#[cfg(feature = "server")]
+impl Options for Opts {
+    type Conf = Config;
+
+    fn shared(&self) -> &crate::opts::Opts { &self.shared }
+
+    fn config(&self) -> Self::Conf {
+        Config {
+            peer_msg_endpoints: self.peer_msg_endpoints.clone(),
+        }
+    }
+}
+
+impl From<crate::Config<stormd::Config>> for crate::Config<Config> {
+    fn from(config: crate::Config<stormd::Config>) -> Self {
+        let ext = Config {
+            peer_msg_endpoints: config.ext.peer_msg_endpoints.clone(),
+        };
+        crate::Config::with(config, ext)
+    }
+}
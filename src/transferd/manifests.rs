@@ -0,0 +1,110 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use microservices::rpc::ServerError;
+use storm::{ChunkId, Container, ContainerId};
+
+use crate::containers::{missing_chunks, retrieve_manifest};
+use crate::DaemonError;
+
+/// Number of chunk ids of a container manifest checked against the store at once.
+pub const MANIFEST_PAGE_SIZE: usize = 4096;
+
+/// Maximal number of chunk ids kept in memory by the cache of hot manifests.
+pub const MANIFEST_CACHE_LIMIT: usize = 1 << 20;
+
+/// Cache of the recently used container manifests, listing container chunks.
+///
+/// Manifests are evicted in least-recently-used order once the total number of the chunk ids they
+/// keep exceeds [`MANIFEST_CACHE_LIMIT`]; manifests which are still used by a transfer remain in
+/// memory until the transfer is complete.
+#[derive(Clone, Debug, Default)]
+pub struct Manifests {
+    cache: HashMap<ContainerId, (Arc<Container>, u64)>,
+    /// Number of chunk ids in the cached manifests
+    size: usize,
+    /// Logical time used to track recency of the manifest use
+    tick: u64,
+}
+
+impl Manifests {
+    /// Returns manifest of the container, retrieving it from the store if it is not cached.
+    pub fn get(
+        &mut self,
+        store: &mut store_rpc::Client,
+        container_id: ContainerId,
+    ) -> Result<Arc<Container>, DaemonError> {
+        self.tick += 1;
+        if let Some((container, used)) = self.cache.get_mut(&container_id) {
+            *used = self.tick;
+            return Ok(container.clone());
+        }
+        let container = retrieve_manifest(store, container_id)?
+            .ok_or(DaemonError::UnknownContainer(container_id))?;
+        if container.container_id() != container_id {
+            return Err(DaemonError::UnknownContainer(container_id));
+        }
+        Ok(self.insert(container))
+    }
+
+    /// Caches manifest of the container.
+    pub fn insert(&mut self, container: Container) -> Arc<Container> {
+        self.tick += 1;
+        let container = Arc::new(container);
+        let len = container.chunks.len();
+        if let Some((prev, _)) =
+            self.cache.insert(container.container_id(), (container.clone(), self.tick))
+        {
+            self.size -= prev.chunks.len();
+        }
+        self.size += len;
+        self.evict(MANIFEST_CACHE_LIMIT);
+        container
+    }
+
+    /// Number of bytes taken by the chunk ids of the cached manifests.
+    pub fn memory(&self) -> u64 { (self.size * std::mem::size_of::<ChunkId>()) as u64 }
+
+    /// Evicts all the manifests which are not used by a transfer.
+    pub fn shrink(&mut self) { self.evict(0) }
+
+    fn evict(&mut self, limit: usize) {
+        while self.size > limit {
+            let lru = self
+                .cache
+                .iter()
+                .filter(|(_, (container, _))| Arc::strong_count(container) == 1)
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(id, _)| *id);
+            match lru.and_then(|id| self.cache.remove(&id)) {
+                Some((container, _)) => {
+                    trace!("Evicting manifest of {} from the cache", container.container_id());
+                    self.size -= container.chunks.len();
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Returns chunks from the page of the manifest starting at `cursor` which are not present in the
+/// store, together with the position of the next page.
+pub fn missing_page(
+    store: &mut store_rpc::Client,
+    container: &Container,
+    cursor: usize,
+) -> Result<(BTreeSet<ChunkId>, usize), ServerError<store_rpc::FailureCode>> {
+    let end = (cursor + MANIFEST_PAGE_SIZE).min(container.chunks.len());
+    let page = container.chunks[cursor..end].iter().copied().collect();
+    Ok((missing_chunks(store, container.container_id(), page)?, end))
+}
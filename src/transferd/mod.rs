@@ -8,13 +8,20 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+mod config;
 mod service;
 #[cfg(feature = "server")]
 mod opts;
 mod automation;
+mod manifests;
+mod state;
+mod window;
 
-pub(self) use automation::StateTy;
-pub use automation::{AutomationError, StateName};
+pub use config::Config;
+pub(crate) use manifests::Manifests;
+pub use manifests::{missing_page, MANIFEST_CACHE_LIMIT, MANIFEST_PAGE_SIZE};
 #[cfg(feature = "server")]
 pub use opts::Opts;
 pub use service::{run, Runtime};
+pub use state::{AutomationError, Info, Phase, State};
+pub use window::{ChunkWindow, INITIAL_WINDOW, MAX_WINDOW};
@@ -8,9 +8,10 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use clap::Parser;
+use clap::{Parser, ValueHint};
+use internet2::addr::ServiceAddr;
 
-use crate::opts::{Options, Opts as SharedOpts};
+use crate::opts::Opts as SharedOpts;
 
 /// Command-line arguments
 #[derive(Parser)]
@@ -21,14 +22,13 @@ pub struct Opts {
     /// command-line args or environment variables
     #[clap(flatten)]
     pub shared: SharedOpts,
-}
-
-impl Options for Opts {
-    type Conf = ();
-
-    fn shared(&self) -> &crate::opts::Opts { &self.shared }
 
-    fn config(&self) -> Self::Conf { () }
+    /// ZMQ socket of the message bus of an additional peer connection service.
+    ///
+    /// Chunk requests are striped across all the peer connection services reaching the remote
+    /// peer. The sockets must be given in the same order as to stormd.
+    #[clap(long = "peer-msg", multiple_occurrences = true, value_hint = ValueHint::FilePath)]
+    pub peer_msg_endpoints: Vec<ServiceAddr>,
 }
 
 #[cfg(feature = "server")]
@@ -8,9 +8,11 @@
 // You should have received a copy of the MIT License along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+use std::collections::HashMap;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use internet2::addr::NodeId;
 use internet2::ZmqSocketType;
 use microservices::error::BootstrapError;
 use microservices::esb::{self, EndpointList, Error};
@@ -18,20 +20,28 @@ use microservices::node::TryService;
 use rand::random;
 use storm_rpc::{AddressedMsg, AppContainer, ServiceId};
 
-use super::StateTy;
-use crate::bus::{AddressedClientMsg, BusMsg, CtlMsg, DaemonId, Endpoints, Responder, ServiceBus};
-use crate::transferd::automation::State;
-use crate::{Config, DaemonError, LaunchError};
+use crate::bus::{
+    terminate, AddressedClientMsg, BusMsg, CtlMsg, DaemonId, Endpoints, MetricsRegistry, PeerPaths,
+    Responder, ServiceBus, METRICS_REPORT_INTERVAL,
+};
+use crate::transferd::state::State;
+use crate::transferd::window::ChunkWindow;
+use crate::transferd::{Config, Manifests};
+use crate::{DaemonError, LaunchError};
 
-pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
+pub fn run(config: crate::Config<Config>) -> Result<(), BootstrapError<LaunchError>> {
     let rpc_endpoint = config.rpc_endpoint.clone();
     let ctl_endpoint = config.ctl_endpoint.clone();
     let msg_endpoint = config.msg_endpoint.clone();
+    let bulk_endpoint = config.bulk_endpoint.clone();
+    let peer_msg_endpoints = config.ext.peer_msg_endpoints.clone();
+    if peer_msg_endpoints.len() > u8::MAX as usize {
+        return Err(LaunchError::TooManyPeerServices(peer_msg_endpoints.len()).into());
+    }
     let runtime = Runtime::init(config)?;
 
     debug!("Connecting to service buses {}, {}", rpc_endpoint, ctl_endpoint);
-    let controller = esb::Controller::with(
-        map! {
+    let mut buses = map! {
             ServiceBus::Rpc => esb::BusConfig::with_addr(
                 rpc_endpoint,
                 ZmqSocketType::RouterConnect,
@@ -47,10 +57,23 @@ pub fn run(config: Config) -> Result<(), BootstrapError<LaunchError>> {
                 ZmqSocketType::RouterConnect,
                 Some(ServiceId::stormd())
             )
-        },
-        runtime,
-    )
-    .map_err(|_| LaunchError::BusSetupFailure)?;
+    };
+    if let Some(endpoint) = bulk_endpoint {
+        debug!("Connecting to bulk data bus {}", endpoint);
+        buses.insert(
+            ServiceBus::Bulk,
+            esb::BusConfig::with_addr(endpoint, ZmqSocketType::RouterConnect, Some(ServiceId::Lnp)),
+        );
+    }
+    for (no, endpoint) in peer_msg_endpoints.into_iter().enumerate() {
+        debug!("Connecting to peer service bus {}", endpoint);
+        buses.insert(
+            ServiceBus::PeerMsg(no as u8 + 1),
+            esb::BusConfig::with_addr(endpoint, ZmqSocketType::RouterConnect, Some(ServiceId::Lnp)),
+        );
+    }
+    let controller =
+        esb::Controller::with(buses, runtime).map_err(|_| LaunchError::BusSetupFailure)?;
 
     controller.run_or_panic("transferd");
 
@@ -61,22 +84,53 @@ pub struct Runtime {
     pub(super) id: DaemonId,
     pub(super) state: State,
     pub(super) store: store_rpc::Client,
+    /// Whether the dedicated bulk data bus is connected
+    pub(super) bulk: bool,
+    /// Chunk request windows adapted to the performance of the remote peers
+    pub(super) windows: HashMap<NodeId, ChunkWindow>,
+    /// Cache of the recently used container manifests
+    pub(super) manifests: Manifests,
+    /// Number of the connected additional peer connection services
+    pub(super) peer_services: u8,
+    /// Number of corrupt chunks received during the transfer in progress
+    pub(super) corrupt_chunks: u16,
+    /// Message buses across which chunk requests to the remote peers are striped
+    pub(super) paths: HashMap<NodeId, Vec<ServiceBus>>,
+    /// Metrics reported to stormd
+    pub(super) metrics: MetricsRegistry,
+    /// Whether the metrics have changed since the last report
+    pub(super) metrics_changed: bool,
+    pub(super) metrics_reported: Instant,
+    /// Memory use last reported to stormd, in bytes
+    pub(super) memory_reported: u64,
 }
 
 impl Runtime {
-    pub fn init(config: Config) -> Result<Self, BootstrapError<LaunchError>> {
+    pub fn init(config: crate::Config<Config>) -> Result<Self, BootstrapError<LaunchError>> {
         debug!("Connecting to store service at {}", config.store_endpoint);
 
         let store = store_rpc::Client::with(&config.store_endpoint).map_err(LaunchError::from)?;
 
         let id = random();
+        let bulk = config.bulk_endpoint.is_some();
+        let peer_services = config.ext.peer_msg_endpoints.len() as u8;
 
         info!("Transfer runtime started successfully");
 
         Ok(Self {
             id,
             store,
-            state: StateTy::Free,
+            state: State::Idle,
+            bulk,
+            windows: empty!(),
+            manifests: empty!(),
+            peer_services,
+            corrupt_chunks: 0,
+            paths: empty!(),
+            metrics: empty!(),
+            metrics_changed: false,
+            metrics_reported: Instant::now(),
+            memory_reported: 0,
         })
     }
 }
@@ -102,10 +156,17 @@ impl esb::Handler<ServiceBus> for Runtime {
         source: ServiceId,
         request: Self::Request,
     ) -> Result<(), Self::Error> {
-        match (bus_id, request, source) {
+        let res = match (bus_id, request, source) {
             (ServiceBus::Ctl, BusMsg::Ctl(msg), source) => self.handle_ctl(endpoints, source, msg),
             (bus, msg, _) => Err(DaemonError::wrong_esb_msg(bus, &msg)),
+        };
+        if let Err(err) = self.report_metrics(endpoints) {
+            warn!("Unable to report metrics: {}", err);
         }
+        if let Err(err) = self.report_memory(endpoints) {
+            warn!("Unable to report memory use: {}", err);
+        }
+        res
     }
 
     fn handle_err(
@@ -121,6 +182,31 @@ impl esb::Handler<ServiceBus> for Runtime {
 }
 
 impl Runtime {
+    /// Reports changed metrics to stormd once the transfer is over or the report interval has
+    /// passed.
+    fn report_metrics(&mut self, endpoints: &mut Endpoints) -> Result<(), DaemonError> {
+        let due = matches!(self.state, State::Idle)
+            || self.metrics_reported.elapsed() >= METRICS_REPORT_INTERVAL;
+        if !self.metrics_changed || !due {
+            return Ok(());
+        }
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::Metrics(self.metrics.samples()))?;
+        self.metrics_changed = false;
+        self.metrics_reported = Instant::now();
+        Ok(())
+    }
+
+    /// Reports memory use of the caches to stormd once it has changed.
+    fn report_memory(&mut self, endpoints: &mut Endpoints) -> Result<(), DaemonError> {
+        let memory = self.manifests.memory();
+        if memory == self.memory_reported {
+            return Ok(());
+        }
+        self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::MemoryUsage(memory))?;
+        self.memory_reported = memory;
+        Ok(())
+    }
+
     fn handle_ctl(
         &mut self,
         endpoints: &mut Endpoints,
@@ -137,15 +223,67 @@ impl Runtime {
                         container_id,
                     },
             }) => {
-                self.handle_receive(endpoints, client_id, storm_app, remote_id, container_id)?;
+                let res = self.handle_receive(
+                    endpoints,
+                    client_id,
+                    storm_app,
+                    remote_id,
+                    container_id,
+                    false,
+                );
+                self.abort_on_error(endpoints, res)?;
+            }
+
+            CtlMsg::GetMetadata(AddressedClientMsg {
+                remote_id,
+                client_id,
+                data:
+                    AppContainer {
+                        storm_app,
+                        container_id,
+                    },
+            }) => {
+                let res = self.handle_receive(
+                    endpoints,
+                    client_id,
+                    storm_app,
+                    remote_id,
+                    container_id,
+                    true,
+                );
+                self.abort_on_error(endpoints, res)?;
             }
 
             CtlMsg::ProcessContainer(container) => {
-                self.handle_container(endpoints, container)?;
+                let res = self.handle_container(endpoints, container);
+                self.abort_on_error(endpoints, res)?;
             }
 
             CtlMsg::ProcessChunk(chunk) => {
-                self.handle_chunk(endpoints, chunk)?;
+                let res = self.handle_chunk(endpoints, chunk);
+                self.abort_on_error(endpoints, res)?;
+            }
+
+            CtlMsg::Rejected(AddressedMsg {
+                remote_id,
+                data: container_id,
+            }) => {
+                self.handle_rejected(endpoints, remote_id, container_id)?;
+            }
+
+            CtlMsg::PeerPaths(PeerPaths {
+                remote_id,
+                services,
+            }) => {
+                let paths = services
+                    .into_iter()
+                    .filter(|no| *no <= self.peer_services)
+                    .map(ServiceBus::peer_service)
+                    .collect::<Vec<_>>();
+                if paths.len() > 1 {
+                    debug!("Striping chunk requests to {} across {} paths", remote_id, paths.len());
+                }
+                self.paths.insert(remote_id, paths);
             }
 
             CtlMsg::AnnounceContainer(AddressedClientMsg {
@@ -185,9 +323,24 @@ impl Runtime {
                     remote_id,
                     data.container_id,
                     data.chunk_ids,
+                    data.bulk,
                 )?;
             }
 
+            CtlMsg::ShrinkCaches => {
+                debug!("Shrinking manifest cache of {} bytes", self.manifests.memory());
+                self.manifests.shrink();
+            }
+
+            CtlMsg::Shutdown => {
+                // Chunks received so far are kept in the store, so the failed retrieval is resumed
+                // by the node after its restart
+                info!("Shutting down");
+                self.fail(endpoints, s!("node is shutting down"))?;
+                self.send_ctl(endpoints, ServiceId::stormd(), CtlMsg::ShutdownComplete)?;
+                terminate();
+            }
+
             wrong_msg => {
                 error!("Request is not supported by the CTL interface");
                 return Err(DaemonError::wrong_esb_msg(ServiceBus::Ctl, &wrong_msg));
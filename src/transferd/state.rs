@@ -0,0 +1,164 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use internet2::addr::NodeId;
+use microservices::esb::ClientId;
+use storm::{ChunkId, Container, ContainerFullId, ContainerId, StormApp};
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AutomationError {
+    /// the service is required to be in a {expected} state, while its state is {found}
+    InvalidState { expected: Phase, found: Phase },
+
+    /// transfer can't move from {from} to {to} state
+    InvalidTransition { from: Phase, to: Phase },
+}
+
+/// Phase of the container transfer protocol.
+///
+/// A transfer starts from `Idle` and goes through `Negotiating` (the container is requested from
+/// the remote peer), `Transferring` (the missed chunks are requested in batches) and `Verifying`
+/// (all container chunks are checked to be present in the store) phases, ending with either
+/// `Complete` or `Failed` phase, after which the service returns to `Idle`. Transfer may fail at
+/// any of its intermediate phases. A delta transfer returns from `Verifying` to `Transferring` if
+/// the chunks of its base turn out to be missing, retrieving the container in full. Retrieval of
+/// the container metadata only completes right after `Negotiating` phase.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Phase {
+    Idle,
+    Negotiating,
+    Transferring,
+    Verifying,
+    Complete,
+    Failed,
+}
+
+impl Phase {
+    /// Detects whether a transfer is in progress.
+    pub fn is_active(self) -> bool {
+        matches!(self, Phase::Negotiating | Phase::Transferring | Phase::Verifying)
+    }
+
+    /// Checks whether the protocol allows transition from this phase into the `next` one.
+    pub fn can_transition_to(self, next: Phase) -> bool {
+        matches!(
+            (self, next),
+            (Phase::Idle, Phase::Negotiating)
+                | (Phase::Negotiating, Phase::Transferring | Phase::Complete)
+                | (Phase::Transferring, Phase::Verifying)
+                | (Phase::Verifying, Phase::Complete | Phase::Transferring)
+                | (Phase::Negotiating | Phase::Transferring | Phase::Verifying, Phase::Failed)
+                | (Phase::Complete | Phase::Failed, Phase::Idle)
+        )
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub struct Info {
+    pub app_id: StormApp,
+    pub client_id: Option<ClientId>,
+    pub remote_id: NodeId,
+    pub id: ContainerFullId,
+    /// Previous version of the container, if only the chunks which differ from it are retrieved
+    pub base: Option<ContainerId>,
+    /// Whether only the container header and manifest are retrieved
+    pub metadata_only: bool,
+}
+
+/// State of the transfer service.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum State {
+    Idle,
+    Negotiating {
+        info: Info,
+    },
+    Transferring {
+        info: Info,
+        /// List of the container chunks
+        manifest: Arc<Container>,
+        /// Position of the next manifest page to be checked for the missing chunks
+        cursor: usize,
+        /// Requested chunks which were not received yet
+        pending: BTreeSet<ChunkId>,
+        /// Missing chunks from the checked manifest pages which are not requested yet
+        queued: BTreeSet<ChunkId>,
+    },
+    Verifying {
+        info: Info,
+    },
+    Complete {
+        info: Info,
+    },
+    Failed {
+        info: Info,
+        reason: String,
+    },
+}
+
+impl Default for State {
+    fn default() -> Self { State::Idle }
+}
+
+impl State {
+    pub fn phase(&self) -> Phase {
+        match self {
+            State::Idle => Phase::Idle,
+            State::Negotiating { .. } => Phase::Negotiating,
+            State::Transferring { .. } => Phase::Transferring,
+            State::Verifying { .. } => Phase::Verifying,
+            State::Complete { .. } => Phase::Complete,
+            State::Failed { .. } => Phase::Failed,
+        }
+    }
+
+    pub fn info(&self) -> Option<Info> {
+        match self {
+            State::Idle => None,
+            State::Negotiating { info }
+            | State::Transferring { info, .. }
+            | State::Verifying { info }
+            | State::Complete { info }
+            | State::Failed { info, .. } => Some(*info),
+        }
+    }
+
+    pub fn require_phase(&self, expected: Phase) -> Result<(), AutomationError> {
+        let found = self.phase();
+        if found != expected {
+            Err(AutomationError::InvalidState { expected, found })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Moves the state machine into the `next` state, if the transition is allowed by the
+    /// protocol.
+    pub fn transition(&mut self, next: State) -> Result<(), AutomationError> {
+        let from = self.phase();
+        let to = next.phase();
+        if !from.can_transition_to(to) {
+            warn!("Invalid transfer state transition {} -> {}", from, to);
+            return Err(AutomationError::InvalidTransition { from, to });
+        }
+        match next.info().or_else(|| self.info()) {
+            Some(info) => {
+                debug!("Transfer of {} changes state {} -> {}", info.id.container_id, from, to)
+            }
+            None => debug!("Transfer state changes {} -> {}", from, to),
+        }
+        *self = next;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+// Storm node providing distributed storage & messaging for lightning network.
+//
+// Written in 2022 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2022 by LNP/BP Standards Association, Switzerland.
+//
+// You should have received a copy of the MIT License along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::time::Instant;
+
+/// Number of chunks requested in the first batch from a peer with no measured performance.
+pub const INITIAL_WINDOW: usize = 4;
+
+/// Maximum number of chunks requested from a peer at once.
+pub const MAX_WINDOW: usize = 256;
+
+/// Window of in-flight chunk requests adapted to the measured peer throughput.
+///
+/// Chunks are requested in batches of the window size. Once the batch is received, its
+/// throughput is compared with the smoothed throughput of the previous batches: if it has not
+/// dropped, the window grows by one chunk (additive increase), otherwise it is halved
+/// (multiplicative decrease).
+#[derive(Clone, Debug)]
+pub struct ChunkWindow {
+    size: usize,
+    /// Smoothed throughput of the completed batches, in bytes per second
+    throughput: Option<u64>,
+    batch_start: Option<Instant>,
+    batch_bytes: u64,
+}
+
+impl Default for ChunkWindow {
+    fn default() -> Self {
+        ChunkWindow {
+            size: INITIAL_WINDOW,
+            throughput: None,
+            batch_start: None,
+            batch_bytes: 0,
+        }
+    }
+}
+
+impl ChunkWindow {
+    pub fn size(&self) -> usize { self.size }
+
+    pub fn start_batch(&mut self) {
+        self.batch_start = Some(Instant::now());
+        self.batch_bytes = 0;
+    }
+
+    pub fn received(&mut self, bytes: usize) { self.batch_bytes += bytes as u64; }
+
+    /// Adjusts window size using throughput of the completed batch.
+    pub fn complete_batch(&mut self) {
+        let start = match self.batch_start.take() {
+            Some(start) => start,
+            None => return,
+        };
+        let elapsed = start.elapsed().as_millis().max(1) as u64;
+        let sample = self.batch_bytes * 1000 / elapsed;
+        match self.throughput {
+            Some(throughput) if sample < throughput * 3 / 4 => {
+                self.size = (self.size / 2).max(1);
+            }
+            _ => self.size = (self.size + 1).min(MAX_WINDOW),
+        }
+        self.throughput = Some(match self.throughput {
+            Some(throughput) => (throughput * 3 + sample) / 4,
+            None => sample,
+        });
+        trace!(
+            "Chunk window is adjusted to {} chunks; batch throughput {} bytes/s",
+            self.size,
+            sample
+        );
+    }
+}